@@ -0,0 +1,113 @@
+//! Learns a technique catalog from optimal solutions and measures its effect
+//!
+//! ```bash
+//! cargo bench --bench pattern_mining                         # learn, compare, save to the data directory
+//! cargo bench --bench pattern_mining -- --boards 100         # solve more boards
+//! cargo bench --bench pattern_mining -- --output learned.json  # save somewhere else
+//! cargo bench --bench pattern_mining -- --dry-run            # compare without saving
+//! ```
+//!
+//! The learned loops are measured next to the built-in techniques on boards
+//! held out from learning. Saved to the data directory, they appear in the
+//! GUI's Techniques panel alongside the built-in ones.
+
+use slider::controller::pattern_mining::{
+    benchmark_catalog, learn_catalog, save_catalog, MiningConfig, PatternEffect, PATTERNS_FILE,
+};
+use slider::controller::storage;
+use slider::model::RelativePatternCatalog;
+use std::path::PathBuf;
+use std::time::Instant;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut config = MiningConfig::default();
+    let mut output = storage::data_file(PATTERNS_FILE);
+    let mut dry_run = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--boards" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(boards) => config.boards = boards,
+                None => {
+                    eprintln!("--boards needs a number");
+                    std::process::exit(1);
+                }
+            },
+            "--seed" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(seed) => config.seed = seed,
+                None => {
+                    eprintln!("--seed needs a number");
+                    std::process::exit(1);
+                }
+            },
+            "--output" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("--output needs a .json path");
+                    std::process::exit(1);
+                }
+            },
+            "--dry-run" => dry_run = true,
+            _ => {}
+        }
+    }
+
+    let started = Instant::now();
+    let learned = match learn_catalog(&config) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            eprintln!("Learning failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "Learned {} loops from {} {}×{} {} boards in {:.1}s\n",
+        learned.len(),
+        config.boards,
+        config.size,
+        config.size,
+        config.difficulty,
+        started.elapsed().as_secs_f64()
+    );
+
+    // Measure on boards the pass never saw
+    let held_out = MiningConfig {
+        seed: config.seed.wrapping_add(config.boards as u64),
+        ..config.clone()
+    };
+    let boards = held_out.boards().expect("size was valid for learning");
+    println!(
+        "{:<28} {:>6} {:>10} {:>10} {:>12}",
+        "technique", "moves", "fits", "improves", "mean change"
+    );
+    print_effects(&RelativePatternCatalog::builtin(), &boards);
+    print_effects(&learned, &boards);
+
+    match output {
+        Some(path) if !dry_run => match save_catalog(&learned, &path) {
+            Ok(()) => println!("\nWrote {}", path.display()),
+            Err(e) => eprintln!("Failed to write {}: {}", path.display(), e),
+        },
+        Some(_) => {}
+        None => eprintln!("No data directory; pass --output to save the catalog"),
+    }
+}
+
+fn print_effects(catalog: &RelativePatternCatalog, boards: &[slider::model::PuzzleState]) {
+    let effects: Vec<PatternEffect> = benchmark_catalog(catalog, boards);
+    for (pattern, effect) in catalog.patterns().iter().zip(effects) {
+        let mean = effect
+            .mean_best_change
+            .map_or("-".to_string(), |change| format!("{:+.2}", change));
+        println!(
+            "{:<28} {:>6} {:>10} {:>10} {:>12}",
+            effect.name,
+            pattern.len(),
+            format!("{}/{}", effect.applicable, effect.boards),
+            effect.improving,
+            mean
+        );
+    }
+}
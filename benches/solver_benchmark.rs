@@ -0,0 +1,216 @@
+//! Named solver benchmarks with comparison against the previous run
+//!
+//! ```bash
+//! cargo bench --bench solver_benchmark                          # full suite
+//! cargo bench --bench solver_benchmark -- solve_4x4              # names containing the text
+//! cargo bench --bench solver_benchmark -- --samples 30           # more boards per benchmark
+//! cargo bench --bench solver_benchmark -- --output results.csv   # also write a .json or .csv report
+//! cargo bench --bench solver_benchmark -- --baseline main.json   # compare with a saved JSON report
+//! cargo bench --bench solver_benchmark -- --corpus boards.json   # solve saved boards (created if missing)
+//! cargo bench --bench solver_benchmark -- --weight 1.5           # weighted A*: f = g + 1.5·h
+//! cargo bench --bench solver_benchmark -- --tie-break low-g      # high-g (default), low-h or low-g
+//! cargo bench --bench solver_benchmark -- --reopen --symmetry    # reopen closed boards; merge mirror images
+//! ```
+//!
+//! The policy flags change how A* searches without recompiling; the `moves`
+//! column shows what a weight or a strict closed set costs in solution length.
+//!
+//! Each run is saved to `target/solver-bench/baseline.json`, and the next run
+//! reports how each benchmark's mean time changed, so checking out two commits
+//! and benchmarking each gives a per-commit comparison.
+
+use slider::controller::solver_benchmark::{
+    benchmark_corpus, compare, policy_label, run_suite, BenchmarkConfig, BenchmarkReport,
+    BenchmarkResult,
+};
+use slider::controller::PuzzleCorpus;
+use slider::model::{PerformanceMetrics, TieBreak};
+use std::path::{Path, PathBuf};
+
+/// Changes within this many percent are reported as noise
+const NOISE_PERCENT: f64 = 5.0;
+
+/// Where each run is saved for the next run to compare against
+fn last_run_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/solver-bench/baseline.json")
+}
+
+fn format_micros(micros: f64) -> String {
+    // Heuristic evaluations take well under a microsecond
+    if micros < 1.0 {
+        format!("{:.0}ns", micros * 1000.0)
+    } else {
+        PerformanceMetrics::format_duration(micros.round() as u64)
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut config = BenchmarkConfig::default();
+    let mut output: Option<PathBuf> = None;
+    let mut baseline_path = last_run_path();
+    let mut corpus_path: Option<PathBuf> = None;
+
+    // cargo bench passes --bench; anything else without a dash is a name filter
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--samples" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(samples) => config.samples = samples,
+                None => {
+                    eprintln!("--samples needs a number");
+                    std::process::exit(1);
+                }
+            },
+            "--seed" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(seed) => config.seed = seed,
+                None => {
+                    eprintln!("--seed needs a number");
+                    std::process::exit(1);
+                }
+            },
+            "--output" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("--output needs a .json or .csv path");
+                    std::process::exit(1);
+                }
+            },
+            "--baseline" => match args.next() {
+                Some(path) => baseline_path = PathBuf::from(path),
+                None => {
+                    eprintln!("--baseline needs a .json report path");
+                    std::process::exit(1);
+                }
+            },
+            "--weight" => match args.next().and_then(|w| w.parse().ok()) {
+                Some(weight) => config.solver.weight = weight,
+                None => {
+                    eprintln!("--weight needs a number such as 1.5");
+                    std::process::exit(1);
+                }
+            },
+            "--tie-break" => match args.next().and_then(|name| TieBreak::from_name(name)) {
+                Some(tie_break) => config.solver.tie_break = tie_break,
+                None => {
+                    eprintln!("--tie-break needs high-g, low-h or low-g");
+                    std::process::exit(1);
+                }
+            },
+            "--reopen" => config.solver.reopen_closed = true,
+            "--symmetry" => config.solver.symmetry = true,
+            "--corpus" => match args.next() {
+                Some(path) => corpus_path = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("--corpus needs a .json path");
+                    std::process::exit(1);
+                }
+            },
+            filter if !filter.starts_with('-') => config.filter = Some(filter.to_string()),
+            _ => {}
+        }
+    }
+
+    if let Some(path) = &corpus_path {
+        config.corpus = Some(load_or_create_corpus(path, &config));
+    }
+
+    // A missing or unreadable baseline just means nothing to compare with
+    let baseline = BenchmarkReport::load(&baseline_path).ok();
+
+    let results = run_suite(&config);
+    println!("A* policy: {}\n", policy_label(&config.solver));
+    println!(
+        "{:<28} {:>10} {:>10} {:>10} {:>10} {:>12} {:>8}",
+        "benchmark", "mean", "median", "min", "max", "nodes", "moves"
+    );
+    for result in &results {
+        let nodes = result
+            .nodes_expanded
+            .map_or("-".to_string(), |n| format!("{:.0}", n));
+        let moves = result
+            .mean_moves
+            .map_or("-".to_string(), |n| format!("{:.1}", n));
+        println!(
+            "{:<28} {:>10} {:>10} {:>10} {:>10} {:>12} {:>8}",
+            result.name,
+            format_micros(result.mean_micros),
+            format_micros(result.median_micros),
+            format_micros(result.min_micros),
+            format_micros(result.max_micros),
+            nodes,
+            moves
+        );
+        if result.unsolved > 0 {
+            println!(
+                "    {} of {} solves hit the solver limits",
+                result.unsolved, result.samples
+            );
+        }
+    }
+
+    let previous = baseline
+        .as_ref()
+        .map_or(&[][..], |report| &report.results[..]);
+    let comparisons = compare(previous, &results);
+    if !comparisons.is_empty() {
+        println!("\nChange since previous run:");
+        for comparison in comparisons {
+            let verdict = if comparison.change_percent > NOISE_PERCENT {
+                "slower"
+            } else if comparison.change_percent < -NOISE_PERCENT {
+                "faster"
+            } else {
+                "no change"
+            };
+            println!(
+                "{:<28} {:>10} -> {:>10} {:>+8.1}%  {}",
+                comparison.name,
+                format_micros(comparison.baseline_micros),
+                format_micros(comparison.current_micros),
+                comparison.change_percent,
+                verdict
+            );
+        }
+    }
+
+    if let Some(path) = &output {
+        match report_for(&config, &results).save(path) {
+            Ok(()) => println!("\nWrote {}", path.display()),
+            Err(e) => eprintln!("Failed to write {}: {}", path.display(), e),
+        }
+    }
+
+    // Keep results of benchmarks skipped by the filter so later runs can still compare them
+    let last_run = last_run_path();
+    let mut saved =
+        BenchmarkReport::load(&last_run).map_or_else(|_| Vec::new(), |report| report.results);
+    saved.retain(|old| results.iter().all(|new| new.name != old.name));
+    saved.extend(results);
+    if let Err(e) = report_for(&config, &saved).save(&last_run) {
+        eprintln!("Failed to save baseline to {}: {}", last_run.display(), e);
+    }
+}
+
+/// Loads the corpus at `path`, or saves the suite's own boards there if there is none yet
+fn load_or_create_corpus(path: &Path, config: &BenchmarkConfig) -> PuzzleCorpus {
+    if path.exists() {
+        match PuzzleCorpus::load(path) {
+            Ok(corpus) => return corpus,
+            Err(e) => {
+                eprintln!("Failed to load corpus {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+    let corpus = benchmark_corpus(config);
+    match corpus.save(path) {
+        Ok(()) => println!("Saved {} boards to {}", corpus.len(), path.display()),
+        Err(e) => eprintln!("Failed to save corpus {}: {}", path.display(), e),
+    }
+    corpus
+}
+
+fn report_for(config: &BenchmarkConfig, results: &[BenchmarkResult]) -> BenchmarkReport {
+    BenchmarkReport::new(config, results.to_vec())
+}
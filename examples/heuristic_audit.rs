@@ -0,0 +1,59 @@
+//! Checks every built-in entropy calculator against the true distance of all 3×3 boards
+//!
+//! ```bash
+//! cargo run --release --example heuristic_audit
+//! ```
+//!
+//! A breadth-first search from the goal finds the optimal solution length of all
+//! 181,440 reachable boards; each calculator's estimate is then compared with it.
+//! Calculators that never overestimate are admissible, and safe to guide an
+//! optimal search. For the others, the board overestimated the most is printed.
+
+use slider::model::{HeuristicCache, HeuristicLab};
+use std::time::Instant;
+
+fn main() {
+    let lab = match HeuristicLab::builtin(3, &HeuristicCache::disabled()) {
+        Ok(lab) => lab,
+        Err(e) => {
+            eprintln!("Could not build the calculators: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let started = Instant::now();
+    let summary = lab.audit(3).expect("3×3 boards can be audited");
+    println!(
+        "Audited {} boards (longest optimal solution {} moves) in {:.1}s\n",
+        summary.states,
+        summary.diameter,
+        started.elapsed().as_secs_f64()
+    );
+
+    println!(
+        "{:<20} {:>10} {:>11} {:>10} {:>10} {:>10}",
+        "calculator", "admissible", "violations", "worst over", "mean error", "worst under"
+    );
+    for report in &summary.heuristics {
+        println!(
+            "{:<20} {:>10} {:>11} {:>10} {:>10.2} {:>10}",
+            report.name,
+            if report.is_admissible() { "yes" } else { "no" },
+            report.admissibility_violations,
+            report.worst_overestimate,
+            report.mean_error,
+            report.worst_underestimate
+        );
+    }
+
+    for report in &summary.heuristics {
+        if let Some(ref board) = report.counterexample {
+            println!(
+                "\n{} overestimates {} by {}",
+                report.name,
+                board.to_state().to_notation(),
+                report.worst_overestimate
+            );
+        }
+    }
+}
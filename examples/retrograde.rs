@@ -0,0 +1,59 @@
+//! Generates a retrograde distance table and saves it for memory-mapped lookups
+//!
+//! ```bash
+//! cargo run --release --example retrograde -- <size> <file> [row,col ...]
+//! ```
+//!
+//! Tracks the tiles whose home cells are listed (every tile if none are), on
+//! the standard goal of a `size`×`size` board. A table of every 3×3 tile, or a
+//! pattern of up to seven 4×4 tiles, builds in seconds to minutes; the file
+//! holds one byte per placement and can be opened with `RetrogradeTable::open`.
+
+use slider::model::{Position, PuzzleState, RetrogradeTable, TileMask};
+use std::time::Instant;
+
+fn parse_home(arg: &str) -> Option<Position> {
+    let (row, col) = arg.split_once(',')?;
+    Some((row.trim().parse().ok()?, col.trim().parse().ok()?))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [size, file, homes @ ..] = args.as_slice() else {
+        fail("usage: retrograde <size> <file> [row,col ...]");
+    };
+    let goal = size
+        .parse()
+        .ok()
+        .and_then(|size| PuzzleState::new(size).ok())
+        .unwrap_or_else(|| fail("size must be a board size such as 3 or 4"));
+    let mask = if homes.is_empty() {
+        TileMask::full(&goal)
+    } else {
+        let homes: Option<Vec<Position>> = homes.iter().map(|arg| parse_home(arg)).collect();
+        homes.and_then(|homes| TileMask::new(&goal, homes))
+    }
+    .unwrap_or_else(|| fail("homes must be row,col cells on the board, other than the empty cell's"));
+
+    let started = Instant::now();
+    let Some(table) = RetrogradeTable::generate(mask) else {
+        fail("too many placements; track fewer tiles");
+    };
+    println!(
+        "{} tiles: {} placements, {} reachable, longest distance {} ({:.1}s)",
+        mask.tracked(),
+        table.placements(),
+        table.reachable(),
+        table.diameter(),
+        started.elapsed().as_secs_f64()
+    );
+    if let Err(e) = table.save(file) {
+        fail(&format!("Could not save {}: {}", file, e));
+    }
+    println!("Saved {}", file);
+}
@@ -0,0 +1,139 @@
+#ifndef SLIDER_H
+#define SLIDER_H
+
+/* Mirrors src/ffi.rs; regenerate with `cbindgen --config cbindgen.toml --output include/slider.h`. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Shuffle difficulty, mirroring `Difficulty` without the custom target
+typedef enum SliderDifficulty {
+  SliderDifficulty_Easy,
+  SliderDifficulty_Medium,
+  SliderDifficulty_Hard,
+  SliderDifficulty_ExtraHard,
+} SliderDifficulty;
+
+// Opaque puzzle handle
+typedef struct SliderPuzzle SliderPuzzle;
+
+// A grid cell, row first
+typedef struct SliderPosition {
+  size_t row;
+  size_t col;
+} SliderPosition;
+
+// Tile positions to move, in order, as returned by `slider_solve`
+//
+// `found` is false (and `moves` null) when the solver gave up or was handed a
+// null puzzle. Release with `slider_solution_free`.
+typedef struct SliderSolution {
+  struct SliderPosition *moves;
+  size_t len;
+  bool found;
+} SliderSolution;
+
+// Creates a solved `size`×`size` puzzle
+// Returns null if the size is out of range
+struct SliderPuzzle *slider_puzzle_new(size_t size);
+
+// Parses a puzzle in text notation, e.g. `"1 2 3 / 4 5 6 / 7 _ 8"`
+// Returns null if the text is malformed or the layout is unsolvable
+//
+// # Safety
+//
+// `notation` must be null or a valid NUL-terminated string.
+struct SliderPuzzle *slider_puzzle_from_notation(const char *notation);
+
+// Returns an independent copy of `puzzle`, or null if `puzzle` is null
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+struct SliderPuzzle *slider_puzzle_clone(const struct SliderPuzzle *puzzle);
+
+// Releases a puzzle handle; null is ignored
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle that is not used afterwards.
+void slider_puzzle_free(struct SliderPuzzle *puzzle);
+
+// Returns the grid size, or 0 if `puzzle` is null
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+size_t slider_puzzle_size(const struct SliderPuzzle *puzzle);
+
+// Returns the tile number at a cell; 0 for the empty cell, a null puzzle, or a cell off the grid
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+uint32_t slider_puzzle_tile(const struct SliderPuzzle *puzzle, size_t row, size_t col);
+
+// Returns the empty cell, or row and column `usize::MAX` if `puzzle` is null
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+struct SliderPosition slider_puzzle_empty_position(const struct SliderPuzzle *puzzle);
+
+// Returns true if every tile is home; false for a null puzzle
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+bool slider_puzzle_is_solved(const struct SliderPuzzle *puzzle);
+
+// Slides the tile at a cell toward the empty cell, pushing any tiles between them
+// Returns false if the cell is not in line with the empty cell
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+bool slider_puzzle_move(struct SliderPuzzle *puzzle, size_t row, size_t col);
+
+// Shuffles the puzzle from its current layout, repeatably for a given seed
+// Returns the number of moves made (0 for a null puzzle)
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+size_t slider_puzzle_shuffle(struct SliderPuzzle *puzzle, enum SliderDifficulty difficulty, uint64_t seed);
+
+// Returns the board in text notation; release with `slider_string_free`
+// Returns null if `puzzle` is null
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+char *slider_puzzle_to_notation(const struct SliderPuzzle *puzzle);
+
+// Releases a string returned by this library; null is ignored
+//
+// # Safety
+//
+// `text` must be null or a string from this library that is not used afterwards.
+void slider_string_free(char *text);
+
+// Finds a solution with A*, giving up after `timeout_ms` (0 for the default limit)
+//
+// A* is fast but its heuristic can overestimate, so the solution is not always a
+// shortest one.
+//
+// # Safety
+//
+// `puzzle` must be null or a live handle from this library.
+struct SliderSolution slider_solve(const struct SliderPuzzle *puzzle, uint64_t timeout_ms);
+
+// Releases the moves of a solution returned by `slider_solve`
+//
+// # Safety
+//
+// `solution` must come from `slider_solve`, unmodified, and be freed only once.
+void slider_solution_free(struct SliderSolution solution);
+
+#endif  /* SLIDER_H */
@@ -0,0 +1,574 @@
+//! JSON puzzle service
+//!
+//! Exposes the solver, shuffler and heuristics over plain HTTP so web apps and
+//! scripts can use them without linking Rust:
+//!
+//! - `POST /solve` — body `{"board": "...", "solver": "astar", "timeout_ms": 5000}`;
+//!   `board` is text notation or a share code, the other fields are optional; the
+//!   reply lists the moves as positions, tile numbers and blank moves (`notation`)
+//! - `POST /shuffle?difficulty=hard&size=4&seed=7` — all parameters optional
+//! - `GET /analyze?board=...` — heuristic estimates for a board
+//!
+//! Every response is JSON; failures carry an `error` message. The server speaks
+//! just enough HTTP/1.1 for curl and `fetch`, one short-lived thread per connection.
+//! Oversized request lines or too many headers get 431, and connections beyond
+//! `MAX_CONNECTIONS` at once get 503.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use slider::model::{
+    Difficulty, EntropyCalculator, HeuristicCache, ManhattanDistance, PuzzleState,
+    ShortestPathHeuristic, SolverConfig, SolverKind, WalkingDistance,
+};
+use slider::ShuffleController;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Address used when `--bind` is not given
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Longest a single solve may run, whatever the request asks for
+const MAX_SOLVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Solve time limit when the request does not set one
+const DEFAULT_SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest accepted request body; boards are a few hundred bytes at most
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest accepted request line or header line, line ending included
+const MAX_LINE_BYTES: usize = 8 * 1024;
+
+/// Most header lines accepted in one request
+const MAX_HEADERS: usize = 64;
+
+/// Most connections handled at once; each has a thread of its own
+const MAX_CONNECTIONS: usize = 64;
+
+/// A parsed HTTP request
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// A JSON response with its status code
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Why a request could not be read
+#[derive(Debug)]
+enum RequestError {
+    /// A line longer than `MAX_LINE_BYTES`, or more than `MAX_HEADERS` headers
+    HeadersTooLarge,
+    /// Malformed or unreadable request
+    Invalid(io::Error),
+}
+
+impl From<io::Error> for RequestError {
+    fn from(error: io::Error) -> Self {
+        RequestError::Invalid(error)
+    }
+}
+
+impl RequestError {
+    /// Returns the response telling the client what was wrong
+    fn response(&self) -> Response {
+        match self {
+            RequestError::HeadersTooLarge => Response::error(431, "request headers too large"),
+            RequestError::Invalid(e) => Response::error(400, e.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SolveRequest {
+    board: String,
+    solver: Option<String>,
+    timeout_ms: Option<u64>,
+}
+
+/// Reads one request from `reader`
+fn read_request(reader: &mut impl BufRead) -> Result<Request, RequestError> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+    let line = read_line(reader)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(invalid("malformed request line").into());
+    };
+    let method = method.to_ascii_uppercase();
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (target.to_string(), HashMap::new()),
+    };
+
+    let mut content_length = 0;
+    let mut headers = 0;
+    loop {
+        let header = read_line(reader)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Err(RequestError::HeadersTooLarge);
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("bad Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(invalid("request body too large").into());
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+/// Reads one line of at most `MAX_LINE_BYTES`, or an empty string at the end of input
+fn read_line(reader: &mut impl BufRead) -> Result<String, RequestError> {
+    let mut line = Vec::new();
+    Read::take(&mut *reader, MAX_LINE_BYTES as u64 + 1).read_until(b'\n', &mut line)?;
+    if line.len() > MAX_LINE_BYTES {
+        return Err(RequestError::HeadersTooLarge);
+    }
+    String::from_utf8(line)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "request is not UTF-8").into())
+}
+
+/// Splits `a=1&b=2` into decoded pairs; later duplicates win
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decodes `%XX` escapes and `+` (space) from a URL component
+/// Malformed escapes are kept as written
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = text
+                    .get(i + 1..i + 3)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                if let Some(byte) = hex {
+                    decoded.push(byte);
+                    i += 2;
+                } else {
+                    decoded.push(b'%');
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Dispatches a request to its endpoint
+fn route(request: &Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/solve") => solve(request),
+        ("POST", "/shuffle") => shuffle(request),
+        ("GET", "/analyze") => analyze(request),
+        (_, "/solve" | "/shuffle" | "/analyze") => Response::error(405, "method not allowed"),
+        _ => Response::error(404, format!("no endpoint at {}", request.path)),
+    }
+}
+
+fn solve(request: &Request) -> Response {
+    let params: SolveRequest = match serde_json::from_slice(&request.body) {
+        Ok(params) => params,
+        Err(e) => return Response::error(400, format!("invalid JSON body: {}", e)),
+    };
+    let state = match PuzzleState::from_notation_or_code(&params.board) {
+        Ok(state) => state,
+        Err(e) => return Response::error(400, e.to_string()),
+    };
+    let kind = match params.solver.as_deref() {
+        Some(name) => match SolverKind::from_name(name) {
+            Some(kind) => kind,
+            None => return Response::error(400, format!("unknown solver '{}'", name)),
+        },
+        None => SolverKind::default_for_size(state.size()),
+    };
+    let timeout = params
+        .timeout_ms
+        .map_or(DEFAULT_SOLVE_TIMEOUT, Duration::from_millis)
+        .min(MAX_SOLVE_TIMEOUT);
+    let config = SolverConfig {
+        timeout,
+        ..SolverConfig::default()
+    };
+
+    log::info!(
+        "Solving {}×{} board with {}",
+        state.size(),
+        state.size(),
+        kind
+    );
+    let Some(solution) = kind.solve_instrumented(config, &state, None, &mut |_| {}) else {
+        return Response::error(422, format!("{} found no solution within its limits", kind));
+    };
+
+    // Report the tile numbers too, which are easier to follow than positions
+    let mut replay = state.clone();
+    let mut tiles = Vec::with_capacity(solution.len());
+    for &(row, col) in &solution.path {
+        tiles.push(replay.to_layout()[row][col]);
+        replay.apply_immediate_move((row, col));
+    }
+
+    Response::ok(json!({
+        "solver": kind.to_string(),
+        "optimal": kind.is_optimal(),
+        "length": solution.len(),
+        "path": solution.path,
+        "tiles": tiles,
+        "notation": solution.to_string(),
+        "nodes_expanded": solution.nodes_expanded,
+        "time_ms": solution.duration.as_secs_f64() * 1000.0,
+    }))
+}
+
+fn shuffle(request: &Request) -> Response {
+    let size = match request.query.get("size").map(|s| s.parse::<usize>()) {
+        Some(Ok(size)) => size,
+        Some(Err(_)) => return Response::error(400, "size must be a number"),
+        None => 4,
+    };
+    let difficulty = match request.query.get("difficulty") {
+        Some(name) => match Difficulty::from_name(name) {
+            Some(difficulty) => difficulty,
+            None => return Response::error(400, format!("unknown difficulty '{}'", name)),
+        },
+        None => Difficulty::Medium,
+    };
+    let seed = match request.query.get("seed").map(|s| s.parse::<u64>()) {
+        Some(Ok(seed)) => Some(seed),
+        Some(Err(_)) => return Response::error(400, "seed must be a number"),
+        None => None,
+    };
+
+    let (mut state, shuffler) = match (PuzzleState::new(size), ShuffleController::new(size)) {
+        (Ok(state), Ok(shuffler)) => (state, shuffler),
+        (Err(e), _) | (_, Err(e)) => return Response::error(400, e.to_string()),
+    };
+    let result = match seed {
+        Some(seed) => shuffler.shuffle_seeded(&mut state, difficulty, &ManhattanDistance, seed),
+        None => shuffler.shuffle(&mut state, difficulty, &ManhattanDistance),
+    };
+
+    Response::ok(json!({
+        "size": size,
+        "difficulty": difficulty.to_string(),
+        "board": state.to_notation(),
+        "share_code": state.to_share_code(),
+        "layout": state.to_layout(),
+        "moves_made": result.moves_made,
+        "entropy": result.final_entropy,
+        "target_met": result.target_met,
+        "seed": result.seed,
+    }))
+}
+
+fn analyze(request: &Request) -> Response {
+    let Some(text) = request.query.get("board") else {
+        return Response::error(400, "missing board parameter");
+    };
+    let state = match PuzzleState::from_notation_or_code(text) {
+        Ok(state) => state,
+        Err(e) => return Response::error(400, e.to_string()),
+    };
+
+    Response::ok(json!({
+        "size": state.size(),
+        "solved": state.is_solved(),
+        "board": state.to_notation(),
+        "share_code": state.to_share_code(),
+        "manhattan_distance": ManhattanDistance.calculate(&state),
+        "shortest_path_heuristic": ShortestPathHeuristic.calculate(&state),
+        "walking_distance": walking_distance(state.size()).map(|table| table.calculate(&state)),
+    }))
+}
+
+/// Returns the shared walking-distance table for `size`, if the size supports one
+/// Tables are loaded (or built) on first use and kept for the life of the server
+fn walking_distance(size: usize) -> Option<&'static WalkingDistance> {
+    static TABLES: [OnceLock<Option<WalkingDistance>>; 2] = [OnceLock::new(), OnceLock::new()];
+    let slot = TABLES.get(size.checked_sub(3)?)?;
+    // Tests build throwaway tables rather than touching the user's cache
+    let cache = if cfg!(test) {
+        HeuristicCache::disabled()
+    } else {
+        HeuristicCache::user()
+    };
+    slot.get_or_init(|| WalkingDistance::load_or_build(size, &cache).ok())
+        .as_ref()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+fn write_response(stream: &mut impl Write, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn handle_connection(mut stream: TcpStream) {
+    if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+        log::warn!("Failed to set read timeout: {}", e);
+    }
+    let response = match stream
+        .try_clone()
+        .map_err(RequestError::from)
+        .and_then(|s| read_request(&mut BufReader::new(s)))
+    {
+        Ok(request) => {
+            log::debug!("{} {}", request.method, request.path);
+            route(&request)
+        }
+        Err(e) => e.response(),
+    };
+    if let Err(e) = write_response(&mut stream, &response) {
+        log::warn!("Failed to send response: {}", e);
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let level = if args.iter().any(|arg| arg == "--verbose" || arg == "-v") {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    slider::logging::init(level);
+
+    // --bind ADDR:PORT picks the listening address
+    let address = args
+        .iter()
+        .position(|arg| arg == "--bind")
+        .and_then(|i| args.get(i + 1))
+        .map_or(DEFAULT_ADDRESS, String::as_str);
+
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to listen on {}: {}", address, e);
+            std::process::exit(1);
+        }
+    };
+    log::info!("Listening on http://{}", address);
+
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(mut stream) => {
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    log::warn!(
+                        "Turning a connection away; {} already open",
+                        MAX_CONNECTIONS
+                    );
+                    let busy = Response::error(503, "too many connections; try again shortly");
+                    if let Err(e) = write_response(&mut stream, &busy) {
+                        log::warn!("Failed to send response: {}", e);
+                    }
+                    continue;
+                }
+                let active = active.clone();
+                std::thread::spawn(move || {
+                    handle_connection(stream);
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+            Err(e) => log::warn!("Failed to accept connection: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Request {
+        read_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn post(path: &str, body: &str) -> Request {
+        request(&format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        ))
+    }
+
+    #[test]
+    fn test_read_request() {
+        let req = post("/shuffle?size=3&difficulty=extra%2Dhard", "{}");
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/shuffle");
+        assert_eq!(req.query["size"], "3");
+        assert_eq!(req.query["difficulty"], "extra-hard");
+        assert_eq!(req.body, b"{}");
+
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+        assert_eq!(percent_decode("1+2%20_%2F%zz"), "1 2 _/%zz");
+    }
+
+    #[test]
+    fn test_solve() {
+        let response = route(&post("/solve", r#"{"board": "1 2 3 / 4 5 6 / 7 _ 8"}"#));
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["length"], 1);
+        assert_eq!(response.body["path"], json!([[2, 2]]));
+        assert_eq!(response.body["tiles"], json!([8]));
+        assert_eq!(response.body["notation"], "R");
+        assert_eq!(response.body["solver"], "IDA*");
+        assert_eq!(response.body["optimal"], true);
+
+        let astar = route(&post(
+            "/solve",
+            r#"{"board": "1 2 3 / 4 5 6 / 7 _ 8", "solver": "astar"}"#,
+        ));
+        assert_eq!(astar.body["length"], 1);
+        assert_eq!(astar.body["optimal"], false); // A* can overestimate
+
+        let bad = route(&post("/solve", r#"{"board": "1 2 3 / 4 5 6 / 8 7 _"}"#));
+        assert_eq!(bad.status, 400);
+        assert!(bad.body["error"].is_string());
+        assert_eq!(route(&post("/solve", "not json")).status, 400);
+        assert_eq!(
+            route(&post(
+                "/solve",
+                r#"{"board": "1 2 3 / 4 5 6 / 7 _ 8", "solver": "bogo"}"#
+            ))
+            .status,
+            400
+        );
+    }
+
+    #[test]
+    fn test_shuffle_and_analyze() {
+        let response = route(&post("/shuffle?difficulty=easy&size=3&seed=11", ""));
+        assert_eq!(response.status, 200);
+        let board = response.body["board"].as_str().unwrap().to_string();
+        let again = route(&post("/shuffle?difficulty=easy&size=3&seed=11", ""));
+        assert_eq!(again.body["board"], board);
+        assert_eq!(again.body["seed"], 11);
+
+        let query = board.replace(' ', "+").replace('/', "%2F");
+        let analysis = route(&request(&format!(
+            "GET /analyze?board={} HTTP/1.1\r\n\r\n",
+            query
+        )));
+        assert_eq!(analysis.status, 200);
+        assert_eq!(analysis.body["board"], board);
+        assert!(analysis.body["manhattan_distance"].as_u64().unwrap() > 0);
+
+        assert_eq!(route(&post("/shuffle?size=99", "")).status, 400);
+        assert_eq!(route(&post("/shuffle?difficulty=brutal", "")).status, 400);
+    }
+
+    #[test]
+    fn test_request_limits() {
+        let status = |raw: String| match read_request(&mut raw.as_bytes()) {
+            Ok(_) => 200,
+            Err(e) => e.response().status,
+        };
+        let long_target = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_BYTES));
+        assert_eq!(status(long_target), 431);
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_LINE_BYTES)
+        );
+        assert_eq!(status(long_header), 431);
+
+        let headers = |count: usize| format!("GET / HTTP/1.1\r\n{}\r\n", "X: 1\r\n".repeat(count));
+        assert_eq!(status(headers(MAX_HEADERS)), 200);
+        assert_eq!(status(headers(MAX_HEADERS + 1)), 431);
+
+        let body_too_large = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(status(body_too_large), 400);
+        assert_eq!(status("GET\r\n\r\n".to_string()), 400);
+    }
+
+    #[test]
+    fn test_routing_errors() {
+        assert_eq!(route(&request("GET /solve HTTP/1.1\r\n\r\n")).status, 405);
+        assert_eq!(route(&request("GET /nowhere HTTP/1.1\r\n\r\n")).status, 404);
+        assert_eq!(route(&request("GET /analyze HTTP/1.1\r\n\r\n")).status, 400);
+    }
+}
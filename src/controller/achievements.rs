@@ -0,0 +1,252 @@
+//! Milestones unlocked by completed games, kept in a local store.
+//!
+//! Achievements are checked against each finished shuffled game and never leave
+//! the machine: unlocks are written to a JSON file in the data directory, like
+//! best times and statistics.
+
+use crate::controller::stats::GameRecord;
+use crate::controller::storage;
+use crate::model::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the achievements store inside the data directory
+pub const ACHIEVEMENTS_FILE: &str = "achievements.json";
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Games that must be completed for `Achievement::Centurion`
+pub const CENTURION_GAMES: usize = 100;
+
+/// A milestone the player can unlock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    /// Solve any shuffled board without auto-solve
+    FirstSolve,
+    /// Solve a board in no more tile moves than par (its optimal solution)
+    SubPar,
+    /// Solve a 5×5 or larger board without auto-solve
+    BigBoard,
+    /// Complete 100 games
+    Centurion,
+    /// Solve a Hard or Extra Hard board without hints or auto-solve
+    NoHintHard,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstSolve,
+        Achievement::SubPar,
+        Achievement::BigBoard,
+        Achievement::Centurion,
+        Achievement::NoHintHard,
+    ];
+
+    /// Returns what the player has to do to unlock the achievement
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstSolve => "Solve a shuffled board on your own",
+            Achievement::SubPar => "Solve a board in no more moves than par",
+            Achievement::BigBoard => "Solve a 5×5 or larger board on your own",
+            Achievement::Centurion => "Complete 100 games",
+            Achievement::NoHintHard => "Solve a Hard board without hints",
+        }
+    }
+
+    /// Returns true if `game` earns the achievement, `games_played` counting it
+    pub fn is_earned(self, game: &GameRecord, games_played: usize) -> bool {
+        let unaided = !game.assisted && !game.failed;
+        match self {
+            Achievement::FirstSolve => unaided,
+            Achievement::SubPar => {
+                unaided && game.optimal_length.is_some_and(|par| game.tile_moves.max(game.moves) <= par as usize)
+            }
+            Achievement::BigBoard => unaided && game.grid_size >= 5,
+            Achievement::Centurion => games_played >= CENTURION_GAMES,
+            Achievement::NoHintHard => {
+                unaided && game.hints == 0 && matches!(game.difficulty, Difficulty::Hard | Difficulty::ExtraHard)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Achievement::FirstSolve => "First Solve",
+            Achievement::SubPar => "Under Par",
+            Achievement::BigBoard => "Big Board",
+            Achievement::Centurion => "Centurion",
+            Achievement::NoHintHard => "No Hints Needed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Serialized form of the store
+#[derive(Debug, Serialize, Deserialize)]
+struct AchievementsFile {
+    version: u32,
+    unlocked: BTreeMap<Achievement, u64>,
+}
+
+/// Unlocked achievements with the time each was unlocked
+#[derive(Debug, Clone, Default)]
+pub struct Achievements {
+    unlocked: BTreeMap<Achievement, u64>, // Unlock time in seconds since the Unix epoch
+    path: Option<PathBuf>,                // Where to persist updates (None = in-memory only)
+}
+
+impl Achievements {
+    /// Creates an empty in-memory store that is never written to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the store from `path`, persisting future unlocks back to it
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read or parsed
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut achievements = Self {
+            unlocked: BTreeMap::new(),
+            path: Some(path.to_path_buf()),
+        };
+
+        match storage::load_json::<AchievementsFile>(path) {
+            Ok(file) => {
+                if file.version == FORMAT_VERSION {
+                    achievements.unlocked = file.unlocked;
+                }
+                Ok(achievements)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(achievements),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the store from the default data directory
+    /// Falls back to an in-memory store if the file is unavailable or corrupt
+    pub fn load_default() -> Self {
+        storage::data_file(ACHIEVEMENTS_FILE)
+            .and_then(|path| Self::load(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if `achievement` has been unlocked
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains_key(&achievement)
+    }
+
+    /// Returns when `achievement` was unlocked, in seconds since the Unix epoch
+    pub fn unlocked_at(&self, achievement: Achievement) -> Option<u64> {
+        self.unlocked.get(&achievement).copied()
+    }
+
+    /// Returns the number of achievements unlocked
+    pub fn unlocked_count(&self) -> usize {
+        self.unlocked.len()
+    }
+
+    /// Unlocks everything `game` earns, returning the achievements that are new
+    ///
+    /// `games_played` is the number of completed games, including this one.
+    /// New unlocks are written to disk when the store has a backing file.
+    pub fn record(&mut self, game: &GameRecord, games_played: usize) -> Vec<Achievement> {
+        let earned: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|&a| !self.is_unlocked(a) && a.is_earned(game, games_played))
+            .collect();
+        if earned.is_empty() {
+            return earned;
+        }
+        for &achievement in &earned {
+            self.unlocked.insert(achievement, game.completed_at);
+        }
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save achievements: {}", e);
+        }
+        earned
+    }
+
+    /// Writes the store to its backing file (no-op for in-memory stores)
+    fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = AchievementsFile {
+            version: FORMAT_VERSION,
+            unlocked: self.unlocked.clone(),
+        };
+        storage::save_json(path, &file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-achievements-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn game(grid_size: usize, difficulty: Difficulty, tile_moves: usize, par: u32, assisted: bool) -> GameRecord {
+        let time = Duration::from_secs(60);
+        GameRecord::new(grid_size, difficulty, tile_moves, tile_moves, time, Some(par), assisted)
+    }
+
+    #[test]
+    fn test_unlocks_once() {
+        let mut achievements = Achievements::new();
+        let first = game(4, Difficulty::Easy, 30, 20, false);
+        assert_eq!(achievements.record(&first, 1), vec![Achievement::FirstSolve]);
+        assert!(achievements.record(&first, 2).is_empty());
+
+        let hard_at_par = game(5, Difficulty::Hard, 40, 40, false);
+        assert_eq!(
+            achievements.record(&hard_at_par, 3),
+            vec![Achievement::SubPar, Achievement::BigBoard, Achievement::NoHintHard]
+        );
+        assert_eq!(achievements.unlocked_count(), 4);
+        assert!(!achievements.is_unlocked(Achievement::Centurion));
+    }
+
+    #[test]
+    fn test_assistance_and_hints_disqualify() {
+        let mut achievements = Achievements::new();
+        let assisted = game(5, Difficulty::Hard, 40, 40, true);
+        assert!(achievements.record(&assisted, 1).is_empty());
+
+        let mut hinted = game(3, Difficulty::ExtraHard, 25, 20, false);
+        hinted.hints = 2;
+        assert_eq!(achievements.record(&hinted, 2), vec![Achievement::FirstSolve]);
+
+        // Any completed game counts towards the hundred
+        assert_eq!(achievements.record(&assisted, CENTURION_GAMES), vec![Achievement::Centurion]);
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let path = temp_path("round_trip.json");
+        let _ = std::fs::remove_file(&path);
+
+        let mut achievements = Achievements::load(&path).unwrap();
+        let record = game(3, Difficulty::Medium, 30, 20, false);
+        achievements.record(&record, 1);
+
+        let reloaded = Achievements::load(&path).unwrap();
+        assert_eq!(reloaded.unlocked_at(Achievement::FirstSolve), Some(record.completed_at));
+        assert_eq!(reloaded.unlocked_count(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,260 @@
+//! Adaptive difficulty: picks the next shuffle target from recent results.
+//!
+//! The engine looks at the last few unassisted games on a grid size in the
+//! statistics store. Players who finish close to par and quickly get a harder
+//! target than their last game; players who wander or take long over each
+//! move get an easier one. The target is a Manhattan distance, used as a
+//! custom difficulty for the next shuffle.
+//!
+//! ```rust
+//! use slider::controller::{AdaptiveDifficulty, Stats};
+//! use slider::Difficulty;
+//!
+//! // With no history the engine starts at Medium
+//! let next = AdaptiveDifficulty::from_stats(&Stats::new(), 4);
+//! assert_eq!(next.target(), Difficulty::Medium.min_entropy(4));
+//! ```
+
+use crate::controller::stats::{GameRecord, Stats};
+use crate::model::Difficulty;
+use std::fmt;
+
+/// Number of recent games the engine weighs
+pub const ADAPTIVE_WINDOW: usize = 5;
+
+/// How far one adjustment moves the target, in percent of the last target
+const STEP_PERCENT: u32 = 15;
+
+/// Mean efficiency at or above which the target rises
+const STRONG_EFFICIENCY: f64 = 0.8;
+
+/// Mean efficiency below which the target falls
+const WEAK_EFFICIENCY: f64 = 0.5;
+
+/// Seconds per par move at or below which the target may rise
+const FAST_SECONDS_PER_MOVE: f64 = 1.5;
+
+/// Seconds per par move above which the target falls
+const SLOW_SECONDS_PER_MOVE: f64 = 5.0;
+
+/// Which way the target moved from the last game's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    Harder,
+    Same,
+    Easier,
+}
+
+impl fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Adjustment::Harder => write!(f, "harder"),
+            Adjustment::Same => write!(f, "same"),
+            Adjustment::Easier => write!(f, "easier"),
+        }
+    }
+}
+
+/// The shuffle target chosen for the next game on one grid size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveDifficulty {
+    target: u32,
+    adjustment: Adjustment,
+    games_considered: usize,
+    efficiency: Option<f64>,
+    seconds_per_move: Option<f64>,
+}
+
+impl AdaptiveDifficulty {
+    /// Chooses the next target for `grid_size` from the games in `stats`
+    ///
+    /// Only unassisted, won games of that size with a known par count. Without any,
+    /// the target is Medium's. Otherwise it starts from the most recent game's
+    /// target and moves by `STEP_PERCENT`, staying within `bounds`.
+    pub fn from_stats(stats: &Stats, grid_size: usize) -> Self {
+        let recent: Vec<&GameRecord> = stats
+            .games()
+            .iter()
+            .rev()
+            .filter(|g| g.grid_size == grid_size && !g.assisted && !g.failed && g.optimal_length.is_some())
+            .take(ADAPTIVE_WINDOW)
+            .collect();
+        let (lower, upper) = Self::bounds(grid_size);
+
+        let Some(last) = recent.first() else {
+            return Self {
+                target: Difficulty::Medium
+                    .min_entropy(grid_size)
+                    .clamp(lower, upper),
+                adjustment: Adjustment::Same,
+                games_considered: 0,
+                efficiency: None,
+                seconds_per_move: None,
+            };
+        };
+
+        let efficiency = mean(recent.iter().filter_map(|g| g.efficiency()));
+        let seconds_per_move = mean(recent.iter().filter_map(|g| {
+            let par = g.optimal_length.filter(|&par| par > 0)?;
+            Some(g.time().as_secs_f64() / par as f64)
+        }));
+        let adjustment = match (efficiency, seconds_per_move) {
+            (Some(e), _) if e < WEAK_EFFICIENCY => Adjustment::Easier,
+            (_, Some(pace)) if pace > SLOW_SECONDS_PER_MOVE => Adjustment::Easier,
+            (Some(e), Some(pace)) if e >= STRONG_EFFICIENCY && pace <= FAST_SECONDS_PER_MOVE => {
+                Adjustment::Harder
+            }
+            _ => Adjustment::Same,
+        };
+
+        let base = last.difficulty.min_entropy(grid_size);
+        let step = (base * STEP_PERCENT / 100).max(1);
+        let target = match adjustment {
+            Adjustment::Harder => base + step,
+            Adjustment::Same => base,
+            Adjustment::Easier => base.saturating_sub(step),
+        };
+
+        Self {
+            target: target.clamp(lower, upper),
+            adjustment,
+            games_considered: recent.len(),
+            efficiency,
+            seconds_per_move,
+        }
+    }
+
+    /// Returns the lowest and highest targets the engine picks for `grid_size`
+    ///
+    /// From Easy up to Extra Hard, but never above three quarters of the
+    /// largest possible distance, which small boards cannot reliably reach.
+    pub fn bounds(grid_size: usize) -> (u32, u32) {
+        let lower = Difficulty::Easy.min_entropy(grid_size);
+        let upper = Difficulty::ExtraHard
+            .min_entropy(grid_size)
+            .min(Difficulty::max_entropy(grid_size) * 3 / 4);
+        (lower, upper.max(lower))
+    }
+
+    /// Returns the Manhattan distance the next shuffle aims for
+    pub fn target(&self) -> u32 {
+        self.target
+    }
+
+    /// Returns the target as a difficulty to shuffle with
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::Custom {
+            min_entropy: self.target,
+        }
+    }
+
+    /// Returns which way the target moved from the last game's
+    pub fn adjustment(&self) -> Adjustment {
+        self.adjustment
+    }
+
+    /// Returns how many recent games the choice was based on
+    pub fn games_considered(&self) -> usize {
+        self.games_considered
+    }
+
+    /// Returns the mean efficiency of the games considered
+    pub fn efficiency(&self) -> Option<f64> {
+        self.efficiency
+    }
+
+    /// Returns the mean play time per par move of the games considered
+    pub fn seconds_per_move(&self) -> Option<f64> {
+        self.seconds_per_move
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn game(difficulty: Difficulty, moves: usize, par: u32, seconds: u64) -> GameRecord {
+        GameRecord::new(
+            4,
+            difficulty,
+            moves,
+            moves,
+            Duration::from_secs(seconds),
+            Some(par),
+            false,
+        )
+    }
+
+    fn stats(games: &[GameRecord]) -> Stats {
+        let mut stats = Stats::new();
+        for game in games {
+            stats.record(game.clone());
+        }
+        stats
+    }
+
+    #[test]
+    fn test_starts_at_medium() {
+        let next = AdaptiveDifficulty::from_stats(&Stats::new(), 4);
+        assert_eq!(next.target(), Difficulty::Medium.min_entropy(4));
+        assert_eq!(next.adjustment(), Adjustment::Same);
+        assert_eq!(next.games_considered(), 0);
+    }
+
+    #[test]
+    fn test_adjusts_from_last_target() {
+        let medium = Difficulty::Medium.min_entropy(4); // 16
+
+        // Near par and quick: harder than the last game
+        let strong = stats(&[game(Difficulty::Medium, 20, 18, 20)]);
+        let next = AdaptiveDifficulty::from_stats(&strong, 4);
+        assert_eq!(next.adjustment(), Adjustment::Harder);
+        assert_eq!(next.target(), medium + 2);
+
+        // Far from par: easier
+        let weak = stats(&[game(Difficulty::Medium, 60, 18, 20)]);
+        let next = AdaptiveDifficulty::from_stats(&weak, 4);
+        assert_eq!(next.adjustment(), Adjustment::Easier);
+        assert_eq!(next.target(), medium - 2);
+
+        // Accurate but slow: easier
+        let slow = stats(&[game(Difficulty::Medium, 20, 18, 200)]);
+        assert_eq!(
+            AdaptiveDifficulty::from_stats(&slow, 4).adjustment(),
+            Adjustment::Easier
+        );
+
+        // In between: the same target again, continuing from a custom one
+        let steady = stats(&[game(Difficulty::Custom { min_entropy: 30 }, 26, 18, 50)]);
+        let next = AdaptiveDifficulty::from_stats(&steady, 4);
+        assert_eq!(next.adjustment(), Adjustment::Same);
+        assert_eq!(next.difficulty(), Difficulty::Custom { min_entropy: 30 });
+    }
+
+    #[test]
+    fn test_ignores_other_games_and_stays_in_bounds() {
+        let mut games = vec![game(Difficulty::ExtraHard, 40, 40, 10); ADAPTIVE_WINDOW + 3];
+        let mut assisted = game(Difficulty::Easy, 200, 10, 900);
+        assisted.assisted = true;
+        let mut other_size = game(Difficulty::Easy, 200, 10, 900);
+        other_size.grid_size = 5;
+        games.push(assisted);
+        games.push(other_size);
+
+        let next = AdaptiveDifficulty::from_stats(&stats(&games), 4);
+        assert_eq!(next.games_considered(), ADAPTIVE_WINDOW);
+        assert_eq!(next.adjustment(), Adjustment::Harder);
+        assert_eq!(next.target(), AdaptiveDifficulty::bounds(4).1);
+
+        for size in 3..=8 {
+            let (lower, upper) = AdaptiveDifficulty::bounds(size);
+            assert!(lower <= upper && upper <= Difficulty::max_entropy(size));
+        }
+    }
+}
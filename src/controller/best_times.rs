@@ -0,0 +1,200 @@
+//! Persistent table of fastest solve times per grid size and difficulty.
+
+use crate::controller::storage;
+use crate::model::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name of the best-times store inside the data directory
+pub const BEST_TIMES_FILE: &str = "best_times.json";
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Serialized form of a single best-time entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BestTimeRecord {
+    grid_size: usize,
+    difficulty: Difficulty,
+    millis: u64,
+}
+
+/// Serialized form of the whole table
+#[derive(Debug, Serialize, Deserialize)]
+struct BestTimesFile {
+    version: u32,
+    records: Vec<BestTimeRecord>,
+}
+
+/// Fastest solve times keyed by (grid size, difficulty)
+#[derive(Debug, Clone, Default)]
+pub struct BestTimes {
+    times: HashMap<(usize, Difficulty), Duration>,
+    path: Option<PathBuf>, // Where to persist updates (None = in-memory only)
+}
+
+impl BestTimes {
+    /// Creates an empty in-memory table that is never written to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the table from `path`, persisting future records back to it
+    ///
+    /// A missing file yields an empty table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read or parsed
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut table = Self {
+            times: HashMap::new(),
+            path: Some(path.to_path_buf()),
+        };
+
+        match storage::load_json::<BestTimesFile>(path) {
+            Ok(file) => {
+                if file.version == FORMAT_VERSION {
+                    for record in file.records {
+                        table.times.insert(
+                            (record.grid_size, record.difficulty),
+                            Duration::from_millis(record.millis),
+                        );
+                    }
+                }
+                Ok(table)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(table),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the table from the default data directory
+    /// Falls back to an in-memory table if the store is unavailable or corrupt
+    pub fn load_default() -> Self {
+        storage::data_file(BEST_TIMES_FILE)
+            .and_then(|path| Self::load(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the best time for the given grid size and difficulty
+    pub fn get(&self, grid_size: usize, difficulty: Difficulty) -> Option<Duration> {
+        self.times.get(&(grid_size, difficulty)).copied()
+    }
+
+    /// Records a completed solve, returning true if it set a new best time
+    /// New bests are written to disk when the table has a backing file
+    pub fn record(&mut self, grid_size: usize, difficulty: Difficulty, time: Duration) -> bool {
+        let key = (grid_size, difficulty);
+        let is_best = self.times.get(&key).is_none_or(|&best| time < best);
+        if is_best {
+            self.times.insert(key, time);
+            if let Err(e) = self.save() {
+                log::warn!("Failed to save best times: {}", e);
+            }
+        }
+        is_best
+    }
+
+    /// Returns all entries sorted by grid size, then difficulty
+    pub fn entries(&self) -> Vec<(usize, Difficulty, Duration)> {
+        let mut entries: Vec<_> = self
+            .times
+            .iter()
+            .map(|(&(size, difficulty), &time)| (size, difficulty, time))
+            .collect();
+        entries.sort_by_key(|&(size, difficulty, _)| (size, difficulty_rank(difficulty)));
+        entries
+    }
+
+    /// Writes the table to its backing file (no-op for in-memory tables)
+    fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = BestTimesFile {
+            version: FORMAT_VERSION,
+            records: self
+                .entries()
+                .into_iter()
+                .map(|(grid_size, difficulty, time)| BestTimeRecord {
+                    grid_size,
+                    difficulty,
+                    millis: time.as_millis() as u64,
+                })
+                .collect(),
+        };
+        storage::save_json(path, &file)
+    }
+}
+
+/// Orders difficulties from easiest to hardest for display
+fn difficulty_rank(difficulty: Difficulty) -> (u8, u32) {
+    match difficulty {
+        Difficulty::Easy => (0, 0),
+        Difficulty::Medium => (1, 0),
+        Difficulty::Hard => (2, 0),
+        Difficulty::ExtraHard => (3, 0),
+        Difficulty::Custom { min_entropy } => (4, min_entropy),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-best-times-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_record_keeps_fastest() {
+        let mut table = BestTimes::new();
+        assert!(table.record(4, Difficulty::Easy, Duration::from_secs(30)));
+        assert!(!table.record(4, Difficulty::Easy, Duration::from_secs(40)));
+        assert!(table.record(4, Difficulty::Easy, Duration::from_secs(20)));
+        assert_eq!(table.get(4, Difficulty::Easy), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_keys_are_independent() {
+        let mut table = BestTimes::new();
+        table.record(4, Difficulty::Easy, Duration::from_secs(30));
+        assert_eq!(table.get(4, Difficulty::Hard), None);
+        assert_eq!(table.get(5, Difficulty::Easy), None);
+    }
+
+    #[test]
+    fn test_entries_sorted() {
+        let mut table = BestTimes::new();
+        table.record(5, Difficulty::Easy, Duration::from_secs(1));
+        table.record(4, Difficulty::Hard, Duration::from_secs(2));
+        table.record(4, Difficulty::Easy, Duration::from_secs(3));
+        let keys: Vec<_> = table.entries().iter().map(|&(s, d, _)| (s, d)).collect();
+        assert_eq!(
+            keys,
+            vec![(4, Difficulty::Easy), (4, Difficulty::Hard), (5, Difficulty::Easy)]
+        );
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let path = temp_path("round_trip.json");
+        let _ = std::fs::remove_file(&path);
+
+        let mut table = BestTimes::load(&path).unwrap();
+        table.record(3, Difficulty::Medium, Duration::from_millis(12_345));
+
+        let reloaded = BestTimes::load(&path).unwrap();
+        assert_eq!(
+            reloaded.get(3, Difficulty::Medium),
+            Some(Duration::from_millis(12_345))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,131 @@
+//! Challenge modes: shuffled games played against a move limit or a countdown.
+//!
+//! A [`GameMode`] is chosen before a game and fixed for its length. In free play
+//! nothing can go wrong; in a challenge the game is lost, and moves refused, as
+//! soon as the limit is reached with the board still unsolved. Lost games are
+//! recorded in the statistics alongside won ones.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Tile moves allowed beyond par by default in `GameMode::MoveLimit`
+pub const DEFAULT_MOVE_SLACK: u32 = 10;
+
+/// Default length of `GameMode::Countdown`, in seconds
+pub const DEFAULT_COUNTDOWN_SECS: u64 = 180;
+
+/// Rules a shuffled game is played under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// No limits
+    #[default]
+    Free,
+    /// Solve within par plus `slack` tile moves
+    ///
+    /// The limit applies once par is known; until the background solve finds
+    /// it, moves are not counted against the player.
+    MoveLimit { slack: u32 },
+    /// Solve before the game clock reaches `seconds`
+    Countdown { seconds: u64 },
+}
+
+impl GameMode {
+    /// Every kind of mode with its default limit, in display order
+    pub const PRESETS: [GameMode; 3] = [
+        GameMode::Free,
+        GameMode::MoveLimit {
+            slack: DEFAULT_MOVE_SLACK,
+        },
+        GameMode::Countdown {
+            seconds: DEFAULT_COUNTDOWN_SECS,
+        },
+    ];
+
+    /// Returns true for a mode that can be lost
+    pub fn is_challenge(self) -> bool {
+        self != GameMode::Free
+    }
+
+    /// Returns the most tile moves allowed on a board whose optimal solution is `par` long
+    /// Returns None without a move limit, or while par is unknown
+    pub fn move_limit(self, par: Option<u32>) -> Option<usize> {
+        match self {
+            GameMode::MoveLimit { slack } => par.map(|par| (par + slack) as usize),
+            _ => None,
+        }
+    }
+
+    /// Returns the play time allowed, or None without a countdown
+    pub fn time_limit(self) -> Option<Duration> {
+        match self {
+            GameMode::Countdown { seconds } => Some(Duration::from_secs(seconds)),
+            _ => None,
+        }
+    }
+
+    /// Returns why an unsolved game has been lost, or None if it can still be won
+    pub fn check(self, tile_moves: usize, par: Option<u32>, elapsed: Duration) -> Option<ChallengeFailure> {
+        if self.move_limit(par).is_some_and(|limit| tile_moves >= limit) {
+            Some(ChallengeFailure::OutOfMoves)
+        } else if self.time_limit().is_some_and(|limit| elapsed >= limit) {
+            Some(ChallengeFailure::OutOfTime)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Free => write!(f, "Free play"),
+            GameMode::MoveLimit { slack } => write!(f, "Par + {} moves", slack),
+            GameMode::Countdown { seconds } => write!(f, "{}:{:02} countdown", seconds / 60, seconds % 60),
+        }
+    }
+}
+
+/// How a challenge was lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeFailure {
+    /// The move limit was reached before the board was solved
+    OutOfMoves,
+    /// The countdown ran out before the board was solved
+    OutOfTime,
+}
+
+impl fmt::Display for ChallengeFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChallengeFailure::OutOfMoves => write!(f, "Out of moves"),
+            ChallengeFailure::OutOfTime => write!(f, "Time's up"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let moves = GameMode::MoveLimit { slack: 4 };
+        assert_eq!(moves.move_limit(Some(20)), Some(24));
+        assert_eq!(moves.move_limit(None), None);
+        assert_eq!(moves.check(23, Some(20), Duration::from_secs(999)), None);
+        assert_eq!(moves.check(24, Some(20), Duration::ZERO), Some(ChallengeFailure::OutOfMoves));
+        assert_eq!(moves.check(500, None, Duration::ZERO), None); // Par still unknown
+
+        let clock = GameMode::Countdown { seconds: 90 };
+        assert_eq!(clock.check(500, Some(20), Duration::from_secs(89)), None);
+        assert_eq!(
+            clock.check(0, None, Duration::from_secs(90)),
+            Some(ChallengeFailure::OutOfTime)
+        );
+        assert_eq!(clock.to_string(), "1:30 countdown");
+
+        assert_eq!(GameMode::Free.check(usize::MAX, Some(1), Duration::MAX), None);
+        assert!(!GameMode::Free.is_challenge());
+    }
+}
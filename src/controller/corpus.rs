@@ -0,0 +1,197 @@
+//! Fixed sets of shuffled boards for repeatable comparisons.
+//!
+//! A corpus is generated once from seeded shuffles, saved as JSON, and loaded
+//! wherever solvers or heuristics are compared, so every run and every machine
+//! sees exactly the same boards.
+//!
+//! ```rust
+//! use slider::controller::PuzzleCorpus;
+//! use slider::Difficulty;
+//!
+//! let corpus = PuzzleCorpus::generate(&[(3, Difficulty::Hard), (4, Difficulty::Easy)], 5, 1)?;
+//! assert_eq!(corpus.boards(3, Difficulty::Hard).len(), 5);
+//! # Ok::<(), slider::model::PuzzleError>(())
+//! ```
+
+use crate::controller::storage;
+use crate::controller::ShuffleController;
+use crate::model::{Difficulty, ManhattanDistance, PuzzleError, PuzzleState};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Format version written into saved corpora
+const CORPUS_VERSION: u32 = 1;
+
+/// One shuffled board and how it was made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    pub size: usize,
+    pub difficulty: Difficulty,
+    /// Seed passed to `ShuffleController::shuffle_seeded`
+    pub seed: u64,
+    /// Board in text notation
+    pub board: String,
+}
+
+impl CorpusEntry {
+    /// Parses the stored board
+    pub fn state(&self) -> Result<PuzzleState, PuzzleError> {
+        self.board.parse()
+    }
+}
+
+/// A saved set of boards grouped by size and difficulty
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleCorpus {
+    version: u32,
+    entries: Vec<CorpusEntry>,
+}
+
+impl PuzzleCorpus {
+    /// Shuffles `per_set` boards for each size and difficulty pair
+    ///
+    /// Board `i` of every set uses seed `seed + i`, so the same arguments always
+    /// produce the same corpus.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if any size is out of range
+    pub fn generate(
+        sets: &[(usize, Difficulty)],
+        per_set: usize,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let mut entries = Vec::with_capacity(sets.len() * per_set);
+        for &(size, difficulty) in sets {
+            let shuffler = ShuffleController::new(size)?;
+            for i in 0..per_set as u64 {
+                let board_seed = seed.wrapping_add(i);
+                let mut state = PuzzleState::new(size)?;
+                shuffler.shuffle_seeded(&mut state, difficulty, &ManhattanDistance, board_seed);
+                entries.push(CorpusEntry {
+                    size,
+                    difficulty,
+                    seed: board_seed,
+                    board: state.to_notation(),
+                });
+            }
+        }
+        log::debug!("Generated corpus of {} boards", entries.len());
+        Ok(Self {
+            version: CORPUS_VERSION,
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[CorpusEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns every board of the given size and difficulty, in corpus order
+    pub fn boards(&self, size: usize, difficulty: Difficulty) -> Vec<PuzzleState> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.size == size && entry.difficulty == difficulty)
+            .filter_map(|entry| entry.state().ok())
+            .collect()
+    }
+
+    /// Writes the corpus as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save_json(path, self)
+    }
+
+    /// Reads a corpus written by `save`, checking that every board is valid
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error, or `InvalidData` if the file is malformed,
+    /// from a newer version, or holds a board that does not parse
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let corpus: Self = storage::load_json(path)?;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        if corpus.version > CORPUS_VERSION {
+            return Err(invalid(format!(
+                "corpus version {} is newer than this build",
+                corpus.version
+            )));
+        }
+        for (index, entry) in corpus.entries.iter().enumerate() {
+            match entry.state() {
+                Ok(state) if state.size() == entry.size => {}
+                Ok(_) => {
+                    return Err(invalid(format!(
+                        "entry {} is not {}×{}",
+                        index, entry.size, entry.size
+                    )))
+                }
+                Err(e) => return Err(invalid(format!("entry {}: {}", index, e))),
+            }
+        }
+        Ok(corpus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-corpus-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_generate_is_repeatable() {
+        let sets = [(3, Difficulty::Medium), (4, Difficulty::Hard)];
+        let corpus = PuzzleCorpus::generate(&sets, 4, 10).unwrap();
+        assert_eq!(corpus.len(), 8);
+        assert_eq!(corpus, PuzzleCorpus::generate(&sets, 4, 10).unwrap());
+        assert_ne!(corpus, PuzzleCorpus::generate(&sets, 4, 11).unwrap());
+
+        let boards = corpus.boards(4, Difficulty::Hard);
+        assert_eq!(boards.len(), 4);
+        assert!(boards.iter().all(|b| b.size() == 4 && !b.is_solved()));
+        assert!(corpus.boards(4, Difficulty::Easy).is_empty());
+        assert!(PuzzleCorpus::generate(&[(2, Difficulty::Easy)], 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = temp_path("round_trip.json");
+        let corpus = PuzzleCorpus::generate(&[(3, Difficulty::Easy)], 3, 5).unwrap();
+        corpus.save(&path).unwrap();
+        assert_eq!(PuzzleCorpus::load(&path).unwrap(), corpus);
+
+        // A hand-edited board that no longer parses is rejected
+        let text = fs::read_to_string(&path).unwrap();
+        let first = &corpus.entries()[0].board;
+        fs::write(
+            &path,
+            text.replacen(first.as_str(), "1 1 2 / 3 4 5 / 6 7 _", 1),
+        )
+        .unwrap();
+        assert_eq!(
+            PuzzleCorpus::load(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -1,28 +1,172 @@
+//! The sliding-puzzle game: player moves, shuffles, background solves, hints,
+//! auto-solve, metrics, and the records kept when a game is won.
+
+use crate::controller::adaptive::AdaptiveDifficulty;
+use crate::controller::achievements::{Achievement, Achievements};
+use crate::controller::best_times::BestTimes;
+use crate::controller::challenge::{ChallengeFailure, GameMode};
+use crate::controller::search_trace::SearchTrace;
+use crate::controller::session::SessionSnapshot;
 use crate::controller::shuffle_controller::{ShuffleController, ShuffleResult};
+use crate::controller::solution_cache::{CachedSolution, SolutionCache};
+use crate::controller::solver_dispatcher::{SolverChoice, SolverDispatcher};
+use crate::controller::solver_task::{
+    spawn_analysis_task, spawn_metrics_task, spawn_shuffle_task, spawn_solver_task, AnalysisJob, AnalysisTask,
+    MetricsJob, MetricsTask, ShuffleJob, ShuffleTask, SolverTask, TaskStatus,
+};
+use crate::controller::stats::{GameRecord, Stats};
+use crate::model::analysis::{GameAnalysis, MoveQuality};
 use crate::model::{
-    AStarSolver, ActualSolutionLength, Difficulty, EntropyCalculator, ManhattanDistance,
-    MoveValidator, PerformanceMetrics, PerformanceTimer, Position, PuzzleError, PuzzleState,
-    ShortestPathHeuristic,
+    AStarSolver, ActualSolutionLength, Annotation, AutoSolveError, Difficulty, Direction, EnhancedHeuristic, EntropyCalculator, EntropyKind, GoalSpec, HeuristicCache, LayeredPuzzle,
+    ManhattanDistance, MovePreview, MoveSystem, MoveValidator, PackedState, PerformanceMetrics, PerformanceTimer, Position, PuzzleError, PuzzleState, Region,
+    RelativePattern, RelativePatternCatalog, ShortestPathHeuristic, Solution, SolveStep, SolverConfig, SolverError, SolverKind, SolverProgress,
+    TieBreak, TileLabels,
 };
-use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use std::sync::mpsc::{channel, Receiver};
+use crate::model::strategic_solver::{skip_annotated_moves, step_at};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
-use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+/// Default pause between auto-solve moves (200ms animation + 500ms pause)
+pub const DEFAULT_AUTO_SOLVE_INTERVAL: Duration = Duration::from_millis(700);
+
+/// Slowest auto-solve playback speed, as a multiple of the normal pace
+pub const MIN_AUTO_SOLVE_SPEED: f32 = 0.1;
+
+/// Fastest auto-solve playback speed, as a multiple of the normal pace
+pub const MAX_AUTO_SOLVE_SPEED: f32 = 10.0;
+
+/// Number of recent shuffled boards a new game must not resemble
+pub const RECENT_SHUFFLES: usize = 5;
+
+/// Shuffles tried before a board resembling a recent one is accepted anyway
+const DISTINCT_SHUFFLE_ATTEMPTS: usize = 8;
+
+/// Heuristic weight of the quick estimate shown while no exact length is known
+const APPROX_WEIGHT: f64 = 3.0;
+
+/// Largest grid the quick estimate is tried on; bigger boards rarely finish in budget
+const APPROX_MAX_SIZE: usize = 5;
+
+/// Threads the par search may use; it runs alongside play and other solves
+/// for up to the IDA* timeout, so it must not take every core
+const PAR_THREADS: usize = 2;
+
+/// Search limits of the quick estimate, which runs on the metrics task
+const APPROX_CONFIG: SolverConfig = SolverConfig {
+    max_iterations: 20_000,
+    timeout: Duration::from_millis(20),
+    max_memory_bytes: 64 * 1024 * 1024,
+    symmetry: false,
+    weight: 1.0,
+    tie_break: TieBreak::HighG,
+    reopen_closed: false,
+    threads: None,
+};
+
 /// Combined entropy and performance metrics
 #[derive(Debug, Clone, Copy)]
 pub struct EntropyMetrics {
     pub manhattan_distance: u32,
     pub shortest_path_heuristic: u32,
     pub actual_solution_length: u32,
+    /// The actual length is a weighted-A* estimate, not the exact optimum
+    pub actual_is_estimate: bool,
+    /// Still being measured: the heuristics are current, but the actual length
+    /// and its timings belong to an earlier board
+    pub stale: bool,
     pub performance: PerformanceMetrics,
 }
 
+impl EntropyMetrics {
+    /// Measures `state` from scratch, without knowing its exact solution length
+    ///
+    /// The actual length is exact for trivial 3×3 boards, a weighted-A* estimate
+    /// on grids up to 5×5, and unknown (999) above. The estimate can take tens of
+    /// milliseconds, so the controller runs this on a background task.
+    pub fn measure(state: &PuzzleState) -> Self {
+        let mut metrics = Self::heuristics(state);
+        let perf = &mut metrics.performance;
+
+        // Only calculate actual solution for trivial puzzles (very low entropy)
+        metrics.actual_solution_length = if state.size() <= 3 && metrics.manhattan_distance <= 5 {
+            let timer = PerformanceTimer::start();
+            let result = ActualSolutionLength::new().calculate(state);
+            perf.actual_time_micros = timer.elapsed_micros();
+            result
+        } else if let Some(solution) = approximate_solution(state) {
+            // Quick weighted search until an exact length is known
+            metrics.actual_is_estimate = true;
+            perf.actual_time_micros = solution.duration.as_micros() as u64;
+            perf.solver_nodes_expanded = solution.nodes_expanded;
+            solution.len() as u32
+        } else {
+            999 // Placeholder for "not calculated"
+        };
+        metrics
+    }
+
+    /// Measures the heuristics of `state`, leaving the actual length unknown (999)
+    fn heuristics(state: &PuzzleState) -> Self {
+        let mut perf = PerformanceMetrics::new();
+
+        // Time Manhattan Distance calculation
+        let timer = PerformanceTimer::start();
+        let manhattan = ManhattanDistance.calculate(state);
+        perf.manhattan_time_micros = timer.elapsed_micros();
+
+        // Time Shortest Path Heuristic calculation
+        let timer = PerformanceTimer::start();
+        let shortest_path = ShortestPathHeuristic.calculate(state);
+        perf.heuristic_time_micros = timer.elapsed_micros();
+
+        Self {
+            manhattan_distance: manhattan,
+            shortest_path_heuristic: shortest_path,
+            actual_solution_length: 999,
+            actual_is_estimate: false,
+            stale: false,
+            performance: perf,
+        }
+    }
+}
+
+/// Runs a short weighted A* search for a near-optimal solution of `state`
+/// Returns None on grids above `APPROX_MAX_SIZE` or if the budget runs out
+fn approximate_solution(state: &PuzzleState) -> Option<Solution> {
+    if state.is_solved() || state.size() > APPROX_MAX_SIZE {
+        return None;
+    }
+    AStarSolver::with_config(APPROX_CONFIG).solve_approx_instrumented(state, APPROX_WEIGHT, None, &mut |_| {})
+}
+
+/// A catalog technique that can be played from the current empty cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TechniqueSuggestion {
+    /// The technique, turned to the orientation that fits best here
+    pub pattern: RelativePattern,
+    /// Immediate moves that play it
+    pub moves: Vec<Position>,
+    /// Change in Manhattan distance once played (negative brings tiles closer to home)
+    pub distance_change: i64,
+}
+
 /// Tracks the history of moves for scoring and statistics
+///
+/// Counts two metrics: player moves (each click, so a chain move counts once)
+/// and tile moves (each tile that slid one cell, so a chain of three counts three).
+/// Slides recorded with `record_slide` are also kept, grouped by player move, so a
+/// finished game can be replayed for analysis.
 #[derive(Debug, Default)]
 pub struct MoveHistory {
-    move_count: usize,
+    player_moves: usize,
+    tile_moves: usize,
+    moves: Vec<Vec<Position>>, // Tiles slid by each completed player move
+    pending: Vec<Position>,    // Tiles slid so far by the move in progress
 }
 
 impl MoveHistory {
@@ -30,44 +174,142 @@ impl MoveHistory {
         Self::default()
     }
 
+    /// Records a completed player move
     pub fn record_move(&mut self) {
-        self.move_count += 1;
+        self.player_moves += 1;
+        if !self.pending.is_empty() {
+            self.moves.push(std::mem::take(&mut self.pending));
+        }
     }
 
-    pub fn move_count(&self) -> usize {
-        self.move_count
+    /// Records one tile sliding one cell
+    pub fn record_tile_move(&mut self) {
+        self.tile_moves += 1;
+    }
+
+    /// Records the tile at `pos` sliding into the empty cell, as part of the move in progress
+    pub fn record_slide(&mut self, pos: Position) {
+        self.record_tile_move();
+        self.pending.push(pos);
+    }
+
+    /// Returns the tiles slid by each completed player move, oldest first
+    pub fn moves(&self) -> &[Vec<Position>] {
+        &self.moves
+    }
+
+    pub fn player_moves(&self) -> usize {
+        self.player_moves
+    }
+
+    pub fn tile_moves(&self) -> usize {
+        self.tile_moves
+    }
+
+    pub fn reset(&mut self) {
+        self.player_moves = 0;
+        self.tile_moves = 0;
+        self.moves.clear();
+        self.pending.clear();
+    }
+}
+
+/// Elapsed play time for the current game
+/// Runs from the player's first move until the puzzle is solved
+#[derive(Debug, Default)]
+pub struct GameClock {
+    started_at: Option<Instant>, // Some while running
+    accumulated: Duration,       // Time banked from earlier running spans
+}
+
+impl GameClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts (or resumes) the clock; no-op if already running
+    pub fn start(&mut self) {
+        if self.started_at.is_none() {
+            self.started_at = Some(Instant::now());
+        }
+    }
+
+    /// Pauses the clock, banking the time elapsed so far
+    pub fn pause(&mut self) {
+        if let Some(started) = self.started_at.take() {
+            self.accumulated += started.elapsed();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.accumulated + self.started_at.map_or(Duration::ZERO, |s| s.elapsed())
     }
 
     pub fn reset(&mut self) {
-        self.move_count = 0;
+        self.started_at = None;
+        self.accumulated = Duration::ZERO;
     }
 }
 
-/// Auto-solve computation state (running in background thread)
+/// Auto-solve computation state (running in a background task)
 pub enum SolverState {
-    Computing(JoinHandle<Option<(Vec<Position>, u64)>>, Arc<AtomicBool>, bool), // handle, cancel_flag, is_for_autosolve
-    Ready(Vec<Position>, u64), // path, solve_time_micros
-    Failed,
+    Computing {
+        task: Box<dyn SolverTask>,
+        kind: SolverKind,
+        for_autosolve: bool, // Animate the result rather than only using it for metrics
+        version: u64,        // State version the solve started from
+        start: PuzzleState,  // Board being solved, to cache the result under
+    },
+    Ready {
+        path: Vec<Position>,
+        annotations: Vec<Annotation>, // Steps of a strategic path, for auto-solve captions
+        solve_time_micros: u64,
+        version: u64, // State version the path starts from
+    },
+    Failed(SolverError),
+}
+
+/// A new game whose board is being shuffled in a background task
+struct PendingShuffle {
+    task: Box<dyn ShuffleTask>,
+    difficulty: Difficulty,
+    attempts: usize, // Shuffles tried so far, this one included
 }
 
 /// Auto-solve state
 pub struct AutoSolveState {
     solution_path: Vec<Position>,
+    annotations: Vec<Annotation>, // What each stretch of the path does, if the solver said
     current_step: usize,
     last_move_time: Instant,
     move_interval: Duration,
+    paused: bool,
+    version: u64, // State version that `current_step` applies to
 }
 
 impl AutoSolveState {
-    pub fn new(solution_path: Vec<Position>, move_interval: Duration) -> Self {
+    pub fn new(solution_path: Vec<Position>, move_interval: Duration, version: u64) -> Self {
         Self {
             solution_path,
+            annotations: Vec::new(),
             current_step: 0,
             last_move_time: Instant::now(),
             move_interval,
+            paused: false,
+            version,
         }
     }
 
+    /// Adds captions for the path's steps
+    pub fn with_annotations(mut self, annotations: Vec<Annotation>) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
     pub fn is_complete(&self) -> bool {
         self.current_step >= self.solution_path.len()
     }
@@ -77,19 +319,116 @@ impl AutoSolveState {
     }
 }
 
+/// Where a partial auto-solve hands the board back to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistTarget {
+    /// Play this many moves of the solution
+    Moves(usize),
+    /// Play until this many more tiles are home than when the assist started
+    Tiles(usize),
+    /// Play until this many rows, counted from the top, are solved
+    Rows(usize),
+    /// Play until the topmost unsolved row is solved, and every row above it
+    NextRow,
+}
+
+impl fmt::Display for AssistTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (count, unit) = match *self {
+            AssistTarget::NextRow => return write!(f, "next row"),
+            AssistTarget::Moves(n) => (n, "move"),
+            AssistTarget::Tiles(n) => (n, "tile"),
+            AssistTarget::Rows(n) => (n, "row"),
+        };
+        write!(f, "next {} {}{}", count, unit, if count == 1 { "" } else { "s" })
+    }
+}
+
+/// The condition a running partial auto-solve stops at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssistStop {
+    MovesLeft(usize),
+    TilesHome(usize),
+    RowsSolved(usize),
+}
+
+impl AssistStop {
+    fn is_reached(&self, state: &PuzzleState) -> bool {
+        match *self {
+            AssistStop::MovesLeft(left) => left == 0,
+            AssistStop::TilesHome(count) => tiles_home(state) >= count,
+            AssistStop::RowsSolved(rows) => (0..rows.min(state.size())).all(|row| row_solved(state, row)),
+        }
+    }
+}
+
+/// Returns the number of tiles in their goal position
+fn tiles_home(state: &PuzzleState) -> usize {
+    state.tiles().filter(|(pos, tile)| tile.home_position == *pos).count()
+}
+
+/// Returns true if every cell of `row` holds its goal tile (or is the goal's empty cell)
+fn row_solved(state: &PuzzleState, row: usize) -> bool {
+    (0..state.size()).all(|col| {
+        let pos = (row, col);
+        pos == state.goal_empty_position() || state.tile_at(pos).is_some_and(|tile| tile.home_position == pos)
+    })
+}
+
 /// Main game controller that orchestrates gameplay
 pub struct GameController {
-    state: PuzzleState,
+    state: PuzzleState, // Board being played (the active layer of a layered game)
+    layers: LayeredPuzzle, // Every layer; the active one is checked out into `state`
+    active_layer: usize,
     history: MoveHistory,
     shuffle_controller: ShuffleController,
-    entropy_calculator: Box<dyn EntropyCalculator>,
+    entropy_calculator: Arc<dyn EntropyCalculator + Send + Sync>, // Shared with background shuffles
+    entropy_kind: Option<EntropyKind>, // Built-in calculator in use; None for one set directly
+    heuristic_cache: HeuristicCache,   // Where walking distance tables are kept
     cached_metrics: Option<EntropyMetrics>,
+    last_metrics: Option<EntropyMetrics>, // Latest fresh metrics, of whichever board
+    metrics_task: Option<(Box<dyn MetricsTask>, u64)>, // Measuring the board at this version
     state_version: u64, // Increments on every state change
     auto_solve: Option<AutoSolveState>,
     solver_state: Option<SolverState>,
+    search_trace: Option<SearchTrace>, // Progress of the latest background solve, while tracing
+    auto_solve_error: Option<AutoSolveError>, // Auto-solve gave up since last taken
     last_solve_time_micros: u64, // Performance metric for last A* solve
+    last_solve_nodes: usize,     // Nodes expanded by last background solve
     last_solution_length: u32, // Actual solution length from last A* solve
     last_shuffle_result: Option<ShuffleResult>, // Track shuffle information
+    pending_shuffle: Option<PendingShuffle>,    // Shuffle running for the next game
+    recent_shuffles: VecDeque<PackedState>,     // Latest shuffled boards, oldest first
+    solver_config: SolverConfig, // Search limits for background solves
+    solver_kind: Option<SolverKind>, // Algorithm for background solves; None lets the dispatcher pick
+    dispatcher: SolverDispatcher,    // Picks each solve's algorithm while solver_kind is None
+    auto_solve_interval: Duration, // Time between auto-solve moves at normal speed
+    auto_solve_speed: f32,         // Playback speed multiplier
+    auto_solve_stop: Option<AssistStop>, // Where a partial auto-solve hands back control
+    solution_cache: SolutionCache, // Earlier solves, reused when a board comes back
+    clock: GameClock,
+    game_difficulty: Option<Difficulty>, // Difficulty of the running shuffled game
+    assisted: bool, // Auto-solve was used, so the time does not count
+    hints_used: usize, // Hints shown during the current game
+    game_mode: GameMode, // Rules for new shuffled games
+    challenge: GameMode, // Rules of the current game
+    challenge_failure: Option<ChallengeFailure>, // Why the current challenge was lost
+    best_times: BestTimes,
+    new_best: bool, // Last completed game set a best time
+    stats: Stats,
+    achievements: Achievements,
+    new_achievements: Vec<Achievement>, // Unlocked since the last take_new_achievements
+    initial_state: Option<PuzzleState>, // Board as shuffled, for optimal-length tracking
+    initial_optimal: Option<u32>,       // Optimal solution length of initial_state
+    par_task: Option<Box<dyn SolverTask>>, // IDA* solve of initial_state that finds initial_optimal
+    finished_game: Option<GameRecord>,  // Game completed since the last take_finished_game
+    analysis_task: Option<Box<dyn AnalysisTask>>, // Post-game analysis running in the background
+    analysis: Option<Result<GameAnalysis, SolverError>>, // Outcome of the last analysis of this game
+    distance_trail: Vec<u32>, // Heuristic distance at the start and after each player move
+    tile_labels: TileLabels, // What the tiles show, and so what counts as solved
+    goal: GoalSpec,          // Solved layout for new games
+    recording: Option<RelativePattern>, // Macro being recorded from the player's moves
+    pattern_catalog: RelativePatternCatalog, // Techniques offered as suggestions
 }
 
 impl GameController {
@@ -101,16 +440,57 @@ impl GameController {
     pub fn new(grid_size: usize) -> Result<Self, PuzzleError> {
         Ok(Self {
             state: PuzzleState::new(grid_size)?,
+            layers: LayeredPuzzle::new(grid_size, 1)?,
+            active_layer: 0,
             history: MoveHistory::new(),
             shuffle_controller: ShuffleController::new(grid_size)?,
-            entropy_calculator: Box::new(ManhattanDistance),
+            entropy_calculator: Arc::new(ManhattanDistance),
+            entropy_kind: Some(EntropyKind::Manhattan),
+            heuristic_cache: HeuristicCache::user(),
             cached_metrics: None,
+            last_metrics: None,
+            metrics_task: None,
             state_version: 0,
             auto_solve: None,
             solver_state: None,
+            search_trace: None,
+            auto_solve_error: None,
             last_solve_time_micros: 0,
+            last_solve_nodes: 0,
             last_solution_length: 0,
             last_shuffle_result: None,
+            pending_shuffle: None,
+            recent_shuffles: VecDeque::new(),
+            solver_config: SolverConfig::default(),
+            solver_kind: None,
+            dispatcher: SolverDispatcher::default(),
+            auto_solve_interval: DEFAULT_AUTO_SOLVE_INTERVAL,
+            auto_solve_speed: 1.0,
+            auto_solve_stop: None,
+            solution_cache: SolutionCache::default(),
+            clock: GameClock::new(),
+            game_difficulty: None,
+            assisted: false,
+            hints_used: 0,
+            game_mode: GameMode::Free,
+            challenge: GameMode::Free,
+            challenge_failure: None,
+            best_times: BestTimes::new(),
+            new_best: false,
+            stats: Stats::new(),
+            achievements: Achievements::new(),
+            new_achievements: Vec::new(),
+            initial_state: None,
+            initial_optimal: None,
+            par_task: None,
+            finished_game: None,
+            analysis_task: None,
+            analysis: None,
+            distance_trail: Vec::new(),
+            tile_labels: TileLabels::default(),
+            goal: GoalSpec::default(),
+            recording: None,
+            pattern_catalog: RelativePatternCatalog::builtin(),
         })
     }
 
@@ -121,6 +501,7 @@ impl GameController {
         // Clear solve time and length only if not auto-solving (manual moves invalidate it)
         if !self.is_auto_solving() {
             self.last_solve_time_micros = 0;
+            self.last_solve_nodes = 0;
             self.last_solution_length = 0;
         }
     }
@@ -132,7 +513,12 @@ impl GameController {
 
     /// Returns the current move count
     pub fn move_count(&self) -> usize {
-        self.history.move_count()
+        self.history.player_moves()
+    }
+
+    /// Returns the number of single-tile moves made (a chain move counts each tile)
+    pub fn tile_move_count(&self) -> usize {
+        self.history.tile_moves()
     }
 
     /// Returns information about the last shuffle operation
@@ -140,588 +526,3644 @@ impl GameController {
         self.last_shuffle_result.as_ref()
     }
 
-    /// Sets the entropy calculator to use
-    pub fn set_entropy_calculator(&mut self, calculator: Box<dyn EntropyCalculator>) {
-        self.entropy_calculator = calculator;
+    /// Returns what the tiles show
+    pub fn tile_labels(&self) -> &TileLabels {
+        &self.tile_labels
     }
 
-    /// Starts a new game with the specified difficulty
-    pub fn new_game(&mut self, difficulty: Difficulty) {
-        // Clean up any running background solver by signaling cancellation
-        if let Some(SolverState::Computing(_handle, cancel_flag, _)) = self.solver_state.take() {
-            cancel_flag.store(true, AtomicOrdering::Relaxed);
-            // Thread will check the flag and exit gracefully
+    /// Changes what the tiles show, relabeling the current board (and every layer)
+    /// in place
+    ///
+    /// Word and custom labels make tiles with equal labels interchangeable, so
+    /// the game is won once the board reads correctly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::LabelCount` if the labels do not fit the board; the
+    /// current labels are kept
+    pub fn set_tile_labels(&mut self, labels: TileLabels) -> Result<(), PuzzleError> {
+        self.state.relabel(&labels)?;
+        for index in 0..self.layers.layer_count() {
+            if let Some(layer) = self.layers.layer_mut(index) {
+                layer.relabel(&labels)?;
+            }
         }
-
-        // Size is guaranteed valid since controller was constructed successfully
-        self.state = PuzzleState::new(self.state.size()).expect("valid size");
-        self.history.reset();
-
-        // Use shuffle_with_result to track shuffle information
-        let shuffle_result = self.shuffle_controller.shuffle_with_result(
-            &mut self.state,
-            difficulty,
-            self.entropy_calculator.as_ref(),
-        );
-
-        self.last_shuffle_result = Some(shuffle_result);
-        self.invalidate_cache();
-        self.auto_solve = None;
-        self.solver_state = None;
-
-        // Start background computation for actual entropy (metrics only, not auto-solve)
-        self.start_background_solve_for_metrics();
+        self.tile_labels = labels;
+        Ok(())
     }
 
-    /// Starts background solver for metrics calculation only (not auto-solve)
-    fn start_background_solve_for_metrics(&mut self) {
-        // Don't compute if already solved or already computing
-        if self.state.is_solved() || self.solver_state.is_some() {
-            return;
-        }
-
-        println!("Computing actual solution length in background...");
-
-        // Clone the state to send to the thread
-        let state = self.state.clone();
-
-        // Create cancellation flag
-        let cancel_flag = Arc::new(AtomicBool::new(false));
-        let cancel_clone = cancel_flag.clone();
-
-        // Spawn solver in background thread
-        let handle = thread::spawn(move || {
-            let timer = PerformanceTimer::start();
-            let solver = AStarSolver::new();
-            let result = solver.solve_with_path_cancellable(&state, Some(cancel_clone));
-            let solve_time = timer.elapsed_micros();
+    /// Returns the solved layout new games are shuffled from
+    pub fn goal(&self) -> GoalSpec {
+        self.goal
+    }
 
-            result.map(|path| (path, solve_time))
-        });
+    /// Changes the solved layout and resets to a board solved that way
+    ///
+    /// Solvers and heuristics measure against each tile's home, so they aim for
+    /// the new goal; the strategic solver cannot finish spiral goals.
+    pub fn set_goal(&mut self, goal: GoalSpec) {
+        self.goal = goal;
+        self.reset();
+    }
 
-        self.solver_state = Some(SolverState::Computing(handle, cancel_flag, false)); // false = not for auto-solve
+    /// Returns a board of the current size solved for the current goal and showing
+    /// the current labels
+    fn solved_board(&self) -> Result<PuzzleState, PuzzleError> {
+        let mut state = PuzzleState::with_goal(self.state.size(), self.goal)?;
+        // Labels were checked against this size when they were set
+        state.relabel(&self.tile_labels)?;
+        Ok(state)
     }
 
-    /// Handles a player click at the given position
-    /// Returns the sequence of immediate moves if valid (for animation)
-    /// Returns None if invalid click or no move possible
-    /// NOTE: Does NOT apply the moves - presenter must apply them during animation
-    pub fn handle_click(&mut self, pos: Position) -> Option<Vec<Position>> {
-        // Check if there's a tile at the clicked position
-        if self.state.tile_at(pos).is_none() {
-            return None;
-        }
+    /// Sets the entropy calculator to use
+    pub fn set_entropy_calculator(&mut self, calculator: Box<dyn EntropyCalculator + Send + Sync>) {
+        self.entropy_calculator = Arc::from(calculator);
+        self.entropy_kind = None;
+    }
 
-        // Can't make manual moves during auto-solve
-        if self.is_auto_solving() {
-            return None;
-        }
+    /// Returns the built-in entropy calculator in use, or None after `set_entropy_calculator`
+    pub fn entropy_kind(&self) -> Option<EntropyKind> {
+        self.entropy_kind
+    }
 
-        // Get the chain move sequence (if valid)
-        let validator = MoveValidator::new(self.state.size()).expect("valid size");
-        if let Some(moves) = validator.resolve_chain_move(pos, self.state.empty_position()) {
-            // Return the sequence WITHOUT applying - presenter will apply during animation
-            println!("→ Manual move to {:?} ({} tiles will move)", pos, moves.len());
-            Some(moves)
-        } else {
-            None
-        }
+    /// Switches to one of the built-in entropy calculators
+    ///
+    /// It measures `current_entropy` and new shuffles, whose difficulty targets
+    /// come from its tuning table (see `target_range`). If the board later changes to a size the
+    /// calculator does not support, Manhattan distance takes over.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::SizeTooLarge` if `kind` does not support the current
+    /// grid; the calculator in use is kept
+    pub fn set_entropy_kind(&mut self, kind: EntropyKind) -> Result<(), PuzzleError> {
+        self.entropy_calculator = kind.build(self.state.size(), &self.heuristic_cache)?;
+        self.entropy_kind = Some(kind);
+        log::info!("Entropy is now measured by {}", kind);
+        Ok(())
     }
 
-    /// Applies a single immediate move (called by presenter after animation)
-    pub fn apply_move(&mut self, pos: Position) -> bool {
-        self.state.apply_immediate_move(pos)
+    /// Returns the entropy a shuffle at `difficulty` aims for on the current board,
+    /// as measured by the calculator in use
+    pub fn target_range(&self, difficulty: Difficulty) -> RangeInclusive<u32> {
+        self.entropy_calculator.target_range(difficulty, self.state.size())
     }
 
-    /// Completes a move sequence (called after all animations done)
-    pub fn complete_move_sequence(&mut self) {
-        self.history.record_move();
-        self.invalidate_cache();
-        println!("  Move complete (total moves: {})", self.move_count());
+    /// Sets where walking distance tables are loaded from and saved to
+    pub fn set_heuristic_cache(&mut self, cache: HeuristicCache) {
+        self.heuristic_cache = cache;
     }
 
-    /// Checks if the puzzle is solved
-    pub fn is_solved(&self) -> bool {
-        self.state.is_solved()
+    /// Starts a new game with the specified difficulty
+    ///
+    /// The board differs in at least a row's worth of cells from each of the
+    /// last `RECENT_SHUFFLES` shuffled boards, reshuffling with a seed drawn
+    /// from the previous one if needed, so the recorded seed still repeats it.
+    pub fn new_game(&mut self, difficulty: Difficulty) {
+        if let Err(e) = self.try_new_game(difficulty) {
+            log::error!("Cannot start a new game: {}", e);
+        }
     }
 
-    /// Returns the current entropy level (using primary calculator)
-    pub fn current_entropy(&self) -> u32 {
-        self.entropy_calculator.calculate(&self.state)
+    /// Starts a new game like `new_game`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if no solved board can be built to shuffle, e.g.
+    /// because the tile labels no longer fit it; the current game is left as is
+    pub fn try_new_game(&mut self, difficulty: Difficulty) -> Result<(), PuzzleError> {
+        self.start_shuffled_game(difficulty, thread_rng().gen(), DISTINCT_SHUFFLE_ATTEMPTS)
     }
 
-    /// Returns entropy calculated by all three methods with performance metrics
-    /// Results are cached and only recalculated when the puzzle state changes
-    pub fn all_entropy_metrics(&mut self) -> EntropyMetrics {
-        // Return cached metrics if available
-        if let Some(metrics) = self.cached_metrics {
-            return metrics;
+    /// Starts a new game shuffled from `seed`
+    ///
+    /// Controllers of the same grid size given the same seed and difficulty get
+    /// identical boards, e.g. for a two-player race, even if they resemble
+    /// recent games.
+    pub fn new_seeded_game(&mut self, difficulty: Difficulty, seed: u64) {
+        if let Err(e) = self.start_shuffled_game(difficulty, seed, 1) {
+            log::error!("Cannot start a seeded game: {}", e);
         }
+    }
 
-        // Calculate metrics
-        let mut perf = PerformanceMetrics::new();
+    /// Starts a new game like `new_game`, shuffling in a background task
+    ///
+    /// Hard shuffles of large grids take long enough to stall a frame. Until the
+    /// shuffle finishes the board stays solved, `is_shuffling` returns true, and
+    /// moves are refused; `update_shuffle_state` installs the shuffled board.
+    pub fn new_game_in_background(&mut self, difficulty: Difficulty) {
+        self.reset();
+        self.last_shuffle_result = None;
+        log::debug!("Shuffling a {0}×{0} board in the background", self.state.size());
+        self.spawn_shuffle(difficulty, thread_rng().gen(), 1);
+    }
 
-        // Time Manhattan Distance calculation
-        let timer = PerformanceTimer::start();
-        let manhattan = ManhattanDistance.calculate(&self.state);
-        perf.manhattan_time_micros = timer.elapsed_micros();
+    /// Starts shuffling the (solved) board in the background
+    fn spawn_shuffle(&mut self, difficulty: Difficulty, seed: u64, attempts: usize) {
+        self.pending_shuffle = Some(PendingShuffle {
+            task: spawn_shuffle_task(ShuffleJob {
+                state: self.state.clone(),
+                difficulty,
+                calculator: self.entropy_calculator.clone(),
+                seed,
+            }),
+            difficulty,
+            attempts,
+        });
+    }
 
-        // Time Shortest Path Heuristic calculation
-        let timer = PerformanceTimer::start();
-        let shortest_path = ShortestPathHeuristic.calculate(&self.state);
-        perf.heuristic_time_micros = timer.elapsed_micros();
+    /// Returns true while a background shuffle is preparing the next game
+    pub fn is_shuffling(&self) -> bool {
+        self.pending_shuffle.is_some()
+    }
 
-        // Only calculate actual solution for trivial puzzles (very low entropy)
-        // to avoid UI hangs. For harder puzzles, use Auto-Solve button.
-        let actual = if self.state.size() <= 3 && manhattan <= 5 {
-            let timer = PerformanceTimer::start();
-            let result = ActualSolutionLength::new().calculate(&self.state);
-            perf.actual_time_micros = timer.elapsed_micros();
-            result
-        } else if self.last_solution_length > 0 {
-            // Use cached solution from background thread
-            perf.actual_time_micros = self.last_solve_time_micros;
-            self.last_solution_length
-        } else {
-            // Not calculated yet
-            perf.actual_time_micros = 0;
-            999 // Placeholder for "not calculated"
+    /// Checks if the background shuffle has finished and starts its game
+    /// Should be called each frame to poll for completion
+    /// Returns true when the shuffled board has just been installed
+    pub fn update_shuffle_state(&mut self) -> bool {
+        let Some(mut pending) = self.pending_shuffle.take() else {
+            return false;
         };
+        match pending.task.poll() {
+            TaskStatus::Running => {
+                self.pending_shuffle = Some(pending);
+                false
+            }
+            TaskStatus::Finished((mut state, result)) => {
+                // Labels may have changed while the shuffle ran; the size has not
+                if let Err(e) = state.relabel(&self.tile_labels) {
+                    log::error!("Shuffled board cannot show the tile labels: {}", e);
+                    return false;
+                }
+                if pending.attempts < DISTINCT_SHUFFLE_ATTEMPTS && self.resembles_recent_shuffle(&state) {
+                    log::debug!("Shuffle resembles a recent game; shuffling again");
+                    self.spawn_shuffle(pending.difficulty, next_seed(result.seed), pending.attempts + 1);
+                    return false;
+                }
+                self.state = state;
+                self.begin_shuffled_game(pending.difficulty, result);
+                true
+            }
+            TaskStatus::Failed => {
+                log::error!("Shuffle task panicked");
+                false
+            }
+        }
+    }
 
-        let metrics = EntropyMetrics {
-            manhattan_distance: manhattan,
-            shortest_path_heuristic: shortest_path,
-            actual_solution_length: actual,
-            performance: perf,
-        };
+    /// Shuffles from `seed`, then from seeds drawn from it while the board
+    /// resembles a recent one, up to `attempts` shuffles in all
+    fn start_shuffled_game(&mut self, difficulty: Difficulty, seed: u64, attempts: usize) -> Result<(), PuzzleError> {
+        let board = self.solved_board()?;
+        self.cancel_background_solver();
+        self.pending_shuffle = None;
+        self.clear_layers();
+        self.history.reset();
 
-        // Cache the result
-        self.cached_metrics = Some(metrics);
+        // Keep the shuffle result, which records the seed so the board can be repeated
+        let calculator = self.entropy_calculator.as_ref();
+        let attempts = attempts.max(1);
+        let mut seed = seed;
+        for attempt in 1..=attempts {
+            self.state = board.clone();
+            let shuffle_result = self
+                .shuffle_controller
+                .shuffle_seeded(&mut self.state, difficulty, calculator, seed);
+            if attempt < attempts && self.resembles_recent_shuffle(&self.state) {
+                log::debug!("Shuffle resembles a recent game; shuffling again");
+                seed = next_seed(seed);
+                continue;
+            }
+            self.begin_shuffled_game(difficulty, shuffle_result);
+            break;
+        }
+        Ok(())
+    }
 
-        metrics
+    /// Returns true if `state` differs in fewer than a row's worth of cells from
+    /// one of the last `RECENT_SHUFFLES` shuffled boards
+    fn resembles_recent_shuffle(&self, state: &PuzzleState) -> bool {
+        let board = PackedState::from_state(state);
+        self.recent_shuffles
+            .iter()
+            .any(|recent| cells_apart(recent, &board) < board.size())
     }
 
-    /// Resets to a new solved puzzle
-    pub fn reset(&mut self) {
-        // Clean up any running background solver by signaling cancellation
-        if let Some(SolverState::Computing(_handle, cancel_flag, _)) = self.solver_state.take() {
-            cancel_flag.store(true, AtomicOrdering::Relaxed);
-            // Thread will check the flag and exit gracefully
+    /// Starts play on the freshly shuffled board
+    fn begin_shuffled_game(&mut self, difficulty: Difficulty, shuffle_result: ShuffleResult) {
+        self.last_shuffle_result = Some(shuffle_result);
+        if self.recent_shuffles.len() == RECENT_SHUFFLES {
+            self.recent_shuffles.pop_front();
         }
-
-        // Size is guaranteed valid since controller was constructed successfully
-        self.state = PuzzleState::new(self.state.size()).expect("valid size");
-        self.history.reset();
+        self.recent_shuffles.push_back(PackedState::from_state(&self.state));
         self.invalidate_cache();
         self.auto_solve = None;
-        self.solver_state = None;
+        self.clock.reset();
+        self.game_difficulty = Some(difficulty);
+        self.assisted = false;
+        self.hints_used = 0;
+        self.challenge = self.game_mode;
+        self.challenge_failure = None;
+        self.new_best = false;
+        self.initial_state = Some(self.state.clone());
+        self.initial_optimal = None;
+        self.finished_game = None;
+        self.clear_analysis();
+        self.start_distance_trail();
+
+        // Start background computation for actual entropy (metrics only, not auto-solve)
+        self.start_background_solve_for_metrics();
+        self.start_par_solve();
     }
 
-    /// Starts auto-solve mode, computing and animating the optimal solution
-    /// Spawns a background thread to compute the solution (non-blocking)
-    /// Returns true if computation started, false if already solved or computing
-    pub fn start_auto_solve(&mut self) -> bool {
-        if self.state.is_solved() {
-            return false;
+    /// Starts a game on `layer_count` stacked boards, each shuffled to `difficulty`
+    ///
+    /// Play starts on layer 0; `set_active_layer` switches boards, and the game is
+    /// solved once every layer is. Layered games end with a finished game like any
+    /// other, but their move totals are not comparable with single boards, so they
+    /// are not recorded in best times or statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidLayerCount` if `layer_count` is 0 or above `MAX_LAYERS`
+    pub fn new_layered_game(&mut self, difficulty: Difficulty, layer_count: usize) -> Result<(), PuzzleError> {
+        let mut layers = LayeredPuzzle::new(self.state.size(), layer_count)?;
+        for index in 1..layer_count {
+            let mut layer = self.solved_board()?;
+            self.shuffle_controller
+                .shuffle(&mut layer, difficulty, self.entropy_calculator.as_ref());
+            layers.swap_layer(index, &mut layer);
         }
 
-        // Check if we have a cached solution ready to use
-        if let Some(SolverState::Ready(path, solve_time)) = self.solver_state.take() {
-            println!("\n=== AUTO-SOLVE START (using cached solution) ===");
-            println!("Cached solution: {} moves", path.len());
-            println!("Original solve time: {}", PerformanceMetrics::format_duration(solve_time));
+        self.try_new_game(difficulty)?; // Shuffles layer 0 straight into the active board
+        self.layers = layers;
+        // Optimal length is tracked for one board, so it says nothing about the stack
+        self.initial_state = None;
+        self.cancel_par_solve();
+        self.challenge = GameMode::Free; // Challenges are played on a single board
+        Ok(())
+    }
 
-            self.auto_solve = Some(AutoSolveState::new(
-                path.clone(),
-                Duration::from_millis(700),
-            ));
+    /// Returns the number of layers (1 for an ordinary game)
+    pub fn layer_count(&self) -> usize {
+        self.layers.layer_count()
+    }
 
-            // Put the cached solution back for future use
-            self.solver_state = Some(SolverState::Ready(path, solve_time));
-            return true;
-        }
+    /// Returns the index of the layer being played
+    pub fn active_layer(&self) -> usize {
+        self.active_layer
+    }
 
-        // Clear failed state from previous attempts
-        if matches!(self.solver_state, Some(SolverState::Failed)) {
-            self.solver_state = None;
+    /// Returns true if the layer at `index` is solved
+    pub fn layer_solved(&self, index: usize) -> bool {
+        let solved = |state: &PuzzleState| {
+            if self.tile_labels.matches_by_label() {
+                state.shows_goal()
+            } else {
+                state.is_solved()
+            }
+        };
+        if index == self.active_layer {
+            solved(&self.state)
+        } else {
+            self.layers.layer(index).is_some_and(solved)
         }
+    }
 
-        // Already computing or running
-        if self.solver_state.is_some() || self.auto_solve.is_some() {
+    /// Switches play to another layer, leaving the current one as it is
+    ///
+    /// Stops any auto-solve and discards solver results for the old layer.
+    /// Returns false if `index` is out of range or already active.
+    pub fn set_active_layer(&mut self, index: usize) -> bool {
+        if index == self.active_layer || index >= self.layers.layer_count() {
             return false;
         }
 
-        println!("\n=== AUTO-SOLVE START ===");
-        println!("Current puzzle state entropy (Manhattan): {}", self.current_entropy());
-        println!("Move count: {}", self.move_count());
-        println!("Spawning solver thread (may take up to 60 seconds)...");
+        self.cancel_background_solver();
+        self.solver_state = None;
+        self.auto_solve = None;
 
-        // Clone the state to send to the thread
-        let state = self.state.clone();
+        // Park the current board, then check out the requested one
+        self.layers.swap_layer(self.active_layer, &mut self.state);
+        self.layers.swap_layer(index, &mut self.state);
+        self.active_layer = index;
 
-        // Create cancellation flag
-        let cancel_flag = Arc::new(AtomicBool::new(false));
-        let cancel_clone = cancel_flag.clone();
+        self.invalidate_cache();
+        self.start_background_solve_for_metrics();
+        true
+    }
 
-        // Spawn solver in background thread
-        let handle = thread::spawn(move || {
-            let timer = PerformanceTimer::start();
-            let solver = AStarSolver::new();
-            let result = solver.solve_with_path_cancellable(&state, Some(cancel_clone));
-            let solve_time = timer.elapsed_micros();
+    /// Drops back to a single layer holding the current board
+    fn clear_layers(&mut self) {
+        self.layers = LayeredPuzzle::new(self.state.size(), 1).expect("valid size");
+        self.active_layer = 0;
+    }
 
-            result.map(|path| (path, solve_time))
-        });
+    /// Starts a new game on a uniformly random solvable board
+    ///
+    /// Random boards have no difficulty, so like custom boards they are not
+    /// recorded in best times or statistics.
+    pub fn new_random_game(&mut self) {
+        let mut state = match self.solved_board() {
+            Ok(state) => state,
+            Err(e) => {
+                log::error!("Cannot start a random game: {}", e);
+                return;
+            }
+        };
+        self.shuffle_controller.shuffle_uniform(&mut state);
+        // Same size as the current board, so the shuffle controller is reused
+        if let Err(e) = self.load_state(state) {
+            log::error!("Cannot start a random game: {}", e);
+        }
+    }
+
+    /// Starts a practice game with only `region` scrambled and the rest solved
+    ///
+    /// Drills a part of the solve, such as the last two rows. Practice boards
+    /// are partly solved by construction, so like custom boards they are not
+    /// recorded in best times or statistics; par is still found as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidRegion` if the region cannot be scrambled on
+    /// this board, e.g. because the goal's empty cell lies outside it
+    pub fn new_practice_game(&mut self, region: Region, difficulty: Difficulty) -> Result<(), PuzzleError> {
+        let mut state = self.solved_board()?;
+        let result =
+            self.shuffle_controller
+                .shuffle_region(&mut state, region, difficulty, self.entropy_calculator.as_ref())?;
+        // Same size as the current board, so the shuffle controller is reused
+        self.load_state(state)?;
+        self.last_shuffle_result = Some(result);
+        Ok(())
+    }
+
+    /// Replaces the board with an externally supplied puzzle (e.g. from `PuzzleState::from_layout`)
+    ///
+    /// The grid may differ in size from the current one. Custom boards have no
+    /// difficulty, so they are not recorded in best times or statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if the shuffle controller cannot be built for the new size
+    pub fn load_state(&mut self, state: PuzzleState) -> Result<(), PuzzleError> {
+        self.cancel_background_solver();
+
+        if state.size() != self.state.size() {
+            self.shuffle_controller = ShuffleController::new(state.size())?;
+            // Size-specific calculators are rebuilt for the new board
+            if let Some(kind) = self.entropy_kind {
+                match kind.build(state.size(), &self.heuristic_cache) {
+                    Ok(calculator) => self.entropy_calculator = calculator,
+                    Err(e) => {
+                        log::info!("{} is not available ({}); using Manhattan distance", kind, e);
+                        self.entropy_calculator = Arc::new(ManhattanDistance);
+                        self.entropy_kind = Some(EntropyKind::Manhattan);
+                    }
+                }
+            }
+        }
+
+        self.pending_shuffle = None;
+        self.state = state;
+        if self.state.relabel(&self.tile_labels).is_err() {
+            log::info!("Tile labels do not fit a {0}×{0} board; showing numbers", self.state.size());
+            self.tile_labels = TileLabels::Numbers;
+            self.state.relabel(&self.tile_labels).expect("numbers fit any board");
+        }
+        self.clear_layers();
+        self.history.reset();
+        self.last_shuffle_result = None;
+        self.invalidate_cache();
+        self.auto_solve = None;
+        self.clock.reset();
+        self.game_difficulty = None;
+        self.assisted = false;
+        self.hints_used = 0;
+        self.challenge = GameMode::Free;
+        self.challenge_failure = None;
+        self.new_best = false;
+        self.initial_state = Some(self.state.clone());
+        self.initial_optimal = None;
+        self.finished_game = None;
+        self.clear_analysis();
+        self.start_distance_trail();
+
+        self.start_background_solve_for_metrics();
+        self.start_par_solve();
+        Ok(())
+    }
+
+    /// Captures the game in progress for crash recovery
+    ///
+    /// Returns None when there is nothing worth restoring: the board is solved or
+    /// untouched, the next board is still being shuffled, or the game is layered.
+    pub fn snapshot(&self) -> Option<SessionSnapshot> {
+        if self.state.is_solved()
+            || self.is_shuffling()
+            || self.layer_count() > 1
+            || self.history.tile_moves() == 0
+            || self.challenge_failure.is_some()
+        {
+            return None;
+        }
+        Some(SessionSnapshot {
+            layout: self.state.to_layout(),
+            goal: self.state.goal(),
+            initial_layout: self.initial_state.as_ref().map(PuzzleState::to_layout),
+            initial_optimal: self.initial_optimal,
+            difficulty: self.game_difficulty,
+            moves: self.history.player_moves(),
+            tile_moves: self.history.tile_moves(),
+            time_millis: self.clock.elapsed().as_millis() as u64,
+            assisted: self.assisted,
+            hints: self.hints_used,
+            mode: self.challenge,
+        })
+    }
+
+    /// Resumes a game captured by `snapshot`
+    ///
+    /// The board may differ in size from the current one. The clock holds the
+    /// saved time and runs again from the next move, and a restored shuffled game
+    /// still counts towards best times and statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if a saved layout is not a valid board
+    pub fn restore(&mut self, snapshot: &SessionSnapshot) -> Result<(), PuzzleError> {
+        let state = PuzzleState::from_layout_with_goal(&snapshot.layout, snapshot.goal)?;
+        let initial = snapshot
+            .initial_layout
+            .as_ref()
+            .map(|layout| PuzzleState::from_layout_with_goal(layout, snapshot.goal))
+            .transpose()?;
+
+        self.goal = snapshot.goal;
+        self.load_state(state)?;
+        if let Some(mut initial) = initial {
+            // Labels were already checked against this size by load_state
+            if initial.relabel(&self.tile_labels).is_ok() {
+                self.initial_state = Some(initial);
+            }
+        }
+        self.initial_optimal = snapshot.initial_optimal;
+        self.start_par_solve();
+        self.game_difficulty = snapshot.difficulty;
+        self.assisted = snapshot.assisted;
+        self.hints_used = snapshot.hints;
+        self.challenge = snapshot.mode;
+        self.history.player_moves = snapshot.moves;
+        self.history.tile_moves = snapshot.tile_moves;
+        self.clock.accumulated = snapshot.time();
+        log::info!(
+            "Restored a {0}×{0} game after {1} moves",
+            self.state.size(),
+            snapshot.moves
+        );
+        Ok(())
+    }
+
+    /// Starts background solver for metrics calculation only (not auto-solve)
+    fn start_background_solve_for_metrics(&mut self) {
+        // Don't compute if already solved or already computing
+        if self.state.is_solved() || self.solver_state.is_some() {
+            return;
+        }
+
+        if self.restore_cached_solution() {
+            return;
+        }
+        log::debug!("Computing actual solution length in background");
+
+        self.solver_state = Some(self.spawn_solver(self.solver_choice(), false)); // false = not for auto-solve
+    }
+
+    /// Starts finding par for the board as shuffled
+    ///
+    /// Par always comes from IDA*, which is optimal whatever solver is selected
+    /// and reads 3×3 boards from the endgame table. Boards the dispatcher would
+    /// not give to IDA* (larger grids, heavily scrambled 5×5 boards) get no par.
+    /// The search runs on `PAR_THREADS` threads, leaving the other cores to play.
+    fn start_par_solve(&mut self) {
+        self.cancel_par_solve();
+        let Some(ref initial) = self.initial_state else {
+            return;
+        };
+        let size = initial.size();
+        let too_hard = size > self.dispatcher.ida_star_max_size
+            || (size > self.dispatcher.astar_max_size
+                && initial.manhattan_distance() > self.dispatcher.ida_star_max_distance);
+        if self.initial_optimal.is_some() || initial.is_solved() || too_hard {
+            return;
+        }
+        let config = SolverConfig {
+            threads: Some(PAR_THREADS),
+            ..self.dispatcher.with_kind(SolverKind::IdaStar, self.solver_config).config
+        };
+        self.par_task = Some(spawn_solver_task(SolverKind::IdaStar, config, initial.clone()));
+    }
+
+    /// Stops any running search for par
+    fn cancel_par_solve(&mut self) {
+        if let Some(task) = self.par_task.take() {
+            task.cancel();
+        }
+    }
+
+    /// Records par once the background IDA* solve of the shuffled board finishes
+    fn update_par(&mut self) {
+        let Some(mut task) = self.par_task.take() else {
+            return;
+        };
+        match task.poll() {
+            TaskStatus::Running => self.par_task = Some(task),
+            TaskStatus::Finished(Ok(solution)) => {
+                if self.initial_state.as_ref().is_some_and(|initial| path_solves(initial, &solution.path)) {
+                    log::debug!("Par is {} moves", solution.len());
+                    self.initial_optimal = Some(solution.len() as u32);
+                }
+            }
+            TaskStatus::Finished(Err(e)) => log::info!("No par for this board: {}", e),
+            TaskStatus::Failed => log::error!("Par solver task panicked"),
+        }
+    }
+
+    /// Returns the solver and limits for the current board
+    fn solver_choice(&self) -> SolverChoice {
+        match self.solver_kind {
+            Some(kind) => self.choice_of(kind),
+            None => self.dispatcher.choose(&self.state, self.solver_config),
+        }
+    }
+
+    /// Returns `kind` with the limits it runs under: the dispatcher's timeout
+    /// for it while choosing automatically, the solver config otherwise
+    fn choice_of(&self, kind: SolverKind) -> SolverChoice {
+        match self.solver_kind {
+            Some(_) => SolverChoice {
+                kind,
+                config: self.solver_config,
+            },
+            None => self.dispatcher.with_kind(kind, self.solver_config),
+        }
+    }
+
+    /// Starts a background solve of the current state
+    fn spawn_solver(&self, choice: SolverChoice, for_autosolve: bool) -> SolverState {
+        let SolverChoice { kind, config } = choice;
+        SolverState::Computing {
+            task: spawn_solver_task(kind, config, self.state.clone()),
+            kind,
+            for_autosolve,
+            version: self.state_version,
+            start: self.state.clone(),
+        }
+    }
+
+    /// Returns the tile that would slide in `direction` into the empty cell
+    /// Returns None if the empty cell is on that edge of the board
+    pub fn tile_for_slide(&self, direction: Direction) -> Option<Position> {
+        // The tile comes from the opposite side of the empty cell
+        let (dr, dc) = direction.delta();
+        let (row, col) = self.state.empty_position();
+        let tile = (row.checked_add_signed(-dr)?, col.checked_add_signed(-dc)?);
+        (tile.0 < self.state.size() && tile.1 < self.state.size()).then_some(tile)
+    }
+
+    /// Returns the tile a scroll over `pos` would slide in `direction`
+    ///
+    /// Vertical scrolls slide the empty cell's column and horizontal scrolls its
+    /// row, so `pos` must share that column or row. Returns None otherwise, or if
+    /// no tile can slide that way (see `tile_for_slide`).
+    pub fn tile_for_scroll(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let (row, col) = self.state.empty_position();
+        let in_line = match direction {
+            Direction::Up | Direction::Down => pos.1 == col,
+            Direction::Left | Direction::Right => pos.0 == row,
+        };
+        if !in_line || pos.0 >= self.state.size() || pos.1 >= self.state.size() {
+            return None;
+        }
+        self.tile_for_slide(direction)
+    }
+
+    /// Handles a player click at the given position
+    /// Returns the sequence of immediate moves if valid (for animation)
+    /// Returns None if invalid click or no move possible
+    /// NOTE: Does NOT apply the moves - presenter must apply them during animation
+    pub fn handle_click(&mut self, pos: Position) -> Option<Vec<Position>> {
+        self.try_handle_click(pos).ok()
+    }
+
+    /// Handles a player click like `handle_click`, saying why a click is refused
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::OutOfBounds` for a click off the board,
+    /// `PuzzleError::TileNotFound` for the empty cell, `PuzzleError::AutoSolving`
+    /// or `PuzzleError::Shuffling` while moves are locked, and
+    /// `PuzzleError::InvalidMove` for a tile out of line with the empty cell
+    pub fn try_handle_click(&mut self, pos: Position) -> Result<Vec<Position>, PuzzleError> {
+        self.check_tile(pos)?;
+        self.check_unlocked()?;
+
+        // Get the chain move sequence (if valid)
+        let validator = MoveValidator::new(self.state.size())?;
+        let moves = validator
+            .resolve_chain_move(pos, self.state.empty_position())
+            .ok_or(PuzzleError::InvalidMove { position: pos })?;
+        // Return the sequence WITHOUT applying - presenter will apply during animation
+        log::trace!("Manual move to {:?} ({} tiles will move)", pos, moves.len());
+        self.clock.start();
+        Ok(moves)
+    }
+
+    /// Checks that `pos` holds a tile
+    fn check_tile(&self, pos: Position) -> Result<(), PuzzleError> {
+        let size = self.state.size();
+        if pos.0 >= size || pos.1 >= size {
+            return Err(PuzzleError::OutOfBounds { position: pos, size });
+        }
+        match self.state.tile_at(pos) {
+            Some(_) => Ok(()),
+            None => Err(PuzzleError::TileNotFound { position: pos }),
+        }
+    }
+
+    /// Returns the tiles a click at `pos` would move, without moving them
+    /// Returns None wherever `handle_click` would reject the click
+    pub fn preview_move(&self, pos: Position) -> Option<MovePreview> {
+        self.state.tile_at(pos)?;
+        if self.moves_locked() {
+            return None;
+        }
+        let validator = MoveValidator::new(self.state.size()).ok()?;
+        validator.preview_chain_move(pos, self.state.empty_position())
+    }
+
+    /// Returns true while manual moves are refused: during auto-solve, a
+    /// background shuffle, or after a lost challenge
+    fn moves_locked(&self) -> bool {
+        self.check_unlocked().is_err()
+    }
+
+    /// Says why manual moves are refused, if they are
+    fn check_unlocked(&self) -> Result<(), PuzzleError> {
+        // Can't make manual moves during auto-solve or while the next board is shuffled
+        if self.is_auto_solving() {
+            return Err(PuzzleError::AutoSolving);
+        }
+        if self.is_shuffling() {
+            return Err(PuzzleError::Shuffling);
+        }
+        if self.challenge_failure.is_some() {
+            return Err(PuzzleError::ChallengeLost);
+        }
+        Ok(())
+    }
+
+    /// Applies a single immediate move (called by presenter after animation)
+    pub fn apply_move(&mut self, pos: Position) -> bool {
+        self.try_apply_move(pos).is_ok()
+    }
 
-        self.solver_state = Some(SolverState::Computing(handle, cancel_flag, true)); // true = for auto-solve
+    /// Applies a single immediate move like `apply_move`, saying why a move is refused
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::OutOfBounds` for a position off the board,
+    /// `PuzzleError::TileNotFound` for the empty cell, `PuzzleError::AutoSolving`,
+    /// `PuzzleError::Shuffling` or `PuzzleError::ChallengeLost` while moves are
+    /// locked, and `PuzzleError::InvalidMove` for a tile not next to the empty cell
+    pub fn try_apply_move(&mut self, pos: Position) -> Result<(), PuzzleError> {
+        self.check_tile(pos)?;
+        self.check_unlocked()?;
+        let empty = self.state.empty_position();
+        if !self.state.apply_immediate_move(pos) {
+            return Err(PuzzleError::InvalidMove { position: pos });
+        }
+        if let Some(ref mut recording) = self.recording {
+            recording.push_move(pos, empty);
+        }
+        self.history.record_slide(pos);
+        self.state_version += 1; // Mid-chain boards must not match cached solutions
+        Ok(())
+    }
+
+    /// Starts recording the player's moves as a macro
+    /// Returns false if a recording is already running
+    pub fn start_recording(&mut self) -> bool {
+        if self.recording.is_some() {
+            return false;
+        }
+        self.recording = Some(RelativePattern::new("", Vec::new()));
         true
     }
 
-    /// Checks if solver thread has completed and transitions state
-    /// Should be called each frame to poll for completion
-    /// Returns true if solution is ready to start executing
-    pub fn update_solver_state(&mut self) -> bool {
-        let state = self.solver_state.take();
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
 
-        match state {
-            Some(SolverState::Computing(handle, cancel_flag, is_for_autosolve)) => {
-                // Check if thread is done (non-blocking)
-                if handle.is_finished() {
-                    match handle.join() {
-                        Ok(Some((path, solve_time))) => {
-                            println!("✓ A* calculated solution path with {} moves", path.len());
-                            if is_for_autosolve {
-                                println!("First 5 moves: {:?}", &path[..path.len().min(5)]);
-                            }
-                            println!("Solve time: {}", PerformanceMetrics::format_duration(solve_time));
-
-                            // Store solve time and solution length for metrics display
-                            self.last_solve_time_micros = solve_time;
-                            self.last_solution_length = path.len() as u32;
+    /// Returns the number of single-tile moves recorded so far
+    pub fn recorded_moves(&self) -> usize {
+        self.recording.as_ref().map_or(0, RelativePattern::len)
+    }
 
-                            // Invalidate cache so GUI shows updated metrics
-                            self.cached_metrics = None;
-
-                            // Only transition to auto-solve animation if this was for auto-solve
-                            if is_for_autosolve {
-                                self.auto_solve = Some(AutoSolveState::new(
-                                    path.clone(),
-                                    Duration::from_millis(700), // 0.7 seconds per move
-                                ));
-                                // Cache the solution for reuse
-                                self.solver_state = Some(SolverState::Ready(path, solve_time));
-                                return true;
-                            } else {
-                                // Cache for later use
-                                self.solver_state = Some(SolverState::Ready(path, solve_time));
-                            }
-                        }
-                        Ok(None) => {
-                            println!("✗ A* failed to find solution!");
-                            self.solver_state = Some(SolverState::Failed);
-                        }
-                        Err(_) => {
-                            println!("✗ Solver thread panicked!");
-                            self.solver_state = Some(SolverState::Failed);
-                        }
+    /// Stops recording and returns the moves as a macro called `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidMacro` if nothing was being recorded, no
+    /// moves were made, or the name is blank. A blank name keeps the recording
+    /// running so it can be saved again under a proper name.
+    pub fn finish_recording(&mut self, name: &str) -> Result<RelativePattern, PuzzleError> {
+        let name = name.trim();
+        if name.is_empty() && self.recording.is_some() {
+            return Err(PuzzleError::InvalidMacro("it needs a name".to_string()));
+        }
+        let mut pattern = self
+            .recording
+            .take()
+            .ok_or_else(|| PuzzleError::InvalidMacro("nothing is being recorded".to_string()))?;
+        if pattern.is_empty() {
+            return Err(PuzzleError::InvalidMacro("no moves were recorded".to_string()));
+        }
+        pattern.set_name(name);
+        Ok(pattern)
+    }
+
+    /// Stops recording and discards the recorded moves
+    pub fn cancel_recording(&mut self) {
+        self.recording = None;
+    }
+
+    /// Returns the immediate moves `pattern` would make from the current empty cell
+    /// Returns None if it does not fit the board here, or while manual moves are refused
+    pub fn macro_moves(&self, pattern: &RelativePattern) -> Option<Vec<Position>> {
+        if self.moves_locked() {
+            return None;
+        }
+        pattern.positions_at(&self.state)
+    }
+
+    /// Starts playing a macro from the current empty cell
+    /// Returns the sequence of immediate moves for the presenter to animate and
+    /// apply, as `handle_click` does; the whole macro counts as one player move.
+    /// Returns None if the macro cannot be played here.
+    pub fn play_macro(&mut self, pattern: &RelativePattern) -> Option<Vec<Position>> {
+        let moves = self.macro_moves(pattern).filter(|moves| !moves.is_empty())?;
+        log::trace!("Playing macro '{}' ({} tiles will move)", pattern.name(), moves.len());
+        self.clock.start();
+        Some(moves)
+    }
+
+    /// Returns the techniques offered by `technique_suggestions`
+    pub fn pattern_catalog(&self) -> &RelativePatternCatalog {
+        &self.pattern_catalog
+    }
+
+    pub fn set_pattern_catalog(&mut self, catalog: RelativePatternCatalog) {
+        self.pattern_catalog = catalog;
+    }
+
+    /// Returns the catalog techniques that can be played from the current empty cell
+    ///
+    /// Each technique appears once, in catalog order, turned to whichever of its
+    /// fitting orientations lowers the Manhattan distance most. Play one with
+    /// `play_macro`. Returns nothing while manual moves are refused.
+    pub fn technique_suggestions(&self) -> Vec<TechniqueSuggestion> {
+        if self.moves_locked() {
+            return Vec::new();
+        }
+        let before = ManhattanDistance.calculate(&self.state) as i64;
+        let mut suggestions: Vec<TechniqueSuggestion> = Vec::new();
+        for (pattern, moves) in self.pattern_catalog.applicable(&self.state) {
+            let mut after = self.state.clone();
+            for &pos in &moves {
+                after.apply_immediate_move(pos);
+            }
+            let distance_change = ManhattanDistance.calculate(&after) as i64 - before;
+            match suggestions.iter_mut().find(|s| s.pattern.name() == pattern.name()) {
+                Some(best) if best.distance_change <= distance_change => {}
+                Some(best) => {
+                    *best = TechniqueSuggestion {
+                        pattern,
+                        moves,
+                        distance_change,
                     }
-                } else {
-                    // Still computing, put it back
-                    self.solver_state = Some(SolverState::Computing(handle, cancel_flag, is_for_autosolve));
                 }
+                None => suggestions.push(TechniqueSuggestion {
+                    pattern,
+                    moves,
+                    distance_change,
+                }),
             }
-            Some(other) => {
-                // Put back non-computing states
-                self.solver_state = Some(other);
+        }
+        suggestions
+    }
+
+    /// Completes a move sequence (called after all animations done)
+    pub fn complete_move_sequence(&mut self) {
+        self.history.record_move();
+        self.invalidate_cache();
+        self.record_distance();
+        log::trace!("Move complete (total moves: {})", self.move_count());
+        if self.is_solved() {
+            self.finish_game();
+        } else {
+            self.update_challenge();
+        }
+    }
+
+    /// Stops the clock and records the finished shuffled game
+    /// Only unassisted single-board games are eligible for best times
+    fn finish_game(&mut self) {
+        self.clock.pause();
+        if let Some(difficulty) = self.game_difficulty.take() {
+            let size = self.state.size();
+            let time = self.clock.elapsed();
+            let layered = self.layers.layer_count() > 1;
+            if !self.assisted && !layered {
+                self.new_best = self.best_times.record(size, difficulty, time);
             }
-            None => {}
+            let record = self.game_record(difficulty);
+            if !layered {
+                self.stats.record(record.clone());
+                let unlocked = self.achievements.record(&record, self.stats.games_played());
+                self.new_achievements.extend(unlocked);
+            }
+            self.finished_game = Some(record);
         }
+    }
 
-        false
+    /// Describes the current game for the statistics
+    fn game_record(&self, difficulty: Difficulty) -> GameRecord {
+        let mut record = GameRecord::new(
+            self.state.size(),
+            difficulty,
+            self.move_count(),
+            self.tile_move_count(),
+            self.clock.elapsed(),
+            self.initial_optimal,
+            self.assisted,
+        );
+        record.hints = self.hints_used;
+        record.mode = self.challenge;
+        record.failed = self.challenge_failure.is_some();
+        record
     }
 
-    /// Returns true if solver is currently computing in background
-    pub fn is_solver_computing(&self) -> bool {
-        matches!(self.solver_state, Some(SolverState::Computing(_, _, _)))
+    /// Returns the rules new shuffled games are played under
+    pub fn game_mode(&self) -> GameMode {
+        self.game_mode
+    }
+
+    /// Sets the rules for shuffled games started from now on
+    /// The current game keeps the rules it started with.
+    pub fn set_game_mode(&mut self, mode: GameMode) {
+        self.game_mode = mode;
+    }
+
+    /// Returns the rules of the current game
+    pub fn challenge(&self) -> GameMode {
+        self.challenge
+    }
+
+    /// Returns why the current challenge was lost, or None while it can still be won
+    pub fn challenge_failure(&self) -> Option<ChallengeFailure> {
+        self.challenge_failure
+    }
+
+    /// Returns the tile moves left before the move limit, once par is known
+    pub fn moves_remaining(&self) -> Option<usize> {
+        let limit = self.challenge.move_limit(self.initial_optimal)?;
+        Some(limit.saturating_sub(self.tile_move_count()))
+    }
+
+    /// Returns the time left on the countdown
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let limit = self.challenge.time_limit()?;
+        Some(limit.saturating_sub(self.clock.elapsed()))
+    }
+
+    /// Checks the current challenge's limits, ending the game if one was reached
+    ///
+    /// Called after every move, and should be called each frame so a countdown
+    /// (or a move limit whose par arrived late) ends the game on time. A lost
+    /// game stops the clock, refuses further moves, and is recorded in the
+    /// statistics as failed. Returns the failure when the game has just been lost.
+    pub fn update_challenge(&mut self) -> Option<ChallengeFailure> {
+        if self.challenge_failure.is_some() || self.game_difficulty.is_none() || self.is_solved() {
+            return None;
+        }
+        let failure = self
+            .challenge
+            .check(self.tile_move_count(), self.initial_optimal, self.clock.elapsed())?;
+        log::info!("Challenge lost: {}", failure);
+        self.clock.pause();
+        self.challenge_failure = Some(failure);
+        self.stop_auto_solve();
+        if let Some(difficulty) = self.game_difficulty.take() {
+            let record = self.game_record(difficulty);
+            self.stats.record(record);
+        }
+        Some(failure)
+    }
+
+    /// Returns the shuffled game completed since the last call, if any
+    /// Lets the view react once to a win (e.g. with a victory screen)
+    pub fn take_finished_game(&mut self) -> Option<GameRecord> {
+        self.finished_game.take()
+    }
+
+    /// Returns the achievements unlocked since the last call, oldest first
+    /// Lets the view announce each unlock once
+    pub fn take_new_achievements(&mut self) -> Vec<Achievement> {
+        std::mem::take(&mut self.new_achievements)
+    }
+
+    /// Returns the number of hints shown during the current game
+    pub fn hints_used(&self) -> usize {
+        self.hints_used
+    }
+
+    /// Returns whether `start_analysis` would start
+    ///
+    /// The board must be solved, with every move since the start recorded: games
+    /// restored after a crash and layered games cannot be analyzed.
+    pub fn can_analyze(&self) -> bool {
+        if self.analysis_task.is_some() || self.analysis.is_some() {
+            return false;
+        }
+        self.replay().is_some()
+    }
+
+    /// Returns the starting board and every tile slide of the solved game, in order
+    ///
+    /// Auto-solved moves are included. Returns None unless the board is solved and
+    /// the recorded slides lead from the start to it, as for `can_analyze`.
+    pub fn replay(&self) -> Option<(&PuzzleState, Vec<Position>)> {
+        if !self.state.is_solved() || self.layer_count() > 1 {
+            return None;
+        }
+        let initial = self.initial_state.as_ref()?;
+        let slides: Vec<Position> = self.history.moves().iter().flatten().copied().collect();
+        if slides.is_empty() {
+            return None;
+        }
+        let mut replay = initial.clone();
+        let replayed = slides.iter().all(|&pos| replay.apply_immediate_move(pos));
+        (replayed && replay.to_layout() == self.state.to_layout()).then_some((initial, slides))
+    }
+
+    /// Starts rating each move of the solved game against optimal play
+    ///
+    /// The moves are replayed in the background from the starting board with
+    /// IDA*, whatever solver is selected, since only an optimal solver rates moves
+    /// fairly; 3×3 boards are read from the endgame table. Poll with
+    /// `update_analysis`. Returns false where `can_analyze` is false.
+    pub fn start_analysis(&mut self) -> bool {
+        if !self.can_analyze() {
+            return false;
+        }
+        let Some(initial) = self.initial_state.clone() else {
+            return false;
+        };
+        let kind = SolverKind::IdaStar;
+        log::info!("Analyzing {} moves with {}", self.history.moves().len(), kind);
+        self.analysis_task = Some(spawn_analysis_task(AnalysisJob {
+            initial,
+            moves: self.history.moves().to_vec(),
+            kind,
+            config: self.solver_config,
+        }));
+        true
+    }
+
+    /// Checks whether the background analysis has finished
+    /// Returns true on the call that finds it done
+    pub fn update_analysis(&mut self) -> bool {
+        let Some(mut task) = self.analysis_task.take() else {
+            return false;
+        };
+        let result = match task.poll() {
+            TaskStatus::Running => {
+                self.analysis_task = Some(task);
+                return false;
+            }
+            TaskStatus::Finished(result) => result,
+            TaskStatus::Failed => Err(SolverError::Crashed),
+        };
+        match result {
+            Ok(ref analysis) => log::info!(
+                "Analysis done: {} optimal, {} tile moves wasted",
+                analysis.count(MoveQuality::Optimal),
+                analysis.wasted_moves()
+            ),
+            Err(ref e) => log::warn!("Analysis failed: {}", e),
+        }
+        self.analysis = Some(result);
+        true
+    }
+
+    pub fn is_analyzing(&self) -> bool {
+        self.analysis_task.is_some()
+    }
+
+    /// Returns the outcome of the finished analysis, or None if none has finished
+    pub fn analysis(&self) -> Option<Result<&GameAnalysis, &SolverError>> {
+        self.analysis.as_ref().map(Result::as_ref)
+    }
+
+    /// Cancels a running analysis and forgets the last one
+    fn clear_analysis(&mut self) {
+        if let Some(task) = self.analysis_task.take() {
+            task.cancel();
+        }
+        self.analysis = None;
+    }
+
+    /// Returns the heuristic distance to the goal at the start of the game and
+    /// after each player move since, for charting progress
+    ///
+    /// Distances use the shortest-path heuristic (Manhattan distance plus linear
+    /// conflicts) and are computed once per move, so reading them is free.
+    pub fn distance_trail(&self) -> &[u32] {
+        &self.distance_trail
+    }
+
+    /// Restarts the distance trail from the current board
+    fn start_distance_trail(&mut self) {
+        self.distance_trail.clear();
+        self.record_distance();
+    }
+
+    fn record_distance(&mut self) {
+        self.distance_trail.push(ShortestPathHeuristic.calculate(&self.state));
+    }
+
+    /// Returns the board as it was when the current game started
+    /// Returns None after a reset
+    pub fn initial_state(&self) -> Option<&PuzzleState> {
+        self.initial_state.as_ref()
+    }
+
+    /// Returns the completed-game statistics
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Replaces the statistics store (e.g. with one loaded from disk)
+    pub fn set_stats(&mut self, stats: Stats) {
+        self.stats = stats;
+    }
+
+    /// Returns the shuffle target adaptive difficulty picks for the next game on
+    /// this grid size, from the player's recent results
+    pub fn adaptive_difficulty(&self) -> AdaptiveDifficulty {
+        AdaptiveDifficulty::from_stats(&self.stats, self.state.size())
+    }
+
+    /// Returns the optimal solution length of the board as it was shuffled
+    /// Returns None until the background solver has finished
+    pub fn initial_optimal_length(&self) -> Option<u32> {
+        self.initial_optimal
+    }
+
+    /// Returns true while the background solve that finds par (the initial optimal length) runs
+    pub fn is_par_pending(&self) -> bool {
+        self.par_task.is_some()
+    }
+
+    /// Returns the elapsed play time of the current game
+    pub fn elapsed_time(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Returns true while the game clock is running
+    pub fn is_timer_running(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    /// Returns the best-times table
+    pub fn best_times(&self) -> &BestTimes {
+        &self.best_times
+    }
+
+    /// Replaces the best-times table (e.g. with one loaded from disk)
+    pub fn set_best_times(&mut self, best_times: BestTimes) {
+        self.best_times = best_times;
+    }
+
+    /// Returns the unlocked achievements
+    pub fn achievements(&self) -> &Achievements {
+        &self.achievements
+    }
+
+    /// Replaces the achievements store (e.g. with one loaded from disk)
+    pub fn set_achievements(&mut self, achievements: Achievements) {
+        self.achievements = achievements;
+    }
+
+    /// Returns the best time for the current grid size at the given difficulty
+    pub fn best_time(&self, difficulty: Difficulty) -> Option<Duration> {
+        self.best_times.get(self.state.size(), difficulty)
+    }
+
+    /// Returns true if the last completed game set a new best time
+    pub fn is_new_best(&self) -> bool {
+        self.new_best
+    }
+
+    /// Checks if the puzzle is solved (every layer, for a layered game)
+    pub fn is_solved(&self) -> bool {
+        (0..self.layers.layer_count()).all(|index| self.layer_solved(index))
+    }
+
+    /// Returns the current entropy level (using primary calculator)
+    pub fn current_entropy(&self) -> u32 {
+        self.entropy_calculator.calculate(&self.state)
+    }
+
+    /// Returns entropy calculated by all three methods with performance metrics
+    ///
+    /// Results are cached and only recalculated when the puzzle state changes.
+    /// Until an exact solution length is known, the actual length comes from a
+    /// quick search on a background task, so this never blocks a frame: while
+    /// that runs the returned metrics are marked `stale`, with current heuristic
+    /// readouts and the previous board's actual length.
+    pub fn all_entropy_metrics(&mut self) -> EntropyMetrics {
+        // Return cached metrics if available
+        if let Some(metrics) = self.cached_metrics {
+            return metrics;
+        }
+
+        // An exact length is already known, so nothing slow is left to measure
+        let exact = if self.last_solution_length > 0 {
+            Some((self.last_solution_length, self.last_solve_time_micros, self.last_solve_nodes))
+        } else {
+            // Solved before, when the board last looked like this
+            self.solution_cache.optimal_length(&self.state).map(|length| (length, 0, 0))
+        };
+        if let Some((length, time_micros, nodes)) = exact {
+            let mut metrics = EntropyMetrics::heuristics(&self.state);
+            metrics.actual_solution_length = length;
+            metrics.performance.actual_time_micros = time_micros;
+            metrics.performance.solver_nodes_expanded = nodes;
+            self.metrics_task = None;
+            self.store_metrics(metrics);
+            return metrics;
+        }
+
+        if !matches!(self.metrics_task, Some((_, version)) if version == self.state_version) {
+            let task = spawn_metrics_task(MetricsJob {
+                state: self.state.clone(),
+            });
+            self.metrics_task = Some((task, self.state_version));
+        }
+        self.update_metrics_task();
+        if let Some(metrics) = self.cached_metrics {
+            return metrics;
+        }
+
+        let mut metrics = EntropyMetrics::heuristics(&self.state);
+        if let Some(previous) = self.last_metrics {
+            metrics.actual_solution_length = previous.actual_solution_length;
+            metrics.actual_is_estimate = previous.actual_is_estimate;
+            metrics.performance.actual_time_micros = previous.performance.actual_time_micros;
+            metrics.performance.solver_nodes_expanded = previous.performance.solver_nodes_expanded;
+        }
+        metrics.stale = true;
+        metrics
+    }
+
+    /// Returns true while metrics for the current board are measured in the background
+    pub fn is_measuring(&self) -> bool {
+        self.metrics_task.is_some()
+    }
+
+    /// Takes finished metrics from the background task, if they are for the current board
+    fn update_metrics_task(&mut self) {
+        let Some((mut task, version)) = self.metrics_task.take() else {
+            return;
+        };
+        match task.poll() {
+            TaskStatus::Running => self.metrics_task = Some((task, version)),
+            TaskStatus::Finished(metrics) if version == self.state_version => self.store_metrics(metrics),
+            TaskStatus::Finished(_) => {} // Measured a board that has since changed
+            TaskStatus::Failed => log::error!("Metrics task panicked"),
+        }
+    }
+
+    /// Caches fresh metrics for the current board
+    fn store_metrics(&mut self, metrics: EntropyMetrics) {
+        self.cached_metrics = Some(metrics);
+        self.last_metrics = Some(metrics);
+    }
+
+    /// Resets to a new solved puzzle
+    pub fn reset(&mut self) {
+        self.cancel_background_solver();
+        self.pending_shuffle = None;
+
+        match self.solved_board() {
+            Ok(state) => self.state = state,
+            Err(e) => log::error!("Cannot reset to a solved board: {}", e),
+        }
+        self.clear_layers();
+        self.history.reset();
+        self.invalidate_cache();
+        self.auto_solve = None;
+        self.clock.reset();
+        self.game_difficulty = None;
+        self.hints_used = 0;
+        self.challenge = GameMode::Free;
+        self.challenge_failure = None;
+        self.new_best = false;
+        self.initial_state = None;
+        self.initial_optimal = None;
+        self.cancel_par_solve();
+        self.finished_game = None;
+        self.clear_analysis();
+        self.start_distance_trail();
+    }
+
+    /// Starts auto-solve mode, computing and animating the optimal solution
+    /// Spawns a background thread to compute the solution (non-blocking)
+    /// Returns true if computation started, false if already solved or computing
+    pub fn start_auto_solve(&mut self) -> bool {
+        if self.state.is_solved() {
+            return false;
+        }
+        if !self.is_auto_solving() && !self.is_solver_computing_for_autosolve() {
+            self.auto_solve_stop = None;
+        }
+
+        // Auto-solved games never count towards best times
+        self.assisted = true;
+        self.auto_solve_error = None;
+
+        if self.auto_solve.is_some() {
+            return false;
+        }
+
+        // Reuse the cached solution if it still fits the board
+        if let Some(path) = self.synced_solution() {
+            let path = path.clone();
+            let annotations = match self.solver_state {
+                Some(SolverState::Ready { ref annotations, .. }) => annotations.clone(),
+                _ => Vec::new(),
+            };
+            log::info!("Auto-solve started with cached solution: {} moves", path.len());
+            self.auto_solve = Some(
+                AutoSolveState::new(path, self.scaled_auto_solve_interval(), self.state_version)
+                    .with_annotations(annotations),
+            );
+            return true;
+        }
+
+        match self.solver_state {
+            // Already computing
+            Some(SolverState::Computing { for_autosolve: true, .. }) => return false,
+            // A metrics solve of this very board is animated once it finishes
+            Some(SolverState::Computing {
+                ref mut for_autosolve,
+                version,
+                ..
+            }) if version == self.state_version => {
+                *for_autosolve = true;
+                log::info!("Auto-solve started; waiting for the solve already running");
+                return true;
+            }
+            // Failed attempts and solves of earlier boards
+            _ => self.cancel_background_solver(),
+        }
+
+        log::info!(
+            "Auto-solve started at Manhattan distance {} after {} moves",
+            self.current_entropy(),
+            self.move_count()
+        );
+        let choice = self.solver_choice();
+        log::debug!(
+            "Spawning {} solver (may take up to {} seconds)",
+            choice.kind,
+            choice.config.timeout.as_secs()
+        );
+
+        self.solver_state = Some(self.spawn_solver(choice, true)); // true = for auto-solve
+        true
+    }
+
+    /// Auto-solves only until `target` is reached, then hands the board back
+    ///
+    /// A guided assist for boards too big to want solved outright. Move targets
+    /// follow the chosen solver's solution; tile and row targets use the
+    /// strategic solver, which places tiles top row first as a person would.
+    /// The moves play and can be paused or stopped like a full auto-solve, and
+    /// mark the game as assisted.
+    /// Returns false if the board is solved, `target` is already reached, or an
+    /// auto-solve is already running or being computed
+    pub fn start_partial_auto_solve(&mut self, target: AssistTarget) -> bool {
+        if self.state.is_solved() || self.is_auto_solving() || self.is_solver_computing_for_autosolve() {
+            return false;
+        }
+        let stop = match target {
+            AssistTarget::Moves(n) => AssistStop::MovesLeft(n),
+            AssistTarget::Tiles(n) => AssistStop::TilesHome(tiles_home(&self.state) + n),
+            AssistTarget::Rows(n) => AssistStop::RowsSolved(n),
+            AssistTarget::NextRow => {
+                let solved = (0..self.state.size()).take_while(|&row| row_solved(&self.state, row)).count();
+                AssistStop::RowsSolved(solved + 1)
+            }
+        };
+        if stop.is_reached(&self.state) {
+            return false;
+        }
+
+        log::info!("Assist started: {}", target);
+        if let AssistTarget::Moves(_) = target {
+            if !self.start_auto_solve() {
+                return false;
+            }
+        } else {
+            self.assisted = true;
+            self.auto_solve_error = None;
+            self.cancel_background_solver();
+            self.solver_state = Some(self.spawn_solver(self.choice_of(SolverKind::Strategic), true));
+        }
+        self.auto_solve_stop = Some(stop);
+        true
+    }
+
+    /// Plays back a solution from elsewhere, such as an imported blank-move string
+    ///
+    /// Moves are animated like auto-solve and can be stopped the same way. The path
+    /// need not solve the board, so a partial or wrong solution can be watched up
+    /// to where it goes astray. Playback marks the game as assisted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::AutoSolving` if auto-solve or another playback is running,
+    /// or `PuzzleError::InvalidSolution` for the first step that is not a legal move
+    pub fn start_playback(&mut self, path: Vec<Position>) -> Result<(), PuzzleError> {
+        if self.auto_solve.is_some() {
+            return Err(PuzzleError::AutoSolving);
+        }
+        let mut replay = self.state.clone();
+        for (index, &pos) in path.iter().enumerate() {
+            if !replay.apply_immediate_move(pos) {
+                return Err(PuzzleError::InvalidSolution {
+                    step: index + 1,
+                    reason: format!("no tile at {:?} can slide into the empty cell", pos),
+                });
+            }
+        }
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        self.assisted = true;
+        self.cancel_background_solver();
+        self.auto_solve_stop = None;
+        log::info!("Playing back a {}-move solution", path.len());
+        self.auto_solve = Some(AutoSolveState::new(path, self.scaled_auto_solve_interval(), self.state_version));
+        Ok(())
+    }
+
+    /// Checks if the background solve has completed and transitions state
+    /// Also records par once the solve of the shuffled board finishes
+    /// Should be called each frame to poll for completion
+    /// Returns true if solution is ready to start executing
+    pub fn update_solver_state(&mut self) -> bool {
+        self.update_par();
+        let state = self.solver_state.take();
+
+        match state {
+            Some(SolverState::Computing {
+                mut task,
+                kind,
+                for_autosolve: is_for_autosolve,
+                version,
+                start,
+            }) => {
+                if let Some(ref mut trace) = self.search_trace {
+                    if trace.is_finished() || !trace.traces(kind, version) {
+                        *trace = SearchTrace::new(kind, version);
+                    }
+                    if let Some(progress) = task.progress() {
+                        trace.record(&progress);
+                    }
+                }
+
+                // Check if the task is done (non-blocking)
+                let status = task.poll();
+                if !matches!(status, TaskStatus::Running) {
+                    if let Some(ref mut trace) = self.search_trace {
+                        trace.finish();
+                    }
+                }
+                match status {
+                    TaskStatus::Finished(Ok(solution)) => {
+                        let solve_time = solution.duration.as_micros() as u64;
+                        let path = solution.path;
+                        let annotations = solution.annotations;
+                        log::info!(
+                            "{} found a {}-move solution in {}",
+                            kind,
+                            path.len(),
+                            PerformanceMetrics::format_duration(solve_time)
+                        );
+                        if is_for_autosolve {
+                            log::debug!("First 5 moves: {:?}", &path[..path.len().min(5)]);
+                        }
+                        log::debug!(
+                            "Nodes: {} expanded, {} generated, peak open set {}, ~{} KB",
+                            solution.nodes_expanded,
+                            solution.nodes_generated,
+                            solution.max_open_size,
+                            solution.peak_memory_bytes / 1024
+                        );
+
+                        // Store solve time and solution length for metrics display
+                        // (non-optimal solvers say nothing about the actual length)
+                        self.last_solve_time_micros = solve_time;
+                        self.last_solve_nodes = solution.nodes_expanded;
+                        if kind.is_optimal() {
+                            self.last_solution_length = path.len() as u32;
+                        }
+
+                        // Invalidate cache so GUI shows updated metrics
+                        self.cached_metrics = None;
+                        self.solution_cache.insert(
+                            &start,
+                            CachedSolution {
+                                path: path.clone(),
+                                optimal: kind.is_optimal(),
+                                solve_time_micros: solve_time,
+                            },
+                        );
+
+                        // Only transition to auto-solve animation if this was for auto-solve
+                        // A board changed since the solve started is resynced on first use
+                        if is_for_autosolve {
+                            self.auto_solve = Some(
+                                AutoSolveState::new(path.clone(), self.scaled_auto_solve_interval(), version)
+                                    .with_annotations(annotations.clone()),
+                            );
+                        }
+                        // Cache the solution for reuse
+                        self.solver_state = Some(SolverState::Ready {
+                            path,
+                            annotations,
+                            solve_time_micros: solve_time,
+                            version,
+                        });
+                        if is_for_autosolve {
+                            return true;
+                        }
+                    }
+                    TaskStatus::Finished(Err(e))
+                        if is_for_autosolve && e != SolverError::Cancelled && self.dispatcher.fallback(kind).is_some() =>
+                    {
+                        // Optimal search gave up (large grid); any solution beats none
+                        let next = self.choice_of(self.dispatcher.fallback(kind).unwrap_or(SolverKind::Strategic));
+                        log::warn!("{} gave up ({}), falling back to {} solver", kind, e, next.kind);
+                        self.solver_state = Some(self.spawn_solver(next, true));
+                    }
+                    TaskStatus::Finished(Err(e)) => {
+                        log::warn!("{} failed to find a solution: {}", kind, e);
+                        self.fail_solve(e, is_for_autosolve);
+                    }
+                    TaskStatus::Failed => {
+                        log::error!("Solver task panicked");
+                        self.fail_solve(SolverError::Crashed, is_for_autosolve);
+                    }
+                    TaskStatus::Running => {
+                        // Still computing, put it back
+                        self.solver_state = Some(SolverState::Computing {
+                            task,
+                            kind,
+                            for_autosolve: is_for_autosolve,
+                            version,
+                            start,
+                        });
+                    }
+                }
+            }
+            Some(other) => {
+                // Put back non-computing states
+                self.solver_state = Some(other);
+            }
+            None => {}
+        }
+
+        false
+    }
+
+    /// Records a background solve that ended without a solution
+    fn fail_solve(&mut self, error: SolverError, for_autosolve: bool) {
+        if for_autosolve {
+            self.auto_solve_error = Some(AutoSolveError::SolverFailed(error.clone()));
+        }
+        self.solver_state = Some(SolverState::Failed(error));
+    }
+
+    /// Returns why the last background solve failed, if it did
+    pub fn solver_error(&self) -> Option<&SolverError> {
+        match self.solver_state {
+            Some(SolverState::Failed(ref e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Takes the error of an auto-solve whose solver gave up, so it is reported once
+    pub fn take_auto_solve_error(&mut self) -> Option<AutoSolveError> {
+        self.auto_solve_error.take()
+    }
+
+    /// Returns true if solver is currently computing in background
+    pub fn is_solver_computing(&self) -> bool {
+        matches!(self.solver_state, Some(SolverState::Computing { .. }))
+    }
+
+    /// Returns true if solver is computing for auto-solve (not just metrics)
+    pub fn is_solver_computing_for_autosolve(&self) -> bool {
+        matches!(
+            self.solver_state,
+            Some(SolverState::Computing {
+                for_autosolve: true,
+                ..
+            })
+        )
+    }
+
+    /// Returns the latest progress snapshot from the background solver
+    /// Returns None if no solver is running or it has not reported yet
+    pub fn solver_progress(&self) -> Option<SolverProgress> {
+        match self.solver_state {
+            Some(SolverState::Computing { ref task, .. }) => task.progress(),
+            _ => None,
+        }
+    }
+
+    /// Turns recording of background solves' progress on or off
+    /// Recording starts with the next poll of a running solve
+    pub fn set_search_tracing(&mut self, on: bool) {
+        if on != self.search_trace.is_some() {
+            self.search_trace = on.then(|| SearchTrace::new(self.solver(), self.state_version));
+        }
+    }
+
+    /// Returns the progress recorded for the latest background solve
+    /// Returns None while tracing is off
+    pub fn search_trace(&self) -> Option<&SearchTrace> {
+        self.search_trace.as_ref()
+    }
+
+    /// Returns the number of node expansions the background solver may use
+    pub fn solver_node_budget(&self) -> usize {
+        self.solver_config.max_iterations
+    }
+
+    /// Returns the search limits used for background solves
+    pub fn solver_config(&self) -> SolverConfig {
+        self.solver_config
+    }
+
+    /// Sets the search limits used for subsequent background solves
+    pub fn set_solver_config(&mut self, config: SolverConfig) {
+        self.solver_config = config;
+    }
+
+    /// Returns the algorithm used for background solves of the current board
+    pub fn solver(&self) -> SolverKind {
+        self.solver_choice().kind
+    }
+
+    /// Returns true if the dispatcher picks the algorithm for each board
+    pub fn is_solver_auto(&self) -> bool {
+        self.solver_kind.is_none()
+    }
+
+    /// Selects the algorithm used for subsequent background solves
+    ///
+    /// A running solve keeps its algorithm; cached solutions from a different
+    /// solver are discarded so auto-solve uses the new choice.
+    pub fn set_solver(&mut self, kind: SolverKind) {
+        self.select_solver(Some(kind));
+    }
+
+    /// Lets the dispatcher pick the algorithm, and its timeout, for each board
+    /// (see `set_solver`)
+    pub fn set_solver_auto(&mut self) {
+        self.select_solver(None);
+    }
+
+    /// Returns the rules used to pick solvers automatically
+    pub fn dispatcher(&self) -> SolverDispatcher {
+        self.dispatcher
+    }
+
+    /// Sets the rules used to pick solvers automatically
+    pub fn set_dispatcher(&mut self, dispatcher: SolverDispatcher) {
+        self.dispatcher = dispatcher;
+    }
+
+    fn select_solver(&mut self, kind: Option<SolverKind>) {
+        if kind == self.solver_kind {
+            return;
+        }
+        self.solver_kind = kind;
+        self.solution_cache.clear();
+        if matches!(self.solver_state, Some(SolverState::Ready { .. }) | Some(SolverState::Failed(_))) {
+            self.solver_state = None;
+        }
+    }
+
+    /// Stops auto-solve mode and cancels any running solver
+    pub fn stop_auto_solve(&mut self) {
+        match self.auto_solve_progress() {
+            Some((current, total)) => log::info!("Auto-solve stopped at move {}/{}", current, total),
+            None => log::debug!("Auto-solve stopped"),
+        }
+        self.auto_solve = None;
+        self.auto_solve_stop = None;
+        self.cancel_background_solver();
+    }
+
+    /// Signals any running background solver to stop and clears the solver state
+    /// The solve observes the request on its next progress check and exits
+    fn cancel_background_solver(&mut self) {
+        if let Some(SolverState::Computing { task, .. }) = self.solver_state.take() {
+            task.cancel();
+        }
+    }
+
+    /// Returns whether auto-solve is active
+    pub fn is_auto_solving(&self) -> bool {
+        self.auto_solve.is_some()
+    }
+
+    /// Returns true if the running or pending auto-solve stops at an assist target
+    pub fn is_partial_auto_solve(&self) -> bool {
+        self.auto_solve_stop.is_some()
+            && (self.is_auto_solving() || self.is_solver_computing_for_autosolve())
+    }
+
+    /// Returns the time between auto-solve moves at normal speed
+    pub fn auto_solve_interval(&self) -> Duration {
+        self.auto_solve_interval
+    }
+
+    /// Sets the time between auto-solve moves at normal speed
+    /// Also applies to an auto-solve that is already playing
+    pub fn set_auto_solve_interval(&mut self, interval: Duration) {
+        self.auto_solve_interval = interval;
+        self.apply_auto_solve_interval();
+    }
+
+    /// Returns the auto-solve playback speed, as a multiple of the normal pace
+    pub fn auto_solve_speed(&self) -> f32 {
+        self.auto_solve_speed
+    }
+
+    /// Sets the auto-solve playback speed, clamped to `MIN_AUTO_SOLVE_SPEED..=MAX_AUTO_SOLVE_SPEED`
+    /// Also applies to an auto-solve that is already playing
+    pub fn set_auto_solve_speed(&mut self, speed: f32) {
+        self.auto_solve_speed = speed.clamp(MIN_AUTO_SOLVE_SPEED, MAX_AUTO_SOLVE_SPEED);
+        self.apply_auto_solve_interval();
+    }
+
+    /// Returns the time between auto-solve moves at the current speed
+    fn scaled_auto_solve_interval(&self) -> Duration {
+        self.auto_solve_interval.div_f32(self.auto_solve_speed)
+    }
+
+    /// Brings a running auto-solve up to date with the interval and speed
+    fn apply_auto_solve_interval(&mut self) {
+        let interval = self.scaled_auto_solve_interval();
+        if let Some(ref mut auto_solve) = self.auto_solve {
+            auto_solve.move_interval = interval;
+        }
+    }
+
+    /// Pauses or resumes a running auto-solve or playback
+    /// Does nothing while the solution is still being computed
+    pub fn set_auto_solve_paused(&mut self, paused: bool) {
+        if let Some(ref mut auto_solve) = self.auto_solve {
+            auto_solve.paused = paused;
+            log::debug!("Auto-solve {}", if paused { "paused" } else { "resumed" });
+        }
+    }
+
+    /// Returns true if a running auto-solve is paused
+    pub fn is_auto_solve_paused(&self) -> bool {
+        self.auto_solve.as_ref().is_some_and(|s| s.paused)
+    }
+
+    /// Plays every remaining auto-solve move at once, without waiting between them
+    ///
+    /// Moves are recorded as if they had played one at a time. Works while
+    /// paused, and ends the auto-solve.
+    /// Returns the number of moves played
+    pub fn skip_auto_solve_to_end(&mut self) -> usize {
+        self.resync_auto_solve();
+        let Some(ref auto_solve) = self.auto_solve else {
+            return 0;
+        };
+        let remaining = auto_solve.solution_path[auto_solve.current_step..].to_vec();
+        let played = remaining
+            .into_iter()
+            .take_while(|&pos| self.apply_auto_solve_move(pos))
+            .count();
+        log::info!("Auto-solve skipped ahead {} moves", played);
+        played
+    }
+
+    /// Returns auto-solve progress (current_step, total_steps)
+    pub fn auto_solve_progress(&self) -> Option<(usize, usize)> {
+        self.auto_solve.as_ref().map(|s| s.progress())
+    }
+
+    /// Returns what the auto-solve's next move is working towards, such as
+    /// "Place tile 5", for a caption under the board
+    /// Only strategic solutions say; returns None for the others
+    pub fn auto_solve_step(&self) -> Option<&SolveStep> {
+        let auto_solve = self.auto_solve.as_ref()?;
+        step_at(&auto_solve.annotations, auto_solve.current_step)
+    }
+
+    /// Checks if auto-solve has a move ready to execute
+    /// Returns the position to move if it's time for the next move
+    pub fn get_next_auto_solve_move(&mut self) -> Option<Position> {
+        self.resync_auto_solve();
+        if let Some(ref auto_solve) = self.auto_solve {
+            if auto_solve.is_complete() || auto_solve.paused {
+                return None;
+            }
+
+            let elapsed = auto_solve.last_move_time.elapsed();
+            if elapsed >= auto_solve.move_interval {
+                return Some(auto_solve.solution_path[auto_solve.current_step]);
+            }
+        }
+        None
+    }
+
+    /// Executes an auto-solve move and advances to the next step
+    /// Should be called after animation completes
+    ///
+    /// A move that is not the next step (the rest of a chain the player started
+    /// before the auto-solve) is still made, but leaves the auto-solve to catch
+    /// up with the board before its next move.
+    pub fn apply_auto_solve_move(&mut self, pos: Position) -> bool {
+        let should_clear;
+        let on_path;
+
+        if let Some(ref mut auto_solve) = self.auto_solve {
+            on_path = auto_solve.version == self.state_version
+                && auto_solve.solution_path.get(auto_solve.current_step) == Some(&pos);
+            if self.state.apply_immediate_move(pos) {
+                if on_path {
+                    auto_solve.current_step += 1;
+                    auto_solve.last_move_time = Instant::now();
+                }
+
+                // Check if complete before we drop the borrow
+                should_clear = on_path && auto_solve.is_complete();
+            } else {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // Now that auto_solve borrow is dropped, we can mutate self again
+        self.history.record_slide(pos);
+        self.history.record_move();
+        self.invalidate_cache();
+        self.record_distance();
+        if on_path {
+            if let Some(ref mut auto_solve) = self.auto_solve {
+                auto_solve.version = self.state_version;
+            }
+            if let Some(AssistStop::MovesLeft(ref mut left)) = self.auto_solve_stop {
+                *left -= 1;
+            }
+        }
+
+        // A partial auto-solve hands the board back once its target is reached
+        let assist_done = self.auto_solve_stop.is_some_and(|stop| stop.is_reached(&self.state));
+        if assist_done {
+            log::info!("Assist finished; the board is back in the player's hands");
+        }
+        if should_clear || assist_done || self.is_solved() {
+            self.auto_solve = None;
+            self.auto_solve_stop = None;
+        }
+        if self.is_solved() {
+            self.finish_game();
+        }
+        true
+    }
+
+    /// Suggests the next move for the player without blocking the UI
+    ///
+    /// Reuses the cached solution when it still applies to the current board.
+    /// Otherwise falls back to a shallow heuristic lookahead and starts a background
+    /// solve so that subsequent hints are optimal.
+    /// Returns None if the puzzle is already solved. Each hint given counts
+    /// against the no-hint achievement.
+    pub fn get_hint(&mut self) -> Option<Position> {
+        if self.state.is_solved() {
+            return None;
+        }
+        self.hints_used += 1;
+
+        // During auto-solve the next step of the running solution is the hint,
+        // once the auto-solve has caught up with any move made under it
+        self.resync_auto_solve();
+        if let Some(ref auto_solve) = self.auto_solve {
+            if auto_solve.version == self.state_version && !auto_solve.is_complete() {
+                return Some(auto_solve.solution_path[auto_solve.current_step]);
+            }
+        }
+
+        if let Some(path) = self.synced_solution() {
+            return path.first().copied();
+        }
+
+        self.start_background_solve_for_metrics();
+        lookahead_hint(&self.state, HINT_LOOKAHEAD_DEPTH)
+    }
+
+    /// Plays exactly one move of the cached solution
+    ///
+    /// Lets learners study a solve one move at a time. Manual moves between steps
+    /// are allowed: if the player kept to the solution the cached path is trimmed to
+    /// match, otherwise a new solve starts in the background and this returns None
+    /// until it is ready. Stepping marks the game as assisted.
+    /// Returns the position the moved tile came from, or None if no move was made
+    pub fn advance_solution_step(&mut self) -> Option<Position> {
+        if self.state.is_solved() || self.is_auto_solving() {
+            return None;
+        }
+        let Some(path) = self.synced_solution() else {
+            self.start_background_solve_for_metrics();
+            return None;
+        };
+
+        let pos = path.remove(0);
+        let remaining = path.len();
+        if !self.state.apply_immediate_move(pos) {
+            self.solver_state = None; // Unreachable for a synced path, but never keep a bad one
+            return None;
+        }
+        log::trace!("Solution step {:?} ({} left)", pos, remaining);
+
+        self.assisted = true;
+        self.clock.start();
+        self.history.record_slide(pos);
+        self.history.record_move();
+        self.invalidate_cache();
+        self.record_distance();
+        if self.is_solved() {
+            self.finish_game();
+        }
+        Some(pos)
+    }
+
+    /// Returns, for each tile the cached solution moves, the step at which it first moves
+    ///
+    /// Keys are the tiles' current positions; tiles the solution never touches are
+    /// left out. Returns None (and starts a background solve) while no solution
+    /// fits the board.
+    pub fn solution_order(&mut self) -> Option<HashMap<Position, usize>> {
+        if self.synced_solution().is_none() {
+            self.start_background_solve_for_metrics();
+            return None;
+        }
+        let Some(SolverState::Ready { ref path, .. }) = self.solver_state else {
+            return None;
+        };
+
+        // Follow each tile through the path: `origin` maps where a tile is now
+        // (during the replay) to where it sits on the real board
+        let size = self.state.size();
+        let mut origin: Vec<Option<Position>> = (0..size * size).map(|i| Some((i / size, i % size))).collect();
+        let mut empty = self.state.empty_position();
+        let mut order = HashMap::new();
+        for (step, &pos) in path.iter().enumerate() {
+            let tile = origin[pos.0 * size + pos.1].take();
+            if let Some(tile) = tile {
+                order.entry(tile).or_insert(step);
+            }
+            origin[empty.0 * size + empty.1] = tile;
+            empty = pos;
+        }
+        Some(order)
+    }
+
+    /// Returns the rest of the known solution for the current board
+    ///
+    /// During auto-solve this is the part still to play; otherwise it is the
+    /// cached solution trimmed to the board. Returns None while no solution fits.
+    pub fn remaining_solution(&mut self) -> Option<Vec<Position>> {
+        self.resync_auto_solve();
+        if let Some(ref auto_solve) = self.auto_solve {
+            if auto_solve.version == self.state_version {
+                return Some(auto_solve.solution_path[auto_solve.current_step..].to_vec());
+            }
+        }
+        self.synced_solution().cloned()
+    }
+
+    /// Returns the cached solution trimmed to fit the current board
+    ///
+    /// Moves the player made along the solution are dropped from its front. Returns
+    /// None (discarding the cache) if the board left the solution's path entirely.
+    fn synced_solution(&mut self) -> Option<&mut Vec<Position>> {
+        let done = match self.solver_state {
+            Some(SolverState::Ready { version, .. }) if version == self.state_version => Some(0),
+            Some(SolverState::Ready { ref path, .. }) => moves_done(&self.state, path),
+            Some(SolverState::Computing { .. }) => return None,
+            Some(SolverState::Failed(_)) | None => None,
+        };
+        let done = match done {
+            Some(done) => Some(done),
+            // An earlier solve of this very board may still be cached
+            None if self.restore_cached_solution() => Some(0),
+            None => {
+                if matches!(self.solver_state, Some(SolverState::Ready { .. })) {
+                    // Cached solution belongs to another board - discard it
+                    self.solver_state = None;
+                }
+                None
+            }
+        };
+        let state_version = self.state_version;
+        match (done, &mut self.solver_state) {
+            (Some(done), Some(SolverState::Ready { path, annotations, version, .. })) => {
+                path.drain(..done);
+                skip_annotated_moves(annotations, done);
+                *version = state_version;
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Makes a solution from the solution cache the working one, if the current board has one
+    ///
+    /// An optimal solution also restores the solution-length readout.
+    /// Returns false (changing nothing) if the board is not cached or a solve is running
+    fn restore_cached_solution(&mut self) -> bool {
+        if self.is_solver_computing() {
+            return false;
+        }
+        let Some(cached) = self.solution_cache.get(&self.state) else {
+            return false;
+        };
+        let (path, optimal, solve_time_micros) = (cached.path.clone(), cached.optimal, cached.solve_time_micros);
+        log::debug!("Reusing a cached {}-move solution", path.len());
+        if optimal {
+            self.last_solution_length = path.len() as u32;
+            self.last_solve_time_micros = solve_time_micros;
+            self.last_solve_nodes = 0;
+            self.cached_metrics = None;
+        }
+        self.solver_state = Some(SolverState::Ready {
+            path,
+            annotations: Vec::new(),
+            solve_time_micros,
+            version: self.state_version,
+        });
+        true
+    }
+
+    /// Brings a running auto-solve back in line with a board changed behind its back
+    ///
+    /// If the board only moved along the solution, the auto-solve skips ahead.
+    /// Otherwise it solves the current board again and resumes once that finishes.
+    fn resync_auto_solve(&mut self) {
+        let Some(ref mut auto_solve) = self.auto_solve else {
+            return;
+        };
+        if auto_solve.version == self.state_version {
+            return;
+        }
+
+        let remaining = &auto_solve.solution_path[auto_solve.current_step..];
+        if let Some(done) = moves_done(&self.state, remaining) {
+            auto_solve.current_step += done;
+            auto_solve.version = self.state_version;
+            if auto_solve.is_complete() {
+                self.auto_solve = None;
+            }
+            return;
+        }
+
+        log::info!("Board changed during auto-solve; solving again");
+        self.auto_solve = None;
+        self.cancel_background_solver();
+        if !self.state.is_solved() {
+            let kind = match self.auto_solve_stop {
+                Some(AssistStop::TilesHome(_)) | Some(AssistStop::RowsSolved(_)) => SolverKind::Strategic,
+                _ => self.solver(),
+            };
+            self.solver_state = Some(self.spawn_solver(self.choice_of(kind), true));
+        }
+    }
+}
+
+/// Returns the seed of the next shuffle tried after one from `seed`
+fn next_seed(seed: u64) -> u64 {
+    StdRng::seed_from_u64(seed).gen()
+}
+
+/// Returns how many cells hold different tiles on two boards
+/// Boards of different sizes never resemble each other, so count as far apart as can be
+fn cells_apart(a: &PackedState, b: &PackedState) -> usize {
+    let size = a.size();
+    if b.size() != size {
+        return usize::MAX;
+    }
+    (0..size * size)
+        .map(|i| (i / size, i % size))
+        .filter(|&pos| a.tile_at(pos) != b.tile_at(pos))
+        .count()
+}
+
+/// Depth of the heuristic lookahead used when no cached solution is available
+const HINT_LOOKAHEAD_DEPTH: usize = 3;
+
+/// Returns true if applying `path` to `state` leaves the puzzle solved
+fn path_solves(state: &PuzzleState, path: &[Position]) -> bool {
+    let mut scratch = state.clone();
+    path.iter().all(|&pos| scratch.apply_immediate_move(pos)) && scratch.is_solved()
+}
+
+/// Returns how many leading moves of `path` were already made to reach `state`
+/// Returns None if no remainder of `path` solves `state`
+fn moves_done(state: &PuzzleState, path: &[Position]) -> Option<usize> {
+    (0..=path.len()).find(|&done| path_solves(state, &path[done..]))
+}
+
+/// Picks the immediate move whose best reachable heuristic within `depth` moves is lowest
+fn lookahead_hint(state: &PuzzleState, depth: usize) -> Option<Position> {
+    let empty = state.empty_position();
+
+    state.legal_moves().into_iter().min_by_key(|&pos| {
+        let mut next = state.clone();
+        next.apply(pos);
+        best_reachable_heuristic(&next, depth.saturating_sub(1), Some(empty))
+    })
+}
+
+/// Minimum Enhanced Heuristic value reachable within `depth` moves (no backtracking)
+fn best_reachable_heuristic(
+    state: &PuzzleState,
+    depth: usize,
+    previous_empty: Option<Position>,
+) -> u32 {
+    if state.is_solved() {
+        return 0;
+    }
+    let here = EnhancedHeuristic.calculate(state);
+    if depth == 0 {
+        return here;
+    }
+
+    let empty = state.empty_position();
+    state
+        .legal_moves()
+        .into_iter()
+        .filter(|&pos| Some(pos) != previous_empty)
+        .map(|pos| {
+            let mut next = state.clone();
+            next.apply(pos);
+            best_reachable_heuristic(&next, depth - 1, Some(empty))
+        })
+        .min()
+        .map_or(here, |best| best.min(here))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AStarSolver, EndgameTable, Grade, TileContent, WalkingDistance};
+
+    #[test]
+    fn test_new_controller() {
+        let controller = GameController::new(4).unwrap();
+        assert_eq!(controller.move_count(), 0);
+        assert!(controller.is_solved());
+    }
+
+    #[test]
+    fn test_new_game_shuffles() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Easy);
+
+        assert_eq!(controller.move_count(), 0); // History reset
+        assert!(!controller.is_solved()); // Should be shuffled
+    }
+
+    #[test]
+    fn test_handle_click_immediate_move() {
+        let mut controller = GameController::new(4).unwrap();
+        let result = controller.handle_click((3, 2));
+
+        assert!(result.is_some());
+
+        // Apply the moves (simulating what presenter does after animation)
+        if let Some(moves) = result {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        assert_eq!(controller.move_count(), 1);
+        assert!(!controller.is_solved());
+    }
+
+    #[test]
+    fn test_handle_click_chain_move() {
+        let mut controller = GameController::new(4).unwrap();
+        let result = controller.handle_click((3, 0));
+
+        assert!(result.is_some());
+        let moves = result.unwrap();
+        assert_eq!(moves.len(), 3); // Chain of 3 moves
+
+        // Apply the moves (simulating what presenter does after animation)
+        for move_pos in moves {
+            controller.apply_move(move_pos);
+        }
+        controller.complete_move_sequence();
+
+        assert_eq!(controller.move_count(), 1); // Counts as one move
+        assert_eq!(controller.tile_move_count(), 3); // But slides three tiles
+        assert_eq!(controller.state().empty_position(), (3, 0));
+    }
+
+    #[test]
+    fn test_handle_click_invalid_move() {
+        let mut controller = GameController::new(4).unwrap();
+        let result = controller.handle_click((0, 0));
+
+        assert!(result.is_none()); // Diagonal, not valid
+        assert_eq!(controller.move_count(), 0);
+    }
+
+    #[test]
+    fn test_handle_click_empty_cell() {
+        let mut controller = GameController::new(4).unwrap();
+        let empty_pos = controller.state().empty_position();
+        let result = controller.handle_click(empty_pos);
+
+        assert!(result.is_none());
+        assert_eq!(controller.move_count(), 0);
+    }
+
+    #[test]
+    fn test_try_handle_click_errors() {
+        let mut controller = GameController::new(4).unwrap();
+        let empty_pos = controller.state().empty_position();
+
+        assert_eq!(
+            controller.try_handle_click((4, 0)),
+            Err(PuzzleError::OutOfBounds { position: (4, 0), size: 4 })
+        );
+        assert_eq!(
+            controller.try_handle_click(empty_pos),
+            Err(PuzzleError::TileNotFound { position: empty_pos })
+        );
+        assert_eq!(
+            controller.try_handle_click((0, 0)),
+            Err(PuzzleError::InvalidMove { position: (0, 0) })
+        );
+        assert_eq!(controller.try_handle_click((3, 2)), Ok(vec![(3, 2)]));
+
+        controller.new_game_in_background(Difficulty::Easy);
+        assert_eq!(controller.try_handle_click((3, 2)), Err(PuzzleError::Shuffling));
+    }
+
+    #[test]
+    fn test_try_apply_move_errors() {
+        let mut controller = GameController::new(4).unwrap();
+
+        assert_eq!(
+            controller.try_apply_move((0, 9)),
+            Err(PuzzleError::OutOfBounds { position: (0, 9), size: 4 })
+        );
+        assert_eq!(
+            controller.try_apply_move((3, 3)),
+            Err(PuzzleError::TileNotFound { position: (3, 3) })
+        );
+        assert_eq!(
+            controller.try_apply_move((3, 1)), // Two cells away
+            Err(PuzzleError::InvalidMove { position: (3, 1) })
+        );
+        assert_eq!(controller.try_apply_move((3, 2)), Ok(()));
+        assert_eq!(controller.state().empty_position(), (3, 2));
+
+        // Moves are refused while an auto-solve plays or the next board is shuffled
+        controller.start_playback(vec![(3, 3)]).unwrap();
+        assert_eq!(controller.try_apply_move((3, 1)), Err(PuzzleError::AutoSolving));
+        assert!(controller.apply_auto_solve_move((3, 3)));
+        controller.new_game_in_background(Difficulty::Easy);
+        assert_eq!(controller.try_apply_move((3, 2)), Err(PuzzleError::Shuffling));
+        assert_eq!(controller.state().empty_position(), (3, 3));
+    }
+
+    #[test]
+    fn test_preview_move_matches_click() {
+        let mut controller = GameController::new(4).unwrap();
+        let before = controller.state().clone();
+
+        let preview = controller.preview_move((3, 0)).unwrap();
+        assert_eq!(preview.len(), 3);
+        assert_eq!(preview.empty_after, (3, 0));
+        assert_eq!(controller.state().to_layout(), before.to_layout()); // Nothing moved
+        assert_eq!(controller.move_count(), 0);
+
+        let moves = controller.handle_click((3, 0)).unwrap();
+        assert_eq!(preview.tiles().collect::<Vec<_>>(), moves);
+
+        assert!(controller.preview_move((0, 0)).is_none()); // Not in line
+        assert!(controller.preview_move((3, 3)).is_none()); // Empty cell
+    }
+
+    #[test]
+    fn test_tile_labels_carry_into_new_games() {
+        let mut controller = GameController::new(4).unwrap();
+        let word = TileLabels::Word("RATE YOUR MIND PAL".to_string());
+        controller.set_tile_labels(word.clone()).unwrap();
+        assert_eq!(controller.tile_labels(), &word);
+
+        controller.new_game(Difficulty::Easy);
+        assert!(controller.state().tiles().all(|(_, tile)| matches!(tile.content, TileContent::Alpha(_))));
+
+        // Labels that do not fit are rejected and the old ones kept
+        assert!(controller
+            .set_tile_labels(TileLabels::Custom(vec!["a".to_string()]))
+            .is_err());
+        assert_eq!(controller.tile_labels(), &word);
+
+        // A board of another size cannot spell the phrase, so it goes back to numbers
+        controller.load_state(PuzzleState::new(3).unwrap()).unwrap();
+        assert_eq!(controller.tile_labels(), &TileLabels::Numbers);
+    }
+
+    #[test]
+    fn test_alternate_goal() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_goal(GoalSpec::Spiral);
+        assert_eq!(controller.state().empty_position(), (1, 1));
+        assert!(controller.is_solved());
+
+        controller.new_game(Difficulty::Easy);
+        assert_eq!(controller.state().goal(), GoalSpec::Spiral);
+        assert!(!controller.is_solved());
+
+        // Optimal solvers aim for the spiral rather than the standard goal
+        let solution = SolverKind::AStar
+            .solve_instrumented(SolverConfig::default(), controller.state(), None, &mut |_| {})
+            .unwrap();
+        for pos in solution.path {
+            assert!(controller.apply_move(pos));
+        }
+        assert!(controller.is_solved());
+        assert_eq!(controller.state().empty_position(), (1, 1));
+    }
+
+    #[test]
+    fn test_auto_solve_simple_puzzle() {
+        let mut controller = GameController::new(3).unwrap();
+
+        // Make a few moves to scramble
+        if let Some(moves) = controller.handle_click((2, 1)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+        if let Some(moves) = controller.handle_click((2, 0)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        // Start auto-solve (spawns background thread)
+        assert!(controller.start_auto_solve());
+        assert!(controller.is_solver_computing());
+
+        // Poll until solver completes (should be fast for 3x3)
+        let mut attempts = 0;
+        while !controller.update_solver_state() && attempts < 1000 {
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+
+        // Should now be auto-solving (solution ready)
+        assert!(controller.is_auto_solving());
+
+        // Get expected path length
+        let (_, total_steps) = controller.auto_solve_progress().unwrap();
+
+        // Force immediate execution by manipulating time
+        if let Some(ref mut auto_solve) = controller.auto_solve {
+            auto_solve.move_interval = Duration::from_millis(0); // Instant moves for testing
+        }
+
+        // Execute all moves
+        for _ in 0..=total_steps {
+            if let Some(next_move) = controller.get_next_auto_solve_move() {
+                controller.apply_auto_solve_move(next_move);
+            }
+        }
+
+        // Should be solved
+        assert!(controller.state().is_solved());
+        assert!(!controller.is_auto_solving()); // Completes and stops
+    }
+
+    #[test]
+    fn test_solver_progress_idle() {
+        let controller = GameController::new(4).unwrap();
+        assert!(controller.solver_progress().is_none());
+        assert!(controller.solver_node_budget() > 0);
+    }
+
+    #[test]
+    fn test_stop_auto_solve_cancels_solver() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Hard);
+
+        controller.stop_auto_solve();
+
+        // Cancellation itself is covered by the solver_task tests
+        assert!(!controller.is_solver_computing());
+        assert!(controller.solver_state.is_none());
+    }
+
+    #[test]
+    fn test_solver_config_round_trip() {
+        let mut controller = GameController::new(4).unwrap();
+        let config = SolverConfig {
+            max_iterations: 5_000,
+            timeout: Duration::from_secs(2),
+            ..SolverConfig::default()
+        };
+        controller.set_solver_config(config);
+        assert_eq!(controller.solver_config(), config);
+        assert_eq!(controller.solver_node_budget(), 5_000);
+    }
+
+    #[test]
+    fn test_par_is_optimal_whatever_the_solver() {
+        let mut controller = GameController::new(3).unwrap();
+        assert_eq!(controller.solver(), SolverKind::AStar);
+        controller.set_solver(SolverKind::Greedy);
+        assert_eq!(controller.solver(), SolverKind::Greedy);
+        controller.new_game(Difficulty::Easy);
+        wait_for_solver(&mut controller);
+
+        // A greedy path is usable for hints but says nothing about the optimum,
+        // so par comes from a search of its own
+        assert!(controller.get_hint().is_some());
+        let initial = controller.initial_state().unwrap();
+        assert_eq!(
+            controller.initial_optimal_length(),
+            EndgameTable::shared().distance(initial)
+        );
+    }
+
+    #[test]
+    fn test_metrics_report_solver_nodes() {
+        let mut controller = GameController::new(3).unwrap();
+        // Only an optimal solve gives the metrics an exact length
+        controller.set_solver(SolverKind::IdaStar);
+        controller.load_state("8 6 7 / 2 5 4 / 3 _ 1".parse().unwrap()).unwrap();
+
+        let mut attempts = 0;
+        while controller.is_solver_computing() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+
+        let metrics = controller.all_entropy_metrics();
+        assert!(metrics.actual_solution_length < 999);
+        assert!(metrics.performance.solver_nodes_expanded > 0);
+    }
+
+    #[test]
+    fn test_metrics_estimate_without_exact_solve() {
+        let mut controller = GameController::new(4).unwrap();
+        // The strategic solver never reports an exact length
+        controller.set_solver(SolverKind::Strategic);
+        controller.load_state(crate::model::solver::scramble(4, 60, 3)).unwrap();
+
+        let metrics = fresh_metrics(&mut controller);
+        assert!(metrics.actual_is_estimate);
+        assert!(metrics.actual_solution_length < 999);
+        assert!(metrics.actual_solution_length >= metrics.manhattan_distance);
+
+        let mut controller = GameController::new(6).unwrap();
+        controller.load_state(crate::model::solver::scramble(6, 60, 3)).unwrap();
+        assert!(!fresh_metrics(&mut controller).actual_is_estimate);
+    }
+
+    #[test]
+    fn test_metrics_are_stale_until_measured() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.set_solver(SolverKind::Strategic);
+        controller.load_state(crate::model::solver::scramble(4, 60, 3)).unwrap();
+        let before = fresh_metrics(&mut controller);
+        assert!(!controller.is_measuring());
+
+        let tile = controller.tile_for_slide(Direction::Left).or(controller.tile_for_slide(Direction::Right));
+        click(&mut controller, tile.unwrap());
+        let during = controller.all_entropy_metrics();
+        if during.stale {
+            // The heuristics are current; the length is the previous board's until measured
+            assert!(controller.is_measuring());
+            assert_eq!(during.manhattan_distance, ManhattanDistance.calculate(controller.state()));
+            assert_eq!(during.actual_solution_length, before.actual_solution_length);
+        }
+        let after = fresh_metrics(&mut controller);
+        assert!(!after.stale);
+        assert!(!controller.is_measuring());
+        assert!(after.actual_is_estimate);
+    }
+
+    #[test]
+    fn test_new_random_game() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_random_game();
+        assert!(controller.state().is_solvable());
+        assert_eq!(controller.move_count(), 0);
+    }
+
+    #[test]
+    fn test_ida_star_auto_solve() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_solver(SolverKind::IdaStar);
+        controller.new_game(Difficulty::Easy);
+
+        let mut attempts = 0;
+        while !controller.is_solved() && attempts < 2000 {
+            if !controller.is_auto_solving() && !controller.is_solver_computing() {
+                controller.start_auto_solve();
+            }
+            controller.update_solver_state();
+            if let Some(pos) = controller.auto_solve.as_ref().and_then(|s| {
+                s.solution_path.get(s.current_step).copied()
+            }) {
+                controller.apply_auto_solve_move(pos);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+            attempts += 1;
+        }
+
+        assert!(controller.is_solved());
+        assert!(controller.initial_optimal_length().is_some());
+    }
+
+    #[test]
+    fn test_large_grid_defaults_to_strategic_solver() {
+        assert_eq!(GameController::new(4).unwrap().solver(), SolverKind::AStar);
+        assert_eq!(GameController::new(10).unwrap().solver(), SolverKind::Strategic);
+        assert_eq!(GameController::new(22).unwrap().solver(), SolverKind::Strategic);
+    }
+
+    #[test]
+    fn test_dispatcher_picks_solver_until_one_is_chosen() {
+        let mut controller = GameController::new(5).unwrap();
+        assert!(controller.is_solver_auto());
+        assert_eq!(controller.solver(), SolverKind::IdaStar);
+        assert_eq!(controller.solver_choice().config.timeout, controller.dispatcher().ida_star_timeout);
+
+        controller.set_dispatcher(SolverDispatcher {
+            ida_star_max_size: 4,
+            ..SolverDispatcher::default()
+        });
+        assert_eq!(controller.solver(), SolverKind::Strategic);
+
+        controller.set_solver(SolverKind::AStar);
+        assert!(!controller.is_solver_auto());
+        assert_eq!(controller.solver_choice().config, controller.solver_config());
+        controller.set_solver_auto();
+        assert_eq!(controller.solver(), SolverKind::Strategic);
+    }
+
+    #[test]
+    fn test_auto_solve_falls_back_to_strategic() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Hard);
+        controller.cancel_background_solver();
+        controller.set_solver_config(SolverConfig {
+            max_iterations: 1,
+            ..SolverConfig::default()
+        });
+        assert!(controller.start_auto_solve());
+
+        let mut attempts = 0;
+        while !controller.is_auto_solving() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(5));
+            attempts += 1;
+        }
+        assert!(controller.is_auto_solving());
+    }
+
+    #[test]
+    fn test_auto_solve_reports_solver_error() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Hard);
+        controller.cancel_background_solver();
+        controller.set_solver(SolverKind::Strategic); // No fallback to hide the failure
+        controller.set_solver_config(SolverConfig {
+            timeout: Duration::ZERO,
+            ..SolverConfig::default()
+        });
+        assert!(controller.start_auto_solve());
+
+        let mut attempts = 0;
+        while controller.is_solver_computing() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(5));
+            attempts += 1;
+        }
+        assert!(matches!(controller.solver_error(), Some(SolverError::TimeLimitExceeded { .. })));
+        assert!(matches!(
+            controller.take_auto_solve_error(),
+            Some(AutoSolveError::SolverFailed(SolverError::TimeLimitExceeded { .. }))
+        ));
+        assert!(controller.take_auto_solve_error().is_none()); // Reported once
+    }
+
+    #[test]
+    fn test_auto_solve_already_solved() {
+        let mut controller = GameController::new(3).unwrap();
+
+        // Try to auto-solve when already solved
+        assert!(!controller.start_auto_solve());
+        assert!(!controller.is_auto_solving());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut controller = GameController::new(4).unwrap();
+
+        // Make moves
+        if let Some(moves) = controller.handle_click((3, 2)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+        if let Some(moves) = controller.handle_click((2, 2)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        assert_eq!(controller.move_count(), 2);
+        assert_eq!(controller.tile_move_count(), 2);
+
+        controller.reset();
+
+        assert_eq!(controller.move_count(), 0);
+        assert_eq!(controller.tile_move_count(), 0);
+        assert!(controller.is_solved());
+    }
+
+    #[test]
+    fn test_current_entropy() {
+        let mut controller = GameController::new(4).unwrap();
+        assert_eq!(controller.current_entropy(), 0);
+
+        // Make a move
+        if let Some(moves) = controller.handle_click((3, 2)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        assert!(controller.current_entropy() > 0);
+    }
+
+    #[test]
+    fn test_metrics_caching() {
+        let mut controller = GameController::new(4).unwrap();
+
+        // First call calculates and caches
+        let metrics1 = fresh_metrics(&mut controller);
+        let time1 = metrics1.performance.manhattan_time_micros;
+
+        // Second call should return cached (time should be the same)
+        let metrics2 = controller.all_entropy_metrics();
+        let time2 = metrics2.performance.manhattan_time_micros;
+
+        assert_eq!(time1, time2);
+        assert_eq!(metrics1.manhattan_distance, metrics2.manhattan_distance);
+
+        // After a move, cache should invalidate
+        if let Some(moves) = controller.handle_click((3, 2)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+        let metrics3 = controller.all_entropy_metrics();
+
+        // Values should differ (state changed)
+        assert_ne!(metrics1.manhattan_distance, metrics3.manhattan_distance);
+    }
+
+    #[test]
+    fn test_hint_on_solved_puzzle() {
+        let mut controller = GameController::new(3).unwrap();
+        assert_eq!(controller.get_hint(), None);
+    }
+
+    #[test]
+    fn test_hint_undoes_single_move() {
+        let mut controller = GameController::new(3).unwrap();
+        if let Some(moves) = controller.handle_click((2, 1)) {
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        // The only move that solves the board is sliding the tile back
+        assert_eq!(controller.get_hint(), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_returning_to_a_solved_board_reuses_its_solution() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_solver(SolverKind::IdaStar);
+        controller.load_state(crate::model::solver::scramble(3, 30, 6)).unwrap();
+        wait_for_solver(&mut controller);
+        let path = controller.remaining_solution().unwrap();
+        let length = path.len() as u32;
+
+        // Stray from the solution, then slide the same tile back
+        let empty = controller.state().empty_position();
+        let stray = controller
+            .state()
+            .legal_moves()
+            .into_iter()
+            .find(|&pos| pos != path[0])
+            .unwrap();
+        click(&mut controller, stray);
+        assert_eq!(controller.remaining_solution(), None);
+        assert_ne!(fresh_metrics(&mut controller).actual_solution_length, length);
+        click(&mut controller, empty);
+
+        assert_eq!(controller.all_entropy_metrics().actual_solution_length, length);
+        assert!(!controller.all_entropy_metrics().actual_is_estimate);
+        assert_eq!(controller.get_hint(), Some(path[0]));
+        assert_eq!(controller.remaining_solution(), Some(path));
+        assert!(!controller.is_solver_computing());
+    }
+
+    #[test]
+    fn test_hint_uses_cached_solution() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_game(Difficulty::Easy);
+
+        // Wait for the background metrics solve to cache a path
+        let mut attempts = 0;
+        while controller.is_solver_computing() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+
+        let hint = controller.get_hint().unwrap();
+        if let Some(SolverState::Ready { ref path, .. }) = controller.solver_state {
+            assert_eq!(Some(hint), path.first().copied());
+        } else {
+            panic!("expected cached solution");
+        }
+    }
+
+    #[test]
+    fn test_remaining_solution_follows_the_board() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_game(Difficulty::Easy);
+
+        let mut attempts = 0;
+        while controller.is_solver_computing() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        let path = controller.remaining_solution().expect("metrics solve caches a path");
+        assert!(!path.is_empty());
+
+        // Playing the first move trims it from the front
+        assert!(controller.apply_move(path[0]));
+        assert_eq!(controller.remaining_solution().as_deref(), Some(&path[1..]));
+    }
+
+    #[test]
+    fn test_entropy_kind_follows_the_board_size() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.set_heuristic_cache(HeuristicCache::disabled());
+        assert_eq!(controller.entropy_kind(), Some(EntropyKind::Manhattan));
+
+        controller.set_entropy_kind(EntropyKind::WalkingDistance).unwrap();
+        controller.new_game(Difficulty::Medium);
+        while controller.is_shuffling() {
+            controller.update_shuffle_state();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let walking = WalkingDistance::new(4).unwrap();
+        assert_eq!(controller.current_entropy(), walking.calculate(controller.state()));
+        assert!(controller.target_range(Difficulty::Medium).contains(&controller.current_entropy()));
+
+        // A 3×3 table replaces the 4×4 one; 5×5 has none, so Manhattan takes over
+        controller.load_state("1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()).unwrap();
+        assert_eq!(controller.entropy_kind(), Some(EntropyKind::WalkingDistance));
+        assert_eq!(controller.current_entropy(), 2);
+        controller.load_state(PuzzleState::new(5).unwrap()).unwrap();
+        assert_eq!(controller.entropy_kind(), Some(EntropyKind::Manhattan));
+        assert!(controller.set_entropy_kind(EntropyKind::WalkingDistance).is_err());
+        assert_eq!(controller.entropy_kind(), Some(EntropyKind::Manhattan));
+
+        controller.set_entropy_calculator(Box::new(EnhancedHeuristic));
+        assert_eq!(controller.entropy_kind(), None);
+    }
+
+    #[test]
+    fn test_distance_trail_follows_the_moves() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.load_state("1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()).unwrap();
+        assert_eq!(controller.distance_trail(), [2]);
+
+        for pos in [(0, 1), (1, 1), (2, 1)] {
+            assert!(controller.apply_move(pos));
+            controller.complete_move_sequence();
+        }
+        // Mid-chain boards add nothing until the move completes
+        assert!(controller.apply_move((2, 2)));
+        assert_eq!(controller.distance_trail(), [2, 3, 2, 1]);
+        controller.complete_move_sequence();
+        assert_eq!(controller.distance_trail(), [2, 3, 2, 1, 0]);
+
+        controller.reset();
+        assert_eq!(controller.distance_trail(), [0]);
+    }
+
+    #[test]
+    fn test_analysis_rates_the_recorded_moves() {
+        let mut controller = GameController::new(3).unwrap();
+        // Moves are rated against IDA*, not the selected solver
+        controller.set_solver(SolverKind::Greedy);
+        controller.load_state("1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()).unwrap();
+        assert!(!controller.start_analysis()); // Not solved yet
+
+        // A slide away and back, then both solving tiles as one chain move
+        for chain in [vec![(0, 1)], vec![(1, 1)], vec![(2, 1), (2, 2)]] {
+            for pos in chain {
+                assert!(controller.apply_move(pos));
+            }
+            controller.complete_move_sequence();
+        }
+        assert!(controller.is_solved());
+        let (start, slides) = controller.replay().unwrap();
+        assert_eq!(start.to_notation(), "1 2 3 / 4 _ 6 / 7 5 8");
+        assert_eq!(slides, [(0, 1), (1, 1), (2, 1), (2, 2)]);
+        assert!(controller.start_analysis());
+        assert!(!controller.start_analysis()); // Already running
+
+        let mut attempts = 0;
+        while !controller.update_analysis() && attempts < 1000 {
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        let analysis = controller.analysis().unwrap().unwrap();
+        let qualities: Vec<_> = analysis.moves.iter().map(|m| m.quality).collect();
+        assert_eq!(qualities, [MoveQuality::Blunder, MoveQuality::Optimal, MoveQuality::Optimal]);
+        assert_eq!(analysis.moves[2].tiles, vec![(2, 1), (2, 2)]);
+        assert!(!controller.can_analyze());
+
+        controller.new_game(Difficulty::Easy);
+        assert!(controller.analysis().is_none());
+    }
+
+    #[test]
+    fn test_playback_stops_at_the_end_of_the_path() {
+        let mut controller = GameController::new(3).unwrap();
+        assert_eq!(
+            controller.start_playback(vec![(2, 1), (0, 0)]),
+            Err(PuzzleError::InvalidSolution {
+                step: 2,
+                reason: "no tile at (0, 0) can slide into the empty cell".to_string(),
+            })
+        );
+        assert!(!controller.is_auto_solving());
+
+        assert_eq!(controller.start_playback(vec![(2, 1), (1, 1)]), Ok(()));
+        assert!(controller.is_auto_solving());
+        assert_eq!(controller.start_playback(vec![(1, 2)]), Err(PuzzleError::AutoSolving));
+        assert!(controller.apply_auto_solve_move((2, 1)));
+        assert!(controller.apply_auto_solve_move((1, 1)));
+
+        // The path did not solve the board, and playback ends with it
+        assert!(!controller.is_auto_solving());
+        assert!(!controller.is_solved());
+        assert_eq!(controller.state().empty_position(), (1, 1));
+    }
+
+    #[test]
+    fn test_hint_is_legal_move() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Medium);
+
+        let hint = controller.get_hint().unwrap();
+        assert!(controller.state().is_legal(hint));
+    }
+
+    #[test]
+    fn test_game_clock_pause_resume() {
+        let mut clock = GameClock::new();
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+        clock.start();
+        std::thread::sleep(Duration::from_millis(5));
+        clock.pause();
+        let banked = clock.elapsed();
+        assert!(banked >= Duration::from_millis(5));
+        assert!(!clock.is_running());
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.elapsed(), banked);
+        clock.reset();
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_clock_starts_on_first_move() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.new_game(Difficulty::Easy);
+        assert!(!controller.is_timer_running());
+
+        let tile = controller.state().legal_moves()[0];
+        assert!(controller.handle_click(tile).is_some());
+        assert!(controller.is_timer_running());
+    }
+
+    #[test]
+    fn test_solving_records_best_time() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_game(Difficulty::Easy);
+
+        // Undo the shuffle via the solver path, playing it as manual moves
+        let path = AStarSolver::new().solve_with_path(controller.state()).unwrap();
+        for pos in path {
+            let moves = controller.handle_click(pos).unwrap();
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        assert!(controller.is_solved());
+        assert!(!controller.is_timer_running());
+        assert!(controller.is_new_best());
+        assert!(controller.best_time(Difficulty::Easy).is_some());
+        assert!(controller.best_time(Difficulty::Hard).is_none());
+    }
+
+    #[test]
+    fn test_completed_game_recorded_in_stats() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_game(Difficulty::Easy);
+
+        // Let the background solver compute the optimal length of the shuffle
+        assert!(controller.is_par_pending());
+        let mut attempts = 0;
+        while controller.is_par_pending() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        let optimal = controller.initial_optimal_length();
+        assert!(optimal.is_some());
+        assert!(!controller.is_par_pending());
+
+        let path = AStarSolver::new().solve_with_path(controller.state()).unwrap();
+        for pos in path {
+            let moves = controller.handle_click(pos).unwrap();
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
+
+        assert_eq!(controller.stats().games_played(), 1);
+        let record = &controller.stats().games()[0];
+        assert_eq!(record.optimal_length, optimal);
+        assert_eq!(record.efficiency(), Some(1.0));
+        assert_eq!(record.tile_moves, record.moves); // Every step was a single tile
+        assert_eq!(record.grade(), Some(Grade::S));
+        assert!(!record.assisted);
+
+        // The win is reported exactly once
+        let record = record.clone();
+        assert_eq!(controller.take_finished_game(), Some(record));
+        assert_eq!(controller.take_finished_game(), None);
+
+        // Solving at par on the first try unlocks both achievements, also once
+        assert_eq!(
+            controller.take_new_achievements(),
+            vec![Achievement::FirstSolve, Achievement::SubPar]
+        );
+        assert!(controller.take_new_achievements().is_empty());
+        assert!(controller.achievements().is_unlocked(Achievement::SubPar));
+    }
+
+    #[test]
+    fn test_move_limit_challenge_is_lost_and_recorded() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_game_mode(GameMode::MoveLimit { slack: 0 });
+        controller.new_game(Difficulty::Easy);
+        wait_for_solver(&mut controller);
+        let par = controller.initial_optimal_length().unwrap() as usize;
+        assert_eq!(controller.moves_remaining(), Some(par));
+
+        // Slide a tile out and back until the limit is spent, never solving the board
+        while controller.challenge_failure().is_none() {
+            let empty = controller.state().empty_position();
+            let tile = controller
+                .state()
+                .legal_moves()
+                .into_iter()
+                .find(|&tile| {
+                    let mut after = controller.state().clone();
+                    after.apply_immediate_move(tile);
+                    !after.is_solved()
+                })
+                .unwrap();
+            for pos in [tile, empty] {
+                controller.handle_click(pos).unwrap();
+                controller.apply_move(pos);
+                controller.complete_move_sequence();
+                if controller.challenge_failure().is_some() {
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(controller.challenge_failure(), Some(ChallengeFailure::OutOfMoves));
+        assert_eq!(controller.tile_move_count(), par);
+        assert!(!controller.is_timer_running());
+        let tile = controller.state().legal_moves()[0];
+        assert_eq!(controller.try_handle_click(tile), Err(PuzzleError::ChallengeLost));
+
+        // Lost games go to the statistics, but not to the victory screen
+        assert_eq!(controller.take_finished_game(), None);
+        let record = &controller.stats().games()[0];
+        assert!(record.failed);
+        assert_eq!(record.mode, GameMode::MoveLimit { slack: 0 });
+        assert_eq!(record.grade(), None);
+        assert_eq!(controller.stats().challenge_results(), (0, 1));
+    }
+
+    #[test]
+    fn test_countdown_challenge_and_free_play() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_game_mode(GameMode::Countdown { seconds: 0 });
+        controller.new_game(Difficulty::Easy);
+        assert_eq!(controller.challenge(), GameMode::Countdown { seconds: 0 });
+        assert_eq!(controller.time_remaining(), Some(Duration::ZERO));
+        assert_eq!(controller.update_challenge(), Some(ChallengeFailure::OutOfTime));
+        assert_eq!(controller.update_challenge(), None); // Reported once
+        assert!(controller.snapshot().is_none());
+
+        controller.set_game_mode(GameMode::Free);
+        controller.new_game(Difficulty::Easy);
+        assert_eq!(controller.challenge_failure(), None);
+        assert_eq!(controller.time_remaining(), None);
+        assert_eq!(controller.update_challenge(), None);
+    }
+
+    #[test]
+    fn test_hints_are_counted_per_game() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_game(Difficulty::Hard);
+        assert_eq!(controller.hints_used(), 0);
+        assert!(controller.get_hint().is_some());
+        assert!(controller.get_hint().is_some());
+        assert_eq!(controller.hints_used(), 2);
+
+        controller.new_game(Difficulty::Hard);
+        assert_eq!(controller.hints_used(), 0);
+    }
+
+    #[test]
+    fn test_auto_solve_interval() {
+        let mut controller = GameController::new(3).unwrap();
+        assert_eq!(controller.auto_solve_interval(), DEFAULT_AUTO_SOLVE_INTERVAL);
+        controller.load_state("1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()).unwrap();
+
+        controller.set_auto_solve_interval(Duration::from_secs(60));
+        assert!(controller.start_auto_solve());
+        let mut attempts = 0;
+        while !controller.is_auto_solving() && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        assert_eq!(controller.get_next_auto_solve_move(), None);
+
+        // Changing the interval applies to the running auto-solve
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(controller.get_next_auto_solve_move().is_some());
+    }
+
+    #[test]
+    fn test_auto_solve_speed_and_pause() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.set_auto_solve_speed(50.0);
+        assert_eq!(controller.auto_solve_speed(), MAX_AUTO_SOLVE_SPEED);
+
+        // A minute at normal pace is six seconds at 10×
+        controller.set_auto_solve_interval(Duration::from_secs(60));
+        controller.start_playback(vec![(2, 1), (2, 0), (1, 0)]).unwrap();
+        assert_eq!(controller.auto_solve.as_ref().unwrap().move_interval, Duration::from_secs(6));
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(controller.get_next_auto_solve_move().is_some());
+
+        controller.set_auto_solve_paused(true);
+        assert!(controller.is_auto_solve_paused());
+        assert_eq!(controller.get_next_auto_solve_move(), None);
+        controller.set_auto_solve_paused(false);
+        assert_eq!(controller.get_next_auto_solve_move(), Some((2, 1)));
+
+        // Skipping plays the rest while paused, and records every move
+        controller.set_auto_solve_paused(true);
+        assert!(controller.apply_auto_solve_move((2, 1)));
+        assert_eq!(controller.skip_auto_solve_to_end(), 2);
+        assert!(!controller.is_auto_solving());
+        assert!(!controller.is_auto_solve_paused());
+        assert_eq!(controller.state().empty_position(), (1, 0));
+        assert_eq!(controller.move_count(), 3);
+        assert_eq!(controller.skip_auto_solve_to_end(), 0);
+    }
+
+    #[test]
+    fn test_partial_auto_solve_hands_back_control() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.load_state(crate::model::solver::scramble(4, 40, 9)).unwrap();
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(!controller.start_partial_auto_solve(AssistTarget::Moves(0)));
+
+        assert!(controller.start_partial_auto_solve(AssistTarget::Moves(3)));
+        assert!(controller.is_partial_auto_solve());
+        assert!(!controller.start_partial_auto_solve(AssistTarget::Rows(1)));
+        wait_for_solver(&mut controller);
+        assert_eq!(controller.skip_auto_solve_to_end(), 3);
+        assert!(!controller.is_auto_solving());
+        assert!(!controller.is_partial_auto_solve());
+        assert_eq!(controller.move_count(), 3);
+
+        // A full auto-solve afterwards is not cut short
+        assert!(controller.start_auto_solve());
+        wait_for_solver(&mut controller);
+        controller.skip_auto_solve_to_end();
+        assert!(controller.is_solved());
+    }
+
+    #[test]
+    fn test_partial_auto_solve_by_rows_and_tiles() {
+        let mut controller = GameController::new(5).unwrap();
+        controller.load_state(crate::model::solver::scramble(5, 300, 4)).unwrap();
+        controller.set_auto_solve_interval(Duration::ZERO);
+
+        assert!(controller.start_partial_auto_solve(AssistTarget::Rows(1)));
+        wait_for_solver(&mut controller);
+        assert!(controller.skip_auto_solve_to_end() > 0);
+        assert!(row_solved(controller.state(), 0));
+        assert!(!controller.is_solved());
+        assert!(!controller.start_partial_auto_solve(AssistTarget::Rows(1)));
+        assert!(controller.start_partial_auto_solve(AssistTarget::NextRow));
+        wait_for_solver(&mut controller);
+        controller.skip_auto_solve_to_end();
+        assert!(row_solved(controller.state(), 1));
+
+        let home = tiles_home(controller.state());
+        assert!(controller.start_partial_auto_solve(AssistTarget::Tiles(2)));
+        wait_for_solver(&mut controller);
+        controller.skip_auto_solve_to_end();
+        assert!(tiles_home(controller.state()) >= home + 2);
+        assert!(!controller.is_auto_solving());
+    }
+
+    /// Solves the active board through the same calls the presenter makes
+    fn play_solution(controller: &mut GameController) {
+        let path = AStarSolver::new().solve_with_path(controller.state()).unwrap();
+        for pos in path {
+            let moves = controller.handle_click(pos).unwrap();
+            for move_pos in moves {
+                controller.apply_move(move_pos);
+            }
+            controller.complete_move_sequence();
+        }
     }
 
-    /// Returns true if solver is computing for auto-solve (not just metrics)
-    pub fn is_solver_computing_for_autosolve(&self) -> bool {
-        matches!(self.solver_state, Some(SolverState::Computing(_, _, true)))
+    /// Polls until the metrics of the current board are measured
+    fn fresh_metrics(controller: &mut GameController) -> EntropyMetrics {
+        let mut attempts = 0;
+        loop {
+            let metrics = controller.all_entropy_metrics();
+            if !metrics.stale || attempts >= 1000 {
+                return metrics;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+            attempts += 1;
+        }
     }
 
-    /// Stops auto-solve mode and cancels any running solver
-    pub fn stop_auto_solve(&mut self) {
-        println!("\n=== AUTO-SOLVE STOPPED ===");
-        if let Some(progress) = self.auto_solve_progress() {
-            println!("Stopped at move {}/{}", progress.0, progress.1);
+    /// Polls until the background solve and the search for par finish
+    fn wait_for_solver(controller: &mut GameController) {
+        let mut attempts = 0;
+        while (controller.is_solver_computing() || controller.is_par_pending()) && attempts < 1000 {
+            controller.update_solver_state();
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
         }
-        self.auto_solve = None;
-        self.solver_state = None;
     }
 
-    /// Returns whether auto-solve is active
-    pub fn is_auto_solving(&self) -> bool {
-        self.auto_solve.is_some()
+    /// Makes a manual move by clicking the tile at `pos`
+    fn click(controller: &mut GameController, pos: Position) {
+        let moves = controller.handle_click(pos).unwrap();
+        for move_pos in moves {
+            controller.apply_move(move_pos);
+        }
+        controller.complete_move_sequence();
     }
 
-    /// Returns auto-solve progress (current_step, total_steps)
-    pub fn auto_solve_progress(&self) -> Option<(usize, usize)> {
-        self.auto_solve.as_ref().map(|s| s.progress())
-    }
+    #[test]
+    fn test_advance_solution_step() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.load_state(crate::model::solver::scramble(3, 20, 5)).unwrap();
+        wait_for_solver(&mut controller);
 
-    /// Checks if auto-solve has a move ready to execute
-    /// Returns the position to move if it's time for the next move
-    pub fn get_next_auto_solve_move(&mut self) -> Option<Position> {
-        if let Some(ref auto_solve) = self.auto_solve {
-            if auto_solve.is_complete() {
-                return None;
-            }
+        let first = controller.advance_solution_step().unwrap();
+        assert_eq!(controller.state().empty_position(), first);
+        assert_eq!(controller.move_count(), 1);
+        assert!(controller.assisted);
 
-            let elapsed = auto_solve.last_move_time.elapsed();
-            if elapsed >= auto_solve.move_interval {
-                return Some(auto_solve.solution_path[auto_solve.current_step]);
-            }
-        }
-        None
+        // A manual move along the solution keeps the cached path
+        let hint = controller.get_hint().unwrap();
+        click(&mut controller, hint);
+        let empty_before = controller.state().empty_position();
+        assert!(controller.advance_solution_step().is_some());
+
+        // Pushing the tile straight back leaves the path, so it is solved again
+        click(&mut controller, empty_before);
+        assert!(controller.advance_solution_step().is_none());
+        assert!(controller.is_solver_computing());
+
+        wait_for_solver(&mut controller);
+        while controller.advance_solution_step().is_some() {}
+        assert!(controller.is_solved());
     }
 
-    /// Executes an auto-solve move and advances to the next step
-    /// Should be called after animation completes
-    pub fn apply_auto_solve_move(&mut self, pos: Position) -> bool {
-        let should_clear;
+    #[test]
+    fn test_seeded_games_match() {
+        let mut first = GameController::new(4).unwrap();
+        let mut second = GameController::new(4).unwrap();
+        first.new_seeded_game(Difficulty::Hard, 2024);
+        second.new_seeded_game(Difficulty::Hard, 2024);
+        assert_eq!(first.state().to_layout(), second.state().to_layout());
+        assert_eq!(first.initial_state().unwrap().to_layout(), first.state().to_layout());
+    }
 
-        if let Some(ref mut auto_solve) = self.auto_solve {
-            if self.state.apply_immediate_move(pos) {
-                auto_solve.current_step += 1;
-                auto_solve.last_move_time = Instant::now();
+    #[test]
+    fn test_new_games_differ_from_recent_ones() {
+        let reshuffled = || {
+            let mut controller = GameController::new(3).unwrap();
+            controller.new_seeded_game(Difficulty::Easy, 7);
+            let first = PackedState::from_state(controller.state());
+            // Seed 7 gives the same board again, so a new game must shuffle once more
+            controller.start_shuffled_game(Difficulty::Easy, 7, DISTINCT_SHUFFLE_ATTEMPTS).unwrap();
+            let second = PackedState::from_state(controller.state());
+            assert!(cells_apart(&first, &second) >= 3);
+            (second, controller.last_shuffle_result().unwrap().seed)
+        };
+        let (board, seed) = reshuffled();
+        assert_ne!(seed, 7);
+        assert_eq!(reshuffled(), (board.clone(), seed)); // Deterministic under seeding
 
-                // Check if complete before we drop the borrow
-                should_clear = auto_solve.is_complete();
-            } else {
-                return false;
-            }
-        } else {
-            return false;
-        }
+        // The recorded seed repeats the board, resemblance or not
+        let mut repeat = GameController::new(3).unwrap();
+        repeat.new_seeded_game(Difficulty::Easy, seed);
+        repeat.new_seeded_game(Difficulty::Easy, seed);
+        assert_eq!(PackedState::from_state(repeat.state()), board);
+        assert_eq!(repeat.recent_shuffles.len(), 2);
+    }
 
-        // Now that auto_solve borrow is dropped, we can mutate self again
-        self.history.record_move();
-        self.invalidate_cache();
+    #[test]
+    fn test_background_shuffle() {
+        let mut controller = GameController::new(5).unwrap();
+        controller.new_game_in_background(Difficulty::Hard);
+        assert!(controller.is_shuffling());
+        // The board stays solved and refuses moves until the shuffle lands
+        assert!(controller.last_shuffle_result().is_none());
+        assert!(controller.handle_click((4, 3)).is_none());
 
-        if should_clear {
-            self.auto_solve = None;
+        let mut attempts = 0;
+        while !controller.update_shuffle_state() && attempts < 1000 {
+            std::thread::sleep(Duration::from_millis(10));
+            attempts += 1;
         }
-        true
-    }
-}
+        assert!(!controller.is_shuffling());
+        assert!(!controller.is_solved());
+        assert!(controller.state().is_solvable());
+        assert_eq!(controller.initial_state().unwrap().to_layout(), controller.state().to_layout());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // The recorded seed repeats the board
+        let seed = controller.last_shuffle_result().unwrap().seed;
+        let mut repeat = GameController::new(5).unwrap();
+        repeat.new_seeded_game(Difficulty::Hard, seed);
+        assert_eq!(repeat.state().to_layout(), controller.state().to_layout());
 
-    #[test]
-    fn test_new_controller() {
-        let controller = GameController::new(4).unwrap();
-        assert_eq!(controller.move_count(), 0);
+        // Resetting drops a shuffle that has not finished
+        controller.new_game_in_background(Difficulty::Hard);
+        controller.reset();
+        assert!(!controller.is_shuffling());
+        assert!(!controller.update_shuffle_state());
         assert!(controller.is_solved());
     }
 
     #[test]
-    fn test_new_game_shuffles() {
+    fn test_practice_game() {
         let mut controller = GameController::new(4).unwrap();
-        controller.new_game(Difficulty::Easy);
+        controller
+            .new_practice_game(Region::bottom_rows(4, 2), Difficulty::Medium)
+            .unwrap();
+        assert!(!controller.is_solved());
+        assert_eq!(&controller.state().to_layout()[..2], &PuzzleState::new(4).unwrap().to_layout()[..2]);
+        assert!(controller.last_shuffle_result().is_some());
+        assert!(controller.initial_state().is_some());
 
-        assert_eq!(controller.move_count(), 0); // History reset
-        assert!(!controller.is_solved()); // Should be shuffled
+        // The goal's empty cell must lie in the region
+        controller.set_goal(GoalSpec::BlankFirst);
+        assert!(controller
+            .new_practice_game(Region::bottom_rows(4, 2), Difficulty::Medium)
+            .is_err());
+        assert!(controller.is_solved());
     }
 
     #[test]
-    fn test_handle_click_immediate_move() {
+    fn test_record_and_play_macro() {
         let mut controller = GameController::new(4).unwrap();
-        let result = controller.handle_click((3, 2));
-
-        assert!(result.is_some());
+        assert!(controller.finish_recording("nothing").is_err());
+        assert!(controller.start_recording());
+        assert!(!controller.start_recording());
 
-        // Apply the moves (simulating what presenter does after animation)
-        if let Some(moves) = result {
-            for move_pos in moves {
-                controller.apply_move(move_pos);
+        // Cycle three tiles around the bottom-right 2×2 block
+        for pos in [(3, 2), (2, 2), (2, 3), (3, 3)] {
+            let moves = controller.handle_click(pos).unwrap();
+            for tile in moves {
+                assert!(controller.apply_move(tile));
             }
             controller.complete_move_sequence();
         }
+        assert_eq!(controller.recorded_moves(), 4);
+        assert!(controller.finish_recording("  ").is_err());
+        assert!(controller.is_recording());
+        let pattern = controller.finish_recording("3-cycle").unwrap();
+        assert_eq!(pattern.name(), "3-cycle");
+        assert!(!controller.is_recording());
 
-        assert_eq!(controller.move_count(), 1);
-        assert!(!controller.is_solved());
+        // Playing is like a click: moves come back for the presenter to apply
+        let before = controller.state().to_layout();
+        let moves = controller.play_macro(&pattern).unwrap();
+        assert_eq!(moves, vec![(3, 2), (2, 2), (2, 3), (3, 3)]);
+        for tile in moves {
+            assert!(controller.apply_move(tile));
+        }
+        controller.complete_move_sequence();
+        assert_ne!(controller.state().to_layout(), before);
+        assert_eq!(controller.move_count(), 5);
+
+        // A macro that leaves the board does not play
+        let off_board = RelativePattern::new("up", vec![Direction::Up]);
+        assert_eq!(controller.play_macro(&off_board), None);
+
+        controller.start_recording();
+        assert_eq!(
+            controller.finish_recording("empty").unwrap_err(),
+            PuzzleError::InvalidMacro("no moves were recorded".to_string())
+        );
     }
 
     #[test]
-    fn test_handle_click_chain_move() {
+    fn test_technique_suggestions() {
         let mut controller = GameController::new(4).unwrap();
-        let result = controller.handle_click((3, 0));
-
-        assert!(result.is_some());
-        let moves = result.unwrap();
-        assert_eq!(moves.len(), 3); // Chain of 3 moves
+        let suggestions = controller.technique_suggestions();
+        let names: Vec<&str> = suggestions.iter().map(|s| s.pattern.name()).collect();
+        assert_eq!(names, vec!["Corner rotation", "Long rotation"]);
+        // Every rotation scrambles a solved board
+        assert!(suggestions.iter().all(|s| s.distance_change > 0));
 
-        // Apply the moves (simulating what presenter does after animation)
-        for move_pos in moves {
-            controller.apply_move(move_pos);
+        // Undoing a corner rotation is suggested as the best orientation
+        let rotation = suggestions[0].clone();
+        for pos in controller.play_macro(&rotation.pattern).unwrap() {
+            controller.apply_move(pos);
+        }
+        controller.complete_move_sequence();
+        let undo = &controller.technique_suggestions()[0];
+        assert_eq!(undo.distance_change, -rotation.distance_change);
+        for &pos in &undo.moves {
+            controller.apply_move(pos);
         }
         controller.complete_move_sequence();
+        assert!(controller.is_solved());
 
-        assert_eq!(controller.move_count(), 1); // Counts as one move
-        assert_eq!(controller.state().empty_position(), (3, 0));
+        controller.set_pattern_catalog(RelativePatternCatalog::new(Vec::new()));
+        assert!(controller.technique_suggestions().is_empty());
     }
 
     #[test]
-    fn test_handle_click_invalid_move() {
-        let mut controller = GameController::new(4).unwrap();
-        let result = controller.handle_click((0, 0));
-
-        assert!(result.is_none()); // Diagonal, not valid
-        assert_eq!(controller.move_count(), 0);
+    fn test_tile_for_slide() {
+        let controller = GameController::new(3).unwrap(); // Empty cell at (2, 2)
+        assert_eq!(controller.tile_for_slide(Direction::Right), Some((2, 1)));
+        assert_eq!(controller.tile_for_slide(Direction::Down), Some((1, 2)));
+        assert_eq!(controller.tile_for_slide(Direction::Left), None);
+        assert_eq!(controller.tile_for_slide(Direction::Up), None);
     }
 
     #[test]
-    fn test_handle_click_empty_cell() {
+    fn test_snapshot_and_restore() {
         let mut controller = GameController::new(4).unwrap();
-        let empty_pos = controller.state().empty_position();
-        let result = controller.handle_click(empty_pos);
+        controller.new_seeded_game(Difficulty::Easy, 7);
+        assert!(controller.snapshot().is_none()); // Nothing played yet
 
-        assert!(result.is_none());
-        assert_eq!(controller.move_count(), 0);
+        let tile = controller.get_hint().unwrap();
+        for pos in controller.handle_click(tile).unwrap() {
+            controller.apply_move(pos);
+        }
+        controller.complete_move_sequence();
+        let snapshot = controller.snapshot().unwrap();
+
+        let mut restored = GameController::new(3).unwrap();
+        restored.restore(&snapshot).unwrap();
+        assert_eq!(restored.state().to_layout(), controller.state().to_layout());
+        assert_eq!(restored.move_count(), 1);
+        assert_eq!(restored.initial_state().map(PuzzleState::to_layout), snapshot.initial_layout);
+        assert_eq!(restored.snapshot(), Some(snapshot));
+        assert!(!restored.clock.is_running()); // Resumes with the next move
     }
 
     #[test]
-    fn test_auto_solve_simple_puzzle() {
+    fn test_tile_for_scroll() {
+        let controller = GameController::new(3).unwrap(); // Empty cell at (2, 2)
+        assert_eq!(controller.tile_for_scroll((0, 2), Direction::Down), Some((1, 2)));
+        assert_eq!(controller.tile_for_scroll((2, 0), Direction::Right), Some((2, 1)));
+        assert_eq!(controller.tile_for_scroll((0, 1), Direction::Down), None); // Other column
+        assert_eq!(controller.tile_for_scroll((1, 2), Direction::Right), None); // Other row
+        assert_eq!(controller.tile_for_scroll((0, 2), Direction::Up), None); // Nothing below
+    }
+
+    #[test]
+    fn test_solution_order() {
         let mut controller = GameController::new(3).unwrap();
+        assert_eq!(controller.solution_order(), None); // Solved boards have nothing to order
+        controller.load_state("1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()).unwrap();
+        assert_eq!(controller.solution_order(), None); // Still solving
+        wait_for_solver(&mut controller);
 
-        // Make a few moves to scramble
-        if let Some(moves) = controller.handle_click((2, 1)) {
-            for move_pos in moves {
-                controller.apply_move(move_pos);
-            }
-            controller.complete_move_sequence();
-        }
-        if let Some(moves) = controller.handle_click((2, 0)) {
-            for move_pos in moves {
-                controller.apply_move(move_pos);
-            }
-            controller.complete_move_sequence();
-        }
+        // Tile 5 moves first, then tile 8
+        let order = controller.solution_order().unwrap();
+        assert_eq!(order, HashMap::from([((2, 1), 0), ((2, 2), 1)]));
 
-        // Start auto-solve (spawns background thread)
+        // After moving tile 5, tile 8 is next
+        click(&mut controller, (2, 1));
+        assert_eq!(controller.solution_order().unwrap(), HashMap::from([((2, 2), 0)]));
+    }
+
+    #[test]
+    fn test_auto_solve_ignores_stale_solution() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.load_state(crate::model::solver::scramble(3, 20, 9)).unwrap();
+        wait_for_solver(&mut controller);
+
+        // Move off the cached path, then auto-solve: the stale path must not be replayed
+        let off_path = controller
+            .state()
+            .legal_moves()
+            .into_iter()
+            .find(|&pos| Some(pos) != controller.get_hint())
+            .unwrap();
+        click(&mut controller, off_path);
         assert!(controller.start_auto_solve());
-        assert!(controller.is_solver_computing());
+        assert!(controller.is_solver_computing_for_autosolve());
 
-        // Poll until solver completes (should be fast for 3x3)
-        let mut attempts = 0;
-        while !controller.update_solver_state() && attempts < 1000 {
-            std::thread::sleep(Duration::from_millis(10));
-            attempts += 1;
-        }
+        wait_for_solver(&mut controller);
+        let auto_solve = controller.auto_solve.as_ref().unwrap();
+        assert!(path_solves(controller.state(), &auto_solve.solution_path));
+    }
 
-        // Should now be auto-solving (solution ready)
-        assert!(controller.is_auto_solving());
+    #[test]
+    fn test_search_tracing() {
+        let mut controller = GameController::new(4).unwrap();
+        assert!(controller.search_trace().is_none());
+        controller.set_search_tracing(true);
+        controller.load_state(crate::model::solver::scramble(4, 60, 3)).unwrap();
+        let version = controller.state_version;
+        wait_for_solver(&mut controller);
 
-        // Get expected path length
-        let (_, total_steps) = controller.auto_solve_progress().unwrap();
+        let trace = controller.search_trace().unwrap();
+        assert!(trace.traces(controller.solver(), version));
+        assert!(trace.is_finished());
+        assert!(trace.samples().windows(2).all(|w| w[0].nodes_explored < w[1].nodes_explored));
 
-        // Force immediate execution by manipulating time
-        if let Some(ref mut auto_solve) = controller.auto_solve {
-            auto_solve.move_interval = Duration::from_millis(0); // Instant moves for testing
-        }
+        controller.set_search_tracing(false);
+        assert!(controller.search_trace().is_none());
+    }
 
-        // Execute all moves
-        for _ in 0..=total_steps {
-            if let Some(next_move) = controller.get_next_auto_solve_move() {
-                controller.apply_auto_solve_move(next_move);
-            }
-        }
+    #[test]
+    fn test_strategic_auto_solve_is_captioned() {
+        let mut controller = GameController::new(4).unwrap();
+        controller.set_solver(SolverKind::Strategic);
+        controller.load_state(crate::model::solver::scramble(4, 80, 2)).unwrap();
+        wait_for_solver(&mut controller);
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(controller.auto_solve_step().is_none());
 
-        // Should be solved
-        assert!(controller.state().is_solved());
-        assert!(!controller.is_auto_solving()); // Completes and stops
+        // Captions survive the cached path being trimmed by a move along it
+        let next = controller.get_hint().unwrap();
+        click(&mut controller, next);
+        assert!(controller.start_auto_solve());
+        assert!(controller.auto_solve_step().is_some());
+        while let Some(pos) = controller.get_next_auto_solve_move() {
+            assert!(controller.auto_solve_step().is_some() || controller.is_solved());
+            assert!(controller.apply_auto_solve_move(pos));
+        }
+        assert!(controller.is_solved());
+        assert!(controller.auto_solve_step().is_none());
     }
 
     #[test]
-    fn test_auto_solve_already_solved() {
+    fn test_hint_follows_an_interrupted_auto_solve() {
         let mut controller = GameController::new(3).unwrap();
+        controller.load_state(crate::model::solver::scramble(3, 20, 11)).unwrap();
+        wait_for_solver(&mut controller);
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(controller.start_auto_solve());
+        let next = controller.get_next_auto_solve_move().unwrap();
 
-        // Try to auto-solve when already solved
-        assert!(!controller.start_auto_solve());
+        // Slide a tile off the path, as the end of a chain under the auto-solve would
+        let off = controller.state().legal_moves().into_iter().find(|&pos| pos != next).unwrap();
+        assert!(controller.apply_auto_solve_move(off));
+
+        // The stale step would be an illegal move now; the hint is legal and
+        // the auto-solve solves the changed board again
+        let hint = controller.get_hint().unwrap();
+        assert_ne!(hint, next);
+        assert!(controller.state().legal_moves().contains(&hint));
         assert!(!controller.is_auto_solving());
+        assert!(controller.is_solver_computing_for_autosolve());
+
+        // Once the new solution lands, the hints follow it
+        wait_for_solver(&mut controller);
+        assert!(controller.is_auto_solving());
+        assert_eq!(controller.get_hint(), controller.get_next_auto_solve_move());
     }
 
     #[test]
-    fn test_reset() {
-        let mut controller = GameController::new(4).unwrap();
+    fn test_auto_solve_resyncs_after_interruption() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.load_state(crate::model::solver::scramble(3, 20, 11)).unwrap();
+        wait_for_solver(&mut controller);
+        controller.set_auto_solve_interval(Duration::ZERO);
+        assert!(controller.start_auto_solve());
 
-        // Make moves
-        if let Some(moves) = controller.handle_click((3, 2)) {
-            for move_pos in moves {
-                controller.apply_move(move_pos);
-            }
-            controller.complete_move_sequence();
+        // A move off the path (the end of a chain) is made without advancing
+        let empty = controller.state().empty_position();
+        let next = controller.get_next_auto_solve_move().unwrap();
+        let off = controller.state().legal_moves().into_iter().find(|&pos| pos != next).unwrap();
+        assert!(controller.apply_auto_solve_move(off));
+        assert_eq!(controller.auto_solve_progress().map(|(step, _)| step), Some(0));
+        assert!(controller.is_auto_solving());
+
+        // Sliding it back returns to the path, which the auto-solve picks up again
+        assert!(controller.apply_auto_solve_move(empty));
+        assert_eq!(controller.get_next_auto_solve_move(), Some(next));
+        assert!(controller.apply_auto_solve_move(next));
+        assert_eq!(controller.auto_solve_progress().map(|(step, _)| step), Some(1));
+
+        // Leaving the path for good solves the board again
+        let next = controller.get_next_auto_solve_move().unwrap();
+        let off = controller.state().legal_moves().into_iter().find(|&pos| pos != next).unwrap();
+        assert!(controller.apply_auto_solve_move(off));
+        assert!(controller.get_next_auto_solve_move().is_none());
+        assert!(!controller.is_auto_solving());
+        assert!(controller.is_solver_computing_for_autosolve());
+
+        wait_for_solver(&mut controller);
+        while let Some(pos) = controller.get_next_auto_solve_move() {
+            assert!(controller.apply_auto_solve_move(pos));
         }
-        if let Some(moves) = controller.handle_click((2, 2)) {
-            for move_pos in moves {
-                controller.apply_move(move_pos);
-            }
-            controller.complete_move_sequence();
+        assert!(controller.is_solved());
+    }
+
+    #[test]
+    fn test_layered_game() {
+        let mut controller = GameController::new(3).unwrap();
+        controller.new_layered_game(Difficulty::Easy, 3).unwrap();
+        assert_eq!(controller.layer_count(), 3);
+        assert_eq!(controller.active_layer(), 0);
+        assert!(!controller.set_active_layer(0));
+        assert!(!controller.set_active_layer(3));
+
+        for layer in 0..3 {
+            assert!(layer == 0 || controller.set_active_layer(layer));
+            play_solution(&mut controller);
+            assert!(controller.layer_solved(layer));
+            assert_eq!(controller.is_solved(), layer == 2);
         }
 
-        assert_eq!(controller.move_count(), 2);
+        // Switching back shows the solved board, not a fresh copy
+        assert!(controller.set_active_layer(0));
+        assert!(controller.state().is_solved());
 
-        controller.reset();
+        // The win is reported but kept out of the single-board records
+        assert!(controller.take_finished_game().is_some());
+        assert_eq!(controller.stats().games_played(), 0);
+        assert!(controller.best_time(Difficulty::Easy).is_none());
 
-        assert_eq!(controller.move_count(), 0);
+        controller.new_game(Difficulty::Easy);
+        assert_eq!(controller.layer_count(), 1);
+    }
+
+    #[test]
+    fn test_invalid_layer_count() {
+        let mut controller = GameController::new(3).unwrap();
+        assert!(matches!(
+            controller.new_layered_game(Difficulty::Easy, 0),
+            Err(PuzzleError::InvalidLayerCount { .. })
+        ));
+        assert_eq!(controller.layer_count(), 1);
         assert!(controller.is_solved());
     }
 
     #[test]
-    fn test_current_entropy() {
+    fn test_unshuffled_game_not_recorded() {
         let mut controller = GameController::new(4).unwrap();
-        assert_eq!(controller.current_entropy(), 0);
-
-        // Make a move
-        if let Some(moves) = controller.handle_click((3, 2)) {
+        for pos in [(3, 2), (3, 3)] {
+            let moves = controller.handle_click(pos).unwrap();
             for move_pos in moves {
                 controller.apply_move(move_pos);
             }
             controller.complete_move_sequence();
         }
-
-        assert!(controller.current_entropy() > 0);
+        assert!(controller.is_solved());
+        assert_eq!(controller.stats().games_played(), 0);
+        assert_eq!(controller.take_finished_game(), None);
     }
 
     #[test]
-    fn test_metrics_caching() {
+    fn test_load_state_from_notation() {
         let mut controller = GameController::new(4).unwrap();
+        let state: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        controller.load_state(state).unwrap();
 
-        // First call calculates and caches
-        let metrics1 = controller.all_entropy_metrics();
-        let time1 = metrics1.performance.manhattan_time_micros;
-
-        // Second call should return cached (time should be the same)
-        let metrics2 = controller.all_entropy_metrics();
-        let time2 = metrics2.performance.manhattan_time_micros;
-
-        assert_eq!(time1, time2);
-        assert_eq!(metrics1.manhattan_distance, metrics2.manhattan_distance);
+        assert_eq!(controller.state().size(), 3);
+        assert_eq!(controller.move_count(), 0);
+        assert!(controller.last_shuffle_result().is_none());
 
-        // After a move, cache should invalidate
-        if let Some(moves) = controller.handle_click((3, 2)) {
+        // Two moves solve it: 5 up, then 8 left
+        for pos in [(2, 1), (2, 2)] {
+            let moves = controller.handle_click(pos).unwrap();
             for move_pos in moves {
                 controller.apply_move(move_pos);
             }
             controller.complete_move_sequence();
         }
-        let metrics3 = controller.all_entropy_metrics();
+        assert!(controller.is_solved());
+        assert_eq!(controller.stats().games_played(), 0);
 
-        // Values should differ (state changed)
-        assert_ne!(metrics1.manhattan_distance, metrics3.manhattan_distance);
+        // Shuffling still works at the new size
+        controller.new_game(Difficulty::Easy);
+        assert_eq!(controller.state().size(), 3);
     }
 
     #[test]
@@ -0,0 +1,176 @@
+//! Game controller for Klotski, the sliding-block variant with blocks of several sizes.
+
+use crate::controller::game_controller::{GameClock, MoveHistory};
+use crate::model::{Direction, KlotskiBoard, KlotskiMove, DEFAULT_KLOTSKI_STATES};
+use std::time::Duration;
+
+/// Drives a Klotski game: moves, timing, hints, and reset
+///
+/// Mirrors `GameController` for the multi-size block variant. Klotski games are
+/// not scored by difficulty, so they leave best times and statistics alone.
+pub struct KlotskiController {
+    board: KlotskiBoard,
+    start: KlotskiBoard, // Board to return to on reset
+    move_history: MoveHistory,
+    clock: GameClock,
+    solution: Option<Vec<KlotskiMove>>, // Remaining moves of the last solve from the current board
+}
+
+impl KlotskiController {
+    /// Starts a game from the given board
+    pub fn new(board: KlotskiBoard) -> Self {
+        Self {
+            start: board.clone(),
+            board,
+            move_history: MoveHistory::new(),
+            clock: GameClock::new(),
+            solution: None,
+        }
+    }
+
+    /// Starts a game on the classic layout
+    pub fn classic() -> Self {
+        Self::new(KlotskiBoard::classic())
+    }
+
+    pub fn board(&self) -> &KlotskiBoard {
+        &self.board
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.move_history.player_moves()
+    }
+
+    pub fn elapsed_time(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    pub fn is_timer_running(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.board.is_solved()
+    }
+
+    /// Returns the directions `block` can slide right now
+    pub fn legal_directions(&self, block: usize) -> Vec<Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(|&direction| self.board.can_move(KlotskiMove { block, direction }))
+            .collect()
+    }
+
+    /// Slides a block one cell
+    /// Returns false if the move is illegal or the puzzle is already solved
+    pub fn move_block(&mut self, block: usize, direction: Direction) -> bool {
+        let mv = KlotskiMove { block, direction };
+        if self.board.is_solved() || !self.board.apply_move(mv) {
+            return false;
+        }
+
+        self.clock.start();
+        self.move_history.record_move();
+        self.move_history.record_tile_move();
+        log::trace!("Klotski block {} moved {}", block, direction);
+
+        // Keep the cached solution only while the player follows it
+        match self.solution.as_mut() {
+            Some(path) if path.first() == Some(&mv) => {
+                path.remove(0);
+            }
+            _ => self.solution = None,
+        }
+
+        if self.board.is_solved() {
+            self.clock.pause();
+            log::info!(
+                "Klotski solved in {} moves ({:.1}s)",
+                self.move_count(),
+                self.clock.elapsed().as_secs_f64()
+            );
+        }
+        true
+    }
+
+    /// Suggests the next move of a shortest solution
+    /// Returns None if the puzzle is solved or no solution was found within the search limit
+    pub fn hint(&mut self) -> Option<KlotskiMove> {
+        if self.board.is_solved() {
+            return None;
+        }
+        if self.solution.as_ref().is_none_or(|path| path.is_empty()) {
+            self.solution = self.board.solve(DEFAULT_KLOTSKI_STATES);
+            log::debug!(
+                "Klotski solve found {:?} moves",
+                self.solution.as_ref().map(Vec::len)
+            );
+        }
+        self.solution.as_ref().and_then(|path| path.first().copied())
+    }
+
+    /// Returns to the starting board and clears the move count and clock
+    pub fn reset(&mut self) {
+        self.board = self.start.clone();
+        self.move_history.reset();
+        self.clock.reset();
+        self.solution = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Block, BlockShape};
+
+    /// Big block above a small one; three moves from solved
+    fn small_board() -> KlotskiBoard {
+        let blocks = vec![
+            Block::new(BlockShape::Big, (0, 0)),
+            Block::new(BlockShape::Small, (2, 0)),
+        ];
+        KlotskiBoard::new(3, 3, blocks, 0, (1, 0)).unwrap()
+    }
+
+    #[test]
+    fn test_moves_and_clock() {
+        let mut controller = KlotskiController::new(small_board());
+        assert_eq!(controller.legal_directions(0), vec![Direction::Right]);
+        assert!(!controller.is_timer_running());
+
+        assert!(!controller.move_block(0, Direction::Down));
+        assert_eq!(controller.move_count(), 0);
+
+        assert!(controller.move_block(1, Direction::Right));
+        assert!(controller.is_timer_running());
+        assert!(controller.move_block(1, Direction::Right));
+        assert!(controller.move_block(0, Direction::Down));
+        assert!(controller.is_solved());
+        assert!(!controller.is_timer_running());
+        assert_eq!(controller.move_count(), 3);
+
+        // No moves once solved
+        assert!(!controller.move_block(0, Direction::Up));
+    }
+
+    #[test]
+    fn test_hint_follows_solution() {
+        let mut controller = KlotskiController::new(small_board());
+        for _ in 0..3 {
+            let hint = controller.hint().unwrap();
+            assert!(controller.move_block(hint.block, hint.direction));
+        }
+        assert!(controller.is_solved());
+        assert!(controller.hint().is_none());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut controller = KlotskiController::new(small_board());
+        controller.move_block(1, Direction::Right);
+        controller.reset();
+        assert_eq!(controller.board(), &small_board());
+        assert_eq!(controller.move_count(), 0);
+        assert_eq!(controller.elapsed_time(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,181 @@
+//! Persistent collection of recorded move macros.
+//!
+//! A macro is a [`RelativePattern`] the player recorded by playing it: the
+//! slides are kept relative to the empty cell, so a macro recorded in one
+//! corner can be replayed wherever the same shape of moves fits. Macros are
+//! kept in recording order, which is also the order of their shortcut keys.
+
+use crate::controller::storage;
+use crate::model::RelativePattern;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the macro store inside the data directory
+pub const MACROS_FILE: &str = "macros.json";
+
+/// Most macros kept; one per digit key 1–9
+pub const MAX_MACROS: usize = 9;
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Serialized form of the store
+#[derive(Debug, Serialize, Deserialize)]
+struct MacrosFile {
+    version: u32,
+    macros: Vec<RelativePattern>,
+}
+
+/// Named move sequences recorded by the player
+#[derive(Debug, Clone, Default)]
+pub struct MacroBook {
+    macros: Vec<RelativePattern>,
+    path: Option<PathBuf>, // Where to persist updates (None = in-memory only)
+}
+
+impl MacroBook {
+    /// Creates an empty in-memory store that is never written to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the store from `path`, persisting future changes back to it
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read or parsed
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut book = Self {
+            macros: Vec::new(),
+            path: Some(path.to_path_buf()),
+        };
+
+        match storage::load_json::<MacrosFile>(path) {
+            Ok(file) => {
+                if file.version == FORMAT_VERSION {
+                    book.macros = file.macros;
+                    book.macros.truncate(MAX_MACROS);
+                }
+                Ok(book)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(book),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the store from the default data directory
+    /// Falls back to an in-memory store if the file is unavailable or corrupt
+    pub fn load_default() -> Self {
+        storage::data_file(MACROS_FILE)
+            .and_then(|path| Self::load(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Adds a macro, replacing any with the same name, and writes the store
+    /// Returns false (and keeps the store unchanged) if it is full or the macro is empty
+    pub fn add(&mut self, pattern: RelativePattern) -> bool {
+        if pattern.is_empty() {
+            return false;
+        }
+        if let Some(existing) = self.macros.iter_mut().find(|m| m.name() == pattern.name()) {
+            *existing = pattern;
+        } else if self.macros.len() < MAX_MACROS {
+            self.macros.push(pattern);
+        } else {
+            return false;
+        }
+        self.persist();
+        true
+    }
+
+    /// Removes the macro at `index` and writes the store
+    pub fn remove(&mut self, index: usize) -> Option<RelativePattern> {
+        (index < self.macros.len()).then(|| {
+            let removed = self.macros.remove(index);
+            self.persist();
+            removed
+        })
+    }
+
+    /// Returns the macro at `index` (shortcut key `index + 1`)
+    pub fn get(&self, index: usize) -> Option<&RelativePattern> {
+        self.macros.get(index)
+    }
+
+    /// Returns all macros in shortcut order
+    pub fn macros(&self) -> &[RelativePattern] {
+        &self.macros
+    }
+
+    pub fn len(&self) -> usize {
+        self.macros.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.macros.is_empty()
+    }
+
+    fn persist(&self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save macros: {}", e);
+        }
+    }
+
+    /// Writes the store to its backing file (no-op for in-memory stores)
+    fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = MacrosFile {
+            version: FORMAT_VERSION,
+            macros: self.macros.clone(),
+        };
+        storage::save_json(path, &file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Direction;
+
+    fn pattern(name: &str) -> RelativePattern {
+        RelativePattern::new(name, vec![Direction::Right, Direction::Down])
+    }
+
+    #[test]
+    fn test_add_replaces_by_name_and_caps() {
+        let mut book = MacroBook::new();
+        assert!(!book.add(RelativePattern::new("empty", Vec::new())));
+        assert!(book.add(pattern("a")));
+        assert!(book.add(RelativePattern::new("a", vec![Direction::Left])));
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.get(0).unwrap().steps(), &[Direction::Left]);
+
+        for i in 1..MAX_MACROS {
+            assert!(book.add(pattern(&i.to_string())));
+        }
+        assert!(!book.add(pattern("overflow")));
+        assert_eq!(book.remove(0).unwrap().name(), "a");
+        assert!(book.remove(MAX_MACROS).is_none());
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("slider-macros-{}", std::process::id()))
+            .join(MACROS_FILE);
+        let _ = std::fs::remove_file(&path);
+
+        let mut book = MacroBook::load(&path).unwrap();
+        assert!(book.is_empty());
+        book.add(pattern("corner"));
+
+        let reloaded = MacroBook::load(&path).unwrap();
+        assert_eq!(reloaded.macros(), &[pattern("corner")]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,323 @@
+//! Endless marathon: one board after another, growing harder with each solve.
+//!
+//! A run starts on an Easy 3×3 board. Each solve clears a stage and moves on to
+//! the next difficulty, and after Extra Hard to the next grid size, up to the
+//! largest board. Stages score points by board size, difficulty and efficiency
+//! against par. The run goes on until the player ends it; finished runs are kept
+//! in a local store, best score first.
+
+use crate::controller::stats::GameRecord;
+use crate::controller::storage;
+use crate::model::puzzle_state::{MAX_SIZE, MIN_SIZE};
+use crate::model::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// File name of the marathon store inside the data directory
+pub const MARATHON_FILE: &str = "marathon.json";
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Finished runs kept in the store
+pub const MAX_MARATHON_RUNS: usize = 10;
+
+/// Difficulties played on each grid size, in order
+const STAGE_DIFFICULTIES: [Difficulty; 4] = [
+    Difficulty::Easy,
+    Difficulty::Medium,
+    Difficulty::Hard,
+    Difficulty::ExtraHard,
+];
+
+/// Efficiency assumed for a stage whose par was never found
+const UNKNOWN_PAR_EFFICIENCY: f64 = 0.5;
+
+/// One board of a marathon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarathonStage {
+    pub grid_size: usize,
+    pub difficulty: Difficulty,
+}
+
+impl MarathonStage {
+    /// Returns the stage at `index` (0 = the opening Easy 3×3)
+    /// Past the largest grid size, every stage is Extra Hard on the largest board
+    pub fn nth(index: usize) -> Self {
+        let per_size = STAGE_DIFFICULTIES.len();
+        let grid_size = MIN_SIZE + index / per_size;
+        if grid_size > MAX_SIZE {
+            return Self {
+                grid_size: MAX_SIZE,
+                difficulty: Difficulty::ExtraHard,
+            };
+        }
+        Self {
+            grid_size,
+            difficulty: STAGE_DIFFICULTIES[index % per_size],
+        }
+    }
+
+    /// Returns the points a solve of this stage earns at full efficiency
+    pub fn base_points(self) -> u64 {
+        let weight = STAGE_DIFFICULTIES
+            .iter()
+            .position(|&d| d == self.difficulty)
+            .map_or(1, |i| i as u64 + 1);
+        (self.grid_size * self.grid_size) as u64 * weight * 10
+    }
+}
+
+/// Returns the points `game` earns as a solve of `stage`
+///
+/// The stage's base points are scaled by efficiency against par. Auto-solved
+/// stages earn nothing.
+pub fn stage_points(stage: MarathonStage, game: &GameRecord) -> u64 {
+    if game.assisted {
+        return 0;
+    }
+    let efficiency = game.efficiency().unwrap_or(UNKNOWN_PAR_EFFICIENCY);
+    (stage.base_points() as f64 * efficiency).round() as u64
+}
+
+/// A marathon in progress
+#[derive(Debug, Clone, Default)]
+pub struct MarathonRun {
+    stages_cleared: usize,
+    score: u64,
+    time: Duration,   // Play time of the cleared stages
+    last_points: u64, // Points earned by the most recent stage
+}
+
+impl MarathonRun {
+    /// Starts a run at the first stage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stage being played
+    pub fn stage(&self) -> MarathonStage {
+        MarathonStage::nth(self.stages_cleared)
+    }
+
+    pub fn stages_cleared(&self) -> usize {
+        self.stages_cleared
+    }
+
+    pub fn score(&self) -> u64 {
+        self.score
+    }
+
+    /// Returns the points earned by the most recently cleared stage
+    pub fn last_points(&self) -> u64 {
+        self.last_points
+    }
+
+    /// Scores the solve of the current stage and moves on to the next
+    /// Returns the next stage
+    pub fn clear_stage(&mut self, game: &GameRecord) -> MarathonStage {
+        self.last_points = stage_points(self.stage(), game);
+        self.score += self.last_points;
+        self.time += game.time();
+        self.stages_cleared += 1;
+        self.stage()
+    }
+
+    /// Ends the run, summarizing it
+    pub fn finish(&self) -> MarathonSummary {
+        let reached = self.stage();
+        MarathonSummary {
+            stages_cleared: self.stages_cleared,
+            score: self.score,
+            time_millis: self.time.as_millis() as u64,
+            reached_size: reached.grid_size,
+            reached_difficulty: reached.difficulty,
+            completed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// A finished marathon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarathonSummary {
+    pub stages_cleared: usize,
+    pub score: u64,
+    /// Play time of the cleared stages in milliseconds
+    pub time_millis: u64,
+    /// Stage the run ended on
+    pub reached_size: usize,
+    pub reached_difficulty: Difficulty,
+    /// Completion time in seconds since the Unix epoch
+    pub completed_at: u64,
+}
+
+impl MarathonSummary {
+    /// Returns the play time of the cleared stages
+    pub fn time(&self) -> Duration {
+        Duration::from_millis(self.time_millis)
+    }
+}
+
+/// Serialized form of the store
+#[derive(Debug, Serialize, Deserialize)]
+struct MarathonFile {
+    version: u32,
+    runs: Vec<MarathonSummary>,
+}
+
+/// Best finished marathons, highest score first
+#[derive(Debug, Clone, Default)]
+pub struct MarathonRecords {
+    runs: Vec<MarathonSummary>,
+    path: Option<PathBuf>, // Where to persist updates (None = in-memory only)
+}
+
+impl MarathonRecords {
+    /// Creates an empty in-memory store that is never written to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the store from `path`, persisting future runs back to it
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read or parsed
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut records = Self {
+            runs: Vec::new(),
+            path: Some(path.to_path_buf()),
+        };
+
+        match storage::load_json::<MarathonFile>(path) {
+            Ok(file) => {
+                if file.version == FORMAT_VERSION {
+                    records.runs = file.runs;
+                }
+                Ok(records)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(records),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the store from the default data directory
+    /// Falls back to an in-memory store if the file is unavailable or corrupt
+    pub fn load_default() -> Self {
+        storage::data_file(MARATHON_FILE)
+            .and_then(|path| Self::load(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the kept runs, highest score first
+    pub fn runs(&self) -> &[MarathonSummary] {
+        &self.runs
+    }
+
+    /// Adds a finished run, returning its rank (0 = best) if it made the table
+    /// The table is written to disk when the store has a backing file.
+    pub fn record(&mut self, run: MarathonSummary) -> Option<usize> {
+        let rank = self.runs.iter().position(|kept| run.score > kept.score).unwrap_or(self.runs.len());
+        if rank >= MAX_MARATHON_RUNS {
+            return None;
+        }
+        self.runs.insert(rank, run);
+        self.runs.truncate(MAX_MARATHON_RUNS);
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save marathon runs: {}", e);
+        }
+        Some(rank)
+    }
+
+    /// Writes the store to its backing file (no-op for in-memory stores)
+    fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = MarathonFile {
+            version: FORMAT_VERSION,
+            runs: self.runs.clone(),
+        };
+        storage::save_json(path, &file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(stage: MarathonStage, tile_moves: usize, par: Option<u32>, assisted: bool) -> GameRecord {
+        GameRecord::new(
+            stage.grid_size,
+            stage.difficulty,
+            tile_moves,
+            tile_moves,
+            Duration::from_secs(30),
+            par,
+            assisted,
+        )
+    }
+
+    #[test]
+    fn test_stages_escalate() {
+        assert_eq!(MarathonStage::nth(0), MarathonStage { grid_size: 3, difficulty: Difficulty::Easy });
+        assert_eq!(MarathonStage::nth(3), MarathonStage { grid_size: 3, difficulty: Difficulty::ExtraHard });
+        assert_eq!(MarathonStage::nth(4), MarathonStage { grid_size: 4, difficulty: Difficulty::Easy });
+        let last = MarathonStage::nth(usize::MAX / 2);
+        assert_eq!(last, MarathonStage { grid_size: MAX_SIZE, difficulty: Difficulty::ExtraHard });
+    }
+
+    #[test]
+    fn test_run_scores_stages() {
+        let mut run = MarathonRun::new();
+        let first = run.stage();
+        assert_eq!(first.base_points(), 90);
+
+        // At par earns everything, twice par half, auto-solve nothing
+        let next = run.clear_stage(&solve(first, 20, Some(20), false));
+        assert_eq!(run.last_points(), 90);
+        assert_eq!(next.difficulty, Difficulty::Medium);
+        run.clear_stage(&solve(next, 40, Some(20), false));
+        assert_eq!(run.last_points(), 90); // 180 base at 50%
+        run.clear_stage(&solve(run.stage(), 10, Some(10), true));
+        assert_eq!(run.last_points(), 0);
+
+        let summary = run.finish();
+        assert_eq!(summary.stages_cleared, 3);
+        assert_eq!(summary.score, 180);
+        assert_eq!(summary.time(), Duration::from_secs(90));
+        assert_eq!((summary.reached_size, summary.reached_difficulty), (3, Difficulty::ExtraHard));
+    }
+
+    #[test]
+    fn test_records_keep_best_runs() {
+        let path = std::env::temp_dir()
+            .join(format!("slider-marathon-{}", std::process::id()))
+            .join(MARATHON_FILE);
+        let _ = std::fs::remove_file(&path);
+
+        let mut records = MarathonRecords::load(&path).unwrap();
+        let run = |score| MarathonSummary {
+            score,
+            ..MarathonRun::new().finish()
+        };
+        assert_eq!(records.record(run(50)), Some(0));
+        assert_eq!(records.record(run(80)), Some(0));
+        assert_eq!(records.record(run(60)), Some(1));
+        for _ in 0..MAX_MARATHON_RUNS {
+            records.record(run(100));
+        }
+        assert_eq!(records.record(run(10)), None);
+
+        let reloaded = MarathonRecords::load(&path).unwrap();
+        assert_eq!(reloaded.runs().len(), MAX_MARATHON_RUNS);
+        assert!(reloaded.runs().iter().all(|r| r.score == 100));
+        let _ = std::fs::remove_file(&path);
+    }
+}
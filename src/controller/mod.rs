@@ -1,10 +1,52 @@
 //! Controller layer orchestrating game logic and user interactions.
 //!
-//! This module contains the game controller (handling player moves and game state)
-//! and the shuffle controller (generating solvable puzzles with entropy requirements).
+//! [`GameController`] runs the sliding puzzle and hands slow work to the
+//! background tasks in [`solver_task`]. The other submodules each add one
+//! feature around it, from challenge modes and variant games to the stores that
+//! keep results between sessions; their own docs describe them.
 
+pub mod achievements;
+pub mod adaptive;
+pub mod best_times;
+pub mod challenge;
+pub mod corpus;
 pub mod game_controller;
+pub mod klotski_controller;
+pub mod macros;
+pub mod marathon;
+pub mod pattern_mining;
+pub mod search_trace;
+pub mod session;
 pub mod shuffle_controller;
+pub mod solution_cache;
+pub mod solver_benchmark;
+pub mod solver_dispatcher;
+pub mod solver_task;
+pub mod stats;
+pub mod storage;
+pub mod variant_controller;
 
-pub use game_controller::{EntropyMetrics, GameController, MoveHistory};
-pub use shuffle_controller::ShuffleController;
\ No newline at end of file
+pub use achievements::{Achievement, Achievements, CENTURION_GAMES};
+pub use adaptive::{AdaptiveDifficulty, Adjustment};
+pub use best_times::BestTimes;
+pub use challenge::{ChallengeFailure, GameMode, DEFAULT_COUNTDOWN_SECS, DEFAULT_MOVE_SLACK};
+pub use corpus::{CorpusEntry, PuzzleCorpus};
+pub use game_controller::{
+    AssistTarget, EntropyMetrics, GameClock, GameController, MoveHistory, TechniqueSuggestion,
+    DEFAULT_AUTO_SOLVE_INTERVAL, MAX_AUTO_SOLVE_SPEED, MIN_AUTO_SOLVE_SPEED, RECENT_SHUFFLES,
+};
+pub use klotski_controller::KlotskiController;
+pub use macros::{MacroBook, MAX_MACROS};
+pub use marathon::{MarathonRecords, MarathonRun, MarathonStage, MarathonSummary};
+pub use search_trace::{SearchTrace, TraceSample, MAX_GHOST_BOARDS, MAX_TRACE_SAMPLES};
+pub use session::{SessionSnapshot, SessionStore};
+pub use shuffle_controller::{ShuffleController, ShuffleResult};
+pub use solution_cache::{CachedSolution, SolutionCache, DEFAULT_SOLUTION_CACHE_CAPACITY};
+pub use solver_dispatcher::{SolverChoice, SolverDispatcher, DEFAULT_IDA_STAR_MAX_DISTANCE};
+pub use solver_task::{
+    spawn_analysis_task, spawn_metrics_task, spawn_shuffle_task, spawn_solver_task, AnalysisJob, AnalysisTask,
+    InlineAnalysisTask, InlineMetricsTask, InlineShuffleTask, InlineTask, MetricsJob, MetricsTask, ShuffleJob,
+    ShuffleTask, SolverTask, TaskStatus, ThreadAnalysisTask, ThreadMetricsTask, ThreadShuffleTask, ThreadTask,
+};
+pub use stats::{GameRecord, Stats};
+pub use variant_controller::VariantController;
@@ -0,0 +1,265 @@
+//! Learned technique catalogs mined from optimal solutions.
+//!
+//! A learning pass shuffles a seeded corpus, solves every board optimally, and
+//! keeps the loops that recur across solutions (see
+//! [`RelativePatternCatalog::mine`]). The result is saved as JSON and merged
+//! into the built-in techniques wherever suggestions are offered. A benchmark
+//! measures how often each technique fits and how much it helps, so learned
+//! and built-in catalogs can be compared on the same boards.
+//!
+//! ```rust,no_run
+//! use slider::controller::pattern_mining::{benchmark_catalog, learn_catalog, MiningConfig};
+//!
+//! let config = MiningConfig::default();
+//! let learned = learn_catalog(&config)?;
+//! for effect in benchmark_catalog(&learned, &config.boards()?) {
+//!     println!("{}: fits {} of {} boards", effect.name, effect.applicable, effect.boards);
+//! }
+//! # Ok::<(), slider::model::PuzzleError>(())
+//! ```
+
+use crate::controller::storage;
+use crate::controller::PuzzleCorpus;
+use crate::model::{
+    Difficulty, EntropyCalculator, ManhattanDistance, PuzzleError, PuzzleState, RelativePattern,
+    RelativePatternCatalog, SolverConfig, SolverKind,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// File name of the learned catalog inside the data directory
+pub const PATTERNS_FILE: &str = "patterns.json";
+
+/// Format version written into saved catalogs
+const CATALOG_VERSION: u32 = 1;
+
+/// Serialized form of a learned catalog
+#[derive(Debug, Serialize, Deserialize)]
+struct CatalogFile {
+    version: u32,
+    catalog: RelativePatternCatalog,
+}
+
+/// Settings for a learning pass
+#[derive(Debug, Clone)]
+pub struct MiningConfig {
+    pub size: usize,
+    pub difficulty: Difficulty,
+    /// Boards shuffled and solved
+    pub boards: usize,
+    /// Seed of the first board; board `i` uses `seed + i`
+    pub seed: u64,
+    /// Longest loop mined, in single-tile moves
+    pub max_length: usize,
+    /// Solutions a loop must appear in to be kept
+    pub min_count: usize,
+    /// Most loops kept
+    pub limit: usize,
+    /// Limits for each optimal solve
+    pub solver: SolverConfig,
+}
+
+impl Default for MiningConfig {
+    fn default() -> Self {
+        Self {
+            size: 3,
+            difficulty: Difficulty::Hard,
+            boards: 40,
+            seed: 1,
+            max_length: 12,
+            min_count: 3,
+            limit: 8,
+            solver: SolverConfig::default(),
+        }
+    }
+}
+
+impl MiningConfig {
+    /// Returns the seeded boards the pass solves
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if the size is out of range
+    pub fn boards(&self) -> Result<Vec<PuzzleState>, PuzzleError> {
+        let sets = [(self.size, self.difficulty)];
+        Ok(PuzzleCorpus::generate(&sets, self.boards, self.seed)?
+            .boards(self.size, self.difficulty))
+    }
+}
+
+/// Solves the configured boards optimally and mines their recurring loops
+///
+/// Boards the solver gives up on are skipped.
+///
+/// # Errors
+///
+/// Returns `PuzzleError` if the size is out of range
+pub fn learn_catalog(config: &MiningConfig) -> Result<RelativePatternCatalog, PuzzleError> {
+    let solutions: Vec<RelativePattern> = config
+        .boards()?
+        .iter()
+        .filter_map(|board| {
+            let solution =
+                SolverKind::AStar.solve_instrumented(config.solver, board, None, &mut |_| {})?;
+            RelativePattern::from_moves("solution", board.empty_position(), &solution.path)
+        })
+        .collect();
+    log::debug!("Mining loops from {} optimal solutions", solutions.len());
+    Ok(RelativePatternCatalog::mine(
+        &solutions,
+        config.max_length,
+        config.min_count,
+        config.limit,
+    ))
+}
+
+/// How one technique fared over a set of boards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternEffect {
+    pub name: String,
+    pub boards: usize,
+    /// Boards where some orientation fits at the empty cell
+    pub applicable: usize,
+    /// Boards where the best fitting orientation lowers the Manhattan distance
+    pub improving: usize,
+    /// Mean Manhattan change of the best orientation over the boards it fits
+    pub mean_best_change: Option<f64>,
+}
+
+/// Measures every technique in `catalog` on `boards`, in catalog order
+pub fn benchmark_catalog(
+    catalog: &RelativePatternCatalog,
+    boards: &[PuzzleState],
+) -> Vec<PatternEffect> {
+    catalog
+        .patterns()
+        .iter()
+        .map(|pattern| {
+            let changes: Vec<i64> = boards
+                .iter()
+                .filter_map(|board| best_change(pattern, board))
+                .collect();
+            PatternEffect {
+                name: pattern.name().to_string(),
+                boards: boards.len(),
+                applicable: changes.len(),
+                improving: changes.iter().filter(|&&change| change < 0).count(),
+                mean_best_change: (!changes.is_empty())
+                    .then(|| changes.iter().sum::<i64>() as f64 / changes.len() as f64),
+            }
+        })
+        .collect()
+}
+
+/// Returns the lowest Manhattan change over the orientations of `pattern` that fit `board`
+fn best_change(pattern: &RelativePattern, board: &PuzzleState) -> Option<i64> {
+    let before = ManhattanDistance.calculate(board) as i64;
+    pattern
+        .orientations()
+        .iter()
+        .filter_map(|orientation| {
+            let mut after = board.clone();
+            for pos in orientation.positions_at(board)? {
+                after.apply_immediate_move(pos);
+            }
+            Some(ManhattanDistance.calculate(&after) as i64 - before)
+        })
+        .min()
+}
+
+/// Writes a catalog as JSON
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the file cannot be written
+pub fn save_catalog(catalog: &RelativePatternCatalog, path: &Path) -> io::Result<()> {
+    let file = CatalogFile {
+        version: CATALOG_VERSION,
+        catalog: catalog.clone(),
+    };
+    storage::save_json(path, &file)
+}
+
+/// Reads a catalog written by `save_catalog`
+///
+/// # Errors
+///
+/// Returns the underlying I/O error, or `InvalidData` if the file is malformed
+/// or from a newer version
+pub fn load_catalog(path: &Path) -> io::Result<RelativePatternCatalog> {
+    let file: CatalogFile = storage::load_json(path)?;
+    if file.version > CATALOG_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("catalog version {} is newer than this build", file.version),
+        ));
+    }
+    Ok(file.catalog)
+}
+
+/// Returns the built-in techniques plus any learned catalog in the data directory
+pub fn load_default_catalog() -> RelativePatternCatalog {
+    let mut catalog = RelativePatternCatalog::builtin();
+    let Some(path) = storage::data_file(PATTERNS_FILE) else {
+        return catalog;
+    };
+    match load_catalog(&path) {
+        Ok(learned) => catalog.extend(learned),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => log::warn!("Ignoring learned techniques in {}: {}", path.display(), e),
+    }
+    catalog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_config() -> MiningConfig {
+        MiningConfig {
+            difficulty: Difficulty::Medium,
+            boards: 12,
+            min_count: 2,
+            ..MiningConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_learned_loops_recur_and_are_repeatable() {
+        let config = small_config();
+        let learned = learn_catalog(&config).unwrap();
+        assert!(!learned.is_empty());
+        assert!(learned.len() <= config.limit);
+        assert!(learned
+            .patterns()
+            .iter()
+            .all(|p| p.is_loop() && p.len() <= config.max_length));
+        assert_eq!(learn_catalog(&config).unwrap(), learned);
+    }
+
+    #[test]
+    fn test_benchmark_catalog() {
+        let boards = small_config().boards().unwrap();
+        let effects = benchmark_catalog(&RelativePatternCatalog::builtin(), &boards);
+        assert_eq!(effects.len(), 2);
+        for effect in &effects {
+            assert_eq!(effect.boards, boards.len());
+            assert!(effect.improving <= effect.applicable && effect.applicable <= effect.boards);
+            assert_eq!(effect.mean_best_change.is_some(), effect.applicable > 0);
+        }
+        // Wherever the empty cell is, some corner rotation fits
+        assert_eq!(effects[0].applicable, boards.len());
+    }
+
+    #[test]
+    fn test_catalog_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("slider-patterns-{}", std::process::id()))
+            .join(PATTERNS_FILE);
+        let catalog = RelativePatternCatalog::builtin();
+        save_catalog(&catalog, &path).unwrap();
+        assert_eq!(load_catalog(&path).unwrap(), catalog);
+        let _ = std::fs::remove_file(&path);
+    }
+}
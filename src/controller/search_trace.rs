@@ -0,0 +1,161 @@
+//! Recording of a background solve's progress over time, for search visualizations.
+//!
+//! The controller feeds each progress snapshot the solver reports into a
+//! [`SearchTrace`] while tracing is on. The trace keeps the f-score and frontier
+//! size of every snapshot (thinned to a fixed budget as the search runs on) and
+//! the last few boards the solver expanded, so a debug view can plot the search
+//! live and show ghosts of where it has been looking.
+
+use crate::model::{PuzzleState, SolverKind, SolverProgress};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Samples kept per trace; every other one is dropped when the budget fills
+pub const MAX_TRACE_SAMPLES: usize = 512;
+
+/// Expanded boards kept for ghost previews
+pub const MAX_GHOST_BOARDS: usize = 6;
+
+/// One progress snapshot, without its board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceSample {
+    pub elapsed: Duration,
+    pub nodes_explored: usize,
+    /// Best f-score so far (A* and greedy: the node expanded; IDA*: the threshold)
+    pub f_score: u32,
+    pub frontier_size: usize,
+}
+
+/// Progress of one solve, oldest sample first
+#[derive(Debug, Clone)]
+pub struct SearchTrace {
+    kind: SolverKind,
+    version: u64, // Board version the solve started from
+    samples: Vec<TraceSample>,
+    ghosts: VecDeque<PuzzleState>, // Newest last
+    finished: bool,
+}
+
+impl SearchTrace {
+    /// Starts an empty trace of a `kind` solve of board `version`
+    pub fn new(kind: SolverKind, version: u64) -> Self {
+        Self {
+            kind,
+            version,
+            samples: Vec::new(),
+            ghosts: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    pub fn kind(&self) -> SolverKind {
+        self.kind
+    }
+
+    /// Returns true if this traces the `kind` solve of board `version`
+    pub fn traces(&self, kind: SolverKind, version: u64) -> bool {
+        self.kind == kind && self.version == version
+    }
+
+    /// Adds a snapshot, unless it repeats the last one
+    /// Returns true if it was new
+    pub fn record(&mut self, progress: &SolverProgress) -> bool {
+        if self.samples.last().is_some_and(|last| last.nodes_explored >= progress.nodes_explored) {
+            return false;
+        }
+        if self.samples.len() >= MAX_TRACE_SAMPLES {
+            // Halve the resolution so the whole search stays in view
+            let mut index = 0;
+            self.samples.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+        }
+        self.samples.push(TraceSample {
+            elapsed: progress.elapsed,
+            nodes_explored: progress.nodes_explored,
+            f_score: progress.current_depth,
+            frontier_size: progress.frontier_size,
+        });
+        if let Some(ref board) = progress.expanded {
+            if self.ghosts.len() >= MAX_GHOST_BOARDS {
+                self.ghosts.pop_front();
+            }
+            self.ghosts.push_back(PuzzleState::from(board));
+        }
+        true
+    }
+
+    /// Marks the solve as over; later snapshots are still accepted
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn samples(&self) -> &[TraceSample] {
+        &self.samples
+    }
+
+    /// Returns the latest sample, or None before the first report
+    pub fn latest(&self) -> Option<&TraceSample> {
+        self.samples.last()
+    }
+
+    /// Returns the largest frontier seen
+    pub fn peak_frontier(&self) -> usize {
+        self.samples.iter().map(|s| s.frontier_size).max().unwrap_or(0)
+    }
+
+    /// Returns the boards expanded at the last few snapshots, newest last
+    pub fn ghosts(&self) -> impl Iterator<Item = &PuzzleState> {
+        self.ghosts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::PackedState;
+
+    fn progress(nodes: usize, f_score: u32) -> SolverProgress {
+        SolverProgress {
+            nodes_explored: nodes,
+            current_depth: f_score,
+            elapsed: Duration::from_millis(nodes as u64),
+            frontier_size: nodes / 2,
+            expanded: Some(PackedState::from_state(&PuzzleState::new(3).unwrap())),
+        }
+    }
+
+    #[test]
+    fn test_records_new_snapshots_only() {
+        let mut trace = SearchTrace::new(SolverKind::AStar, 4);
+        assert!(trace.traces(SolverKind::AStar, 4));
+        assert!(!trace.traces(SolverKind::AStar, 5));
+        assert!(trace.latest().is_none());
+
+        assert!(trace.record(&progress(1000, 20)));
+        assert!(!trace.record(&progress(1000, 20)));
+        assert!(trace.record(&progress(2000, 22)));
+        assert_eq!(trace.samples().len(), 2);
+        assert_eq!(trace.latest().unwrap().f_score, 22);
+        assert_eq!(trace.peak_frontier(), 1000);
+        assert_eq!(trace.ghosts().count(), 2);
+    }
+
+    #[test]
+    fn test_budgets_are_kept() {
+        let mut trace = SearchTrace::new(SolverKind::IdaStar, 0);
+        for i in 1..=MAX_TRACE_SAMPLES * 3 {
+            trace.record(&progress(i * 1000, 30));
+        }
+        assert!(trace.samples().len() <= MAX_TRACE_SAMPLES);
+        assert_eq!(trace.samples()[0].nodes_explored, 1000);
+        assert_eq!(trace.latest().unwrap().nodes_explored, MAX_TRACE_SAMPLES * 3000);
+        assert!(trace.samples().windows(2).all(|w| w[0].nodes_explored < w[1].nodes_explored));
+        assert_eq!(trace.ghosts().count(), MAX_GHOST_BOARDS);
+    }
+}
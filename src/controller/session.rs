@@ -0,0 +1,194 @@
+//! Crash recovery for the game in progress.
+//!
+//! While a game is running the GUI periodically writes a [`SessionSnapshot`] of
+//! the board, move counts, and elapsed time to a session file, and removes the
+//! file when the app exits cleanly. A session file found at startup therefore
+//! means the last run ended uncleanly, and its game can be offered for restoring.
+
+use crate::controller::challenge::GameMode;
+use crate::controller::storage;
+use crate::model::{Difficulty, GoalSpec};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name of the session snapshot inside the data directory
+pub const SESSION_FILE: &str = "session.json";
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// The game in progress, as captured by `GameController::snapshot`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Tile numbers of the board being played, numbered as in `goal` (None = empty cell)
+    pub layout: Vec<Vec<Option<u32>>>,
+    pub goal: GoalSpec,
+    /// Board as shuffled, for par and optimal-length tracking
+    pub initial_layout: Option<Vec<Vec<Option<u32>>>>,
+    /// Optimal solution length of the initial board, if it was computed
+    pub initial_optimal: Option<u32>,
+    /// Difficulty of a shuffled game (None for custom, random, and practice boards)
+    pub difficulty: Option<Difficulty>,
+    /// Player moves taken (a chain move counts once)
+    pub moves: usize,
+    /// Single-tile moves taken
+    pub tile_moves: usize,
+    /// Elapsed play time in milliseconds
+    pub time_millis: u64,
+    /// Auto-solve or solution steps were used
+    pub assisted: bool,
+    /// Hints shown so far
+    #[serde(default)]
+    pub hints: usize,
+    /// Rules of a shuffled game
+    #[serde(default)]
+    pub mode: GameMode,
+}
+
+impl SessionSnapshot {
+    /// Returns the grid size of the saved board
+    pub fn grid_size(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Returns the elapsed play time
+    pub fn time(&self) -> Duration {
+        Duration::from_millis(self.time_millis)
+    }
+}
+
+/// Serialized form of the session file
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionFile {
+    version: u32,
+    snapshot: SessionSnapshot,
+}
+
+/// Where the session snapshot is kept between runs
+#[derive(Debug, Clone, Default)]
+pub struct SessionStore {
+    path: Option<PathBuf>, // None = in-memory only, nothing is saved or recovered
+}
+
+impl SessionStore {
+    /// Creates a store that never touches the disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store backed by the file at `path`
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Creates a store backed by the default data directory
+    /// Falls back to an in-memory store if no data directory can be found
+    pub fn open_default() -> Self {
+        storage::data_file(SESSION_FILE)
+            .map(|path| Self::open(&path))
+            .unwrap_or_default()
+    }
+
+    /// Returns the snapshot left by a run that did not exit cleanly
+    /// Returns None if there is none, or it is unreadable or from another format version
+    pub fn recover(&self) -> Option<SessionSnapshot> {
+        let path = self.path.as_ref()?;
+        match storage::load_json::<SessionFile>(path) {
+            Ok(file) if file.version == FORMAT_VERSION => Some(file.snapshot),
+            Ok(_) => None,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    log::warn!("Ignoring unreadable session file: {}", e);
+                }
+                None
+            }
+        }
+    }
+
+    /// Writes `snapshot`, replacing the previous one
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn save(&self, snapshot: &SessionSnapshot) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = SessionFile {
+            version: FORMAT_VERSION,
+            snapshot: snapshot.clone(),
+        };
+        storage::save_json(path, &file)
+    }
+
+    /// Removes the snapshot, e.g. on a clean exit or once nothing is left to recover
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file exists but cannot be removed
+    pub fn clear(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-session-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn snapshot() -> SessionSnapshot {
+        SessionSnapshot {
+            layout: vec![
+                vec![Some(1), Some(2), Some(3)],
+                vec![Some(4), Some(5), Some(6)],
+                vec![Some(7), None, Some(8)],
+            ],
+            goal: GoalSpec::Standard,
+            initial_layout: None,
+            initial_optimal: Some(1),
+            difficulty: Some(Difficulty::Easy),
+            moves: 3,
+            tile_moves: 4,
+            time_millis: 12_500,
+            assisted: false,
+            hints: 1,
+            mode: GameMode::Countdown { seconds: 60 },
+        }
+    }
+
+    #[test]
+    fn test_save_recover_clear() {
+        let store = SessionStore::open(&temp_path("round_trip.json"));
+        assert!(store.recover().is_none());
+
+        store.save(&snapshot()).unwrap();
+        assert_eq!(store.recover(), Some(snapshot()));
+
+        store.clear().unwrap();
+        assert!(store.recover().is_none());
+        store.clear().unwrap(); // Clearing twice is fine
+    }
+
+    #[test]
+    fn test_in_memory_store() {
+        let store = SessionStore::new();
+        store.save(&snapshot()).unwrap();
+        assert!(store.recover().is_none());
+    }
+}
@@ -1,20 +1,44 @@
+//! Shuffling: random walks from the solved board that keep the puzzle solvable
+//! and scramble it to the entropy a difficulty asks for.
+
 use crate::model::{
-    Difficulty, EntropyCalculator, MoveValidator, Position, PuzzleError, PuzzleState,
+    Difficulty, EntropyCalculator, ManhattanDistance, MoveSystem, MoveValidator, Position, PuzzleError,
+    PuzzleState, Region,
 };
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// Number of random walks attempted before settling for a shuffle below target entropy
+const SHUFFLE_ATTEMPTS: usize = 8;
 
 /// Result of a shuffle operation
+///
+/// Every shuffle draws its moves from a seeded generator, so passing `seed` to
+/// the matching `_seeded` method on the same starting board repeats it exactly.
 #[derive(Debug, Clone)]
 pub struct ShuffleResult {
     pub moves_made: usize,
     pub final_entropy: u32,
     pub target_met: bool,
+    pub seed: u64,
+    pub duration: Duration,
+}
+
+/// What a random walk achieved, before timing and seed are attached
+struct WalkOutcome {
+    moves_made: usize,
+    final_entropy: u32,
+    target_met: bool,
 }
 
 /// Controls puzzle shuffling with entropy requirements
+///
+/// Walks only ever play the board's own legal moves (see `MoveSystem`), which
+/// keeps every shuffle solvable.
 pub struct ShuffleController {
-    validator: MoveValidator,
+    grid_size: usize,
 }
 
 impl ShuffleController {
@@ -25,43 +49,193 @@ impl ShuffleController {
     /// Returns `PuzzleError` if grid_size is invalid (< 3 or > 22)
     pub fn new(grid_size: usize) -> Result<Self, PuzzleError> {
         MoveValidator::new(grid_size)?;
-        Ok(Self {
-            validator: MoveValidator::new(grid_size)?,
-        })
+        Ok(Self { grid_size })
+    }
+
+    /// Returns the grid size the controller was created for
+    pub fn grid_size(&self) -> usize {
+        self.grid_size
     }
 
     /// Shuffles the puzzle to meet the specified difficulty level
     /// Uses only immediate moves with no backtracking to guarantee solvability
+    ///
+    /// A random walk can hit the difficulty's move cap before reaching the target
+    /// entropy. When that happens the walk is re-rolled from the original state (up to
+    /// `SHUFFLE_ATTEMPTS` times), keeping the most disordered attempt if none succeed.
+    /// The target is the start of the calculator's `target_range` for the difficulty.
     pub fn shuffle(
         &self,
         state: &mut PuzzleState,
         difficulty: Difficulty,
         calculator: &dyn EntropyCalculator,
-    ) {
-        let _result = self.shuffle_with_result(state, difficulty, calculator);
+    ) -> ShuffleResult {
+        self.shuffle_seeded(state, difficulty, calculator, thread_rng().gen())
     }
 
     /// Shuffles the puzzle and returns detailed results about the operation
+    ///
+    /// Equivalent to `shuffle`, which now returns the same result.
     pub fn shuffle_with_result(
         &self,
         state: &mut PuzzleState,
         difficulty: Difficulty,
         calculator: &dyn EntropyCalculator,
     ) -> ShuffleResult {
-        let target_entropy = difficulty.min_entropy(state.size());
-        let max_moves = difficulty.max_shuffle_moves(state.size());
-        let mut rng = thread_rng();
+        self.shuffle(state, difficulty, calculator)
+    }
+
+    /// Shuffles like `shuffle`, drawing moves from a generator with the given seed
+    ///
+    /// The same seed, starting board, difficulty and calculator always produce the
+    /// same shuffle, so several players can race on identical boards.
+    pub fn shuffle_seeded(
+        &self,
+        state: &mut PuzzleState,
+        difficulty: Difficulty,
+        calculator: &dyn EntropyCalculator,
+        seed: u64,
+    ) -> ShuffleResult {
+        let started = Instant::now();
+        let walk = self.shuffle_with_rng(
+            state,
+            *calculator.target_range(difficulty, state.size()).start(),
+            difficulty.max_shuffle_moves(state.size()),
+            None,
+            calculator,
+            &mut StdRng::seed_from_u64(seed),
+        );
+        ShuffleResult {
+            moves_made: walk.moves_made,
+            final_entropy: walk.final_entropy,
+            target_met: walk.target_met,
+            seed,
+            duration: started.elapsed(),
+        }
+    }
+
+    /// Shuffles only the cells inside `region`, leaving the rest of the board as it is
+    ///
+    /// The empty cell never leaves the region, so tiles outside it stay put; on
+    /// a solved board this drills the end of a solve, such as the last two rows.
+    /// Preset difficulties aim for the share of their usual target that the
+    /// region covers; custom targets are used whole. Both come from the
+    /// calculator's `target_range`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidRegion` if the region does not fit the board,
+    /// is narrower than 2×2, or does not hold the empty cell
+    pub fn shuffle_region(
+        &self,
+        state: &mut PuzzleState,
+        region: Region,
+        difficulty: Difficulty,
+        calculator: &dyn EntropyCalculator,
+    ) -> Result<ShuffleResult, PuzzleError> {
+        self.shuffle_region_seeded(state, region, difficulty, calculator, thread_rng().gen())
+    }
+
+    /// Shuffles a region like `shuffle_region`, repeatably for a given seed
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidRegion` as `shuffle_region` does
+    pub fn shuffle_region_seeded(
+        &self,
+        state: &mut PuzzleState,
+        region: Region,
+        difficulty: Difficulty,
+        calculator: &dyn EntropyCalculator,
+        seed: u64,
+    ) -> Result<ShuffleResult, PuzzleError> {
+        let size = state.size();
+        region.check(size, state.empty_position())?;
+        let started = Instant::now();
+        let threshold = *calculator.target_range(difficulty, size).start();
+        let target_entropy = match difficulty {
+            Difficulty::Custom { .. } => threshold,
+            _ => (threshold as usize * region.len() / (size * size)).max(1) as u32,
+        };
+        let walk = self.shuffle_with_rng(
+            state,
+            target_entropy,
+            difficulty.max_shuffle_moves(size),
+            Some(region),
+            calculator,
+            &mut StdRng::seed_from_u64(seed),
+        );
+        Ok(ShuffleResult {
+            moves_made: walk.moves_made,
+            final_entropy: walk.final_entropy,
+            target_met: walk.target_met,
+            seed,
+            duration: started.elapsed(),
+        })
+    }
+
+    fn shuffle_with_rng(
+        &self,
+        state: &mut PuzzleState,
+        target_entropy: u32,
+        max_moves: usize,
+        region: Option<Region>,
+        calculator: &dyn EntropyCalculator,
+        rng: &mut impl Rng,
+    ) -> WalkOutcome {
+        let original = state.clone();
+        let mut best: Option<(PuzzleState, WalkOutcome)> = None;
+
+        for _ in 0..SHUFFLE_ATTEMPTS {
+            let mut attempt = original.clone();
+            let result = self.random_walk(&mut attempt, target_entropy, max_moves, region, calculator, rng);
+
+            if result.target_met {
+                debug_assert!(attempt.is_solvable(), "shuffle produced an unsolvable state");
+                *state = attempt;
+                return result;
+            }
+
+            let is_better = best
+                .as_ref()
+                .is_none_or(|(_, b)| result.final_entropy > b.final_entropy);
+            if is_better {
+                best = Some((attempt, result));
+            }
+        }
+
+        // SHUFFLE_ATTEMPTS > 0, so at least one attempt was recorded
+        let (best_state, best_result) = best.expect("at least one shuffle attempt");
+        debug_assert!(best_state.is_solvable(), "shuffle produced an unsolvable state");
+        *state = best_state;
+        best_result
+    }
+
+    /// Performs a single no-backtracking random walk until the target entropy or move cap
+    /// With a region, the empty cell only moves within it
+    fn random_walk(
+        &self,
+        state: &mut PuzzleState,
+        target_entropy: u32,
+        max_moves: usize,
+        region: Option<Region>,
+        calculator: &dyn EntropyCalculator,
+        rng: &mut impl Rng,
+    ) -> WalkOutcome {
         let mut previous_empty: Option<Position> = None;
         let mut moves_made = 0;
 
         while calculator.calculate(state) < target_entropy && moves_made < max_moves {
             let current_empty = state.empty_position();
-            let mut moves = self.validator.get_immediate_moves(current_empty);
+            let mut moves = state.legal_moves();
 
             // Remove backtracking move
             if let Some(prev) = previous_empty {
                 moves.retain(|&pos| pos != prev);
             }
+            if let Some(region) = region {
+                moves.retain(|&pos| region.contains(pos));
+            }
 
             // If no moves available (shouldn't happen), break
             if moves.is_empty() {
@@ -69,45 +243,90 @@ impl ShuffleController {
             }
 
             // Choose a random move
-            let chosen_move = moves.choose(&mut rng).unwrap();
+            let chosen_move = moves.choose(rng).unwrap();
             previous_empty = Some(current_empty);
 
-            state.apply_immediate_move(*chosen_move);
+            state.apply(*chosen_move);
             moves_made += 1;
         }
 
         let final_entropy = calculator.calculate(state);
-        let target_met = final_entropy >= target_entropy;
-
-        ShuffleResult {
+        WalkOutcome {
             moves_made,
             final_entropy,
-            target_met,
+            target_met: final_entropy >= target_entropy,
         }
     }
 
-    /// Shuffles with a specific number of moves (alternative to entropy-based)
-    pub fn shuffle_n_moves(&self, state: &mut PuzzleState, n: usize) {
-        let mut rng = thread_rng();
-        let mut previous_empty: Option<Position> = None;
-
-        for _ in 0..n {
-            let current_empty = state.empty_position();
-            let mut moves = self.validator.get_immediate_moves(current_empty);
+    /// Replaces the board with a uniformly random solvable layout
+    ///
+    /// Random walks favor states near the goal, so even long walks produce boards
+    /// that look alike. This draws a random permutation instead; half of all
+    /// permutations are unsolvable, and swapping any two tiles flips the parity,
+    /// so an unsolvable draw is fixed by swapping the first two tiles.
+    ///
+    /// No moves are made, so the result reports 0 moves; entropy is measured
+    /// with Manhattan distance.
+    pub fn shuffle_uniform(&self, state: &mut PuzzleState) -> ShuffleResult {
+        self.shuffle_uniform_seeded(state, thread_rng().gen())
+    }
 
-            // Remove backtracking move
-            if let Some(prev) = previous_empty {
-                moves.retain(|&pos| pos != prev);
+    /// Draws a uniform layout like `shuffle_uniform`, repeatably for a given seed
+    pub fn shuffle_uniform_seeded(&self, state: &mut PuzzleState, seed: u64) -> ShuffleResult {
+        let started = Instant::now();
+        let size = state.size();
+        let mut cells: Vec<Option<u32>> = (1..(size * size) as u32).map(Some).collect();
+        cells.push(None);
+        cells.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        let layout = |cells: &[Option<u32>]| -> Vec<Vec<Option<u32>>> {
+            cells.chunks(size).map(<[Option<u32>]>::to_vec).collect()
+        };
+        let goal = state.goal();
+        let shuffled = match PuzzleState::from_layout_with_goal(&layout(&cells), goal) {
+            Err(PuzzleError::UnsolvableLayout) => {
+                // Swap the first two tiles (never the blank) to flip the parity
+                let mut tiles = (0..cells.len()).filter(|&i| cells[i].is_some());
+                let first = tiles.next().expect("grid has at least two tiles");
+                let second = tiles.next().expect("grid has at least two tiles");
+                cells.swap(first, second);
+                PuzzleState::from_layout_with_goal(&layout(&cells), goal)
             }
+            other => other,
+        };
 
-            if moves.is_empty() {
-                break;
-            }
+        // A parity swap always yields a solvable layout of the same size
+        *state = shuffled.expect("parity-corrected layout is solvable");
+        ShuffleResult {
+            moves_made: 0,
+            final_entropy: ManhattanDistance.calculate(state),
+            target_met: true,
+            seed,
+            duration: started.elapsed(),
+        }
+    }
 
-            let chosen_move = moves.choose(&mut rng).unwrap();
-            previous_empty = Some(current_empty);
+    /// Shuffles with a specific number of moves (alternative to entropy-based)
+    ///
+    /// The target is met once all `n` moves are made; entropy is measured with
+    /// Manhattan distance.
+    pub fn shuffle_n_moves(&self, state: &mut PuzzleState, n: usize) -> ShuffleResult {
+        self.shuffle_n_moves_seeded(state, n, thread_rng().gen())
+    }
+
+    /// Makes `n` random moves like `shuffle_n_moves`, repeatably for a given seed
+    pub fn shuffle_n_moves_seeded(&self, state: &mut PuzzleState, n: usize, seed: u64) -> ShuffleResult {
+        let started = Instant::now();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let moves_made = state.random_walk(n, &mut rng).len();
 
-            state.apply_immediate_move(*chosen_move);
+        debug_assert!(state.is_solvable(), "shuffle produced an unsolvable state");
+        ShuffleResult {
+            moves_made,
+            final_entropy: ManhattanDistance.calculate(state),
+            target_met: moves_made == n,
+            seed,
+            duration: started.elapsed(),
         }
     }
 }
@@ -142,6 +361,50 @@ mod tests {
         assert!(entropy >= Difficulty::Medium.min_entropy(4));
     }
 
+    #[test]
+    fn test_shuffle_uniform() {
+        for size in [3, 4, 7] {
+            let controller = ShuffleController::new(size).unwrap();
+            let mut seen = std::collections::HashSet::new();
+            for _ in 0..20 {
+                let mut puzzle = PuzzleState::new(size).unwrap();
+                controller.shuffle_uniform(&mut puzzle);
+                assert_eq!(puzzle.size(), size);
+                assert!(puzzle.is_solvable());
+                seen.insert(puzzle.to_notation());
+            }
+            // 20 independent draws from at least 181,440 boards never all collide
+            assert!(seen.len() > 1);
+        }
+    }
+
+    #[test]
+    fn test_custom_difficulty_target() {
+        let controller = ShuffleController::new(4).unwrap();
+        let calculator = ManhattanDistance;
+
+        let mut puzzle = PuzzleState::new(4).unwrap();
+        let result = controller.shuffle_with_result(&mut puzzle, Difficulty::Custom { min_entropy: 30 }, &calculator);
+        assert!(result.target_met);
+        assert!(calculator.calculate(&puzzle) >= 30);
+
+        // Unreachable targets give up instead of walking forever
+        let mut puzzle = PuzzleState::new(4).unwrap();
+        let impossible = Difficulty::Custom {
+            min_entropy: Difficulty::max_entropy(4) + 1,
+        };
+        let result = controller.shuffle_with_result(&mut puzzle, impossible, &calculator);
+        assert!(!result.target_met);
+        assert!(puzzle.is_solvable());
+
+        // Extra Hard asks more of a 3×3 board than any layout has
+        let controller = ShuffleController::new(3).unwrap();
+        let mut puzzle = PuzzleState::new(3).unwrap();
+        let result = controller.shuffle_with_result(&mut puzzle, Difficulty::ExtraHard, &calculator);
+        assert!(!result.target_met);
+        assert!(puzzle.is_solvable());
+    }
+
     #[test]
     fn test_shuffle_n_moves() {
         let mut puzzle = PuzzleState::new(4).unwrap();
@@ -155,6 +418,18 @@ mod tests {
         assert_ne!(initial_empty, after_empty);
     }
 
+    #[test]
+    fn test_shuffled_puzzle_is_solvable() {
+        for size in [3, 4, 5] {
+            let controller = ShuffleController::new(size).unwrap();
+            for difficulty in [Difficulty::Easy, Difficulty::Hard] {
+                let mut puzzle = PuzzleState::new(size).unwrap();
+                controller.shuffle(&mut puzzle, difficulty, &ManhattanDistance);
+                assert!(puzzle.is_solvable());
+            }
+        }
+    }
+
     #[test]
     fn test_shuffled_puzzle_not_solved() {
         let mut puzzle = PuzzleState::new(4).unwrap();
@@ -167,6 +442,75 @@ mod tests {
         assert!(!puzzle.is_solved());
     }
 
+    #[test]
+    fn test_seeded_shuffles_repeat() {
+        let controller = ShuffleController::new(4).unwrap();
+        let shuffled = |seed| {
+            let mut puzzle = PuzzleState::new(4).unwrap();
+            controller.shuffle_seeded(&mut puzzle, Difficulty::Medium, &ManhattanDistance, seed);
+            puzzle.to_layout()
+        };
+
+        assert_eq!(shuffled(42), shuffled(42));
+        assert_ne!(shuffled(42), shuffled(43));
+    }
+
+    #[test]
+    fn test_results_record_seed() {
+        let controller = ShuffleController::new(4).unwrap();
+        let mut puzzle = PuzzleState::new(4).unwrap();
+        let result = controller.shuffle(&mut puzzle, Difficulty::Medium, &ManhattanDistance);
+        assert!(result.target_met);
+        assert_eq!(result.final_entropy, ManhattanDistance.calculate(&puzzle));
+
+        // The reported seed repeats an unseeded shuffle
+        let mut again = PuzzleState::new(4).unwrap();
+        let repeat = controller.shuffle_seeded(&mut again, Difficulty::Medium, &ManhattanDistance, result.seed);
+        assert_eq!(repeat.moves_made, result.moves_made);
+        assert_eq!(again.to_layout(), puzzle.to_layout());
+
+        let mut walked = PuzzleState::new(4).unwrap();
+        let result = controller.shuffle_n_moves(&mut walked, 12);
+        assert!(result.target_met);
+        assert_eq!(result.moves_made, 12);
+        let mut again = PuzzleState::new(4).unwrap();
+        controller.shuffle_n_moves_seeded(&mut again, 12, result.seed);
+        assert_eq!(again.to_layout(), walked.to_layout());
+
+        let mut uniform = PuzzleState::new(4).unwrap();
+        let result = controller.shuffle_uniform(&mut uniform);
+        assert_eq!(result.moves_made, 0);
+        let mut again = PuzzleState::new(4).unwrap();
+        controller.shuffle_uniform_seeded(&mut again, result.seed);
+        assert_eq!(again.to_layout(), uniform.to_layout());
+    }
+
+    #[test]
+    fn test_shuffle_region() {
+        let controller = ShuffleController::new(5).unwrap();
+        let region = Region::bottom_rows(5, 2);
+        let mut puzzle = PuzzleState::new(5).unwrap();
+        let result = controller
+            .shuffle_region_seeded(&mut puzzle, region, Difficulty::Hard, &ManhattanDistance, 3)
+            .unwrap();
+        assert!(result.target_met);
+        assert!(puzzle.is_solvable() && !puzzle.is_solved());
+        // Only tiles in the region moved
+        let solved = PuzzleState::new(5).unwrap().to_layout();
+        let layout = puzzle.to_layout();
+        for row in 0..3 {
+            assert_eq!(layout[row], solved[row]);
+        }
+
+        let mut puzzle = PuzzleState::new(5).unwrap();
+        let outside = Region::new(0, 0, 2, 2);
+        assert!(matches!(
+            controller.shuffle_region(&mut puzzle, outside, Difficulty::Easy, &ManhattanDistance),
+            Err(PuzzleError::InvalidRegion(_))
+        ));
+        assert!(puzzle.is_solved());
+    }
+
     #[test]
     fn test_no_immediate_backtracking_during_shuffle() {
         // This test verifies that shuffles don't immediately undo the previous move
@@ -0,0 +1,163 @@
+//! Bounded cache of solver results, keyed by board layout.
+//!
+//! The game controller keeps the solution it is working from in its solver
+//! state, and trims it as the player follows it. Once the player strays, that
+//! path is gone. This cache remembers every board solved recently, so returning
+//! to one (by sliding tiles back, for instance) brings its solution back for
+//! hints, auto-solve and the solution-length readout without solving again.
+//! When full, the least recently used board is forgotten.
+
+use crate::model::{PackedState, Position, PuzzleState};
+use std::collections::HashMap;
+
+/// Boards remembered by default
+pub const DEFAULT_SOLUTION_CACHE_CAPACITY: usize = 64;
+
+/// A solution found for one board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedSolution {
+    /// Tile positions to move, in order
+    pub path: Vec<Position>,
+    /// True if the solver guarantees no shorter path exists
+    pub optimal: bool,
+    /// How long the original solve took
+    pub solve_time_micros: u64,
+}
+
+/// Least-recently-used map from board layout to solution
+#[derive(Debug, Clone)]
+pub struct SolutionCache {
+    capacity: usize,
+    entries: HashMap<PackedState, (CachedSolution, u64)>, // Solution and last use
+    clock: u64, // Ticks on every use, ordering entries by recency
+}
+
+impl SolutionCache {
+    /// Creates an empty cache holding up to `capacity` boards (0 disables it)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Returns the number of boards held
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the solution cached for `state`, marking it as recently used
+    pub fn get(&mut self, state: &PuzzleState) -> Option<&CachedSolution> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(&PackedState::from_state(state)).map(|(solution, used)| {
+            *used = clock;
+            &*solution
+        })
+    }
+
+    /// Returns the optimal solution length cached for `state` without marking it as used
+    pub fn optimal_length(&self, state: &PuzzleState) -> Option<u32> {
+        self.entries
+            .get(&PackedState::from_state(state))
+            .filter(|(solution, _)| solution.optimal)
+            .map(|(solution, _)| solution.path.len() as u32)
+    }
+
+    /// Remembers `solution` for `state`, forgetting the least recently used board if full
+    ///
+    /// A cached optimal solution is never replaced by a non-optimal one.
+    pub fn insert(&mut self, state: &PuzzleState, solution: CachedSolution) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        let key = PackedState::from_state(state);
+        if let Some((cached, used)) = self.entries.get_mut(&key) {
+            if solution.optimal || !cached.optimal {
+                *cached = solution;
+            }
+            *used = self.clock;
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            let oldest = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (solution, self.clock));
+    }
+
+    /// Forgets every board
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for SolutionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SOLUTION_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(path: Vec<Position>, optimal: bool) -> CachedSolution {
+        CachedSolution {
+            path,
+            optimal,
+            solve_time_micros: 0,
+        }
+    }
+
+    fn board(moves: &[Position]) -> PuzzleState {
+        let mut state = PuzzleState::new(3).unwrap();
+        for &pos in moves {
+            assert!(state.apply_immediate_move(pos));
+        }
+        state
+    }
+
+    #[test]
+    fn test_least_recently_used_board_is_forgotten() {
+        let mut cache = SolutionCache::new(2);
+        let (a, b, c) = (board(&[(2, 1)]), board(&[(1, 2)]), board(&[(2, 1), (1, 1)]));
+        cache.insert(&a, solution(vec![(2, 2)], true));
+        cache.insert(&b, solution(vec![(2, 2)], true));
+        assert!(cache.get(&a).is_some()); // b is now the oldest
+        cache.insert(&c, solution(vec![(2, 1), (2, 2)], true));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&b).is_none());
+        assert_eq!(cache.optimal_length(&a), Some(1));
+        assert_eq!(cache.optimal_length(&c), Some(2));
+    }
+
+    #[test]
+    fn test_optimal_solutions_are_kept() {
+        let mut cache = SolutionCache::default();
+        let state = board(&[(2, 1)]);
+        cache.insert(&state, solution(vec![(2, 2)], true));
+        cache.insert(&state, solution(vec![(1, 1), (2, 1), (2, 2)], false));
+        assert_eq!(cache.get(&state).unwrap().path, vec![(2, 2)]);
+
+        let other = board(&[(1, 2)]);
+        cache.insert(&other, solution(vec![(1, 1), (2, 2)], false));
+        assert_eq!(cache.optimal_length(&other), None);
+        cache.insert(&other, solution(vec![(2, 2)], true));
+        assert_eq!(cache.optimal_length(&other), Some(1));
+
+        let mut disabled = SolutionCache::new(0);
+        disabled.insert(&state, solution(vec![(2, 2)], true));
+        assert!(disabled.is_empty());
+    }
+}
@@ -0,0 +1,504 @@
+//! # Solver Benchmarks
+//!
+//! A fixed suite of named benchmarks over the solvers, heuristics and shuffler.
+//! Boards come from seeded shuffles, so a given configuration measures the same
+//! work every run and results can be compared across commits by name.
+//!
+//! ```rust,no_run
+//! use slider::controller::solver_benchmark::{run_suite, BenchmarkConfig, BenchmarkReport};
+//! use std::path::Path;
+//!
+//! let config = BenchmarkConfig::default();
+//! let results = run_suite(&config);
+//! for result in &results {
+//!     println!("{}: {:.1}μs", result.name, result.mean_micros);
+//! }
+//! BenchmarkReport::new(&config, results).save(Path::new("results.csv"))?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::controller::{PuzzleCorpus, ShuffleController};
+use crate::model::{
+    AStarSolver, Difficulty, EntropyCalculator, ManhattanDistance, PuzzleState,
+    ShortestPathHeuristic, SolverConfig,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Heuristic evaluations timed together per sample; one is too fast to measure
+const HEURISTIC_BATCH: usize = 1_000;
+
+/// Names of every benchmark in the suite, in run order
+pub const BENCHMARK_NAMES: [&str; 6] = [
+    "solve_3x3_hard",
+    "solve_4x4_easy",
+    "solve_4x4_medium",
+    "heuristic_manhattan",
+    "heuristic_linear_conflict",
+    "shuffle_hard",
+];
+
+/// Settings for a suite run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    /// Measurements per benchmark, each on its own seeded board
+    pub samples: usize,
+    /// Seed of the first sample's board; sample `i` uses `seed + i`
+    pub seed: u64,
+    /// Limits and A* policy (weight, tie-break, reopening) for each solve
+    pub solver: SolverConfig,
+    /// Runs only benchmarks whose name contains this text
+    pub filter: Option<String>,
+    /// Boards for the solve benchmarks; sets missing from the corpus fall back to seeded shuffles
+    pub corpus: Option<PuzzleCorpus>,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            samples: 10,
+            seed: 1,
+            solver: SolverConfig::default(),
+            filter: None,
+            corpus: None,
+        }
+    }
+}
+
+/// Sizes and difficulties of the solve benchmarks, in suite order
+pub const SOLVE_SETS: [(usize, Difficulty); 3] = [
+    (3, Difficulty::Hard),
+    (4, Difficulty::Easy),
+    (4, Difficulty::Medium),
+];
+
+/// Builds the corpus the solve benchmarks would shuffle for `config`
+///
+/// Saving it and passing it back through `BenchmarkConfig::corpus` keeps the
+/// boards fixed even if the shuffler changes between commits.
+pub fn benchmark_corpus(config: &BenchmarkConfig) -> PuzzleCorpus {
+    PuzzleCorpus::generate(&SOLVE_SETS, config.samples, config.seed)
+        .expect("benchmark sizes are valid")
+}
+
+/// Timing summary for one benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub name: String,
+    pub samples: usize,
+    pub mean_micros: f64,
+    pub median_micros: f64,
+    pub min_micros: f64,
+    pub max_micros: f64,
+    /// Mean nodes expanded per successful solve; None for benchmarks that do not search
+    pub nodes_expanded: Option<f64>,
+    /// Mean solution length per successful solve, to set against `nodes_expanded`
+    /// when the policy gives up optimality; None for benchmarks that do not search
+    #[serde(default)]
+    pub mean_moves: Option<f64>,
+    /// Solves that hit the solver's limits
+    pub unsolved: usize,
+}
+
+/// A suite run with enough context to compare it with later runs
+///
+/// Saved as JSON (which `load` reads back) or as CSV with one row per benchmark,
+/// so CI jobs and notebooks can track results over time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    /// Version of this crate that produced the report
+    pub crate_version: String,
+    /// When the run finished, in seconds since the Unix epoch
+    pub created_at: u64,
+    pub samples: usize,
+    pub seed: u64,
+    /// The A* policy the solves ran with (see `policy_label`)
+    #[serde(default)]
+    pub policy: String,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// File formats a report can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    /// Picks the format from a file extension (`.json` or `.csv`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ReportFormat::Json),
+            "csv" => Some(ReportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl BenchmarkReport {
+    /// Wraps the results of a run with `config`
+    pub fn new(config: &BenchmarkConfig, results: Vec<BenchmarkResult>) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at,
+            samples: config.samples,
+            seed: config.seed,
+            policy: policy_label(&config.solver),
+            results,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports serialize")
+    }
+
+    /// Formats one row per benchmark; `nodes_expanded` and `mean_moves` are
+    /// empty for benchmarks that do not search
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "name,samples,mean_micros,median_micros,min_micros,max_micros,nodes_expanded,mean_moves,unsolved\n",
+        );
+        for r in &self.results {
+            let optional = |value: Option<f64>| value.map_or(String::new(), |n| format!("{:.1}", n));
+            csv.push_str(&format!(
+                "{},{},{:.3},{:.3},{:.3},{:.3},{},{},{}\n",
+                r.name,
+                r.samples,
+                r.mean_micros,
+                r.median_micros,
+                r.min_micros,
+                r.max_micros,
+                optional(r.nodes_expanded),
+                optional(r.mean_moves),
+                r.unsolved
+            ));
+        }
+        csv
+    }
+
+    /// Writes the report in the format given by the path's extension
+    ///
+    /// # Errors
+    ///
+    /// Fails if the extension is neither `.json` nor `.csv`, or the file cannot be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = match ReportFormat::from_path(path) {
+            Some(ReportFormat::Json) => self.to_json(),
+            Some(ReportFormat::Csv) => self.to_csv(),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a .json or .csv file", path.display()),
+                ))
+            }
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)
+    }
+
+    /// Reads a report saved as JSON
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Change in mean time between two runs of the same benchmark
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline_micros: f64,
+    pub current_micros: f64,
+    /// Positive when the current run is slower
+    pub change_percent: f64,
+}
+
+/// Runs every benchmark selected by `config`
+pub fn run_suite(config: &BenchmarkConfig) -> Vec<BenchmarkResult> {
+    BENCHMARK_NAMES
+        .iter()
+        .filter(|name| {
+            config
+                .filter
+                .as_deref()
+                .is_none_or(|filter| name.contains(filter))
+        })
+        .map(|&name| run_benchmark(name, config))
+        .collect()
+}
+
+/// Pairs benchmarks present in both runs, in `current` order
+pub fn compare(baseline: &[BenchmarkResult], current: &[BenchmarkResult]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|now| {
+            let before = baseline.iter().find(|b| b.name == now.name)?;
+            let change_percent = if before.mean_micros > 0.0 {
+                (now.mean_micros - before.mean_micros) / before.mean_micros * 100.0
+            } else {
+                0.0
+            };
+            Some(Comparison {
+                name: now.name.clone(),
+                baseline_micros: before.mean_micros,
+                current_micros: now.mean_micros,
+                change_percent,
+            })
+        })
+        .collect()
+}
+
+fn run_benchmark(name: &str, config: &BenchmarkConfig) -> BenchmarkResult {
+    log::info!("Running benchmark {}", name);
+    let seeds = (0..config.samples as u64).map(|i| config.seed.wrapping_add(i));
+    let mut times = Vec::with_capacity(config.samples);
+    let mut nodes = Vec::new();
+    let mut moves = Vec::new();
+    let mut unsolved = 0;
+
+    match name {
+        "solve_3x3_hard" | "solve_4x4_easy" | "solve_4x4_medium" => {
+            let (size, difficulty) = match name {
+                "solve_3x3_hard" => SOLVE_SETS[0],
+                "solve_4x4_easy" => SOLVE_SETS[1],
+                _ => SOLVE_SETS[2],
+            };
+            let boards = match config
+                .corpus
+                .as_ref()
+                .map(|corpus| corpus.boards(size, difficulty))
+            {
+                Some(boards) if !boards.is_empty() => boards,
+                _ => seeds.map(|seed| shuffled(size, difficulty, seed)).collect(),
+            };
+            let solver = AStarSolver::with_config(config.solver);
+            for board in boards {
+                let start = Instant::now();
+                let solution = solver.solve_instrumented(&board, None, &mut |_| {});
+                times.push(start.elapsed());
+                match solution {
+                    Some(solution) => {
+                        nodes.push(solution.nodes_expanded);
+                        moves.push(solution.len());
+                    }
+                    None => unsolved += 1,
+                }
+            }
+        }
+        "heuristic_manhattan" | "heuristic_linear_conflict" => {
+            let calculator: &dyn EntropyCalculator = if name == "heuristic_manhattan" {
+                &ManhattanDistance
+            } else {
+                &ShortestPathHeuristic
+            };
+            for seed in seeds {
+                let board = shuffled(4, Difficulty::Hard, seed);
+                let start = Instant::now();
+                for _ in 0..HEURISTIC_BATCH {
+                    std::hint::black_box(calculator.calculate(std::hint::black_box(&board)));
+                }
+                times.push(start.elapsed() / HEURISTIC_BATCH as u32);
+            }
+        }
+        "shuffle_hard" => {
+            for seed in seeds {
+                let start = Instant::now();
+                std::hint::black_box(shuffled(4, Difficulty::Hard, seed));
+                times.push(start.elapsed());
+            }
+        }
+        _ => unreachable!("unknown benchmark {}", name),
+    }
+
+    summarize(name, times, &nodes, &moves, unsolved)
+}
+
+/// Returns a `size`×`size` board shuffled from solved with the given seed
+fn shuffled(size: usize, difficulty: Difficulty, seed: u64) -> PuzzleState {
+    let mut state = PuzzleState::new(size).expect("benchmark sizes are valid");
+    ShuffleController::new(size)
+        .expect("benchmark sizes are valid")
+        .shuffle_seeded(&mut state, difficulty, &ManhattanDistance, seed);
+    state
+}
+
+fn summarize(
+    name: &str,
+    mut times: Vec<Duration>,
+    nodes: &[usize],
+    moves: &[usize],
+    unsolved: usize,
+) -> BenchmarkResult {
+    times.sort();
+    let micros: Vec<f64> = times
+        .iter()
+        .map(|t| t.as_secs_f64() * 1_000_000.0)
+        .collect();
+
+    BenchmarkResult {
+        name: name.to_string(),
+        samples: micros.len(),
+        mean_micros: mean(micros.iter().copied()),
+        median_micros: micros.get(micros.len() / 2).copied().unwrap_or(0.0),
+        min_micros: micros.first().copied().unwrap_or(0.0),
+        max_micros: micros.last().copied().unwrap_or(0.0),
+        nodes_expanded: name
+            .starts_with("solve_")
+            .then(|| mean(nodes.iter().map(|&n| n as f64))),
+        mean_moves: name
+            .starts_with("solve_")
+            .then(|| mean(moves.iter().map(|&n| n as f64))),
+        unsolved,
+    }
+}
+
+/// Describes the A* policy of `config`, such as `weight 1.50, ties low-g, reopen`
+pub fn policy_label(config: &SolverConfig) -> String {
+    let mut label = format!("weight {:.2}, ties {}", config.weight.max(1.0), config.tie_break);
+    if config.reopen_closed {
+        label.push_str(", reopen");
+    }
+    if config.symmetry {
+        label.push_str(", symmetry");
+    }
+    label
+}
+
+/// Returns the mean of `values`, or 0 if there are none
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TieBreak;
+
+    #[test]
+    fn test_run_suite() {
+        let config = BenchmarkConfig {
+            samples: 3,
+            filter: Some("3x3".to_string()),
+            ..BenchmarkConfig::default()
+        };
+        let results = run_suite(&config);
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.name, "solve_3x3_hard");
+        assert_eq!(result.samples, 3);
+        assert_eq!(result.unsolved, 0);
+        assert!(result.nodes_expanded.unwrap() > 0.0);
+        assert!(
+            result.min_micros <= result.median_micros && result.median_micros <= result.max_micros
+        );
+
+        let heuristic = run_suite(&BenchmarkConfig {
+            samples: 1,
+            filter: Some("heuristic_manhattan".to_string()),
+            ..BenchmarkConfig::default()
+        });
+        assert_eq!(heuristic[0].nodes_expanded, None);
+    }
+
+    #[test]
+    fn test_policy_is_reported() {
+        let config = |weight| BenchmarkConfig {
+            samples: 3,
+            filter: Some("solve_4x4_medium".to_string()),
+            solver: SolverConfig {
+                weight,
+                tie_break: TieBreak::LowG,
+                reopen_closed: true,
+                ..SolverConfig::default()
+            },
+            ..BenchmarkConfig::default()
+        };
+        let weighted = run_suite(&config(3.0));
+        assert_eq!(weighted[0].unsolved, 0);
+        assert!(weighted[0].mean_moves.unwrap() > 0.0);
+        assert!(weighted[0].nodes_expanded.unwrap() > 0.0);
+        assert_eq!(policy_label(&SolverConfig::default()), "weight 1.00, ties high-g");
+
+        let report = BenchmarkReport::new(&config(3.0), weighted);
+        assert_eq!(report.policy, "weight 3.00, ties low-g, reopen");
+    }
+
+    #[test]
+    fn test_corpus_boards() {
+        let mut config = BenchmarkConfig {
+            samples: 2,
+            filter: Some("solve_3x3".to_string()),
+            ..BenchmarkConfig::default()
+        };
+        let shuffled = run_suite(&config);
+
+        // The suite's own corpus holds the same boards it would shuffle
+        config.corpus = Some(benchmark_corpus(&config));
+        let from_corpus = run_suite(&config);
+        assert_eq!(from_corpus[0].samples, 2);
+        assert_eq!(from_corpus[0].nodes_expanded, shuffled[0].nodes_expanded);
+
+        config.corpus = Some(PuzzleCorpus::generate(&[(3, Difficulty::Hard)], 5, 99).unwrap());
+        assert_eq!(run_suite(&config)[0].samples, 5);
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let config = BenchmarkConfig {
+            samples: 1,
+            filter: Some("heuristic".to_string()),
+            ..BenchmarkConfig::default()
+        };
+        let report = BenchmarkReport::new(&config, run_suite(&config));
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("name,samples,"));
+        assert!(lines[1].starts_with("heuristic_manhattan,1,"));
+        assert!(lines[1].ends_with(",,0")); // No node count for heuristics
+
+        let dir = std::env::temp_dir().join(format!("slider-report-{}", std::process::id()));
+        let path = dir.join("report.json");
+        report.save(&path).unwrap();
+        assert_eq!(BenchmarkReport::load(&path).unwrap(), report);
+        assert!(report.save(&dir.join("report.txt")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compare() {
+        let result = |name: &str, mean_micros| BenchmarkResult {
+            name: name.to_string(),
+            samples: 1,
+            mean_micros,
+            median_micros: mean_micros,
+            min_micros: mean_micros,
+            max_micros: mean_micros,
+            nodes_expanded: None,
+            mean_moves: None,
+            unsolved: 0,
+        };
+        let baseline = vec![result("a", 100.0), result("gone", 5.0)];
+        let current = vec![result("a", 150.0), result("new", 1.0)];
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].name, "a");
+        assert!((comparisons[0].change_percent - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,146 @@
+//! Picks the solver for each background solve, and what to try when it gives up.
+//!
+//! A* with the enhanced heuristic is quickest up to 4×4. On 5×5 the open set
+//! outgrows memory, so boards go to IDA*, which searches on every core with
+//! memory linear in depth; walking distance tables stop at 4×4, so it uses
+//! Manhattan distance there. Even so, a heavily scrambled 5×5 board can take
+//! hours, so boards whose Manhattan distance is above a limit go straight to
+//! the strategic solver, as does every larger grid. Each choice gets a timeout
+//! of its own, and an optimal solver that times out or runs out of nodes hands
+//! the board to the strategic solver, which always finishes.
+//!
+//! ```rust
+//! use slider::controller::SolverDispatcher;
+//! use slider::model::{PuzzleState, SolverConfig, SolverKind};
+//!
+//! let dispatcher = SolverDispatcher::default();
+//! let choice = dispatcher.choose(&PuzzleState::new(4)?, SolverConfig::default());
+//! assert_eq!(choice.kind, SolverKind::AStar);
+//! assert_eq!(dispatcher.fallback(choice.kind), Some(SolverKind::Strategic));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::model::{PuzzleState, SolverConfig, SolverKind};
+use std::time::Duration;
+
+/// Manhattan distance above which 5×5 boards skip IDA* by default
+pub const DEFAULT_IDA_STAR_MAX_DISTANCE: u32 = 60;
+
+/// A solver and the limits to run it with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverChoice {
+    pub kind: SolverKind,
+    pub config: SolverConfig,
+}
+
+/// Rules for picking a solver by board size and scramble
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverDispatcher {
+    /// Largest grid solved with A*
+    pub astar_max_size: usize,
+    /// Largest grid solved with IDA*; larger ones use the strategic solver
+    pub ida_star_max_size: usize,
+    /// Largest Manhattan distance IDA* takes on
+    pub ida_star_max_distance: u32,
+    pub astar_timeout: Duration,
+    pub ida_star_timeout: Duration,
+    pub strategic_timeout: Duration,
+}
+
+impl Default for SolverDispatcher {
+    fn default() -> Self {
+        Self {
+            astar_max_size: 4,
+            ida_star_max_size: 5,
+            ida_star_max_distance: DEFAULT_IDA_STAR_MAX_DISTANCE,
+            astar_timeout: Duration::from_secs(15),
+            ida_star_timeout: Duration::from_secs(30),
+            strategic_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SolverDispatcher {
+    /// Returns the solver for `state`, with `base` limited to its timeout
+    pub fn choose(&self, state: &PuzzleState, base: SolverConfig) -> SolverChoice {
+        let size = state.size();
+        let kind = if size <= self.astar_max_size {
+            SolverKind::AStar
+        } else if size <= self.ida_star_max_size && state.manhattan_distance() <= self.ida_star_max_distance {
+            SolverKind::IdaStar
+        } else {
+            SolverKind::Strategic
+        };
+        self.with_kind(kind, base)
+    }
+
+    /// Returns `kind` with `base` limited to the timeout for that solver
+    ///
+    /// Greedy search has no timeout of its own and keeps `base` as it is.
+    pub fn with_kind(&self, kind: SolverKind, base: SolverConfig) -> SolverChoice {
+        let timeout = match kind {
+            SolverKind::AStar => self.astar_timeout,
+            SolverKind::IdaStar => self.ida_star_timeout,
+            SolverKind::Strategic => self.strategic_timeout,
+            SolverKind::Greedy => base.timeout,
+        };
+        SolverChoice {
+            kind,
+            config: SolverConfig {
+                timeout: timeout.min(base.timeout),
+                ..base
+            },
+        }
+    }
+
+    /// Returns the solver to try when `kind` gives up on a board
+    /// Returns None for the strategic solver, which has nothing to fall back to
+    pub fn fallback(&self, kind: SolverKind) -> Option<SolverKind> {
+        (kind != SolverKind::Strategic).then_some(SolverKind::Strategic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::scramble;
+
+    #[test]
+    fn test_choice_follows_size_and_distance() {
+        let dispatcher = SolverDispatcher::default();
+        let config = SolverConfig::default();
+        let kind = |state: &PuzzleState| dispatcher.choose(state, config).kind;
+
+        assert_eq!(kind(&scramble(3, 40, 1)), SolverKind::AStar);
+        assert_eq!(kind(&scramble(4, 40, 1)), SolverKind::AStar);
+        assert_eq!(kind(&scramble(5, 10, 1)), SolverKind::IdaStar);
+        assert_eq!(kind(&scramble(8, 10, 1)), SolverKind::Strategic);
+
+        let scrambled = scramble(5, 1000, 1);
+        assert!(scrambled.manhattan_distance() > 20);
+        let cautious = SolverDispatcher {
+            ida_star_max_distance: 20,
+            ..dispatcher
+        };
+        assert_eq!(cautious.choose(&scrambled, config).kind, SolverKind::Strategic);
+    }
+
+    #[test]
+    fn test_timeouts_never_exceed_the_base() {
+        let dispatcher = SolverDispatcher::default();
+        let base = SolverConfig::default();
+        let choice = dispatcher.choose(&scramble(4, 40, 1), base);
+        assert_eq!(choice.config.timeout, dispatcher.astar_timeout);
+        assert_eq!(choice.config.max_iterations, base.max_iterations);
+
+        let short = SolverConfig {
+            timeout: Duration::from_secs(1),
+            ..base
+        };
+        assert_eq!(dispatcher.with_kind(SolverKind::Strategic, short).config.timeout, Duration::from_secs(1));
+        assert_eq!(dispatcher.with_kind(SolverKind::Greedy, base).config, base);
+
+        assert_eq!(dispatcher.fallback(SolverKind::IdaStar), Some(SolverKind::Strategic));
+        assert_eq!(dispatcher.fallback(SolverKind::Strategic), None);
+    }
+}
@@ -0,0 +1,576 @@
+//! Background solves, shuffles, game analyses and metrics behind a small polling interface.
+//!
+//! The controller never touches threads directly: it starts a [`SolverTask`] with
+//! [`spawn_solver_task`], polls it once per frame, and cancels it when the board
+//! changes. Native builds run each solve on its own thread ([`ThreadTask`]);
+//! targets without threads (wasm32) run it on the first poll ([`InlineTask`]).
+//! Other backends, such as web workers or executor futures, only need to
+//! implement the trait.
+//!
+//! Shuffles of large grids use the same scheme through [`ShuffleTask`] and
+//! [`spawn_shuffle_task`], post-game analyses through [`AnalysisTask`] and
+//! [`spawn_analysis_task`], and entropy metrics through [`MetricsTask`] and
+//! [`spawn_metrics_task`].
+
+use crate::controller::game_controller::EntropyMetrics;
+use crate::controller::shuffle_controller::{ShuffleController, ShuffleResult};
+use crate::model::analysis::{analyze_game, GameAnalysis};
+use crate::model::{
+    Difficulty, EntropyCalculator, Position, PuzzleState, Solution, SolverConfig, SolverError, SolverKind,
+    SolverProgress,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Latest progress snapshot shared between a running solve and its task
+type ProgressSlot = Arc<Mutex<Option<SolverProgress>>>;
+
+/// What a poll found
+#[derive(Debug)]
+pub enum TaskStatus<T = Result<Solution, SolverError>> {
+    /// The task is still running
+    Running,
+    /// The task ended; for solves, the error says why the solver gave up
+    Finished(T),
+    /// The task crashed, or was already finished
+    Failed,
+}
+
+/// A solve running off the caller's thread of control
+pub trait SolverTask: Send {
+    /// Checks for a result without blocking (except for [`InlineTask`])
+    ///
+    /// Returns `Finished` or `Failed` once; the task is spent afterwards and
+    /// further polls return `Failed`.
+    fn poll(&mut self) -> TaskStatus;
+
+    /// Asks the solve to stop at its next progress check
+    fn cancel(&self);
+
+    /// Returns the latest progress snapshot, or None if none was reported yet
+    fn progress(&self) -> Option<SolverProgress>;
+}
+
+/// Starts solving `state` with the backend suited to the target
+pub fn spawn_solver_task(kind: SolverKind, config: SolverConfig, state: PuzzleState) -> Box<dyn SolverTask> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(ThreadTask::spawn(kind, config, state))
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(InlineTask::new(kind, config, state))
+    }
+}
+
+/// Runs the solve and stores progress snapshots in `progress`
+fn run(
+    kind: SolverKind,
+    config: SolverConfig,
+    state: &PuzzleState,
+    cancel: Arc<AtomicBool>,
+    progress: &ProgressSlot,
+) -> Result<Solution, SolverError> {
+    kind.try_solve_instrumented(config, state, Some(cancel), &mut |p| {
+        if let Ok(mut slot) = progress.lock() {
+            *slot = Some(p);
+        }
+    })
+}
+
+fn latest(progress: &ProgressSlot) -> Option<SolverProgress> {
+    progress.lock().ok().and_then(|slot| slot.clone())
+}
+
+/// Solve running on a dedicated thread
+///
+/// Dropping the task cancels the solve; the thread exits at its next progress check.
+pub struct ThreadTask {
+    handle: Option<JoinHandle<Result<Solution, SolverError>>>,
+    cancel: Arc<AtomicBool>,
+    progress: ProgressSlot,
+}
+
+impl ThreadTask {
+    /// Spawns a thread that solves `state`
+    pub fn spawn(kind: SolverKind, config: SolverConfig, state: PuzzleState) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let progress: ProgressSlot = Arc::new(Mutex::new(None));
+
+        let thread_cancel = cancel.clone();
+        let thread_progress = progress.clone();
+        let handle = thread::spawn(move || run(kind, config, &state, thread_cancel, &thread_progress));
+
+        Self {
+            handle: Some(handle),
+            cancel,
+            progress,
+        }
+    }
+}
+
+/// Takes a finished thread's result without blocking, leaving running threads in place
+fn poll_thread<T>(slot: &mut Option<JoinHandle<T>>) -> TaskStatus<T> {
+    match slot.take() {
+        Some(handle) if handle.is_finished() => match handle.join() {
+            Ok(result) => TaskStatus::Finished(result),
+            Err(_) => TaskStatus::Failed, // Thread panicked
+        },
+        Some(handle) => {
+            *slot = Some(handle);
+            TaskStatus::Running
+        }
+        None => TaskStatus::Failed,
+    }
+}
+
+impl SolverTask for ThreadTask {
+    fn poll(&mut self) -> TaskStatus {
+        poll_thread(&mut self.handle)
+    }
+
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    fn progress(&self) -> Option<SolverProgress> {
+        latest(&self.progress)
+    }
+}
+
+impl Drop for ThreadTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Solve that runs to completion inside the first poll
+///
+/// Used where threads are unavailable; the caller blocks for the length of the
+/// solve, so it suits small grids and tests.
+pub struct InlineTask {
+    job: Option<(SolverKind, SolverConfig, PuzzleState)>,
+    cancel: Arc<AtomicBool>,
+    progress: ProgressSlot,
+}
+
+impl InlineTask {
+    /// Prepares a solve of `state`; nothing runs until the first poll
+    pub fn new(kind: SolverKind, config: SolverConfig, state: PuzzleState) -> Self {
+        Self {
+            job: Some((kind, config, state)),
+            cancel: Arc::new(AtomicBool::new(false)),
+            progress: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl SolverTask for InlineTask {
+    fn poll(&mut self) -> TaskStatus {
+        let Some((kind, config, state)) = self.job.take() else {
+            return TaskStatus::Failed;
+        };
+        if self.cancel.load(Ordering::Relaxed) {
+            return TaskStatus::Finished(Err(SolverError::Cancelled));
+        }
+        TaskStatus::Finished(run(kind, config, &state, self.cancel.clone(), &self.progress))
+    }
+
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    fn progress(&self) -> Option<SolverProgress> {
+        latest(&self.progress)
+    }
+}
+
+/// Everything a background shuffle needs, moved onto its thread
+pub struct ShuffleJob {
+    /// Board to shuffle from, usually solved
+    pub state: PuzzleState,
+    pub difficulty: Difficulty,
+    pub calculator: Arc<dyn EntropyCalculator + Send + Sync>,
+    pub seed: u64,
+}
+
+impl ShuffleJob {
+    fn run(mut self) -> (PuzzleState, ShuffleResult) {
+        // The board is already valid, so its size is too
+        let shuffler = ShuffleController::new(self.state.size()).expect("valid size");
+        let result =
+            shuffler.shuffle_seeded(&mut self.state, self.difficulty, self.calculator.as_ref(), self.seed);
+        (self.state, result)
+    }
+}
+
+/// A shuffle running off the caller's thread of control
+///
+/// Shuffles stop on their own within the difficulty's move cap, so there is no
+/// cancel; dropping the task discards the result.
+pub trait ShuffleTask: Send {
+    /// Checks for the shuffled board without blocking (except inline), as [`SolverTask::poll`]
+    fn poll(&mut self) -> TaskStatus<(PuzzleState, ShuffleResult)>;
+}
+
+/// Starts `job` with the backend suited to the target
+pub fn spawn_shuffle_task(job: ShuffleJob) -> Box<dyn ShuffleTask> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(ThreadShuffleTask {
+            handle: Some(thread::spawn(move || job.run())),
+        })
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(InlineShuffleTask { job: Some(job) })
+    }
+}
+
+/// Shuffle running on a dedicated thread
+pub struct ThreadShuffleTask {
+    handle: Option<JoinHandle<(PuzzleState, ShuffleResult)>>,
+}
+
+impl ShuffleTask for ThreadShuffleTask {
+    fn poll(&mut self) -> TaskStatus<(PuzzleState, ShuffleResult)> {
+        poll_thread(&mut self.handle)
+    }
+}
+
+/// Shuffle that runs inside the first poll, where threads are unavailable
+pub struct InlineShuffleTask {
+    job: Option<ShuffleJob>,
+}
+
+impl InlineShuffleTask {
+    /// Prepares `job`; nothing runs until the first poll
+    pub fn new(job: ShuffleJob) -> Self {
+        Self { job: Some(job) }
+    }
+}
+
+impl ShuffleTask for InlineShuffleTask {
+    fn poll(&mut self) -> TaskStatus<(PuzzleState, ShuffleResult)> {
+        match self.job.take() {
+            Some(job) => TaskStatus::Finished(job.run()),
+            None => TaskStatus::Failed,
+        }
+    }
+}
+
+/// Everything a background analysis needs, moved onto its thread
+pub struct AnalysisJob {
+    /// Board the game started from
+    pub initial: PuzzleState,
+    /// Player moves, each as the tiles it slid
+    pub moves: Vec<Vec<Position>>,
+    pub kind: SolverKind,
+    pub config: SolverConfig,
+}
+
+impl AnalysisJob {
+    fn run(self, cancel: Arc<AtomicBool>) -> Result<GameAnalysis, SolverError> {
+        analyze_game(&self.initial, &self.moves, self.kind, self.config, Some(cancel))
+    }
+}
+
+/// An analysis running off the caller's thread of control
+pub trait AnalysisTask: Send {
+    /// Checks for the analysis without blocking (except inline), as [`SolverTask::poll`]
+    fn poll(&mut self) -> TaskStatus<Result<GameAnalysis, SolverError>>;
+
+    /// Asks the analysis to stop at its next search
+    fn cancel(&self);
+}
+
+/// Starts `job` with the backend suited to the target
+pub fn spawn_analysis_task(job: AnalysisJob) -> Box<dyn AnalysisTask> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(ThreadAnalysisTask::spawn(job))
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(InlineAnalysisTask::new(job))
+    }
+}
+
+/// Analysis running on a dedicated thread
+///
+/// Dropping the task cancels the analysis.
+pub struct ThreadAnalysisTask {
+    handle: Option<JoinHandle<Result<GameAnalysis, SolverError>>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl ThreadAnalysisTask {
+    /// Spawns a thread that runs `job`
+    pub fn spawn(job: AnalysisJob) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        Self {
+            handle: Some(thread::spawn(move || job.run(thread_cancel))),
+            cancel,
+        }
+    }
+}
+
+impl AnalysisTask for ThreadAnalysisTask {
+    fn poll(&mut self) -> TaskStatus<Result<GameAnalysis, SolverError>> {
+        poll_thread(&mut self.handle)
+    }
+
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ThreadAnalysisTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Analysis that runs inside the first poll, where threads are unavailable
+pub struct InlineAnalysisTask {
+    job: Option<AnalysisJob>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl InlineAnalysisTask {
+    /// Prepares `job`; nothing runs until the first poll
+    pub fn new(job: AnalysisJob) -> Self {
+        Self {
+            job: Some(job),
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl AnalysisTask for InlineAnalysisTask {
+    fn poll(&mut self) -> TaskStatus<Result<GameAnalysis, SolverError>> {
+        match self.job.take() {
+            Some(job) => TaskStatus::Finished(job.run(self.cancel.clone())),
+            None => TaskStatus::Failed,
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Everything a background metrics pass needs, moved onto its thread
+pub struct MetricsJob {
+    pub state: PuzzleState,
+}
+
+impl MetricsJob {
+    fn run(self) -> EntropyMetrics {
+        EntropyMetrics::measure(&self.state)
+    }
+}
+
+/// Entropy metrics measured off the caller's thread of control
+///
+/// The measurement stops on its own within a small search budget, so there is
+/// no cancel; dropping the task discards the result.
+pub trait MetricsTask: Send {
+    /// Checks for the metrics without blocking (except inline), as [`SolverTask::poll`]
+    fn poll(&mut self) -> TaskStatus<EntropyMetrics>;
+}
+
+/// Starts `job` with the backend suited to the target
+pub fn spawn_metrics_task(job: MetricsJob) -> Box<dyn MetricsTask> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(ThreadMetricsTask {
+            handle: Some(thread::spawn(move || job.run())),
+        })
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(InlineMetricsTask::new(job))
+    }
+}
+
+/// Metrics measured on a dedicated thread
+pub struct ThreadMetricsTask {
+    handle: Option<JoinHandle<EntropyMetrics>>,
+}
+
+impl MetricsTask for ThreadMetricsTask {
+    fn poll(&mut self) -> TaskStatus<EntropyMetrics> {
+        poll_thread(&mut self.handle)
+    }
+}
+
+/// Metrics measured inside the first poll, where threads are unavailable
+pub struct InlineMetricsTask {
+    job: Option<MetricsJob>,
+}
+
+impl InlineMetricsTask {
+    /// Prepares `job`; nothing runs until the first poll
+    pub fn new(job: MetricsJob) -> Self {
+        Self { job: Some(job) }
+    }
+}
+
+impl MetricsTask for InlineMetricsTask {
+    fn poll(&mut self) -> TaskStatus<EntropyMetrics> {
+        match self.job.take() {
+            Some(job) => TaskStatus::Finished(job.run()),
+            None => TaskStatus::Failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ManhattanDistance;
+    use std::time::{Duration, Instant};
+
+    fn puzzle() -> PuzzleState {
+        "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap()
+    }
+
+    /// Polls until the task stops running or `limit` passes
+    fn wait(task: &mut dyn SolverTask, limit: Duration) -> TaskStatus {
+        let start = Instant::now();
+        loop {
+            match task.poll() {
+                TaskStatus::Running if start.elapsed() < limit => thread::sleep(Duration::from_millis(5)),
+                status => return status,
+            }
+        }
+    }
+
+    #[test]
+    fn test_thread_task_finishes() {
+        let mut task = ThreadTask::spawn(SolverKind::AStar, SolverConfig::default(), puzzle());
+        match wait(&mut task, Duration::from_secs(10)) {
+            TaskStatus::Finished(Ok(solution)) => assert_eq!(solution.len(), 2),
+            status => panic!("unexpected status {:?}", status),
+        }
+        assert!(matches!(task.poll(), TaskStatus::Failed)); // Spent
+    }
+
+    #[test]
+    fn test_thread_task_cancel() {
+        // A 15×15 board keeps IDA* busy far longer than the test waits
+        let state = crate::model::solver::scramble(15, 400, 7);
+        let config = SolverConfig {
+            max_iterations: usize::MAX,
+            timeout: Duration::from_secs(600),
+            ..SolverConfig::default()
+        };
+        let mut task = ThreadTask::spawn(SolverKind::IdaStar, config, state);
+        task.cancel();
+        assert!(matches!(wait(&mut task, Duration::from_secs(10)), TaskStatus::Finished(Err(SolverError::Cancelled))));
+    }
+
+    #[test]
+    fn test_inline_task() {
+        let mut task = InlineTask::new(SolverKind::AStar, SolverConfig::default(), puzzle());
+        match task.poll() {
+            TaskStatus::Finished(Ok(solution)) => assert_eq!(solution.len(), 2),
+            status => panic!("unexpected status {:?}", status),
+        }
+
+        let mut cancelled = InlineTask::new(SolverKind::AStar, SolverConfig::default(), puzzle());
+        cancelled.cancel();
+        assert!(matches!(cancelled.poll(), TaskStatus::Finished(Err(SolverError::Cancelled))));
+        assert!(cancelled.progress().is_none());
+    }
+
+    #[test]
+    fn test_shuffle_tasks_match_seeded_shuffle() {
+        let job = || ShuffleJob {
+            state: PuzzleState::new(5).unwrap(),
+            difficulty: Difficulty::Hard,
+            calculator: Arc::new(ManhattanDistance),
+            seed: 9,
+        };
+        let mut expected = PuzzleState::new(5).unwrap();
+        ShuffleController::new(5)
+            .unwrap()
+            .shuffle_seeded(&mut expected, Difficulty::Hard, &ManhattanDistance, 9);
+
+        let mut threaded = spawn_shuffle_task(job());
+        let start = Instant::now();
+        let (state, result) = loop {
+            match threaded.poll() {
+                TaskStatus::Running if start.elapsed() < Duration::from_secs(10) => {
+                    thread::sleep(Duration::from_millis(5))
+                }
+                TaskStatus::Finished(done) => break done,
+                status => panic!("unexpected status {:?}", status),
+            }
+        };
+        assert_eq!(state.to_layout(), expected.to_layout());
+        assert_eq!(result.seed, 9);
+        assert!(matches!(threaded.poll(), TaskStatus::Failed)); // Spent
+
+        let mut inline = InlineShuffleTask::new(job());
+        match inline.poll() {
+            TaskStatus::Finished((state, _)) => assert_eq!(state.to_layout(), expected.to_layout()),
+            status => panic!("unexpected status {:?}", status),
+        }
+    }
+
+    #[test]
+    fn test_analysis_tasks() {
+        let job = || AnalysisJob {
+            initial: puzzle(),
+            moves: vec![vec![(2, 1)], vec![(2, 2)]],
+            kind: SolverKind::IdaStar,
+            config: SolverConfig::default(),
+        };
+
+        let mut threaded = spawn_analysis_task(job());
+        let start = Instant::now();
+        let analysis = loop {
+            match threaded.poll() {
+                TaskStatus::Running if start.elapsed() < Duration::from_secs(10) => {
+                    thread::sleep(Duration::from_millis(5))
+                }
+                TaskStatus::Finished(done) => break done.unwrap(),
+                status => panic!("unexpected status {:?}", status),
+            }
+        };
+        assert_eq!(analysis.starting_distance(), Some(2));
+        assert_eq!(analysis.wasted_moves(), 0);
+
+        let mut cancelled = InlineAnalysisTask::new(job());
+        cancelled.cancel();
+        assert!(matches!(cancelled.poll(), TaskStatus::Finished(Err(SolverError::Cancelled))));
+    }
+
+    #[test]
+    fn test_metrics_tasks() {
+        let job = || MetricsJob { state: puzzle() };
+
+        let mut threaded = spawn_metrics_task(job());
+        let start = Instant::now();
+        let metrics = loop {
+            match threaded.poll() {
+                TaskStatus::Running if start.elapsed() < Duration::from_secs(10) => {
+                    thread::sleep(Duration::from_millis(5))
+                }
+                TaskStatus::Finished(done) => break done,
+                status => panic!("unexpected status {:?}", status),
+            }
+        };
+        assert_eq!(metrics.manhattan_distance, 2);
+        assert_eq!(metrics.actual_solution_length, 2);
+        assert!(!metrics.stale);
+
+        let mut inline = InlineMetricsTask::new(job());
+        assert!(matches!(inline.poll(), TaskStatus::Finished(m) if m.actual_solution_length == 2));
+        assert!(matches!(inline.poll(), TaskStatus::Failed));
+    }
+}
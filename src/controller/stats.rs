@@ -0,0 +1,292 @@
+//! Persistent record of completed games with simple aggregate queries.
+
+use crate::controller::challenge::GameMode;
+use crate::controller::storage;
+use crate::model::{Difficulty, Grade};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// File name of the statistics store inside the data directory
+pub const STATS_FILE: &str = "stats.json";
+
+/// On-disk format version, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// A single completed game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub grid_size: usize,
+    pub difficulty: Difficulty,
+    /// Player moves taken (a chain move counts once)
+    pub moves: usize,
+    /// Single-tile moves taken (a chain move counts each tile); 0 in records from older versions
+    #[serde(default)]
+    pub tile_moves: usize,
+    /// Elapsed play time in milliseconds
+    pub time_millis: u64,
+    /// Optimal solution length of the starting board, if it was computed
+    pub optimal_length: Option<u32>,
+    /// Auto-solve was used to finish the game
+    pub assisted: bool,
+    /// Hints shown during play; 0 in records from older versions
+    #[serde(default)]
+    pub hints: usize,
+    /// Rules the game was played under
+    #[serde(default)]
+    pub mode: GameMode,
+    /// The challenge was lost: the board was left unsolved
+    #[serde(default)]
+    pub failed: bool,
+    /// Completion time in seconds since the Unix epoch
+    pub completed_at: u64,
+}
+
+impl GameRecord {
+    /// Creates a record stamped with the current time
+    pub fn new(
+        grid_size: usize,
+        difficulty: Difficulty,
+        moves: usize,
+        tile_moves: usize,
+        time: Duration,
+        optimal_length: Option<u32>,
+        assisted: bool,
+    ) -> Self {
+        let completed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            grid_size,
+            difficulty,
+            moves,
+            tile_moves,
+            time_millis: time.as_millis() as u64,
+            optimal_length,
+            assisted,
+            hints: 0,
+            mode: GameMode::Free,
+            failed: false,
+            completed_at,
+        }
+    }
+
+    /// Returns the elapsed play time
+    pub fn time(&self) -> Duration {
+        Duration::from_millis(self.time_millis)
+    }
+
+    /// Returns optimal length divided by moves taken, capped at 1.0
+    /// Returns None if the optimal length is unknown or no moves were made
+    pub fn efficiency(&self) -> Option<f64> {
+        let optimal = self.optimal_length?;
+        if self.moves == 0 {
+            return None;
+        }
+        Some((optimal as f64 / self.moves as f64).min(1.0))
+    }
+
+    /// Returns the grade against par (the optimal length)
+    /// Returns None if par is unknown, auto-solve finished the game, or the challenge was lost
+    pub fn grade(&self) -> Option<Grade> {
+        let par = self.optimal_length.filter(|_| !self.assisted && !self.failed)?;
+        // Records from older versions have no tile count; player moves are a lower bound
+        Some(Grade::for_moves(self.tile_moves.max(self.moves), par))
+    }
+}
+
+/// Serialized form of the store
+#[derive(Debug, Serialize, Deserialize)]
+struct StatsFile {
+    version: u32,
+    games: Vec<GameRecord>,
+}
+
+/// History of completed games
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    games: Vec<GameRecord>,
+    path: Option<PathBuf>, // Where to persist updates (None = in-memory only)
+}
+
+impl Stats {
+    /// Creates an empty in-memory store that is never written to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the store from `path`, persisting future records back to it
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read or parsed
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut stats = Self {
+            games: Vec::new(),
+            path: Some(path.to_path_buf()),
+        };
+
+        match storage::load_json::<StatsFile>(path) {
+            Ok(file) => {
+                if file.version == FORMAT_VERSION {
+                    stats.games = file.games;
+                }
+                Ok(stats)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(stats),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the store from the default data directory
+    /// Falls back to an in-memory store if the file is unavailable or corrupt
+    pub fn load_default() -> Self {
+        storage::data_file(STATS_FILE)
+            .and_then(|path| Self::load(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Appends a completed game and writes the store to disk (if backed by a file)
+    pub fn record(&mut self, game: GameRecord) {
+        self.games.push(game);
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save statistics: {}", e);
+        }
+    }
+
+    /// Returns all recorded games, oldest first
+    pub fn games(&self) -> &[GameRecord] {
+        &self.games
+    }
+
+    /// Returns the number of completed games, lost challenges included
+    pub fn games_played(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Returns the number of challenges won and lost
+    pub fn challenge_results(&self) -> (usize, usize) {
+        let challenges = self.games.iter().filter(|g| g.mode.is_challenge());
+        let lost = challenges.clone().filter(|g| g.failed).count();
+        (challenges.count() - lost, lost)
+    }
+
+    /// Returns the mean efficiency of unassisted, won games with a known optimal length
+    pub fn average_efficiency(&self) -> Option<f64> {
+        let efficiencies: Vec<f64> = self
+            .games
+            .iter()
+            .filter(|g| !g.assisted && !g.failed)
+            .filter_map(GameRecord::efficiency)
+            .collect();
+        if efficiencies.is_empty() {
+            return None;
+        }
+        Some(efficiencies.iter().sum::<f64>() / efficiencies.len() as f64)
+    }
+
+    /// Returns the mean play time of unassisted, won games
+    pub fn average_time(&self) -> Option<Duration> {
+        let times: Vec<Duration> = self
+            .games
+            .iter()
+            .filter(|g| !g.assisted && !g.failed)
+            .map(GameRecord::time)
+            .collect();
+        if times.is_empty() {
+            return None;
+        }
+        Some(times.iter().sum::<Duration>() / times.len() as u32)
+    }
+
+    /// Writes the store to its backing file (no-op for in-memory stores)
+    fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file = StatsFile {
+            version: FORMAT_VERSION,
+            games: self.games.clone(),
+        };
+        storage::save_json(path, &file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(moves: usize, optimal: Option<u32>, assisted: bool) -> GameRecord {
+        GameRecord::new(
+            4,
+            Difficulty::Medium,
+            moves,
+            moves,
+            Duration::from_secs(moves as u64),
+            optimal,
+            assisted,
+        )
+    }
+
+    #[test]
+    fn test_efficiency() {
+        assert_eq!(game(20, Some(10), false).efficiency(), Some(0.5));
+        assert_eq!(game(5, Some(10), false).efficiency(), Some(1.0)); // Chain moves can beat optimal
+        assert_eq!(game(20, None, false).efficiency(), None);
+        assert_eq!(game(0, Some(10), false).efficiency(), None);
+    }
+
+    #[test]
+    fn test_grade() {
+        assert_eq!(game(10, Some(10), false).grade(), Some(Grade::S));
+        assert_eq!(game(16, Some(10), false).grade(), Some(Grade::C));
+        assert_eq!(game(10, Some(10), true).grade(), None);
+        assert_eq!(game(10, None, false).grade(), None);
+
+        let mut chained = game(8, Some(10), false);
+        chained.tile_moves = 12;
+        assert_eq!(chained.grade(), Some(Grade::A)); // Graded on tiles, not clicks
+    }
+
+    #[test]
+    fn test_aggregates_skip_assisted_games() {
+        let mut stats = Stats::new();
+        assert_eq!(stats.average_efficiency(), None);
+        assert_eq!(stats.average_time(), None);
+
+        stats.record(game(20, Some(10), false));
+        stats.record(game(10, Some(10), false));
+        stats.record(game(40, Some(10), true));
+
+        assert_eq!(stats.games_played(), 3);
+        assert_eq!(stats.average_efficiency(), Some(0.75));
+        assert_eq!(stats.average_time(), Some(Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("slider-stats-{}", std::process::id()))
+            .join("stats.json");
+        let _ = std::fs::remove_file(&path);
+
+        let mut stats = Stats::load(&path).unwrap();
+        stats.record(game(12, Some(8), false));
+
+        let reloaded = Stats::load(&path).unwrap();
+        assert_eq!(reloaded.games(), stats.games());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_records_without_tile_moves_load() {
+        let json = r#"{"grid_size":4,"difficulty":"Easy","moves":9,"time_millis":5000,
+            "optimal_length":null,"assisted":false,"completed_at":0}"#;
+        let record: GameRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.moves, 9);
+        assert_eq!(record.tile_moves, 0);
+    }
+}
@@ -0,0 +1,151 @@
+//! Local persistence helpers shared by the controller's on-disk stores.
+//!
+//! Everything Slider remembers between sessions (best times, statistics, ...) is
+//! stored as JSON in a per-user data directory; the hand-editable `slider.toml`
+//! lives in a separate config directory. Stores take explicit paths so tests and
+//! embedders never touch the real directories unless they ask for it.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV: &str = "SLIDER_DATA_DIR";
+
+/// Environment variable overriding the config directory
+pub const CONFIG_DIR_ENV: &str = "SLIDER_CONFIG_DIR";
+
+/// Returns the directory used for Slider's persistent data
+///
+/// Honors `SLIDER_DATA_DIR`, then `XDG_DATA_HOME/slider`, then
+/// `~/.local/share/slider` (`%APPDATA%\slider` on Windows).
+/// Returns None if no home directory can be determined.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        return Some(PathBuf::from(dir).join("slider"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = std::env::var_os("APPDATA") {
+            return Some(PathBuf::from(dir).join("slider"));
+        }
+    }
+    std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join("slider")
+    })
+}
+
+/// Returns the path of a named file inside the data directory
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(name))
+}
+
+/// Returns the directory holding Slider's configuration file
+///
+/// Honors `SLIDER_CONFIG_DIR`, then `XDG_CONFIG_HOME/slider`, then
+/// `~/.config/slider` (`%APPDATA%\slider` on Windows).
+/// Returns None if no home directory can be determined.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(dir).join("slider"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = std::env::var_os("APPDATA") {
+            return Some(PathBuf::from(dir).join("slider"));
+        }
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("slider"))
+}
+
+/// Returns the path of a named file inside the config directory
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(name))
+}
+
+/// Reads and deserializes a JSON file
+///
+/// # Errors
+///
+/// Returns the underlying I/O error, or `InvalidData` if the JSON is malformed
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let text = fs::read_to_string(path)?;
+    serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Serializes a value to a JSON file, creating parent directories as needed
+///
+/// Writes to a temporary sibling first and renames it into place so a crash
+/// mid-write never leaves a truncated file behind.
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the file cannot be written
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    save_text(path, &text)
+}
+
+/// Writes a text file, creating parent directories as needed
+///
+/// Uses the same write-then-rename approach as [`save_json`].
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the file cannot be written
+pub fn save_text(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, text)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-storage-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let path = temp_path("round_trip.json");
+        let value = vec![1u32, 2, 3];
+        save_json(&path, &value).unwrap();
+        let loaded: Vec<u32> = load_json(&path).unwrap();
+        assert_eq!(loaded, value);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = temp_path("does_not_exist.json");
+        let result: io::Result<Vec<u32>> = load_json(&path);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_load_malformed_file() {
+        let path = temp_path("malformed.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not json").unwrap();
+        let result: io::Result<Vec<u32>> = load_json(&path);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_file(&path);
+    }
+}
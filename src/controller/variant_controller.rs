@@ -0,0 +1,141 @@
+//! Game controller for the other move systems, such as Loopover's rotating rows.
+
+use crate::controller::game_controller::{GameClock, MoveHistory};
+use crate::model::MoveSystem;
+use rand::Rng;
+use std::time::Duration;
+
+/// Drives a game on any [`MoveSystem`]: scrambling, moves, timing, and reset
+///
+/// The sliding-tile game has its own `GameController` with solvers, hints and
+/// records; this is the lighter controller for the other members of the family,
+/// such as Loopover. Its games are not scored by difficulty, so they leave best
+/// times and statistics alone.
+pub struct VariantController<M: MoveSystem> {
+    board: M,
+    start: M, // Board to return to on reset
+    move_history: MoveHistory,
+    clock: GameClock,
+}
+
+impl<M: MoveSystem> VariantController<M> {
+    /// Starts a game from the given board
+    pub fn new(board: M) -> Self {
+        Self {
+            start: board.clone(),
+            board,
+            move_history: MoveHistory::new(),
+            clock: GameClock::new(),
+        }
+    }
+
+    pub fn board(&self) -> &M {
+        &self.board
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.move_history.player_moves()
+    }
+
+    pub fn elapsed_time(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    pub fn is_timer_running(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.board.is_solved()
+    }
+
+    /// Scrambles the board with `moves` random moves and starts a new game from it
+    ///
+    /// Walks on until the board is unsolved, so even tiny boards never start solved.
+    pub fn scramble<R: Rng>(&mut self, moves: usize, rng: &mut R) {
+        self.board.random_walk(moves, rng);
+        while self.board.is_solved() && !self.board.random_walk(1, rng).is_empty() {}
+        self.start = self.board.clone();
+        self.move_history.reset();
+        self.clock.reset();
+        log::debug!("Variant board scrambled with {} moves", moves);
+    }
+
+    /// Plays one move
+    /// Returns false if the move is illegal or the puzzle is already solved
+    pub fn play(&mut self, mv: M::Move) -> bool {
+        if self.board.is_solved() || !self.board.apply(mv) {
+            return false;
+        }
+
+        self.clock.start();
+        self.move_history.record_move();
+        self.move_history.record_tile_move();
+        log::trace!("Variant move {:?}", mv);
+
+        if self.board.is_solved() {
+            self.clock.pause();
+            log::info!(
+                "Variant solved in {} moves ({:.1}s)",
+                self.move_count(),
+                self.clock.elapsed().as_secs_f64()
+            );
+        }
+        true
+    }
+
+    /// Returns to the starting board and clears the move count and clock
+    pub fn reset(&mut self) {
+        self.board = self.start.clone();
+        self.move_history.reset();
+        self.clock.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Direction, LoopoverBoard, LoopoverMove};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_moves_and_clock() {
+        let mut controller = VariantController::new(LoopoverBoard::new(3).unwrap());
+        let right = LoopoverMove { line: 0, direction: Direction::Right };
+        // Solved boards take no moves until scrambled
+        assert!(!controller.play(right));
+
+        controller.scramble(0, &mut StdRng::seed_from_u64(1));
+        assert!(!controller.is_solved());
+        assert!(!controller.is_timer_running());
+
+        let start = controller.board().clone();
+        let mv = controller.board().legal_moves()[0];
+        assert!(controller.play(mv));
+        assert!(controller.is_timer_running());
+        assert_eq!(controller.move_count(), 1);
+
+        controller.reset();
+        assert_eq!(controller.board(), &start);
+        assert_eq!(controller.move_count(), 0);
+        assert_eq!(controller.elapsed_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_undoing_the_scramble_solves() {
+        let mut controller = VariantController::new(LoopoverBoard::new(4).unwrap());
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut walk = LoopoverBoard::new(4).unwrap();
+        let moves = walk.random_walk(12, &mut rng);
+
+        controller.scramble(12, &mut StdRng::seed_from_u64(9));
+        assert_eq!(controller.board(), &walk);
+        for &mv in moves.iter().rev() {
+            assert!(controller.play(walk.inverse(mv)));
+        }
+        assert!(controller.is_solved());
+        assert!(!controller.is_timer_running());
+        assert_eq!(controller.move_count(), 12);
+    }
+}
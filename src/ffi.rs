@@ -0,0 +1,374 @@
+//! # C API
+//!
+//! A `extern "C"` surface over the model layer for C, C++ and game-engine front
+//! ends. Boards are opaque `SliderPuzzle` handles created by `slider_puzzle_new`
+//! or `slider_puzzle_from_notation` and released with `slider_puzzle_free`;
+//! solutions and strings returned by the library have their own free functions.
+//!
+//! The matching header lives in `include/slider.h` and is generated with
+//! `cbindgen --config cbindgen.toml --output include/slider.h`.
+//!
+//! Functions never panic on bad input: null handles, out-of-range cells and
+//! malformed text are reported through return values.
+
+use crate::controller::ShuffleController;
+use crate::model::{AStarSolver, Difficulty, ManhattanDistance, PuzzleState, SolverConfig};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::time::Duration;
+
+/// Opaque puzzle handle
+pub struct SliderPuzzle {
+    state: PuzzleState,
+}
+
+/// Shuffle difficulty, mirroring `Difficulty` without the custom target
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliderDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    ExtraHard,
+}
+
+impl From<SliderDifficulty> for Difficulty {
+    fn from(difficulty: SliderDifficulty) -> Self {
+        match difficulty {
+            SliderDifficulty::Easy => Difficulty::Easy,
+            SliderDifficulty::Medium => Difficulty::Medium,
+            SliderDifficulty::Hard => Difficulty::Hard,
+            SliderDifficulty::ExtraHard => Difficulty::ExtraHard,
+        }
+    }
+}
+
+/// A grid cell, row first
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliderPosition {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Tile positions to move, in order, as returned by `slider_solve`
+///
+/// `found` is false (and `moves` null) when the solver gave up or was handed a
+/// null puzzle. Release with `slider_solution_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct SliderSolution {
+    pub moves: *mut SliderPosition,
+    pub len: usize,
+    pub found: bool,
+}
+
+impl SliderSolution {
+    fn not_found() -> Self {
+        Self {
+            moves: ptr::null_mut(),
+            len: 0,
+            found: false,
+        }
+    }
+}
+
+fn into_handle(state: PuzzleState) -> *mut SliderPuzzle {
+    Box::into_raw(Box::new(SliderPuzzle { state }))
+}
+
+/// Creates a solved `size`×`size` puzzle
+/// Returns null if the size is out of range
+#[no_mangle]
+pub extern "C" fn slider_puzzle_new(size: usize) -> *mut SliderPuzzle {
+    PuzzleState::new(size).map_or(ptr::null_mut(), into_handle)
+}
+
+/// Parses a puzzle in text notation, e.g. `"1 2 3 / 4 5 6 / 7 _ 8"`
+/// Returns null if the text is malformed or the layout is unsolvable
+///
+/// # Safety
+///
+/// `notation` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_from_notation(notation: *const c_char) -> *mut SliderPuzzle {
+    if notation.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: the caller guarantees a valid NUL-terminated string
+    let text = unsafe { CStr::from_ptr(notation) };
+    match text.to_str().map(str::parse::<PuzzleState>) {
+        Ok(Ok(state)) => into_handle(state),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Returns an independent copy of `puzzle`, or null if `puzzle` is null
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_clone(puzzle: *const SliderPuzzle) -> *mut SliderPuzzle {
+    // SAFETY: the caller guarantees a live handle or null
+    unsafe { puzzle.as_ref() }.map_or(ptr::null_mut(), |p| into_handle(p.state.clone()))
+}
+
+/// Releases a puzzle handle; null is ignored
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_free(puzzle: *mut SliderPuzzle) {
+    if !puzzle.is_null() {
+        // SAFETY: the handle came from Box::into_raw and is released once
+        drop(unsafe { Box::from_raw(puzzle) });
+    }
+}
+
+/// Returns the grid size, or 0 if `puzzle` is null
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_size(puzzle: *const SliderPuzzle) -> usize {
+    // SAFETY: the caller guarantees a live handle or null
+    unsafe { puzzle.as_ref() }.map_or(0, |p| p.state.size())
+}
+
+/// Returns the tile number at a cell; 0 for the empty cell, a null puzzle, or a cell off the grid
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_tile(
+    puzzle: *const SliderPuzzle,
+    row: usize,
+    col: usize,
+) -> u32 {
+    // SAFETY: the caller guarantees a live handle or null
+    unsafe { puzzle.as_ref() }
+        .and_then(|p| p.state.tile_number((row, col)))
+        .unwrap_or(0)
+}
+
+/// Returns the empty cell, or row and column `usize::MAX` if `puzzle` is null
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_empty_position(
+    puzzle: *const SliderPuzzle,
+) -> SliderPosition {
+    // SAFETY: the caller guarantees a live handle or null
+    let (row, col) =
+        unsafe { puzzle.as_ref() }.map_or((usize::MAX, usize::MAX), |p| p.state.empty_position());
+    SliderPosition { row, col }
+}
+
+/// Returns true if every tile is home; false for a null puzzle
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_is_solved(puzzle: *const SliderPuzzle) -> bool {
+    // SAFETY: the caller guarantees a live handle or null
+    unsafe { puzzle.as_ref() }.is_some_and(|p| p.state.is_solved())
+}
+
+/// Slides the tile at a cell toward the empty cell, pushing any tiles between them
+/// Returns false if the cell is not in line with the empty cell
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_move(
+    puzzle: *mut SliderPuzzle,
+    row: usize,
+    col: usize,
+) -> bool {
+    // SAFETY: the caller guarantees a live handle or null
+    let Some(p) = (unsafe { puzzle.as_mut() }) else {
+        return false;
+    };
+    let size = p.state.size();
+    row < size && col < size && p.state.apply_chain_move((row, col))
+}
+
+/// Shuffles the puzzle from its current layout, repeatably for a given seed
+/// Returns the number of moves made (0 for a null puzzle)
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_shuffle(
+    puzzle: *mut SliderPuzzle,
+    difficulty: SliderDifficulty,
+    seed: u64,
+) -> usize {
+    // SAFETY: the caller guarantees a live handle or null
+    let Some(p) = (unsafe { puzzle.as_mut() }) else {
+        return 0;
+    };
+    let Ok(shuffler) = ShuffleController::new(p.state.size()) else {
+        return 0;
+    };
+    shuffler
+        .shuffle_seeded(&mut p.state, difficulty.into(), &ManhattanDistance, seed)
+        .moves_made
+}
+
+/// Returns the board in text notation; release with `slider_string_free`
+/// Returns null if `puzzle` is null
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_puzzle_to_notation(puzzle: *const SliderPuzzle) -> *mut c_char {
+    // SAFETY: the caller guarantees a live handle or null
+    unsafe { puzzle.as_ref() }
+        .and_then(|p| CString::new(p.state.to_notation()).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this library; null is ignored
+///
+/// # Safety
+///
+/// `text` must be null or a string from this library that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn slider_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the string came from CString::into_raw and is released once
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Finds a solution with A*, giving up after `timeout_ms` (0 for the default limit)
+///
+/// A* is fast but its heuristic can overestimate, so the solution is not always a
+/// shortest one.
+///
+/// # Safety
+///
+/// `puzzle` must be null or a live handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn slider_solve(
+    puzzle: *const SliderPuzzle,
+    timeout_ms: u64,
+) -> SliderSolution {
+    // SAFETY: the caller guarantees a live handle or null
+    let Some(p) = (unsafe { puzzle.as_ref() }) else {
+        return SliderSolution::not_found();
+    };
+    let mut config = SolverConfig::default();
+    if timeout_ms > 0 {
+        config.timeout = Duration::from_millis(timeout_ms);
+    }
+    let Some(path) = AStarSolver::with_config(config).solve_with_path(&p.state) else {
+        return SliderSolution::not_found();
+    };
+
+    let moves: Box<[SliderPosition]> = path
+        .into_iter()
+        .map(|(row, col)| SliderPosition { row, col })
+        .collect();
+    let len = moves.len();
+    SliderSolution {
+        moves: Box::into_raw(moves).cast(),
+        len,
+        found: true,
+    }
+}
+
+/// Releases the moves of a solution returned by `slider_solve`
+///
+/// # Safety
+///
+/// `solution` must come from `slider_solve`, unmodified, and be freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn slider_solution_free(solution: SliderSolution) {
+    if !solution.moves.is_null() {
+        // SAFETY: moves and len describe the boxed slice built by slider_solve
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(solution.moves, solution.len)) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_lifecycle() {
+        unsafe {
+            let puzzle = slider_puzzle_new(3);
+            assert_eq!(slider_puzzle_size(puzzle), 3);
+            assert!(slider_puzzle_is_solved(puzzle));
+            assert_eq!(slider_puzzle_tile(puzzle, 0, 0), 1);
+            assert_eq!(slider_puzzle_tile(puzzle, 2, 2), 0);
+            assert_eq!(
+                slider_puzzle_empty_position(puzzle),
+                SliderPosition { row: 2, col: 2 }
+            );
+
+            // Chain move slides two tiles; diagonal and off-grid cells are rejected
+            assert!(slider_puzzle_move(puzzle, 2, 0));
+            assert_eq!(
+                slider_puzzle_empty_position(puzzle),
+                SliderPosition { row: 2, col: 0 }
+            );
+            assert!(!slider_puzzle_move(puzzle, 1, 1));
+            assert!(!slider_puzzle_move(puzzle, 9, 0));
+
+            let text = slider_puzzle_to_notation(puzzle);
+            let copy = slider_puzzle_from_notation(text);
+            assert_eq!(slider_puzzle_tile(copy, 2, 1), 7);
+            slider_string_free(text);
+            slider_puzzle_free(copy);
+            slider_puzzle_free(puzzle);
+        }
+    }
+
+    #[test]
+    fn test_solve_round_trip() {
+        unsafe {
+            let puzzle = slider_puzzle_new(3);
+            assert!(slider_puzzle_shuffle(puzzle, SliderDifficulty::Medium, 5) > 0);
+            assert!(!slider_puzzle_is_solved(puzzle));
+
+            let solution = slider_solve(puzzle, 0);
+            assert!(solution.found);
+            let moves = std::slice::from_raw_parts(solution.moves, solution.len);
+            for step in moves {
+                assert!(slider_puzzle_move(puzzle, step.row, step.col));
+            }
+            assert!(slider_puzzle_is_solved(puzzle));
+            slider_solution_free(solution);
+            slider_puzzle_free(puzzle);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        unsafe {
+            assert!(slider_puzzle_new(2).is_null());
+            assert!(slider_puzzle_from_notation(ptr::null()).is_null());
+            assert!(slider_puzzle_from_notation(c"1 2 3 / 4 5 6 / 8 7 _".as_ptr()).is_null());
+
+            let null = ptr::null_mut();
+            assert_eq!(slider_puzzle_size(null), 0);
+            assert!(!slider_puzzle_move(null, 0, 0));
+            assert!(!slider_solve(null, 0).found);
+            slider_solution_free(slider_solve(null, 0));
+            slider_puzzle_free(null);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! # Puzzle Files
+//!
+//! A `.sldr` file holds one board and what is known about it: its goal, the
+//! seed it was shuffled from, who made it, its difficulty, and a solution. The
+//! file is JSON, so it can be written by hand or by other tools; the board is
+//! in the text notation of [`PuzzleState::to_notation`], numbered after the
+//! goal, and the solution in blank-move notation such as `RDLU`. Only the board
+//! is required.
+//!
+//! Every file records the format version it was written with. Files from a
+//! newer version are refused rather than half-read.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::io::PuzzleFile;
+//! use slider::model::PuzzleState;
+//!
+//! let text = r#"{ "format": "slider-puzzle", "version": 1, "board": "1 2 3 / 4 5 6 / 7 _ 8",
+//!                 "author": "Jack", "solution": "R" }"#;
+//! let file = PuzzleFile::from_text(text)?;
+//! assert_eq!(file.author.as_deref(), Some("Jack"));
+//! assert_eq!(file.solution, Some(vec![(2, 2)]));
+//! assert_eq!(PuzzleFile::from_text(&file.to_text())?, file);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::controller::storage;
+use crate::model::notation;
+use crate::model::{Difficulty, GoalSpec, Position, PuzzleState};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// File extension of puzzle files
+pub const SLDR_EXTENSION: &str = "sldr";
+
+/// Format version written into new files
+pub const SLDR_VERSION: u32 = 1;
+
+/// Names the format, so other JSON files are not mistaken for puzzles
+const SLDR_FORMAT: &str = "slider-puzzle";
+
+/// The file as stored
+#[derive(Serialize, Deserialize)]
+struct Stored {
+    format: String,
+    version: u32,
+    board: String,
+    #[serde(default)]
+    goal: GoalSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    difficulty: Option<Difficulty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    solution: Option<String>,
+}
+
+/// A board with its metadata, as read from or written to a `.sldr` file
+#[derive(Debug, Clone)]
+pub struct PuzzleFile {
+    /// The board to play, with its goal
+    pub state: PuzzleState,
+    /// Seed passed to `ShuffleController::shuffle_seeded` to make the board
+    pub seed: Option<u64>,
+    pub author: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    /// Tile positions to move from `state`, in order
+    pub solution: Option<Vec<Position>>,
+}
+
+impl PartialEq for PuzzleFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.state.to_notation() == other.state.to_notation()
+            && self.state.goal() == other.state.goal()
+            && self.seed == other.seed
+            && self.author == other.author
+            && self.difficulty == other.difficulty
+            && self.solution == other.solution
+    }
+}
+
+impl PuzzleFile {
+    /// Creates a file holding `state` and nothing else
+    pub fn new(state: PuzzleState) -> Self {
+        Self {
+            state,
+            seed: None,
+            author: None,
+            difficulty: None,
+            solution: None,
+        }
+    }
+
+    /// Returns the file's contents as JSON
+    pub fn to_text(&self) -> String {
+        let stored = Stored {
+            format: SLDR_FORMAT.to_string(),
+            version: SLDR_VERSION,
+            board: self.state.to_notation(),
+            goal: self.state.goal(),
+            seed: self.seed,
+            author: self.author.clone(),
+            difficulty: self.difficulty,
+            solution: self
+                .solution
+                .as_ref()
+                .map(|path| notation::blank_moves(self.state.empty_position(), path)),
+        };
+        serde_json::to_string_pretty(&stored).expect("puzzle files always serialize")
+    }
+
+    /// Reads a file's contents, checking the board and that the solution can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the text is not a puzzle file, is from a newer
+    /// version, or holds a board or solution that does not parse
+    pub fn from_text(text: &str) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let stored: Stored = serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        if stored.format != SLDR_FORMAT {
+            return Err(invalid(format!("not a puzzle file (format '{}')", stored.format)));
+        }
+        if stored.version > SLDR_VERSION {
+            return Err(invalid(format!(
+                "puzzle file version {} is newer than this build",
+                stored.version
+            )));
+        }
+        let state = PuzzleState::parse_layout(&stored.board)
+            .and_then(|layout| PuzzleState::from_layout_with_goal(&layout, stored.goal))
+            .map_err(|e| invalid(format!("board: {}", e)))?;
+        let solution = match stored.solution {
+            Some(moves) => Some(
+                notation::parse_blank_moves(&state, &moves).map_err(|e| invalid(format!("solution: {}", e)))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            state,
+            seed: stored.seed,
+            author: stored.author,
+            difficulty: stored.difficulty,
+            solution,
+        })
+    }
+
+    /// Writes the file
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save_text(path, &self.to_text())
+    }
+
+    /// Reads a file written by `save` or by hand
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error, or the `from_text` errors
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_text(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::AStarSolver;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("slider-io-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let state = scramble(4, 30, 3);
+        let file = PuzzleFile {
+            solution: AStarSolver::new().solve_with_path(&state),
+            seed: Some(3),
+            author: Some("Ada".to_string()),
+            difficulty: Some(Difficulty::Hard),
+            state,
+        };
+        let path = temp_path("board.sldr");
+        file.save(&path).unwrap();
+        assert_eq!(PuzzleFile::load(&path).unwrap(), file);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        // Goals other than the standard one keep their numbering
+        let spiral = scramble_from(PuzzleState::with_goal(3, GoalSpec::Spiral).unwrap(), 20, 1);
+        let loaded = PuzzleFile::from_text(&PuzzleFile::new(spiral.clone()).to_text()).unwrap();
+        assert_eq!(loaded.state.goal(), GoalSpec::Spiral);
+        assert_eq!(loaded.state.to_layout(), spiral.to_layout());
+        assert_eq!(loaded.solution, None);
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let file = |fields: &str| {
+            PuzzleFile::from_text(&format!(r#"{{ "format": "slider-puzzle", {} }}"#, fields))
+                .map_err(|e| e.kind())
+        };
+        assert!(file(r#""version": 1, "board": "1 2 3 / 4 5 6 / 7 8 _""#).is_ok());
+        let invalid = Err(io::ErrorKind::InvalidData);
+        assert_eq!(file(r#""version": 2, "board": "1 2 3 / 4 5 6 / 7 8 _""#), invalid);
+        assert_eq!(file(r#""version": 1, "board": "1 2 3 / 4 5 6 / 8 7 _""#), invalid);
+        assert_eq!(file(r#""version": 1, "board": "1 2 3 / 4 5 6 / 7 8 _", "solution": "D""#), invalid);
+        assert_eq!(file(r#""version": 1""#), invalid);
+        assert_eq!(
+            PuzzleFile::from_text(r#"{ "format": "corpus", "version": 1, "board": "1 2 3 / 4 5 6 / 7 8 _" }"#)
+                .map_err(|e| e.kind()),
+            invalid
+        );
+    }
+}
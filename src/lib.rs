@@ -12,6 +12,11 @@
 //! - **Controller** ([`controller`]): Game orchestration, shuffling, and move history
 //! - **Presenter** ([`presenter`]): GUI rendering using egui framework
 //!
+//! The `testing` module (behind the `testing` feature) provides seeded board generators for property tests.
+//! The [`io`] module reads and writes `.sldr` puzzle files, boards with their metadata.
+//! The [`logging`] module writes log lines to stderr for the command line and the puzzle service.
+//! The [`ffi`] module exposes the model layer to C and C++ through an `extern "C"` API.
+//!
 //! ## Quick Start
 //!
 //! ```rust,no_run
@@ -25,17 +30,17 @@
 //!
 //! ## Features
 //!
-//! - Grid sizes from 3×3 to 15×15
+//! - Grid sizes from 3×3 to 22×22
 //! - Immediate moves and chain moves (click any tile in line with empty cell)
 //! - **Smooth tile animations** - 200ms ease-out cubic interpolation
 //! - **Auto-solve with A\* pathfinding**:
-//!   - Watch optimal solutions play out with smooth animations
+//!   - Watch near-optimal solutions play out with smooth animations
 //!   - Recalculates fresh path on each invocation
 //!   - Never times out on 4×4 puzzles (2M iteration limit)
 //! - Three entropy calculation algorithms:
 //!   - Manhattan Distance (fast heuristic, microseconds)
 //!   - Shortest Path with linear conflicts (improved heuristic)
-//!   - A\* Actual Solution Length (exact optimal path, milliseconds)
+//!   - A\* Actual Solution Length (length of the path A\* finds, milliseconds)
 //! - Three difficulty levels with entropy-based thresholds
 //! - Guaranteed solvable shuffles using mechanical simulation
 //! - Performance metrics with calculation timing for A\* solver
@@ -65,9 +70,14 @@
 //! ```
 
 pub mod controller;
+pub mod ffi;
+pub mod io;
+pub mod logging;
 pub mod model;
 pub mod presenter;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use controller::{GameController, ShuffleController};
 pub use model::{Difficulty, PuzzleState};
-pub use presenter::run_gui;
\ No newline at end of file
+pub use presenter::{run_gui, run_gui_with_config};
\ No newline at end of file
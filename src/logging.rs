@@ -0,0 +1,67 @@
+//! Logging to stderr for the command line and the puzzle service.
+//!
+//! Both binaries install [`StderrLogger`] through [`init`], so their lines look
+//! the same. Only this crate's own targets (the library, `slider` and
+//! `slider_server`) log below warnings; dependencies such as egui, winit and wgpu
+//! would otherwise drown them out.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Minimal logger writing `level target: message` lines to stderr
+pub struct StderrLogger {
+    level: LevelFilter,
+}
+
+impl StderrLogger {
+    /// Creates a logger showing records at `level` and above
+    pub fn new(level: LevelFilter) -> Self {
+        Self { level }
+    }
+
+    /// Returns whether a record from `target` at `level` is written
+    fn shows(&self, level: Level, target: &str) -> bool {
+        level <= self.level && (target.starts_with("slider") || level <= Level::Warn)
+    }
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.shows(metadata.level(), metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{:<5} {}: {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs a [`StderrLogger`] at `level` as the global logger
+/// Does nothing if a logger is already installed
+pub fn init(level: LevelFilter) {
+    let logger = Box::leak(Box::new(StderrLogger::new(level)));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependencies_only_show_warnings() {
+        let logger = StderrLogger::new(LevelFilter::Debug);
+        assert!(logger.shows(Level::Debug, "slider::controller"));
+        assert!(logger.shows(Level::Info, "slider_server"));
+        assert!(!logger.shows(Level::Trace, "slider"));
+        assert!(!logger.shows(Level::Info, "wgpu_core"));
+        assert!(logger.shows(Level::Warn, "wgpu_core"));
+
+        let quiet = StderrLogger::new(LevelFilter::Warn);
+        assert!(!quiet.shows(Level::Info, "slider"));
+        assert!(quiet.shows(Level::Error, "egui"));
+    }
+}
@@ -1,16 +1,441 @@
-use slider::run_gui;
+//! Slider command line
+//!
+//! ```bash
+//! slider                                   # play with the defaults from slider.toml
+//! slider play 5                            # play a 5×5 board
+//! slider solve "1 2 3 / 4 _ 6 / 7 5 8"     # print the tiles to move, in order
+//! slider shuffle 4 hard --seed 7           # print a share code for a shuffled board
+//! slider bench --suite full                # run the solver benchmark suite
+//! ```
+//!
+//! `-v`/`--verbose` and `-vv`/`--trace` log to stderr with any command, and
+//! `--rebuild-cache` discards the cached heuristic tables first.
 
-fn main() -> eframe::Result<()> {
-    // Default to 4x4 grid
-    let grid_size = std::env::args()
-        .nth(1)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(4);
+use log::LevelFilter;
+use slider::controller::solver_benchmark::{run_suite, BenchmarkConfig, BenchmarkReport};
+use slider::model::notation;
+use slider::model::puzzle_state::{MAX_SIZE, MIN_SIZE};
+use slider::model::{
+    Difficulty, HeuristicCache, ManhattanDistance, PerformanceMetrics, PuzzleState, Solution, SolverConfig, SolverKind,
+};
+use slider::presenter::Config;
+use slider::{run_gui_with_config, ShuffleController};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
 
-    if grid_size < 3 || grid_size > 15 {
-        eprintln!("Grid size must be between 3 and 15");
-        std::process::exit(1);
+const USAGE: &str = "\
+Usage:
+  slider [play] [SIZE]
+  slider solve BOARD [--solver astar|idastar|greedy|strategic] [--timeout SECONDS]
+  slider shuffle [SIZE] [DIFFICULTY] [--seed N]
+  slider bench [--suite full|quick|NAME] [--samples N] [--seed N] [--output FILE]
+
+BOARD is text notation (\"1 2 3 / 4 _ 6 / 7 5 8\") or a share code.
+DIFFICULTY is easy, medium, hard, extra-hard or custom:N.
+Options for every command: -v/--verbose, -vv/--trace, --rebuild-cache";
+
+/// Benchmark samples per benchmark for `--suite quick`
+const QUICK_SAMPLES: usize = 3;
+
+/// What the command line asked for
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Open the GUI; None uses the grid size from slider.toml
+    Play { grid_size: Option<usize> },
+    Solve {
+        board: String,
+        solver: Option<SolverKind>, // None picks one suited to the grid size
+        timeout: Option<Duration>,
+    },
+    Shuffle {
+        size: usize,
+        difficulty: Difficulty,
+        seed: Option<u64>,
+    },
+    Bench {
+        config: BenchmarkConfig,
+        output: Option<PathBuf>,
+    },
+    Help,
+}
+
+/// Options accepted by every command, removed before the command is parsed
+const GLOBAL_FLAGS: [&str; 6] = ["-v", "--verbose", "-vv", "--trace", "--rebuild-cache", "--"];
+
+/// Parses a numeric argument, naming it in the error
+fn number<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} must be a number, not '{}'", name, value))
+}
+
+/// Parses a positive, finite number of seconds, naming it in the error
+fn seconds(name: &str, value: &str) -> Result<Duration, String> {
+    let seconds: f64 = number(name, value)?;
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(format!("{} must be a positive number of seconds, not '{}'", name, value));
     }
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("{} is too long: '{}'", name, value))
+}
 
-    run_gui(grid_size)
+/// Parses the arguments after the program name, ignoring the global flags
+fn parse_command(args: &[String]) -> Result<Command, String> {
+    let mut args = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !GLOBAL_FLAGS.contains(arg))
+        .peekable();
+    let command = match args.peek().copied() {
+        None => return Ok(Command::Play { grid_size: None }),
+        Some("help" | "-h" | "--help") => return Ok(Command::Help),
+        // A bare grid size plays, as it always has
+        Some(size) if size.parse::<usize>().is_ok() => "play",
+        Some(command) => {
+            args.next();
+            command
+        }
+    };
+
+    let mut positional = Vec::new();
+    let mut options = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+            options.push((arg, value));
+        } else {
+            positional.push(arg);
+        }
+    }
+    let grid_size = |value: &str| -> Result<usize, String> {
+        let size = number("SIZE", value)?;
+        if (MIN_SIZE..=MAX_SIZE).contains(&size) {
+            Ok(size)
+        } else {
+            Err(format!("Grid size must be between {} and {}", MIN_SIZE, MAX_SIZE))
+        }
+    };
+    let unexpected = |arg: &str| Err(format!("unexpected argument '{}' for {}", arg, command));
+
+    match command {
+        "play" => {
+            let grid_size = match positional[..] {
+                [] => None,
+                [size] => Some(grid_size(size)?),
+                [_, extra, ..] => return unexpected(extra),
+            };
+            if let Some((option, _)) = options.first() {
+                return unexpected(option);
+            }
+            Ok(Command::Play { grid_size })
+        }
+        "solve" => {
+            // Unquoted notation arrives as several words
+            if positional.is_empty() {
+                return Err("solve needs a board".to_string());
+            }
+            let mut solver = None;
+            let mut timeout = None;
+            for (option, value) in options {
+                match option {
+                    "--solver" => {
+                        solver = Some(SolverKind::from_name(value).ok_or_else(|| format!("unknown solver '{}'", value))?)
+                    }
+                    "--timeout" => timeout = Some(seconds("--timeout", value)?),
+                    _ => return unexpected(option),
+                }
+            }
+            Ok(Command::Solve {
+                board: positional.join(" "),
+                solver,
+                timeout,
+            })
+        }
+        "shuffle" => {
+            let (size, difficulty) = match positional[..] {
+                [] => (4, Difficulty::Medium),
+                [size] => (grid_size(size)?, Difficulty::Medium),
+                [size, name] => (
+                    grid_size(size)?,
+                    Difficulty::from_name(name).ok_or_else(|| format!("unknown difficulty '{}'", name))?,
+                ),
+                [_, _, extra, ..] => return unexpected(extra),
+            };
+            let mut seed = None;
+            for (option, value) in options {
+                match option {
+                    "--seed" => seed = Some(number("--seed", value)?),
+                    _ => return unexpected(option),
+                }
+            }
+            Ok(Command::Shuffle { size, difficulty, seed })
+        }
+        "bench" => {
+            if let Some(extra) = positional.first() {
+                return unexpected(extra);
+            }
+            let mut config = BenchmarkConfig::default();
+            let mut output = None;
+            for (option, value) in options {
+                match option {
+                    "--suite" => match value {
+                        "full" => {}
+                        "quick" => config.samples = QUICK_SAMPLES,
+                        name => config.filter = Some(name.to_string()),
+                    },
+                    "--samples" => config.samples = number("--samples", value)?,
+                    "--seed" => config.seed = number("--seed", value)?,
+                    "--output" => output = Some(PathBuf::from(value)),
+                    _ => return unexpected(option),
+                }
+            }
+            Ok(Command::Bench { config, output })
+        }
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+/// Prints the tile numbers to move on one line, with blank moves and a summary on stderr
+fn solve(board: &str, solver: Option<SolverKind>, timeout: Option<Duration>) -> Result<(), String> {
+    let state = PuzzleState::from_notation_or_code(board).map_err(|e| e.to_string())?;
+    let mut config = SolverConfig::default();
+    if let Some(timeout) = timeout {
+        config.timeout = timeout;
+    }
+
+    let (kind, solution) = find_solution(&state, solver, config)?;
+    println!("{}", notation::tile_numbers(&state, &solution.path));
+    eprintln!(
+        "{} ({} moves with {}, {}), {} nodes in {}",
+        solution,
+        solution.len(),
+        kind,
+        if kind.is_optimal() { "optimal" } else { "not optimal" },
+        solution.nodes_expanded,
+        PerformanceMetrics::format_duration(solution.duration.as_micros() as u64)
+    );
+    Ok(())
+}
+
+/// Solves with the named solver, or by default with the one suited to the grid size
+/// When the default gives up, the strategic solver takes over and stderr says so
+fn find_solution(
+    state: &PuzzleState,
+    solver: Option<SolverKind>,
+    config: SolverConfig,
+) -> Result<(SolverKind, Solution), String> {
+    let kind = solver.unwrap_or_else(|| SolverKind::default_for_size(state.size()));
+    match kind.try_solve_instrumented(config, state, None, &mut |_| {}) {
+        Ok(solution) => Ok((kind, solution)),
+        Err(e) if solver.is_none() && kind != SolverKind::Strategic => {
+            eprintln!("{} gave up ({}), falling back to {}", kind, e, SolverKind::Strategic);
+            SolverKind::Strategic
+                .try_solve_instrumented(config, state, None, &mut |_| {})
+                .map(|solution| (SolverKind::Strategic, solution))
+                .map_err(|e| format!("{} found no solution: {}", SolverKind::Strategic, e))
+        }
+        Err(e) => Err(format!("{} found no solution: {}", kind, e)),
+    }
+}
+
+/// Prints a share code for a shuffled board, with the board itself on stderr
+fn shuffle(size: usize, difficulty: Difficulty, seed: Option<u64>) -> Result<(), String> {
+    let mut state = PuzzleState::new(size).map_err(|e| e.to_string())?;
+    let shuffler = ShuffleController::new(size).map_err(|e| e.to_string())?;
+    let result = match seed {
+        Some(seed) => shuffler.shuffle_seeded(&mut state, difficulty, &ManhattanDistance, seed),
+        None => shuffler.shuffle(&mut state, difficulty, &ManhattanDistance),
+    };
+
+    println!("{}", state.to_share_code());
+    eprintln!(
+        "{} (entropy {}, seed {})",
+        state.to_notation(),
+        result.final_entropy,
+        result.seed
+    );
+    Ok(())
+}
+
+/// Runs the benchmark suite and prints one line per benchmark
+fn bench(config: &BenchmarkConfig, output: Option<PathBuf>) -> Result<(), String> {
+    let results = run_suite(config);
+    if results.is_empty() {
+        return Err("no benchmark matches the suite name".to_string());
+    }
+    println!("{:<28} {:>10} {:>10} {:>12}", "benchmark", "mean", "median", "nodes");
+    for result in &results {
+        let nodes = result.nodes_expanded.map_or("-".to_string(), |n| format!("{:.0}", n));
+        println!(
+            "{:<28} {:>10} {:>10} {:>12}",
+            result.name,
+            PerformanceMetrics::format_duration(result.mean_micros.round() as u64),
+            PerformanceMetrics::format_duration(result.median_micros.round() as u64),
+            nodes
+        );
+    }
+
+    if let Some(path) = output {
+        BenchmarkReport::new(config, results)
+            .save(&path)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        eprintln!("Report written to {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // --verbose shows solver and game events; -vv (or --trace) shows every move
+    let level = if args.iter().any(|arg| arg == "-vv" || arg == "--trace") {
+        LevelFilter::Trace
+    } else if args.iter().any(|arg| arg == "--verbose" || arg == "-v") {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Warn
+    };
+    slider::logging::init(level);
+
+    if args.iter().any(|arg| arg == "--rebuild-cache") {
+        let cache = HeuristicCache::user();
+        match cache.clear() {
+            Ok(removed) => println!("Cleared {} cached heuristic table(s)", removed),
+            Err(e) => eprintln!("Failed to clear heuristic cache: {}", e),
+        }
+    }
+
+    let command = match parse_command(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = match command {
+        Command::Play { grid_size } => {
+            // The command line overrides the grid size from slider.toml
+            let config = Config::load_default();
+            let grid_size = grid_size.unwrap_or(config.grid_size);
+            run_gui_with_config(Config { grid_size, ..config }).map_err(|e| e.to_string())
+        }
+        Command::Solve { board, solver, timeout } => solve(&board, solver, timeout),
+        Command::Shuffle { size, difficulty, seed } => shuffle(size, difficulty, seed),
+        Command::Bench { config, output } => bench(&config, output),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse_command(&args)
+    }
+
+    #[test]
+    fn test_play() {
+        assert_eq!(parse(""), Ok(Command::Play { grid_size: None }));
+        assert_eq!(parse("--verbose 5"), Ok(Command::Play { grid_size: Some(5) }));
+        assert_eq!(parse("play 6"), Ok(Command::Play { grid_size: Some(6) }));
+        assert!(parse("play 99").is_err());
+        assert!(parse("2").is_err());
+        assert!(parse("juggle").is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            parse("solve 1 2 3 / 4 _ 6 / 7 5 8 --solver ida* --timeout 2"),
+            Ok(Command::Solve {
+                board: "1 2 3 / 4 _ 6 / 7 5 8".to_string(),
+                solver: Some(SolverKind::IdaStar),
+                timeout: Some(Duration::from_secs(2)),
+            })
+        );
+        assert!(parse("solve").is_err());
+        assert!(parse("solve ABC --solver bogus").is_err());
+        for bad in ["-1", "0", "nan", "inf", "1e300", "soon"] {
+            assert!(parse(&format!("solve ABC --timeout {}", bad)).is_err(), "{}", bad);
+        }
+        assert_eq!(
+            parse("solve ABC --timeout 0.5"),
+            Ok(Command::Solve {
+                board: "ABC".to_string(),
+                solver: None,
+                timeout: Some(Duration::from_millis(500)),
+            })
+        );
+        assert!(solve("1 2 3 / 4 _ 6 / 7 5 8", None, None).is_ok());
+        assert!(solve("not a board", None, None).is_err());
+    }
+
+    #[test]
+    fn test_solve_falls_back_to_strategic() {
+        let mut state = PuzzleState::new(5).unwrap();
+        ShuffleController::new(5)
+            .unwrap()
+            .shuffle_seeded(&mut state, Difficulty::Hard, &ManhattanDistance, 7);
+        let config = SolverConfig {
+            timeout: Duration::from_millis(200),
+            ..SolverConfig::default()
+        };
+
+        // IDA* cannot finish a hard 5×5 board this quickly
+        let (kind, solution) = find_solution(&state, None, config).unwrap();
+        assert_eq!(kind, SolverKind::Strategic);
+        assert!(!solution.path.is_empty());
+        // A named solver is never swapped for another
+        assert!(find_solution(&state, Some(SolverKind::IdaStar), config).is_err());
+    }
+
+    #[test]
+    fn test_shuffle() {
+        assert_eq!(
+            parse("shuffle 4 hard --seed 7"),
+            Ok(Command::Shuffle {
+                size: 4,
+                difficulty: Difficulty::Hard,
+                seed: Some(7),
+            })
+        );
+        assert_eq!(
+            parse("shuffle"),
+            Ok(Command::Shuffle {
+                size: 4,
+                difficulty: Difficulty::Medium,
+                seed: None,
+            })
+        );
+        assert!(parse("shuffle 4 impossible").is_err());
+        assert!(parse("shuffle 4 --seed").is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let Ok(Command::Bench { config, output }) = parse("bench --suite quick --output out.csv") else {
+            panic!("bench should parse");
+        };
+        assert_eq!(config.samples, QUICK_SAMPLES);
+        assert_eq!(config.filter, None);
+        assert_eq!(output, Some(PathBuf::from("out.csv")));
+
+        let Ok(Command::Bench { config, .. }) = parse("bench --suite solve_3x3") else {
+            panic!("bench should parse");
+        };
+        assert_eq!(config.filter.as_deref(), Some("solve_3x3"));
+        assert!(parse("bench --samples many").is_err());
+    }
 }
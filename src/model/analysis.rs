@@ -0,0 +1,275 @@
+//! # Game Analysis
+//!
+//! Replays a finished game and rates each player move by how it changed the
+//! optimal distance to the goal (the length of the shortest solution from the
+//! board). A single tile slide always changes that distance by exactly one, so a
+//! chain move of `n` tiles changes it by at most `n`:
+//!
+//! | Quality | Change in distance                      |
+//! |---------|-----------------------------------------|
+//! | Optimal | down by the number of tiles moved       |
+//! | Neutral | down by less, or unchanged              |
+//! | Blunder | up                                      |
+//!
+//! Solving every board of a long game from scratch would be slow, so the
+//! analysis re-solves incrementally: it keeps an optimal path from the current
+//! board and only searches again when a move leaves that path. Sliding the tile
+//! back and following the old path still solves the board, so the new search's
+//! path is kept only when it is shorter than that one; the search itself runs
+//! without a bound and costs the same either way.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::analysis::{analyze_game, MoveQuality};
+//! use slider::model::{PuzzleState, SolverConfig, SolverKind};
+//!
+//! let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse()?;
+//! // Up, then back down, then the two moves of the solution
+//! let moves = [vec![(0, 1)], vec![(1, 1)], vec![(2, 1)], vec![(2, 2)]];
+//! let analysis = analyze_game(&puzzle, &moves, SolverKind::IdaStar, SolverConfig::default(), None)?;
+//!
+//! assert_eq!(analysis.moves[0].quality, MoveQuality::Blunder);
+//! assert_eq!(analysis.count(MoveQuality::Optimal), 3);
+//! assert_eq!(analysis.wasted_moves(), 2);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::error::SolverError;
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+use super::solver::{SolverConfig, SolverKind};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// How good a player move was
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveQuality {
+    /// Every tile moved brought the board one step nearer the goal
+    Optimal,
+    /// The board got no further from the goal, but some tiles were wasted
+    Neutral,
+    /// The board ended further from the goal than before
+    Blunder,
+}
+
+impl MoveQuality {
+    pub const ALL: [MoveQuality; 3] = [MoveQuality::Optimal, MoveQuality::Neutral, MoveQuality::Blunder];
+
+    /// Rates a move of `tiles` tiles that changed the optimal distance by `change`
+    pub fn from_change(tiles: usize, change: i64) -> Self {
+        if change > 0 {
+            MoveQuality::Blunder
+        } else if change == -(tiles as i64) {
+            MoveQuality::Optimal
+        } else {
+            MoveQuality::Neutral
+        }
+    }
+}
+
+impl fmt::Display for MoveQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MoveQuality::Optimal => "Optimal",
+            MoveQuality::Neutral => "Neutral",
+            MoveQuality::Blunder => "Blunder",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One player move as rated by the analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveAnalysis {
+    /// Tiles slid by the move, in order (more than one for a chain move)
+    pub tiles: Vec<Position>,
+    /// Optimal distance before the move
+    pub distance_before: u32,
+    /// Optimal distance after the move
+    pub distance_after: u32,
+    pub quality: MoveQuality,
+}
+
+impl MoveAnalysis {
+    /// Returns how the move changed the optimal distance (negative is progress)
+    pub fn change(&self) -> i64 {
+        self.distance_after as i64 - self.distance_before as i64
+    }
+}
+
+/// Every move of a game, rated against optimal play
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameAnalysis {
+    pub moves: Vec<MoveAnalysis>,
+}
+
+impl GameAnalysis {
+    /// Returns the number of moves of the given quality
+    pub fn count(&self, quality: MoveQuality) -> usize {
+        self.moves.iter().filter(|m| m.quality == quality).count()
+    }
+
+    /// Returns the optimal distance of the starting board, or None for a game without moves
+    pub fn starting_distance(&self) -> Option<u32> {
+        self.moves.first().map(|m| m.distance_before)
+    }
+
+    /// Returns the tile moves spent beyond the optimal solution of the starting board
+    pub fn wasted_moves(&self) -> usize {
+        let tiles: usize = self.moves.iter().map(|m| m.tiles.len()).sum();
+        let progress = self.starting_distance().unwrap_or(0) as i64
+            - self.moves.last().map_or(0, |m| m.distance_after) as i64;
+        (tiles as i64 - progress).max(0) as usize
+    }
+}
+
+/// Rates each move of a game played from `initial`
+///
+/// `moves` lists the player moves in order, each as the tiles it slid. `kind`
+/// should be an optimal solver; a non-optimal one gives distances that are only
+/// upper bounds, and so ratings that may be unfair. `cancel_flag` stops the
+/// analysis at the next search.
+///
+/// # Errors
+///
+/// Returns `SolverError::InvalidState` if a move is not legal on the board it was
+/// played on, `SolverError::Cancelled` if cancelled, or the solver's error if a
+/// search gives up
+pub fn analyze_game(
+    initial: &PuzzleState,
+    moves: &[Vec<Position>],
+    kind: SolverKind,
+    config: SolverConfig,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> Result<GameAnalysis, SolverError> {
+    let mut board = initial.clone();
+    let mut path = optimal_path(kind, config, &board, &cancel_flag)?;
+    let mut analysis = GameAnalysis::default();
+
+    for (index, tiles) in moves.iter().enumerate() {
+        let distance_before = path.len() as u32;
+        for &tile in tiles {
+            let empty = board.empty_position();
+            if !board.apply_immediate_move(tile) {
+                return Err(SolverError::InvalidState(format!(
+                    "move {} slides {:?}, which is not next to the empty cell",
+                    index + 1,
+                    tile
+                )));
+            }
+            if path.front() == Some(&tile) {
+                path.pop_front();
+                continue;
+            }
+
+            // Sliding the tile back and following the old path still solves the board
+            path.push_front(empty);
+            let shorter = optimal_path(kind, config, &board, &cancel_flag)?;
+            if shorter.len() < path.len() {
+                path = shorter;
+            }
+        }
+
+        let distance_after = path.len() as u32;
+        let change = distance_after as i64 - distance_before as i64;
+        analysis.moves.push(MoveAnalysis {
+            tiles: tiles.clone(),
+            distance_before,
+            distance_after,
+            quality: MoveQuality::from_change(tiles.len(), change),
+        });
+    }
+    Ok(analysis)
+}
+
+/// Solves `board`, or returns an empty path if it is already solved
+fn optimal_path(
+    kind: SolverKind,
+    config: SolverConfig,
+    board: &PuzzleState,
+    cancel_flag: &Option<Arc<AtomicBool>>,
+) -> Result<VecDeque<Position>, SolverError> {
+    if cancel_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+        return Err(SolverError::Cancelled);
+    }
+    if board.is_solved() {
+        return Ok(VecDeque::new());
+    }
+    let solution = kind.try_solve_instrumented(config, board, cancel_flag.clone(), &mut |_| {})?;
+    Ok(solution.path.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::scramble;
+    use crate::model::MoveSystem;
+
+    /// Optimal distance of `board`, solved from scratch
+    fn distance(board: &PuzzleState) -> u32 {
+        SolverKind::IdaStar
+            .try_solve_instrumented(SolverConfig::default(), board, None, &mut |_| {})
+            .unwrap()
+            .len() as u32
+    }
+
+    #[test]
+    fn test_move_quality_from_change() {
+        assert_eq!(MoveQuality::from_change(1, -1), MoveQuality::Optimal);
+        assert_eq!(MoveQuality::from_change(1, 1), MoveQuality::Blunder);
+        assert_eq!(MoveQuality::from_change(3, -3), MoveQuality::Optimal);
+        assert_eq!(MoveQuality::from_change(3, -1), MoveQuality::Neutral);
+        assert_eq!(MoveQuality::from_change(2, 0), MoveQuality::Neutral);
+        assert_eq!(MoveQuality::from_change(3, 1), MoveQuality::Blunder);
+    }
+
+    #[test]
+    fn test_incremental_distances_match_fresh_solves() {
+        // Wander about the board, on and off optimal lines
+        let initial = scramble(3, 30, 5);
+        let mut board = initial.clone();
+        let mut moves = Vec::new();
+        for step in 0..12 {
+            let options = board.legal_moves();
+            let tile = options[(step * 7) % options.len()];
+            board.apply_immediate_move(tile);
+            moves.push(vec![tile]);
+        }
+
+        let analysis = analyze_game(&initial, &moves, SolverKind::IdaStar, SolverConfig::default(), None).unwrap();
+        let mut replay = initial.clone();
+        for (rated, tiles) in analysis.moves.iter().zip(&moves) {
+            assert_eq!(rated.distance_before, distance(&replay));
+            replay.apply_immediate_move(tiles[0]);
+            assert_eq!(rated.distance_after, distance(&replay));
+            assert_eq!(rated.change().abs(), 1);
+        }
+    }
+
+    #[test]
+    fn test_chain_moves_and_waste() {
+        let initial = PuzzleState::new(3).unwrap();
+        // Chain two tiles out of place, then one back, then the other
+        let moves = [vec![(2, 1), (2, 0)], vec![(2, 1)], vec![(2, 2)]];
+        let analysis = analyze_game(&initial, &moves, SolverKind::IdaStar, SolverConfig::default(), None).unwrap();
+        let qualities: Vec<_> = analysis.moves.iter().map(|m| m.quality).collect();
+        assert_eq!(qualities, [MoveQuality::Blunder, MoveQuality::Optimal, MoveQuality::Optimal]);
+        assert_eq!(analysis.starting_distance(), Some(0));
+        assert_eq!(analysis.wasted_moves(), 4);
+    }
+
+    #[test]
+    fn test_illegal_move_and_cancel() {
+        let initial = PuzzleState::new(3).unwrap();
+        let err = analyze_game(&initial, &[vec![(0, 0)]], SolverKind::IdaStar, SolverConfig::default(), None);
+        assert!(matches!(err, Err(SolverError::InvalidState(_))));
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        let puzzle = scramble(3, 20, 2);
+        let err = analyze_game(&puzzle, &[], SolverKind::IdaStar, SolverConfig::default(), Some(cancel));
+        assert_eq!(err, Err(SolverError::Cancelled));
+    }
+}
@@ -0,0 +1,312 @@
+//! # 3×3 Endgame Table
+//!
+//! The exact distance to the goal of every 3×3 board, found once by a
+//! breadth-first search out from the goal. There are 9!/2 = 181,440 reachable
+//! boards, stored one byte each by permutation rank, so the table is 363 KB and
+//! builds in a fraction of a second; it is kept in the [`HeuristicCache`] so
+//! later runs only read it back.
+//!
+//! With the table, a 3×3 board needs no search at all: from any board, some
+//! move always leads one step closer, so following those moves plays an optimal
+//! solution straight away. The optimal solvers do this for every 3×3 game.
+//!
+//! The table also covers the 3×3 corner block of larger boards once every tile
+//! outside it is home: the distance is then the fewest moves that finish the
+//! board without leaving the block. The strategic solver reduces larger boards
+//! to that block and finishes them from the table.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{EndgameTable, PuzzleState};
+//!
+//! let table = EndgameTable::build();
+//! let board: PuzzleState = "8 6 7 / 2 5 4 / 3 _ 1".parse()?;
+//! assert_eq!(table.distance(&board), Some(31));
+//! assert_eq!(table.solve(&board).map(|path| path.len()), Some(31));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::entropy::{EntropyCalculator, ManhattanDistance};
+use super::heuristic_cache::{CachedTable, HeuristicCache};
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+
+/// Side of the block the table covers
+pub const ENDGAME_SIZE: usize = 3;
+
+const CELLS: usize = ENDGAME_SIZE * ENDGAME_SIZE;
+
+/// Arrangements of nine cells (9!), half of them reachable
+const ARRANGEMENTS: usize = 362_880;
+
+/// Marks arrangements that cannot reach the goal
+const UNREACHABLE: u8 = u8::MAX;
+
+/// Goal index of every cell of the block, read row by row; the empty cell is last
+type Block = [u8; CELLS];
+
+/// The block of a board the table can measure, and where it sits on the board
+struct Placement {
+    block: Block,
+    top: usize,
+    left: usize,
+    flip_rows: bool, // The goal's empty cell is in the block's top row
+    flip_cols: bool, // ... or its left column
+}
+
+impl Placement {
+    /// Returns the board cell at `index` of the block
+    fn position(&self, index: usize) -> Position {
+        let flip = |i: usize, flipped: bool| if flipped { ENDGAME_SIZE - 1 - i } else { i };
+        (
+            self.top + flip(index / ENDGAME_SIZE, self.flip_rows),
+            self.left + flip(index % ENDGAME_SIZE, self.flip_cols),
+        )
+    }
+}
+
+/// Distance to the goal of every 3×3 arrangement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndgameTable {
+    distances: Vec<u8>, // By permutation rank; UNREACHABLE for the other half
+}
+
+impl EndgameTable {
+    /// Builds the table by breadth-first search from the goal
+    pub fn build() -> Self {
+        let mut distances = vec![UNREACHABLE; ARRANGEMENTS];
+        let goal: Block = std::array::from_fn(|i| i as u8);
+        distances[rank(&goal)] = 0;
+        let mut queue = VecDeque::from([goal]);
+        while let Some(block) = queue.pop_front() {
+            let distance = distances[rank(&block)];
+            for next in successors(&block) {
+                let next_rank = rank(&next);
+                if distances[next_rank] == UNREACHABLE {
+                    distances[next_rank] = distance + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        Self { distances }
+    }
+
+    /// Loads the table from `cache`, building and storing it if needed
+    pub fn load_or_build(cache: &HeuristicCache) -> Self {
+        cache.load_or_build("3x3", Self::build)
+    }
+
+    /// Returns the table for this process, loading or building it on first use
+    /// from the per-user cache
+    pub fn shared() -> &'static Self {
+        static TABLE: OnceLock<EndgameTable> = OnceLock::new();
+        TABLE.get_or_init(|| Self::load_or_build(&HeuristicCache::user()))
+    }
+
+    /// Returns the number of arrangements that can reach the goal
+    pub fn reachable(&self) -> usize {
+        self.distances.iter().filter(|&&d| d != UNREACHABLE).count()
+    }
+
+    /// Returns the fewest moves that solve `state`
+    /// Returns None if the table cannot measure it (see `covers`) or it is unsolvable
+    ///
+    /// On larger boards the moves are those that finish the corner block
+    /// without leaving it.
+    pub fn distance(&self, state: &PuzzleState) -> Option<u32> {
+        let placement = placement(state)?;
+        self.lookup(&placement.block).map(u32::from)
+    }
+
+    /// Returns true if `state` is a 3×3 board, or a larger one whose only
+    /// unsolved tiles fill the 3×3 block in the goal's empty-cell corner
+    ///
+    /// The goal's empty cell must belong in a corner, so spiral goals are not covered.
+    pub fn covers(&self, state: &PuzzleState) -> bool {
+        placement(state).is_some()
+    }
+
+    /// Returns an optimal solution of `state`, as tile positions to move
+    /// Returns None where `distance` does
+    pub fn solve(&self, state: &PuzzleState) -> Option<Vec<Position>> {
+        let placement = placement(state)?;
+        let mut block = placement.block;
+        let mut distance = self.lookup(&block)?;
+        let mut path = Vec::with_capacity(distance as usize);
+        while distance > 0 {
+            // Some neighbour is always one step closer, or the table would be wrong
+            let (next, moved) = successors_with_moves(&block)
+                .find(|(next, _)| self.lookup(next) == Some(distance - 1))?;
+            path.push(placement.position(moved));
+            block = next;
+            distance -= 1;
+        }
+        Some(path)
+    }
+
+    fn lookup(&self, block: &Block) -> Option<u8> {
+        Some(self.distances[rank(block)]).filter(|&d| d != UNREACHABLE)
+    }
+}
+
+impl CachedTable for EndgameTable {
+    const KIND: &'static str = "endgame";
+    const VERSION: u32 = 1;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.distances.clone()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        (bytes.len() == ARRANGEMENTS).then(|| Self {
+            distances: bytes.to_vec(),
+        })
+    }
+}
+
+/// Exact distances where the table covers the board, Manhattan distance elsewhere
+impl EntropyCalculator for EndgameTable {
+    fn calculate(&self, state: &PuzzleState) -> u32 {
+        self.distance(state).unwrap_or_else(|| ManhattanDistance.calculate(state))
+    }
+}
+
+/// Finds the block of `state` the table can measure
+fn placement(state: &PuzzleState) -> Option<Placement> {
+    let n = state.size();
+    let (goal_row, goal_col) = state.goal_empty_position();
+    let corner = |goal: usize| match goal {
+        g if g == n - 1 => Some((n - ENDGAME_SIZE, false)),
+        0 => Some((0, true)),
+        _ => None,
+    };
+    let ((top, flip_rows), (left, flip_cols)) = (corner(goal_row)?, corner(goal_col)?);
+    let inside = |(row, col): Position| (top..top + ENDGAME_SIZE).contains(&row) && (left..left + ENDGAME_SIZE).contains(&col);
+    let flip = |i: usize, flipped: bool| if flipped { ENDGAME_SIZE - 1 - i } else { i };
+    let index = |(row, col): Position| flip(row - top, flip_rows) * ENDGAME_SIZE + flip(col - left, flip_cols);
+
+    let empty = state.empty_position();
+    if !inside(empty) {
+        return None;
+    }
+    let mut block = [0u8; CELLS];
+    block[index(empty)] = index((goal_row, goal_col)) as u8;
+    for (pos, tile) in state.tiles() {
+        match (inside(pos), inside(tile.home_position)) {
+            (true, true) => block[index(pos)] = index(tile.home_position) as u8,
+            (false, _) if pos == tile.home_position => {}
+            _ => return None,
+        }
+    }
+    Some(Placement {
+        block,
+        top,
+        left,
+        flip_rows,
+        flip_cols,
+    })
+}
+
+/// Arrangements one move away, each with the block index of the tile that moved
+fn successors_with_moves(block: &Block) -> impl Iterator<Item = (Block, usize)> + '_ {
+    let empty = block.iter().position(|&goal| goal as usize == CELLS - 1).expect("block has an empty cell");
+    let (row, col) = (empty / ENDGAME_SIZE, empty % ENDGAME_SIZE);
+    [
+        (row > 0).then(|| empty - ENDGAME_SIZE),
+        (row + 1 < ENDGAME_SIZE).then(|| empty + ENDGAME_SIZE),
+        (col > 0).then(|| empty - 1),
+        (col + 1 < ENDGAME_SIZE).then(|| empty + 1),
+    ]
+    .into_iter()
+    .flatten()
+    .map(move |tile| {
+        let mut next = *block;
+        next.swap(empty, tile);
+        (next, tile)
+    })
+}
+
+fn successors(block: &Block) -> impl Iterator<Item = Block> + '_ {
+    successors_with_moves(block).map(|(next, _)| next)
+}
+
+/// Lexicographic rank of a permutation of 0..9 (its Lehmer code)
+fn rank(block: &Block) -> usize {
+    let mut rank = 0;
+    for i in 0..CELLS {
+        let smaller_after = block[i + 1..].iter().filter(|&&later| later < block[i]).count();
+        rank = rank * (CELLS - i) + smaller_after;
+    }
+    rank
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::heuristic_lab::optimal_distances;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::{GoalSpec, PackedState};
+
+    fn table() -> &'static EndgameTable {
+        EndgameTable::shared()
+    }
+
+    #[test]
+    fn test_matches_breadth_first_distances() {
+        let table = EndgameTable::build();
+        assert_eq!(table.reachable(), 181_440);
+        let goal = PuzzleState::new(3).unwrap();
+        for (board, distance) in optimal_distances(&goal).into_iter().step_by(97) {
+            assert_eq!(table.distance(&board.to_state()), Some(distance));
+        }
+        assert_eq!(rank(&std::array::from_fn(|i| (CELLS - 1 - i) as u8)), ARRANGEMENTS - 1);
+    }
+
+    #[test]
+    fn test_solutions_are_optimal_and_valid() {
+        for goal in [GoalSpec::Standard, GoalSpec::BlankFirst, GoalSpec::Reversed] {
+            let distances = optimal_distances(&PuzzleState::with_goal(3, goal).unwrap());
+            for seed in 0..10 {
+                let board = scramble_from(PuzzleState::with_goal(3, goal).unwrap(), 60, seed);
+                let path = table().solve(&board).unwrap();
+                assert_eq!(path.len() as u32, distances[&PackedState::from_state(&board)]);
+                let mut state = board.clone();
+                for pos in path {
+                    assert!(state.apply_immediate_move(pos));
+                }
+                assert!(state.is_solved());
+            }
+        }
+        let spiral = PuzzleState::with_goal(3, GoalSpec::Spiral).unwrap();
+        assert!(!table().covers(&spiral));
+    }
+
+    #[test]
+    fn test_covers_the_corner_of_larger_boards() {
+        let mut corner = PuzzleState::new(4).unwrap();
+        for pos in [(2, 3), (2, 2), (1, 2), (1, 1), (2, 1), (3, 1), (3, 2)] {
+            assert!(corner.apply_immediate_move(pos));
+        }
+        let path = table().solve(&corner).unwrap();
+        assert_eq!(table().distance(&corner), Some(path.len() as u32));
+        assert!(path.iter().all(|&(row, col)| row >= 1 && col >= 1));
+        let mut state = corner.clone();
+        for pos in path {
+            assert!(state.apply_immediate_move(pos));
+        }
+        assert!(state.is_solved());
+
+        assert!(!table().covers(&scramble(4, 40, 1)));
+        assert_eq!(table().calculate(&scramble(4, 40, 1)), ManhattanDistance.calculate(&scramble(4, 40, 1)));
+    }
+
+    #[test]
+    fn test_round_trips_through_bytes() {
+        let bytes = table().to_bytes();
+        assert_eq!(EndgameTable::from_bytes(&bytes).as_ref(), Some(table()));
+        assert!(EndgameTable::from_bytes(&bytes[1..]).is_none());
+    }
+}
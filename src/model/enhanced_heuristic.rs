@@ -29,7 +29,7 @@
 //! ## Example
 //!
 //! ```rust
-//! use slider::model::{EnhancedHeuristic, PuzzleState};
+//! use slider::model::{EnhancedHeuristic, EntropyCalculator, PuzzleState};
 //!
 //! let heuristic = EnhancedHeuristic;
 //! let puzzle = PuzzleState::new(4)?;
@@ -45,10 +45,22 @@
 /// not just tile displacement, but also the geometric constraints that make
 /// certain configurations harder to solve.
 ///
-/// The heuristic is **admissible** (never overestimates) and **consistent**,
-/// making it suitable for A* search with optimal solution guarantees.
-use super::entropy::{count_linear_conflicts, EntropyCalculator, ManhattanDistance};
+/// The corner and edge penalties are empirical rather than proven lower bounds
+/// (one move that displaces a corner tile scores 4), so the heuristic is **not
+/// admissible**: it can overestimate, and A* search with it finds near-optimal
+/// solutions rather than guaranteed shortest ones.
+use super::entropy::{count_linear_conflicts, Difficulty, EntropyCalculator, ManhattanDistance, TuningTable};
 use super::puzzle_state::PuzzleState;
+use std::ops::RangeInclusive;
+
+/// Corner and edge penalties weigh most on small, lightly shuffled boards
+const TUNING: TuningTable = [
+    [218, 252, 219, 214],
+    [191, 188, 176, 157],
+    [166, 163, 155, 141],
+    [157, 147, 144, 131],
+    [147, 133, 130, 122],
+];
 
 #[derive(Debug, Default)]
 pub struct EnhancedHeuristic;
@@ -70,6 +82,10 @@ impl EntropyCalculator for EnhancedHeuristic {
         // Combine all signals
         base_score + (linear_conflicts * 2) + corner_penalty + edge_penalty
     }
+
+    fn target_range(&self, difficulty: Difficulty, size: usize) -> RangeInclusive<u32> {
+        difficulty.tuned_range(size, &TUNING)
+    }
 }
 
 /// Calculate penalty for corner tiles being out of place
@@ -183,6 +199,14 @@ mod tests {
         assert!(enhanced_score >= manhattan_score);
     }
 
+    #[test]
+    fn test_enhanced_can_overestimate() {
+        // One move from solved, tile 8 sits in the bottom-right corner
+        let mut puzzle = PuzzleState::new(3).unwrap();
+        assert!(puzzle.apply_immediate_move((2, 1)));
+        assert_eq!(EnhancedHeuristic.calculate(&puzzle), 4);
+    }
+
     #[test]
     fn test_corner_penalty() {
         let puzzle = PuzzleState::new(3).unwrap();
@@ -192,6 +216,23 @@ mod tests {
         assert_eq!(penalty, 0);
     }
 
+    #[test]
+    fn test_tuned_targets_match_manhattan_disorder() {
+        use crate::controller::ShuffleController;
+
+        // Untuned, Medium would stop at about 9 Manhattan distance on a 4×4 board
+        let shuffler = ShuffleController::new(4).unwrap();
+        let total: u32 = (0..20)
+            .map(|seed| {
+                let mut puzzle = PuzzleState::new(4).unwrap();
+                shuffler.shuffle_seeded(&mut puzzle, Difficulty::Medium, &EnhancedHeuristic, seed);
+                ManhattanDistance.calculate(&puzzle)
+            })
+            .sum();
+        let average = total / 20;
+        assert!((13..=20).contains(&average), "average Manhattan distance {}", average);
+    }
+
     #[test]
     fn test_edge_penalty() {
         let puzzle = PuzzleState::new(3).unwrap();
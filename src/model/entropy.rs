@@ -1,12 +1,47 @@
 use super::puzzle_state::PuzzleState;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 /// Trait for calculating entropy (disorder) in the puzzle
 pub trait EntropyCalculator {
     /// Calculate the entropy value for the given puzzle state
     /// Higher values indicate more disorder
     fn calculate(&self, state: &PuzzleState) -> u32;
+
+    /// Returns the entropy a board of `size` shuffled to `difficulty` should reach,
+    /// as measured by this calculator
+    ///
+    /// Shuffles stop once the board reaches the start of the range; the end is
+    /// where the next harder difficulty begins. The default is the Manhattan-scale
+    /// [`Difficulty::target_range`]; calculators that score boards differently
+    /// override it, usually with [`Difficulty::tuned_range`] and a tuning table,
+    /// so each difficulty asks for about the same disorder whichever calculator
+    /// measures it.
+    fn target_range(&self, difficulty: Difficulty, size: usize) -> RangeInclusive<u32> {
+        difficulty.target_range(size)
+    }
+}
+
+/// Shared calculators, such as those built by `EntropyKind::build`, measure as their contents do
+impl<T: EntropyCalculator + ?Sized> EntropyCalculator for Arc<T> {
+    fn calculate(&self, state: &PuzzleState) -> u32 {
+        (**self).calculate(state)
+    }
+
+    fn target_range(&self, difficulty: Difficulty, size: usize) -> RangeInclusive<u32> {
+        (**self).target_range(difficulty, size)
+    }
 }
 
+/// Difficulty thresholds as percentages of the Manhattan-scale ones
+///
+/// Rows are grid sizes 3, 4, 5, 6, and 7 or more; columns are Easy, Medium,
+/// Hard and Extra Hard. Tables are measured as the calculator's average value
+/// over Manhattan distance on boards shuffled to each difficulty.
+pub type TuningTable = [[u32; 4]; 5];
+
 /// Calculates entropy using Manhattan Distance
 /// Sum of distances from each tile's current position to its home position
 #[derive(Debug, Default)]
@@ -14,23 +49,17 @@ pub struct ManhattanDistance;
 
 impl EntropyCalculator for ManhattanDistance {
     fn calculate(&self, state: &PuzzleState) -> u32 {
-        let mut total_distance = 0u32;
-
-        for (current_pos, tile) in state.tiles() {
-            let (current_row, current_col) = current_pos;
-            let (home_row, home_col) = tile.home_position;
-
-            let distance = current_row.abs_diff(home_row) + current_col.abs_diff(home_col);
-            total_distance += distance as u32;
-        }
-
-        total_distance
+        // Kept up to date on the board as tiles move
+        state.manhattan_distance()
     }
 }
 
 /// Calculates entropy using a heuristic for shortest path length
 /// This is an approximation - actual shortest path requires A* search
-/// Uses Manhattan distance as the heuristic (admissible and consistent)
+///
+/// Manhattan distance plus two moves per conflicting pair. Counting pairs
+/// rather than the tiles that must leave each line can overestimate: seven
+/// 3×3 boards score above their true distance (see `HeuristicLab::audit`).
 #[derive(Debug, Default)]
 pub struct ShortestPathHeuristic;
 
@@ -46,71 +75,64 @@ impl EntropyCalculator for ShortestPathHeuristic {
 
         base_score + (linear_conflicts * 2)
     }
-}
-
-/// Counts linear conflicts: pairs of tiles in the same row or column
-/// that are in their target row/column but in reverse order
-pub fn count_linear_conflicts(state: &PuzzleState) -> u32 {
-    let mut conflicts = 0;
-
-    // Check row conflicts
-    for row in 0..state.size() {
-        let mut tiles_in_row = Vec::new();
-        for col in 0..state.size() {
-            if let Some(tile) = state.tile_at((row, col)) {
-                let (home_row, home_col) = tile.home_position;
-                if home_row == row {
-                    tiles_in_row.push((col, home_col));
-                }
-            }
-        }
 
-        // Count inversions in this row
-        for i in 0..tiles_in_row.len() {
-            for j in (i + 1)..tiles_in_row.len() {
-                let (curr_i, target_i) = tiles_in_row[i];
-                let (curr_j, target_j) = tiles_in_row[j];
-                if curr_i < curr_j && target_i > target_j {
-                    conflicts += 1;
-                }
-            }
-        }
+    fn target_range(&self, difficulty: Difficulty, size: usize) -> RangeInclusive<u32> {
+        difficulty.tuned_range(size, &SHORTEST_PATH_TUNING)
     }
+}
 
-    // Check column conflicts
-    for col in 0..state.size() {
-        let mut tiles_in_col = Vec::new();
-        for row in 0..state.size() {
-            if let Some(tile) = state.tile_at((row, col)) {
-                let (home_row, home_col) = tile.home_position;
-                if home_col == col {
-                    tiles_in_col.push((row, home_row));
-                }
-            }
-        }
+/// Linear conflicts are rare on lightly shuffled boards, so the tuning is slight
+const SHORTEST_PATH_TUNING: TuningTable = [
+    [100, 101, 101, 103],
+    [100, 101, 103, 102],
+    [100, 101, 103, 103],
+    [100, 101, 104, 104],
+    [100, 101, 103, 104],
+];
 
-        // Count inversions in this column
-        for i in 0..tiles_in_col.len() {
-            for j in (i + 1)..tiles_in_col.len() {
-                let (curr_i, target_i) = tiles_in_col[i];
-                let (curr_j, target_j) = tiles_in_col[j];
-                if curr_i < curr_j && target_i > target_j {
-                    conflicts += 1;
-                }
-            }
+/// Calculates entropy as Manhattan distance plus the empty cell's walk to a misplaced tile
+///
+/// A misplaced tile can only slide once the empty cell is next to it, and the
+/// steps it takes to get there move tiles that are already home. Boards whose
+/// disorder is far from the empty cell therefore score higher than Manhattan
+/// distance alone says. Every such step moves a tile that is home away from
+/// it, so the estimate never exceeds the true distance (an exhaustive audit of
+/// 3×3 boards agrees), though it gains little on Manhattan distance.
+#[derive(Debug, Default)]
+pub struct EmptyCellPath;
+
+impl EntropyCalculator for EmptyCellPath {
+    fn calculate(&self, state: &PuzzleState) -> u32 {
+        let (empty_row, empty_col) = state.empty_position();
+        let walk = state
+            .tiles()
+            .filter(|&(pos, tile)| pos != tile.home_position)
+            .map(|((row, col), _)| row.abs_diff(empty_row) + col.abs_diff(empty_col))
+            .min();
+        match walk {
+            Some(steps) => ManhattanDistance.calculate(state) + steps as u32 - 1,
+            None => 0,
         }
     }
+}
 
-    conflicts
+/// Counts linear conflicts: pairs of tiles in the same row or column
+/// that are in their target row/column but in reverse order
+///
+/// The count is kept up to date on the board as tiles move, so this is O(1).
+pub fn count_linear_conflicts(state: &PuzzleState) -> u32 {
+    state.linear_conflicts()
 }
 
 /// Difficulty levels based on entropy thresholds and shuffle move caps
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Difficulty {
     Easy,
     Medium,
     Hard,
     ExtraHard,
+    /// Shuffle until the entropy reaches a user-chosen target
+    Custom { min_entropy: u32 },
 }
 
 impl Difficulty {
@@ -123,24 +145,143 @@ impl Difficulty {
             Difficulty::Medium => scale,
             Difficulty::Hard => scale * 2,
             Difficulty::ExtraHard => scale * 3,
+            Difficulty::Custom { min_entropy } => *min_entropy,
         }
     }
 
+    /// Returns the range of Manhattan distance this difficulty aims for
+    ///
+    /// Runs from `min_entropy` up to just below the next harder preset's threshold;
+    /// Extra Hard and custom targets run up to `max_entropy`.
+    pub fn target_range(&self, grid_size: usize) -> RangeInclusive<u32> {
+        let start = self.min_entropy(grid_size);
+        let end = match self {
+            Difficulty::Easy => Difficulty::Medium.min_entropy(grid_size) - 1,
+            Difficulty::Medium => Difficulty::Hard.min_entropy(grid_size) - 1,
+            Difficulty::Hard => Difficulty::ExtraHard.min_entropy(grid_size) - 1,
+            Difficulty::ExtraHard | Difficulty::Custom { .. } => Difficulty::max_entropy(grid_size),
+        };
+        start..=end.max(start)
+    }
+
+    /// Returns `target_range` scaled by a calculator's tuning table
+    ///
+    /// Custom targets use the column of the hardest preset they reach.
+    pub fn tuned_range(&self, grid_size: usize, table: &TuningTable) -> RangeInclusive<u32> {
+        let column = match self {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => 1,
+            Difficulty::Hard => 2,
+            Difficulty::ExtraHard => 3,
+            Difficulty::Custom { min_entropy } => [Difficulty::Medium, Difficulty::Hard, Difficulty::ExtraHard]
+                .iter()
+                .take_while(|preset| preset.min_entropy(grid_size) <= *min_entropy)
+                .count(),
+        };
+        let percent = table[grid_size.clamp(3, 7) - 3][column];
+        let range = self.target_range(grid_size);
+        range.start() * percent / 100..=range.end() * percent / 100
+    }
+
     /// Returns the maximum number of shuffle moves for this difficulty
     pub fn max_shuffle_moves(&self, grid_size: usize) -> usize {
         match self {
             Difficulty::Easy => grid_size * 2,
             Difficulty::Medium => grid_size * 6,
             Difficulty::Hard => grid_size * 12,
-            Difficulty::ExtraHard => usize::MAX, // No cap for extra hard
+            // Generous enough for any reachable target; 3×3 boards can never reach theirs
+            Difficulty::ExtraHard => grid_size * grid_size * 1_000,
+            // Custom targets may be unreachable, so the walk must stop eventually
+            Difficulty::Custom { .. } => grid_size * grid_size * 16,
+        }
+    }
+
+    /// Parses a difficulty name such as `hard`, `extra-hard` or `custom:40`
+    /// Returns None for anything else; case and surrounding whitespace are ignored
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        if let Some(target) = name.strip_prefix("custom:") {
+            return target
+                .parse()
+                .ok()
+                .map(|min_entropy| Difficulty::Custom { min_entropy });
+        }
+        match name.as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            "extra-hard" | "extra_hard" | "extrahard" => Some(Difficulty::ExtraHard),
+            _ => None,
         }
     }
+
+    /// Returns an upper bound on the Manhattan distance of any board of this size
+    ///
+    /// Sums each tile's distance to the farthest corner from its home, so useful
+    /// custom targets lie at or below this value.
+    pub fn max_entropy(grid_size: usize) -> u32 {
+        let far = |i: usize| i.max(grid_size - 1 - i);
+        (0..grid_size * grid_size - 1)
+            .map(|home| (far(home / grid_size) + far(home % grid_size)) as u32)
+            .sum()
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::ExtraHard => "Extra Hard",
+            Difficulty::Custom { min_entropy } => return write!(f, "Custom ({}+)", min_entropy),
+        };
+        write!(f, "{}", label)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_difficulty_display() {
+        assert_eq!(Difficulty::Easy.to_string(), "Easy");
+        assert_eq!(Difficulty::ExtraHard.to_string(), "Extra Hard");
+        assert_eq!(Difficulty::Custom { min_entropy: 55 }.to_string(), "Custom (55+)");
+    }
+
+    #[test]
+    fn test_target_ranges() {
+        assert_eq!(Difficulty::Easy.target_range(4), 8..=15);
+        assert_eq!(Difficulty::Hard.target_range(4), 32..=47);
+        assert_eq!(Difficulty::ExtraHard.target_range(4), 48..=Difficulty::max_entropy(4));
+        // 3×3 boards never reach Extra Hard, so its range is just the threshold
+        assert_eq!(Difficulty::ExtraHard.target_range(3), 27..=27);
+
+        let doubled: TuningTable = [[200, 200, 300, 400]; 5];
+        assert_eq!(Difficulty::Easy.tuned_range(4, &doubled), 16..=30);
+        assert_eq!(Difficulty::Custom { min_entropy: 40 }.tuned_range(4, &doubled).start(), &120);
+        assert_eq!(Difficulty::Custom { min_entropy: 4 }.tuned_range(9, &doubled).start(), &8);
+        assert_eq!(ManhattanDistance.target_range(Difficulty::Medium, 5), 25..=49);
+    }
+
+    #[test]
+    fn test_custom_difficulty() {
+        let custom = Difficulty::Custom { min_entropy: 55 };
+        assert_eq!(custom.min_entropy(4), 55);
+        assert!(custom.max_shuffle_moves(4) < usize::MAX);
+        assert_eq!(Difficulty::max_entropy(3), 26);
+    }
+
+    #[test]
+    fn test_difficulty_from_name() {
+        assert_eq!(Difficulty::from_name(" Extra-Hard "), Some(Difficulty::ExtraHard));
+        assert_eq!(Difficulty::from_name("custom:40"), Some(Difficulty::Custom { min_entropy: 40 }));
+        assert_eq!(Difficulty::from_name("custom:lots"), None);
+        assert_eq!(Difficulty::from_name("impossible"), None);
+    }
+
     #[test]
     fn test_solved_puzzle_zero_entropy() {
         let puzzle = PuzzleState::new(4).unwrap();
@@ -199,4 +340,15 @@ mod tests {
         assert!(after_one > initial);
         assert!(after_two > after_one);
     }
+
+    #[test]
+    fn test_empty_cell_path_adds_the_walk() {
+        assert_eq!(EmptyCellPath.calculate(&PuzzleState::new(3).unwrap()), 0);
+
+        // Top row cycled; the empty cell is two steps from the nearest of them
+        let puzzle: PuzzleState = "2 3 1 / 4 5 6 / 7 8 _".parse().unwrap();
+        assert_eq!(ManhattanDistance.calculate(&puzzle), 4);
+        assert_eq!(EmptyCellPath.calculate(&puzzle), 5);
+        assert_eq!(EmptyCellPath.target_range(Difficulty::Medium, 4), 16..=31);
+    }
 }
@@ -0,0 +1,176 @@
+//! # Entropy Calculator Registry
+//!
+//! Names the entropy calculators a player can choose between, so the game can
+//! offer them in a list, save the choice, and build the calculator for any grid.
+//! Each calculator tunes the difficulty thresholds to its own values (see
+//! [`EntropyCalculator::target_range`]), so switching calculators keeps
+//! difficulties comparable.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{EntropyKind, HeuristicCache, PuzzleState};
+//!
+//! let kind = EntropyKind::from_name("walking-distance").unwrap();
+//! assert!(kind.supports(4) && !kind.supports(5));
+//!
+//! let calculator = kind.build(4, &HeuristicCache::disabled())?;
+//! assert_eq!(calculator.calculate(&PuzzleState::new(4)?), 0);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::enhanced_heuristic::EnhancedHeuristic;
+use super::entropy::{EmptyCellPath, EntropyCalculator, ManhattanDistance, ShortestPathHeuristic};
+use super::error::PuzzleError;
+use super::heuristic_cache::HeuristicCache;
+use super::walking_distance::{WalkingDistance, MAX_WALKING_DISTANCE_SIZE};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// One of the built-in entropy calculators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EntropyKind {
+    /// [`ManhattanDistance`]
+    #[default]
+    Manhattan,
+    /// [`ShortestPathHeuristic`]
+    ShortestPath,
+    /// [`EnhancedHeuristic`]
+    Enhanced,
+    /// [`WalkingDistance`], for grids up to 4×4
+    WalkingDistance,
+    /// [`EmptyCellPath`]
+    EmptyCellPath,
+}
+
+impl EntropyKind {
+    pub const ALL: [EntropyKind; 5] = [
+        EntropyKind::Manhattan,
+        EntropyKind::ShortestPath,
+        EntropyKind::Enhanced,
+        EntropyKind::WalkingDistance,
+        EntropyKind::EmptyCellPath,
+    ];
+
+    /// Returns whether the calculator can measure boards of this size
+    pub fn supports(self, size: usize) -> bool {
+        match self {
+            EntropyKind::WalkingDistance => size <= MAX_WALKING_DISTANCE_SIZE,
+            _ => true,
+        }
+    }
+
+    /// Builds the calculator for boards of `size`
+    ///
+    /// Walking distance tables are loaded from `cache`, or built and stored there.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::SizeTooLarge` where `supports` is false
+    pub fn build(
+        self,
+        size: usize,
+        cache: &HeuristicCache,
+    ) -> Result<Arc<dyn EntropyCalculator + Send + Sync>, PuzzleError> {
+        Ok(match self {
+            EntropyKind::Manhattan => Arc::new(ManhattanDistance),
+            EntropyKind::ShortestPath => Arc::new(ShortestPathHeuristic),
+            EntropyKind::Enhanced => Arc::new(EnhancedHeuristic),
+            EntropyKind::WalkingDistance => Arc::new(WalkingDistance::load_or_build(size, cache)?),
+            EntropyKind::EmptyCellPath => Arc::new(EmptyCellPath),
+        })
+    }
+
+    /// One-line description for tooltips
+    pub fn description(self) -> &'static str {
+        match self {
+            EntropyKind::Manhattan => "Sum of each tile's distance from home",
+            EntropyKind::ShortestPath => "Manhattan distance plus 2 per pair of tiles blocking each other in a line",
+            EntropyKind::Enhanced => "Shortest path plus penalties for misplaced corner and edge tiles",
+            EntropyKind::WalkingDistance => "Table-driven lower bound that counts tiles stepping around each other (up to 4×4)",
+            EntropyKind::EmptyCellPath => "Manhattan distance plus the empty cell's walk to the nearest misplaced tile",
+        }
+    }
+
+    /// Parses a name such as `manhattan` or `walking-distance`
+    /// Returns None for anything else; case, `_` versus `-`, and surrounding whitespace are ignored
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "manhattan" => Some(EntropyKind::Manhattan),
+            "shortest-path" => Some(EntropyKind::ShortestPath),
+            "enhanced" => Some(EntropyKind::Enhanced),
+            "walking-distance" => Some(EntropyKind::WalkingDistance),
+            "empty-cell-path" => Some(EntropyKind::EmptyCellPath),
+            _ => None,
+        }
+    }
+
+    /// Returns the name `from_name` reads
+    pub fn name(self) -> &'static str {
+        match self {
+            EntropyKind::Manhattan => "manhattan",
+            EntropyKind::ShortestPath => "shortest-path",
+            EntropyKind::Enhanced => "enhanced",
+            EntropyKind::WalkingDistance => "walking-distance",
+            EntropyKind::EmptyCellPath => "empty-cell-path",
+        }
+    }
+}
+
+impl fmt::Display for EntropyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            EntropyKind::Manhattan => "Manhattan",
+            EntropyKind::ShortestPath => "Shortest Path",
+            EntropyKind::Enhanced => "Enhanced",
+            EntropyKind::WalkingDistance => "Walking Distance",
+            EntropyKind::EmptyCellPath => "Empty Cell Path",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::ShuffleController;
+    use crate::model::{Difficulty, PuzzleState};
+
+    #[test]
+    fn test_names_round_trip() {
+        for kind in EntropyKind::ALL {
+            assert_eq!(EntropyKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(EntropyKind::from_name(" Walking_Distance "), Some(EntropyKind::WalkingDistance));
+        assert_eq!(EntropyKind::from_name("euclid"), None);
+    }
+
+    #[test]
+    fn test_build_respects_supported_sizes() {
+        let cache = HeuristicCache::disabled();
+        for kind in EntropyKind::ALL {
+            assert!(kind.build(3, &cache).is_ok(), "{} builds for 3×3", kind);
+            assert_eq!(kind.build(5, &cache).is_ok(), kind.supports(5));
+        }
+        assert!(matches!(
+            EntropyKind::WalkingDistance.build(5, &cache),
+            Err(PuzzleError::SizeTooLarge { size: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_thresholds_are_scaled_to_each_calculator() {
+        let cache = HeuristicCache::disabled();
+        let shuffler = ShuffleController::new(4).unwrap();
+        for kind in EntropyKind::ALL {
+            let calculator = kind.build(4, &cache).unwrap();
+            let mut state = PuzzleState::new(4).unwrap();
+            let result = shuffler.shuffle_seeded(&mut state, Difficulty::Medium, calculator.as_ref(), 3);
+            let target = *calculator.target_range(Difficulty::Medium, 4).start();
+            assert!(target >= Difficulty::Medium.min_entropy(4));
+            assert_eq!(result.target_met, result.final_entropy >= target, "{}", kind);
+            assert_eq!(calculator.calculate(&state), result.final_entropy);
+        }
+    }
+}
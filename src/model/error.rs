@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::Duration;
 
 /// Errors that can occur during puzzle operations
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,6 +12,30 @@ pub enum PuzzleError {
     InvalidMove { position: (usize, usize) },
     /// Tile not found at position
     TileNotFound { position: (usize, usize) },
+    /// Position lies outside the board
+    OutOfBounds { position: (usize, usize), size: usize },
+    /// Moves are refused while auto-solve plays the solution
+    AutoSolving,
+    /// Moves are refused while the next board is shuffled
+    Shuffling,
+    /// Moves are refused once a challenge has been lost
+    ChallengeLost,
+    /// Externally supplied layout is malformed
+    InvalidLayout(String),
+    /// Layout is well-formed but cannot reach the solved state
+    UnsolvableLayout,
+    /// Share code is malformed or truncated
+    InvalidShareCode(String),
+    /// Layered puzzle has no layers or too many
+    InvalidLayerCount { count: usize, max: usize },
+    /// Tile labels do not match the number of tiles on the board
+    LabelCount { expected: usize, found: usize },
+    /// Region cannot be scrambled on this board
+    InvalidRegion(String),
+    /// Recorded macro cannot be saved
+    InvalidMacro(String),
+    /// Imported solution has a move that cannot be played (steps count from 1)
+    InvalidSolution { step: usize, reason: String },
 }
 
 impl fmt::Display for PuzzleError {
@@ -28,6 +53,46 @@ impl fmt::Display for PuzzleError {
             PuzzleError::TileNotFound { position } => {
                 write!(f, "No tile found at position ({}, {})", position.0, position.1)
             }
+            PuzzleError::OutOfBounds { position, size } => {
+                write!(
+                    f,
+                    "Position ({}, {}) is outside the {}×{} board",
+                    position.0, position.1, size, size
+                )
+            }
+            PuzzleError::AutoSolving => {
+                write!(f, "Moves are locked while auto-solve runs")
+            }
+            PuzzleError::Shuffling => {
+                write!(f, "Moves are locked while the next board is shuffled")
+            }
+            PuzzleError::ChallengeLost => {
+                write!(f, "Moves are locked once the challenge is lost; start a new game")
+            }
+            PuzzleError::InvalidLayout(msg) => {
+                write!(f, "Invalid layout: {}", msg)
+            }
+            PuzzleError::UnsolvableLayout => {
+                write!(f, "Layout is not solvable")
+            }
+            PuzzleError::InvalidShareCode(msg) => {
+                write!(f, "Invalid share code: {}", msg)
+            }
+            PuzzleError::InvalidLayerCount { count, max } => {
+                write!(f, "Layer count {} is invalid (1 to {})", count, max)
+            }
+            PuzzleError::LabelCount { expected, found } => {
+                write!(f, "Board needs {} tile labels, got {}", expected, found)
+            }
+            PuzzleError::InvalidRegion(msg) => {
+                write!(f, "Invalid region: {}", msg)
+            }
+            PuzzleError::InvalidMacro(msg) => {
+                write!(f, "Cannot save macro: {}", msg)
+            }
+            PuzzleError::InvalidSolution { step, reason } => {
+                write!(f, "Solution move {}: {}", step, reason)
+            }
         }
     }
 }
@@ -41,6 +106,14 @@ pub enum SolverError {
     Unsolvable,
     /// Maximum iterations exceeded
     TimeoutExceeded { max_iterations: usize },
+    /// Wall-clock time limit exceeded
+    TimeLimitExceeded { timeout: Duration },
+    /// Search bookkeeping outgrew the memory budget
+    MemoryExceeded { limit_bytes: usize },
+    /// Search was cancelled by the caller
+    Cancelled,
+    /// Solver stopped unexpectedly (a background solve panicked)
+    Crashed,
     /// Invalid puzzle state
     InvalidState(String),
 }
@@ -58,6 +131,22 @@ impl fmt::Display for SolverError {
                     max_iterations
                 )
             }
+            SolverError::TimeLimitExceeded { timeout } => {
+                write!(f, "Solver timeout: exceeded {:.1}s", timeout.as_secs_f64())
+            }
+            SolverError::MemoryExceeded { limit_bytes } => {
+                write!(
+                    f,
+                    "Solver ran out of memory: exceeded the {} MB budget",
+                    limit_bytes / (1024 * 1024)
+                )
+            }
+            SolverError::Cancelled => {
+                write!(f, "Solver was cancelled")
+            }
+            SolverError::Crashed => {
+                write!(f, "Solver stopped unexpectedly")
+            }
             SolverError::InvalidState(msg) => {
                 write!(f, "Invalid puzzle state: {}", msg)
             }
@@ -121,6 +210,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_layout_error_display() {
+        let err = PuzzleError::InvalidLayout("duplicate tile 3".to_string());
+        assert_eq!(err.to_string(), "Invalid layout: duplicate tile 3");
+        assert_eq!(
+            PuzzleError::UnsolvableLayout.to_string(),
+            "Layout is not solvable"
+        );
+        assert_eq!(
+            PuzzleError::InvalidShareCode("too short".to_string()).to_string(),
+            "Invalid share code: too short"
+        );
+        assert_eq!(
+            PuzzleError::InvalidLayerCount { count: 0, max: 9 }.to_string(),
+            "Layer count 0 is invalid (1 to 9)"
+        );
+    }
+
+    #[test]
+    fn test_move_error_display() {
+        let err = PuzzleError::OutOfBounds {
+            position: (4, 0),
+            size: 4,
+        };
+        assert_eq!(err.to_string(), "Position (4, 0) is outside the 4×4 board");
+        assert_eq!(
+            PuzzleError::AutoSolving.to_string(),
+            "Moves are locked while auto-solve runs"
+        );
+        let err = PuzzleError::InvalidSolution {
+            step: 3,
+            reason: "U moves the empty cell off the board".to_string(),
+        };
+        assert_eq!(err.to_string(), "Solution move 3: U moves the empty cell off the board");
+    }
+
     #[test]
     fn test_solver_error_display() {
         let err = SolverError::TimeoutExceeded {
@@ -130,6 +255,14 @@ mod tests {
             err.to_string(),
             "Solver timeout: exceeded 500000 iterations"
         );
+
+        let err = SolverError::MemoryExceeded {
+            limit_bytes: 256 * 1024 * 1024,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Solver ran out of memory: exceeded the 256 MB budget"
+        );
     }
 
     #[test]
@@ -0,0 +1,157 @@
+//! # Goal Configurations
+//!
+//! A [`GoalSpec`] says where each numbered tile and the empty cell belong in the
+//! solved board. The goal is recorded in each tile's home position, so solved
+//! checks, heuristics and solvers that measure against home positions follow
+//! whichever goal the board was built with.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{GoalSpec, PuzzleState};
+//!
+//! let puzzle = PuzzleState::with_goal(3, GoalSpec::Spiral)?;
+//! assert_eq!(puzzle.to_notation(), "1 2 3 / 8 _ 4 / 7 6 5");
+//! assert_eq!(puzzle.empty_position(), (1, 1));
+//! assert!(puzzle.is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::move_validator::Position;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where the tiles and the empty cell sit in the solved board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum GoalSpec {
+    /// Tiles in row-major order with the empty cell bottom-right
+    #[default]
+    Standard,
+    /// Empty cell top-left, then tiles in row-major order
+    BlankFirst,
+    /// Tiles in row-major order from the bottom-right corner backwards, empty cell top-left
+    Reversed,
+    /// Tiles spiral clockwise inward from the top-left corner, ending with the empty cell
+    Spiral,
+}
+
+impl GoalSpec {
+    /// Every goal, in display order
+    pub const ALL: [GoalSpec; 4] = [
+        GoalSpec::Standard,
+        GoalSpec::BlankFirst,
+        GoalSpec::Reversed,
+        GoalSpec::Spiral,
+    ];
+
+    /// Returns the goal cell of each tile in number order, followed by the empty cell's
+    pub fn cells(self, size: usize) -> Vec<Position> {
+        let row_major = (0..size * size).map(|i| (i / size, i % size));
+        match self {
+            GoalSpec::Standard => row_major.collect(),
+            GoalSpec::BlankFirst => row_major.cycle().skip(1).take(size * size).collect(),
+            GoalSpec::Reversed => row_major.rev().collect(),
+            GoalSpec::Spiral => spiral(size),
+        }
+    }
+
+    /// Returns the tile number for each cell in row-major order (0 for the empty cell)
+    pub fn numbering(self, size: usize) -> Vec<u32> {
+        let mut numbers = vec![0; size * size];
+        for (index, (row, col)) in self
+            .cells(size)
+            .into_iter()
+            .enumerate()
+            .take(size * size - 1)
+        {
+            numbers[row * size + col] = index as u32 + 1;
+        }
+        numbers
+    }
+
+    /// Returns where the empty cell belongs
+    pub fn empty_position(self, size: usize) -> Position {
+        match self {
+            GoalSpec::Standard => (size - 1, size - 1),
+            GoalSpec::BlankFirst | GoalSpec::Reversed => (0, 0),
+            GoalSpec::Spiral => spiral(size)[size * size - 1],
+        }
+    }
+}
+
+impl fmt::Display for GoalSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalSpec::Standard => write!(f, "Standard"),
+            GoalSpec::BlankFirst => write!(f, "Blank first"),
+            GoalSpec::Reversed => write!(f, "Reversed"),
+            GoalSpec::Spiral => write!(f, "Spiral"),
+        }
+    }
+}
+
+/// Visits every cell clockwise from the top-left corner, spiralling inward
+fn spiral(size: usize) -> Vec<Position> {
+    let mut cells = Vec::with_capacity(size * size);
+    let (mut top, mut left) = (0, 0);
+    let (mut bottom, mut right) = (size - 1, size - 1);
+    while cells.len() < size * size {
+        cells.extend((left..=right).map(|col| (top, col)));
+        cells.extend((top + 1..=bottom).map(|row| (row, right)));
+        if top < bottom {
+            cells.extend((left..right).rev().map(|col| (bottom, col)));
+        }
+        if left < right {
+            cells.extend((top + 1..bottom).rev().map(|row| (row, left)));
+        }
+        top += 1;
+        left += 1;
+        bottom = bottom.saturating_sub(1);
+        right = right.saturating_sub(1);
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cells_cover_the_grid() {
+        for size in 3..=6 {
+            for goal in GoalSpec::ALL {
+                let mut cells = goal.cells(size);
+                assert_eq!(
+                    cells[size * size - 1],
+                    goal.empty_position(size),
+                    "{}",
+                    goal
+                );
+                cells.sort_unstable();
+                cells.dedup();
+                assert_eq!(cells.len(), size * size, "{} on {}×{}", goal, size, size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_numbering() {
+        assert_eq!(
+            GoalSpec::Standard.numbering(3),
+            vec![1, 2, 3, 4, 5, 6, 7, 8, 0]
+        );
+        assert_eq!(
+            GoalSpec::BlankFirst.numbering(3),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8]
+        );
+        assert_eq!(
+            GoalSpec::Reversed.numbering(3),
+            vec![0, 8, 7, 6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            GoalSpec::Spiral.numbering(4),
+            vec![1, 2, 3, 4, 12, 13, 14, 5, 11, 0, 15, 6, 10, 9, 8, 7]
+        );
+        assert_eq!(GoalSpec::Spiral.empty_position(4), (2, 1));
+    }
+}
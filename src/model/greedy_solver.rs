@@ -0,0 +1,241 @@
+//! # Greedy Best-First Solver
+//!
+//! Always expands the state with the lowest heuristic estimate, ignoring how many
+//! moves it took to get there. Solutions are usually far longer than optimal but are
+//! found orders of magnitude faster than with [`AStarSolver`], which makes this solver
+//! useful when any solution will do.
+//!
+//! [`AStarSolver`]: super::solver::AStarSolver
+
+use super::enhanced_heuristic::EnhancedHeuristic;
+use super::entropy::EntropyCalculator;
+use super::error::SolverError;
+use super::move_system::MoveSystem;
+use super::move_validator::Position;
+use super::node_arena::{NodeArena, NodeId};
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use super::solver::{state_hash, Solution, SolverConfig, SolverProgress, PROGRESS_INTERVAL};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Greedy best-first solver guided by Enhanced Heuristic (non-optimal)
+pub struct GreedySolver {
+    heuristic: EnhancedHeuristic,
+    config: SolverConfig,
+}
+
+impl GreedySolver {
+    pub fn new() -> Self {
+        Self::with_config(SolverConfig::default())
+    }
+
+    /// Creates a solver with custom search limits
+    pub fn with_config(config: SolverConfig) -> Self {
+        Self {
+            heuristic: EnhancedHeuristic,
+            config,
+        }
+    }
+
+    /// Returns the search limits used by this solver
+    pub fn config(&self) -> SolverConfig {
+        self.config
+    }
+
+    /// Returns a (not necessarily shortest) solution path
+    /// Returns None if unsolvable or timeout
+    pub fn solve_with_path(&self, initial_state: &PuzzleState) -> Option<Vec<Position>> {
+        self.solve_with_path_cancellable(initial_state, None)
+    }
+
+    /// Returns a solution path with support for cancellation
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_cancellable(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+    ) -> Option<Vec<Position>> {
+        self.solve_with_path_reporting(initial_state, cancel_flag, &mut |_| {})
+    }
+
+    /// Returns a solution path, invoking `on_progress` every `PROGRESS_INTERVAL`
+    /// expansions; `current_depth` is the heuristic value being expanded
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_reporting(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Vec<Position>> {
+        self.solve_instrumented(initial_state, cancel_flag, on_progress)
+            .map(|solution| solution.path)
+    }
+
+    /// Returns a solution with node counts and timing, reporting progress like
+    /// `solve_with_path_reporting`
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.try_solve_instrumented(initial_state, cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Returns a solution like `solve_instrumented`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why the search stopped: a search limit
+    /// from the config, cancellation, or an exhausted search space
+    pub fn try_solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        let started = Instant::now();
+        if initial_state.is_solved() {
+            return Ok(Solution {
+                start_empty: initial_state.empty_position(),
+                ..Solution::default()
+            });
+        }
+
+        // Min-heap on (heuristic, insertion order) so ties expand oldest first
+        let mut open_set = BinaryHeap::new();
+        let mut visited = HashSet::new();
+        let initial_packed = PackedState::from_state(initial_state);
+        visited.insert(state_hash(&initial_packed));
+        // A node's move is the tile that slid out of its empty cell, so nodes need no data
+        let mut nodes = NodeArena::new(initial_state.size());
+        let root = nodes.push(&initial_packed, None, ());
+        open_set.push(Reverse((self.heuristic.calculate(initial_state), root)));
+
+        let mut iterations = 0;
+        let mut max_open_size = open_set.len();
+
+        while let Some(Reverse((h_score, current_id))) = open_set.pop() {
+            iterations += 1;
+            let current_state = nodes.state(current_id);
+
+            if iterations % PROGRESS_INTERVAL == 0 {
+                if let Some(ref cancel) = cancel_flag {
+                    if cancel.load(AtomicOrdering::Relaxed) {
+                        return Err(SolverError::Cancelled);
+                    }
+                }
+                if started.elapsed() >= self.config.timeout {
+                    return Err(SolverError::TimeLimitExceeded {
+                        timeout: self.config.timeout,
+                    });
+                }
+                on_progress(SolverProgress {
+                    nodes_explored: iterations,
+                    current_depth: h_score,
+                    elapsed: started.elapsed(),
+                    frontier_size: open_set.len(),
+                    expanded: Some(current_state.clone()),
+                });
+            }
+
+            if iterations > self.config.max_iterations {
+                return Err(SolverError::TimeoutExceeded {
+                    max_iterations: self.config.max_iterations,
+                });
+            }
+
+            if current_state.is_solved() {
+                return Ok(Solution {
+                    path: reconstruct_path(&nodes, current_id),
+                    nodes_expanded: iterations - 1, // The goal itself is not expanded
+                    nodes_generated: nodes.len() - 1,
+                    nodes_pruned: 0,
+                    max_open_size,
+                    duration: started.elapsed(),
+                    peak_memory_bytes: 0,
+                    start_empty: initial_state.empty_position(),
+                    annotations: Vec::new(),
+                });
+            }
+
+            for next_pos in current_state.legal_moves() {
+                let mut next_state = current_state.clone();
+                if !next_state.apply(next_pos) {
+                    continue;
+                }
+                if !visited.insert(state_hash(&next_state)) {
+                    continue; // Already queued or expanded
+                }
+
+                let next_h = self.heuristic.calculate(&next_state.to_state());
+                let next_id = nodes.push(&next_state, Some(current_id), ());
+                open_set.push(Reverse((next_h, next_id)));
+            }
+            max_open_size = max_open_size.max(open_set.len());
+        }
+
+        Err(SolverError::Unsolvable) // Search space exhausted
+    }
+}
+
+impl Default for GreedySolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reconstructs the solution path by following parent indices
+fn reconstruct_path(nodes: &NodeArena<()>, goal_id: NodeId) -> Vec<Position> {
+    let mut path = Vec::new();
+    let mut current_id = goal_id;
+
+    // The tile each move slid left its cell empty
+    while let Some(parent_id) = nodes.parent(current_id) {
+        path.push(nodes.state(current_id).empty_position());
+        current_id = parent_id;
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::scramble;
+
+    #[test]
+    fn test_solved_puzzle() {
+        let puzzle = PuzzleState::new(3).unwrap();
+        assert_eq!(GreedySolver::new().solve_with_path(&puzzle), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_finds_valid_solution() {
+        for (size, seed) in [(3, 1), (4, 2), (5, 3)] {
+            let mut puzzle = scramble(size, 60, seed);
+            let start = puzzle.clone();
+            let path = GreedySolver::new().solve_with_path(&start).unwrap();
+            for pos in path {
+                assert!(puzzle.apply_immediate_move(pos));
+            }
+            assert!(puzzle.is_solved(), "{}x{} not solved", size, size);
+        }
+    }
+
+    #[test]
+    fn test_respects_iteration_limit() {
+        let solver = GreedySolver::with_config(SolverConfig {
+            max_iterations: 1,
+            ..SolverConfig::default()
+        });
+        assert_eq!(solver.solve_with_path(&scramble(4, 40, 7)), None);
+    }
+}
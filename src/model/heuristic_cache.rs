@@ -0,0 +1,298 @@
+//! # Heuristic Table Cache
+//!
+//! Table-driven heuristics such as [`WalkingDistance`] spend most of their
+//! construction time enumerating lookup tables that never change between runs.
+//! [`HeuristicCache`] stores those tables as files in a per-user cache directory so
+//! later constructions only pay for a file read.
+//!
+//! ## Invalidation
+//!
+//! Every file starts with a header holding the table's [`CachedTable::VERSION`].
+//! Bumping the version when a table's contents or encoding change makes every
+//! existing file stale: stale, corrupt, or unreadable files are rebuilt and
+//! overwritten transparently. [`HeuristicCache::clear`] removes everything, which
+//! is what the `--rebuild-cache` command-line flag does.
+//!
+//! [`WalkingDistance`]: super::walking_distance::WalkingDistance
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{HeuristicCache, WalkingDistance};
+//!
+//! let dir = std::env::temp_dir().join("slider-doc-cache");
+//! let cache = HeuristicCache::new(&dir);
+//! let heuristic = WalkingDistance::load_or_build(4, &cache)?;
+//! assert_eq!(heuristic.size(), 4);
+//! # let _ = cache.clear();
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the cache directory
+pub const CACHE_DIR_ENV: &str = "SLIDER_CACHE_DIR";
+
+/// Magic bytes at the start of every cache file
+const CACHE_MAGIC: &[u8; 4] = b"SLHC";
+
+/// Extension of cache files, so `clear` never removes anything else
+const CACHE_EXTENSION: &str = "bin";
+
+/// A lookup table that can be stored in a [`HeuristicCache`]
+pub trait CachedTable: Sized {
+    /// Short file-name-safe identifier for this kind of table
+    const KIND: &'static str;
+
+    /// Bumped whenever the table's contents or encoding change
+    const VERSION: u32;
+
+    /// Serializes the table
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Restores a table written by `to_bytes`
+    /// Returns None if the bytes are not a valid table
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Directory of cached heuristic tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeuristicCache {
+    dir: Option<PathBuf>, // None disables caching
+}
+
+impl HeuristicCache {
+    /// Creates a cache that stores tables in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Creates a cache in the per-user cache directory (see [`cache_dir`])
+    /// Caching is disabled if no such directory can be determined
+    pub fn user() -> Self {
+        Self { dir: cache_dir() }
+    }
+
+    /// Creates a cache that never reads or writes files
+    pub fn disabled() -> Self {
+        Self { dir: None }
+    }
+
+    /// Returns the cache directory, or None if caching is disabled
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns the file that holds table `name` of kind `T`
+    pub fn path_for<T: CachedTable>(&self, name: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}-{}.{}", T::KIND, name, CACHE_EXTENSION)))
+    }
+
+    /// Loads table `name`, or None if it is missing, stale, or corrupt
+    pub fn load<T: CachedTable>(&self, name: &str) -> Option<T> {
+        let bytes = fs::read(self.path_for::<T>(name)?).ok()?;
+        let (header, body) = bytes.split_at_checked(CACHE_MAGIC.len() + 4)?;
+        let (magic, version) = header.split_at(CACHE_MAGIC.len());
+        if magic != CACHE_MAGIC || version != T::VERSION.to_le_bytes() {
+            return None; // Written by another version
+        }
+        T::from_bytes(body)
+    }
+
+    /// Stores table `name`, replacing any previous file
+    ///
+    /// Does nothing if caching is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn store<T: CachedTable>(&self, name: &str, table: &T) -> io::Result<()> {
+        let Some(path) = self.path_for::<T>(name) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut bytes = CACHE_MAGIC.to_vec();
+        bytes.extend_from_slice(&T::VERSION.to_le_bytes());
+        bytes.extend_from_slice(&table.to_bytes());
+
+        // Write then rename so a crash mid-write never leaves a truncated table
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Loads table `name`, or builds it with `build` and stores it
+    ///
+    /// A table that cannot be stored still gets returned; the failure is logged
+    /// and only costs a rebuild next time.
+    pub fn load_or_build<T: CachedTable>(&self, name: &str, build: impl FnOnce() -> T) -> T {
+        if let Some(table) = self.load(name) {
+            return table;
+        }
+
+        let table = build();
+        if let Err(e) = self.store(name, &table) {
+            log::warn!("Failed to cache {} table {}: {}", T::KIND, name, e);
+        }
+        table
+    }
+
+    /// Removes every cached table; returns how many files were removed
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the directory cannot be read or a file
+    /// cannot be removed. A missing directory counts as already clear.
+    pub fn clear(&self) -> io::Result<usize> {
+        let Some(dir) = &self.dir else {
+            return Ok(0);
+        };
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == CACHE_EXTENSION) {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl Default for HeuristicCache {
+    fn default() -> Self {
+        Self::user()
+    }
+}
+
+/// Returns the directory used for cached heuristic tables
+///
+/// Honors `SLIDER_CACHE_DIR`, then `XDG_CACHE_HOME/slider`, then
+/// `~/.cache/slider` (`%LOCALAPPDATA%\slider\cache` on Windows).
+/// Returns None if no home directory can be determined.
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(CACHE_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("slider"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = std::env::var_os("LOCALAPPDATA") {
+            return Some(PathBuf::from(dir).join("slider").join("cache"));
+        }
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("slider"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    struct Squares(Vec<u8>);
+
+    impl CachedTable for Squares {
+        const KIND: &'static str = "squares";
+        const VERSION: u32 = 2;
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Option<Self> {
+            Some(Self(bytes.to_vec()))
+        }
+    }
+
+    fn temp_cache(name: &str) -> HeuristicCache {
+        let dir = std::env::temp_dir().join(format!("slider-cache-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        HeuristicCache::new(dir)
+    }
+
+    fn build_counting(builds: &Cell<u32>) -> Squares {
+        builds.set(builds.get() + 1);
+        Squares((0..10).map(|i| i * i).collect())
+    }
+
+    #[test]
+    fn test_builds_once() {
+        let cache = temp_cache("once");
+        let builds = Cell::new(0);
+
+        let first = cache.load_or_build("a", || build_counting(&builds));
+        let second = cache.load_or_build("a", || build_counting(&builds));
+        assert_eq!(first, second);
+        assert_eq!(builds.get(), 1);
+        assert!(cache.path_for::<Squares>("a").unwrap().exists());
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_stale_version_rebuilt() {
+        let cache = temp_cache("stale");
+        let path = cache.path_for::<Squares>("a").unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let mut stale = CACHE_MAGIC.to_vec();
+        stale.extend_from_slice(&1u32.to_le_bytes());
+        stale.extend_from_slice(&[9, 9, 9]);
+        fs::write(&path, stale).unwrap();
+        assert_eq!(cache.load::<Squares>("a"), None);
+
+        let builds = Cell::new(0);
+        cache.load_or_build("a", || build_counting(&builds));
+        assert_eq!(builds.get(), 1);
+        assert!(cache.load::<Squares>("a").is_some());
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_corrupt_file_ignored() {
+        let cache = temp_cache("corrupt");
+        let path = cache.path_for::<Squares>("a").unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"SL").unwrap();
+        assert_eq!(cache.load::<Squares>("a"), None);
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_clear_removes_tables() {
+        let cache = temp_cache("clear");
+        cache.store("a", &Squares(vec![1])).unwrap();
+        cache.store("b", &Squares(vec![2])).unwrap();
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert_eq!(cache.load::<Squares>("a"), None);
+        assert_eq!(cache.clear().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_disabled_cache_always_builds() {
+        let cache = HeuristicCache::disabled();
+        let builds = Cell::new(0);
+        cache.load_or_build("a", || build_counting(&builds));
+        cache.load_or_build("a", || build_counting(&builds));
+        assert_eq!(builds.get(), 2);
+        assert_eq!(cache.path_for::<Squares>("a"), None);
+        assert_eq!(cache.clear().unwrap(), 0);
+    }
+}
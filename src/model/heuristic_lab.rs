@@ -0,0 +1,481 @@
+//! # Heuristic Lab
+//!
+//! Side-by-side evaluation of entropy calculators as search heuristics. Each
+//! registered calculator is scored on a shared set of boards against the true
+//! optimal solution length:
+//!
+//! - **Admissibility violations**: boards where the estimate exceeds the optimum,
+//!   which breaks A\*'s optimality guarantee
+//! - **Mean error**: how far below the optimum the estimate sits on average
+//! - **Nodes expanded**: the work a plain A\* guided by the calculator does
+//!
+//! On 3×3 boards, [`HeuristicLab::audit`] goes further: a breadth-first search
+//! from the goal finds the true distance of all 181,440 reachable boards, and
+//! every calculator is checked against each of them, so an admissible verdict
+//! is a proof rather than a sample. [`HeuristicLab::builtin`] registers every
+//! [`EntropyKind`] for a quick audit of the calculators the game ships with.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{HeuristicLab, ManhattanDistance, ShortestPathHeuristic};
+//!
+//! let mut lab = HeuristicLab::new();
+//! lab.register("manhattan", ManhattanDistance);
+//! lab.register("linear conflict", ShortestPathHeuristic);
+//!
+//! let report = lab.run(&HeuristicLab::random_walk_states(3, 10, 20, 7));
+//! for result in &report.heuristics {
+//!     assert_eq!(result.admissibility_violations, 0);
+//! }
+//! ```
+//!
+//! [`EntropyKind`]: super::entropy_kind::EntropyKind
+
+use super::entropy::EntropyCalculator;
+use super::entropy_kind::EntropyKind;
+use super::error::PuzzleError;
+use super::heuristic_cache::HeuristicCache;
+use super::ida_star_solver::IDAStarSolver;
+use super::move_system::MoveSystem;
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use super::solver::SolverConfig;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Node expansions allowed per guided search before it is counted as unsolved
+pub const DEFAULT_LAB_NODE_LIMIT: usize = 200_000;
+
+/// Largest board an audit enumerates; 4×4 boards have over 10^13 reachable layouts
+pub const MAX_AUDIT_SIZE: usize = 3;
+
+/// Scores for one calculator over every board with a known optimum
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeuristicReport {
+    pub name: String,
+    /// Boards where the estimate exceeded the optimal solution length
+    pub admissibility_violations: usize,
+    /// Largest amount by which the estimate exceeded the optimum (0 if admissible)
+    pub worst_overestimate: u32,
+    /// Mean of `optimum - estimate`; negative when the calculator overestimates
+    pub mean_error: f64,
+    /// Mean nodes expanded by guided searches that finished
+    pub mean_nodes_expanded: f64,
+    /// Guided searches that hit the node limit
+    pub failed_searches: usize,
+}
+
+/// Results of a lab run
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabReport {
+    /// Boards with a known optimum, which every score is based on
+    pub states: usize,
+    /// Boards skipped because the reference solver gave up
+    pub unknown_optimum: usize,
+    pub heuristics: Vec<HeuristicReport>,
+}
+
+/// One calculator checked against the true distance of every reachable board
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditReport {
+    pub name: String,
+    /// Boards where the estimate exceeded the true distance
+    pub admissibility_violations: usize,
+    /// Largest amount by which the estimate exceeded the true distance (0 if admissible)
+    pub worst_overestimate: u32,
+    /// A board overestimated by `worst_overestimate`, if any was
+    pub counterexample: Option<PackedState>,
+    /// Mean of `distance - estimate`; negative when the calculator overestimates
+    pub mean_error: f64,
+    /// Largest amount by which the estimate fell short of the true distance
+    pub worst_underestimate: u32,
+}
+
+impl AuditReport {
+    pub fn is_admissible(&self) -> bool {
+        self.admissibility_violations == 0
+    }
+}
+
+/// Results of an exhaustive audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditSummary {
+    /// Reachable boards, every one of which was checked
+    pub states: usize,
+    /// Longest optimal solution among them (31 moves on 3×3)
+    pub diameter: u32,
+    pub heuristics: Vec<AuditReport>,
+}
+
+/// Registry of calculators to compare on the same boards
+pub struct HeuristicLab {
+    calculators: Vec<(String, Box<dyn EntropyCalculator>)>,
+    node_limit: usize,
+    reference: SolverConfig,
+}
+
+impl HeuristicLab {
+    pub fn new() -> Self {
+        Self {
+            calculators: Vec::new(),
+            node_limit: DEFAULT_LAB_NODE_LIMIT,
+            reference: SolverConfig::default(),
+        }
+    }
+
+    /// Creates a lab with every built-in calculator that supports boards of `size`,
+    /// each registered under its `EntropyKind::name`
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of any calculator that fails to build (walking
+    /// distance tables are loaded from `cache`, or built there)
+    pub fn builtin(size: usize, cache: &HeuristicCache) -> Result<Self, PuzzleError> {
+        let mut lab = Self::new();
+        for kind in EntropyKind::ALL.into_iter().filter(|kind| kind.supports(size)) {
+            lab.register(kind.name(), kind.build(size, cache)?);
+        }
+        Ok(lab)
+    }
+
+    /// Adds a calculator under `name`
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        calculator: impl EntropyCalculator + 'static,
+    ) -> &mut Self {
+        self.calculators.push((name.into(), Box::new(calculator)));
+        self
+    }
+
+    /// Sets the node expansions allowed per guided search
+    pub fn set_node_limit(&mut self, node_limit: usize) -> &mut Self {
+        self.node_limit = node_limit;
+        self
+    }
+
+    /// Sets the limits of the IDA\* reference solve that finds each board's optimum
+    pub fn set_reference_config(&mut self, config: SolverConfig) -> &mut Self {
+        self.reference = config;
+        self
+    }
+
+    /// Scores every registered calculator on `states`
+    pub fn run(&self, states: &[PuzzleState]) -> LabReport {
+        // IDA* with Manhattan distance is admissible, so its lengths are true optima
+        let reference = IDAStarSolver::with_config(self.reference);
+        let known: Vec<(&PuzzleState, u32)> = states
+            .iter()
+            .filter_map(|state| reference.solve(state).map(|optimum| (state, optimum)))
+            .collect();
+        log::info!(
+            "Heuristic lab: {} calculators on {} of {} boards",
+            self.calculators.len(),
+            known.len(),
+            states.len()
+        );
+
+        let heuristics = self
+            .calculators
+            .iter()
+            .map(|(name, calculator)| self.score(name, calculator.as_ref(), &known))
+            .collect();
+        LabReport {
+            states: known.len(),
+            unknown_optimum: states.len() - known.len(),
+            heuristics,
+        }
+    }
+
+    fn score(
+        &self,
+        name: &str,
+        calculator: &dyn EntropyCalculator,
+        known: &[(&PuzzleState, u32)],
+    ) -> HeuristicReport {
+        let mut violations = 0;
+        let mut worst_overestimate = 0;
+        let mut total_error = 0i64;
+        let mut total_nodes = 0;
+        let mut finished = 0;
+
+        for &(state, optimum) in known {
+            let estimate = calculator.calculate(state);
+            if estimate > optimum {
+                violations += 1;
+                worst_overestimate = worst_overestimate.max(estimate - optimum);
+            }
+            total_error += i64::from(optimum) - i64::from(estimate);
+
+            if let Some(nodes) = guided_search(calculator, state, self.node_limit) {
+                total_nodes += nodes;
+                finished += 1;
+            }
+        }
+        log::debug!("{}: {} admissibility violations", name, violations);
+
+        let mean = |total: f64, count: usize| {
+            if count == 0 {
+                0.0
+            } else {
+                total / count as f64
+            }
+        };
+        HeuristicReport {
+            name: name.to_string(),
+            admissibility_violations: violations,
+            worst_overestimate,
+            mean_error: mean(total_error as f64, known.len()),
+            mean_nodes_expanded: mean(total_nodes as f64, finished),
+            failed_searches: known.len() - finished,
+        }
+    }
+
+    /// Checks every registered calculator against the true distance of every
+    /// board of `size` that can reach the standard goal
+    /// Returns None if `size` is invalid or above `MAX_AUDIT_SIZE`
+    ///
+    /// Takes a few seconds on 3×3 in release builds.
+    pub fn audit(&self, size: usize) -> Option<AuditSummary> {
+        if size > MAX_AUDIT_SIZE {
+            return None;
+        }
+        let distances = optimal_distances(&PuzzleState::new(size).ok()?);
+        log::info!(
+            "Heuristic audit: {} calculators on all {} boards of {}×{}",
+            self.calculators.len(),
+            distances.len(),
+            size,
+            size
+        );
+
+        let mut heuristics: Vec<AuditReport> = self
+            .calculators
+            .iter()
+            .map(|(name, _)| AuditReport {
+                name: name.clone(),
+                admissibility_violations: 0,
+                worst_overestimate: 0,
+                counterexample: None,
+                mean_error: 0.0,
+                worst_underestimate: 0,
+            })
+            .collect();
+        let mut total_errors = vec![0i64; heuristics.len()];
+        for (packed, &distance) in &distances {
+            let state = packed.to_state();
+            for (index, (_, calculator)) in self.calculators.iter().enumerate() {
+                let estimate = calculator.calculate(&state);
+                let report = &mut heuristics[index];
+                if estimate > distance {
+                    report.admissibility_violations += 1;
+                    if estimate - distance > report.worst_overestimate {
+                        report.worst_overestimate = estimate - distance;
+                        report.counterexample = Some(packed.clone());
+                    }
+                } else {
+                    report.worst_underestimate = report.worst_underestimate.max(distance - estimate);
+                }
+                total_errors[index] += i64::from(distance) - i64::from(estimate);
+            }
+        }
+        for (report, total) in heuristics.iter_mut().zip(total_errors) {
+            report.mean_error = total as f64 / distances.len() as f64;
+            log::debug!("{}: {} admissibility violations", report.name, report.admissibility_violations);
+        }
+
+        Some(AuditSummary {
+            states: distances.len(),
+            diameter: distances.values().copied().max().unwrap_or(0),
+            heuristics,
+        })
+    }
+
+    /// Generates `count` boards by seeded random walks of `steps` moves from solved
+    ///
+    /// Walks never undo their previous move, so short walks still reach distinct boards.
+    pub fn random_walk_states(
+        size: usize,
+        count: usize,
+        steps: usize,
+        seed: u64,
+    ) -> Vec<PuzzleState> {
+        let Ok(solved) = PuzzleState::new(size) else {
+            return Vec::new();
+        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| {
+                let mut state = solved.clone();
+                state.random_walk(steps, &mut rng);
+                state
+            })
+            .collect()
+    }
+}
+
+impl Default for HeuristicLab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the optimal solution length of every board that can reach `goal`
+///
+/// Breadth-first search out from the goal; moves are their own inverses, so the
+/// depth at which a board is first reached is its distance. Memory grows with
+/// the number of reachable boards, which rules out anything above 3×3.
+pub fn optimal_distances(goal: &PuzzleState) -> HashMap<PackedState, u32> {
+    let start = PackedState::from_state(goal);
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(board) = queue.pop_front() {
+        let distance = distances[&board];
+        for from in board.legal_moves() {
+            let mut next = board.clone();
+            next.apply(from);
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Runs A\* guided by `calculator` and returns the nodes expanded to reach the goal
+/// Returns None if the goal was not reached within `node_limit` expansions
+///
+/// Nodes are reopened when a cheaper path turns up, so inadmissible or
+/// inconsistent calculators still terminate (though not necessarily optimally).
+fn guided_search(
+    calculator: &dyn EntropyCalculator,
+    start: &PuzzleState,
+    node_limit: usize,
+) -> Option<usize> {
+    let mut best_g: HashMap<PackedState, u32> = HashMap::new();
+    let mut nodes = vec![PackedState::from_state(start)]; // Heap entries index into this
+    let mut open = BinaryHeap::new();
+
+    best_g.insert(nodes[0].clone(), 0);
+    open.push((Reverse(calculator.calculate(start)), Reverse(0u32), 0));
+
+    let mut expanded = 0;
+    while let Some((_, Reverse(g), index)) = open.pop() {
+        let node = nodes[index].clone();
+        if best_g.get(&node).is_some_and(|&best| best < g) {
+            continue; // Stale entry; a cheaper copy was pushed later
+        }
+        if node.is_solved() {
+            return Some(expanded);
+        }
+        if expanded >= node_limit {
+            return None;
+        }
+        expanded += 1;
+
+        for from in node.legal_moves() {
+            let mut next = node.clone();
+            next.apply(from);
+            if best_g.get(&next).is_some_and(|&best| best <= g + 1) {
+                continue;
+            }
+            let h = calculator.calculate(&next.to_state());
+            best_g.insert(next.clone(), g + 1);
+            open.push((Reverse(g + 1 + h), Reverse(g + 1), nodes.len()));
+            nodes.push(next);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ManhattanDistance, ShortestPathHeuristic, WalkingDistance};
+
+    /// Deliberately inadmissible: triples Manhattan distance
+    struct Tripled;
+
+    impl EntropyCalculator for Tripled {
+        fn calculate(&self, state: &PuzzleState) -> u32 {
+            ManhattanDistance.calculate(state) * 3
+        }
+    }
+
+    #[test]
+    fn test_lab_scores() {
+        let states = HeuristicLab::random_walk_states(3, 8, 24, 42);
+        assert_eq!(states.len(), 8);
+
+        let mut lab = HeuristicLab::new();
+        lab.register("manhattan", ManhattanDistance)
+            .register("linear conflict", ShortestPathHeuristic)
+            .register("tripled", Tripled);
+        let report = lab.run(&states);
+        assert_eq!(report.states, 8);
+        assert_eq!(report.unknown_optimum, 0);
+
+        let [manhattan, conflict, tripled] = &report.heuristics[..] else {
+            panic!("expected three reports");
+        };
+        assert_eq!(manhattan.admissibility_violations, 0);
+        assert_eq!(conflict.admissibility_violations, 0);
+        assert!(manhattan.mean_error >= conflict.mean_error); // Linear conflicts only add
+        assert!(tripled.admissibility_violations > 0);
+        assert!(tripled.mean_error < 0.0);
+
+        // Better-informed admissible heuristics never need more nodes on average here
+        assert_eq!(manhattan.failed_searches, 0);
+        assert!(conflict.mean_nodes_expanded <= manhattan.mean_nodes_expanded);
+    }
+
+    #[test]
+    fn test_audit_checks_every_board() {
+        let mut lab = HeuristicLab::new();
+        lab.register("manhattan", ManhattanDistance)
+            .register("walking distance", WalkingDistance::new(3).unwrap())
+            .register("tripled", Tripled);
+        assert!(lab.audit(4).is_none());
+
+        let summary = lab.audit(3).unwrap();
+        assert_eq!(summary.states, 181_440);
+        assert_eq!(summary.diameter, 31);
+
+        let [manhattan, walking, tripled] = &summary.heuristics[..] else {
+            panic!("expected three reports");
+        };
+        assert!(manhattan.is_admissible() && manhattan.counterexample.is_none());
+        assert!(walking.is_admissible());
+        assert!(walking.mean_error < manhattan.mean_error);
+        assert!(!tripled.is_admissible());
+        let board = tripled.counterexample.as_ref().unwrap().to_state();
+        let distance = optimal_distances(&PuzzleState::new(3).unwrap())[&PackedState::from_state(&board)];
+        assert_eq!(Tripled.calculate(&board) - distance, tripled.worst_overestimate);
+    }
+
+    #[test]
+    fn test_builtin_registers_supported_calculators() {
+        let cache = HeuristicCache::disabled();
+        assert_eq!(HeuristicLab::builtin(3, &cache).unwrap().calculators.len(), EntropyKind::ALL.len());
+        assert_eq!(HeuristicLab::builtin(5, &cache).unwrap().calculators.len(), EntropyKind::ALL.len() - 1);
+    }
+
+    #[test]
+    fn test_random_walk_states_repeat() {
+        let a = HeuristicLab::random_walk_states(4, 3, 30, 9);
+        let b = HeuristicLab::random_walk_states(4, 3, 30, 9);
+        let layouts = |states: &[PuzzleState]| {
+            states
+                .iter()
+                .map(PuzzleState::to_layout)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(layouts(&a), layouts(&b));
+        assert!(a.iter().all(PuzzleState::is_solvable));
+        assert!(HeuristicLab::random_walk_states(2, 3, 30, 9).is_empty());
+    }
+}
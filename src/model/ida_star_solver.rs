@@ -0,0 +1,751 @@
+//! # IDA* Solver
+//!
+//! Iterative-deepening A* finds the same optimal solutions as [`AStarSolver`] but
+//! performs a series of depth-first searches bounded by an f-score threshold instead
+//! of keeping an open set in memory.
+//!
+//! ## Trade-offs
+//!
+//! - **Memory**: Proportional to solution depth, so 5×5 and larger grids never run
+//!   out of memory
+//! - **Time**: Re-expands shallow nodes on every iteration. Within an iteration,
+//!   a transposition table of boards already reached at no greater depth cuts
+//!   off the paths that only rearrange moves, and successors are tried in order
+//!   of their Manhattan distance, so the final iteration finds the goal early
+//! - **Threads**: With [`IDAStarSolver::threads`], each iteration's subtrees two
+//!   moves deep are shared among threads, bringing exact 5×5 solves within
+//!   reach of a desktop CPU
+//! - **Heuristic**: Uses plain Manhattan distance, which never overestimates, so
+//!   solutions are guaranteed shortest (the A* solver's Enhanced Heuristic is
+//!   faster but can overestimate)
+//!
+//! [`AStarSolver`]: super::solver::AStarSolver
+//!
+//! ## Example Usage
+//!
+//! ```rust
+//! use slider::model::{IDAStarSolver, PuzzleState};
+//!
+//! let solver = IDAStarSolver::new();
+//! let mut puzzle = PuzzleState::new(3)?;
+//! puzzle.apply_immediate_move((2, 1));
+//!
+//! assert_eq!(solver.solve(&puzzle), Some(1));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::entropy::{EntropyCalculator, ManhattanDistance};
+use super::error::SolverError;
+use super::move_validator::Position;
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use super::solver::{state_hash, Solution, SolverConfig, SolverProgress, PROGRESS_INTERVAL};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Instant;
+
+/// Boards the transposition table holds by default, about 16 MB of entries
+pub const DEFAULT_TRANSPOSITIONS: usize = 1 << 20;
+
+/// Moves made from the start before the parallel search splits into branches
+const BRANCH_DEPTH: usize = 2;
+
+/// Result of one bounded depth-first search
+enum Step {
+    Found,
+    Exceeded(u32), // Smallest f-score seen above the threshold
+    Aborted(SolverError), // Cancelled, timed out, or out of iterations
+}
+
+/// Mutable search context shared across the recursion
+struct Search<'a> {
+    config: SolverConfig,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    on_progress: &'a mut dyn FnMut(SolverProgress),
+    started: Instant,
+    nodes: usize,
+    generated: usize,
+    max_depth: usize,
+    threshold: u32,
+    path: Vec<Position>,
+    transpositions: HashMap<u64, u32>, // Board hash → least depth reached this iteration
+    capacity: usize,
+    pruned: usize,
+    shared: Option<&'a Shared>, // Set for the threads of a parallel search
+}
+
+/// What the threads of a parallel search share
+#[derive(Default)]
+struct Shared {
+    stop: AtomicBool,   // Some thread found the goal or hit a limit
+    nodes: AtomicUsize, // Expanded by every thread, counted in whole progress intervals
+}
+
+/// A board a few moves from the start, searched by one thread of a parallel iteration
+struct Branch {
+    state: PackedState,
+    h_score: u32,
+    path: Vec<Position>,
+    previous_empty: Option<Position>,
+}
+
+/// What one thread of a parallel iteration did
+struct Worker {
+    step: Step,
+    path: Vec<Position>,
+    nodes: usize,
+    generated: usize,
+    pruned: usize,
+    max_depth: usize,
+}
+
+impl Search<'_> {
+    /// Depth-first search below `state`, whose Manhattan distance is `h_score`,
+    /// never undoing the move that led here
+    fn search(&mut self, state: &mut PackedState, g_score: u32, h_score: u32, previous_empty: Option<Position>) -> Step {
+        let f_score = g_score + h_score;
+        if f_score > self.threshold {
+            return Step::Exceeded(f_score);
+        }
+        if h_score == 0 {
+            return Step::Found;
+        }
+        if self.is_transposition(state, g_score) {
+            self.pruned += 1;
+            return Step::Exceeded(u32::MAX); // Searched from no deeper already
+        }
+
+        self.nodes += 1;
+        self.max_depth = self.max_depth.max(self.path.len());
+        if self.nodes.is_multiple_of(PROGRESS_INTERVAL) {
+            if let Some(ref cancel) = self.cancel_flag {
+                if cancel.load(AtomicOrdering::Relaxed) {
+                    return Step::Aborted(SolverError::Cancelled);
+                }
+            }
+            if self.started.elapsed() >= self.config.timeout {
+                return Step::Aborted(SolverError::TimeLimitExceeded {
+                    timeout: self.config.timeout,
+                });
+            }
+            if let Some(shared) = self.shared {
+                if shared.stop.load(AtomicOrdering::Relaxed) {
+                    return Step::Aborted(SolverError::Cancelled); // Decided by another thread
+                }
+                if shared.nodes.fetch_add(PROGRESS_INTERVAL, AtomicOrdering::Relaxed) + PROGRESS_INTERVAL
+                    > self.config.max_iterations
+                {
+                    return Step::Aborted(SolverError::TimeoutExceeded {
+                        max_iterations: self.config.max_iterations,
+                    });
+                }
+            }
+            (self.on_progress)(SolverProgress {
+                nodes_explored: self.nodes,
+                current_depth: self.threshold,
+                elapsed: self.started.elapsed(),
+                frontier_size: self.path.len(),
+                expanded: Some(state.clone()),
+            });
+        }
+        if self.nodes > self.config.max_iterations {
+            return Step::Aborted(SolverError::TimeoutExceeded {
+                max_iterations: self.config.max_iterations,
+            });
+        }
+
+        let empty_pos = state.empty_position();
+        let mut next_threshold = u32::MAX;
+
+        // Most promising first: the move that brings its tile closer to home
+        let successors: Option<Vec<(u32, Position)>> = neighbours(empty_pos, state.size())
+            .filter(|&next_pos| Some(next_pos) != previous_empty) // Would undo the previous move
+            .map(|next_pos| Some((moved_h_score(state, h_score, next_pos, empty_pos)?, next_pos)))
+            .collect();
+        let Some(mut successors) = successors else {
+            return Step::Aborted(no_tile_beside(empty_pos));
+        };
+        successors.sort_unstable_by_key(|&(h, _)| h);
+
+        for (next_h, next_pos) in successors {
+            state.apply_immediate_move(next_pos);
+            self.path.push(next_pos);
+            self.generated += 1;
+
+            match self.search(state, g_score + 1, next_h, Some(empty_pos)) {
+                Step::Found => return Step::Found,
+                Step::Aborted(e) => return Step::Aborted(e),
+                Step::Exceeded(t) => next_threshold = next_threshold.min(t),
+            }
+
+            // Undo: the moved tile now sits where the empty cell was
+            self.path.pop();
+            state.apply_immediate_move(empty_pos);
+        }
+
+        Step::Exceeded(next_threshold)
+    }
+
+    /// Records that `state` was reached at depth `g_score`
+    /// Returns true if this iteration already searched it from no deeper
+    ///
+    /// Once the table is full, boards not in it are searched as usual.
+    fn is_transposition(&mut self, state: &PackedState, g_score: u32) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        let room = self.transpositions.len() < self.capacity;
+        match self.transpositions.get_mut(&state_hash(state)) {
+            Some(&mut seen) if seen <= g_score => true,
+            Some(seen) => {
+                *seen = g_score;
+                false
+            }
+            None => {
+                if room {
+                    self.transpositions.insert(state_hash(state), g_score);
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Boards `BRANCH_DEPTH` moves from `start` that do not undo a move, most promising first
+///
+/// # Errors
+///
+/// Returns `SolverError::InvalidState` if a cell next to the empty cell holds no tile
+fn branches(start: PackedState, h_score: u32) -> Result<Vec<Branch>, SolverError> {
+    let mut branches = vec![Branch {
+        state: start,
+        h_score,
+        path: Vec::new(),
+        previous_empty: None,
+    }];
+    for _ in 0..BRANCH_DEPTH {
+        branches = branches
+            .iter()
+            .flat_map(|branch| {
+                let empty_pos = branch.state.empty_position();
+                neighbours(empty_pos, branch.state.size())
+                    .filter(move |&next_pos| Some(next_pos) != branch.previous_empty)
+                    .map(move |next_pos| {
+                        let h_score = moved_h_score(&branch.state, branch.h_score, next_pos, empty_pos)
+                            .ok_or_else(|| no_tile_beside(empty_pos))?;
+                        let mut state = branch.state.clone();
+                        state.apply_immediate_move(next_pos);
+                        let mut path = branch.path.clone();
+                        path.push(next_pos);
+                        Ok(Branch {
+                            h_score,
+                            state,
+                            path,
+                            previous_empty: Some(empty_pos),
+                        })
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    branches.sort_by_key(|branch| branch.h_score);
+    Ok(branches)
+}
+
+/// Cells orthogonally next to `(row, col)` on a `size`×`size` board
+fn neighbours((row, col): Position, size: usize) -> impl Iterator<Item = Position> {
+    [
+        (row > 0).then(|| (row - 1, col)),
+        (row + 1 < size).then(|| (row + 1, col)),
+        (col > 0).then(|| (row, col - 1)),
+        (col + 1 < size).then(|| (row, col + 1)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Manhattan distance of `state` once the tile at `from` slides into `empty`
+/// Returns None if `from` holds no tile, which only a malformed board allows
+fn moved_h_score(state: &PackedState, h_score: u32, from: Position, empty: Position) -> Option<u32> {
+    let size = state.size();
+    let home = state.tile_at(from)?;
+    let (home_row, home_col) = (home / size, home % size);
+    let distance = |(row, col): Position| (row.abs_diff(home_row) + col.abs_diff(home_col)) as u32;
+    (h_score + distance(empty)).checked_sub(distance(from))
+}
+
+/// The error for a board with an empty cell next to `empty`
+fn no_tile_beside(empty: Position) -> SolverError {
+    SolverError::InvalidState(format!("no tile next to the empty cell at {:?}", empty))
+}
+
+/// IDA* solver that finds the optimal solution path using Manhattan distance
+pub struct IDAStarSolver {
+    heuristic: ManhattanDistance,
+    config: SolverConfig,
+    transpositions: usize,
+    threads: usize,
+}
+
+impl IDAStarSolver {
+    pub fn new() -> Self {
+        Self::with_config(SolverConfig::default())
+    }
+
+    /// Creates a solver with custom search limits
+    pub fn with_config(config: SolverConfig) -> Self {
+        Self {
+            heuristic: ManhattanDistance,
+            config,
+            transpositions: DEFAULT_TRANSPOSITIONS,
+            threads: 1,
+        }
+    }
+
+    /// Sets how many boards the transposition table may hold; 0 turns it off
+    /// A parallel search gives each thread a table of its own
+    pub fn transpositions(mut self, capacity: usize) -> Self {
+        self.transpositions = capacity;
+        self
+    }
+
+    /// Sets how many threads share each iteration; 1, the default, searches
+    /// on the calling thread
+    ///
+    /// The boards two moves from the start are handed out to the threads in
+    /// turn, and the iteration ends once every one is searched or a thread
+    /// finds the goal. Every solution an iteration finds is optimal, but which
+    /// one is returned can vary from run to run.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Shares each iteration among as many threads as the machine runs at once
+    /// Targets without threads search on the calling thread
+    pub fn parallel(self) -> Self {
+        self.threads(thread::available_parallelism().map_or(1, |threads| threads.get()))
+    }
+
+    /// Returns how many threads share each iteration
+    pub fn thread_count(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the search limits used by this solver
+    pub fn config(&self) -> SolverConfig {
+        self.config
+    }
+
+    /// Returns the length of the optimal solution, or None if unsolvable/timeout
+    pub fn solve(&self, initial_state: &PuzzleState) -> Option<u32> {
+        self.solve_with_path(initial_state).map(|path| path.len() as u32)
+    }
+
+    /// Returns the optimal solution path as a sequence of tile positions to move
+    /// Returns None if unsolvable or timeout
+    pub fn solve_with_path(&self, initial_state: &PuzzleState) -> Option<Vec<Position>> {
+        self.solve_with_path_cancellable(initial_state, None)
+    }
+
+    /// Returns the optimal solution path with support for cancellation
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_cancellable(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+    ) -> Option<Vec<Position>> {
+        self.solve_with_path_reporting(initial_state, cancel_flag, &mut |_| {})
+    }
+
+    /// Returns the optimal solution path, invoking `on_progress` every
+    /// `PROGRESS_INTERVAL` expansions; `current_depth` is the active threshold
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_reporting(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Vec<Position>> {
+        self.solve_instrumented(initial_state, cancel_flag, on_progress)
+            .map(|solution| solution.path)
+    }
+
+    /// Returns the optimal solution with node counts across all iterations;
+    /// `max_open_size` is the deepest point the search stack reached and
+    /// `nodes_pruned` counts the boards the transposition table cut off
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.try_solve_instrumented(initial_state, cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Returns a solution like `solve_instrumented`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why the search stopped: a search limit
+    /// from the config, cancellation, or an exhausted search space
+    pub fn try_solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        if initial_state.is_solved() {
+            return Ok(Solution {
+                start_empty: initial_state.empty_position(),
+                ..Solution::default()
+            });
+        }
+        if !initial_state.is_solvable() {
+            // Would otherwise deepen until the limits are hit
+            return Err(SolverError::Unsolvable);
+        }
+
+        let h_score = self.heuristic.calculate(initial_state);
+        if self.threads > 1 && h_score as usize > BRANCH_DEPTH {
+            // The goal is further than the branches, so none of them is solved
+            return self.try_solve_parallel(initial_state, h_score, cancel_flag, on_progress);
+        }
+        let mut search = Search {
+            config: self.config,
+            cancel_flag,
+            on_progress,
+            started: Instant::now(),
+            nodes: 0,
+            generated: 0,
+            max_depth: 0,
+            threshold: h_score,
+            path: Vec::new(),
+            transpositions: HashMap::new(),
+            capacity: self.transpositions,
+            pruned: 0,
+            shared: None,
+        };
+        let mut state = PackedState::from_state(initial_state);
+
+        loop {
+            // Depths recorded under a lower threshold do not bound this one
+            search.transpositions.clear();
+            match search.search(&mut state, 0, h_score, None) {
+                Step::Found => {
+                    return Ok(Solution {
+                        nodes_expanded: search.nodes,
+                        nodes_generated: search.generated,
+                        nodes_pruned: search.pruned,
+                        max_open_size: search.max_depth.max(search.path.len()),
+                        duration: search.started.elapsed(),
+                        peak_memory_bytes: 0,
+                        start_empty: initial_state.empty_position(),
+                        annotations: Vec::new(),
+                        path: search.path,
+                    })
+                }
+                Step::Aborted(e) => return Err(e),
+                Step::Exceeded(u32::MAX) => return Err(SolverError::Unsolvable), // Search space exhausted
+                Step::Exceeded(next) => search.threshold = next,
+            }
+        }
+    }
+}
+
+impl IDAStarSolver {
+    /// Runs each threshold iteration on `self.threads` threads, which take the
+    /// branches in turn and report progress through the calling thread
+    fn try_solve_parallel(
+        &self,
+        initial_state: &PuzzleState,
+        h_score: u32,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        let started = Instant::now();
+        let branches = branches(PackedState::from_state(initial_state), h_score)?;
+        let shared = Shared::default();
+        let (mut nodes, mut generated, mut pruned, mut max_depth) = (0, 0, 0, BRANCH_DEPTH);
+        let mut threshold = h_score;
+
+        loop {
+            let next_branch = AtomicUsize::new(0);
+            let (sender, receiver) = mpsc::channel();
+            let workers: Vec<Worker> = thread::scope(|scope| {
+                let handles: Vec<_> = (0..self.threads)
+                    .map(|_| {
+                        let (sender, cancel_flag) = (sender.clone(), cancel_flag.clone());
+                        let (branches, shared, next_branch) = (&branches, &shared, &next_branch);
+                        scope.spawn(move || {
+                            let mut report = |mut progress: SolverProgress| {
+                                progress.nodes_explored = shared.nodes.load(AtomicOrdering::Relaxed);
+                                let _ = sender.send(progress);
+                            };
+                            let mut search = Search {
+                                config: self.config,
+                                cancel_flag,
+                                on_progress: &mut report,
+                                started,
+                                nodes: 0,
+                                generated: 0,
+                                max_depth: 0,
+                                threshold,
+                                path: Vec::new(),
+                                transpositions: HashMap::new(),
+                                capacity: self.transpositions,
+                                pruned: 0,
+                                shared: Some(shared),
+                            };
+                            let mut next = u32::MAX;
+                            let step = loop {
+                                let Some(branch) = branches.get(next_branch.fetch_add(1, AtomicOrdering::Relaxed)) else {
+                                    break Step::Exceeded(next);
+                                };
+                                let mut state = branch.state.clone();
+                                search.path.clone_from(&branch.path);
+                                match search.search(&mut state, BRANCH_DEPTH as u32, branch.h_score, branch.previous_empty) {
+                                    Step::Exceeded(t) => next = next.min(t),
+                                    step => {
+                                        shared.stop.store(true, AtomicOrdering::Relaxed);
+                                        break step;
+                                    }
+                                }
+                            };
+                            Worker {
+                                step,
+                                path: search.path,
+                                nodes: search.nodes,
+                                generated: search.generated,
+                                pruned: search.pruned,
+                                max_depth: search.max_depth,
+                            }
+                        })
+                    })
+                    .collect();
+                drop(sender);
+                for progress in receiver {
+                    on_progress(progress);
+                }
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("search thread panicked"))
+                    .collect()
+            });
+
+            let mut next = u32::MAX;
+            let mut error = None;
+            for worker in &workers {
+                nodes += worker.nodes;
+                generated += worker.generated;
+                pruned += worker.pruned;
+                max_depth = max_depth.max(worker.max_depth);
+            }
+            for worker in workers {
+                match worker.step {
+                    Step::Found => {
+                        return Ok(Solution {
+                            nodes_expanded: nodes,
+                            nodes_generated: generated,
+                            nodes_pruned: pruned,
+                            max_open_size: max_depth.max(worker.path.len()),
+                            duration: started.elapsed(),
+                            peak_memory_bytes: 0,
+                            start_empty: initial_state.empty_position(),
+                            annotations: Vec::new(),
+                            path: worker.path,
+                        })
+                    }
+                    // Threads stopped by another report cancellation; keep the reason they stopped
+                    Step::Aborted(e) if error.is_none() || e != SolverError::Cancelled => error = Some(e),
+                    Step::Aborted(_) => {}
+                    Step::Exceeded(t) => next = next.min(t),
+                }
+            }
+            match (error, next) {
+                (Some(e), _) => return Err(e),
+                (None, u32::MAX) => return Err(SolverError::Unsolvable), // Search space exhausted
+                (None, next) => threshold = next,
+            }
+        }
+    }
+}
+
+impl Default for IDAStarSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::MoveSystem;
+    use crate::model::{AStarSolver, GoalSpec};
+
+    fn apply_path(state: &PuzzleState, path: &[Position]) -> PuzzleState {
+        let mut state = state.clone();
+        for &pos in path {
+            assert!(state.apply_immediate_move(pos));
+        }
+        state
+    }
+
+    #[test]
+    fn test_moved_h_score_needs_a_tile() {
+        let puzzle = scramble(3, 10, 2);
+        let state = PackedState::from_state(&puzzle);
+        let h_score = ManhattanDistance.calculate(&puzzle);
+        let empty = state.empty_position();
+        let from = neighbours(empty, 3).next().unwrap();
+
+        let mut moved = puzzle.clone();
+        moved.apply_immediate_move(from);
+        assert_eq!(moved_h_score(&state, h_score, from, empty), Some(ManhattanDistance.calculate(&moved)));
+        // The empty cell has no tile to slide, so there is no score rather than a panic
+        assert_eq!(moved_h_score(&state, h_score, empty, empty), None);
+    }
+
+    #[test]
+    fn test_solved_puzzle() {
+        let puzzle = PuzzleState::new(4).unwrap();
+        assert_eq!(IDAStarSolver::new().solve_with_path(&puzzle), Some(Vec::new()));
+    }
+
+    /// Breadth-first search length, the ground truth for small grids
+    fn bfs_length(start: &PuzzleState) -> usize {
+        use std::collections::{HashSet, VecDeque};
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([(start.clone(), 0)]);
+        seen.insert(start.to_layout());
+        while let Some((state, depth)) = queue.pop_front() {
+            if state.is_solved() {
+                return depth;
+            }
+            for pos in state.legal_moves() {
+                let mut next = state.clone();
+                next.apply(pos);
+                if seen.insert(next.to_layout()) {
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+        unreachable!("solvable puzzle");
+    }
+
+    #[test]
+    fn test_finds_optimal_length() {
+        for seed in 1..=3 {
+            let puzzle = scramble(3, 16, seed);
+            let path = IDAStarSolver::new().solve_with_path(&puzzle).unwrap();
+            assert!(apply_path(&puzzle, &path).is_solved());
+            assert_eq!(path.len(), bfs_length(&puzzle));
+        }
+    }
+
+    #[test]
+    fn test_solves_every_goal_on_4x4() {
+        for goal in [GoalSpec::BlankFirst, GoalSpec::Reversed, GoalSpec::Spiral] {
+            let puzzle = scramble_from(PuzzleState::with_goal(4, goal).unwrap(), 25, 1);
+            let path = IDAStarSolver::new().solve_with_path(&puzzle).unwrap();
+            assert!(apply_path(&puzzle, &path).is_solved(), "{}", goal);
+            let astar = AStarSolver::new().solve_with_path(&puzzle).unwrap();
+            assert!(apply_path(&puzzle, &astar).is_solved(), "{}", goal);
+            assert!(path.len() <= astar.len());
+        }
+    }
+
+    #[test]
+    fn test_never_longer_than_astar() {
+        for seed in 1..=3 {
+            let puzzle = scramble(4, 24, seed);
+            let path = IDAStarSolver::new().solve_with_path(&puzzle).unwrap();
+            assert!(apply_path(&puzzle, &path).is_solved());
+            assert!(path.len() <= AStarSolver::new().solve_with_path(&puzzle).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_transpositions_cut_nodes_not_moves() {
+        let (mut with_table, mut without) = (0, 0);
+        for seed in 1..=3 {
+            let puzzle = scramble(4, 60, seed);
+            let solve = |solver: IDAStarSolver| solver.try_solve_instrumented(&puzzle, None, &mut |_| {}).unwrap();
+            let pruned = solve(IDAStarSolver::new());
+            let plain = solve(IDAStarSolver::new().transpositions(0));
+            assert!(apply_path(&puzzle, &pruned.path).is_solved());
+            assert_eq!(pruned.len(), plain.len());
+            assert_eq!(plain.nodes_pruned, 0);
+            with_table += pruned.nodes_expanded;
+            without += plain.nodes_expanded;
+        }
+        assert!(with_table < without, "{} nodes with the table, {} without", with_table, without);
+
+        // A table that fills up still finds optimal solutions
+        let puzzle = scramble(3, 30, 5);
+        let path = IDAStarSolver::new().transpositions(8).solve_with_path(&puzzle).unwrap();
+        assert_eq!(path.len(), bfs_length(&puzzle));
+    }
+
+    #[test]
+    fn test_parallel_search_stays_optimal() {
+        for seed in 1..=3 {
+            let puzzle = scramble(4, 60, seed);
+            let sequential = IDAStarSolver::new().solve_with_path(&puzzle).unwrap();
+            let parallel = IDAStarSolver::new()
+                .threads(4)
+                .try_solve_instrumented(&puzzle, None, &mut |_| {})
+                .unwrap();
+            assert!(apply_path(&puzzle, &parallel.path).is_solved());
+            assert_eq!(parallel.len(), sequential.len());
+            assert!(parallel.nodes_expanded > 0);
+        }
+        // Boards within reach of the branches are searched on one thread
+        let near = scramble(4, 2, 1);
+        assert_eq!(IDAStarSolver::new().threads(4).solve(&near), IDAStarSolver::new().solve(&near));
+
+        let limited = IDAStarSolver::with_config(SolverConfig {
+            max_iterations: 2000,
+            ..SolverConfig::default()
+        })
+        .threads(4);
+        assert!(matches!(
+            limited.try_solve_instrumented(&scramble(5, 200, 3), None, &mut |_| {}),
+            Err(SolverError::TimeoutExceeded { .. })
+        ));
+        let cancel = Some(Arc::new(AtomicBool::new(true)));
+        let solver = IDAStarSolver::new().threads(4);
+        assert_eq!(solver.solve_with_path_cancellable(&scramble(5, 200, 3), cancel), None);
+    }
+
+    #[test]
+    fn test_reports_threshold_progress() {
+        let puzzle = scramble(4, 40, 7);
+        let mut reports = Vec::new();
+        let path = IDAStarSolver::new().solve_with_path_reporting(&puzzle, None, &mut |p| reports.push(p));
+
+        assert!(path.is_some());
+        assert!(!reports.is_empty());
+        assert!(reports.windows(2).all(|w| w[0].current_depth <= w[1].current_depth));
+    }
+
+    #[test]
+    fn test_respects_iteration_limit() {
+        let solver = IDAStarSolver::with_config(SolverConfig {
+            max_iterations: 10,
+            ..SolverConfig::default()
+        });
+        assert_eq!(solver.solve_with_path(&scramble(4, 40, 7)), None);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let solver = IDAStarSolver::new();
+        assert_eq!(solver.solve_with_path_cancellable(&scramble(4, 40, 7), Some(cancel)), None);
+    }
+}
@@ -0,0 +1,453 @@
+//! # Klotski Puzzles
+//!
+//! Klotski (Huarong Dao, "Forget-me-not") is a sliding-block puzzle whose blocks
+//! cover one, two, or four cells. A move slides one block a single cell into free
+//! space; the puzzle is solved when the goal block reaches its goal position,
+//! wherever the other blocks are.
+//!
+//! ```text
+//!  T B B T      B = goal block (2×2)
+//!  T B B T      T = tall block (1×2)
+//!  T W W T      W = wide block (2×1)
+//!  T s s T      s = small block (1×1)
+//!  s . . s      . = free cell; the goal is B at row 3, column 1
+//! ```
+//!
+//! [`KlotskiBoard::solve`] finds a shortest solution by breadth-first search.
+//! Blocks of the same shape are interchangeable, so states are compared by the
+//! shapes covering each cell, which keeps the classic layout to a few tens of
+//! thousands of states.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{Direction, KlotskiBoard, KlotskiMove};
+//!
+//! let mut board = KlotskiBoard::classic();
+//! assert!(!board.is_solved());
+//!
+//! let small = board.block_at((3, 1)).unwrap();
+//! assert!(board.apply_move(KlotskiMove { block: small, direction: Direction::Down }));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::error::PuzzleError;
+use super::move_validator::Position;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+/// Smallest board side
+pub const MIN_KLOTSKI_SIZE: usize = 2;
+
+/// Largest board side
+pub const MAX_KLOTSKI_SIZE: usize = 8;
+
+/// Default limit on states explored by `solve`
+pub const DEFAULT_KLOTSKI_STATES: usize = 500_000;
+
+/// Footprint of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockShape {
+    /// 1×1
+    Small,
+    /// Two cells side by side
+    Wide,
+    /// Two cells stacked
+    Tall,
+    /// 2×2
+    Big,
+}
+
+impl BlockShape {
+    /// Returns the number of columns covered
+    pub fn width(self) -> usize {
+        match self {
+            BlockShape::Small | BlockShape::Tall => 1,
+            BlockShape::Wide | BlockShape::Big => 2,
+        }
+    }
+
+    /// Returns the number of rows covered
+    pub fn height(self) -> usize {
+        match self {
+            BlockShape::Small | BlockShape::Wide => 1,
+            BlockShape::Tall | BlockShape::Big => 2,
+        }
+    }
+}
+
+/// Direction a block slides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Every direction
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// Returns the (row, column) offset of one step
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// Returns the direction that undoes a step this way
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Up => write!(f, "up"),
+            Direction::Down => write!(f, "down"),
+            Direction::Left => write!(f, "left"),
+            Direction::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// A block on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Block {
+    pub shape: BlockShape,
+    /// Top-left cell
+    pub position: Position,
+}
+
+impl Block {
+    pub fn new(shape: BlockShape, position: Position) -> Self {
+        Self { shape, position }
+    }
+
+    /// Returns every cell the block covers
+    pub fn cells(&self) -> impl Iterator<Item = Position> + '_ {
+        let (row, col) = self.position;
+        (0..self.shape.height())
+            .flat_map(move |dr| (0..self.shape.width()).map(move |dc| (row + dr, col + dc)))
+    }
+}
+
+/// Slides `block` (an index into `KlotskiBoard::blocks`) one cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KlotskiMove {
+    pub block: usize,
+    pub direction: Direction,
+}
+
+/// A Klotski board: blocks, free cells, and where the goal block must go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KlotskiBoard {
+    rows: usize,
+    cols: usize,
+    blocks: Vec<Block>,
+    goal_block: usize,
+    goal_position: Position,
+}
+
+impl KlotskiBoard {
+    /// Creates a board from its blocks
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if a side is outside `MIN_KLOTSKI_SIZE..=MAX_KLOTSKI_SIZE`,
+    /// blocks overlap or leave the board, no cell is free, or the goal block
+    /// does not exist or cannot fit at the goal position
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        blocks: Vec<Block>,
+        goal_block: usize,
+        goal_position: Position,
+    ) -> Result<Self, PuzzleError> {
+        for side in [rows, cols] {
+            if side < MIN_KLOTSKI_SIZE {
+                return Err(PuzzleError::SizeTooSmall { size: side, min: MIN_KLOTSKI_SIZE });
+            }
+            if side > MAX_KLOTSKI_SIZE {
+                return Err(PuzzleError::SizeTooLarge { size: side, max: MAX_KLOTSKI_SIZE });
+            }
+        }
+
+        let mut covered = vec![false; rows * cols];
+        for block in &blocks {
+            for (row, col) in block.cells() {
+                if row >= rows || col >= cols {
+                    return Err(PuzzleError::InvalidLayout(format!(
+                        "block at ({}, {}) leaves the board",
+                        block.position.0, block.position.1
+                    )));
+                }
+                if std::mem::replace(&mut covered[row * cols + col], true) {
+                    return Err(PuzzleError::InvalidLayout(format!(
+                        "blocks overlap at ({}, {})",
+                        row, col
+                    )));
+                }
+            }
+        }
+        if covered.iter().all(|&c| c) {
+            return Err(PuzzleError::InvalidLayout("no free cell to move into".to_string()));
+        }
+
+        let Some(goal) = blocks.get(goal_block) else {
+            return Err(PuzzleError::InvalidLayout(format!("no goal block {}", goal_block)));
+        };
+        if goal_position.0 + goal.shape.height() > rows || goal_position.1 + goal.shape.width() > cols {
+            return Err(PuzzleError::InvalidLayout(
+                "goal block does not fit at the goal position".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            rows,
+            cols,
+            blocks,
+            goal_block,
+            goal_position,
+        })
+    }
+
+    /// Returns the classic 4×5 "Huarong Dao" layout
+    pub fn classic() -> Self {
+        use BlockShape::*;
+        let blocks = vec![
+            Block::new(Big, (0, 1)),
+            Block::new(Tall, (0, 0)),
+            Block::new(Tall, (0, 3)),
+            Block::new(Tall, (2, 0)),
+            Block::new(Tall, (2, 3)),
+            Block::new(Wide, (2, 1)),
+            Block::new(Small, (3, 1)),
+            Block::new(Small, (3, 2)),
+            Block::new(Small, (4, 0)),
+            Block::new(Small, (4, 3)),
+        ];
+        Self::new(5, 4, blocks, 0, (3, 1)).expect("classic layout is valid")
+    }
+
+    /// Returns the number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns every block; indices are stable across moves
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// Returns the goal block's index and the position it must reach
+    pub fn goal(&self) -> (usize, Position) {
+        (self.goal_block, self.goal_position)
+    }
+
+    /// Returns the index of the block covering `pos`, or None for a free cell
+    pub fn block_at(&self, pos: Position) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|block| block.cells().any(|cell| cell == pos))
+    }
+
+    /// Returns true if the move stays on the board and only enters free cells
+    pub fn can_move(&self, mv: KlotskiMove) -> bool {
+        let Some(block) = self.blocks.get(mv.block) else {
+            return false;
+        };
+        let (dr, dc) = mv.direction.delta();
+        block.cells().all(|(row, col)| {
+            let (Some(row), Some(col)) = (row.checked_add_signed(dr), col.checked_add_signed(dc)) else {
+                return false;
+            };
+            row < self.rows
+                && col < self.cols
+                && self.block_at((row, col)).is_none_or(|other| other == mv.block)
+        })
+    }
+
+    /// Applies the move if legal; returns false (leaving the board unchanged) otherwise
+    pub fn apply_move(&mut self, mv: KlotskiMove) -> bool {
+        if !self.can_move(mv) {
+            return false;
+        }
+        let (dr, dc) = mv.direction.delta();
+        let block = &mut self.blocks[mv.block];
+        block.position = (
+            block.position.0.wrapping_add_signed(dr),
+            block.position.1.wrapping_add_signed(dc),
+        );
+        true
+    }
+
+    /// Returns every legal move
+    pub fn legal_moves(&self) -> Vec<KlotskiMove> {
+        (0..self.blocks.len())
+            .flat_map(|block| Direction::ALL.into_iter().map(move |direction| KlotskiMove { block, direction }))
+            .filter(|&mv| self.can_move(mv))
+            .collect()
+    }
+
+    /// Returns true once the goal block is at the goal position
+    pub fn is_solved(&self) -> bool {
+        self.blocks[self.goal_block].position == self.goal_position
+    }
+
+    /// Finds a shortest solution by breadth-first search
+    /// Returns None if the goal is unreachable within `max_states` distinct states
+    pub fn solve(&self, max_states: usize) -> Option<Vec<KlotskiMove>> {
+        if self.is_solved() {
+            return Some(Vec::new());
+        }
+
+        // Each node: blocks, parent index, move from the parent
+        let mut nodes: Vec<(Vec<Block>, usize, Option<KlotskiMove>)> = vec![(self.blocks.clone(), 0, None)];
+        let mut seen = HashSet::from([self.shape_key(&self.blocks)]);
+        let mut queue = VecDeque::from([0]);
+        let mut scratch = self.clone();
+
+        while let Some(index) = queue.pop_front() {
+            scratch.blocks.clone_from(&nodes[index].0);
+            for mv in scratch.legal_moves() {
+                let mut next = scratch.clone();
+                next.apply_move(mv);
+                if !seen.insert(self.shape_key(&next.blocks)) {
+                    continue;
+                }
+
+                nodes.push((next.blocks.clone(), index, Some(mv)));
+                if next.is_solved() {
+                    return Some(path_to(&nodes, nodes.len() - 1));
+                }
+                if seen.len() >= max_states {
+                    return None;
+                }
+                queue.push_back(nodes.len() - 1);
+            }
+        }
+        None
+    }
+
+    /// Encodes which shape covers each cell, so equal shapes are interchangeable
+    fn shape_key(&self, blocks: &[Block]) -> Vec<u8> {
+        let mut key = vec![0u8; self.rows * self.cols];
+        for (index, block) in blocks.iter().enumerate() {
+            let code = if index == self.goal_block {
+                5 // The goal block is never interchangeable
+            } else {
+                block.shape as u8 + 1
+            };
+            for (row, col) in block.cells() {
+                key[row * self.cols + col] = code;
+            }
+        }
+        key
+    }
+}
+
+/// Follows parent links back to the start
+fn path_to(nodes: &[(Vec<Block>, usize, Option<KlotskiMove>)], mut index: usize) -> Vec<KlotskiMove> {
+    let mut path = Vec::new();
+    while let (_, parent, Some(mv)) = &nodes[index] {
+        path.push(*mv);
+        index = *parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_layout() {
+        let board = KlotskiBoard::classic();
+        assert_eq!((board.rows(), board.cols()), (5, 4));
+        assert_eq!(board.block_at((1, 2)), Some(0));
+        assert_eq!(board.block_at((4, 1)), None);
+        assert!(!board.is_solved());
+
+        // Only the four small blocks next to the free cells can move
+        let moves = board.legal_moves();
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|mv| board.blocks()[mv.block].shape == BlockShape::Small));
+    }
+
+    #[test]
+    fn test_invalid_boards() {
+        use BlockShape::*;
+        let overlap = vec![Block::new(Big, (0, 0)), Block::new(Small, (1, 1))];
+        assert!(matches!(
+            KlotskiBoard::new(3, 3, overlap, 0, (1, 1)),
+            Err(PuzzleError::InvalidLayout(_))
+        ));
+
+        let outside = vec![Block::new(Wide, (0, 2))];
+        assert!(KlotskiBoard::new(3, 3, outside, 0, (0, 0)).is_err());
+
+        let full = vec![Block::new(Big, (0, 0))];
+        assert!(KlotskiBoard::new(2, 2, full, 0, (0, 0)).is_err());
+
+        assert!(KlotskiBoard::new(3, 3, vec![Block::new(Small, (0, 0))], 1, (0, 0)).is_err());
+        assert!(KlotskiBoard::new(1, 3, Vec::new(), 0, (0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_moves_respect_blocks_and_edges() {
+        let mut board = KlotskiBoard::classic();
+        let wide = board.block_at((2, 1)).unwrap();
+        assert!(!board.can_move(KlotskiMove { block: wide, direction: Direction::Down }));
+
+        let left_small = board.block_at((3, 1)).unwrap();
+        assert!(board.apply_move(KlotskiMove { block: left_small, direction: Direction::Down }));
+        assert_eq!(board.blocks()[left_small].position, (4, 1));
+        assert!(!board.apply_move(KlotskiMove { block: left_small, direction: Direction::Down }));
+
+        let corner = board.block_at((4, 0)).unwrap();
+        assert!(!board.can_move(KlotskiMove { block: corner, direction: Direction::Left }));
+    }
+
+    #[test]
+    fn test_solve_small_board() {
+        use BlockShape::*;
+        // Slide the big block down past a small block
+        let blocks = vec![Block::new(Big, (0, 0)), Block::new(Small, (2, 0))];
+        let board = KlotskiBoard::new(3, 3, blocks, 0, (1, 0)).unwrap();
+
+        let path = board.solve(DEFAULT_KLOTSKI_STATES).unwrap();
+        let mut replay = board.clone();
+        assert!(path.iter().all(|&mv| replay.apply_move(mv)));
+        assert!(replay.is_solved());
+        assert_eq!(path.len(), 3); // Small block right, right; big block down
+    }
+
+    #[test]
+    fn test_solve_classic() {
+        let board = KlotskiBoard::classic();
+        let path = board.solve(DEFAULT_KLOTSKI_STATES).unwrap();
+
+        let mut replay = board.clone();
+        assert!(path.iter().all(|&mv| replay.apply_move(mv)));
+        assert!(replay.is_solved());
+        assert!(board.solve(100).is_none());
+    }
+}
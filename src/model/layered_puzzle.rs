@@ -0,0 +1,180 @@
+//! # Layered Puzzles
+//!
+//! A layered puzzle stacks several independent N×N boards. Each layer plays like
+//! an ordinary puzzle, and the whole stack counts as solved only once every layer
+//! is solved. Callers usually work on one layer at a time: [`LayeredPuzzle::swap_layer`]
+//! checks a board out of the stack and puts it back without copying.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{LayeredPuzzle, PuzzleState};
+//!
+//! let mut stack = LayeredPuzzle::new(3, 2)?;
+//! stack.layer_mut(1).unwrap().apply_immediate_move((2, 1));
+//! assert!(!stack.is_solved());
+//! assert_eq!(stack.solved_layers(), 1);
+//!
+//! // Work on layer 1 outside the stack, then put it back
+//! let mut board = PuzzleState::new(3)?;
+//! stack.swap_layer(1, &mut board);
+//! assert!(board.apply_immediate_move((2, 2)));
+//! stack.swap_layer(1, &mut board);
+//! assert!(stack.is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::error::PuzzleError;
+use super::puzzle_state::PuzzleState;
+
+/// Largest number of layers in one stack
+pub const MAX_LAYERS: usize = 9;
+
+/// A stack of equally sized boards that must all be solved
+#[derive(Debug, Clone)]
+pub struct LayeredPuzzle {
+    layers: Vec<PuzzleState>,
+}
+
+impl LayeredPuzzle {
+    /// Creates `count` solved layers of the given size
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if the size is invalid or `count` is 0 or above `MAX_LAYERS`
+    pub fn new(size: usize, count: usize) -> Result<Self, PuzzleError> {
+        check_count(count)?;
+        let board = PuzzleState::new(size)?;
+        Ok(Self {
+            layers: vec![board; count],
+        })
+    }
+
+    /// Stacks existing boards, bottom layer first
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if there are no boards, too many, or they differ in size
+    pub fn from_layers(layers: Vec<PuzzleState>) -> Result<Self, PuzzleError> {
+        check_count(layers.len())?;
+        let size = layers[0].size();
+        if layers.iter().any(|layer| layer.size() != size) {
+            return Err(PuzzleError::InvalidLayout(
+                "all layers must have the same size".to_string(),
+            ));
+        }
+        Ok(Self { layers })
+    }
+
+    /// Returns the size of each layer (n for n×n)
+    pub fn size(&self) -> usize {
+        self.layers[0].size()
+    }
+
+    /// Returns the number of layers
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns all layers, bottom first
+    pub fn layers(&self) -> &[PuzzleState] {
+        &self.layers
+    }
+
+    /// Returns the layer at `index`, or None if out of range
+    pub fn layer(&self, index: usize) -> Option<&PuzzleState> {
+        self.layers.get(index)
+    }
+
+    /// Returns the layer at `index` for modification, or None if out of range
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut PuzzleState> {
+        self.layers.get_mut(index)
+    }
+
+    /// Exchanges layer `index` with `board`
+    /// Returns false (and leaves both untouched) if `index` is out of range or the sizes differ
+    pub fn swap_layer(&mut self, index: usize, board: &mut PuzzleState) -> bool {
+        match self.layers.get_mut(index) {
+            Some(layer) if layer.size() == board.size() => {
+                std::mem::swap(layer, board);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the number of solved layers
+    pub fn solved_layers(&self) -> usize {
+        self.layers.iter().filter(|layer| layer.is_solved()).count()
+    }
+
+    /// Returns true once every layer is solved
+    pub fn is_solved(&self) -> bool {
+        self.layers.iter().all(PuzzleState::is_solved)
+    }
+}
+
+fn check_count(count: usize) -> Result<(), PuzzleError> {
+    if count == 0 || count > MAX_LAYERS {
+        return Err(PuzzleError::InvalidLayerCount {
+            count,
+            max: MAX_LAYERS,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_stack_is_solved() {
+        let stack = LayeredPuzzle::new(4, 3).unwrap();
+        assert_eq!(stack.size(), 4);
+        assert_eq!(stack.layer_count(), 3);
+        assert_eq!(stack.solved_layers(), 3);
+        assert!(stack.is_solved());
+    }
+
+    #[test]
+    fn test_invalid_stacks() {
+        assert!(matches!(
+            LayeredPuzzle::new(3, 0),
+            Err(PuzzleError::InvalidLayerCount { count: 0, max: MAX_LAYERS })
+        ));
+        assert!(LayeredPuzzle::new(3, MAX_LAYERS + 1).is_err());
+        assert!(LayeredPuzzle::new(2, 2).is_err());
+
+        let mixed = vec![PuzzleState::new(3).unwrap(), PuzzleState::new(4).unwrap()];
+        assert!(matches!(LayeredPuzzle::from_layers(mixed), Err(PuzzleError::InvalidLayout(_))));
+    }
+
+    #[test]
+    fn test_every_layer_must_be_solved() {
+        let scrambled: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        let mut stack = LayeredPuzzle::from_layers(vec![PuzzleState::new(3).unwrap(), scrambled]).unwrap();
+        assert!(!stack.is_solved());
+        assert_eq!(stack.solved_layers(), 1);
+
+        let layer = stack.layer_mut(1).unwrap();
+        assert!(layer.apply_immediate_move((2, 1)));
+        assert!(layer.apply_immediate_move((2, 2)));
+        assert!(stack.is_solved());
+    }
+
+    #[test]
+    fn test_swap_layer() {
+        let mut stack = LayeredPuzzle::new(3, 2).unwrap();
+        let mut board: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        let layout = board.to_layout();
+
+        assert!(stack.swap_layer(1, &mut board));
+        assert!(board.is_solved());
+        assert_eq!(stack.layer(1).unwrap().to_layout(), layout);
+
+        let mut wrong_size = PuzzleState::new(4).unwrap();
+        assert!(!stack.swap_layer(0, &mut wrong_size));
+        assert!(!stack.swap_layer(2, &mut board));
+    }
+}
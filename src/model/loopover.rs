@@ -0,0 +1,215 @@
+//! # Loopover Puzzles
+//!
+//! Loopover is a sliding puzzle with no empty cell: a move rotates a whole row
+//! or column by one cell, and the tile pushed off one edge comes back in on the
+//! opposite edge. The board is solved when the tiles read 1, 2, 3, … row by row.
+//!
+//! ```text
+//!  1 2 3      rotate row 0 right      3 1 2
+//!  4 5 6     ------------------->     4 5 6
+//!  7 8 9                              7 8 9
+//! ```
+//!
+//! On odd boards every rotation is an even permutation, so only half of all
+//! layouts can be reached; boards are scrambled by playing moves (see
+//! [`MoveSystem::random_walk`]) rather than by shuffling tiles, which keeps every
+//! scramble solvable.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{Direction, LoopoverBoard, LoopoverMove};
+//!
+//! let mut board = LoopoverBoard::new(3)?;
+//! assert!(board.rotate(LoopoverMove { line: 0, direction: Direction::Right }));
+//! assert_eq!(board.tile_at((0, 0)), 3);
+//! assert!(!board.is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`MoveSystem::random_walk`]: super::move_system::MoveSystem::random_walk
+
+use super::error::PuzzleError;
+use super::klotski::Direction;
+use super::move_system::MoveSystem;
+use super::move_validator::Position;
+use std::fmt;
+
+/// Smallest board side
+pub const MIN_LOOPOVER_SIZE: usize = 2;
+
+/// Largest board side
+pub const MAX_LOOPOVER_SIZE: usize = 10;
+
+/// Rotates one row or column by a cell
+///
+/// `Left` and `Right` rotate row `line`; `Up` and `Down` rotate column `line`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoopoverMove {
+    pub line: usize,
+    pub direction: Direction,
+}
+
+impl LoopoverMove {
+    /// Returns true if the move rotates a row rather than a column
+    pub fn is_row(self) -> bool {
+        matches!(self.direction, Direction::Left | Direction::Right)
+    }
+}
+
+impl fmt::Display for LoopoverMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let axis = if self.is_row() { "row" } else { "column" };
+        write!(f, "{} {} {}", axis, self.line + 1, self.direction)
+    }
+}
+
+/// Square Loopover board
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoopoverBoard {
+    size: usize,
+    cells: Vec<usize>, // Tile number (1-based) in each cell, row by row
+}
+
+impl LoopoverBoard {
+    /// Creates a solved board
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::SizeTooSmall` or `PuzzleError::SizeTooLarge` if
+    /// `size` is outside `MIN_LOOPOVER_SIZE..=MAX_LOOPOVER_SIZE`
+    pub fn new(size: usize) -> Result<Self, PuzzleError> {
+        if size < MIN_LOOPOVER_SIZE {
+            return Err(PuzzleError::SizeTooSmall { size, min: MIN_LOOPOVER_SIZE });
+        }
+        if size > MAX_LOOPOVER_SIZE {
+            return Err(PuzzleError::SizeTooLarge { size, max: MAX_LOOPOVER_SIZE });
+        }
+        Ok(Self {
+            size,
+            cells: (1..=size * size).collect(),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of the tile at `pos`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` lies outside the board
+    pub fn tile_at(&self, pos: Position) -> usize {
+        assert!(pos.0 < self.size && pos.1 < self.size, "{:?} is off the board", pos);
+        self.cells[pos.0 * self.size + pos.1]
+    }
+
+    /// Returns the cell where `tile` belongs
+    pub fn home_of(&self, tile: usize) -> Position {
+        ((tile - 1) / self.size, (tile - 1) % self.size)
+    }
+
+    /// Rotates a row or column by one cell
+    /// Returns false (leaving the board unchanged) if `mv.line` is off the board
+    pub fn rotate(&mut self, mv: LoopoverMove) -> bool {
+        if mv.line >= self.size {
+            return false;
+        }
+        let n = self.size;
+        let cells: Vec<usize> = if mv.is_row() {
+            (0..n).map(|col| mv.line * n + col).collect()
+        } else {
+            (0..n).map(|row| row * n + mv.line).collect()
+        };
+        let mut line: Vec<usize> = cells.iter().map(|&i| self.cells[i]).collect();
+        match mv.direction {
+            Direction::Right | Direction::Down => line.rotate_right(1),
+            Direction::Left | Direction::Up => line.rotate_left(1),
+        }
+        for (&i, tile) in cells.iter().zip(line) {
+            self.cells[i] = tile;
+        }
+        true
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.cells.iter().enumerate().all(|(i, &tile)| tile == i + 1)
+    }
+
+    /// Returns the total distance of every tile from home, counting wraparound
+    pub fn wrapped_distance(&self) -> usize {
+        let n = self.size;
+        let wrap = |a: usize, b: usize| {
+            let d = a.abs_diff(b);
+            d.min(n - d)
+        };
+        (0..n * n)
+            .map(|i| {
+                let (row, col) = self.home_of(self.cells[i]);
+                wrap(row, i / n) + wrap(col, i % n)
+            })
+            .sum()
+    }
+}
+
+impl MoveSystem for LoopoverBoard {
+    type Move = LoopoverMove;
+
+    fn legal_moves(&self) -> Vec<LoopoverMove> {
+        (0..self.size)
+            .flat_map(|line| Direction::ALL.into_iter().map(move |direction| LoopoverMove { line, direction }))
+            .collect()
+    }
+
+    fn apply(&mut self, mv: LoopoverMove) -> bool {
+        self.rotate(mv)
+    }
+
+    fn inverse(&self, mv: LoopoverMove) -> LoopoverMove {
+        LoopoverMove {
+            line: mv.line,
+            direction: mv.direction.opposite(),
+        }
+    }
+
+    fn is_solved(&self) -> bool {
+        LoopoverBoard::is_solved(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(board: &LoopoverBoard) -> Vec<Vec<usize>> {
+        (0..board.size())
+            .map(|row| (0..board.size()).map(|col| board.tile_at((row, col))).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotations_wrap() {
+        let mut board = LoopoverBoard::new(3).unwrap();
+        assert!(board.rotate(LoopoverMove { line: 1, direction: Direction::Left }));
+        assert_eq!(rows(&board), vec![vec![1, 2, 3], vec![5, 6, 4], vec![7, 8, 9]]);
+        assert!(board.rotate(LoopoverMove { line: 0, direction: Direction::Down }));
+        assert_eq!(rows(&board), vec![vec![7, 2, 3], vec![1, 6, 4], vec![5, 8, 9]]);
+        assert_eq!(board.wrapped_distance(), 6);
+
+        assert!(board.rotate(LoopoverMove { line: 0, direction: Direction::Up }));
+        assert!(board.rotate(LoopoverMove { line: 1, direction: Direction::Right }));
+        assert!(board.is_solved());
+        assert!(!board.rotate(LoopoverMove { line: 3, direction: Direction::Up }));
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert!(matches!(LoopoverBoard::new(1), Err(PuzzleError::SizeTooSmall { .. })));
+        assert!(matches!(LoopoverBoard::new(11), Err(PuzzleError::SizeTooLarge { .. })));
+        let board = LoopoverBoard::new(4).unwrap();
+        assert_eq!(board.legal_moves().len(), 16);
+        assert_eq!(board.home_of(7), (1, 2));
+        assert_eq!(LoopoverMove { line: 2, direction: Direction::Down }.to_string(), "column 3 down");
+    }
+}
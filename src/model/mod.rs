@@ -6,9 +6,18 @@
 //! ## Core Components
 //!
 //! - [`PuzzleState`] - The main game state representing the tile grid
-//! - [`MoveValidator`] - Validates legal moves and resolves chain moves
+//! - [`GoalSpec`] - Alternative solved layouts (blank first, reversed, spiral)
+//! - [`MoveValidator`] - Resolves chain moves and previews them
 //! - [`Tile`] - Individual tile representation with content abstraction
 //! - [`Position`] - Grid position type with validation
+//! - [`PackedState`] - Compact layout copy used inside solvers
+//! - [`LayeredPuzzle`] - Stack of boards that must all be solved
+//! - [`Region`] - Rectangle of cells for scrambling part of a board
+//! - [`RelativePattern`] - Named move sequence replayable from any empty cell
+//! - [`RelativePatternCatalog`] - Named techniques suggested during play
+//! - [`KlotskiBoard`] - Klotski variant with blocks covering one to four cells
+//! - [`LoopoverBoard`] - Loopover variant whose rows and columns rotate, with no empty cell
+//! - [`MoveSystem`] - Rules shared by every board above, for code that plays any of them
 //!
 //! ## Entropy Calculation
 //!
@@ -16,13 +25,29 @@
 //!
 //! - [`ManhattanDistance`] - Fast heuristic based on tile displacement
 //! - [`ShortestPathHeuristic`] - Enhanced heuristic with linear conflict detection
-//! - [`ActualSolutionLength`] - Exact optimal solution length using A* search
+//! - [`ActualSolutionLength`] - Length of the solution A* search finds, near the optimum
 //! - [`EnhancedHeuristic`] - Combined heuristic for improved accuracy
+//! - [`WalkingDistance`] - Admissible table-driven heuristic that dominates Manhattan distance
+//! - [`HeuristicCache`] - Stores precomputed heuristic tables on disk between runs
+//! - [`EmptyCellPath`] - Manhattan distance plus the empty cell's walk to the nearest misplaced tile
+//! - [`EntropyKind`] - Names the calculators above so one can be picked as the primary
+//! - [`HeuristicLab`] - Compares calculators for admissibility, accuracy, and search effort
 //!
 //! ## Solving Algorithms
 //!
-//! - [`AStarSolver`] - Optimal pathfinding using the A* algorithm
+//! - [`AStarSolver`] - Fast, near-optimal pathfinding using the A* algorithm
+//!   ([`AStarSolverBuilder`] picks its heuristic and macro moves)
+//! - [`IDAStarSolver`] - Optimal iterative-deepening A* with memory linear in depth
+//! - [`GreedySolver`] - Fast, non-optimal greedy best-first search
+//! - [`StrategicSolver`] - Human-style row/column reduction for any grid size
+//! - [`EndgameTable`] - Exact distance of every 3×3 board; solves 3×3 games without searching
+//! - [`RetrogradeTable`] - Exact distances of chosen tiles by backward search, saved and memory-mapped
+//! - [`SolverKind`] - Selects one of the above for background solves
+//! - [`SymmetryGroup`] - Reflections a goal permits, so A* can close mirrored boards together
 //! - Supports cancellation and configurable iteration limits
+//! - Reports [`SolverProgress`] snapshots for progress displays
+//! - [`notation`] writes solutions as blank moves (`RDLU`) or tile numbers
+//! - [`analysis`] rates each move of a finished game against optimal play
 //! - Memory-efficient implementation using indexed storage
 //!
 //! ## Difficulty Levels
@@ -31,11 +56,17 @@
 //! - [`Difficulty::Easy`] - Low entropy puzzles
 //! - [`Difficulty::Medium`] - Moderate complexity
 //! - [`Difficulty::Hard`] - High complexity requiring more moves
+//! - [`Difficulty::Custom`] - User-chosen minimum entropy
+//!
+//! ## Scoring
+//!
+//! - [`Grade`] - S/A/B/C rating of tile moves against par (the optimal length)
 //!
 //! ## Performance Monitoring
 //!
 //! - [`PerformanceMetrics`] - Tracks algorithm execution times
 //! - [`PerformanceTimer`] - High-precision timing utilities
+//! - [`FrameMetrics`] - Costs of recent GUI frames, for diagnosing stutter
 //!
 //! ## Error Handling
 //!
@@ -47,7 +78,7 @@
 //! ## Example Usage
 //!
 //! ```rust
-//! use slider::model::{PuzzleState, Difficulty, ManhattanDistance};
+//! use slider::model::{EntropyCalculator, ManhattanDistance, PuzzleState};
 //!
 //! // Create a new 4×4 puzzle
 //! let mut puzzle = PuzzleState::new(4)?;
@@ -57,7 +88,7 @@
 //! println!("Initial entropy: {}", entropy);
 //!
 //! // Make a move
-//! puzzle.apply_immediate_move((3, 2))?;
+//! assert!(puzzle.apply_immediate_move((3, 2)));
 //!
 //! // Check if solved
 //! if puzzle.is_solved() {
@@ -66,20 +97,64 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+pub mod analysis;
+pub mod endgame;
 pub mod enhanced_heuristic;
 pub mod entropy;
+pub mod entropy_kind;
 pub mod error;
+pub mod goal;
+pub mod greedy_solver;
+pub mod heuristic_cache;
+pub mod heuristic_lab;
+pub mod ida_star_solver;
+pub mod klotski;
+pub mod layered_puzzle;
+pub mod loopover;
+pub mod move_system;
 pub mod move_validator;
+mod node_arena;
+pub mod notation;
+pub mod packed_state;
+pub mod pattern;
 pub mod performance;
 pub mod puzzle_state;
+pub mod region;
+pub mod retrograde;
+pub mod scoring;
 pub mod solver;
+pub mod strategic_solver;
+pub mod symmetry;
 pub mod tile;
+pub mod walking_distance;
 
+pub use endgame::{EndgameTable, ENDGAME_SIZE};
 pub use enhanced_heuristic::EnhancedHeuristic;
-pub use entropy::{Difficulty, EntropyCalculator, ManhattanDistance, ShortestPathHeuristic};
+pub use entropy::{Difficulty, EmptyCellPath, EntropyCalculator, ManhattanDistance, ShortestPathHeuristic, TuningTable};
+pub use entropy_kind::EntropyKind;
 pub use error::{AutoSolveError, PuzzleError, SolverError};
-pub use move_validator::{MoveValidator, Position};
-pub use performance::{PerformanceMetrics, PerformanceTimer};
+pub use goal::GoalSpec;
+pub use greedy_solver::GreedySolver;
+pub use heuristic_cache::{CachedTable, HeuristicCache};
+pub use heuristic_lab::{optimal_distances, AuditReport, AuditSummary, HeuristicLab, HeuristicReport, LabReport, MAX_AUDIT_SIZE};
+pub use ida_star_solver::IDAStarSolver;
+pub use klotski::{Block, BlockShape, Direction, KlotskiBoard, KlotskiMove, DEFAULT_KLOTSKI_STATES};
+pub use layered_puzzle::{LayeredPuzzle, MAX_LAYERS};
+pub use loopover::{LoopoverBoard, LoopoverMove};
+pub use move_system::MoveSystem;
+pub use move_validator::{MovePreview, MoveValidator, Position};
+pub use packed_state::PackedState;
+pub use pattern::{RelativePattern, RelativePatternCatalog};
+pub use performance::{FrameCost, FrameMetrics, PerformanceMetrics, PerformanceTimer, FRAME_BUDGET_MICROS};
 pub use puzzle_state::PuzzleState;
-pub use solver::{AStarSolver, ActualSolutionLength};
-pub use tile::{Tile, TileContent};
+pub use region::Region;
+pub use retrograde::{RetrogradeTable, TileMask, MAX_MASK_SIZE, MAX_RETROGRADE_STATES};
+pub use scoring::Grade;
+pub use solver::{
+    AStarSolver, AStarSolverBuilder, ActualSolutionLength, Solution, SolverConfig, SolverKind,
+    SolverProgress, TieBreak,
+};
+pub use strategic_solver::{Annotation, SolveStep, StrategicSolver};
+pub use symmetry::{Symmetry, SymmetryGroup};
+pub use tile::{Tile, TileContent, TileLabels};
+pub use walking_distance::WalkingDistance;
@@ -0,0 +1,221 @@
+//! # Move Systems
+//!
+//! A [`MoveSystem`] is the rule set of one member of the sliding-puzzle family:
+//! which moves a board offers, how each changes it, and when it is solved. Code
+//! written against the trait (scrambling, move counting, the variant controller)
+//! works for every member. The solvers, the shuffle controller and the game
+//! controller ask the classic board for its moves through the trait rather than
+//! working out adjacency themselves:
+//!
+//! - [`PuzzleState`] - tiles slide into the empty cell; a move names the tile
+//! - [`PackedState`] - the same rules on the compact copy searched by solvers
+//! - [`KlotskiBoard`] - blocks of one to four cells slide one cell into free space
+//! - [`LoopoverBoard`] - whole rows and columns rotate; there is no empty cell
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{LoopoverBoard, MoveSystem};
+//!
+//! let mut board = LoopoverBoard::new(4)?;
+//! let mv = board.legal_moves()[0];
+//! let undo = board.inverse(mv);
+//!
+//! assert!(board.apply(mv));
+//! assert!(!board.is_solved());
+//! assert!(board.apply(undo));
+//! assert!(board.is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`LoopoverBoard`]: super::loopover::LoopoverBoard
+
+use super::klotski::{Direction, KlotskiBoard, KlotskiMove};
+use super::move_validator::Position;
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+
+/// Rules for moving on one kind of board
+pub trait MoveSystem: Clone {
+    /// One move, as the player would make it
+    type Move: Copy + Eq + fmt::Debug;
+
+    /// Returns every move legal from the current board
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Applies `mv` if legal; returns false (leaving the board unchanged) otherwise
+    fn apply(&mut self, mv: Self::Move) -> bool;
+
+    /// Returns the move that undoes `mv` when played straight after it
+    /// Must be asked before `mv` is applied
+    fn inverse(&self, mv: Self::Move) -> Self::Move;
+
+    fn is_solved(&self) -> bool;
+
+    /// Returns true if `mv` can be played from the current board
+    fn is_legal(&self, mv: Self::Move) -> bool {
+        self.legal_moves().contains(&mv)
+    }
+
+    /// Plays up to `moves` random legal moves, never undoing the move just played
+    /// Returns the moves played, in order (fewer only if a board has no moves)
+    fn random_walk<R: Rng>(&mut self, moves: usize, rng: &mut R) -> Vec<Self::Move> {
+        let mut played = Vec::with_capacity(moves);
+        let mut undo = None;
+        for _ in 0..moves {
+            let candidates: Vec<Self::Move> = self
+                .legal_moves()
+                .into_iter()
+                .filter(|&mv| Some(mv) != undo)
+                .collect();
+            let Some(&mv) = candidates.choose(rng) else {
+                break;
+            };
+            undo = Some(self.inverse(mv));
+            self.apply(mv);
+            played.push(mv);
+        }
+        played
+    }
+}
+
+/// Returns the cells orthogonally adjacent to `empty` on a `size`×`size` board
+/// Ordered up, down, left, right, so seeded walks stay repeatable
+fn tiles_around(empty: Position, size: usize) -> Vec<Position> {
+    let (row, col) = empty;
+    Direction::ALL
+        .into_iter()
+        .filter_map(|direction| {
+            let (dr, dc) = direction.delta();
+            let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+            (pos.0 < size && pos.1 < size).then_some(pos)
+        })
+        .collect()
+}
+
+/// Classic sliding tiles: a move slides the named tile into the adjacent empty cell
+///
+/// Chain moves (sliding a whole line toward the empty cell) are a player
+/// convenience built from these; see `MoveValidator::resolve_chain_move`.
+impl MoveSystem for PuzzleState {
+    type Move = Position;
+
+    fn legal_moves(&self) -> Vec<Position> {
+        tiles_around(self.empty_position(), self.size())
+    }
+
+    fn apply(&mut self, mv: Position) -> bool {
+        self.apply_immediate_move(mv)
+    }
+
+    /// The tile slides back out of the cell that was empty
+    fn inverse(&self, _mv: Position) -> Position {
+        self.empty_position()
+    }
+
+    fn is_solved(&self) -> bool {
+        PuzzleState::is_solved(self)
+    }
+
+    fn is_legal(&self, mv: Position) -> bool {
+        let empty = self.empty_position();
+        mv.0 < self.size() && mv.1 < self.size() && mv.0.abs_diff(empty.0) + mv.1.abs_diff(empty.1) == 1
+    }
+}
+
+impl MoveSystem for PackedState {
+    type Move = Position;
+
+    fn legal_moves(&self) -> Vec<Position> {
+        tiles_around(self.empty_position(), self.size())
+    }
+
+    fn apply(&mut self, mv: Position) -> bool {
+        self.apply_immediate_move(mv)
+    }
+
+    fn inverse(&self, _mv: Position) -> Position {
+        self.empty_position()
+    }
+
+    fn is_solved(&self) -> bool {
+        PackedState::is_solved(self)
+    }
+}
+
+impl MoveSystem for KlotskiBoard {
+    type Move = KlotskiMove;
+
+    fn legal_moves(&self) -> Vec<KlotskiMove> {
+        KlotskiBoard::legal_moves(self)
+    }
+
+    fn apply(&mut self, mv: KlotskiMove) -> bool {
+        self.apply_move(mv)
+    }
+
+    fn inverse(&self, mv: KlotskiMove) -> KlotskiMove {
+        KlotskiMove {
+            block: mv.block,
+            direction: mv.direction.opposite(),
+        }
+    }
+
+    fn is_solved(&self) -> bool {
+        KlotskiBoard::is_solved(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::LoopoverBoard;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Replays a random walk from `board`, then undoes it move by move
+    /// Boards are compared by `layout`
+    fn round_trip<M: MoveSystem, L: PartialEq + fmt::Debug>(board: M, steps: usize, layout: impl Fn(&M) -> L) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let walk = board.clone().random_walk(steps, &mut rng);
+        assert_eq!(walk.len(), steps);
+
+        let mut walked = board.clone();
+        let mut undo = Vec::new();
+        for &mv in &walk {
+            undo.push(walked.inverse(mv));
+            assert!(walked.apply(mv));
+        }
+        assert!(walk.windows(2).zip(&undo).all(|(pair, &back)| pair[1] != back));
+        for &mv in undo.iter().rev() {
+            assert!(walked.legal_moves().contains(&mv), "{:?} cannot be played", mv);
+            assert!(walked.apply(mv));
+        }
+        assert_eq!(layout(&walked), layout(&board));
+    }
+
+    #[test]
+    fn test_walks_undo_on_every_board() {
+        round_trip(PuzzleState::new(4).unwrap(), 40, PuzzleState::to_notation);
+        round_trip(PackedState::from_state(&PuzzleState::new(5).unwrap()), 50, PackedState::clone);
+        round_trip(KlotskiBoard::classic(), 30, KlotskiBoard::clone);
+        round_trip(LoopoverBoard::new(5).unwrap(), 60, LoopoverBoard::clone);
+    }
+
+    #[test]
+    fn test_sliding_moves_name_neighbours_of_the_empty_cell() {
+        let mut state = PuzzleState::new(3).unwrap();
+        let mut moves = MoveSystem::legal_moves(&state);
+        moves.sort();
+        assert_eq!(moves, vec![(1, 2), (2, 1)]);
+        assert_eq!(state.inverse((1, 2)), (2, 2));
+        assert!(state.is_legal((2, 1)));
+        assert!(!state.is_legal((0, 0)));
+        assert!(!state.is_legal((2, 3)));
+        assert!(MoveSystem::apply(&mut state, (1, 2)));
+        assert!(!MoveSystem::apply(&mut state, (0, 0)));
+    }
+}
@@ -5,6 +5,31 @@ use std::collections::HashSet;
 /// Represents a position in the grid (row, col)
 pub type Position = (usize, usize);
 
+/// What a move would do to the board, worked out without applying it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovePreview {
+    /// Each tile that would move as (from, to), nearest the empty cell first
+    pub shifts: Vec<(Position, Position)>,
+    /// Where the empty cell ends up (the clicked position)
+    pub empty_after: Position,
+}
+
+impl MovePreview {
+    /// Returns the current positions of the tiles that would move
+    pub fn tiles(&self) -> impl Iterator<Item = Position> + '_ {
+        self.shifts.iter().map(|&(from, _)| from)
+    }
+
+    /// Returns how many tiles would move
+    pub fn len(&self) -> usize {
+        self.shifts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shifts.is_empty()
+    }
+}
+
 /// Validates moves based on empty cell position and grid constraints
 pub struct MoveValidator {
     grid_size: usize,
@@ -163,6 +188,26 @@ impl MoveValidator {
 
         Some(moves)
     }
+
+    /// Works out which tiles a chain move from `from` would shift, and where to
+    /// Returns None if the move is not legal or `from` is the empty cell
+    pub fn preview_chain_move(&self, from: Position, empty_pos: Position) -> Option<MovePreview> {
+        if from == empty_pos {
+            return None;
+        }
+        let moves = self.resolve_chain_move(from, empty_pos)?;
+
+        // Each immediate move slides its tile into the cell the previous one vacated
+        let mut empty = empty_pos;
+        let shifts = moves
+            .into_iter()
+            .map(|pos| (pos, std::mem::replace(&mut empty, pos)))
+            .collect();
+        Some(MovePreview {
+            shifts,
+            empty_after: empty,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -224,6 +269,25 @@ mod tests {
         assert_eq!(moves, vec![(1, 2)]); // Single immediate move
     }
 
+    #[test]
+    fn test_preview_chain_move() {
+        let validator = MoveValidator::new(4).unwrap();
+        // Empty at (1, 3), click tile at (1, 0): three tiles shift right
+        let preview = validator.preview_chain_move((1, 0), (1, 3)).unwrap();
+        assert_eq!(
+            preview.shifts,
+            vec![((1, 2), (1, 3)), ((1, 1), (1, 2)), ((1, 0), (1, 1))]
+        );
+        assert_eq!(preview.empty_after, (1, 0));
+        assert_eq!(preview.tiles().collect::<Vec<_>>(), vec![(1, 2), (1, 1), (1, 0)]);
+
+        let preview = validator.preview_chain_move((0, 2), (1, 2)).unwrap();
+        assert_eq!(preview.shifts, vec![((0, 2), (1, 2))]);
+
+        assert!(validator.preview_chain_move((0, 0), (1, 3)).is_none());
+        assert!(validator.preview_chain_move((1, 3), (1, 3)).is_none());
+    }
+
     #[test]
     fn test_illegal_move() {
         let validator = MoveValidator::new(4).unwrap();
@@ -0,0 +1,200 @@
+//! # Solution Notation
+//!
+//! Solver paths list the board positions of the tiles to slide, which is what the
+//! game needs but hard for people to follow. This module writes them in the two
+//! notations used when sharing sliding-puzzle solutions:
+//!
+//! - **Blank moves**: one letter per move for the direction the empty cell
+//!   travels (`U`, `D`, `L`, `R`), so `RD` means the blank swaps with the tile to
+//!   its right, then with the tile below.
+//! - **Tile numbers**: the number of each tile as it slides, such as `12 8 7`.
+//!
+//! Blank moves can also be read back, so solutions from other solvers or from
+//! books can be checked on a board.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::notation::{blank_moves, tile_numbers};
+//! use slider::model::PuzzleState;
+//!
+//! let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse()?;
+//! let path = [(2, 1), (2, 2)];
+//! assert_eq!(blank_moves(puzzle.empty_position(), &path), "DR");
+//! assert_eq!(tile_numbers(&puzzle, &path), "5 8");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::error::PuzzleError;
+use super::klotski::Direction;
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+
+/// Returns the direction the empty cell travels when the tile at `tile` slides into it
+/// Returns None if the two cells are not orthogonally adjacent
+pub fn blank_direction(empty: Position, tile: Position) -> Option<Direction> {
+    let delta = (
+        tile.0 as isize - empty.0 as isize,
+        tile.1 as isize - empty.1 as isize,
+    );
+    Direction::ALL.into_iter().find(|d| d.delta() == delta)
+}
+
+/// Returns the letter for a blank move in `direction`
+pub fn blank_letter(direction: Direction) -> char {
+    match direction {
+        Direction::Up => 'U',
+        Direction::Down => 'D',
+        Direction::Left => 'L',
+        Direction::Right => 'R',
+    }
+}
+
+/// Writes `path`, played from a board whose empty cell is at `empty`, as blank moves
+///
+/// A step that is not a single slide into the empty cell is written as `?`;
+/// solver paths never contain one.
+pub fn blank_moves(empty: Position, path: &[Position]) -> String {
+    let mut empty = empty;
+    path.iter()
+        .map(|&tile| {
+            let letter = blank_direction(empty, tile).map_or('?', blank_letter);
+            empty = tile;
+            letter
+        })
+        .collect()
+}
+
+/// Moves read from blank-move notation, up to the first one that cannot be played
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadMoves {
+    /// Tile positions to move, in order
+    pub path: Vec<Position>,
+    /// Why reading stopped early, if it did
+    pub error: Option<PuzzleError>,
+}
+
+/// Reads blank moves for an n×n board whose empty cell starts at `empty`
+///
+/// Letters may be upper or lower case, and whitespace is ignored. Reading stops
+/// at the first letter that is not `U`, `D`, `L` or `R`, or that would move the
+/// empty cell off the board; the moves before it are kept so they can still be
+/// shown.
+pub fn read_blank_moves(empty: Position, size: usize, text: &str) -> ReadMoves {
+    let mut path = Vec::new();
+    let mut empty = empty;
+    for (index, letter) in text.chars().filter(|c| !c.is_whitespace()).enumerate() {
+        let invalid = |reason: String| PuzzleError::InvalidSolution { step: index + 1, reason };
+        let Some(direction) = Direction::ALL
+            .into_iter()
+            .find(|&d| blank_letter(d) == letter.to_ascii_uppercase())
+        else {
+            return ReadMoves {
+                path,
+                error: Some(invalid(format!("'{}' is not U, D, L or R", letter))),
+            };
+        };
+
+        let (dr, dc) = direction.delta();
+        let tile = (empty.0.wrapping_add_signed(dr), empty.1.wrapping_add_signed(dc));
+        if tile.0 >= size || tile.1 >= size {
+            return ReadMoves {
+                path,
+                error: Some(invalid(format!("{} moves the empty cell off the board", blank_letter(direction)))),
+            };
+        }
+        path.push(tile);
+        empty = tile;
+    }
+    ReadMoves { path, error: None }
+}
+
+/// Parses blank moves for `state`, rejecting the text if any move cannot be played
+///
+/// # Errors
+///
+/// Returns `PuzzleError::InvalidSolution` for the first bad move (see [`read_blank_moves`])
+pub fn parse_blank_moves(state: &PuzzleState, text: &str) -> Result<Vec<Position>, PuzzleError> {
+    let moves = read_blank_moves(state.empty_position(), state.size(), text);
+    match moves.error {
+        Some(error) => Err(error),
+        None => Ok(moves.path),
+    }
+}
+
+/// Writes `path`, played from `state`, as the numbers of the tiles that slide
+///
+/// Numbers follow the board's goal, as shown on the tiles. A step without a
+/// tile to slide is written as `?`.
+pub fn tile_numbers(state: &PuzzleState, path: &[Position]) -> String {
+    let mut replay = state.clone();
+    path.iter()
+        .map(|&pos| {
+            let number = replay.tile_number(pos).map_or("?".to_string(), |n| n.to_string());
+            replay.apply_immediate_move(pos);
+            number
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::scramble;
+    use crate::model::{GoalSpec, SolverConfig, SolverKind};
+
+    #[test]
+    fn test_blank_moves() {
+        assert_eq!(blank_moves((1, 1), &[(0, 1), (0, 0), (1, 0), (1, 1)]), "ULDR");
+        assert_eq!(blank_moves((1, 1), &[(1, 2), (2, 0)]), "R?");
+        assert_eq!(blank_moves((0, 0), &[]), "");
+    }
+
+    #[test]
+    fn test_read_blank_moves() {
+        let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        assert_eq!(parse_blank_moves(&puzzle, "d r"), Ok(vec![(2, 1), (2, 2)]));
+
+        let moves = read_blank_moves((1, 1), 3, "DRD");
+        assert_eq!(moves.path, vec![(2, 1), (2, 2)]);
+        assert_eq!(
+            moves.error,
+            Some(PuzzleError::InvalidSolution {
+                step: 3,
+                reason: "D moves the empty cell off the board".to_string(),
+            })
+        );
+        let moves = read_blank_moves((1, 1), 3, "U x");
+        assert_eq!(moves.path, vec![(0, 1)]);
+        assert!(matches!(moves.error, Some(PuzzleError::InvalidSolution { step: 2, .. })));
+    }
+
+    #[test]
+    fn test_blank_moves_round_trip() {
+        let puzzle = PuzzleState::new(4).unwrap();
+        let path = parse_blank_moves(&puzzle, "LLUURRDDLU").unwrap();
+        assert_eq!(blank_moves(puzzle.empty_position(), &path), "LLUURRDDLU");
+    }
+
+    #[test]
+    fn test_tile_numbers_follow_the_goal() {
+        let mut puzzle = PuzzleState::with_goal(3, GoalSpec::Reversed).unwrap();
+        assert_eq!(puzzle.empty_position(), (0, 0));
+        assert_eq!(tile_numbers(&puzzle, &[(0, 1), (1, 1)]), "8 5");
+        puzzle.apply_immediate_move((0, 1));
+        assert_eq!(tile_numbers(&puzzle, &[(0, 1)]), "?");
+    }
+
+    #[test]
+    fn test_solution_display() {
+        let puzzle = scramble(3, 20, 3);
+        let solution = SolverKind::AStar
+            .try_solve_instrumented(SolverConfig::default(), &puzzle, None, &mut |_| {})
+            .unwrap();
+        let notation = solution.to_string();
+        assert_eq!(notation.len(), solution.len());
+        assert!(notation.chars().all(|c| "UDLR".contains(c)));
+        assert_eq!(notation, blank_moves(puzzle.empty_position(), &solution.path));
+    }
+}
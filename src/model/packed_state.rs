@@ -0,0 +1,370 @@
+//! # Packed Puzzle State
+//!
+//! [`PuzzleState`] keeps a full `Tile` per cell, which is convenient for the game but
+//! expensive for solvers that store hundreds of thousands of states. [`PackedState`]
+//! stores only which tile occupies each cell:
+//!
+//! | Grid        | Encoding                         | Cell storage |
+//! |-------------|----------------------------------|--------------|
+//! | up to 3×3   | 4 bits per cell in one word      | 16 bytes     |
+//! | 4×4 and 5×5 | 5 bits per cell in one word      | 16 bytes     |
+//! | 6×6 to 15×15| 1 byte per cell on the heap      | n² bytes     |
+//! | 16×16 and up| 2 bytes per cell on the heap     | 2n² bytes    |
+//!
+//! Each cell holds its tile's goal index plus one (`row * n + col + 1`), with 0 for
+//! the empty cell. Packed states are hashable and compare by layout, so solvers use
+//! them directly for duplicate detection.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{PackedState, PuzzleState};
+//!
+//! let mut puzzle = PuzzleState::new(4)?;
+//! puzzle.apply_immediate_move((3, 2));
+//!
+//! let mut packed = PackedState::from_state(&puzzle);
+//! assert!(!packed.is_solved());
+//! assert!(packed.apply_immediate_move((3, 3)));
+//! assert!(packed.is_solved());
+//! assert!(packed.to_state().is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+use super::symmetry::Symmetry;
+use super::tile::Tile;
+
+/// Largest grid whose cells fit in a single 128-bit word
+const MAX_WORD_SIZE: usize = 5;
+
+/// Largest grid whose cell values (up to n²) fit in a byte
+const MAX_BYTE_SIZE: usize = 15;
+
+/// Cell storage: bit-packed for small grids, one byte per cell for mid-sized
+/// grids, and two bytes per cell once n² no longer fits in a byte
+///
+/// The word is split into 64-bit halves so the struct keeps 8-byte alignment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Cells {
+    Word([u64; 2]),
+    Bytes(Box<[u8]>),
+    Wide(Box<[u16]>),
+}
+
+/// Compact copy of a puzzle layout for use inside solvers
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackedState {
+    cells: Cells,
+    size: u8,
+    empty: u16, // Row-major index of the empty cell
+}
+
+impl PackedState {
+    /// Packs the layout of `state`
+    pub fn from_state(state: &PuzzleState) -> Self {
+        let size = state.size();
+        let mut values = vec![0u16; size * size];
+        for ((row, col), tile) in state.tiles() {
+            let (home_row, home_col) = tile.home_position;
+            values[row * size + col] = (home_row * size + home_col + 1) as u16;
+        }
+        let (empty_row, empty_col) = state.empty_position();
+        Self::from_values(size, values, empty_row * size + empty_col)
+    }
+
+    /// Packs cell values (goal index plus one, 0 for the empty cell at `empty`)
+    fn from_values(size: usize, values: Vec<u16>, empty: usize) -> Self {
+        let cells = if size <= MAX_WORD_SIZE {
+            let bits = bits_per_cell(size);
+            let word = values
+                .iter()
+                .enumerate()
+                .fold(0u128, |word, (i, &v)| word | (v as u128) << (i * bits));
+            Cells::Word(split(word))
+        } else if size <= MAX_BYTE_SIZE {
+            Cells::Bytes(values.into_iter().map(|v| v as u8).collect())
+        } else {
+            Cells::Wide(values.into_boxed_slice())
+        };
+
+        Self {
+            cells,
+            size: size as u8,
+            empty: empty as u16,
+        }
+    }
+
+    /// Returns the board reflected or rotated by `symmetry`, each tile renamed
+    /// after its moved home cell
+    ///
+    /// The image is as far from the goal as this board when `symmetry` keeps the
+    /// goal's empty cell in place (see `SymmetryGroup`).
+    pub fn transformed(&self, symmetry: Symmetry) -> Self {
+        let n = self.size();
+        let index = |pos: Position| {
+            let (row, col) = symmetry.apply(pos, n);
+            row * n + col
+        };
+        let mut values = vec![0u16; n * n];
+        for i in 0..n * n {
+            if let Some(home) = self.tile_at((i / n, i % n)) {
+                values[index((i / n, i % n))] = (index((home / n, home % n)) + 1) as u16;
+            }
+        }
+        Self::from_values(n, values, index(self.empty_position()))
+    }
+
+    /// Rebuilds the full puzzle state
+    pub fn to_state(&self) -> PuzzleState {
+        let n = self.size();
+        let grid = (0..n)
+            .map(|row| {
+                (0..n)
+                    .map(|col| {
+                        self.tile_at((row, col)).map(|home| {
+                            Tile::new_numeric(home as u32 + 1, (home / n, home % n))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        PuzzleState::from_grid(grid, self.empty_position())
+    }
+
+    /// Returns the number of bytes `write_bytes` appends for a `size`×`size` board
+    pub(super) fn encoded_len(size: usize) -> usize {
+        let cells = if size <= MAX_WORD_SIZE {
+            (size * size * bits_per_cell(size)).div_ceil(8)
+        } else if size <= MAX_BYTE_SIZE {
+            size * size
+        } else {
+            2 * size * size
+        };
+        cells + 2 // The empty cell's index
+    }
+
+    /// Appends the layout to `out` as `encoded_len` bytes, for arenas that
+    /// store many boards in one buffer
+    pub(super) fn write_bytes(&self, out: &mut Vec<u8>) {
+        match &self.cells {
+            Cells::Word(halves) => {
+                let len = Self::encoded_len(self.size()) - 2;
+                out.extend_from_slice(&join(*halves).to_le_bytes()[..len]);
+            }
+            Cells::Bytes(bytes) => out.extend_from_slice(bytes),
+            Cells::Wide(cells) => out.extend(cells.iter().flat_map(|cell| cell.to_le_bytes())),
+        }
+        out.extend_from_slice(&self.empty.to_le_bytes());
+    }
+
+    /// Restores a `size`×`size` layout written by `write_bytes`
+    pub(super) fn read_bytes(size: usize, bytes: &[u8]) -> Self {
+        let (cells, empty) = bytes.split_at(bytes.len() - 2);
+        let cells = if size <= MAX_WORD_SIZE {
+            let mut word = [0u8; 16];
+            word[..cells.len()].copy_from_slice(cells);
+            Cells::Word(split(u128::from_le_bytes(word)))
+        } else if size <= MAX_BYTE_SIZE {
+            Cells::Bytes(cells.into())
+        } else {
+            Cells::Wide(cells.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect())
+        };
+        Self {
+            cells,
+            size: size as u8,
+            empty: u16::from_le_bytes([empty[0], empty[1]]),
+        }
+    }
+
+    /// Returns the bytes stored outside the struct itself (0 up to 5×5)
+    pub fn heap_bytes(&self) -> usize {
+        match self.cells {
+            Cells::Word(_) => 0,
+            Cells::Bytes(ref bytes) => bytes.len(),
+            Cells::Wide(ref cells) => std::mem::size_of_val(&**cells),
+        }
+    }
+
+    /// Returns the size of the grid (n for an n×n puzzle)
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Returns the position of the empty cell
+    pub fn empty_position(&self) -> Position {
+        let n = self.size();
+        let empty = self.empty as usize;
+        (empty / n, empty % n)
+    }
+
+    /// Returns the goal index (`row * n + col`) of the tile at `pos`
+    /// Returns None for the empty cell
+    pub fn tile_at(&self, (row, col): Position) -> Option<usize> {
+        match self.cell(row * self.size() + col) {
+            0 => None,
+            v => Some(v as usize - 1),
+        }
+    }
+
+    /// Slides the tile at `from` into the adjacent empty cell
+    /// Returns false if `from` is not orthogonally adjacent to the empty cell
+    pub fn apply_immediate_move(&mut self, from: Position) -> bool {
+        let (empty_row, empty_col) = self.empty_position();
+        if from.0.abs_diff(empty_row) + from.1.abs_diff(empty_col) != 1 {
+            return false;
+        }
+
+        let from_index = from.0 * self.size() + from.1;
+        let value = self.cell(from_index);
+        self.set_cell(self.empty as usize, value);
+        self.set_cell(from_index, 0);
+        self.empty = from_index as u16;
+        true
+    }
+
+    /// Returns true if every tile is in its goal position
+    /// The empty cell may be anywhere, so goals other than the standard one work too
+    pub fn is_solved(&self) -> bool {
+        let cells = self.size() * self.size();
+        (0..cells).all(|i| {
+            let value = self.cell(i) as usize;
+            value == 0 || value == i + 1
+        })
+    }
+
+    fn cell(&self, index: usize) -> u16 {
+        match &self.cells {
+            Cells::Word(halves) => {
+                let bits = bits_per_cell(self.size());
+                ((join(*halves) >> (index * bits)) & ((1 << bits) - 1)) as u16
+            }
+            Cells::Bytes(bytes) => bytes[index] as u16,
+            Cells::Wide(cells) => cells[index],
+        }
+    }
+
+    fn set_cell(&mut self, index: usize, value: u16) {
+        let bits = bits_per_cell(self.size());
+        match &mut self.cells {
+            Cells::Word(halves) => {
+                let shift = index * bits;
+                let word = (join(*halves) & !(((1u128 << bits) - 1) << shift)) | (value as u128) << shift;
+                *halves = split(word);
+            }
+            Cells::Bytes(bytes) => bytes[index] = value as u8,
+            Cells::Wide(cells) => cells[index] = value,
+        }
+    }
+}
+
+impl From<&PuzzleState> for PackedState {
+    fn from(state: &PuzzleState) -> Self {
+        Self::from_state(state)
+    }
+}
+
+impl From<&PackedState> for PuzzleState {
+    fn from(packed: &PackedState) -> Self {
+        packed.to_state()
+    }
+}
+
+fn split(word: u128) -> [u64; 2] {
+    [word as u64, (word >> 64) as u64]
+}
+
+fn join([low, high]: [u64; 2]) -> u128 {
+    (high as u128) << 64 | low as u128
+}
+
+/// Bits needed to store values 0..=n² (only meaningful for word-packed sizes)
+///
+/// A 4×4 board needs 5: under goals other than the standard one, a tile can
+/// belong in the last cell and so store 16.
+fn bits_per_cell(size: usize) -> usize {
+    (usize::BITS - (size * size).leading_zeros()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::GoalSpec;
+
+    #[test]
+    fn test_round_trip() {
+        for size in [3, 4, 5, 6, 9, 16, 22] {
+            let puzzle = scramble(size, 50, size as u64);
+            let packed = PackedState::from_state(&puzzle);
+            assert_eq!(packed.size(), size);
+            assert_eq!(packed.empty_position(), puzzle.empty_position());
+            assert_eq!(packed.to_state().to_layout(), puzzle.to_layout());
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_every_goal() {
+        for goal in GoalSpec::ALL {
+            for size in [3, 4, 5] {
+                let puzzle = scramble_from(PuzzleState::with_goal(size, goal).unwrap(), 60, size as u64);
+                let packed = PackedState::from_state(&puzzle);
+                // Packed states keep each tile's home, not the goal's numbering
+                let homes = |state: &PuzzleState| state.tiles().map(|(pos, tile)| (pos, tile.home_position)).collect::<Vec<_>>();
+                assert_eq!(homes(&packed.to_state()), homes(&puzzle), "{} on {}×{}", goal, size, size);
+                assert_eq!(packed.is_solved(), puzzle.is_solved());
+                let mut bytes = Vec::new();
+                packed.write_bytes(&mut bytes);
+                assert_eq!(PackedState::read_bytes(size, &bytes), packed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_moves_match_puzzle_state() {
+        for size in [4, 5, 7, 22] {
+            let mut puzzle = PuzzleState::new(size).unwrap();
+            let mut packed = PackedState::from_state(&puzzle);
+            assert!(packed.is_solved());
+
+            let moves = [(size - 1, size - 2), (size - 2, size - 2), (size - 2, size - 1)];
+            for pos in moves {
+                assert!(puzzle.apply_immediate_move(pos));
+                assert!(packed.apply_immediate_move(pos));
+                assert_eq!(packed, PackedState::from_state(&puzzle));
+            }
+            assert!(!packed.is_solved());
+            assert!(!packed.apply_immediate_move((0, 0)));
+        }
+    }
+
+    #[test]
+    fn test_byte_encoding_round_trip() {
+        for size in [3, 4, 5, 6, 16] {
+            let packed = PackedState::from_state(&scramble(size, 40, size as u64));
+            let mut bytes = vec![7];
+            packed.write_bytes(&mut bytes);
+            assert_eq!(bytes.len(), 1 + PackedState::encoded_len(size));
+            assert_eq!(PackedState::read_bytes(size, &bytes[1..]), packed);
+        }
+        assert_eq!(PackedState::encoded_len(3), 7);
+        assert_eq!(PackedState::encoded_len(4), 12);
+    }
+
+    #[test]
+    fn test_tile_at() {
+        let packed = PackedState::from_state(&"1 2 3 / 4 5 6 / 7 _ 8".parse().unwrap());
+        assert_eq!(packed.tile_at((0, 0)), Some(0));
+        assert_eq!(packed.tile_at((2, 2)), Some(7));
+        assert_eq!(packed.tile_at((2, 1)), None);
+    }
+
+    #[test]
+    fn test_compact_footprint() {
+        assert!(std::mem::size_of::<PackedState>() <= 32);
+        assert_eq!(PackedState::from_state(&PuzzleState::new(5).unwrap()).heap_bytes(), 0);
+        assert_eq!(PackedState::from_state(&PuzzleState::new(6).unwrap()).heap_bytes(), 36);
+        assert_eq!(PackedState::from_state(&PuzzleState::new(16).unwrap()).heap_bytes(), 512);
+    }
+}
@@ -0,0 +1,436 @@
+//! # Relative Move Patterns
+//!
+//! A [`RelativePattern`] is a named sequence of single-tile slides described
+//! relative to the empty cell rather than by board position, so the same
+//! pattern can be replayed wherever the empty cell happens to be. Each step is
+//! the [`Direction`] the tile slides into the empty cell.
+//!
+//! A pattern can be rotated and mirrored, so one recorded shape covers all
+//! eight orientations. [`RelativePatternCatalog`] collects named techniques
+//! and finds the orientations that fit at the current empty cell. Besides the
+//! built-in techniques, a catalog can be mined from optimal solutions: loops
+//! that bring the empty cell back where it started and recur across many
+//! solutions are kept as learned techniques.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{Direction, PuzzleState, RelativePattern};
+//!
+//! // Slide the tile left of the empty cell right, then the one above it down
+//! let pattern = RelativePattern::new("corner", vec![Direction::Right, Direction::Down]);
+//! let puzzle = PuzzleState::new(3)?;
+//! assert_eq!(pattern.positions_at(&puzzle), Some(vec![(2, 1), (1, 1)]));
+//!
+//! // From the top-left corner there is no tile to the left
+//! let corner: PuzzleState = "_ 1 2 / 3 4 5 / 6 7 8".parse()?;
+//! assert_eq!(pattern.positions_at(&corner), None);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::klotski::Direction;
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A named sequence of slides relative to the empty cell
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelativePattern {
+    name: String,
+    steps: Vec<Direction>,
+}
+
+impl RelativePattern {
+    pub fn new(name: impl Into<String>, steps: Vec<Direction>) -> Self {
+        Self {
+            name: name.into(),
+            steps,
+        }
+    }
+
+    /// Builds a pattern from immediate moves played from `empty`
+    /// Returns None if a move is not next to the empty cell at that point
+    pub fn from_moves(
+        name: impl Into<String>,
+        empty: Position,
+        moves: &[Position],
+    ) -> Option<Self> {
+        let mut pattern = Self::new(name, Vec::with_capacity(moves.len()));
+        let mut empty = empty;
+        for &tile in moves {
+            if !pattern.push_move(tile, empty) {
+                return None;
+            }
+            empty = tile;
+        }
+        Some(pattern)
+    }
+
+    /// Appends the slide of `tile` into the adjacent `empty` cell
+    /// Returns false (leaving the pattern unchanged) if they are not adjacent
+    pub fn push_move(&mut self, tile: Position, empty: Position) -> bool {
+        let delta = (
+            empty.0 as isize - tile.0 as isize,
+            empty.1 as isize - tile.1 as isize,
+        );
+        match Direction::ALL.into_iter().find(|d| d.delta() == delta) {
+            Some(direction) => {
+                self.steps.push(direction);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    /// Returns the slides in order
+    pub fn steps(&self) -> &[Direction] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Returns the immediate moves that play the pattern on `state`
+    /// Returns None if any step would take the empty cell off the board
+    pub fn positions_at(&self, state: &PuzzleState) -> Option<Vec<Position>> {
+        self.positions_from(state.empty_position(), state.size())
+    }
+
+    /// Returns the immediate moves that play the pattern with the empty cell at
+    /// `empty` on a `size`×`size` board
+    /// Returns None if any step would take the empty cell off the board
+    pub fn positions_from(&self, empty: Position, size: usize) -> Option<Vec<Position>> {
+        let (mut row, mut col) = empty;
+        self.steps
+            .iter()
+            .map(|direction| {
+                // The tile comes from the opposite side of the empty cell
+                let (dr, dc) = direction.delta();
+                let tile = (row.checked_add_signed(-dr)?, col.checked_add_signed(-dc)?);
+                if tile.0 >= size || tile.1 >= size {
+                    return None;
+                }
+                (row, col) = tile;
+                Some(tile)
+            })
+            .collect()
+    }
+
+    /// Returns true if the whole pattern fits on `state` from its empty cell
+    pub fn applies_to(&self, state: &PuzzleState) -> bool {
+        self.positions_at(state).is_some()
+    }
+
+    /// Returns the pattern turned a quarter turn clockwise
+    pub fn rotated(&self) -> Self {
+        self.map_steps(|direction| match direction {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        })
+    }
+
+    /// Returns the pattern mirrored left to right
+    pub fn mirrored(&self) -> Self {
+        self.map_steps(|direction| match direction {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            other => other,
+        })
+    }
+
+    /// Returns every distinct rotation and reflection, starting with the pattern itself
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations: Vec<Self> = Vec::with_capacity(8);
+        for start in [self.clone(), self.mirrored()] {
+            let mut turned = start;
+            for _ in 0..4 {
+                let next = turned.rotated();
+                if !orientations.iter().any(|o| o.steps == turned.steps) {
+                    orientations.push(turned);
+                }
+                turned = next;
+            }
+        }
+        orientations
+    }
+
+    /// Returns the orientation with the smallest steps, shared by every orientation
+    pub fn canonical(&self) -> Self {
+        self.orientations()
+            .into_iter()
+            .min_by(|a, b| a.steps.cmp(&b.steps))
+            .expect("a pattern is one of its own orientations")
+    }
+
+    /// Returns true if the steps bring the empty cell back to where it started
+    pub fn is_loop(&self) -> bool {
+        let (dr, dc) = self.steps.iter().fold((0, 0), |(r, c), d| {
+            let (dr, dc) = d.delta();
+            (r + dr, c + dc)
+        });
+        !self.steps.is_empty() && dr == 0 && dc == 0
+    }
+
+    fn map_steps(&self, f: impl Fn(Direction) -> Direction) -> Self {
+        Self::new(
+            self.name.clone(),
+            self.steps.iter().map(|&d| f(d)).collect(),
+        )
+    }
+}
+
+impl fmt::Display for RelativePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} moves)", self.name, self.steps.len())
+    }
+}
+
+/// Named techniques that can be suggested during play
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelativePatternCatalog {
+    patterns: Vec<RelativePattern>,
+}
+
+impl RelativePatternCatalog {
+    pub fn new(patterns: Vec<RelativePattern>) -> Self {
+        Self { patterns }
+    }
+
+    /// The built-in techniques
+    ///
+    /// - Corner rotation: cycles the three tiles of a 2×2 block around the empty cell
+    /// - Long rotation: cycles the five tiles of a 2×3 block around the empty cell
+    pub fn builtin() -> Self {
+        use Direction::{Down, Left, Right, Up};
+        Self::new(vec![
+            RelativePattern::new("Corner rotation", vec![Right, Down, Left, Up]),
+            RelativePattern::new("Long rotation", vec![Right, Right, Down, Left, Left, Up]),
+        ])
+    }
+
+    /// Returns the techniques in catalog order
+    pub fn patterns(&self) -> &[RelativePattern] {
+        &self.patterns
+    }
+
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Mines recurring loops from solutions, most frequent first
+    ///
+    /// Every window of an even length from 4 up to `max_length` steps that
+    /// returns the empty cell to its start, without passing through it on the
+    /// way, is counted once per solution it appears in, with all orientations
+    /// counted together. Loops seen in at least `min_count` solutions are kept,
+    /// up to `limit` of them, and named by length and rank.
+    pub fn mine(
+        solutions: &[RelativePattern],
+        max_length: usize,
+        min_count: usize,
+        limit: usize,
+    ) -> Self {
+        let mut counts: HashMap<Vec<Direction>, usize> = HashMap::new();
+        for solution in solutions {
+            let mut seen: Vec<Vec<Direction>> = Vec::new();
+            for length in (4..=max_length.min(solution.len())).step_by(2) {
+                for window in solution.steps.windows(length) {
+                    let candidate = RelativePattern::new("", window.to_vec());
+                    let closes_early = (2..length).step_by(2).any(|prefix| {
+                        RelativePattern::new("", window[..prefix].to_vec()).is_loop()
+                    });
+                    if !candidate.is_loop() || closes_early {
+                        continue;
+                    }
+                    let steps = candidate.canonical().steps;
+                    if !seen.contains(&steps) {
+                        *counts.entry(steps.clone()).or_default() += 1;
+                        seen.push(steps);
+                    }
+                }
+            }
+        }
+
+        let mut ranked: Vec<(Vec<Direction>, usize)> = counts
+            .into_iter()
+            .filter(|&(_, count)| count >= min_count)
+            .collect();
+        // Ties go to shorter loops, then a fixed order so mining is repeatable
+        ranked.sort_by(|(a, a_count), (b, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then(a.len().cmp(&b.len()))
+                .then(a.cmp(b))
+        });
+        let patterns = ranked
+            .into_iter()
+            .take(limit)
+            .enumerate()
+            .map(|(rank, (steps, _))| {
+                RelativePattern::new(
+                    format!("Learned {}-move loop {}", steps.len(), rank + 1),
+                    steps,
+                )
+            })
+            .collect();
+        Self::new(patterns)
+    }
+
+    /// Adds the techniques of `other` that are not already here in some orientation
+    pub fn extend(&mut self, other: RelativePatternCatalog) {
+        for pattern in other.patterns {
+            let canonical = pattern.canonical();
+            if !self
+                .patterns
+                .iter()
+                .any(|p| p.canonical().steps == canonical.steps)
+            {
+                self.patterns.push(pattern);
+            }
+        }
+    }
+
+    /// Returns every orientation of every technique that fits on `state` from its
+    /// empty cell, with the immediate moves that play it, in catalog order
+    pub fn applicable(&self, state: &PuzzleState) -> Vec<(RelativePattern, Vec<Position>)> {
+        self.patterns
+            .iter()
+            .flat_map(RelativePattern::orientations)
+            .filter_map(|pattern| {
+                let moves = pattern.positions_at(state)?;
+                Some((pattern, moves))
+            })
+            .collect()
+    }
+}
+
+impl Default for RelativePatternCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_through_moves() {
+        let mut state = PuzzleState::new(4).unwrap();
+        let moves = vec![(3, 2), (2, 2), (2, 3), (3, 3)];
+        let pattern = RelativePattern::from_moves("cycle", state.empty_position(), &moves).unwrap();
+        assert_eq!(
+            pattern.steps(),
+            &[
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+                Direction::Up
+            ]
+        );
+        assert_eq!(pattern.positions_at(&state), Some(moves.clone()));
+
+        // Replayed from elsewhere the same slides land on other cells
+        assert!(state.apply_immediate_move((2, 3)));
+        assert_eq!(
+            pattern.positions_at(&state),
+            Some(vec![(2, 2), (1, 2), (1, 3), (2, 3)])
+        );
+
+        assert!(RelativePattern::from_moves("gap", (3, 3), &[(3, 1)]).is_none());
+    }
+
+    #[test]
+    fn test_orientations() {
+        let corner = RelativePatternCatalog::builtin().patterns()[0].clone();
+        let orientations = corner.orientations();
+        assert_eq!(orientations.len(), 8);
+        assert_eq!(orientations[0], corner);
+        assert_eq!(corner.rotated().rotated().rotated().rotated(), corner);
+        assert_eq!(corner.mirrored().mirrored(), corner);
+
+        // A straight line only has four distinct orientations
+        let line = RelativePattern::new("line", vec![Direction::Left, Direction::Left]);
+        assert_eq!(line.orientations().len(), 4);
+    }
+
+    #[test]
+    fn test_catalog_fits_at_the_empty_cell() {
+        let catalog = RelativePatternCatalog::builtin();
+        let state = PuzzleState::new(3).unwrap();
+
+        // From the corner only the orientations that stay on the board fit
+        let applicable = catalog.applicable(&state);
+        assert!(!applicable.is_empty());
+        for (pattern, moves) in &applicable {
+            assert_eq!(pattern.positions_at(&state).as_ref(), Some(moves));
+            let mut replay = state.clone();
+            assert!(moves.iter().all(|&pos| replay.apply_immediate_move(pos)));
+            assert_eq!(replay.empty_position(), state.empty_position());
+        }
+        let corners = applicable
+            .iter()
+            .filter(|(pattern, _)| pattern.name() == "Corner rotation")
+            .count();
+        assert_eq!(corners, 2);
+    }
+
+    #[test]
+    fn test_mine_counts_loops_across_orientations() {
+        use Direction::{Down, Left, Right, Up};
+        // Two solutions using the corner rotation in different orientations,
+        // with straight slides around it
+        let solutions = vec![
+            RelativePattern::new("a", vec![Left, Right, Down, Left, Up, Up]),
+            RelativePattern::new("b", vec![Up, Down, Right, Up, Left, Left, Left]),
+            RelativePattern::new("c", vec![Left, Left, Left]),
+        ];
+        let mined = RelativePatternCatalog::mine(&solutions, 8, 2, 5);
+        assert_eq!(mined.len(), 1);
+        let builtin = RelativePatternCatalog::builtin();
+        let corner = &builtin.patterns()[0];
+        assert_eq!(
+            mined.patterns()[0].canonical().steps(),
+            corner.canonical().steps()
+        );
+        assert_eq!(mined.patterns()[0].name(), "Learned 4-move loop 1");
+
+        assert!(RelativePatternCatalog::mine(&solutions, 8, 3, 5).is_empty());
+
+        // The corner rotation is already built in, so extending adds nothing
+        let mut catalog = RelativePatternCatalog::builtin();
+        catalog.extend(mined);
+        assert_eq!(catalog, builtin);
+    }
+
+    #[test]
+    fn test_off_board_steps_do_not_apply() {
+        let state = PuzzleState::new(3).unwrap();
+        let up = RelativePattern::new("up", vec![Direction::Up]);
+        assert!(!up.applies_to(&state));
+        let back_and_forth =
+            RelativePattern::new("wiggle", vec![Direction::Right, Direction::Left]);
+        assert!(back_and_forth.applies_to(&state));
+    }
+}
@@ -1,11 +1,19 @@
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Frames `FrameMetrics` keeps for its averages, two seconds at 60 fps
+pub const FRAME_HISTORY: usize = 120;
+
+/// Longest frame that still keeps up with a 60 Hz display
+pub const FRAME_BUDGET_MICROS: u64 = 16_667;
+
 /// Performance metrics for entropy calculations
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PerformanceMetrics {
     pub manhattan_time_micros: u64,
     pub heuristic_time_micros: u64,
     pub actual_time_micros: u64,
+    pub solver_nodes_expanded: usize, // Nodes expanded by the last background solve (0 = unknown)
 }
 
 impl PerformanceMetrics {
@@ -28,6 +36,88 @@ impl PerformanceMetrics {
     }
 }
 
+/// Costs of one GUI frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameCost {
+    pub total_micros: u64,   // CPU time of the whole frame, painting included
+    pub advance_micros: u64, // Polling background tasks and stepping animations
+    pub board_micros: u64,   // Laying out the board
+}
+
+/// Per-frame counterpart of `PerformanceMetrics`, kept over the last `FRAME_HISTORY` frames
+///
+/// Sections are added to `current` while a frame is built; the frame's total
+/// is only known once it has been painted, so `finish_frame` is called with it
+/// at the start of the next one.
+#[derive(Debug, Clone, Default)]
+pub struct FrameMetrics {
+    pub current: FrameCost,
+    frames: VecDeque<FrameCost>, // Newest last
+    frame_count: u64,
+    slow_frames: u64, // Frames over FRAME_BUDGET_MICROS
+}
+
+impl FrameMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the frame built since the last call, which took `total_micros` in all
+    pub fn finish_frame(&mut self, total_micros: u64) {
+        let frame = FrameCost {
+            total_micros,
+            ..std::mem::take(&mut self.current)
+        };
+        if self.frames.len() == FRAME_HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+        self.frame_count += 1;
+        if total_micros > FRAME_BUDGET_MICROS {
+            self.slow_frames += 1;
+        }
+    }
+
+    /// Returns the kept frames, oldest first
+    pub fn frames(&self) -> impl ExactSizeIterator<Item = &FrameCost> + '_ {
+        self.frames.iter()
+    }
+
+    pub fn last(&self) -> Option<FrameCost> {
+        self.frames.back().copied()
+    }
+
+    /// Returns the mean cost of each section over the kept frames
+    pub fn mean(&self) -> FrameCost {
+        let count = self.frames.len().max(1) as u64;
+        let sum = self.frames.iter().fold(FrameCost::default(), |sum, frame| FrameCost {
+            total_micros: sum.total_micros + frame.total_micros,
+            advance_micros: sum.advance_micros + frame.advance_micros,
+            board_micros: sum.board_micros + frame.board_micros,
+        });
+        FrameCost {
+            total_micros: sum.total_micros / count,
+            advance_micros: sum.advance_micros / count,
+            board_micros: sum.board_micros / count,
+        }
+    }
+
+    /// Returns the slowest kept frame
+    pub fn worst(&self) -> Option<FrameCost> {
+        self.frames.iter().copied().max_by_key(|frame| frame.total_micros)
+    }
+
+    /// Returns the frames recorded since the start, not just those kept
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Returns the frames since the start that went over `FRAME_BUDGET_MICROS`
+    pub fn slow_frames(&self) -> u64 {
+        self.slow_frames
+    }
+}
+
 /// Timer wrapper for measuring calculation performance
 pub struct PerformanceTimer {
     start: Instant,
@@ -89,6 +179,39 @@ mod tests {
         assert_eq!(formatted, "N/A");
     }
 
+    #[test]
+    fn test_frame_metrics_keep_the_latest_frames() {
+        let mut metrics = FrameMetrics::new();
+        assert_eq!(metrics.last(), None);
+        assert_eq!(metrics.mean(), FrameCost::default());
+
+        metrics.current.advance_micros += 300;
+        metrics.current.board_micros = 1_000;
+        metrics.finish_frame(4_000);
+        assert_eq!(
+            metrics.last(),
+            Some(FrameCost {
+                total_micros: 4_000,
+                advance_micros: 300,
+                board_micros: 1_000
+            })
+        );
+        assert_eq!(metrics.current, FrameCost::default());
+
+        metrics.finish_frame(FRAME_BUDGET_MICROS + 1);
+        assert_eq!(metrics.worst().map(|frame| frame.total_micros), Some(FRAME_BUDGET_MICROS + 1));
+        assert_eq!(metrics.mean().advance_micros, 150);
+        assert_eq!(metrics.slow_frames(), 1);
+
+        for _ in 0..FRAME_HISTORY {
+            metrics.finish_frame(2_000);
+        }
+        assert_eq!(metrics.frames().len(), FRAME_HISTORY);
+        assert_eq!(metrics.frame_count(), FRAME_HISTORY as u64 + 2);
+        assert_eq!(metrics.mean().total_micros, 2_000);
+        assert_eq!(metrics.slow_frames(), 1);
+    }
+
     #[test]
     fn test_default_metrics() {
         let metrics = PerformanceMetrics::default();
@@ -1,10 +1,15 @@
 use super::error::PuzzleError;
+use super::goal::GoalSpec;
 use super::move_validator::{MoveValidator, Position};
-use super::tile::Tile;
+use super::tile::{Tile, TileContent, TileLabels};
+use std::str::FromStr;
 
 /// Constants for puzzle size limits
 pub const MIN_SIZE: usize = 3;
-pub const MAX_SIZE: usize = 15;
+pub const MAX_SIZE: usize = 22;
+
+/// Crockford base32 alphabet used by share codes (no I, L, O, or U)
+const SHARE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
 
 /// Represents the current state of the puzzle
 #[derive(Debug, Clone)]
@@ -15,6 +20,12 @@ pub struct PuzzleState {
     empty_pos: Position,
     /// Size of the grid (n x n)
     size: usize,
+    /// How tiles are numbered in the solved board (home positions already follow it)
+    goal: GoalSpec,
+    /// Sum of every tile's distance from home, kept up to date as tiles move
+    manhattan: u32,
+    /// Linear conflicts on the board, kept up to date as tiles move
+    linear_conflicts: u32,
 }
 
 impl PuzzleState {
@@ -23,43 +34,388 @@ impl PuzzleState {
     /// # Errors
     ///
     /// Returns `PuzzleError::SizeTooSmall` if size < 3
-    /// Returns `PuzzleError::SizeTooLarge` if size > 15
+    /// Returns `PuzzleError::SizeTooLarge` if size > 22
     pub fn new(size: usize) -> Result<Self, PuzzleError> {
-        if size < MIN_SIZE {
-            return Err(PuzzleError::SizeTooSmall {
-                size,
-                min: MIN_SIZE,
-            });
+        Self::with_goal(size, GoalSpec::Standard)
+    }
+
+    /// Creates a puzzle solved according to `goal`
+    ///
+    /// # Errors
+    ///
+    /// Returns a size error as for `new`
+    pub fn with_goal(size: usize, goal: GoalSpec) -> Result<Self, PuzzleError> {
+        check_size(size)?;
+
+        let mut grid = vec![vec![None; size]; size];
+        let cells = goal.cells(size);
+        for (index, &(row, col)) in cells.iter().enumerate().take(size * size - 1) {
+            grid[row][col] = Some(Tile::new_numeric(index as u32 + 1, (row, col)));
         }
-        if size > MAX_SIZE {
-            return Err(PuzzleError::SizeTooLarge {
-                size,
-                max: MAX_SIZE,
-            });
+
+        Ok(Self {
+            grid,
+            empty_pos: cells[size * size - 1],
+            size,
+            goal,
+            manhattan: 0,
+            linear_conflicts: 0,
+        })
+    }
+
+    /// Creates a solved puzzle whose tiles show `labels`
+    ///
+    /// # Errors
+    ///
+    /// Returns a size error as for `new`, or `PuzzleError::LabelCount` if the
+    /// labels do not fit the board
+    pub fn with_labels(size: usize, labels: &TileLabels) -> Result<Self, PuzzleError> {
+        let mut state = Self::new(size)?;
+        state.relabel(labels)?;
+        Ok(state)
+    }
+
+    /// Gives every tile the label of its home position, leaving the arrangement as it is
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::LabelCount` if the labels do not fit the board
+    pub fn relabel(&mut self, labels: &TileLabels) -> Result<(), PuzzleError> {
+        let contents = labels.contents(self.size)?;
+        let numbering = self.goal.numbering(self.size);
+        let size = self.size;
+        for tile in self.grid.iter_mut().flatten().flatten() {
+            let (home_row, home_col) = tile.home_position;
+            let number = numbering[home_row * size + home_col] as usize;
+            tile.content = contents[number - 1].clone();
         }
+        Ok(())
+    }
 
+    /// Assembles a state from a grid already known to be valid (used by packed states)
+    ///
+    /// Home positions carry the goal, so solved checks and heuristics work on the
+    /// result whatever the goal was; only the tile numbering assumes the standard one.
+    pub(super) fn from_grid(grid: Vec<Vec<Option<Tile>>>, empty_pos: Position) -> Self {
+        let size = grid.len();
+        let mut state = Self {
+            grid,
+            empty_pos,
+            size,
+            goal: GoalSpec::Standard,
+            manhattan: 0,
+            linear_conflicts: 0,
+        };
+        state.recount();
+        state
+    }
+
+    /// Returns the board turned 180°, home positions included
+    /// (used by solvers that expect the empty cell's goal bottom-right)
+    pub(super) fn rotated_half_turn(&self) -> Self {
+        let n = self.size;
+        let turn = |(row, col): Position| (n - 1 - row, n - 1 - col);
+        let grid = (0..n)
+            .map(|row| {
+                (0..n)
+                    .map(|col| {
+                        self.tile_at(turn((row, col))).map(|tile| Tile {
+                            home_position: turn(tile.home_position),
+                            ..tile.clone()
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_grid(grid, turn(self.empty_pos))
+    }
+
+    /// Creates a puzzle from an explicit layout of tile numbers (None = empty cell)
+    ///
+    /// Tiles are numbered 1..n²-1 in row-major goal order, so tile `v` belongs at
+    /// `((v - 1) / n, (v - 1) % n)`.
+    ///
+    /// # Errors
+    ///
+    /// As for `from_layout_with_goal`
+    pub fn from_layout(layout: &[Vec<Option<u32>>]) -> Result<Self, PuzzleError> {
+        Self::from_layout_with_goal(layout, GoalSpec::Standard)
+    }
+
+    /// Creates a puzzle from a layout of tile numbers, numbered as in `goal`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidLayout` if the grid is not square, a tile is
+    /// missing, duplicated, or out of range, or there is not exactly one empty cell.
+    /// Returns `PuzzleError::UnsolvableLayout` if the layout has the wrong parity.
+    /// Returns a size error if the grid is outside the supported range.
+    pub fn from_layout_with_goal(
+        layout: &[Vec<Option<u32>>],
+        goal: GoalSpec,
+    ) -> Result<Self, PuzzleError> {
+        let size = layout.len();
+        check_size(size)?;
+
+        let homes = goal.cells(size);
+        let tile_count = (size * size - 1) as u32;
+        let mut seen = vec![false; size * size];
+        let mut empty_pos = None;
         let mut grid = Vec::with_capacity(size);
-        let mut tile_number = 1u32;
-
-        for row in 0..size {
-            let mut row_vec = Vec::with_capacity(size);
-            for col in 0..size {
-                if row == size - 1 && col == size - 1 {
-                    // Last cell is empty
-                    row_vec.push(None);
-                } else {
-                    row_vec.push(Some(Tile::new_numeric(tile_number, (row, col))));
-                    tile_number += 1;
+
+        for (row, row_vec) in layout.iter().enumerate() {
+            if row_vec.len() != size {
+                return Err(PuzzleError::InvalidLayout(format!(
+                    "row {} has {} cells, expected {}",
+                    row + 1,
+                    row_vec.len(),
+                    size
+                )));
+            }
+
+            let mut cells = Vec::with_capacity(size);
+            for (col, &cell) in row_vec.iter().enumerate() {
+                match cell {
+                    None => {
+                        if empty_pos.is_some() {
+                            return Err(PuzzleError::InvalidLayout(
+                                "more than one empty cell".to_string(),
+                            ));
+                        }
+                        empty_pos = Some((row, col));
+                        cells.push(None);
+                    }
+                    Some(value) => {
+                        if value == 0 || value > tile_count {
+                            return Err(PuzzleError::InvalidLayout(format!(
+                                "tile {} out of range 1..={}",
+                                value, tile_count
+                            )));
+                        }
+                        if seen[value as usize] {
+                            return Err(PuzzleError::InvalidLayout(format!(
+                                "duplicate tile {}",
+                                value
+                            )));
+                        }
+                        seen[value as usize] = true;
+                        cells.push(Some(Tile::new_numeric(value, homes[value as usize - 1])));
+                    }
                 }
             }
-            grid.push(row_vec);
+            grid.push(cells);
         }
 
-        Ok(Self {
+        let empty_pos = empty_pos
+            .ok_or_else(|| PuzzleError::InvalidLayout("no empty cell".to_string()))?;
+
+        let mut state = Self {
             grid,
-            empty_pos: (size - 1, size - 1),
+            empty_pos,
             size,
-        })
+            goal,
+            manhattan: 0,
+            linear_conflicts: 0,
+        };
+        state.recount();
+
+        if !state.is_solvable() {
+            return Err(PuzzleError::UnsolvableLayout);
+        }
+
+        Ok(state)
+    }
+
+    /// Parses a layout written in compact text notation
+    ///
+    /// Rows are separated by `/` or newlines and cells by whitespace or commas.
+    /// The empty cell is written as `_`, `.`, or `0`.
+    /// Example: `"1 2 3 / 4 _ 6 / 7 5 8"`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidLayout` if a cell cannot be parsed
+    pub fn parse_layout(text: &str) -> Result<Vec<Vec<Option<u32>>>, PuzzleError> {
+        text.split(['/', '\n'])
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .map(|row| {
+                row.split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|cell| !cell.is_empty())
+                    .map(|cell| match cell {
+                        "_" | "." | "0" => Ok(None),
+                        _ => cell.parse::<u32>().map(Some).map_err(|_| {
+                            PuzzleError::InvalidLayout(format!("cannot parse cell '{}'", cell))
+                        }),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the layout as tile numbers in row-major order (None = empty cell)
+    pub fn to_layout(&self) -> Vec<Vec<Option<u32>>> {
+        let numbering = self.goal.numbering(self.size);
+        self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.as_ref().map(|tile| self.goal_number(&numbering, tile)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Formats the layout in the compact text notation accepted by `parse_layout`
+    pub fn to_notation(&self) -> String {
+        self.to_layout()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.map_or("_".to_string(), |v| v.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Encodes the layout as a short code that can be pasted into chat
+    ///
+    /// The first character is the grid size; the rest bit-pack each cell's tile
+    /// number (0 for the empty cell) using as few bits as the largest tile needs,
+    /// written in Crockford base32. A 4×4 board takes 14 characters. The goal is
+    /// not recorded, so codes always decode with the standard goal.
+    pub fn to_share_code(&self) -> String {
+        let bits = share_bits(self.size);
+        let mut code = String::new();
+        code.push(SHARE_ALPHABET[self.size] as char);
+
+        let (mut buffer, mut buffered) = (0u32, 0);
+        for cell in self.to_layout().into_iter().flatten() {
+            buffer = (buffer << bits) | cell.unwrap_or(0);
+            buffered += bits;
+            while buffered >= 5 {
+                buffered -= 5;
+                code.push(SHARE_ALPHABET[((buffer >> buffered) & 31) as usize] as char);
+            }
+        }
+        if buffered > 0 {
+            code.push(SHARE_ALPHABET[((buffer << (5 - buffered)) & 31) as usize] as char);
+        }
+        code
+    }
+
+    /// Decodes a code produced by `to_share_code`
+    ///
+    /// Case, dashes, and whitespace are ignored, and the Crockford look-alikes
+    /// `I`/`L` (for 1) and `O` (for 0) are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidShareCode` if the code has bad characters or
+    /// the wrong length, and the `from_layout` errors if it decodes to a bad board.
+    pub fn from_share_code(code: &str) -> Result<Self, PuzzleError> {
+        let digits = code
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| {
+                let c = match c.to_ascii_uppercase() {
+                    'I' | 'L' => '1',
+                    'O' => '0',
+                    c => c,
+                };
+                SHARE_ALPHABET
+                    .iter()
+                    .position(|&a| a as char == c)
+                    .map(|v| v as u32)
+                    .ok_or_else(|| PuzzleError::InvalidShareCode(format!("unexpected character '{}'", c)))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        let (&size, data) = digits
+            .split_first()
+            .ok_or_else(|| PuzzleError::InvalidShareCode("empty code".to_string()))?;
+        let size = size as usize;
+        if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+            return Err(PuzzleError::InvalidShareCode(format!("unsupported grid size {}", size)));
+        }
+        let bits = share_bits(size);
+        if data.len() != (size * size * bits as usize).div_ceil(5) {
+            return Err(PuzzleError::InvalidShareCode(format!(
+                "expected {} characters for a {}x{} board",
+                1 + (size * size * bits as usize).div_ceil(5),
+                size,
+                size
+            )));
+        }
+
+        let mut cells = Vec::with_capacity(size * size);
+        let (mut buffer, mut buffered) = (0u32, 0);
+        for &digit in data {
+            buffer = (buffer << 5) | digit;
+            buffered += 5;
+            while buffered >= bits && cells.len() < size * size {
+                buffered -= bits;
+                let value = (buffer >> buffered) & ((1 << bits) - 1);
+                cells.push((value != 0).then_some(value));
+            }
+        }
+
+        let layout: Vec<Vec<Option<u32>>> = cells.chunks(size).map(<[Option<u32>]>::to_vec).collect();
+        Self::from_layout(&layout)
+    }
+
+    /// Parses a board typed either in text notation or as a share code
+    ///
+    /// Notation always separates cells, so a single word is read as a share code.
+    ///
+    /// # Errors
+    ///
+    /// Returns the notation errors (see `FromStr`) or the `from_share_code` errors
+    pub fn from_notation_or_code(text: &str) -> Result<Self, PuzzleError> {
+        let text = text.trim();
+        if text.contains(|c: char| c == '/' || c == ',' || c.is_whitespace()) {
+            text.parse()
+        } else {
+            Self::from_share_code(text)
+        }
+    }
+
+    /// Returns true if the solved state is reachable from this one
+    ///
+    /// Every move swaps the empty cell with a neighbour, flipping the parity of the
+    /// permutation from the goal as well as the parity of the empty cell's distance
+    /// from its goal cell, so the board is solvable iff the two parities agree.
+    /// With the standard goal this is the usual inversion-count test.
+    pub fn is_solvable(&self) -> bool {
+        let n = self.size;
+        let goal_empty = self.goal_empty_position();
+
+        // Goal cell of whatever occupies each cell, the empty cell included
+        let mut target = vec![goal_empty.0 * n + goal_empty.1; n * n];
+        for ((row, col), tile) in self.tiles() {
+            target[row * n + col] = tile.home_position.0 * n + tile.home_position.1;
+        }
+
+        // A permutation's parity is that of its length minus its cycle count
+        let mut visited = vec![false; n * n];
+        let mut cycles = 0;
+        for start in 0..n * n {
+            if visited[start] {
+                continue;
+            }
+            cycles += 1;
+            let mut cell = start;
+            while !visited[cell] {
+                visited[cell] = true;
+                cell = target[cell];
+            }
+        }
+
+        let empty_distance =
+            self.empty_pos.0.abs_diff(goal_empty.0) + self.empty_pos.1.abs_diff(goal_empty.1);
+        (n * n - cycles) % 2 == empty_distance % 2
     }
 
     /// Returns the grid size
@@ -78,6 +434,35 @@ impl PuzzleState {
         self.grid.get(row).and_then(|r| r.get(col)).and_then(|t| t.as_ref())
     }
 
+    /// Returns the number of the tile at `pos` in goal order (1 belongs top-left),
+    /// whatever label the tile shows
+    pub fn tile_number(&self, pos: Position) -> Option<u32> {
+        let numbering = self.goal.numbering(self.size);
+        self.tile_at(pos).map(|tile| self.goal_number(&numbering, tile))
+    }
+
+    /// Looks up a tile's number in `numbering` (from `GoalSpec::numbering`)
+    fn goal_number(&self, numbering: &[u32], tile: &Tile) -> u32 {
+        let (home_row, home_col) = tile.home_position;
+        numbering[home_row * self.size + home_col]
+    }
+
+    /// Returns the goal the tiles are numbered by
+    pub fn goal(&self) -> GoalSpec {
+        self.goal
+    }
+
+    /// Returns where the empty cell belongs: the one cell that is no tile's home
+    pub fn goal_empty_position(&self) -> Position {
+        let cells = self.size * self.size;
+        let homes: usize = self
+            .tiles()
+            .map(|(_, tile)| tile.home_position.0 * self.size + tile.home_position.1)
+            .sum();
+        let empty = cells * (cells - 1) / 2 - homes;
+        (empty / self.size, empty % self.size)
+    }
+
     /// Returns the current position of a specific tile (by its home position)
     pub fn find_tile_position(&self, home_pos: Position) -> Option<Position> {
         for (row, row_vec) in self.grid.iter().enumerate() {
@@ -104,6 +489,15 @@ impl PuzzleState {
 
         let (from_row, from_col) = from;
         let (empty_row, empty_col) = self.empty_pos;
+        let Some(home) = self.tile_at(from).map(|tile| tile.home_position) else {
+            return false;
+        };
+
+        // The tile keeps its order along the line it slides in, so only its
+        // conflicts across that line change
+        let across = if from_row == empty_row { Line::Column } else { Line::Row };
+        let old_conflicts = self.conflicts_with(from, home, across);
+        let old_distance = distance(from, home);
 
         // Swap tile with empty cell
         let tile = self.grid[from_row][from_col].take();
@@ -113,9 +507,73 @@ impl PuzzleState {
         // Update empty position
         self.empty_pos = from;
 
+        let to = (empty_row, empty_col);
+        self.manhattan = self.manhattan + distance(to, home) - old_distance;
+        self.linear_conflicts = self.linear_conflicts + self.conflicts_with(to, home, across) - old_conflicts;
+
         true
     }
 
+    /// Returns the total Manhattan distance of the tiles from their homes
+    ///
+    /// Kept up to date as tiles move, so reading it is O(1) on any board.
+    pub fn manhattan_distance(&self) -> u32 {
+        self.manhattan
+    }
+
+    /// Returns the number of linear conflicts: pairs of tiles in their goal row
+    /// (or column) that sit in the reverse of their goal order
+    ///
+    /// Kept up to date as tiles move, so reading it is O(1) on any board.
+    pub fn linear_conflicts(&self) -> u32 {
+        self.linear_conflicts
+    }
+
+    /// Recomputes the Manhattan and linear conflict totals from scratch
+    fn recount(&mut self) {
+        self.manhattan = self.tiles().map(|(pos, tile)| distance(pos, tile.home_position)).sum();
+        // Each conflicting pair is seen once from each of its tiles
+        let conflicts: u32 = self
+            .tiles()
+            .map(|(pos, tile)| {
+                self.conflicts_with(pos, tile.home_position, Line::Row)
+                    + self.conflicts_with(pos, tile.home_position, Line::Column)
+            })
+            .sum();
+        self.linear_conflicts = conflicts / 2;
+    }
+
+    /// Counts the tiles that conflict with a tile at `pos` whose home is `home`, along `line`
+    ///
+    /// Two tiles conflict when both sit in their goal row (or column) but in the
+    /// reverse of their goal order.
+    fn conflicts_with(&self, pos: Position, home: Position, line: Line) -> u32 {
+        // Row lines compare columns; column lines compare rows
+        let split = |(row, col): Position| match line {
+            Line::Row => (col, row),
+            Line::Column => (row, col),
+        };
+        let (pos_along, pos_across) = split(pos);
+        let (home_along, home_across) = split(home);
+        if pos_across != home_across {
+            return 0;
+        }
+        (0..self.size)
+            .filter(|&other_along| other_along != pos_along)
+            .filter_map(|other_along| {
+                let other = match line {
+                    Line::Row => (pos_across, other_along),
+                    Line::Column => (other_along, pos_across),
+                };
+                self.tile_at(other).map(|tile| (other_along, split(tile.home_position)))
+            })
+            .filter(|&(other_along, (other_home_along, other_home_across))| {
+                other_home_across == pos_across
+                    && (other_along < pos_along) != (other_home_along < home_along)
+            })
+            .count() as u32
+    }
+
     /// Applies a chain move by executing a sequence of immediate moves
     pub fn apply_chain_move(&mut self, target: Position) -> bool {
         // Size is guaranteed valid since PuzzleState was constructed successfully
@@ -147,6 +605,21 @@ impl PuzzleState {
         true
     }
 
+    /// Checks if every cell shows the label its goal tile has
+    ///
+    /// Unlike `is_solved`, tiles with equal labels count as interchangeable, so a
+    /// word board that spells its phrase is solved even if two equal letters
+    /// have swapped homes.
+    pub fn shows_goal(&self) -> bool {
+        let mut goal: Vec<Option<&TileContent>> = vec![None; self.size * self.size];
+        for (_, tile) in self.tiles() {
+            let (home_row, home_col) = tile.home_position;
+            goal[home_row * self.size + home_col] = Some(&tile.content);
+        }
+        self.tiles()
+            .all(|((row, col), tile)| goal[row * self.size + col] == Some(&tile.content))
+    }
+
     /// Returns an iterator over all tiles and their current positions
     pub fn tiles(&self) -> impl Iterator<Item = (Position, &Tile)> {
         self.grid.iter().enumerate().flat_map(|(row, row_vec)| {
@@ -157,10 +630,99 @@ impl PuzzleState {
     }
 }
 
+/// A row or column of the board, for counting linear conflicts
+#[derive(Debug, Clone, Copy)]
+enum Line {
+    Row,
+    Column,
+}
+
+/// Returns the Manhattan distance between two cells
+fn distance(a: Position, b: Position) -> u32 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u32
+}
+
+/// Rejects grid sizes outside `MIN_SIZE..=MAX_SIZE`
+fn check_size(size: usize) -> Result<(), PuzzleError> {
+    if size < MIN_SIZE {
+        return Err(PuzzleError::SizeTooSmall {
+            size,
+            min: MIN_SIZE,
+        });
+    }
+    if size > MAX_SIZE {
+        return Err(PuzzleError::SizeTooLarge {
+            size,
+            max: MAX_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Bits per cell in a share code: enough for the largest tile number
+fn share_bits(size: usize) -> u32 {
+    u32::BITS - ((size * size - 1) as u32).leading_zeros()
+}
+
+impl FromStr for PuzzleState {
+    type Err = PuzzleError;
+
+    /// Parses and validates a layout in compact text notation
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_layout(&Self::parse_layout(text)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Counts linear conflicts pair by pair
+    fn naive_conflicts(state: &PuzzleState) -> u32 {
+        let tiles: Vec<_> = state.tiles().map(|(pos, tile)| (pos, tile.home_position)).collect();
+        let mut conflicts = 0;
+        for (i, &(a, a_home)) in tiles.iter().enumerate() {
+            for &(b, b_home) in &tiles[i + 1..] {
+                let in_row = a.0 == b.0 && a_home.0 == a.0 && b_home.0 == b.0;
+                let in_col = a.1 == b.1 && a_home.1 == a.1 && b_home.1 == b.1;
+                if (in_row && (a.1 < b.1) != (a_home.1 < b_home.1))
+                    || (in_col && (a.0 < b.0) != (a_home.0 < b_home.0))
+                {
+                    conflicts += 1;
+                }
+            }
+        }
+        conflicts
+    }
+
+    #[test]
+    fn test_heuristic_totals_follow_moves() {
+        use crate::testing::{check, grid_size, in_line_position};
+
+        check("heuristic totals match a recount", 20, |rng| {
+            let mut puzzle = PuzzleState::new(grid_size(rng, 3..=7)).unwrap();
+            for _ in 0..60 {
+                puzzle.apply_chain_move(in_line_position(rng, &puzzle));
+                let mut fresh = puzzle.clone();
+                fresh.recount();
+                if puzzle.manhattan_distance() != fresh.manhattan_distance()
+                    || puzzle.linear_conflicts() != naive_conflicts(&puzzle)
+                {
+                    return Err(puzzle.to_notation());
+                }
+            }
+            Ok(())
+        });
+
+        // Parsed and rotated boards start with their totals counted
+        let puzzle: PuzzleState = "4 1 3 / 2 _ 6 / 7 5 8".parse().unwrap();
+        assert_eq!(puzzle.manhattan_distance(), 6);
+        assert_eq!(puzzle.linear_conflicts(), naive_conflicts(&puzzle));
+        let rotated = puzzle.rotated_half_turn();
+        assert_eq!(rotated.manhattan_distance(), 6);
+        assert_eq!(rotated.linear_conflicts(), naive_conflicts(&rotated));
+    }
+
     #[test]
     fn test_new_puzzle_is_solved() {
         let puzzle = PuzzleState::new(4).unwrap();
@@ -219,6 +781,214 @@ mod tests {
         assert_eq!(puzzle.empty_position(), (3, 0));
     }
 
+    #[test]
+    fn test_from_layout_solved() {
+        let layout = vec![
+            vec![Some(1), Some(2), Some(3)],
+            vec![Some(4), Some(5), Some(6)],
+            vec![Some(7), Some(8), None],
+        ];
+        let puzzle = PuzzleState::from_layout(&layout).unwrap();
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.to_layout(), layout);
+    }
+
+    #[test]
+    fn test_parse_notation() {
+        let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        assert_eq!(puzzle.empty_position(), (1, 1));
+        assert_eq!(puzzle.tile_at((2, 1)).unwrap().home_position, (1, 1));
+        assert_eq!(puzzle.to_notation(), "1 2 3 / 4 _ 6 / 7 5 8");
+    }
+
+    #[test]
+    fn test_parse_multiline_notation() {
+        let puzzle: PuzzleState = "1,2,3\n4,5,6\n7,0,8".parse().unwrap();
+        assert_eq!(puzzle.empty_position(), (2, 1));
+    }
+
+    #[test]
+    fn test_notation_round_trip_after_moves() {
+        let mut puzzle = PuzzleState::new(4).unwrap();
+        puzzle.apply_chain_move((0, 3));
+        puzzle.apply_chain_move((0, 0));
+        let parsed: PuzzleState = puzzle.to_notation().parse().unwrap();
+        assert_eq!(parsed.to_layout(), puzzle.to_layout());
+    }
+
+    #[test]
+    fn test_from_layout_rejects_malformed() {
+        assert!(matches!(
+            "1 2 3 / 4 5 / 6 7 _".parse::<PuzzleState>(),
+            Err(PuzzleError::InvalidLayout(_))
+        ));
+        assert!(matches!(
+            "1 2 3 / 4 5 5 / 7 8 _".parse::<PuzzleState>(),
+            Err(PuzzleError::InvalidLayout(_))
+        ));
+        assert!(matches!(
+            "1 2 3 / 4 5 6 / 7 8 9".parse::<PuzzleState>(),
+            Err(PuzzleError::InvalidLayout(_))
+        ));
+        assert!(matches!(
+            "1 2 x / 4 5 6 / 7 8 _".parse::<PuzzleState>(),
+            Err(PuzzleError::InvalidLayout(_))
+        ));
+        assert!(matches!(
+            "1 _ / 2 3".parse::<PuzzleState>(),
+            Err(PuzzleError::SizeTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_layout_rejects_unsolvable() {
+        // Classic Sam Loyd 14-15 swap
+        let result: Result<PuzzleState, _> =
+            "1 2 3 4 / 5 6 7 8 / 9 10 11 12 / 13 15 14 _".parse();
+        assert_eq!(result.unwrap_err(), PuzzleError::UnsolvableLayout);
+
+        let result: Result<PuzzleState, _> = "2 1 3 / 4 5 6 / 7 8 _".parse();
+        assert_eq!(result.unwrap_err(), PuzzleError::UnsolvableLayout);
+    }
+
+    #[test]
+    fn test_share_code_round_trip() {
+        for size in [3, 4, 5, 15, 22] {
+            let mut puzzle = PuzzleState::new(size).unwrap();
+            for pos in [(size - 1, size - 2), (size - 2, size - 2), (size - 2, size - 3)] {
+                puzzle.apply_immediate_move(pos);
+            }
+            let code = puzzle.to_share_code();
+            let decoded = PuzzleState::from_share_code(&code).unwrap();
+            assert_eq!(decoded.to_layout(), puzzle.to_layout(), "{}", code);
+        }
+        assert_eq!(PuzzleState::new(4).unwrap().to_share_code().len(), 14);
+    }
+
+    #[test]
+    fn test_share_code_is_forgiving() {
+        let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        let code = puzzle.to_share_code();
+        let mangled = format!(" {}-{} ", &code[..4], code[4..].to_lowercase().replace('1', "l"));
+        assert_eq!(PuzzleState::from_share_code(&mangled).unwrap().to_layout(), puzzle.to_layout());
+    }
+
+    #[test]
+    fn test_from_notation_or_code() {
+        let puzzle: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        let from_code = PuzzleState::from_notation_or_code(&format!(" {} ", puzzle.to_share_code())).unwrap();
+        assert_eq!(from_code.to_layout(), puzzle.to_layout());
+        let from_notation = PuzzleState::from_notation_or_code("1 2 3\n4 _ 6\n7 5 8").unwrap();
+        assert_eq!(from_notation.to_layout(), puzzle.to_layout());
+        assert!(PuzzleState::from_notation_or_code("1 2 / 3").is_err());
+    }
+
+    #[test]
+    fn test_share_code_rejects_garbage() {
+        let code = PuzzleState::new(4).unwrap().to_share_code();
+        assert!(matches!(PuzzleState::from_share_code(""), Err(PuzzleError::InvalidShareCode(_))));
+        assert!(matches!(PuzzleState::from_share_code("4U"), Err(PuzzleError::InvalidShareCode(_))));
+        assert!(matches!(
+            PuzzleState::from_share_code(&code[..code.len() - 1]),
+            Err(PuzzleError::InvalidShareCode(_))
+        ));
+        assert!(matches!(PuzzleState::from_share_code("2000"), Err(PuzzleError::InvalidShareCode(_))));
+    }
+
+    #[test]
+    fn test_is_solvable() {
+        for size in 3..=6 {
+            let mut puzzle = PuzzleState::new(size).unwrap();
+            assert!(puzzle.is_solvable());
+
+            // Any sequence of legal moves preserves solvability
+            for pos in [(size - 1, 0), (0, 0), (0, size - 1), (size - 1, size - 1), (1, 1)] {
+                puzzle.apply_chain_move(pos);
+                assert!(puzzle.is_solvable());
+            }
+        }
+
+        let mut grid = PuzzleState::new(4).unwrap().to_layout();
+        grid[3].swap(1, 2);
+        assert!(matches!(
+            PuzzleState::from_layout(&grid),
+            Err(PuzzleError::UnsolvableLayout)
+        ));
+    }
+
+    #[test]
+    fn test_word_labels() {
+        let word = TileLabels::Word("RATE YOUR MIND PAL".to_string());
+        let mut puzzle = PuzzleState::with_labels(4, &word).unwrap();
+        assert_eq!(puzzle.tile_at((0, 0)).unwrap().content, TileContent::Alpha('R'));
+        assert_eq!(puzzle.tile_number((0, 0)), Some(1));
+        assert_eq!(puzzle.to_layout(), PuzzleState::new(4).unwrap().to_layout());
+        assert!(PuzzleState::with_labels(3, &word).is_err());
+
+        // Swapping both pairs of equal letters (R and A) is an even permutation,
+        // so the board is reachable and spells the phrase without being solved
+        let mut grid = puzzle.to_layout();
+        grid[0][0] = Some(8);
+        grid[1][3] = Some(1);
+        grid[0][1] = Some(14);
+        grid[3][1] = Some(2);
+        let mut swapped = PuzzleState::from_layout(&grid).unwrap();
+        swapped.relabel(&word).unwrap();
+        assert!(swapped.shows_goal());
+        assert!(!swapped.is_solved());
+
+        puzzle.apply_immediate_move((3, 2));
+        assert!(!puzzle.shows_goal());
+    }
+
+    #[test]
+    fn test_goal_layouts() {
+        let puzzle = PuzzleState::with_goal(3, GoalSpec::BlankFirst).unwrap();
+        assert_eq!(puzzle.to_notation(), "_ 1 2 / 3 4 5 / 6 7 8");
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.goal_empty_position(), (0, 0));
+
+        let mut reversed = PuzzleState::with_goal(4, GoalSpec::Reversed).unwrap();
+        assert_eq!(reversed.tile_number((3, 3)), Some(1));
+        reversed.apply_chain_move((3, 0));
+        let layout = reversed.to_layout();
+        let parsed = PuzzleState::from_layout_with_goal(&layout, GoalSpec::Reversed).unwrap();
+        assert_eq!(parsed.to_layout(), layout);
+        assert_eq!(parsed.goal(), GoalSpec::Reversed);
+        assert!(!parsed.is_solved());
+
+        // Labels follow the goal's numbering
+        let word = TileLabels::Word("ABCDEFGH".to_string());
+        let spiral = PuzzleState::with_goal(3, GoalSpec::Spiral).unwrap();
+        let mut labelled = spiral.clone();
+        labelled.relabel(&word).unwrap();
+        assert_eq!(labelled.tile_at((1, 0)).unwrap().content, TileContent::Alpha('H'));
+    }
+
+    #[test]
+    fn test_is_solvable_for_other_goals() {
+        for goal in GoalSpec::ALL {
+            for size in 3..=5 {
+                let mut puzzle = PuzzleState::with_goal(size, goal).unwrap();
+                for pos in [(0, 0), (size - 1, 0), (size - 1, size - 1), (0, size - 1), (1, 1)] {
+                    puzzle.apply_chain_move(pos);
+                    assert!(puzzle.is_solvable(), "{} on {}×{}", goal, size, size);
+                }
+
+                // Swapping two tiles always breaks solvability
+                let mut layout = puzzle.to_layout();
+                let tiles: Vec<Position> = puzzle.tiles().map(|(pos, _)| pos).take(2).collect();
+                let first = layout[tiles[0].0][tiles[0].1];
+                layout[tiles[0].0][tiles[0].1] = layout[tiles[1].0][tiles[1].1];
+                layout[tiles[1].0][tiles[1].1] = first;
+                assert_eq!(
+                    PuzzleState::from_layout_with_goal(&layout, goal).unwrap_err(),
+                    PuzzleError::UnsolvableLayout
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_find_tile_position() {
         let mut puzzle = PuzzleState::new(4).unwrap();
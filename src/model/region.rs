@@ -0,0 +1,145 @@
+//! # Board Regions
+//!
+//! A [`Region`] is a rectangle of cells, used to scramble part of a board while
+//! the rest stays solved (for example to practice the last two rows).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::Region;
+//!
+//! let rows = Region::bottom_rows(4, 2);
+//! assert!(rows.contains((3, 0)));
+//! assert!(!rows.contains((1, 3)));
+//! assert_eq!(rows.len(), 8);
+//! ```
+
+use super::error::PuzzleError;
+use super::move_validator::Position;
+use std::fmt;
+
+/// A rectangle of cells on a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region {
+    pub top: usize,
+    pub left: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Region {
+    pub fn new(top: usize, left: usize, rows: usize, cols: usize) -> Self {
+        Self {
+            top,
+            left,
+            rows,
+            cols,
+        }
+    }
+
+    /// The last `rows` rows of a `size`×`size` board
+    pub fn bottom_rows(size: usize, rows: usize) -> Self {
+        Self::new(size.saturating_sub(rows), 0, rows.min(size), size)
+    }
+
+    /// The last `cols` columns of a `size`×`size` board
+    pub fn right_columns(size: usize, cols: usize) -> Self {
+        Self::new(0, size.saturating_sub(cols), size, cols.min(size))
+    }
+
+    /// The `n`×`n` block in the bottom-right corner of a `size`×`size` board
+    pub fn bottom_right(size: usize, n: usize) -> Self {
+        let n = n.min(size);
+        Self::new(size - n, size - n, n, n)
+    }
+
+    /// Returns true if `pos` lies inside the region
+    pub fn contains(&self, (row, col): Position) -> bool {
+        (self.top..self.top + self.rows).contains(&row)
+            && (self.left..self.left + self.cols).contains(&col)
+    }
+
+    /// Returns the number of cells in the region
+    pub fn len(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every cell of the region in row-major order
+    pub fn cells(&self) -> impl Iterator<Item = Position> + '_ {
+        (self.top..self.top + self.rows)
+            .flat_map(move |row| (self.left..self.left + self.cols).map(move |col| (row, col)))
+    }
+
+    /// Checks that the region can be scrambled on a `size`×`size` board
+    ///
+    /// The region must fit on the board, be at least 2×2 (sliding along a
+    /// single line never reorders tiles), and contain `empty`, the cell the
+    /// scramble starts and ends its walk from.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::InvalidRegion` describing the first problem found
+    pub fn check(&self, size: usize, empty: Position) -> Result<(), PuzzleError> {
+        let invalid = |msg: &str| Err(PuzzleError::InvalidRegion(format!("{} {}", self, msg)));
+        if self.top + self.rows > size || self.left + self.cols > size {
+            return invalid(&format!("does not fit a {0}×{0} board", size));
+        }
+        if self.rows < 2 || self.cols < 2 {
+            return invalid("is narrower than 2×2");
+        }
+        if !self.contains(empty) {
+            return invalid(&format!(
+                "does not contain the empty cell at ({}, {})",
+                empty.0, empty.1
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}×{} region at ({}, {})",
+            self.rows, self.cols, self.top, self.left
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(Region::bottom_rows(5, 2), Region::new(3, 0, 2, 5));
+        assert_eq!(Region::right_columns(5, 2), Region::new(0, 3, 5, 2));
+        assert_eq!(Region::bottom_right(5, 3), Region::new(2, 2, 3, 3));
+
+        let block = Region::bottom_right(4, 2);
+        let cells: Vec<Position> = block.cells().collect();
+        assert_eq!(cells, vec![(2, 2), (2, 3), (3, 2), (3, 3)]);
+        assert!(cells.iter().all(|&pos| block.contains(pos)));
+        assert!(!block.contains((1, 3)));
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(Region::bottom_rows(4, 2).check(4, (3, 3)).is_ok());
+        assert!(Region::bottom_rows(4, 2).check(4, (0, 0)).is_err());
+        assert!(Region::bottom_rows(4, 1).check(4, (3, 3)).is_err());
+        assert!(Region::new(2, 2, 3, 3).check(4, (3, 3)).is_err());
+        assert_eq!(
+            Region::new(2, 2, 3, 3)
+                .check(4, (3, 3))
+                .unwrap_err()
+                .to_string(),
+            "Invalid region: 3×3 region at (2, 2) does not fit a 4×4 board"
+        );
+    }
+}
@@ -0,0 +1,478 @@
+//! # Retrograde Distance Tables
+//!
+//! A [`RetrogradeTable`] holds the exact distance to the goal of every
+//! placement of a chosen set of tiles and the empty cell, found by a
+//! breadth-first search backward from the goal. Sliding moves undo each other,
+//! so walking back from the goal one layer at a time reaches every placement in
+//! order of its fewest moves. The [`EndgameTable`] is the smallest case: every
+//! tile of a 3×3 board.
+//!
+//! A [`TileMask`] picks the tiles by their home cells. Tracking every tile gives
+//! perfect play on boards small enough to enumerate; tracking a few makes a
+//! pattern database, whose distances ignore the other tiles and so never
+//! overestimate. Each table has one byte per placement, `cells! / (cells -
+//! tracked - 1)!` in all, which is why tables are written once with
+//! [`save`](RetrogradeTable::save) and memory-mapped by
+//! [`open`](RetrogradeTable::open) rather than read into memory.
+//!
+//! Distances depend only on where the goal's empty cell is, not on how the goal
+//! numbers its tiles, so one table serves every goal sharing that cell.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{PuzzleState, RetrogradeTable, TileMask};
+//!
+//! let goal = PuzzleState::new(3)?;
+//! let table = RetrogradeTable::generate(TileMask::full(&goal).unwrap()).unwrap();
+//! let board: PuzzleState = "8 6 7 / 2 5 4 / 3 _ 1".parse()?;
+//! assert_eq!(table.distance(&board), Some(31));
+//!
+//! let path = std::env::temp_dir().join("slider-doc-3x3.slrg");
+//! table.save(&path)?;
+//! assert_eq!(RetrogradeTable::open(&path)?.distance(&board), Some(31));
+//! # let _ = std::fs::remove_file(&path);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`EndgameTable`]: super::endgame::EndgameTable
+
+use super::entropy::{EntropyCalculator, ManhattanDistance};
+use super::move_validator::Position;
+use super::puzzle_state::PuzzleState;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Largest table `generate` builds, in placements (one byte each)
+pub const MAX_RETROGRADE_STATES: usize = 1 << 30;
+
+/// Largest board a mask can describe, one bit per cell
+pub const MAX_MASK_SIZE: usize = 8;
+
+/// Magic bytes at the start of every table file
+const FILE_MAGIC: &[u8; 4] = b"SLRG";
+
+/// Bumped whenever the file layout changes
+const FILE_VERSION: u32 = 1;
+
+/// Magic, version, size, goal empty cell, then the tracked home cells
+const HEADER_LEN: usize = 4 + 4 + 4 + 4 + 8;
+
+/// Marks placements that cannot reach the goal
+const UNREACHABLE: u8 = u8::MAX;
+
+/// The tiles a table tracks, named by their home cells, plus the empty cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileMask {
+    size: usize,
+    blank: usize, // Cell index of the goal's empty cell
+    tiles: u64,   // One bit per home cell, row by row
+}
+
+impl TileMask {
+    /// Tracks every tile of `goal`
+    /// Returns None if the board is larger than `MAX_MASK_SIZE`
+    pub fn full(goal: &PuzzleState) -> Option<Self> {
+        Self::new(goal, goal.tiles().map(|(_, tile)| tile.home_position))
+    }
+
+    /// Tracks the tiles whose home cells in `goal` are `homes`
+    /// Returns None if the board is larger than `MAX_MASK_SIZE`, or a home is
+    /// off the board or is the empty cell's
+    pub fn new(goal: &PuzzleState, homes: impl IntoIterator<Item = Position>) -> Option<Self> {
+        let size = goal.size();
+        if size > MAX_MASK_SIZE {
+            return None;
+        }
+        let blank = cell_index(goal.goal_empty_position(), size);
+        let mut tiles = 0u64;
+        for (row, col) in homes {
+            let home = cell_index((row, col), size);
+            if row >= size || col >= size || home == blank {
+                return None;
+            }
+            tiles |= 1 << home;
+        }
+        Some(Self { size, blank, tiles })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of tracked tiles, not counting the empty cell
+    pub fn tracked(&self) -> usize {
+        self.tiles.count_ones() as usize
+    }
+
+    /// Returns true if the tile that belongs at `home` is tracked
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.size && col < self.size && self.tiles & (1 << cell_index((row, col), self.size)) != 0
+    }
+
+    /// Returns the number of placements of the tracked tiles and the empty cell
+    /// Returns None if it overflows
+    pub fn placements(&self) -> Option<usize> {
+        let cells = self.size * self.size;
+        (cells - self.tracked()..=cells).try_fold(1usize, |total, factor| total.checked_mul(factor))
+    }
+
+    /// Home cell indexes of the tracked tiles, ascending
+    fn homes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.size * self.size).filter(|&home| self.tiles & (1 << home) != 0)
+    }
+
+    /// Returns the tracked tile's place among the tracked tiles
+    fn slot(&self, home: usize) -> usize {
+        (self.tiles & ((1 << home) - 1)).count_ones() as usize
+    }
+}
+
+/// Distances are kept in memory, or read from a mapped file
+#[derive(Debug)]
+enum Storage {
+    Owned(Vec<u8>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Mapped(memmap2::Mmap), // Whole file, header included
+}
+
+/// Distance to the goal of every placement of a [`TileMask`]'s tiles
+#[derive(Debug)]
+pub struct RetrogradeTable {
+    mask: TileMask,
+    storage: Storage,
+}
+
+impl RetrogradeTable {
+    /// Builds the table by breadth-first search backward from the goal
+    /// Returns None if the mask has more than `MAX_RETROGRADE_STATES` placements
+    pub fn generate(mask: TileMask) -> Option<Self> {
+        let total = mask.placements().filter(|&total| total <= MAX_RETROGRADE_STATES)?;
+        let cells = mask.size * mask.size;
+        let goal: Vec<usize> = mask.homes().chain([mask.blank]).collect();
+        let mut distances = vec![UNREACHABLE; total];
+        distances[rank(&goal, cells)] = 0;
+
+        // Ranks fit in u32 below MAX_RETROGRADE_STATES, halving the layers' memory
+        let mut layer = vec![rank(&goal, cells) as u32];
+        let mut placement = goal.clone();
+        let mut depth = 0u8;
+        while !layer.is_empty() {
+            assert!(depth < UNREACHABLE - 1, "distances outgrew a byte");
+            let mut next = Vec::new();
+            for &current in &layer {
+                unrank(current as usize, cells, &mut placement);
+                for neighbour in neighbours(&mut placement, mask.size) {
+                    let distance = &mut distances[neighbour];
+                    if *distance == UNREACHABLE {
+                        *distance = depth + 1;
+                        next.push(neighbour as u32);
+                    }
+                }
+            }
+            layer = next;
+            depth += 1;
+        }
+        Some(Self {
+            mask,
+            storage: Storage::Owned(distances),
+        })
+    }
+
+    /// Writes the table to `path`, replacing any previous file
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.placements());
+        bytes.extend_from_slice(FILE_MAGIC);
+        bytes.extend_from_slice(&FILE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.mask.size as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.mask.blank as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.mask.tiles.to_le_bytes());
+        bytes.extend_from_slice(self.distances());
+
+        // Write then rename so a crash mid-write never leaves a truncated table
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Opens a table written by `save`, mapping the file rather than reading it
+    /// (reading it where mapping is unavailable)
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be opened, or
+    /// `InvalidData` if it is not a table of this version
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let storage = {
+            let file = fs::File::open(path)?;
+            // SAFETY: the map is only read, and tables are replaced by renaming a
+            // new file over the old one, so a mapped file is never written to
+            Storage::Mapped(unsafe { memmap2::Mmap::map(&file)? })
+        };
+        #[cfg(target_arch = "wasm32")]
+        let storage = Storage::Owned(fs::read(path)?);
+
+        let bytes = match &storage {
+            Storage::Owned(bytes) => bytes.as_slice(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Storage::Mapped(map) => &map[..],
+        };
+        let mask = parse_header(bytes).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a retrograde table"))?;
+        let storage = match storage {
+            Storage::Owned(mut bytes) => Storage::Owned(bytes.split_off(HEADER_LEN)),
+            #[cfg(not(target_arch = "wasm32"))]
+            mapped => mapped,
+        };
+        Ok(Self { mask, storage })
+    }
+
+    pub fn mask(&self) -> TileMask {
+        self.mask
+    }
+
+    /// Returns the number of placements in the table, reachable or not
+    pub fn placements(&self) -> usize {
+        self.distances().len()
+    }
+
+    /// Returns the number of placements that can reach the goal
+    pub fn reachable(&self) -> usize {
+        self.distances().iter().filter(|&&d| d != UNREACHABLE).count()
+    }
+
+    /// Returns the largest distance in the table
+    pub fn diameter(&self) -> u32 {
+        self.distances().iter().filter(|&&d| d != UNREACHABLE).max().map_or(0, |&d| u32::from(d))
+    }
+
+    /// Returns the fewest moves that bring the tracked tiles and the empty cell
+    /// of `state` home, ignoring every other tile
+    /// Returns None if `state` is another size, its goal's empty cell is
+    /// elsewhere, or the placement cannot reach the goal
+    pub fn distance(&self, state: &PuzzleState) -> Option<u32> {
+        let size = self.mask.size;
+        if state.size() != size || cell_index(state.goal_empty_position(), size) != self.mask.blank {
+            return None;
+        }
+        let mut placement = vec![0; self.mask.tracked() + 1];
+        for (pos, tile) in state.tiles() {
+            if self.mask.contains(tile.home_position) {
+                placement[self.mask.slot(cell_index(tile.home_position, size))] = cell_index(pos, size);
+            }
+        }
+        placement[self.mask.tracked()] = cell_index(state.empty_position(), size);
+        self.distance_of(&placement)
+    }
+
+    /// Returns the distance of a placement given as cell indexes (row by row):
+    /// the tracked tiles in order of their home cells, then the empty cell
+    /// Returns None if the placement is malformed or cannot reach the goal
+    pub fn distance_of(&self, placement: &[usize]) -> Option<u32> {
+        let cells = self.mask.size * self.mask.size;
+        let mut seen = 0u64;
+        for &cell in placement {
+            if cell >= cells || seen & (1 << cell) != 0 {
+                return None;
+            }
+            seen |= 1 << cell;
+        }
+        if placement.len() != self.mask.tracked() + 1 {
+            return None;
+        }
+        Some(self.distances()[rank(placement, cells)])
+            .filter(|&d| d != UNREACHABLE)
+            .map(u32::from)
+    }
+
+    fn distances(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Owned(distances) => distances,
+            #[cfg(not(target_arch = "wasm32"))]
+            Storage::Mapped(map) => &map[HEADER_LEN..],
+        }
+    }
+}
+
+/// Tracked-tile distances where the table covers the board, Manhattan distance elsewhere
+impl EntropyCalculator for RetrogradeTable {
+    fn calculate(&self, state: &PuzzleState) -> u32 {
+        self.distance(state).unwrap_or_else(|| ManhattanDistance.calculate(state))
+    }
+}
+
+/// Reads the mask from a table file, checking the file holds all its placements
+fn parse_header(bytes: &[u8]) -> Option<TileMask> {
+    let (header, body) = bytes.split_at_checked(HEADER_LEN)?;
+    let word = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap()) as usize;
+    if &header[..4] != FILE_MAGIC || word(4) != FILE_VERSION as usize {
+        return None;
+    }
+    let (size, blank) = (word(8), word(12));
+    let tiles = u64::from_le_bytes(header[16..24].try_into().unwrap());
+    let cells = size * size;
+    let valid = (1..=MAX_MASK_SIZE).contains(&size)
+        && blank < cells
+        && tiles & (1 << blank) == 0
+        && (cells == 64 || tiles >> cells == 0);
+    let mask = valid.then_some(TileMask { size, blank, tiles })?;
+    (mask.placements() == Some(body.len())).then_some(mask)
+}
+
+fn cell_index((row, col): Position, size: usize) -> usize {
+    row * size + col
+}
+
+/// Ranks of the placements one move from `placement`, which is left as it was
+fn neighbours(placement: &mut [usize], size: usize) -> Vec<usize> {
+    let cells = size * size;
+    let last = placement.len() - 1;
+    let empty = placement[last];
+    let (row, col) = (empty / size, empty % size);
+    let targets = [
+        (row > 0).then(|| empty - size),
+        (row + 1 < size).then(|| empty + size),
+        (col > 0).then(|| empty - 1),
+        (col + 1 < size).then(|| empty + 1),
+    ];
+    targets
+        .into_iter()
+        .flatten()
+        .map(|target| {
+            // An untracked tile may sit in `target`; it simply swaps with the empty cell unseen
+            let tracked = placement[..last].iter().position(|&cell| cell == target);
+            if let Some(tile) = tracked {
+                placement[tile] = empty;
+            }
+            placement[last] = target;
+            let ranked = rank(placement, cells);
+            if let Some(tile) = tracked {
+                placement[tile] = target;
+            }
+            placement[last] = empty;
+            ranked
+        })
+        .collect()
+}
+
+/// Rank of a placement of distinct cells among `cells`, as a mixed-radix number
+/// whose digit `i` counts the cells below `placement[i]` not used before it
+fn rank(placement: &[usize], cells: usize) -> usize {
+    let mut rank = 0;
+    for (i, &cell) in placement.iter().enumerate() {
+        let used_below = placement[..i].iter().filter(|&&earlier| earlier < cell).count();
+        rank = rank * (cells - i) + cell - used_below;
+    }
+    rank
+}
+
+/// Inverse of `rank`, filling `placement` in place
+fn unrank(mut rank: usize, cells: usize, placement: &mut [usize]) {
+    let mut digits = vec![0; placement.len()];
+    for i in (0..placement.len()).rev() {
+        digits[i] = rank % (cells - i);
+        rank /= cells - i;
+    }
+    let mut used = 0u64;
+    for (slot, digit) in placement.iter_mut().zip(digits) {
+        let cell = (0..cells).filter(|&cell| used & (1 << cell) == 0).nth(digit).expect("digit below the free cells");
+        used |= 1 << cell;
+        *slot = cell;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::heuristic_lab::optimal_distances;
+    use crate::model::solver::scramble;
+    use crate::model::{EndgameTable, GoalSpec};
+
+    #[test]
+    fn test_full_mask_matches_the_endgame_table() {
+        let goal = PuzzleState::new(3).unwrap();
+        let table = RetrogradeTable::generate(TileMask::full(&goal).unwrap()).unwrap();
+        assert_eq!(table.placements(), 362_880);
+        assert_eq!(table.reachable(), 181_440);
+        assert_eq!(table.diameter(), 31);
+
+        let endgame = EndgameTable::shared();
+        for (board, distance) in optimal_distances(&goal).into_iter().step_by(101) {
+            let board = board.to_state();
+            assert_eq!(table.distance(&board), Some(distance));
+            assert_eq!(endgame.distance(&board), Some(distance));
+        }
+
+        // Only the goal's empty cell matters, not its numbering
+        let reversed = PuzzleState::with_goal(3, GoalSpec::Reversed).unwrap();
+        assert_eq!(table.distance(&reversed), None);
+        let blank_first = PuzzleState::with_goal(3, GoalSpec::BlankFirst).unwrap();
+        assert_eq!(table.distance(&blank_first), None);
+    }
+
+    #[test]
+    fn test_patterns_never_overestimate() {
+        let goal = PuzzleState::new(3).unwrap();
+        let mask = TileMask::new(&goal, [(0, 0), (0, 1), (0, 2), (1, 0)]).unwrap();
+        assert_eq!(mask.tracked(), 4);
+        assert!(mask.contains((0, 2)) && !mask.contains((1, 1)));
+        let table = RetrogradeTable::generate(mask).unwrap();
+        assert_eq!(table.placements(), 9 * 8 * 7 * 6 * 5);
+        assert_eq!(table.reachable(), table.placements());
+        assert_eq!(table.distance(&goal), Some(0));
+
+        for (board, distance) in optimal_distances(&goal).into_iter().step_by(37) {
+            assert!(table.distance(&board.to_state()).unwrap() <= distance);
+        }
+        assert_eq!(table.calculate(&PuzzleState::new(4).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_masks_reject_what_they_cannot_describe() {
+        let goal = PuzzleState::new(3).unwrap();
+        assert!(TileMask::new(&goal, [(2, 2)]).is_none());
+        assert!(TileMask::new(&goal, [(0, 3)]).is_none());
+        assert!(TileMask::full(&PuzzleState::new(9).unwrap()).is_none());
+        let huge = TileMask::full(&PuzzleState::new(5).unwrap()).unwrap();
+        assert!(RetrogradeTable::generate(huge).is_none());
+    }
+
+    #[test]
+    fn test_ranks_round_trip() {
+        let mut placement = [0; 4];
+        for r in [0, 1, 999, 16 * 15 * 14 * 13 - 1] {
+            unrank(r, 16, &mut placement);
+            assert_eq!(rank(&placement, 16), r);
+        }
+        assert_eq!(placement, [15, 14, 13, 12]);
+    }
+
+    #[test]
+    fn test_saved_tables_open_mapped() {
+        let goal = PuzzleState::new(4).unwrap();
+        let table = RetrogradeTable::generate(TileMask::new(&goal, [(3, 0), (3, 1), (3, 2)]).unwrap()).unwrap();
+        let dir = std::env::temp_dir().join(format!("slider-retrograde-{}", std::process::id()));
+        let path = dir.join("bottom-row.slrg");
+        table.save(&path).unwrap();
+
+        let opened = RetrogradeTable::open(&path).unwrap();
+        assert_eq!(opened.mask(), table.mask());
+        assert_eq!(opened.distances(), table.distances());
+        let board = scramble(4, 30, 3);
+        assert_eq!(opened.distance(&board), table.distance(&board));
+
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert_eq!(RetrogradeTable::open(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,86 @@
+//! # Scoring
+//!
+//! Grades a finished game by comparing the tile moves taken with **par**, the
+//! optimal solution length of the shuffled board. Par counts single-tile moves,
+//! so grading uses tile moves too: a chain move that slides three tiles costs
+//! three, and no game can beat par.
+//!
+//! | Grade | Tile moves       |
+//! |-------|------------------|
+//! | S     | at par           |
+//! | A     | up to 125% of par |
+//! | B     | up to 150% of par |
+//! | C     | anything more    |
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::Grade;
+//!
+//! assert_eq!(Grade::for_moves(42, 42), Grade::S);
+//! assert_eq!(Grade::for_moves(50, 42), Grade::A);
+//! assert_eq!(Grade::for_moves(90, 42), Grade::C);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest A-grade move count, as a percentage of par
+const A_PERCENT: usize = 125;
+
+/// Largest B-grade move count, as a percentage of par
+const B_PERCENT: usize = 150;
+
+/// Letter grade for a finished game, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Grade {
+    S,
+    A,
+    B,
+    C,
+}
+
+impl Grade {
+    /// Grades `tile_moves` against `par`
+    pub fn for_moves(tile_moves: usize, par: u32) -> Grade {
+        let par = par as usize;
+        if tile_moves <= par {
+            Grade::S
+        } else if tile_moves * 100 <= par * A_PERCENT {
+            Grade::A
+        } else if tile_moves * 100 <= par * B_PERCENT {
+            Grade::B
+        } else {
+            Grade::C
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grade_thresholds() {
+        assert_eq!(Grade::for_moves(40, 40), Grade::S);
+        assert_eq!(Grade::for_moves(41, 40), Grade::A);
+        assert_eq!(Grade::for_moves(50, 40), Grade::A);
+        assert_eq!(Grade::for_moves(51, 40), Grade::B);
+        assert_eq!(Grade::for_moves(60, 40), Grade::B);
+        assert_eq!(Grade::for_moves(61, 40), Grade::C);
+        assert_eq!(Grade::for_moves(0, 0), Grade::S); // Already solved
+        assert!(Grade::S < Grade::C);
+    }
+}
@@ -1,8 +1,10 @@
 //! # A* Pathfinding Solver
 //!
-//! This module implements an optimal pathfinding solver using the A* algorithm.
-//! The solver finds the shortest possible solution to sliding tile puzzles
-//! using an enhanced heuristic that combines multiple puzzle complexity metrics.
+//! This module implements a pathfinding solver using the A* algorithm.
+//! The solver finds short solutions to sliding tile puzzles using an enhanced
+//! heuristic that combines multiple puzzle complexity metrics. That heuristic
+//! can overestimate, so a solution is not always the shortest; IDA*
+//! ([`SolverKind::IdaStar`]) is the solver that guarantees one.
 //!
 //! ## Algorithm Overview
 //!
@@ -15,11 +17,20 @@
 //!
 //! ## Key Features
 //!
-//! - **Optimal Solutions**: Guaranteed to find the shortest possible path
-//! - **Memory Efficient**: Uses indexed storage instead of exponential parent chains
+//! - **Near-Optimal Solutions**: Shortest with an admissible heuristic such as
+//!   [`ManhattanDistance`](super::ManhattanDistance), close to it with the default one
+//! - **Memory Efficient**: Uses indexed storage instead of exponential parent chains,
+//!   keeping every node's compact [`PackedState`] bytes in one arena buffer
 //! - **Cancellation Support**: Can be interrupted during long searches
-//! - **Configurable Limits**: Adjustable iteration limits to prevent infinite searches
-//! - **Fast State Hashing**: U64 hashing for efficient duplicate detection
+//! - **Configurable Limits**: Iteration and wall-clock limits via [`SolverConfig`]
+//! - **Fast State Hashing**: U64 hashing for efficient duplicate detection, optionally
+//!   shared between mirror-image boards (see [`SolverConfig::symmetry`])
+//! - **Memory Budget**: Searches stop with [`SolverError::MemoryExceeded`] instead of
+//!   exhausting RAM (see [`SolverConfig::max_memory_bytes`])
+//! - **Fast Estimates**: [`AStarSolver::solve_approx`] trades optimality for speed
+//!   with weighted A*
+//! - **Pluggable Heuristic**: [`AStarSolver::builder`] swaps in any
+//!   [`EntropyCalculator`] and can add catalog techniques as macro moves
 //!
 //! ## Performance Characteristics
 //!
@@ -37,7 +48,7 @@
 //!
 //! // Get solution length
 //! if let Some(length) = solver.solve(&puzzle) {
-//!     println!("Optimal solution: {} moves", length);
+//!     println!("Solution: {} moves", length);
 //! }
 //!
 //! // Get full solution path
@@ -49,31 +60,156 @@
 //! }
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! ## Choosing the Heuristic
+//!
+//! ```rust
+//! use slider::model::{AStarSolver, ManhattanDistance, PuzzleState, RelativePatternCatalog};
+//!
+//! let solver = AStarSolver::builder()
+//!     .heuristic(Box::new(ManhattanDistance))
+//!     .max_iterations(200_000)
+//!     .patterns(RelativePatternCatalog::builtin())
+//!     .build();
+//! let mut puzzle = PuzzleState::new(3)?;
+//! puzzle.apply_immediate_move((2, 1));
+//! assert_eq!(solver.solve(&puzzle), Some(1));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
+use super::endgame::{EndgameTable, ENDGAME_SIZE};
 use super::enhanced_heuristic::EnhancedHeuristic;
 use super::entropy::EntropyCalculator;
-use super::move_validator::{MoveValidator, Position};
+use super::error::SolverError;
+use super::greedy_solver::GreedySolver;
+use super::ida_star_solver::IDAStarSolver;
+use super::move_system::MoveSystem;
+use super::move_validator::Position;
+use super::node_arena::{NodeArena, NodeId};
+use super::notation;
+use super::packed_state::PackedState;
+use super::pattern::{RelativePattern, RelativePatternCatalog};
 use super::puzzle_state::PuzzleState;
+use super::strategic_solver::{Annotation, StrategicSolver};
+use super::symmetry::SymmetryGroup;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::collections::hash_map::DefaultHasher;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::mem::size_of;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Number of node expansions between cancellation checks and progress reports
+pub(super) const PROGRESS_INTERVAL: usize = 1000;
+
+/// Snapshot of an in-flight search, reported periodically while solving
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolverProgress {
+    /// Nodes expanded so far
+    pub nodes_explored: usize,
+    /// Current search depth (for A*, the f-score of the node being expanded)
+    pub current_depth: u32,
+    /// Wall-clock time since the search started
+    pub elapsed: Duration,
+    /// Nodes waiting to be expanded (for IDA*, the depth of the search stack)
+    pub frontier_size: usize,
+    /// Board being expanded when the snapshot was taken, for search visualizations
+    pub expanded: Option<PackedState>,
+}
+
+/// A solution path together with statistics about the search that found it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Solution {
+    /// Tile positions to move, in order
+    pub path: Vec<Position>,
+    /// Nodes taken off the frontier and expanded
+    pub nodes_expanded: usize,
+    /// Successor nodes created (duplicates that were discarded are not counted)
+    pub nodes_generated: usize,
+    /// Repeated boards cut off without expanding them (IDA* only; 0 for the others)
+    pub nodes_pruned: usize,
+    /// Peak frontier size (for depth-first solvers, the deepest search stack)
+    pub max_open_size: usize,
+    /// Wall-clock time spent searching
+    pub duration: Duration,
+    /// Estimated peak memory of the search bookkeeping, in bytes
+    /// (A* only; 0 for solvers that do not track it)
+    pub peak_memory_bytes: usize,
+    /// Empty cell before the first move, which the blank-move notation starts from
+    pub start_empty: Position,
+    /// What each stretch of the path achieves (strategic solver only; empty for the others)
+    pub annotations: Vec<Annotation>,
+}
+
+impl Solution {
+    /// Returns the number of moves in the solution
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns true if the puzzle was already solved
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+}
+
+impl fmt::Display for Solution {
+    /// Writes the path in blank-move notation, such as `RDLU`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&notation::blank_moves(self.start_empty, &self.path))
+    }
+}
 
-/// Represents a state in the A* search
-#[derive(Clone)]
+/// Scores of a state in the A* search; its board and parent live in the [`NodeArena`]
+#[derive(Clone, Copy)]
 struct SearchNode {
-    state: PuzzleState,
     g_score: u32,           // Cost from start (moves taken)
     h_score: u32,           // Heuristic estimate to goal
-    parent_index: Option<usize>, // Index into node storage vector
-    move_from_parent: Option<Position>, // Single move that led to this state
+    move_from_parent: Option<Edge>, // Move or macro that led to this state
+}
+
+/// Boards A* has expanded or reached, keyed by their hash under the goal's symmetries
+struct SeenStates {
+    closed_set: HashSet<u64>,
+    best_g_scores: HashMap<u64, u32>,
+    symmetries: SymmetryGroup,
+}
+
+impl SeenStates {
+    fn new(symmetries: SymmetryGroup) -> Self {
+        Self {
+            closed_set: HashSet::new(),
+            best_g_scores: HashMap::new(),
+            symmetries,
+        }
+    }
+
+    /// Returns the key `state` and its mirror images share
+    fn key(&self, state: &PackedState) -> u64 {
+        self.symmetries.canonical_hash(state)
+    }
+}
+
+/// How a search node was reached from its parent
+#[derive(Clone, Copy)]
+enum Edge {
+    /// A single immediate move of the tile at this row-major cell index
+    Move(u16),
+    /// A whole macro pattern, by index into the solver's macro list
+    Pattern(u32),
 }
 
 impl SearchNode {
-    fn f_score(&self) -> u32 {
-        self.g_score + self.h_score
+    /// Priority of the node; a weight of 1 gives the exact A* f-score
+    fn f_score(&self, weight: f64) -> u32 {
+        if weight == 1.0 {
+            self.g_score + self.h_score
+        } else {
+            self.g_score + (weight * f64::from(self.h_score)).round() as u32
+        }
     }
 }
 
@@ -81,18 +217,17 @@ impl SearchNode {
 #[derive(Eq, PartialEq)]
 struct HeapEntry {
     f_score: u32,
-    g_score: u32, // Used for tie-breaking
-    node_index: usize,
+    tie_score: u32, // Higher goes first among equal f_scores (see `TieBreak`)
+    node_index: NodeId,
 }
 
 impl Ord for HeapEntry {
     fn cmp(&self, other: &Self) -> Ordering {
         // Reverse ordering for min-heap behavior (lower f_score = higher priority)
-        // When f_scores are equal, prefer higher g_score (closer to goal)
         other
             .f_score
             .cmp(&self.f_score)
-            .then_with(|| self.g_score.cmp(&other.g_score))
+            .then_with(|| self.tie_score.cmp(&other.tie_score))
     }
 }
 
@@ -102,178 +237,618 @@ impl PartialOrd for HeapEntry {
     }
 }
 
-/// A* solver that finds the optimal solution path using Enhanced Heuristic
+/// Open set ordered by `g + weight × h` (a weight of 1 is plain A*), ties
+/// broken by `tie_break`
+struct Frontier {
+    heap: BinaryHeap<HeapEntry>,
+    weight: f64,
+    tie_break: TieBreak,
+}
+
+impl Frontier {
+    fn new(weight: f64, tie_break: TieBreak) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            weight,
+            tie_break,
+        }
+    }
+
+    fn push(&mut self, node: &SearchNode, node_index: NodeId) {
+        self.heap.push(HeapEntry {
+            f_score: node.f_score(self.weight),
+            tie_score: match self.tie_break {
+                TieBreak::HighG => node.g_score,
+                TieBreak::LowH => u32::MAX - node.h_score,
+                TieBreak::LowG => u32::MAX - node.g_score,
+            },
+            node_index,
+        });
+    }
+
+    fn pop(&mut self) -> Option<HeapEntry> {
+        self.heap.pop()
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// Approximate bytes per hash-table entry of type `T`: the entry plus its
+/// control byte, at the table's 7/8 maximum load
+fn table_entry_bytes<T>() -> usize {
+    (size_of::<T>() + 1) * 8 / 7
+}
+
+/// Which of two A* frontier nodes with the same priority is expanded first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// The node furthest from the start, which dives toward the goal
+    #[default]
+    HighG,
+    /// The node with the lowest estimate; orders like `HighG` under plain A*,
+    /// but also separates nodes whose weighted estimates round alike
+    LowH,
+    /// The node nearest the start, widening the search like breadth-first search
+    LowG,
+}
+
+impl TieBreak {
+    pub const ALL: [TieBreak; 3] = [TieBreak::HighG, TieBreak::LowH, TieBreak::LowG];
+
+    /// Parses `high-g`, `low-h` or `low-g`
+    /// Returns None for anything else; case and surrounding whitespace are ignored
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|tie_break| tie_break.to_string().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl fmt::Display for TieBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TieBreak::HighG => "high-g",
+            TieBreak::LowH => "low-h",
+            TieBreak::LowG => "low-g",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Search limits and A* policy for the solver
+///
+/// The policy fields trade optimality for speed: a `weight` above 1 or
+/// `reopen_closed` off with an inconsistent heuristic can return longer
+/// solutions. The defaults are plain A* with a strict closed set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverConfig {
+    /// Maximum number of node expansions before giving up
+    pub max_iterations: usize,
+    /// Maximum wall-clock time before giving up
+    pub timeout: Duration,
+    /// Approximate memory A* may hold in nodes, frontier, and hash tables
+    /// before giving up with [`SolverError::MemoryExceeded`]
+    pub max_memory_bytes: usize,
+    /// Whether A* closes a board together with its mirror images under the
+    /// goal's symmetries (see [`SymmetryGroup`]); solutions stay optimal as
+    /// long as the heuristic scores mirrored boards alike, as every built-in
+    /// admissible one does
+    pub symmetry: bool,
+    /// A* orders nodes by `g + weight × h`; above 1 it trusts the heuristic
+    /// more, expanding fewer nodes for a solution at most `weight` times the
+    /// optimum (with an admissible heuristic). Values below 1 count as 1.
+    pub weight: f64,
+    /// Which of two A* nodes with the same priority is expanded first
+    pub tie_break: TieBreak,
+    /// Whether A* expands a closed board again when a shorter path to it turns
+    /// up, which only happens under a weight or an inconsistent heuristic;
+    /// off, the first expansion stands
+    pub reopen_closed: bool,
+    /// How many threads IDA* shares each iteration among when run through
+    /// [`SolverKind`]; None uses every core (see [`IDAStarSolver::parallel`])
+    pub threads: Option<usize>,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1_000_000,
+            timeout: Duration::from_secs(60),
+            max_memory_bytes: 1024 * 1024 * 1024,
+            symmetry: false,
+            weight: 1.0,
+            tie_break: TieBreak::HighG,
+            reopen_closed: false,
+            threads: None,
+        }
+    }
+}
+
+/// Selects which search algorithm is used for background solves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SolverKind {
+    /// A* search with the enhanced heuristic; fast, but the heuristic can
+    /// overestimate, so solutions are near-optimal rather than shortest
+    #[default]
+    AStar,
+    /// Optimal iterative-deepening A*; uses memory proportional to solution depth
+    IdaStar,
+    /// Greedy best-first search; fast but solutions are not optimal
+    Greedy,
+    /// Row-by-row reduction; works on every grid size but solutions are long
+    Strategic,
+}
+
+impl SolverKind {
+    /// All solver kinds, in display order
+    pub const ALL: [SolverKind; 4] = [
+        SolverKind::AStar,
+        SolverKind::IdaStar,
+        SolverKind::Greedy,
+        SolverKind::Strategic,
+    ];
+
+    /// Returns true if this solver always finds a shortest solution
+    /// Only IDA* searches with an admissible heuristic; A* trades that for speed
+    pub fn is_optimal(self) -> bool {
+        self == SolverKind::IdaStar
+    }
+
+    /// Parses a solver name such as `astar`, `ida*` or `strategic`
+    /// Returns None for anything else; case and surrounding whitespace are ignored
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "astar" | "a*" => Some(SolverKind::AStar),
+            "idastar" | "ida*" => Some(SolverKind::IdaStar),
+            "greedy" => Some(SolverKind::Greedy),
+            "strategic" => Some(SolverKind::Strategic),
+            _ => None,
+        }
+    }
+
+    /// Returns a sensible default for the given grid size: IDA*, for shortest
+    /// solutions, up to 5×5; optimal search is hopeless beyond that, so larger
+    /// grids use the strategic solver
+    pub fn default_for_size(size: usize) -> Self {
+        if size <= 5 {
+            SolverKind::IdaStar
+        } else {
+            SolverKind::Strategic
+        }
+    }
+
+    /// Runs the selected solver with the given limits
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_reporting(
+        self,
+        config: SolverConfig,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Vec<Position>> {
+        self.solve_instrumented(config, initial_state, cancel_flag, on_progress)
+            .map(|solution| solution.path)
+    }
+
+    /// Runs the selected solver with the given limits, returning search statistics
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_instrumented(
+        self,
+        config: SolverConfig,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.try_solve_instrumented(config, initial_state, cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Runs the selected solver like `solve_instrumented`
+    ///
+    /// A* and IDA* read 3×3 solutions from the [`EndgameTable`] instead of
+    /// searching, so those report no progress and count one node per move.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why the solver stopped without a solution
+    pub fn try_solve_instrumented(
+        self,
+        config: SolverConfig,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        // Every 3×3 board is in the endgame table, so the heuristic searches need not run
+        if matches!(self, SolverKind::AStar | SolverKind::IdaStar) && initial_state.size() == ENDGAME_SIZE {
+            let started = Instant::now();
+            if let Some(path) = EndgameTable::shared().solve(initial_state) {
+                return Ok(Solution {
+                    nodes_expanded: path.len(),
+                    path,
+                    duration: started.elapsed(),
+                    start_empty: initial_state.empty_position(),
+                    ..Solution::default()
+                });
+            }
+        }
+        match self {
+            SolverKind::AStar => AStarSolver::with_config(config)
+                .try_solve_instrumented(initial_state, cancel_flag, on_progress),
+            SolverKind::IdaStar => {
+                let solver = IDAStarSolver::with_config(config);
+                match config.threads {
+                    Some(threads) => solver.threads(threads),
+                    None => solver.parallel(),
+                }
+                .try_solve_instrumented(initial_state, cancel_flag, on_progress)
+            }
+            SolverKind::Greedy => GreedySolver::with_config(config)
+                .try_solve_instrumented(initial_state, cancel_flag, on_progress),
+            SolverKind::Strategic => StrategicSolver::with_config(config)
+                .try_solve_instrumented(initial_state, cancel_flag, on_progress),
+        }
+    }
+}
+
+impl fmt::Display for SolverKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SolverKind::AStar => "A*",
+            SolverKind::IdaStar => "IDA*",
+            SolverKind::Greedy => "Greedy",
+            SolverKind::Strategic => "Strategic",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A* solver that finds a near-optimal solution path using Enhanced Heuristic
+///
+/// Enhanced Heuristic can overestimate, so paths are not always shortest; a
+/// solver built with an admissible heuristic finds shortest paths.
+///
+/// [`AStarSolver::builder`] configures another heuristic and macro moves.
 pub struct AStarSolver {
-    heuristic: EnhancedHeuristic,
-    max_iterations: usize,
+    heuristic: Box<dyn EntropyCalculator + Send + Sync>,
+    config: SolverConfig,
+    macros: Vec<RelativePattern>, // Every orientation of the catalog patterns, tried from each node
 }
 
 impl AStarSolver {
     pub fn new() -> Self {
-        Self {
-            heuristic: EnhancedHeuristic,
-            max_iterations: 1_000_000,
+        Self::with_config(SolverConfig::default())
+    }
+
+    /// Creates a solver with custom search limits
+    pub fn with_config(config: SolverConfig) -> Self {
+        Self::builder().config(config).build()
+    }
+
+    /// Starts configuring a solver; unset options keep the defaults of `new`
+    pub fn builder() -> AStarSolverBuilder {
+        AStarSolverBuilder {
+            heuristic: None,
+            config: SolverConfig::default(),
+            patterns: None,
         }
     }
 
-    /// Returns the length of the optimal solution, or None if unsolvable/timeout
+    /// Returns the search limits used by this solver
+    pub fn config(&self) -> SolverConfig {
+        self.config
+    }
+
+    /// Returns the maximum number of node expansions before the search gives up
+    pub fn max_iterations(&self) -> usize {
+        self.config.max_iterations
+    }
+
+    /// Returns the length of the solution found, or None if unsolvable/timeout
     pub fn solve(&self, initial_state: &PuzzleState) -> Option<u32> {
         self.solve_with_path(initial_state).map(|path| path.len() as u32)
     }
 
-    /// Returns the optimal solution path as a sequence of tile positions to move
+    /// Returns the solution path as a sequence of tile positions to move
     /// Returns None if unsolvable or timeout
     pub fn solve_with_path(&self, initial_state: &PuzzleState) -> Option<Vec<Position>> {
         self.solve_with_path_cancellable(initial_state, None)
     }
 
-    /// Returns the optimal solution path with support for cancellation
+    /// Returns the solution path like `solve_with_path`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why no path was found (see
+    /// `try_solve_instrumented`)
+    pub fn try_solve(&self, initial_state: &PuzzleState) -> Result<Vec<Position>, SolverError> {
+        self.try_solve_instrumented(initial_state, None, &mut |_| {})
+            .map(|solution| solution.path)
+    }
+
+    /// Returns the solution path with support for cancellation
     /// Returns None if unsolvable, timeout, or cancelled
     pub fn solve_with_path_cancellable(
         &self,
         initial_state: &PuzzleState,
         cancel_flag: Option<Arc<AtomicBool>>,
     ) -> Option<Vec<Position>> {
+        self.solve_with_path_reporting(initial_state, cancel_flag, &mut |_| {})
+    }
+
+    /// Returns the solution path, invoking `on_progress` every
+    /// `PROGRESS_INTERVAL` expansions so callers can display search progress
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_reporting(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Vec<Position>> {
+        self.solve_instrumented(initial_state, cancel_flag, on_progress)
+            .map(|solution| solution.path)
+    }
+
+    /// Returns the solution with node counts and timing, reporting
+    /// progress like `solve_with_path_reporting`
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.try_solve_instrumented(initial_state, cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Returns the solution like `solve_instrumented`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why the search stopped: a search limit
+    /// from the config (iterations, time, or memory), cancellation, or an
+    /// exhausted search space
+    pub fn try_solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        self.search(initial_state, self.config.weight.max(1.0), cancel_flag, on_progress)
+    }
+
+    /// Returns the length of a near-optimal solution found by weighted A*
+    /// Returns None if unsolvable or timeout
+    ///
+    /// Nodes are ordered by `g + weight × h`, so a weight above 1 trusts the
+    /// heuristic more and reaches the goal after far fewer expansions. With an
+    /// admissible heuristic the answer is at most `weight` times the optimum;
+    /// a weight of 1 is exact A*. Weights below 1 are treated as 1. The weight
+    /// given here replaces `SolverConfig::weight`.
+    pub fn solve_approx(&self, initial_state: &PuzzleState, weight: f64) -> Option<u32> {
+        self.solve_approx_instrumented(initial_state, weight, None, &mut |_| {})
+            .map(|solution| solution.len() as u32)
+    }
+
+    /// Returns a near-optimal solution like `solve_approx`, with node counts
+    /// and timing, reporting progress like `solve_with_path_reporting`
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_approx_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        weight: f64,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.search(initial_state, weight.max(1.0), cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Runs the search, ordering nodes by `g + weight × h`
+    fn search(
+        &self,
+        initial_state: &PuzzleState,
+        weight: f64,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        let started = Instant::now();
         if initial_state.is_solved() {
-            return Some(Vec::new());
+            return Ok(Solution {
+                start_empty: initial_state.empty_position(),
+                ..Solution::default()
+            });
         }
 
-        let mut open_set = BinaryHeap::new();
-        let mut closed_set = HashSet::new();
-        let mut best_g_scores: HashMap<u64, u32> = HashMap::new();
-        let mut node_storage: Vec<SearchNode> = Vec::new();
+        let mut open_set = Frontier::new(weight, self.config.tie_break);
+        let mut seen = SeenStates::new(if self.config.symmetry {
+            SymmetryGroup::of_goal(initial_state)
+        } else {
+            SymmetryGroup::trivial()
+        });
+        let mut nodes = NodeArena::new(initial_state.size());
+
+        let initial_packed = PackedState::from_state(initial_state);
+        seen.best_g_scores.insert(seen.key(&initial_packed), 0);
 
         let initial_node = SearchNode {
-            state: initial_state.clone(),
             g_score: 0,
             h_score: self.heuristic.calculate(initial_state),
-            parent_index: None,
             move_from_parent: None,
         };
+        let root = nodes.push(&initial_packed, None, initial_node);
+        open_set.push(&initial_node, root);
 
-        let initial_f_score = initial_node.f_score();
-        let initial_g_score = initial_node.g_score;
-        node_storage.push(initial_node);
-        open_set.push(HeapEntry {
-            f_score: initial_f_score,
-            g_score: initial_g_score,
-            node_index: 0,
-        });
-
-        best_g_scores.insert(self.state_hash(initial_state), 0);
-
-        // Size is guaranteed valid since initial_state was constructed successfully
-        let validator = MoveValidator::new(initial_state.size()).expect("valid size");
         let mut iterations = 0;
+        let mut nodes_expanded = 0;
+        let mut max_open_size = open_set.len();
+        let mut peak_memory_bytes = 0;
+
+        // Every node keeps its state and a best-g entry; the frontier and
+        // closed set are charged per entry
+        let node_bytes = nodes.node_bytes() + table_entry_bytes::<(u64, u32)>();
 
-        while let Some(HeapEntry { node_index: current_idx, .. }) = open_set.pop() {
+        while let Some(HeapEntry { node_index: current_id, f_score, .. }) = open_set.pop() {
             iterations += 1;
+            let current_state = nodes.state(current_id);
 
-            // Check for cancellation every 1000 iterations
-            if iterations % 1000 == 0 {
+            // Check for cancellation, timeout, and report progress periodically
+            if iterations % PROGRESS_INTERVAL == 0 {
                 if let Some(ref cancel) = cancel_flag {
                     if cancel.load(AtomicOrdering::Relaxed) {
-                        return None; // Cancelled
+                        return Err(SolverError::Cancelled);
                     }
                 }
+                if started.elapsed() >= self.config.timeout {
+                    return Err(SolverError::TimeLimitExceeded {
+                        timeout: self.config.timeout,
+                    });
+                }
+                on_progress(SolverProgress {
+                    nodes_explored: iterations,
+                    current_depth: f_score,
+                    elapsed: started.elapsed(),
+                    frontier_size: open_set.len(),
+                    expanded: Some(current_state.clone()),
+                });
             }
 
-            if iterations > self.max_iterations {
-                return None; // Timeout
+            if iterations > self.config.max_iterations {
+                return Err(SolverError::TimeoutExceeded {
+                    max_iterations: self.config.max_iterations,
+                });
             }
 
-            let current = &node_storage[current_idx];
+            let memory_bytes = nodes.len() * node_bytes
+                + open_set.len() * size_of::<HeapEntry>()
+                + seen.closed_set.len() * table_entry_bytes::<u64>();
+            peak_memory_bytes = peak_memory_bytes.max(memory_bytes);
+            if memory_bytes > self.config.max_memory_bytes {
+                return Err(SolverError::MemoryExceeded {
+                    limit_bytes: self.config.max_memory_bytes,
+                });
+            }
 
-            if current.state.is_solved() {
-                return Some(self.reconstruct_path(&node_storage, current_idx));
+            if current_state.is_solved() {
+                return Ok(Solution {
+                    path: self.reconstruct_path(&nodes, current_id),
+                    nodes_expanded,
+                    nodes_generated: nodes.len() - 1,
+                    nodes_pruned: 0,
+                    max_open_size,
+                    duration: started.elapsed(),
+                    peak_memory_bytes,
+                    start_empty: initial_state.empty_position(),
+                    annotations: Vec::new(),
+                });
             }
 
-            let current_hash = self.state_hash(&current.state);
-            if closed_set.contains(&current_hash) {
+            // A shorter path to the board was found after this entry was queued
+            let current_key = seen.key(&current_state);
+            let current_g = nodes.data(current_id).g_score;
+            if seen.best_g_scores.get(&current_key).is_some_and(|&best| current_g > best) {
                 continue;
             }
-            closed_set.insert(current_hash);
+            if !seen.closed_set.insert(current_key) {
+                continue;
+            }
+            nodes_expanded += 1;
 
             // Explore all immediate moves (no chain moves for solver)
-            let empty_pos = current.state.empty_position();
-            for next_pos in validator.get_immediate_moves(empty_pos) {
+            let size = current_state.size();
+            for (row, col) in current_state.legal_moves() {
                 self.explore_successor(
-                    current_idx,
-                    next_pos,
-                    &mut node_storage,
+                    current_id,
+                    &current_state,
+                    Edge::Move((row * size + col) as u16),
+                    &mut nodes,
                     &mut open_set,
-                    &closed_set,
-                    &mut best_g_scores,
+                    &mut seen,
                 );
             }
+            for index in 0..self.macros.len() {
+                self.explore_successor(
+                    current_id,
+                    &current_state,
+                    Edge::Pattern(index as u32),
+                    &mut nodes,
+                    &mut open_set,
+                    &mut seen,
+                );
+            }
+            max_open_size = max_open_size.max(open_set.len());
         }
 
-        None // No solution found
+        Err(SolverError::Unsolvable) // Search space exhausted
     }
 
     /// Helper to explore a successor state
     fn explore_successor(
         &self,
-        current_idx: usize,
-        move_pos: Position,
-        node_storage: &mut Vec<SearchNode>,
-        open_set: &mut BinaryHeap<HeapEntry>,
-        closed_set: &HashSet<u64>,
-        best_g_scores: &mut HashMap<u64, u32>,
+        current_id: NodeId,
+        current_state: &PackedState,
+        edge: Edge,
+        nodes: &mut NodeArena<SearchNode>,
+        open_set: &mut Frontier,
+        seen: &mut SeenStates,
     ) {
-        let mut next_state = node_storage[current_idx].state.clone();
-        if !next_state.apply_immediate_move(move_pos) {
+        let mut next_state = current_state.clone();
+        let Some(moves) = self.edge_moves(&next_state, edge) else {
             return;
-        }
-
-        let tentative_g = node_storage[current_idx].g_score + 1;
-        let next_hash = self.state_hash(&next_state);
-
-        // Skip if this state is already in closed set (fully explored)
-        if closed_set.contains(&next_hash) {
+        };
+        if !moves.iter().all(|&pos| next_state.apply_immediate_move(pos)) {
             return;
         }
 
+        let tentative_g = nodes.data(current_id).g_score + moves.len() as u32;
+        let next_hash = seen.key(&next_state);
+
         // Skip if we've found a better path to this state
-        if let Some(&best_g) = best_g_scores.get(&next_hash) {
+        if let Some(&best_g) = seen.best_g_scores.get(&next_hash) {
             if tentative_g >= best_g {
                 return;
             }
         }
 
-        best_g_scores.insert(next_hash, tentative_g);
+        // Skip if this state is already in closed set (fully explored), unless
+        // the config reopens it for the shorter path
+        if seen.closed_set.contains(&next_hash) {
+            if !self.config.reopen_closed {
+                return;
+            }
+            seen.closed_set.remove(&next_hash);
+        }
+
+        seen.best_g_scores.insert(next_hash, tentative_g);
 
-        let h_score = self.heuristic.calculate(&next_state);
+        // Heuristics work on full states; only unpack nodes that survive deduplication
+        let h_score = self.heuristic.calculate(&next_state.to_state());
         let next_node = SearchNode {
-            state: next_state,
             g_score: tentative_g,
             h_score,
-            parent_index: Some(current_idx),
-            move_from_parent: Some(move_pos),
+            move_from_parent: Some(edge),
         };
 
-        let f_score = next_node.f_score();
-        let g_score = next_node.g_score;
-        let next_idx = node_storage.len();
-        node_storage.push(next_node);
-        open_set.push(HeapEntry {
-            f_score,
-            g_score,
-            node_index: next_idx,
-        });
+        let next_id = nodes.push(&next_state, Some(current_id), next_node);
+        open_set.push(&next_node, next_id);
     }
 
     /// Reconstructs the solution path by following parent indices
-    fn reconstruct_path(&self, node_storage: &[SearchNode], goal_idx: usize) -> Vec<Position> {
+    fn reconstruct_path(&self, nodes: &NodeArena<SearchNode>, goal_id: NodeId) -> Vec<Position> {
         let mut path = Vec::new();
-        let mut current_idx = goal_idx;
+        let mut current_id = goal_id;
 
         // Walk backwards from goal to start, collecting moves
-        while let Some(parent_idx) = node_storage[current_idx].parent_index {
-            if let Some(move_pos) = node_storage[current_idx].move_from_parent {
-                path.push(move_pos);
+        while let Some(parent_id) = nodes.parent(current_id) {
+            if let Some(edge) = nodes.data(current_id).move_from_parent {
+                let moves = self
+                    .edge_moves(&nodes.state(parent_id), edge)
+                    .expect("edges were applied when the node was created");
+                path.extend(moves.into_iter().rev());
             }
-            current_idx = parent_idx;
+            current_id = parent_id;
         }
 
         // Reverse to get path from start to goal
@@ -281,35 +856,105 @@ impl AStarSolver {
         path
     }
 
-    /// Creates a hash representation of the puzzle state for deduplication
-    fn state_hash(&self, state: &PuzzleState) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        let size = state.size();
-
-        for row in 0..size {
-            for col in 0..size {
-                if let Some(tile) = state.tile_at((row, col)) {
-                    if let Some(num) = tile.numeric_value() {
-                        num.hash(&mut hasher);
-                    }
-                } else {
-                    // Use a special value for empty cell
-                    u32::MAX.hash(&mut hasher);
-                }
+    /// Returns the immediate moves `edge` makes from `state`
+    /// Returns None for a macro that does not fit from the state's empty cell
+    fn edge_moves(&self, state: &PackedState, edge: Edge) -> Option<Vec<Position>> {
+        match edge {
+            Edge::Move(cell) => {
+                let cell = usize::from(cell);
+                Some(vec![(cell / state.size(), cell % state.size())])
+            }
+            Edge::Pattern(index) => {
+                self.macros[index as usize].positions_from(state.empty_position(), state.size())
             }
         }
+    }
 
-        hasher.finish()
+    /// Creates a hash representation of the puzzle state for deduplication
+    #[cfg(test)]
+    fn state_hash(&self, state: &PuzzleState) -> u64 {
+        state_hash(&PackedState::from_state(state))
     }
 }
 
+/// Creates a hash representation of a packed state for deduplication
+pub(super) fn state_hash(state: &PackedState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl Default for AStarSolver {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// Calculator for actual solution length using A* solver
+/// Options for an [`AStarSolver`], created by [`AStarSolver::builder`]
+///
+/// The solver stays optimal as long as the heuristic never overestimates the
+/// remaining moves. Macro moves from a pattern catalog cost their full length,
+/// so they never make a solution longer; they let the search jump several
+/// moves at once, at the price of more successors per node.
+pub struct AStarSolverBuilder {
+    heuristic: Option<Box<dyn EntropyCalculator + Send + Sync>>,
+    config: SolverConfig,
+    patterns: Option<RelativePatternCatalog>,
+}
+
+impl AStarSolverBuilder {
+    /// Estimates remaining moves with `heuristic` instead of `EnhancedHeuristic`
+    pub fn heuristic(mut self, heuristic: Box<dyn EntropyCalculator + Send + Sync>) -> Self {
+        self.heuristic = Some(heuristic);
+        self
+    }
+
+    /// Replaces all search limits
+    pub fn config(mut self, config: SolverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Limits the number of node expansions
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.config.max_iterations = max_iterations;
+        self
+    }
+
+    /// Limits the wall-clock time of a search
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Also expands every orientation of the catalog's techniques from each node
+    pub fn patterns(mut self, catalog: RelativePatternCatalog) -> Self {
+        self.patterns = Some(catalog);
+        self
+    }
+
+    pub fn build(self) -> AStarSolver {
+        let macros = self
+            .patterns
+            .map(|catalog| {
+                catalog
+                    .patterns()
+                    .iter()
+                    .flat_map(RelativePattern::orientations)
+                    .collect()
+            })
+            .unwrap_or_default();
+        AStarSolver {
+            heuristic: self.heuristic.unwrap_or_else(|| Box::new(EnhancedHeuristic)),
+            config: self.config,
+            macros,
+        }
+    }
+}
+
+/// Calculator for solution length using A* solver
+///
+/// The length is that of the path A* finds, which can exceed the shortest one.
 pub struct ActualSolutionLength {
     solver: AStarSolver,
 }
@@ -334,9 +979,35 @@ impl EntropyCalculator for ActualSolutionLength {
     }
 }
 
+/// Deterministic no-backtracking scramble driven by a small LCG (shared by solver tests)
+#[cfg(test)]
+pub(crate) fn scramble(size: usize, steps: usize, seed: u64) -> PuzzleState {
+    scramble_from(PuzzleState::new(size).unwrap(), steps, seed)
+}
+
+/// Scrambles `puzzle` as `scramble` does, e.g. starting from another goal
+#[cfg(test)]
+pub(crate) fn scramble_from(mut puzzle: PuzzleState, steps: usize, mut seed: u64) -> PuzzleState {
+    let mut previous_empty = None;
+    for _ in 0..steps {
+        let empty = puzzle.empty_position();
+        let moves: Vec<Position> = puzzle
+            .legal_moves()
+            .into_iter()
+            .filter(|&pos| Some(pos) != previous_empty)
+            .collect();
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let choice = moves[(seed >> 33) as usize % moves.len()];
+        puzzle.apply_immediate_move(choice);
+        previous_empty = Some(empty);
+    }
+    puzzle
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::ManhattanDistance;
 
     fn create_simple_puzzle() -> PuzzleState {
         let mut puzzle = PuzzleState::new(3).unwrap();
@@ -345,6 +1016,27 @@ mod tests {
         puzzle
     }
 
+    #[test]
+    fn test_solver_kind_from_name() {
+        assert_eq!(SolverKind::from_name("IDA*"), Some(SolverKind::IdaStar));
+        assert_eq!(SolverKind::from_name(" strategic"), Some(SolverKind::Strategic));
+        assert_eq!(SolverKind::from_name("dijkstra"), None);
+    }
+
+    #[test]
+    fn test_ida_star_thread_count_keeps_the_length() {
+        let puzzle = scramble(4, 40, 3);
+        let length = |threads| {
+            let config = SolverConfig { threads, ..SolverConfig::default() };
+            SolverKind::IdaStar
+                .try_solve_instrumented(config, &puzzle, None, &mut |_| {})
+                .unwrap()
+                .len()
+        };
+        assert_eq!(length(Some(1)), length(None));
+        assert_eq!(length(Some(2)), length(None));
+    }
+
     #[test]
     fn test_solver_solved_puzzle() {
         let puzzle = PuzzleState::new(3).unwrap();
@@ -418,6 +1110,94 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[test]
+    fn test_solver_reports_progress() {
+        let puzzle = scramble(4, 40, 7);
+
+        let solver = AStarSolver::new();
+        let mut reports = Vec::new();
+        let path = solver.solve_with_path_reporting(&puzzle, None, &mut |p| reports.push(p));
+
+        assert!(path.is_some());
+        assert!(!reports.is_empty());
+        // Node counts must be monotonically increasing across reports
+        for pair in reports.windows(2) {
+            assert!(pair[1].nodes_explored > pair[0].nodes_explored);
+            assert!(pair[1].elapsed >= pair[0].elapsed);
+        }
+        assert!(reports.iter().all(|p| p.frontier_size > 0 && p.expanded.is_some()));
+    }
+
+    #[test]
+    fn test_solver_respects_timeout() {
+        let puzzle = scramble(4, 40, 7);
+        let config = SolverConfig {
+            timeout: Duration::ZERO,
+            ..SolverConfig::default()
+        };
+        let solver = AStarSolver::with_config(config);
+        assert_eq!(solver.solve_with_path(&puzzle), None);
+    }
+
+    #[test]
+    fn test_solver_respects_iteration_limit() {
+        let puzzle = scramble(4, 40, 7);
+        let config = SolverConfig {
+            max_iterations: 10,
+            ..SolverConfig::default()
+        };
+        let solver = AStarSolver::with_config(config);
+        assert_eq!(solver.max_iterations(), 10);
+        assert_eq!(solver.solve_with_path(&puzzle), None);
+    }
+
+    #[test]
+    fn test_solver_cancellation() {
+        let puzzle = scramble(4, 40, 7);
+        let solver = AStarSolver::new();
+        let cancel = Arc::new(AtomicBool::new(true));
+        assert_eq!(solver.solve_with_path_cancellable(&puzzle, Some(cancel)), None);
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let puzzle = scramble(4, 40, 7);
+        let solution = AStarSolver::new()
+            .try_solve_instrumented(&puzzle, None, &mut |_| {})
+            .unwrap();
+        assert!(solution.peak_memory_bytes > 0);
+
+        let config = SolverConfig {
+            max_memory_bytes: solution.peak_memory_bytes / 2,
+            ..SolverConfig::default()
+        };
+        let result = AStarSolver::with_config(config).try_solve_instrumented(&puzzle, None, &mut |_| {});
+        assert_eq!(
+            result,
+            Err(SolverError::MemoryExceeded {
+                limit_bytes: config.max_memory_bytes
+            })
+        );
+
+        // The other limits are told apart too
+        let cancel = Arc::new(AtomicBool::new(true));
+        let result = AStarSolver::with_config(SolverConfig {
+            max_iterations: PROGRESS_INTERVAL * 2,
+            ..SolverConfig::default()
+        })
+        .try_solve_instrumented(&scramble(4, 200, 3), Some(cancel), &mut |_| {});
+        assert_eq!(result, Err(SolverError::Cancelled));
+    }
+
+    #[test]
+    fn test_progress_not_reported_for_solved_puzzle() {
+        let puzzle = PuzzleState::new(3).unwrap();
+        let solver = AStarSolver::new();
+        let mut calls = 0;
+        solver.solve_with_path_reporting(&puzzle, None, &mut |_| calls += 1);
+        assert_eq!(calls, 0);
+    }
+
     #[test]
     fn test_actual_solution_length_calculator() {
         let puzzle = create_simple_puzzle();
@@ -425,4 +1205,140 @@ mod tests {
         let length = calculator.calculate(&puzzle);
         assert_eq!(length, 1);
     }
+
+    #[test]
+    fn test_instrumented_solution_statistics() {
+        let puzzle = scramble(4, 30, 11);
+        for kind in SolverKind::ALL {
+            let solution = kind
+                .solve_instrumented(SolverConfig::default(), &puzzle, None, &mut |_| {})
+                .unwrap();
+            assert_eq!(
+                Some(&solution.path),
+                kind.solve_with_path_reporting(SolverConfig::default(), &puzzle, None, &mut |_| {})
+                    .as_ref(),
+                "{}",
+                kind
+            );
+            assert!(solution.nodes_expanded > 0, "{}", kind);
+            assert!(solution.nodes_generated >= solution.nodes_expanded, "{}", kind);
+            assert!(solution.max_open_size > 0, "{}", kind);
+        }
+
+        let solved = AStarSolver::new()
+            .solve_instrumented(&PuzzleState::new(4).unwrap(), None, &mut |_| {})
+            .unwrap();
+        assert!(solved.is_empty());
+        assert_eq!(solved.nodes_expanded, 0);
+    }
+
+    #[test]
+    fn test_solve_approx_is_bounded() {
+        let puzzle = scramble(3, 40, 8);
+        let solver = AStarSolver::builder()
+            .heuristic(Box::new(ManhattanDistance))
+            .build();
+        let optimal = solver.solve(&puzzle).unwrap();
+        assert_eq!(solver.solve_approx(&puzzle, 1.0), Some(optimal));
+        assert_eq!(solver.solve_approx(&puzzle, 0.5), Some(optimal));
+
+        let approx = solver
+            .solve_approx_instrumented(&puzzle, 2.0, None, &mut |_| {})
+            .unwrap();
+        assert!(approx.len() as u32 >= optimal && approx.len() as u32 <= 2 * optimal);
+        let mut replay = puzzle.clone();
+        assert!(approx.path.iter().all(|&pos| replay.apply_immediate_move(pos)));
+        assert!(replay.is_solved());
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let puzzle = scramble(3, 24, 5);
+        let manhattan = AStarSolver::builder()
+            .heuristic(Box::new(ManhattanDistance))
+            .max_iterations(500_000)
+            .timeout(Duration::from_secs(30))
+            .build();
+        assert_eq!(manhattan.max_iterations(), 500_000);
+        // Manhattan is admissible, so this is the optimal length
+        let optimal = manhattan.solve(&puzzle).unwrap();
+        assert!(optimal <= AStarSolver::new().solve(&puzzle).unwrap());
+
+        // Macro moves keep solutions optimal, and their paths still replay move by move
+        let with_macros = AStarSolver::builder()
+            .heuristic(Box::new(ManhattanDistance))
+            .patterns(RelativePatternCatalog::builtin())
+            .build();
+        let path = with_macros.solve_with_path(&puzzle).unwrap();
+        assert_eq!(path.len() as u32, optimal);
+        let mut replay = puzzle.clone();
+        assert!(path.iter().all(|&pos| replay.apply_immediate_move(pos)));
+        assert!(replay.is_solved());
+
+        let limited = AStarSolver::builder().max_iterations(1).build();
+        assert_eq!(limited.solve(&puzzle), None);
+    }
+
+    #[test]
+    fn test_symmetry_keeps_solutions_optimal() {
+        let solver = |symmetry| {
+            AStarSolver::builder()
+                .heuristic(Box::new(ManhattanDistance))
+                .config(SolverConfig {
+                    symmetry,
+                    ..SolverConfig::default()
+                })
+                .build()
+        };
+        let (plain, mirrored) = (solver(false), solver(true));
+        let (mut plain_nodes, mut mirrored_nodes) = (0, 0);
+        for seed in 0..6 {
+            let puzzle = scramble(4, 40, seed);
+            let reference = plain.solve_instrumented(&puzzle, None, &mut |_| {}).unwrap();
+            let solution = mirrored.solve_instrumented(&puzzle, None, &mut |_| {}).unwrap();
+            // Manhattan distance is admissible, so the plain search is optimal
+            assert_eq!(solution.len(), reference.len());
+            let mut replay = puzzle.clone();
+            assert!(solution.path.iter().all(|&pos| replay.apply_immediate_move(pos)));
+            assert!(replay.is_solved());
+            plain_nodes += reference.nodes_expanded;
+            mirrored_nodes += solution.nodes_expanded;
+        }
+        assert!(mirrored_nodes < plain_nodes, "{} vs {}", mirrored_nodes, plain_nodes);
+    }
+
+    #[test]
+    fn test_policy_options() {
+        let puzzle = scramble(4, 40, 11);
+        let solve = |config: SolverConfig| {
+            AStarSolver::builder()
+                .heuristic(Box::new(ManhattanDistance))
+                .config(config)
+                .build()
+                .solve_instrumented(&puzzle, None, &mut |_| {})
+                .unwrap()
+        };
+        let optimal = solve(SolverConfig::default()).len();
+        for tie_break in TieBreak::ALL {
+            let solution = solve(SolverConfig {
+                tie_break,
+                ..SolverConfig::default()
+            });
+            assert_eq!(solution.len(), optimal, "{}", tie_break);
+            assert_eq!(TieBreak::from_name(&tie_break.to_string().to_uppercase()), Some(tie_break));
+        }
+        assert_eq!(TieBreak::from_name("fifo"), None);
+
+        for reopen_closed in [false, true] {
+            let weighted = solve(SolverConfig {
+                weight: 2.0,
+                reopen_closed,
+                ..SolverConfig::default()
+            });
+            assert!(weighted.len() <= 2 * optimal);
+            let mut replay = puzzle.clone();
+            assert!(weighted.path.iter().all(|&pos| replay.apply_immediate_move(pos)));
+            assert!(replay.is_solved());
+        }
+    }
 }
@@ -0,0 +1,821 @@
+//! # Strategic Solver
+//!
+//! A non-optimal, human-style solver that works on every supported grid size.
+//! It solves the top row, then the left column, and recurses on the remaining
+//! (n-1)×(n-1) sub-grid until only a 3×3 block is left, which is finished in
+//! the fewest moves by following the [`EndgameTable`]. Rows and columns that are
+//! already solved are skipped, so boards scrambled only in a corner region stay
+//! solved outside it; when that leaves a region only two cells tall or wide, the
+//! reduction goes on to a 2×2 block, finished by cycling the empty cell around it.
+//!
+//! ## Placing Tiles
+//!
+//! Solved tiles are locked and never disturbed again. Each tile is routed to its
+//! target by a breadth-first search over (tile position, empty position) pairs,
+//! so it always takes the fewest moves possible without touching locked cells.
+//!
+//! The last two tiles of a row (or column) cannot be placed one after the other.
+//! They are parked in an L shape beside their targets and swung in together
+//! (if the second tile is trapped where the first belongs, it is evicted first):
+//!
+//! ```text
+//!  . . a      . a b
+//!  . . b  ->  . . .
+//! ```
+//!
+//! ## Annotations
+//!
+//! Every solution comes with [`Annotation`]s saying what each stretch of moves
+//! achieves, in the terms a person would use ("Place tile 5", "Finish row 2 with
+//! tiles 7 and 8"), so an auto-solve can be followed as a lesson.
+//!
+//! ## Performance Characteristics
+//!
+//! - **Moves**: Typically a few times the optimal length; optimal on 3×3
+//! - **Time**: Polynomial in grid size; a 15×15 board solves in milliseconds
+//! - **Goals**: The empty cell must belong in the top-left or bottom-right corner
+//!   (the standard, blank-first and reversed goals); spiral goals are not supported
+//!
+//! ## Example Usage
+//!
+//! ```rust
+//! use slider::model::{PuzzleState, StrategicSolver};
+//!
+//! let mut puzzle: PuzzleState = "8 7 6 / 5 4 3 / 2 1 _".parse()?;
+//! let path = StrategicSolver::new().solve_with_path(&puzzle).unwrap();
+//!
+//! for pos in path {
+//!     puzzle.apply_immediate_move(pos);
+//! }
+//! assert!(puzzle.is_solved());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`EndgameTable`]: super::endgame::EndgameTable
+
+use super::endgame::{EndgameTable, ENDGAME_SIZE};
+use super::error::SolverError;
+use super::move_validator::Position;
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use super::solver::{Solution, SolverConfig, SolverProgress};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Times to retry parking a row/column end pair before giving up
+const PAIR_ATTEMPTS: usize = 4;
+
+/// One stage of a strategic solve, described as a person would
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveStep {
+    /// Route one tile home and leave it there
+    PlaceTile { tile: String },
+    /// Park the last two tiles of a row beside their cells and swing them in together
+    FinishRow { row: usize, tiles: [String; 2] },
+    /// The same for the last two tiles of a column
+    FinishColumn { column: usize, tiles: [String; 2] },
+    /// Cycle the last three tiles around the final 2×2 block
+    CycleBlock { tiles: Vec<String> },
+    /// Finish the last 3×3 block optimally, from the endgame table
+    SolveCorner { tiles: Vec<String> },
+}
+
+impl fmt::Display for SolveStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveStep::PlaceTile { tile } => write!(f, "Place tile {}", tile),
+            SolveStep::FinishRow { row, tiles: [a, b] } => {
+                write!(f, "Finish row {} by swinging tiles {} and {} in together", row + 1, a, b)
+            }
+            SolveStep::FinishColumn { column, tiles: [a, b] } => {
+                write!(f, "Finish column {} by swinging tiles {} and {} in together", column + 1, a, b)
+            }
+            SolveStep::CycleBlock { tiles } => {
+                write!(f, "Cycle tiles {} around the last 2×2 block", tiles.join(", "))
+            }
+            SolveStep::SolveCorner { tiles } => {
+                write!(f, "Sort tiles {} in the last 3×3 block, in the fewest moves", tiles.join(", "))
+            }
+        }
+    }
+}
+
+/// A step of a strategic solution and the first move that plays it
+///
+/// The step runs until the next annotation's `first_move`, or to the end of the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub first_move: usize,
+    pub step: SolveStep,
+}
+
+/// Returns the step being played at move `index` of an annotated path
+pub fn step_at(annotations: &[Annotation], index: usize) -> Option<&SolveStep> {
+    annotations
+        .iter()
+        .take_while(|annotation| annotation.first_move <= index)
+        .last()
+        .map(|annotation| &annotation.step)
+}
+
+impl Annotation {
+    /// Renumbers the rows and columns of a step found on the board turned upside down
+    fn turned(self, size: usize) -> Self {
+        let step = match self.step {
+            SolveStep::FinishRow { row, tiles } => SolveStep::FinishRow { row: size - 1 - row, tiles },
+            SolveStep::FinishColumn { column, tiles } => SolveStep::FinishColumn {
+                column: size - 1 - column,
+                tiles,
+            },
+            step => step,
+        };
+        Self { step, ..self }
+    }
+}
+
+/// Renumbers `annotations` for the path left once its first `moves` moves are played
+pub fn skip_annotated_moves(annotations: &mut Vec<Annotation>, moves: usize) {
+    let current = annotations.iter().take_while(|a| a.first_move <= moves).count();
+    annotations.drain(..current.saturating_sub(1));
+    for annotation in annotations.iter_mut() {
+        annotation.first_move = annotation.first_move.saturating_sub(moves);
+    }
+}
+
+/// Cells involved in placing the last two tiles of a row or column
+struct PairCells {
+    first: Position,        // Home of the first tile (becomes a pocket once `last` is locked)
+    last: Position,         // Home of the second tile, i.e. the corner
+    below_corner: Position, // Where the second tile waits before the swing
+    escape: Position,       // Where the second tile is evicted to if trapped by the pocket
+}
+
+/// Why a solve stopped early
+enum Abort {
+    Cancelled,
+    TimedOut,
+    Unreachable, // A target could not be reached (only for unsolvable states)
+}
+
+/// Working state for one solve: the board, locked cells, and moves made so far
+struct Work<'a> {
+    state: PuzzleState,
+    size: usize,
+    locked: Vec<bool>, // Row-major, true = cell holds a finished tile
+    path: Vec<Position>,
+    annotations: Vec<Annotation>,
+    config: SolverConfig,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    on_progress: &'a mut dyn FnMut(SolverProgress),
+    started: Instant,
+    tiles_placed: usize,
+    nodes_expanded: usize, // Totals over every breadth-first route search
+    nodes_generated: usize,
+    max_open_size: usize,
+}
+
+impl Work<'_> {
+    fn index(&self, pos: Position) -> usize {
+        pos.0 * self.size + pos.1
+    }
+
+    fn position(&self, index: usize) -> Position {
+        (index / self.size, index % self.size)
+    }
+
+    fn lock(&mut self, pos: Position, locked: bool) {
+        let index = self.index(pos);
+        self.locked[index] = locked;
+    }
+
+    fn is_free(&self, pos: Position) -> bool {
+        !self.locked[self.index(pos)]
+    }
+
+    /// Orthogonal neighbors of a cell that lie inside the grid
+    fn neighbors(&self, pos: Position) -> impl Iterator<Item = Position> {
+        let (row, col) = pos;
+        let size = self.size;
+        [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ]
+        .into_iter()
+        .filter(move |&(r, c)| r < size && c < size)
+    }
+
+    /// Returns the label of the tile whose home is `home`
+    fn label(&self, home: Position) -> String {
+        self.state
+            .find_tile_position(home)
+            .and_then(|pos| self.state.tile_at(pos))
+            .and_then(|tile| tile.content.label())
+            .unwrap_or_default()
+    }
+
+    /// Starts a new step at the next move, replacing the last step if it made no moves
+    fn annotate(&mut self, step: SolveStep) {
+        let first_move = self.path.len();
+        if self.annotations.last().is_some_and(|last| last.first_move == first_move) {
+            self.annotations.pop();
+        }
+        self.annotations.push(Annotation { first_move, step });
+    }
+
+    /// Drops a trailing step that made no moves
+    fn close_annotations(&mut self) {
+        if self.annotations.last().is_some_and(|last| last.first_move == self.path.len()) {
+            self.annotations.pop();
+        }
+    }
+
+    /// Slides the tile at `pos` into the empty cell and records the move
+    fn push_move(&mut self, pos: Position) {
+        let moved = self.state.apply_immediate_move(pos);
+        debug_assert!(moved, "strategic solver made an illegal move");
+        self.path.push(pos);
+    }
+
+    /// Checks the cancel flag and timeout, then reports progress
+    fn checkpoint(&mut self) -> Result<(), Abort> {
+        if let Some(ref cancel) = self.cancel_flag {
+            if cancel.load(AtomicOrdering::Relaxed) {
+                return Err(Abort::Cancelled);
+            }
+        }
+        if self.started.elapsed() >= self.config.timeout {
+            return Err(Abort::TimedOut);
+        }
+        (self.on_progress)(SolverProgress {
+            nodes_explored: self.tiles_placed,
+            current_depth: self.path.len() as u32,
+            elapsed: self.started.elapsed(),
+            frontier_size: 0,
+            expanded: Some(PackedState::from_state(&self.state)),
+        });
+        Ok(())
+    }
+
+    /// Moves the empty cell to `target` along free cells, never passing through `avoid`
+    fn move_empty_to(&mut self, target: Position, avoid: Option<Position>) -> Result<(), Abort> {
+        let cells = self.size * self.size;
+        let start = self.index(self.state.empty_position());
+        let goal = self.index(target);
+        let mut parent: Vec<Option<usize>> = vec![None; cells];
+        let mut seen = vec![false; cells];
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+
+        while let Some(current) = queue.pop_front() {
+            if current == goal {
+                break;
+            }
+            self.nodes_expanded += 1;
+            for next in self.neighbors(self.position(current)) {
+                let next_idx = self.index(next);
+                if seen[next_idx] || !self.is_free(next) || Some(next) == avoid {
+                    continue;
+                }
+                seen[next_idx] = true;
+                parent[next_idx] = Some(current);
+                queue.push_back(next_idx);
+                self.nodes_generated += 1;
+            }
+            self.max_open_size = self.max_open_size.max(queue.len());
+        }
+
+        if !seen[goal] {
+            return Err(Abort::Unreachable);
+        }
+
+        // Each cell on the route holds the tile that slides into the empty cell
+        let mut route = Vec::new();
+        let mut current = goal;
+        while let Some(prev) = parent[current] {
+            route.push(self.position(current));
+            current = prev;
+        }
+        for pos in route.into_iter().rev() {
+            self.push_move(pos);
+        }
+        Ok(())
+    }
+
+    /// Moves the tile whose home is `home` to `target` using only free cells
+    ///
+    /// Searches (tile, empty) pairs breadth-first, so the tile takes the fewest
+    /// moves possible and no separate "bring the empty cell around" logic is needed.
+    fn move_tile_to(&mut self, home: Position, target: Position) -> Result<(), Abort> {
+        let tile_pos = self
+            .state
+            .find_tile_position(home)
+            .ok_or(Abort::Unreachable)?;
+        if tile_pos == target {
+            return Ok(());
+        }
+
+        let cells = self.size * self.size;
+        let encode = |tile: usize, empty: usize| tile * cells + empty;
+        let start = encode(self.index(tile_pos), self.index(self.state.empty_position()));
+        let goal_tile = self.index(target);
+
+        // parent[state] = (previous state, cell of the tile that moved)
+        let mut parent: Vec<Option<(usize, Position)>> = vec![None; cells * cells];
+        let mut seen = vec![false; cells * cells];
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+        let mut found = None;
+
+        while let Some(current) = queue.pop_front() {
+            let (tile, empty) = (current / cells, current % cells);
+            if tile == goal_tile {
+                found = Some(current);
+                break;
+            }
+            self.nodes_expanded += 1;
+            for next in self.neighbors(self.position(empty)) {
+                if !self.is_free(next) {
+                    continue;
+                }
+                let next_idx = self.index(next);
+                // The neighbor slides into the empty cell; if it is our tile, the tile moves
+                let next_tile = if next_idx == tile { empty } else { tile };
+                let next_state = encode(next_tile, next_idx);
+                if seen[next_state] {
+                    continue;
+                }
+                seen[next_state] = true;
+                parent[next_state] = Some((current, next));
+                queue.push_back(next_state);
+                self.nodes_generated += 1;
+            }
+            self.max_open_size = self.max_open_size.max(queue.len());
+        }
+
+        let Some(mut current) = found else {
+            return Err(Abort::Unreachable);
+        };
+        let mut moves = Vec::new();
+        while let Some((prev, pos)) = parent[current] {
+            moves.push(pos);
+            current = prev;
+        }
+        for pos in moves.into_iter().rev() {
+            self.push_move(pos);
+        }
+        Ok(())
+    }
+
+    /// Places a single tile at its home and locks it
+    fn place(&mut self, home: Position) -> Result<(), Abort> {
+        self.annotate(SolveStep::PlaceTile { tile: self.label(home) });
+        self.move_tile_to(home, home)?;
+        self.lock(home, true);
+        self.tiles_placed += 1;
+        self.checkpoint()
+    }
+
+    /// Places the last two tiles of `row` (columns n-2 and n-1)
+    fn place_row_end(&mut self, row: usize) -> Result<(), Abort> {
+        let n = self.size;
+        let tiles = [self.label((row, n - 2)), self.label((row, n - 1))];
+        self.annotate(SolveStep::FinishRow { row, tiles });
+        self.place_pair(PairCells {
+            first: (row, n - 2),
+            last: (row, n - 1),
+            below_corner: (row + 1, n - 1),
+            escape: (row + 2, n - 2),
+        })
+    }
+
+    /// Places the last two tiles of `col` (rows n-2 and n-1)
+    fn place_column_end(&mut self, col: usize) -> Result<(), Abort> {
+        let n = self.size;
+        let tiles = [self.label((n - 2, col)), self.label((n - 1, col))];
+        self.annotate(SolveStep::FinishColumn { column: col, tiles });
+        self.place_pair(PairCells {
+            first: (n - 2, col),
+            last: (n - 1, col),
+            below_corner: (n - 1, col + 1),
+            escape: (n - 2, col + 2),
+        })
+    }
+
+    /// Places two tiles that end a row or column using the L-shaped swing
+    fn place_pair(&mut self, cells: PairCells) -> Result<(), Abort> {
+        let PairCells { first, last, below_corner, escape } = cells;
+        if self.is_home(first) && self.is_home(last) {
+            self.lock(first, true);
+            self.lock(last, true);
+            return Ok(());
+        }
+
+        // Park `first` in the corner (last's home) and `last` next to it.
+        // With the corner locked, first's home becomes a dead-end pocket; if `last`
+        // is caught in or at the mouth of that pocket it cannot be routed, so it is
+        // evicted with the corner unlocked and parking starts over.
+        let mut parked = false;
+        for _ in 0..PAIR_ATTEMPTS {
+            self.move_tile_to(first, last)?;
+            self.lock(last, true);
+            match self.move_tile_to(last, below_corner) {
+                Ok(()) => {
+                    parked = true;
+                    break;
+                }
+                Err(Abort::Unreachable) => {
+                    self.lock(last, false);
+                    self.move_tile_to(last, escape)?;
+                }
+                Err(abort) => return Err(abort),
+            }
+        }
+        if !parked {
+            return Err(Abort::Unreachable);
+        }
+
+        // Bring the empty cell into the pocket, then swing both tiles in
+        self.lock(below_corner, true);
+        self.move_empty_to(first, None)?;
+        self.lock(last, false);
+        self.lock(below_corner, false);
+        self.push_move(last);
+        self.push_move(below_corner);
+
+        self.lock(first, true);
+        self.lock(last, true);
+        self.tiles_placed += 2;
+        self.checkpoint()
+    }
+
+    /// Finishes the bottom-right 3×3 block (from row and column `top`) along
+    /// the endgame table's optimal path
+    fn finish_corner(&mut self, top: usize) -> Result<(), Abort> {
+        let path = EndgameTable::shared().solve(&self.state).ok_or(Abort::Unreachable)?;
+        let tiles = (top..self.size)
+            .flat_map(|row| (top..self.size).map(move |col| (row, col)))
+            .filter(|&home| home != (self.size - 1, self.size - 1) && !self.is_home(home))
+            .map(|home| self.label(home))
+            .collect();
+        self.annotate(SolveStep::SolveCorner { tiles });
+        for pos in path {
+            self.push_move(pos);
+        }
+        self.tiles_placed += ENDGAME_SIZE * ENDGAME_SIZE - 1;
+        self.close_annotations();
+        self.checkpoint()
+    }
+
+    /// Finishes the bottom-right 2×2 block by cycling the empty cell around it
+    fn finish_block(&mut self) -> Result<(), Abort> {
+        let n = self.size;
+        let cycle = [(n - 2, n - 2), (n - 2, n - 1), (n - 1, n - 1), (n - 1, n - 2)];
+
+        // Twelve moves visit every arrangement of three tiles in the block
+        for _ in 0..12 {
+            if self.state.is_solved() {
+                return Ok(());
+            }
+            let empty = self.state.empty_position();
+            let i = cycle.iter().position(|&c| c == empty).ok_or(Abort::Unreachable)?;
+            self.push_move(cycle[(i + 1) % 4]);
+        }
+
+        if self.state.is_solved() {
+            Ok(())
+        } else {
+            Err(Abort::Unreachable)
+        }
+    }
+
+    fn is_home(&self, home: Position) -> bool {
+        self.state.find_tile_position(home) == Some(home)
+    }
+
+    /// Locks `cells` if every one already holds its own tile
+    fn lock_if_home(&mut self, cells: &[Position]) -> bool {
+        if !cells.iter().all(|&pos| self.is_home(pos)) {
+            return false;
+        }
+        for &pos in cells {
+            self.lock(pos, true);
+        }
+        self.tiles_placed += cells.len();
+        true
+    }
+
+    /// Runs the full row/column reduction
+    ///
+    /// Works on the unsolved rectangle from (`top`, `left`) to the corner, taking
+    /// its top row while it is at least as tall as it is wide and its left column
+    /// otherwise. Outer rows and columns that are already solved are locked
+    /// without moves, so a board scrambled only near the corner is solved there.
+    fn solve(&mut self) -> Result<(), Abort> {
+        let n = self.size;
+        let (mut top, mut left) = (0, 0);
+        while n - top > 2 || n - left > 2 {
+            if (n - top, n - left) == (ENDGAME_SIZE, ENDGAME_SIZE) {
+                return self.finish_corner(top);
+            }
+            let row: Vec<Position> = (left..n).map(|col| (top, col)).collect();
+            let column: Vec<Position> = (top..n).map(|row| (row, left)).collect();
+            if n - top > 2 && self.lock_if_home(&row) {
+                top += 1;
+            } else if n - left > 2 && self.lock_if_home(&column) {
+                left += 1;
+            } else if n - top > 2 && n - top >= n - left {
+                for col in left..n - 2 {
+                    self.place((top, col))?;
+                }
+                self.place_row_end(top)?;
+                top += 1;
+            } else {
+                for row in top..n - 2 {
+                    self.place((row, left))?;
+                }
+                self.place_column_end(left)?;
+                left += 1;
+            }
+        }
+
+        // Park the empty cell inside the remaining block first
+        if !self.state.is_solved() {
+            let tiles = [(n - 2, n - 2), (n - 2, n - 1), (n - 1, n - 2)].map(|home| self.label(home));
+            self.annotate(SolveStep::CycleBlock { tiles: tiles.to_vec() });
+            self.move_empty_to((n - 1, n - 1), None)?;
+        }
+        self.finish_block()?;
+        self.close_annotations();
+        Ok(())
+    }
+}
+
+/// Human-style solver that reduces the grid row by row (non-optimal)
+pub struct StrategicSolver {
+    config: SolverConfig,
+}
+
+impl StrategicSolver {
+    pub fn new() -> Self {
+        Self::with_config(SolverConfig::default())
+    }
+
+    /// Creates a solver with custom search limits
+    /// Only the timeout applies; the move count is bounded by the grid size
+    pub fn with_config(config: SolverConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the search limits used by this solver
+    pub fn config(&self) -> SolverConfig {
+        self.config
+    }
+
+    /// Returns a (not necessarily shortest) solution path
+    /// Returns None if unsolvable or timeout
+    pub fn solve_with_path(&self, initial_state: &PuzzleState) -> Option<Vec<Position>> {
+        self.solve_with_path_cancellable(initial_state, None)
+    }
+
+    /// Returns a solution path with support for cancellation
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_cancellable(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+    ) -> Option<Vec<Position>> {
+        self.solve_with_path_reporting(initial_state, cancel_flag, &mut |_| {})
+    }
+
+    /// Returns a solution path, invoking `on_progress` after each placed tile;
+    /// `nodes_explored` counts placed tiles and `current_depth` the moves so far
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_with_path_reporting(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Vec<Position>> {
+        self.solve_instrumented(initial_state, cancel_flag, on_progress)
+            .map(|solution| solution.path)
+    }
+
+    /// Returns a solution with timing and node counts summed over the short
+    /// route searches used to place each tile
+    /// Returns None if unsolvable, timeout, or cancelled
+    pub fn solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Option<Solution> {
+        self.try_solve_instrumented(initial_state, cancel_flag, on_progress)
+            .ok()
+    }
+
+    /// Returns a solution like `solve_instrumented`
+    ///
+    /// # Errors
+    ///
+    /// Returns the `SolverError` saying why the search stopped: the time
+    /// limit, cancellation, an unsolvable state, or a goal this solver cannot
+    /// finish
+    pub fn try_solve_instrumented(
+        &self,
+        initial_state: &PuzzleState,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: &mut dyn FnMut(SolverProgress),
+    ) -> Result<Solution, SolverError> {
+        if initial_state.is_solved() {
+            return Ok(Solution {
+                start_empty: initial_state.empty_position(),
+                ..Solution::default()
+            });
+        }
+        if !initial_state.is_solvable() {
+            return Err(SolverError::Unsolvable);
+        }
+
+        // The reduction finishes in the bottom-right corner, so a goal with the
+        // empty cell top-left is solved upside down and the path turned back
+        let size = initial_state.size();
+        let turn = |(row, col): Position| (size - 1 - row, size - 1 - col);
+        let (state, turned) = match initial_state.goal_empty_position() {
+            pos if pos == (size - 1, size - 1) => (initial_state.clone(), false),
+            (0, 0) => (initial_state.rotated_half_turn(), true),
+            pos => {
+                return Err(SolverError::InvalidState(format!(
+                    "strategic solver cannot finish with the empty cell at {:?}",
+                    pos
+                )));
+            }
+        };
+
+        let mut work = Work {
+            state,
+            size,
+            locked: vec![false; size * size],
+            path: Vec::new(),
+            annotations: Vec::new(),
+            config: self.config,
+            cancel_flag,
+            on_progress,
+            started: Instant::now(),
+            tiles_placed: 0,
+            nodes_expanded: 0,
+            nodes_generated: 0,
+            max_open_size: 0,
+        };
+
+        match work.solve() {
+            Ok(()) => Ok(Solution {
+                path: if turned {
+                    work.path.into_iter().map(turn).collect()
+                } else {
+                    work.path
+                },
+                // Rows and columns were counted on the turned board
+                annotations: if turned {
+                    work.annotations.into_iter().map(|a| a.turned(size)).collect()
+                } else {
+                    work.annotations
+                },
+                nodes_expanded: work.nodes_expanded,
+                nodes_generated: work.nodes_generated,
+                nodes_pruned: 0,
+                max_open_size: work.max_open_size,
+                duration: work.started.elapsed(),
+                peak_memory_bytes: 0,
+                start_empty: initial_state.empty_position(),
+            }),
+            Err(Abort::Cancelled) => Err(SolverError::Cancelled),
+            Err(Abort::TimedOut) => Err(SolverError::TimeLimitExceeded {
+                timeout: self.config.timeout,
+            }),
+            Err(Abort::Unreachable) => Err(SolverError::Unsolvable),
+        }
+    }
+}
+
+impl Default for StrategicSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::GoalSpec;
+
+    fn assert_solves(puzzle: &PuzzleState) -> usize {
+        let path = StrategicSolver::new().solve_with_path(puzzle).unwrap();
+        let mut state = puzzle.clone();
+        for &pos in &path {
+            assert!(state.apply_immediate_move(pos));
+        }
+        assert!(state.is_solved(), "{}", puzzle.to_notation());
+        path.len()
+    }
+
+    #[test]
+    fn test_solved_puzzle() {
+        let puzzle = PuzzleState::new(5).unwrap();
+        assert_eq!(StrategicSolver::new().solve_with_path(&puzzle), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_solves_small_grids() {
+        for size in 3..=5 {
+            for seed in 0..20 {
+                assert_solves(&scramble(size, 200, seed));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solves_large_grids() {
+        for size in [8, 12, 15, 22] {
+            assert_solves(&scramble(size, 2000, size as u64));
+        }
+    }
+
+    #[test]
+    fn test_row_end_swap() {
+        // Last two tiles of the top row swapped: the case naive placement gets stuck on
+        // (a second swap in the middle row keeps the parity solvable)
+        let puzzle: PuzzleState = "1 3 2 / 4 6 5 / 7 8 _".parse().unwrap();
+        assert_solves(&puzzle);
+    }
+
+    #[test]
+    fn test_goals_with_empty_cell_top_left() {
+        for goal in [GoalSpec::BlankFirst, GoalSpec::Reversed] {
+            for (size, seed) in [(3, 1), (4, 2), (7, 3)] {
+                let puzzle = scramble_from(PuzzleState::with_goal(size, goal).unwrap(), 300, seed);
+                assert_solves(&puzzle);
+            }
+        }
+        let spiral = scramble_from(PuzzleState::with_goal(4, GoalSpec::Spiral).unwrap(), 50, 4);
+        assert_eq!(StrategicSolver::new().solve_with_path(&spiral), None);
+    }
+
+    #[test]
+    fn test_solved_rows_and_columns_stay_put() {
+        // Only the last two rows are scrambled: the top rows are never touched
+        let rows: PuzzleState = "1 2 3 4 / 5 6 7 8 / 13 9 10 11 / _ 14 15 12".parse().unwrap();
+        let path = StrategicSolver::new().solve_with_path(&rows).unwrap();
+        assert!(path.iter().all(|&(row, _)| row >= 2));
+        assert_solves(&rows);
+
+        // Only the bottom-right 3×3 block of a 6×6 board is scrambled
+        let block: PuzzleState = "1 2 3 4 5 6 / 7 8 9 10 11 12 / 13 14 15 16 17 18 / \
+                                  19 20 21 29 23 22 / 25 26 27 35 28 24 / 31 32 33 30 34 _"
+            .parse()
+            .unwrap();
+        let path = StrategicSolver::new().solve_with_path(&block).unwrap();
+        assert!(path.iter().all(|&(row, col)| row >= 3 && col >= 3));
+        assert_solves(&block);
+    }
+
+    #[test]
+    fn test_annotations_cover_the_path() {
+        let puzzle = scramble(4, 200, 6);
+        let solution = StrategicSolver::new().try_solve_instrumented(&puzzle, None, &mut |_| {}).unwrap();
+        let annotations = &solution.annotations;
+        assert_eq!(annotations[0].first_move, 0);
+        assert!(annotations.windows(2).all(|pair| pair[0].first_move < pair[1].first_move));
+        assert!(annotations.last().unwrap().first_move < solution.path.len());
+        assert!(matches!(annotations.last().unwrap().step, SolveStep::SolveCorner { .. }));
+        assert_eq!(step_at(annotations, solution.path.len() - 1), Some(&annotations.last().unwrap().step));
+
+        let step = SolveStep::FinishRow { row: 0, tiles: ["3".to_string(), "4".to_string()] };
+        assert_eq!(step.to_string(), "Finish row 1 by swinging tiles 3 and 4 in together");
+    }
+
+    #[test]
+    fn test_skipping_keeps_the_step_in_progress() {
+        let place = |first_move, tile: &str| Annotation {
+            first_move,
+            step: SolveStep::PlaceTile { tile: tile.to_string() },
+        };
+        let mut annotations = vec![place(0, "1"), place(4, "2"), place(9, "3")];
+        skip_annotated_moves(&mut annotations, 6);
+        assert_eq!(annotations, vec![place(0, "2"), place(3, "3")]);
+        skip_annotated_moves(&mut annotations, 3);
+        assert_eq!(annotations, vec![place(0, "3")]);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let result = StrategicSolver::new().solve_with_path_cancellable(&scramble(6, 500, 3), Some(cancel));
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,181 @@
+//! # Board Symmetries
+//!
+//! Reflecting or rotating a board, and renaming every tile after its reflected
+//! home cell, gives a board exactly as far from the goal as the original,
+//! provided the goal's empty cell stays where it was. The standard goal keeps
+//! its empty cell under a reflection across the main diagonal, so a board and
+//! its transpose always need the same number of moves; a goal whose empty cell
+//! is in the centre permits all eight symmetries of the square.
+//!
+//! A [`SymmetryGroup`] holds the symmetries a goal permits and gives each board
+//! a hash shared with all of its images. A* uses it, when
+//! [`SolverConfig::symmetry`] is on, to close a board once any of its images
+//! has been expanded.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{PackedState, PuzzleState, Symmetry, SymmetryGroup};
+//!
+//! let goal = PuzzleState::new(4)?;
+//! let group = SymmetryGroup::of_goal(&goal);
+//! assert_eq!(group.members(), &[Symmetry::Identity, Symmetry::Transpose]);
+//!
+//! let mut board = goal.clone();
+//! board.apply_immediate_move((3, 2));
+//! let mut mirrored = goal.clone();
+//! mirrored.apply_immediate_move((2, 3));
+//! let (board, mirrored) = (PackedState::from_state(&board), PackedState::from_state(&mirrored));
+//! assert_eq!(board.transformed(Symmetry::Transpose), mirrored);
+//! assert_eq!(group.canonical_hash(&board), group.canonical_hash(&mirrored));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`SolverConfig::symmetry`]: super::solver::SolverConfig::symmetry
+
+use super::move_validator::Position;
+use super::packed_state::PackedState;
+use super::puzzle_state::PuzzleState;
+use super::solver::state_hash;
+
+/// One of the eight ways to map a square board onto itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    Rotate180,
+    /// Quarter turn anticlockwise
+    Rotate270,
+    /// Upside down: the top row becomes the bottom row
+    FlipRows,
+    /// Mirror image: the left column becomes the right column
+    FlipColumns,
+    /// Reflection across the diagonal from top-left to bottom-right
+    Transpose,
+    /// Reflection across the diagonal from top-right to bottom-left
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All symmetries, identity first
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipRows,
+        Symmetry::FlipColumns,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Returns where the cell at `pos` of a `size`×`size` board lands
+    pub fn apply(self, (row, col): Position, size: usize) -> Position {
+        let last = size - 1;
+        match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, last - row),
+            Symmetry::Rotate180 => (last - row, last - col),
+            Symmetry::Rotate270 => (last - col, row),
+            Symmetry::FlipRows => (last - row, col),
+            Symmetry::FlipColumns => (row, last - col),
+            Symmetry::Transpose => (col, row),
+            Symmetry::AntiTranspose => (last - col, last - row),
+        }
+    }
+}
+
+/// The symmetries that leave a goal unchanged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetryGroup {
+    members: Vec<Symmetry>, // Identity first
+}
+
+impl SymmetryGroup {
+    /// Returns the symmetries that keep `goal`'s empty cell in place
+    pub fn of_goal(goal: &PuzzleState) -> Self {
+        let (size, empty) = (goal.size(), goal.goal_empty_position());
+        Self {
+            members: Symmetry::ALL
+                .into_iter()
+                .filter(|symmetry| symmetry.apply(empty, size) == empty)
+                .collect(),
+        }
+    }
+
+    /// Returns the group of the identity alone, which tells every board apart
+    pub fn trivial() -> Self {
+        Self {
+            members: vec![Symmetry::Identity],
+        }
+    }
+
+    pub fn members(&self) -> &[Symmetry] {
+        &self.members
+    }
+
+    /// Returns true if only the identity is in the group
+    pub fn is_trivial(&self) -> bool {
+        self.members.len() == 1
+    }
+
+    /// Returns a hash `state` shares with each of its images under the group
+    ///
+    /// Boards of the trivial group get their plain hash.
+    pub fn canonical_hash(&self, state: &PackedState) -> u64 {
+        self.members[1..]
+            .iter()
+            .map(|&symmetry| state_hash(&state.transformed(symmetry)))
+            .fold(state_hash(state), u64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::{EndgameTable, GoalSpec};
+
+    #[test]
+    fn test_groups_keep_the_goal() {
+        let standard = SymmetryGroup::of_goal(&PuzzleState::new(4).unwrap());
+        assert_eq!(standard.members(), &[Symmetry::Identity, Symmetry::Transpose]);
+        let blank_first = SymmetryGroup::of_goal(&PuzzleState::with_goal(4, GoalSpec::BlankFirst).unwrap());
+        assert_eq!(blank_first, standard);
+        let spiral = SymmetryGroup::of_goal(&PuzzleState::with_goal(3, GoalSpec::Spiral).unwrap());
+        assert_eq!(spiral.members().len(), 8);
+        assert!(SymmetryGroup::trivial().is_trivial());
+
+        for goal in [GoalSpec::Standard, GoalSpec::BlankFirst, GoalSpec::Reversed] {
+            let state = PuzzleState::with_goal(5, goal).unwrap();
+            for &symmetry in SymmetryGroup::of_goal(&state).members() {
+                assert!(PackedState::from_state(&state).transformed(symmetry).is_solved());
+            }
+        }
+    }
+
+    #[test]
+    fn test_images_are_as_far_from_the_goal() {
+        let table = EndgameTable::shared();
+        let spiral = PuzzleState::with_goal(3, GoalSpec::Spiral).unwrap();
+        for seed in 0..20 {
+            let board = PackedState::from_state(&scramble(3, 40, seed));
+            let image = board.transformed(Symmetry::Transpose);
+            assert_eq!(table.distance(&image.to_state()), table.distance(&board.to_state()));
+
+            // The spiral's empty cell is in the centre, so all eight images share a hash
+            let board = PackedState::from_state(&scramble_from(spiral.clone(), 40, seed));
+            let hashes: Vec<u64> = Symmetry::ALL
+                .iter()
+                .map(|&symmetry| SymmetryGroup::of_goal(&spiral).canonical_hash(&board.transformed(symmetry)))
+                .collect();
+            assert!(hashes.iter().all(|&hash| hash == hashes[0]));
+        }
+        let pos = (0, 1);
+        let twice = |symmetry: Symmetry| symmetry.apply(symmetry.apply(pos, 4), 4);
+        assert_eq!(twice(Symmetry::Rotate90), Symmetry::Rotate180.apply(pos, 4));
+        assert_eq!(Symmetry::Rotate270.apply(Symmetry::Rotate90.apply(pos, 4), 4), pos);
+        assert_eq!(twice(Symmetry::AntiTranspose), pos);
+    }
+}
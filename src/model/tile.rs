@@ -1,12 +1,106 @@
+use super::error::PuzzleError;
+
 /// Represents the visual content of a tile
 #[derive(Debug, Clone, PartialEq)]
 pub enum TileContent {
     /// Numeric label for the tile
     Numeric(u32),
+    /// A single letter, e.g. for word puzzles
+    Alpha(char),
+    /// Arbitrary text label (empty for a blank tile)
+    Custom(String),
     /// Placeholder for future image support
     Image(ImageData),
 }
 
+impl TileContent {
+    /// Returns the text shown on the tile, or None for image tiles
+    pub fn label(&self) -> Option<String> {
+        match self {
+            TileContent::Numeric(n) => Some(n.to_string()),
+            TileContent::Alpha(c) => Some(c.to_string()),
+            TileContent::Custom(text) => Some(text.clone()),
+            TileContent::Image(_) => None,
+        }
+    }
+}
+
+/// Defines what each tile shows, and so what the solved board looks like
+///
+/// Labels are listed in goal order: row-major, skipping the empty cell.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TileLabels {
+    /// Numbers 1 to n²-1
+    #[default]
+    Numbers,
+    /// Letters A to Z, then AA, AB, ... on boards with more than 26 tiles
+    Letters,
+    /// One letter per tile from a phrase such as "RATE YOUR MIND PAL" (whitespace ignored)
+    Word(String),
+    /// One label per tile
+    Custom(Vec<String>),
+    /// No labels; tiles are told apart by color alone
+    Blank,
+}
+
+impl TileLabels {
+    /// Returns the content of the tile at `index` in goal order
+    fn content(&self, index: usize) -> TileContent {
+        match self {
+            TileLabels::Numbers => TileContent::Numeric(index as u32 + 1),
+            TileLabels::Letters => match letter_name(index) {
+                name if name.len() == 1 => TileContent::Alpha(name.chars().next().unwrap()),
+                name => TileContent::Custom(name),
+            },
+            TileLabels::Word(text) => {
+                TileContent::Alpha(text.chars().filter(|c| !c.is_whitespace()).nth(index).unwrap_or('?'))
+            }
+            TileLabels::Custom(labels) => TileContent::Custom(labels.get(index).cloned().unwrap_or_default()),
+            TileLabels::Blank => TileContent::Custom(String::new()),
+        }
+    }
+
+    /// Returns the content of every tile of an `size`×`size` board, in goal order
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError::LabelCount` if a word or custom label list does not
+    /// have exactly one entry per tile
+    pub fn contents(&self, size: usize) -> Result<Vec<TileContent>, PuzzleError> {
+        let expected = size * size - 1;
+        let found = match self {
+            TileLabels::Word(text) => text.chars().filter(|c| !c.is_whitespace()).count(),
+            TileLabels::Custom(labels) => labels.len(),
+            _ => expected,
+        };
+        if found != expected {
+            return Err(PuzzleError::LabelCount { expected, found });
+        }
+        Ok((0..expected).map(|index| self.content(index)).collect())
+    }
+
+    /// Returns true if tiles with the same label are interchangeable
+    ///
+    /// A word board whose letters spell the phrase counts as solved even when two
+    /// equal letters sit in each other's places. Numbers and letters are unique,
+    /// and blank tiles are only told apart by their home position.
+    pub fn matches_by_label(&self) -> bool {
+        matches!(self, TileLabels::Word(_) | TileLabels::Custom(_))
+    }
+}
+
+/// Spreadsheet-style name for `index`: A..Z, then AA, AB, ...
+fn letter_name(index: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        name.push((b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    name.iter().rev().collect()
+}
+
 /// Placeholder for future image data
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImageData {
@@ -31,6 +125,14 @@ impl Tile {
         }
     }
 
+    /// Creates a new tile with the given content
+    pub fn with_content(content: TileContent, home_position: (usize, usize)) -> Self {
+        Self {
+            content,
+            home_position,
+        }
+    }
+
     /// Returns the numeric value if this is a numeric tile
     pub fn numeric_value(&self) -> Option<u32> {
         match self.content {
@@ -51,6 +153,25 @@ mod tests {
         assert_eq!(tile.home_position, (1, 2));
     }
 
+    #[test]
+    fn test_tile_labels() {
+        let word = TileLabels::Word("RATE YOUR MIND PAL".to_string());
+        let contents = word.contents(4).unwrap();
+        assert_eq!(contents[0], TileContent::Alpha('R'));
+        assert_eq!(contents[14], TileContent::Alpha('L'));
+        assert_eq!(
+            TileLabels::Word("TOO SHORT".to_string()).contents(4),
+            Err(PuzzleError::LabelCount { expected: 15, found: 8 })
+        );
+
+        let letters = TileLabels::Letters.contents(6).unwrap();
+        assert_eq!(letters[25], TileContent::Alpha('Z'));
+        assert_eq!(letters[26], TileContent::Custom("AA".to_string()));
+        assert_eq!(TileLabels::Numbers.contents(3).unwrap()[7], TileContent::Numeric(8));
+        assert_eq!(TileLabels::Blank.contents(3).unwrap()[0].label(), Some(String::new()));
+        assert!(TileLabels::Custom(vec!["x".to_string(); 8]).contents(3).is_ok());
+    }
+
     #[test]
     fn test_tile_content_equality() {
         let tile1 = Tile::new_numeric(3, (0, 0));
@@ -0,0 +1,394 @@
+//! # Walking Distance Heuristic
+//!
+//! Walking Distance (Ken'ichiro Takahashi) is a lower bound on solution length that
+//! dominates Manhattan distance: it accounts for tiles in the same row (or column)
+//! having to step around each other, which Manhattan distance ignores.
+//!
+//! ## How It Works
+//!
+//! For the vertical component, the board is reduced to a matrix of counts —
+//! `counts[r][g]` is the number of tiles in row `r` whose goal row is `g` — plus the
+//! row of the empty cell. Every vertical move swaps the empty cell with one tile from
+//! an adjacent row, so the fewest vertical moves needed is a shortest path in the
+//! graph of these matrices. The horizontal component is the same computation on
+//! columns, and by symmetry uses the same table.
+//!
+//! ## Table Construction
+//!
+//! The table is built by an exhaustive backward breadth-first search from the goal
+//! configuration over every reachable count matrix. Because every entry is an exact
+//! shortest distance in the relaxed problem, the heuristic is **admissible**.
+//!
+//! | Grid | Configurations |
+//! |------|----------------|
+//! | 3×3  | 105            |
+//! | 4×4  | 24,964         |
+//!
+//! 5×5 has over 65 million configurations and takes minutes to enumerate, so tables
+//! are limited to 4×4; larger grids fall back to other heuristics.
+//!
+//! Tables can be saved with [`WalkingDistance::to_bytes`] and restored with
+//! [`WalkingDistance::from_bytes`] to skip the search;
+//! [`WalkingDistance::load_or_build`] does this through a [`HeuristicCache`].
+//!
+//! [`HeuristicCache`]: super::heuristic_cache::HeuristicCache
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{EntropyCalculator, PuzzleState, WalkingDistance};
+//!
+//! let heuristic = WalkingDistance::new(4)?;
+//! let puzzle = PuzzleState::new(4)?;
+//! assert_eq!(heuristic.calculate(&puzzle), 0);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::entropy::{Difficulty, EntropyCalculator, ManhattanDistance, TuningTable};
+use super::error::PuzzleError;
+use super::heuristic_cache::{CachedTable, HeuristicCache};
+use super::puzzle_state::PuzzleState;
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+
+/// Largest grid whose table can be built in reasonable time and memory
+pub const MAX_WALKING_DISTANCE_SIZE: usize = 4;
+
+/// Smallest supported grid
+const MIN_WALKING_DISTANCE_SIZE: usize = 3;
+
+/// Bits per count entry in an encoded configuration (counts never exceed 4)
+const COUNT_BITS: usize = 3;
+
+/// Header identifying serialized tables: magic bytes, then format version and size
+const TABLE_MAGIC: &[u8; 4] = b"SLWD";
+
+/// Serialized table format version, bumped on incompatible changes
+const TABLE_VERSION: u8 = 1;
+
+/// Difficulty tuning; only the 3×3 and 4×4 rows are ever used
+const TUNING: TuningTable = [
+    [100, 103, 104, 108],
+    [100, 102, 103, 104],
+    [100, 100, 100, 100],
+    [100, 100, 100, 100],
+    [100, 100, 100, 100],
+];
+
+/// Walking Distance heuristic backed by a precomputed lookup table
+#[derive(Debug, Clone)]
+pub struct WalkingDistance {
+    size: usize,
+    table: HashMap<u64, u8>, // Encoded configuration -> moves along one axis
+}
+
+impl WalkingDistance {
+    /// Builds the lookup table for the given grid size by exhaustive BFS
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if size is below 3 or above `MAX_WALKING_DISTANCE_SIZE`
+    pub fn new(size: usize) -> Result<Self, PuzzleError> {
+        check_size(size)?;
+        Ok(Self {
+            size,
+            table: build_table(size),
+        })
+    }
+
+    /// Loads the table for `size` from `cache`, building and storing it if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns `PuzzleError` if size is below 3 or above `MAX_WALKING_DISTANCE_SIZE`
+    pub fn load_or_build(size: usize, cache: &HeuristicCache) -> Result<Self, PuzzleError> {
+        check_size(size)?;
+        let name = format!("{}x{}", size, size);
+        let table = cache.load_or_build(&name, || Self {
+            size,
+            table: build_table(size),
+        });
+        if table.size == size {
+            Ok(table)
+        } else {
+            Self::new(size) // File for another size under this name
+        }
+    }
+
+    /// Returns the grid size this table was built for
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of distinct configurations in the table
+    pub fn table_len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Serializes the table (header followed by sorted key/distance pairs)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut entries: Vec<_> = self.table.iter().collect();
+        entries.sort_unstable();
+
+        let mut bytes = Vec::with_capacity(6 + entries.len() * 9);
+        bytes.extend_from_slice(TABLE_MAGIC);
+        bytes.push(TABLE_VERSION);
+        bytes.push(self.size as u8);
+        for (&key, &distance) in entries {
+            bytes.extend_from_slice(&key.to_le_bytes());
+            bytes.push(distance);
+        }
+        bytes
+    }
+
+    /// Restores a table written by `to_bytes`
+    /// Returns None if the bytes are truncated, from another version, or for an unsupported size
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (header, body) = bytes.split_at_checked(6)?;
+        if &header[..4] != TABLE_MAGIC || header[4] != TABLE_VERSION {
+            return None;
+        }
+        let size = header[5] as usize;
+        check_size(size).ok()?;
+        if !body.len().is_multiple_of(9) {
+            return None;
+        }
+
+        let table = body
+            .chunks_exact(9)
+            .map(|chunk| {
+                let key = u64::from_le_bytes(chunk[..8].try_into().expect("8-byte key"));
+                (key, chunk[8])
+            })
+            .collect();
+        Some(Self { size, table })
+    }
+
+    /// Looks up the moves needed along one axis
+    fn lookup(&self, counts: &[u8], blank: usize) -> u32 {
+        // Every valid board maps to a reachable configuration
+        self.table
+            .get(&encode(self.size, counts, blank))
+            .copied()
+            .unwrap_or(0) as u32
+    }
+}
+
+impl CachedTable for WalkingDistance {
+    const KIND: &'static str = "walking-distance";
+    const VERSION: u32 = TABLE_VERSION as u32;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        WalkingDistance::to_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        WalkingDistance::from_bytes(bytes)
+    }
+}
+
+impl EntropyCalculator for WalkingDistance {
+    /// Returns vertical plus horizontal walking distance
+    /// Returns 0 for boards of a different size than the table
+    ///
+    /// The table assumes the empty cell belongs in the last row and column; goals
+    /// with it in the first are mirrored to match, and any other goal (such as a
+    /// spiral, which ends mid-board) falls back to Manhattan distance.
+    fn calculate(&self, state: &PuzzleState) -> u32 {
+        let n = self.size;
+        if state.size() != n {
+            return 0;
+        }
+
+        let (goal_row, goal_col) = state.goal_empty_position();
+        let mirror = |goal: usize| match goal {
+            g if g == n - 1 => Some(false),
+            0 => Some(true),
+            _ => None,
+        };
+        let (Some(flip_rows), Some(flip_cols)) = (mirror(goal_row), mirror(goal_col)) else {
+            return ManhattanDistance.calculate(state);
+        };
+        let flip = |index: usize, flipped: bool| if flipped { n - 1 - index } else { index };
+
+        let mut rows = vec![0u8; n * n];
+        let mut cols = vec![0u8; n * n];
+        for ((row, col), tile) in state.tiles() {
+            let (home_row, home_col) = tile.home_position;
+            rows[flip(row, flip_rows) * n + flip(home_row, flip_rows)] += 1;
+            cols[flip(col, flip_cols) * n + flip(home_col, flip_cols)] += 1;
+        }
+
+        let (empty_row, empty_col) = state.empty_position();
+        self.lookup(&rows, flip(empty_row, flip_rows)) + self.lookup(&cols, flip(empty_col, flip_cols))
+    }
+
+    fn target_range(&self, difficulty: Difficulty, size: usize) -> RangeInclusive<u32> {
+        difficulty.tuned_range(size, &TUNING)
+    }
+}
+
+fn check_size(size: usize) -> Result<(), PuzzleError> {
+    if size < MIN_WALKING_DISTANCE_SIZE {
+        return Err(PuzzleError::SizeTooSmall {
+            size,
+            min: MIN_WALKING_DISTANCE_SIZE,
+        });
+    }
+    if size > MAX_WALKING_DISTANCE_SIZE {
+        return Err(PuzzleError::SizeTooLarge {
+            size,
+            max: MAX_WALKING_DISTANCE_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Packs a configuration into a u64
+///
+/// Each row's last count is implied by the row total, so only the first n-1 counts
+/// of each row are stored, followed by the blank's row.
+fn encode(size: usize, counts: &[u8], blank: usize) -> u64 {
+    let mut key = 0u64;
+    for row in 0..size {
+        for goal in 0..size - 1 {
+            key = (key << COUNT_BITS) | counts[row * size + goal] as u64;
+        }
+    }
+    (key << COUNT_BITS) | blank as u64
+}
+
+/// Backward BFS from the goal over every reachable count configuration
+fn build_table(size: usize) -> HashMap<u64, u8> {
+    let started = std::time::Instant::now();
+
+    // Goal: every tile in its own row; the last row is one short (the blank)
+    let mut goal = vec![0u8; size * size];
+    for row in 0..size {
+        goal[row * size + row] = size as u8;
+    }
+    goal[size * size - 1] -= 1;
+
+    let mut table = HashMap::new();
+    let mut queue = VecDeque::new();
+    table.insert(encode(size, &goal, size - 1), 0u8);
+    queue.push_back((goal, size - 1, 0u8));
+
+    while let Some((counts, blank, distance)) = queue.pop_front() {
+        let neighbors = [blank.checked_sub(1), Some(blank + 1).filter(|&r| r < size)];
+        for adjacent in neighbors.into_iter().flatten() {
+            // Any tile from the adjacent row may step into the blank's row
+            for goal_row in 0..size {
+                if counts[adjacent * size + goal_row] == 0 {
+                    continue;
+                }
+                let mut next = counts.clone();
+                next[adjacent * size + goal_row] -= 1;
+                next[blank * size + goal_row] += 1;
+
+                let key = encode(size, &next, adjacent);
+                if let std::collections::hash_map::Entry::Vacant(entry) = table.entry(key) {
+                    entry.insert(distance + 1);
+                    queue.push_back((next, adjacent, distance + 1));
+                }
+            }
+        }
+    }
+
+    log::debug!(
+        "Built {}×{} walking distance table ({} configurations) in {:?}",
+        size,
+        size,
+        table.len(),
+        started.elapsed()
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::solver::{scramble, scramble_from};
+    use crate::model::{GoalSpec, IDAStarSolver};
+
+    #[test]
+    fn test_table_sizes() {
+        assert_eq!(WalkingDistance::new(3).unwrap().table_len(), 105);
+        assert_eq!(WalkingDistance::new(4).unwrap().table_len(), 24_964);
+    }
+
+    #[test]
+    fn test_unsupported_sizes() {
+        assert!(matches!(WalkingDistance::new(2), Err(PuzzleError::SizeTooSmall { .. })));
+        assert!(matches!(WalkingDistance::new(5), Err(PuzzleError::SizeTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_single_move() {
+        let heuristic = WalkingDistance::new(4).unwrap();
+        let mut puzzle = PuzzleState::new(4).unwrap();
+        puzzle.apply_immediate_move((3, 2));
+        assert_eq!(heuristic.calculate(&puzzle), 1);
+    }
+
+    #[test]
+    fn test_dominates_manhattan() {
+        let heuristic = WalkingDistance::new(4).unwrap();
+        for seed in 0..50 {
+            let puzzle = scramble(4, 80, seed);
+            assert!(heuristic.calculate(&puzzle) >= ManhattanDistance.calculate(&puzzle));
+        }
+    }
+
+    #[test]
+    fn test_admissible_against_optimal_solver() {
+        for (size, steps) in [(3, 40), (4, 22)] {
+            let heuristic = WalkingDistance::new(size).unwrap();
+            for seed in 0..10 {
+                let puzzle = scramble(size, steps, seed);
+                let optimal = IDAStarSolver::new().solve(&puzzle).unwrap();
+                let estimate = heuristic.calculate(&puzzle);
+                assert!(estimate <= optimal, "{} > {} for {}", estimate, optimal, puzzle.to_notation());
+            }
+        }
+    }
+
+    #[test]
+    fn test_other_goals() {
+        let heuristic = WalkingDistance::new(3).unwrap();
+        for goal in GoalSpec::ALL {
+            assert_eq!(heuristic.calculate(&PuzzleState::with_goal(3, goal).unwrap()), 0);
+            for seed in 0..10 {
+                let puzzle = scramble_from(PuzzleState::with_goal(3, goal).unwrap(), 30, seed);
+                let optimal = IDAStarSolver::new().solve(&puzzle).unwrap();
+                let estimate = heuristic.calculate(&puzzle);
+                assert!(estimate <= optimal, "{}: {} > {} for {}", goal, estimate, optimal, puzzle.to_notation());
+                assert!(estimate >= ManhattanDistance.calculate(&puzzle));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let heuristic = WalkingDistance::new(4).unwrap();
+        let restored = WalkingDistance::from_bytes(&heuristic.to_bytes()).unwrap();
+        assert_eq!(restored.size(), 4);
+        assert_eq!(restored.table, heuristic.table);
+
+        assert!(WalkingDistance::from_bytes(b"SLWD").is_none());
+        assert!(WalkingDistance::from_bytes(&heuristic.to_bytes()[..100]).is_none());
+    }
+
+    #[test]
+    fn test_load_or_build_caches_table() {
+        let dir = std::env::temp_dir().join(format!("slider-wd-{}", std::process::id()));
+        let cache = HeuristicCache::new(dir);
+        let _ = cache.clear();
+
+        let built = WalkingDistance::load_or_build(3, &cache).unwrap();
+        assert!(cache.path_for::<WalkingDistance>("3x3").unwrap().exists());
+        let loaded = WalkingDistance::load_or_build(3, &cache).unwrap();
+        assert_eq!(loaded.table, built.table);
+        let _ = cache.clear();
+    }
+}
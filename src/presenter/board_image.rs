@@ -0,0 +1,461 @@
+//! Headless board images: the board drawn without a GUI, as pixels or as SVG.
+//!
+//! [`TileRenderer::draw_board`] paints a board onto any [`Surface`], apart from
+//! the live egui frame, so the same colors, borders, hatching, labels and
+//! heatmap tints reach every output. Two surfaces are provided:
+//!
+//! - [`Canvas`] - pixels, drawn without anti-aliasing so a frame has few colors
+//!   (replay frames, PNG images)
+//! - [`SvgCanvas`] - SVG markup with real text, for documentation
+//!
+//! With the `board-export` feature, [`export_board_image`] writes the current
+//! board as a PNG or SVG file.
+//!
+//! [`TileRenderer::draw_board`]: super::tile_renderer::TileRenderer::draw_board
+
+#[cfg(feature = "board-export")]
+use crate::controller::storage;
+#[cfg(feature = "board-export")]
+use crate::model::{Position, PuzzleState};
+#[cfg(feature = "board-export")]
+use crate::presenter::tile_renderer::TileRenderer;
+use egui::{Color32, ColorImage, Pos2, Rect, Vec2};
+use std::fmt::Write;
+#[cfg(feature = "board-export")]
+use std::fmt;
+#[cfg(feature = "board-export")]
+use std::io;
+#[cfg(feature = "board-export")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "board-export")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Height of a text line relative to the height of its capitals
+const FONT_TO_CAP: f32 = 1.4;
+
+/// Average advance of a sans-serif character, as a fraction of the font size
+const CHAR_WIDTH: f32 = 0.6;
+
+/// Something a board can be painted on
+///
+/// Colors may be translucent; they are drawn over what is already there.
+pub trait Surface {
+    /// Fills a rectangle with corners rounded to `radius`
+    fn fill_rounded(&mut self, rect: Rect, radius: f32, color: Color32);
+
+    /// Outlines a rounded rectangle with a border `width` thick, inside its edge
+    fn stroke_rounded(&mut self, rect: Rect, radius: f32, width: f32, color: Color32);
+
+    fn fill_circle(&mut self, center: Pos2, radius: f32, color: Color32);
+
+    fn line(&mut self, from: Pos2, to: Pos2, width: f32, color: Color32);
+
+    /// Writes `text` centered on `center` with capitals about `height` tall,
+    /// shrinking it to fit `max_width`
+    fn text(&mut self, center: Pos2, text: &str, height: f32, max_width: f32, color: Color32);
+}
+
+/// 3×5 pixel glyphs for digits and capital letters, one row of three bits per byte
+const GLYPHS: [(char, [u8; 5]); 36] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b001, 0b001, 0b001]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', [0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', [0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', [0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', [0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', [0b010, 0b101, 0b101, 0b101, 0b010]),
+    ('P', [0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', [0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', [0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', [0b011, 0b100, 0b010, 0b001, 0b110]),
+    ('T', [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', [0b111, 0b001, 0b010, 0b100, 0b111]),
+];
+
+/// Pixel surface, one pixel per point
+///
+/// Shapes are drawn without anti-aliasing, which keeps the colors of a frame
+/// few enough for one GIF palette. Text uses a blocky built-in font of digits
+/// and capital letters; other characters are left as spaces.
+pub struct Canvas {
+    image: ColorImage,
+}
+
+impl Canvas {
+    pub fn new(size: Vec2, background: Color32) -> Self {
+        Self {
+            image: ColorImage::new([size.x.ceil() as usize, size.y.ceil() as usize], background),
+        }
+    }
+
+    pub fn into_image(self) -> ColorImage {
+        self.image
+    }
+
+    /// Blends `color` into each pixel of `rect` whose center passes `inside`, clipped to the canvas
+    fn paint(&mut self, rect: Rect, color: Color32, inside: impl Fn(Pos2) -> bool) {
+        let clip = |from: f32, to: f32, limit: usize| (from.round().max(0.0) as usize)..(to.round().max(0.0) as usize).min(limit);
+        let columns = clip(rect.left(), rect.right(), self.image.width());
+        for y in clip(rect.top(), rect.bottom(), self.image.height()) {
+            for x in columns.clone() {
+                if inside(Pos2::new(x as f32 + 0.5, y as f32 + 0.5)) {
+                    let pixel = &mut self.image[(x, y)];
+                    *pixel = blend(*pixel, color);
+                }
+            }
+        }
+    }
+}
+
+/// Draws `over` on top of `under`, mixing the unmultiplied channels by `over`'s alpha
+fn blend(under: Color32, over: Color32) -> Color32 {
+    let [r, g, b, a] = over.to_srgba_unmultiplied();
+    if a == 255 {
+        return over;
+    }
+    let [ur, ug, ub, ua] = under.to_srgba_unmultiplied();
+    let mix = |u: u8, o: u8| (u as f32 + (o as f32 - u as f32) * a as f32 / 255.0).round() as u8;
+    let alpha = (a as f32 + ua as f32 * (255 - a) as f32 / 255.0).round() as u8;
+    Color32::from_rgba_unmultiplied(mix(ur, r), mix(ug, g), mix(ub, b), alpha)
+}
+
+impl Surface for Canvas {
+    fn fill_rounded(&mut self, rect: Rect, radius: f32, color: Color32) {
+        self.paint(rect, color, |p| in_rounded(rect, radius, p));
+    }
+
+    fn stroke_rounded(&mut self, rect: Rect, radius: f32, width: f32, color: Color32) {
+        let inner = rect.shrink(width);
+        let inner_radius = (radius - width).max(0.0);
+        self.paint(rect, color, |p| in_rounded(rect, radius, p) && !in_rounded(inner, inner_radius, p));
+    }
+
+    fn fill_circle(&mut self, center: Pos2, radius: f32, color: Color32) {
+        let bounds = Rect::from_center_size(center, Vec2::splat(radius * 2.0));
+        self.paint(bounds, color, |p| p.distance(center) <= radius);
+    }
+
+    fn line(&mut self, from: Pos2, to: Pos2, width: f32, color: Color32) {
+        let bounds = Rect::from_two_pos(from, to).expand(width);
+        let along = to - from;
+        self.paint(bounds, color, |p| {
+            let t = ((p - from).dot(along) / along.length_sq().max(f32::EPSILON)).clamp(0.0, 1.0);
+            p.distance(from + along * t) <= width / 2.0
+        });
+    }
+
+    fn text(&mut self, center: Pos2, text: &str, height: f32, max_width: f32, color: Color32) {
+        let chars: Vec<char> = text.chars().map(|c| c.to_ascii_uppercase()).collect();
+        let columns = (chars.len() * 4).saturating_sub(1) as f32;
+        let unit = (height / 5.0).min(max_width / columns.max(1.0)).floor().max(1.0);
+        let origin = center - Vec2::new(columns * unit / 2.0, 2.5 * unit);
+        for (i, c) in chars.iter().enumerate() {
+            let Some((_, rows)) = GLYPHS.iter().find(|(glyph, _)| glyph == c) else {
+                continue;
+            };
+            for (row, bits) in rows.iter().enumerate() {
+                for col in (0..3).filter(|col| bits & (0b100 >> col) != 0) {
+                    let min = origin + Vec2::new((i * 4 + col) as f32 * unit, row as f32 * unit);
+                    self.fill_rounded(Rect::from_min_size(min, Vec2::splat(unit)), 0.0, color);
+                }
+            }
+        }
+    }
+}
+
+/// Returns true if `p` lies inside `rect` with its corners rounded to `radius`
+fn in_rounded(rect: Rect, radius: f32, p: Pos2) -> bool {
+    if !rect.contains(p) {
+        return false;
+    }
+    let radius = radius.min(rect.width() / 2.0).min(rect.height() / 2.0);
+    let core = rect.shrink(radius);
+    let nearest = Pos2::new(p.x.clamp(core.left(), core.right()), p.y.clamp(core.top(), core.bottom()));
+    p.distance(nearest) <= radius
+}
+
+/// SVG surface, sized in points
+pub struct SvgCanvas {
+    size: Vec2,
+    body: String,
+}
+
+impl SvgCanvas {
+    pub fn new(size: Vec2, background: Color32) -> Self {
+        let mut canvas = Self {
+            size,
+            body: String::new(),
+        };
+        canvas.fill_rounded(Rect::from_min_size(Pos2::ZERO, size), 0.0, background);
+        canvas
+    }
+
+    /// Returns the finished document
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            self.body,
+            w = self.size.x,
+            h = self.size.y,
+        )
+    }
+}
+
+/// Returns an SVG paint and its opacity attribute for `color`
+fn svg_paint(color: Color32, attribute: &str) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("{}=\"rgb({},{},{})\"", attribute, r, g, b)
+    } else {
+        format!("{}=\"rgb({},{},{})\" {}-opacity=\"{:.3}\"", attribute, r, g, b, attribute, a as f32 / 255.0)
+    }
+}
+
+/// Escapes text for use inside an XML element
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl Surface for SvgCanvas {
+    fn fill_rounded(&mut self, rect: Rect, radius: f32, color: Color32) {
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height(),
+            radius,
+            svg_paint(color, "fill"),
+        );
+    }
+
+    fn stroke_rounded(&mut self, rect: Rect, radius: f32, width: f32, color: Color32) {
+        // SVG strokes straddle the edge, so the path runs half a width inside it
+        let path = rect.shrink(width / 2.0);
+        let _ = writeln!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"none\" stroke-width=\"{}\" {}/>",
+            path.left(),
+            path.top(),
+            path.width(),
+            path.height(),
+            (radius - width / 2.0).max(0.0),
+            width,
+            svg_paint(color, "stroke"),
+        );
+    }
+
+    fn fill_circle(&mut self, center: Pos2, radius: f32, color: Color32) {
+        let _ = writeln!(
+            self.body,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+            center.x,
+            center.y,
+            radius,
+            svg_paint(color, "fill"),
+        );
+    }
+
+    fn line(&mut self, from: Pos2, to: Pos2, width: f32, color: Color32) {
+        let _ = writeln!(
+            self.body,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke-width=\"{}\" {}/>",
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            width,
+            svg_paint(color, "stroke"),
+        );
+    }
+
+    fn text(&mut self, center: Pos2, text: &str, height: f32, max_width: f32, color: Color32) {
+        let mut font_size = height * FONT_TO_CAP;
+        let estimated = text.chars().count() as f32 * font_size * CHAR_WIDTH;
+        if estimated > max_width {
+            font_size *= max_width / estimated;
+        }
+        let _ = writeln!(
+            self.body,
+            "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+            center.x,
+            center.y,
+            font_size,
+            svg_paint(color, "fill"),
+            xml_escape(text),
+        );
+    }
+}
+
+/// File type of an exported board image
+#[cfg(feature = "board-export")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardImageFormat {
+    Png,
+    Svg,
+}
+
+#[cfg(feature = "board-export")]
+impl BoardImageFormat {
+    pub const ALL: [BoardImageFormat; 2] = [BoardImageFormat::Png, BoardImageFormat::Svg];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            BoardImageFormat::Png => "png",
+            BoardImageFormat::Svg => "svg",
+        }
+    }
+}
+
+#[cfg(feature = "board-export")]
+impl fmt::Display for BoardImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardImageFormat::Png => write!(f, "PNG"),
+            BoardImageFormat::Svg => write!(f, "SVG"),
+        }
+    }
+}
+
+/// Returns a fresh path for a board image inside the data directory
+/// Returns None if no data directory can be determined
+#[cfg(feature = "board-export")]
+pub fn board_image_file(format: BoardImageFormat, grid_size: usize) -> Option<PathBuf> {
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let name = format!("board-{}x{}-{}.{}", grid_size, grid_size, stamp, format.extension());
+    storage::data_file(IMAGE_DIR).map(|dir| dir.join(name))
+}
+
+/// Subdirectory of the data directory that board images are written to
+#[cfg(feature = "board-export")]
+pub const IMAGE_DIR: &str = "images";
+
+/// Returns `state` drawn as an image file's bytes, with `tints` over their cells
+///
+/// # Errors
+///
+/// Returns an `InvalidData` I/O error if the PNG cannot be encoded
+#[cfg(feature = "board-export")]
+pub fn board_image(
+    renderer: &TileRenderer,
+    state: &PuzzleState,
+    tints: &[(Position, Color32)],
+    format: BoardImageFormat,
+) -> io::Result<Vec<u8>> {
+    match format {
+        BoardImageFormat::Svg => Ok(renderer.to_svg(state, tints).into_bytes()),
+        BoardImageFormat::Png => {
+            use image::ImageEncoder;
+            let image = renderer.rasterize(state, tints);
+            let rgba: Vec<u8> = image.pixels.iter().flat_map(|p| p.to_srgba_unmultiplied()).collect();
+            let mut png = Vec::new();
+            image::codecs::png::PngEncoder::new(&mut png)
+                .write_image(&rgba, image.width() as u32, image.height() as u32, image::ExtendedColorType::Rgba8)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Ok(png)
+        }
+    }
+}
+
+/// Writes `state` as an image file at `path`, creating parent directories as needed
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the image cannot be encoded or written
+#[cfg(feature = "board-export")]
+pub fn export_board_image(
+    renderer: &TileRenderer,
+    state: &PuzzleState,
+    tints: &[(Position, Color32)],
+    format: BoardImageFormat,
+    path: &Path,
+) -> io::Result<()> {
+    let bytes = board_image(renderer, state, tints, format)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)?;
+    log::info!("Exported the board as {} to {}", format, path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::PuzzleState;
+    use crate::presenter::tile_renderer::TileRenderer;
+
+    #[test]
+    fn test_canvas_blends_translucent_paint() {
+        let mut canvas = Canvas::new(Vec2::new(4.0, 2.0), Color32::WHITE);
+        canvas.fill_rounded(Rect::from_min_size(Pos2::ZERO, Vec2::new(2.0, 2.0)), 0.0, Color32::from_rgb(0, 0, 200));
+        canvas.fill_rounded(Rect::from_min_size(Pos2::ZERO, Vec2::new(4.0, 1.0)), 0.0, Color32::from_black_alpha(128));
+        let image = canvas.into_image();
+        assert_eq!(image[(0, 1)], Color32::from_rgb(0, 0, 200));
+        assert_eq!(image[(3, 1)], Color32::WHITE);
+        assert_eq!(image[(0, 0)], Color32::from_rgb(0, 0, 100));
+        assert_eq!(image[(3, 0)], Color32::from_rgb(127, 127, 127));
+
+        // egui premultiplies in linear space, so a tint must not be added on as if in gamma space
+        let sky = Color32::from_rgb(155, 198, 222);
+        let heat = blend(sky, crate::presenter::tile_renderer::heatmap_color(0, 2));
+        assert!(heat.r() > sky.r() && heat.b() < sky.b());
+    }
+
+    #[test]
+    fn test_board_images_show_tiles_and_tints() {
+        let renderer = TileRenderer::new(40.0, 4.0, 3);
+        let state: PuzzleState = "1 2 3 / 4 _ 6 / 7 5 8".parse().unwrap();
+        let tint = Color32::from_rgba_unmultiplied(255, 120, 0, 120);
+
+        let plain = renderer.rasterize(&state, &[]);
+        let tinted = renderer.rasterize(&state, &[((0, 0), tint)]);
+        assert_eq!(plain.size, [136, 136]); // Three tiles, two gaps and a margin each side
+        assert_ne!(plain[(20, 20)], tinted[(20, 20)]);
+        assert_eq!(plain[(100, 20)], tinted[(100, 20)]);
+
+        let svg = renderer.to_svg(&state, &[((0, 0), tint)]);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"136\" height=\"136\""));
+        assert_eq!(svg.matches("</text>").count(), 8);
+        assert!(svg.contains(">5</text>"));
+        assert!(svg.contains("fill-opacity=\"0.471\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(xml_escape("<a & b>"), "&lt;a &amp; b&gt;");
+    }
+
+    #[cfg(feature = "board-export")]
+    #[test]
+    fn test_png_decodes_to_the_raster() {
+        let renderer = TileRenderer::new(30.0, 3.0, 3);
+        let state = PuzzleState::new(3).unwrap();
+        let png = board_image(&renderer, &state, &[], BoardImageFormat::Png).unwrap();
+        let decoded = image::load_from_memory_with_format(&png, image::ImageFormat::Png).unwrap().to_rgba8();
+        let raster = renderer.rasterize(&state, &[]);
+        assert_eq!((decoded.width() as usize, decoded.height() as usize), (raster.width(), raster.height()));
+        assert_eq!(decoded.get_pixel(15, 15).0, raster[(15, 15)].to_srgba_unmultiplied());
+    }
+}
@@ -0,0 +1,188 @@
+//! Screenshot and webcam capture for picture tiles.
+//!
+//! Built with the `capture` feature. Rather than link a platform capture
+//! library, a frame is grabbed with whichever command-line tool the system
+//! already has (`grim`, `scrot` or `screencapture` for the screen, `ffmpeg` for
+//! the webcam), written to a temporary PNG, and read back. The frame is cropped
+//! to a centered square so it can be cut into tiles on any grid size.
+
+use eframe::egui::ColorImage;
+use image::imageops::FilterType;
+use image::RgbaImage;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Longest side of a captured picture, in pixels; larger frames are scaled down
+pub const MAX_PICTURE_SIDE: u32 = 1024;
+
+/// Where a picture is captured from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureSource {
+    /// The whole desktop
+    Screenshot,
+    /// The first webcam
+    Webcam,
+}
+
+impl CaptureSource {
+    pub const ALL: [CaptureSource; 2] = [CaptureSource::Screenshot, CaptureSource::Webcam];
+
+    /// Returns the commands that may capture from this source into `out`, most specific first
+    fn commands(self, out: &Path) -> Vec<Command> {
+        let command = |program: &str, args: &[&str]| {
+            let mut command = Command::new(program);
+            command.args(args).arg(out);
+            command
+        };
+        match self {
+            CaptureSource::Screenshot if cfg!(target_os = "macos") => vec![command("screencapture", &["-x"])],
+            CaptureSource::Screenshot if cfg!(unix) => vec![
+                command("grim", &[]),
+                command("gnome-screenshot", &["-f"]),
+                command("spectacle", &["-b", "-n", "-o"]),
+                command("scrot", &["-o"]),
+                command("import", &["-window", "root"]),
+            ],
+            CaptureSource::Webcam if cfg!(target_os = "macos") => vec![command(
+                "ffmpeg",
+                &["-y", "-loglevel", "error", "-f", "avfoundation", "-framerate", "30", "-i", "0", "-frames:v", "1"],
+            )],
+            CaptureSource::Webcam if cfg!(unix) => vec![command(
+                "ffmpeg",
+                &["-y", "-loglevel", "error", "-f", "v4l2", "-i", "/dev/video0", "-frames:v", "1"],
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for CaptureSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureSource::Screenshot => write!(f, "Screenshot"),
+            CaptureSource::Webcam => write!(f, "Webcam"),
+        }
+    }
+}
+
+/// Why a capture failed
+#[derive(Debug)]
+pub enum CaptureError {
+    /// No capture tool for the source is installed on this system
+    NoTool(CaptureSource),
+    /// The capture tool ran but did not produce a picture
+    ToolFailed { tool: String, message: String },
+    /// The captured file could not be read or decoded
+    Image(String),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::NoTool(CaptureSource::Screenshot) => {
+                write!(f, "No screenshot tool found (install grim, scrot or ImageMagick)")
+            }
+            CaptureError::NoTool(CaptureSource::Webcam) => write!(f, "No webcam tool found (install ffmpeg)"),
+            CaptureError::ToolFailed { tool, message } => write!(f, "{} failed: {}", tool, message),
+            CaptureError::Image(message) => write!(f, "Could not read the captured picture: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// Captures a frame from `source` as a square picture
+///
+/// Blocks until the capture tool exits, which for a webcam can take a second.
+///
+/// # Errors
+///
+/// Returns `CaptureError::NoTool` if none of the tools for the source is
+/// installed, `CaptureError::ToolFailed` if the last tool tried failed, or
+/// `CaptureError::Image` if its output cannot be decoded
+pub fn capture(source: CaptureSource) -> Result<ColorImage, CaptureError> {
+    let out = std::env::temp_dir().join(format!("slider-capture-{}.png", std::process::id()));
+    let mut error = CaptureError::NoTool(source);
+    for mut command in source.commands(&out) {
+        let tool = command.get_program().to_string_lossy().into_owned();
+        match command.output() {
+            Ok(output) if output.status.success() && out.exists() => {
+                let picture = fs::read(&out)
+                    .map_err(|e| CaptureError::Image(e.to_string()))
+                    .and_then(|bytes| decode_picture(&bytes));
+                let _ = fs::remove_file(&out);
+                return picture;
+            }
+            Ok(output) => {
+                let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                log::debug!("{} could not capture: {}", tool, message);
+                error = CaptureError::ToolFailed {
+                    tool,
+                    message: if message.is_empty() { output.status.to_string() } else { message },
+                };
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                error = CaptureError::ToolFailed {
+                    tool,
+                    message: e.to_string(),
+                }
+            }
+        }
+    }
+    Err(error)
+}
+
+/// Decodes a PNG and crops it to a square picture
+///
+/// # Errors
+///
+/// Returns `CaptureError::Image` if `bytes` is not a PNG
+pub fn decode_picture(bytes: &[u8]) -> Result<ColorImage, CaptureError> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Png)
+        .map_err(|e| CaptureError::Image(e.to_string()))?;
+    Ok(square_picture(&image.to_rgba8()))
+}
+
+/// Crops the largest centered square from `image`, scaled down to `MAX_PICTURE_SIDE`
+pub fn square_picture(image: &RgbaImage) -> ColorImage {
+    let side = image.width().min(image.height());
+    let x = (image.width() - side) / 2;
+    let y = (image.height() - side) / 2;
+    let mut square = image::imageops::crop_imm(image, x, y, side, side).to_image();
+    if side > MAX_PICTURE_SIDE {
+        square = image::imageops::resize(&square, MAX_PICTURE_SIDE, MAX_PICTURE_SIDE, FilterType::Triangle);
+    }
+    let size = [square.width() as usize, square.height() as usize];
+    ColorImage::from_rgba_unmultiplied(size, square.as_raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_square_picture_is_centered() {
+        // Wide frame: red left margin, green middle, blue right margin
+        let frame = RgbaImage::from_fn(6, 2, |x, _| match x {
+            0 | 1 => Rgba([255, 0, 0, 255]),
+            2 | 3 => Rgba([0, 255, 0, 255]),
+            _ => Rgba([0, 0, 255, 255]),
+        });
+        let picture = square_picture(&frame);
+        assert_eq!(picture.size, [2, 2]);
+        assert!(picture.pixels.iter().all(|&p| p == eframe::egui::Color32::GREEN));
+
+        let tall = RgbaImage::new(MAX_PICTURE_SIDE + 10, MAX_PICTURE_SIDE * 2);
+        assert_eq!(square_picture(&tall).size, [MAX_PICTURE_SIDE as usize; 2]);
+    }
+
+    #[test]
+    fn test_decode_rejects_non_png() {
+        assert!(matches!(decode_picture(b"not a picture"), Err(CaptureError::Image(_))));
+    }
+}
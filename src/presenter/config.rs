@@ -0,0 +1,469 @@
+//! User configuration loaded from `slider.toml`.
+//!
+//! The config file holds the defaults a new session starts with: grid size,
+//! difficulty, solver, language, theme, tile colors, animation timing and the
+//! keyboard shortcuts. It is created with the built-in defaults on first run and can be
+//! edited by hand or rewritten from the Settings window ("Save as defaults").
+//!
+//! Missing keys take their defaults, and a key with an unusable value is
+//! logged and ignored, so a typo never stops the game from starting.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::presenter::Config;
+//!
+//! let config = Config::from_toml("grid_size = 5\n[keys]\nhint = \"F1\"")?;
+//! assert_eq!(config.grid_size, 5);
+//! assert_eq!(config.keys.hint, egui::Key::F1);
+//! assert_eq!(config.keys.new_game, Config::default().keys.new_game);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::controller::storage;
+use crate::controller::DEFAULT_AUTO_SOLVE_INTERVAL;
+use crate::model::puzzle_state::{MAX_SIZE, MIN_SIZE};
+use crate::model::{Difficulty, Direction, SolverKind};
+use crate::presenter::i18n::Locale;
+use crate::presenter::tile_renderer::{ColorMode, NumberStyle};
+use eframe::egui;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+/// File name of the configuration inside the config directory
+pub const CONFIG_FILE: &str = "slider.toml";
+
+/// Light or dark visuals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Follow the operating system
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// Every theme, in display order
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Dark, Theme::Light];
+
+    /// Returns the egui preference for this theme
+    pub fn preference(self) -> egui::ThemePreference {
+        match self {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Dark => egui::ThemePreference::Dark,
+            Theme::Light => egui::ThemePreference::Light,
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Theme::System => write!(f, "System"),
+            Theme::Dark => write!(f, "Dark"),
+            Theme::Light => write!(f, "Light"),
+        }
+    }
+}
+
+/// Keyboard shortcuts for the sliding puzzle
+///
+/// Slide keys move a tile into the empty cell in the named direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub slide_up: egui::Key,
+    pub slide_down: egui::Key,
+    pub slide_left: egui::Key,
+    pub slide_right: egui::Key,
+    pub hint: egui::Key,
+    pub new_game: egui::Key,
+    pub auto_solve: egui::Key,
+}
+
+impl KeyBindings {
+    /// Returns each slide key with the direction it slides
+    pub fn slides(&self) -> [(egui::Key, Direction); 4] {
+        [
+            (self.slide_up, Direction::Up),
+            (self.slide_down, Direction::Down),
+            (self.slide_left, Direction::Left),
+            (self.slide_right, Direction::Right),
+        ]
+    }
+
+    /// Returns every binding with its name in the config file
+    fn entries(&mut self) -> [(&'static str, &mut egui::Key); 7] {
+        [
+            ("slide_up", &mut self.slide_up),
+            ("slide_down", &mut self.slide_down),
+            ("slide_left", &mut self.slide_left),
+            ("slide_right", &mut self.slide_right),
+            ("hint", &mut self.hint),
+            ("new_game", &mut self.new_game),
+            ("auto_solve", &mut self.auto_solve),
+        ]
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            slide_up: egui::Key::ArrowUp,
+            slide_down: egui::Key::ArrowDown,
+            slide_left: egui::Key::ArrowLeft,
+            slide_right: egui::Key::ArrowRight,
+            hint: egui::Key::H,
+            new_game: egui::Key::N,
+            auto_solve: egui::Key::A,
+        }
+    }
+}
+
+/// Defaults a new session starts with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Board size when none is given on the command line
+    pub grid_size: usize,
+    pub difficulty: Difficulty,
+    /// None picks a solver suited to the grid size
+    pub solver: Option<SolverKind>,
+    pub language: Locale,
+    pub theme: Theme,
+    pub color_mode: ColorMode,
+    pub colorblind: bool,
+    pub number_style: NumberStyle,
+    pub slide_ms: u64, // 0 = instant
+    pub auto_solve_interval_ms: u64,
+    pub reduce_motion: bool,
+    pub keys: KeyBindings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            grid_size: 4,
+            difficulty: Difficulty::Medium,
+            solver: None,
+            language: Locale::default(),
+            theme: Theme::default(),
+            color_mode: ColorMode::default(),
+            colorblind: false,
+            number_style: NumberStyle::default(),
+            slide_ms: 200,
+            auto_solve_interval_ms: DEFAULT_AUTO_SOLVE_INTERVAL.as_millis() as u64,
+            reduce_motion: false,
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Parses a config file's contents
+    ///
+    /// Keys that are missing or hold unusable values keep their defaults; the
+    /// latter are logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the text is not valid TOML
+    pub fn from_toml(text: &str) -> io::Result<Self> {
+        let doc: DocumentMut = text
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut config = Self::default();
+
+        let root = Some(doc.as_item());
+        read(root, "grid_size", &mut config.grid_size, |item| {
+            item.as_integer()
+                .and_then(|n| usize::try_from(n).ok())
+                .filter(|n| (MIN_SIZE..=MAX_SIZE).contains(n))
+        });
+        read(root, "difficulty", &mut config.difficulty, parse_difficulty);
+        read(root, "solver", &mut config.solver, |item| parse_solver(item.as_str()?));
+
+        let appearance = doc.get("appearance");
+        read(appearance, "language", &mut config.language, |item| Locale::from_code(item.as_str()?));
+        read(appearance, "theme", &mut config.theme, |item| {
+            let name = item.as_str()?;
+            Theme::ALL.into_iter().find(|theme| theme_name(*theme) == name)
+        });
+        read(appearance, "color_mode", &mut config.color_mode, |item| {
+            let name = item.as_str()?;
+            ColorMode::ALL.into_iter().find(|mode| color_mode_name(*mode) == name)
+        });
+        read(appearance, "colorblind", &mut config.colorblind, Item::as_bool);
+        read(appearance, "number_style", &mut config.number_style, |item| {
+            let name = item.as_str()?;
+            NumberStyle::ALL.into_iter().find(|style| number_style_name(*style) == name)
+        });
+
+        let animation = doc.get("animation");
+        read(animation, "slide_ms", &mut config.slide_ms, |item| {
+            item.as_integer().and_then(|n| u64::try_from(n).ok())
+        });
+        read(animation, "auto_solve_interval_ms", &mut config.auto_solve_interval_ms, |item| {
+            item.as_integer().and_then(|n| u64::try_from(n).ok())
+        });
+        read(animation, "reduce_motion", &mut config.reduce_motion, Item::as_bool);
+
+        let keys = doc.get("keys");
+        for (name, key) in config.keys.entries() {
+            read(keys, name, key, |item| egui::Key::from_name(item.as_str()?));
+        }
+        Ok(config)
+    }
+
+    /// Formats the config as a commented TOML file
+    pub fn to_toml(&self) -> String {
+        let solver = self.solver.map_or("auto", solver_name);
+        let mut keys = self.keys;
+        let bindings: String = keys
+            .entries()
+            .iter()
+            .map(|(name, key)| format!("{} = \"{}\"\n", name, key.name()))
+            .collect();
+
+        format!(
+            "# Slider configuration, read at startup.\n\
+             # Settings > \"Save as defaults\" rewrites this file.\n\
+             \n\
+             # Board size ({min}-{max}) used when none is given on the command line\n\
+             grid_size = {grid_size}\n\
+             # \"easy\", \"medium\", \"hard\", \"extra-hard\", or a minimum entropy\n\
+             difficulty = {difficulty}\n\
+             # \"auto\", \"astar\", \"idastar\", \"greedy\" or \"strategic\"\n\
+             solver = \"{solver}\"\n\
+             \n\
+             [appearance]\n\
+             # \"en\" (English), \"es\" (Spanish) or \"de\" (German)\n\
+             language = \"{language}\"\n\
+             # \"system\", \"dark\" or \"light\"\n\
+             theme = \"{theme}\"\n\
+             # \"distance\", \"row\", \"band\" or \"off\"\n\
+             color_mode = \"{color_mode}\"\n\
+             colorblind = {colorblind}\n\
+             # \"decimal\", \"zero-based\", \"hex\", \"roman\" or \"pips\"\n\
+             number_style = \"{number_style}\"\n\
+             \n\
+             [animation]\n\
+             slide_ms = {slide_ms}\n\
+             auto_solve_interval_ms = {interval}\n\
+             reduce_motion = {reduce_motion}\n\
+             \n\
+             [keys]\n\
+             # egui key names, such as \"Up\", \"H\", \"F1\" or \"Space\"\n\
+             {bindings}",
+            min = MIN_SIZE,
+            max = MAX_SIZE,
+            grid_size = self.grid_size,
+            difficulty = difficulty_value(self.difficulty),
+            solver = solver,
+            language = self.language.code(),
+            theme = theme_name(self.theme),
+            color_mode = color_mode_name(self.color_mode),
+            colorblind = self.colorblind,
+            number_style = number_style_name(self.number_style),
+            slide_ms = self.slide_ms,
+            interval = self.auto_solve_interval_ms,
+            reduce_motion = self.reduce_motion,
+            bindings = bindings,
+        )
+    }
+
+    /// Loads a config file
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error, or `InvalidData` if the file is not valid TOML
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Writes the config file, creating parent directories as needed
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be written
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save_text(path, &self.to_toml())
+    }
+
+    /// Returns the config file in the user's config directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::config_file(CONFIG_FILE)
+    }
+
+    /// Loads the user's config file, creating it with defaults on first run
+    ///
+    /// Falls back to the defaults if the file cannot be read, leaving a broken
+    /// file in place for the player to fix.
+    pub fn load_default() -> Self {
+        let Some(path) = Self::default_path() else {
+            return Self::default();
+        };
+        match Self::load(&path) {
+            Ok(config) => config,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let config = Self::default();
+                if let Err(e) = config.save(&path) {
+                    log::warn!("Failed to create {}: {}", path.display(), e);
+                }
+                config
+            }
+            Err(e) => {
+                log::warn!("Ignoring {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Overwrites `value` with the parsed `table[key]`, logging values `parse` rejects
+fn read<T>(table: Option<&Item>, key: &str, value: &mut T, parse: impl FnOnce(&Item) -> Option<T>) {
+    let Some(item) = table.and_then(|table| table.get(key)) else {
+        return;
+    };
+    match parse(item) {
+        Some(parsed) => *value = parsed,
+        None => log::warn!("Ignoring invalid {} ({}) in {}", key, item.type_name(), CONFIG_FILE),
+    }
+}
+
+fn parse_difficulty(item: &Item) -> Option<Difficulty> {
+    if let Some(min_entropy) = item.as_integer() {
+        return u32::try_from(min_entropy).ok().map(|min_entropy| Difficulty::Custom { min_entropy });
+    }
+    match item.as_str()? {
+        "easy" => Some(Difficulty::Easy),
+        "medium" => Some(Difficulty::Medium),
+        "hard" => Some(Difficulty::Hard),
+        "extra-hard" => Some(Difficulty::ExtraHard),
+        _ => None,
+    }
+}
+
+/// Formats a difficulty as a TOML value: a quoted preset name or a bare entropy target
+fn difficulty_value(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Easy => "\"easy\"".to_string(),
+        Difficulty::Medium => "\"medium\"".to_string(),
+        Difficulty::Hard => "\"hard\"".to_string(),
+        Difficulty::ExtraHard => "\"extra-hard\"".to_string(),
+        Difficulty::Custom { min_entropy } => min_entropy.to_string(),
+    }
+}
+
+/// Returns Some(None) for "auto", which lets the dispatcher pick a solver for each board
+fn parse_solver(name: &str) -> Option<Option<SolverKind>> {
+    if name == "auto" {
+        return Some(None);
+    }
+    SolverKind::ALL.into_iter().find(|kind| solver_name(*kind) == name).map(Some)
+}
+
+fn solver_name(kind: SolverKind) -> &'static str {
+    match kind {
+        SolverKind::AStar => "astar",
+        SolverKind::IdaStar => "idastar",
+        SolverKind::Greedy => "greedy",
+        SolverKind::Strategic => "strategic",
+    }
+}
+
+fn theme_name(theme: Theme) -> &'static str {
+    match theme {
+        Theme::System => "system",
+        Theme::Dark => "dark",
+        Theme::Light => "light",
+    }
+}
+
+fn color_mode_name(mode: ColorMode) -> &'static str {
+    match mode {
+        ColorMode::Distance => "distance",
+        ColorMode::Row => "row",
+        ColorMode::Band => "band",
+        ColorMode::Off => "off",
+    }
+}
+
+fn number_style_name(style: NumberStyle) -> &'static str {
+    match style {
+        NumberStyle::Decimal => "decimal",
+        NumberStyle::ZeroBased => "zero-based",
+        NumberStyle::Hexadecimal => "hex",
+        NumberStyle::Roman => "roman",
+        NumberStyle::Pips => "pips",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let config = Config {
+            grid_size: 6,
+            difficulty: Difficulty::Custom { min_entropy: 90 },
+            solver: Some(SolverKind::IdaStar),
+            language: Locale::German,
+            theme: Theme::Light,
+            color_mode: ColorMode::Band,
+            colorblind: true,
+            number_style: NumberStyle::Roman,
+            slide_ms: 0,
+            auto_solve_interval_ms: 250,
+            reduce_motion: true,
+            keys: KeyBindings {
+                hint: egui::Key::F1,
+                slide_up: egui::Key::W,
+                ..KeyBindings::default()
+            },
+        };
+        assert_eq!(Config::from_toml(&config.to_toml()).unwrap(), config);
+        assert_eq!(Config::from_toml(&Config::default().to_toml()).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_missing_and_invalid_keys_keep_defaults() {
+        let text = "grid_size = 99\ndifficulty = \"hard\"\nsolver = \"quantum\"\n\
+                    [appearance]\nlanguage = \"es-MX\"\ntheme = \"dark\"\ncolorblind = \"yes\"\n\
+                    [keys]\nhint = \"NotAKey\"\nnew_game = \"Space\"";
+        let config = Config::from_toml(text).unwrap();
+        let defaults = Config::default();
+        assert_eq!(config.grid_size, defaults.grid_size);
+        assert_eq!(config.difficulty, Difficulty::Hard);
+        assert_eq!(config.solver, None);
+        assert_eq!(config.language, Locale::Spanish);
+        assert_eq!(config.theme, Theme::Dark);
+        assert!(!config.colorblind);
+        assert_eq!(config.keys.hint, defaults.keys.hint);
+        assert_eq!(config.keys.new_game, egui::Key::Space);
+        assert_eq!(config.slide_ms, defaults.slide_ms);
+    }
+
+    #[test]
+    fn test_malformed_file() {
+        let error = Config::from_toml("grid_size = [").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("slider-config-{}", std::process::id()))
+            .join(CONFIG_FILE);
+        let config = Config {
+            grid_size: 3,
+            ..Config::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), config);
+        let _ = fs::remove_file(&path);
+    }
+}
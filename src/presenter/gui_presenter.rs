@@ -1,9 +1,213 @@
-use crate::controller::GameController;
-use crate::model::{Difficulty, PerformanceMetrics, Position};
-use crate::presenter::tile_renderer::TileRenderer;
+use crate::controller::pattern_mining;
+use crate::controller::{
+    Achievement, Achievements, AssistTarget, BestTimes, GameController, GameMode, GameRecord, KlotskiController, MacroBook, MarathonRecords, MarathonRun,
+    MarathonSummary, SearchTrace, SessionSnapshot, SessionStore, Stats, VariantController,
+    DEFAULT_AUTO_SOLVE_INTERVAL, MAX_AUTO_SOLVE_SPEED, MAX_MACROS, MIN_AUTO_SOLVE_SPEED,
+};
+use crate::model::analysis::{GameAnalysis, MoveQuality};
+use crate::model::loopover::{MAX_LOOPOVER_SIZE, MIN_LOOPOVER_SIZE};
+use crate::io::{PuzzleFile, SLDR_EXTENSION};
+use crate::model::notation;
+use crate::model::puzzle_state::{MAX_SIZE, MIN_SIZE};
+use crate::model::{
+    Direction, Difficulty, EntropyKind, GoalSpec, FrameMetrics, KlotskiMove, LoopoverBoard, PerformanceMetrics, PerformanceTimer, Position, PuzzleError, PuzzleState, Region, RelativePattern,
+    SolveStep, SolverKind, TileLabels, FRAME_BUDGET_MICROS, MAX_LAYERS,
+};
+#[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+use crate::presenter::capture::{self, CaptureSource};
+#[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+use crate::presenter::replay_export::{self, ReplayFormat};
+#[cfg(all(feature = "board-export", not(target_arch = "wasm32")))]
+use crate::presenter::board_image::{self, BoardImageFormat};
+use crate::presenter::config::{Config, Theme};
+use crate::presenter::i18n::{self, t, tr, tr_value, warning, Locale};
+use crate::presenter::input::{self, GamepadSource, InputAction};
+use crate::presenter::klotski_renderer::KlotskiRenderer;
+use crate::presenter::loopover_renderer::LoopoverRenderer;
+use crate::presenter::tile_renderer::{heatmap_color, tile_size_for, ColorMode, NumberStyle, TileRenderer, TILE_GAP};
+use crate::presenter::tutorial::{Tutorial, TutorialEvent, TutorialStep};
 use eframe::egui;
 use egui::{CentralPanel, Context, Pos2, TopBottomPanel};
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How long a hinted tile keeps flashing
+const HINT_FLASH_MS: u128 = 1500;
+
+/// How long an achievement toast stays on screen
+const TOAST_MS: u128 = 4000;
+
+/// Largest selectable slide duration
+const MAX_SLIDE_MS: u64 = 1000;
+
+/// Largest selectable pause between auto-solve moves
+const MAX_AUTO_SOLVE_INTERVAL_MS: u64 = 3000;
+
+/// Time between session snapshots written for crash recovery
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Random rotations per cell when a Loopover board is scrambled
+const LOOPOVER_SCRAMBLE_PER_CELL: usize = 2;
+
+/// Wheel or trackpad travel (in points) that slides one tile
+const SCROLL_NOTCH: f32 = 40.0;
+
+/// egui's default for its own widget animations (collapsing headers, scrolling)
+const EGUI_ANIMATION_TIME: f32 = 1.0 / 12.0;
+
+/// Shortcut keys playing the saved macros, in order
+const MACRO_KEYS: [egui::Key; MAX_MACROS] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// Curve mapping animation time to distance travelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Easing {
+    Linear,
+    /// Fast start with smooth deceleration
+    #[default]
+    EaseOutCubic,
+    /// Smooth start and stop
+    EaseInOutCubic,
+}
+
+impl Easing {
+    const ALL: [Easing; 3] = [Easing::Linear, Easing::EaseOutCubic, Easing::EaseInOutCubic];
+
+    /// Maps linear progress `t` in 0..=1 to eased progress
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Easing::Linear => f.write_str(t!("Linear")),
+            Easing::EaseOutCubic => f.write_str(t!("Ease out")),
+            Easing::EaseInOutCubic => f.write_str(t!("Ease in-out")),
+        }
+    }
+}
+
+/// Kind of label chosen in Settings; text labels also need the typed text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum LabelStyle {
+    #[default]
+    Numbers,
+    Letters,
+    /// A phrase spelt one letter per tile, or comma-separated labels
+    Text,
+    Blank,
+}
+
+impl LabelStyle {
+    const ALL: [LabelStyle; 4] = [LabelStyle::Numbers, LabelStyle::Letters, LabelStyle::Text, LabelStyle::Blank];
+
+    /// Builds the labels, reading `text` as comma-separated labels if it has a comma
+    fn labels(self, text: &str) -> TileLabels {
+        match self {
+            LabelStyle::Numbers => TileLabels::Numbers,
+            LabelStyle::Letters => TileLabels::Letters,
+            LabelStyle::Text if text.contains(',') => {
+                TileLabels::Custom(text.split(',').map(|label| label.trim().to_string()).collect())
+            }
+            LabelStyle::Text => TileLabels::Word(text.to_string()),
+            LabelStyle::Blank => TileLabels::Blank,
+        }
+    }
+}
+
+impl std::fmt::Display for LabelStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelStyle::Numbers => f.write_str(t!("Numbers")),
+            LabelStyle::Letters => f.write_str(t!("Letters")),
+            LabelStyle::Text => f.write_str(t!("Word or custom")),
+            LabelStyle::Blank => f.write_str(t!("Blank")),
+        }
+    }
+}
+
+/// A message shown for a few seconds in the bottom-right corner
+enum Toast {
+    Achievement(Achievement),
+    Error(String), // A dropped file that could not be used, for instance
+}
+
+/// Timing of tile slides and auto-solve playback, shared by all tabs
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AnimationSettings {
+    duration_ms: u64, // 0 = instant
+    easing: Easing,
+    auto_solve_interval_ms: u64,
+    /// Moves apply instantly and highlights hold steady instead of pulsing
+    ///
+    /// egui 0.30 does not report the OS reduced-motion preference, so this is
+    /// only set from Settings.
+    reduce_motion: bool,
+}
+
+impl AnimationSettings {
+    /// Returns the slide duration actually used
+    fn slide_ms(&self) -> u64 {
+        if self.reduce_motion {
+            0
+        } else {
+            self.duration_ms
+        }
+    }
+
+    /// Returns a highlight intensity pulsing with `phase`, or a steady one under reduced motion
+    fn pulse(&self, phase: f32) -> f32 {
+        if self.reduce_motion {
+            1.0
+        } else {
+            0.5 + 0.5 * phase.cos()
+        }
+    }
+}
+
+impl AnimationSettings {
+    /// Returns the timing from `slider.toml`, with the default easing
+    fn from_config(config: &Config) -> Self {
+        Self {
+            duration_ms: config.slide_ms,
+            auto_solve_interval_ms: config.auto_solve_interval_ms,
+            reduce_motion: config.reduce_motion,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            duration_ms: 200,
+            easing: Easing::default(),
+            auto_solve_interval_ms: DEFAULT_AUTO_SOLVE_INTERVAL.as_millis() as u64,
+            reduce_motion: false,
+        }
+    }
+}
 
 /// Animation state for a sliding tile
 #[derive(Debug, Clone)]
@@ -13,20 +217,35 @@ struct TileAnimation {
     to_pos: Position,
     start_time: Instant,
     duration_ms: u64,
+    easing: Easing,
+    applied: bool, // The controller already made the move; only the slide is shown
 }
 
 impl TileAnimation {
-    fn new(tile_pos: Position, from_pos: Position, to_pos: Position, duration_ms: u64) -> Self {
+    fn new(tile_pos: Position, from_pos: Position, to_pos: Position, settings: &AnimationSettings) -> Self {
         Self {
             tile_pos,
             from_pos,
             to_pos,
             start_time: Instant::now(),
-            duration_ms,
+            duration_ms: settings.slide_ms(),
+            easing: settings.easing,
+            applied: false,
+        }
+    }
+
+    /// Slides a tile that the controller has already moved from `from_pos` to `to_pos`
+    fn replay(from_pos: Position, to_pos: Position, settings: &AnimationSettings) -> Self {
+        Self {
+            applied: true,
+            ..Self::new(to_pos, from_pos, to_pos, settings)
         }
     }
 
     fn progress(&self) -> f32 {
+        if self.duration_ms == 0 {
+            return 1.0; // Instant moves
+        }
         let elapsed = self.start_time.elapsed().as_millis() as f32;
         let duration = self.duration_ms as f32;
         (elapsed / duration).min(1.0)
@@ -37,9 +256,7 @@ impl TileAnimation {
     }
 
     fn current_pos(&self) -> (f32, f32) {
-        let t = self.progress();
-        // Ease-out cubic for smooth deceleration
-        let t = 1.0 - (1.0 - t).powi(3);
+        let t = self.easing.apply(self.progress());
 
         let (from_row, from_col) = self.from_pos;
         let (to_row, to_col) = self.to_pos;
@@ -51,54 +268,378 @@ impl TileAnimation {
     }
 }
 
-/// Main GUI presenter using egui
-pub struct GuiPresenter {
+/// One open puzzle: its controller plus the per-board UI state
+struct GameTab {
     controller: GameController,
     renderer: TileRenderer,
     difficulty: Difficulty,
-    show_performance: bool,
+    custom_entropy: u32, // Target for Difficulty::Custom, kept while other presets are picked
+    layers: usize,       // Boards stacked in the next new game
+    adaptive: bool,      // New games use the adaptive target instead of `difficulty`
     animation: Option<TileAnimation>,
     animation_queue: Vec<Position>,  // Queue of moves to animate in sequence
     status_message: Option<String>,  // Status message for user feedback
+    hint: Option<(Position, Instant)>, // Suggested tile and when the hint was requested
+    victory: Option<GameRecord>,       // Just-finished game shown in the victory screen
+    scroll: egui::Vec2,                // Wheel travel not yet turned into a slide
+    line_target: Option<Position>,     // Tile a gamepad chain move would slide, stepped with the shoulder buttons
+    marathon: Option<MarathonRun>,     // Run that moves to a harder board after each solve
 }
 
-impl GuiPresenter {
-    pub fn new(grid_size: usize) -> Result<Self, crate::model::PuzzleError> {
-        // Dynamic tile sizing based on grid size to fit screen
-        // Target total grid size ~600px (leaves room for UI controls)
-        let target_grid_size = 600.0;
-        let gap = 5.0;
-
-        // Calculate tile size: (target - gaps) / grid_size
-        // Number of gaps = grid_size - 1
-        let tile_size = (target_grid_size - (grid_size as f32 - 1.0) * gap) / grid_size as f32;
+/// Button pressed in the victory screen
+enum VictoryAction {
+    Close,
+    NewGame,
+    SameDifficulty,
+    Share,
+    Analyze,
+    #[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+    Export(ReplayFormat),
+}
 
-        // Clamp to reasonable min/max for usability
-        let tile_size = tile_size.max(30.0).min(100.0);
+impl GameTab {
+    fn new(grid_size: usize, best_times: BestTimes, stats: Stats, achievements: Achievements) -> Result<Self, PuzzleError> {
+        let mut controller = GameController::new(grid_size)?;
+        controller.set_best_times(best_times);
+        controller.set_stats(stats);
+        controller.set_achievements(achievements);
+        controller.set_pattern_catalog(pattern_mining::load_default_catalog());
 
         Ok(Self {
-            controller: GameController::new(grid_size)?,
-            renderer: TileRenderer::new(tile_size, gap),
+            controller,
+            renderer: renderer_for(grid_size),
             difficulty: Difficulty::Medium,
-            show_performance: false,
+            custom_entropy: Difficulty::Hard.min_entropy(grid_size),
+            layers: 1,
+            adaptive: false,
             animation: None,
             animation_queue: Vec::new(),
             status_message: None,
+            hint: None,
+            line_target: None,
+            victory: None,
+            scroll: egui::Vec2::ZERO,
+            marathon: None,
         })
     }
-}
 
-impl eframe::App for GuiPresenter {
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-        // Poll solver state (non-blocking)
+    /// Applies the default difficulty and solver from `slider.toml`
+    fn apply_config(&mut self, config: &Config) {
+        self.difficulty = config.difficulty;
+        if let Difficulty::Custom { min_entropy } = config.difficulty {
+            self.custom_entropy = min_entropy;
+        }
+        if let Some(solver) = config.solver {
+            self.controller.set_solver(solver);
+        }
+    }
+
+    /// Replaces the puzzle with a solved board of another size
+    ///
+    /// Rebuilds the controller and renderer, so move history, cached metrics and the
+    /// solver choice start over; best times, statistics, achievements and the
+    /// entropy calculator (where it supports the new size) carry across.
+    fn resize(&mut self, grid_size: usize) -> Result<(), PuzzleError> {
+        let best_times = self.controller.best_times().clone();
+        let stats = self.controller.stats().clone();
+        let achievements = self.controller.achievements().clone();
+        let mut resized = Self::new(grid_size, best_times, stats, achievements)?;
+        resized.difficulty = match self.difficulty {
+            Difficulty::Custom { .. } => Difficulty::Custom {
+                min_entropy: resized.custom_entropy,
+            },
+            preset => preset,
+        };
+        resized.layers = self.layers;
+        resized.adaptive = self.adaptive;
+        resized.renderer.set_picture(self.renderer.picture().cloned());
+        resized.controller.set_goal(self.controller.goal());
+        if let Some(kind) = self.controller.entropy_kind().filter(|kind| kind.supports(grid_size)) {
+            if let Err(e) = resized.controller.set_entropy_kind(kind) {
+                log::warn!("Cannot keep {} on a {1}×{1} board: {2}", kind, grid_size, e);
+            }
+        }
+        // Labels that only fit the old size (such as a word) fall back to numbers
+        if resized.controller.set_tile_labels(self.controller.tile_labels().clone()).is_err() {
+            log::info!("Tile labels do not fit a {0}×{0} board; showing numbers", grid_size);
+        }
+
+        self.controller.stop_auto_solve();
+        *self = resized;
+        Ok(())
+    }
+
+    /// Replaces the board with `state`, which may be of another size
+    fn load(&mut self, state: PuzzleState) -> Result<(), PuzzleError> {
+        let size = state.size();
+        self.controller.load_state(state)?;
+        self.renderer = renderer_for(size);
+        self.animation = None;
+        self.animation_queue.clear();
+        self.hint = None;
+        self.status_message = None;
+        Ok(())
+    }
+
+    /// Resumes a game saved before a crash, resizing the board to fit it first
+    fn restore(&mut self, snapshot: &SessionSnapshot) -> Result<(), PuzzleError> {
+        if snapshot.grid_size() != self.controller.state().size() {
+            self.resize(snapshot.grid_size())?;
+        }
+        self.controller.restore(snapshot)?;
+        self.hint = None;
+        self.victory = None;
+        self.status_message = Some(t!("Restored the game from the last session").to_string());
+        Ok(())
+    }
+
+    /// Returns the difficulty "New Game" uses: the adaptive target when enabled
+    fn chosen_difficulty(&self) -> Difficulty {
+        if self.adaptive {
+            self.controller.adaptive_difficulty().difficulty()
+        } else {
+            self.difficulty
+        }
+    }
+
+    /// Starts a new game with the chosen number of layers
+    fn start_game(&mut self, difficulty: Difficulty) {
+        if self.layers > 1 {
+            if let Err(e) = self.controller.new_layered_game(difficulty, self.layers) {
+                self.status_message = Some(warning(e));
+            }
+        } else {
+            self.controller.new_game_in_background(difficulty);
+        }
+    }
+
+    /// Starts an endless marathon from its first stage
+    fn start_marathon(&mut self) {
+        self.marathon = Some(MarathonRun::new());
+        self.victory = None;
+        self.play_marathon_stage();
+    }
+
+    /// Shuffles a board for the marathon's current stage, resizing the tab if the stage is larger
+    ///
+    /// Stages are single boards in free play, whatever the toolbar is set to.
+    fn play_marathon_stage(&mut self) {
+        let Some(stage) = self.marathon.as_ref().map(MarathonRun::stage) else {
+            return;
+        };
+        if stage.grid_size != self.controller.state().size() {
+            // Resizing rebuilds the tab, so the run is carried across by hand
+            let run = self.marathon.take();
+            if let Err(e) = self.resize(stage.grid_size) {
+                self.status_message = Some(warning(e));
+            }
+            self.marathon = run;
+        }
+        self.hint = None;
+        self.controller.set_game_mode(GameMode::Free);
+        self.controller.new_game_in_background(stage.difficulty);
+    }
+
+    /// Stops the marathon in progress, returning its summary
+    fn end_marathon(&mut self) -> Option<MarathonSummary> {
+        self.marathon.take().map(|run| run.finish())
+    }
+
+    /// Cuts a captured picture into the tiles and scrambles the board behind it
+    #[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+    fn scramble_capture(&mut self, ctx: &Context, source: CaptureSource) {
+        match capture::capture(source) {
+            Ok(picture) => self.scramble_picture(ctx, picture),
+            Err(e) => self.status_message = Some(warning(e)),
+        }
+    }
+
+    /// Cuts `picture` into the tiles and scrambles the board behind it
+    #[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+    fn scramble_picture(&mut self, ctx: &Context, picture: egui::ColorImage) {
+        let texture = ctx.load_texture("tile_picture", picture, egui::TextureOptions::LINEAR);
+        self.renderer.set_picture(Some(texture));
+        self.hint = None;
+        self.start_game(self.chosen_difficulty());
+    }
+
+    /// Writes the solved game's replay to the data directory, reporting where in the status line
+    #[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+    fn export_replay(&mut self, format: ReplayFormat) {
+        let Some((initial, slides)) = self.controller.replay() else {
+            self.status_message = Some(warning(t!("Only a solved game with every move recorded can be exported")));
+            return;
+        };
+        let Some(path) = replay_export::replay_file(format, initial.size()) else {
+            self.status_message = Some(warning(t!("No data directory to save the replay in")));
+            return;
+        };
+        self.status_message = Some(match replay_export::export_replay(&self.renderer, initial, &slides, format, &path) {
+            Ok(()) => t!("Replay saved to {}", path.display()),
+            Err(e) => warning(e),
+        });
+    }
+
+    /// Writes the current board to the data directory as an image, with the
+    /// solution heatmap if `heatmap` is set, reporting where in the status line
+    #[cfg(all(feature = "board-export", not(target_arch = "wasm32")))]
+    fn export_board_image(&mut self, format: BoardImageFormat, heatmap: bool) {
+        let tints: Vec<(Position, egui::Color32)> = match heatmap.then(|| self.controller.solution_order()).flatten() {
+            Some(order) => {
+                let steps = order.values().max().map_or(0, |last| last + 1);
+                order.iter().map(|(&pos, &step)| (pos, heatmap_color(step, steps))).collect()
+            }
+            None => Vec::new(),
+        };
+        let state = self.controller.state();
+        let Some(path) = board_image::board_image_file(format, state.size()) else {
+            self.status_message = Some(warning(t!("No data directory to save the image in")));
+            return;
+        };
+        self.status_message = Some(match board_image::export_board_image(&self.renderer, state, &tints, format, &path) {
+            Ok(()) => t!("Board image saved to {}", path.display()),
+            Err(e) => warning(t!("Could not write the image: {}", e)),
+        });
+    }
+
+    /// Starts animating a sequence of immediate moves from `handle_click` or `play_macro`
+    fn animate_moves(&mut self, moves: &[Position], settings: &AnimationSettings) {
+        let Some((&first, rest)) = moves.split_first() else {
+            return;
+        };
+        let old_empty = self.controller.state().empty_position();
+        self.animation = Some(TileAnimation::new(first, first, old_empty, settings));
+        self.animation_queue = rest.to_vec();
+    }
+
+    /// Adds wheel travel over `pos`, returning the moves of a slide once a notch builds up
+    ///
+    /// The dominant axis decides: vertical travel slides the empty cell's column and
+    /// horizontal travel its row, toward the way the content scrolls.
+    fn scroll_over(&mut self, pos: Position, delta: egui::Vec2) -> Option<Vec<Position>> {
+        self.scroll += delta;
+        let egui::Vec2 { x, y } = self.scroll;
+        if x.abs().max(y.abs()) < SCROLL_NOTCH {
+            return None;
+        }
+        self.scroll = egui::Vec2::ZERO;
+        let direction = match (y.abs() >= x.abs(), y < 0.0, x < 0.0) {
+            (true, true, _) => Direction::Down,
+            (true, false, _) => Direction::Up,
+            (false, _, true) => Direction::Right,
+            (false, _, false) => Direction::Left,
+        };
+        let tile = self.controller.tile_for_scroll(pos, direction)?;
+        self.controller.handle_click(tile)
+    }
+
+    /// Plays a saved macro from the current empty cell, reporting why if it cannot
+    fn play_macro(&mut self, pattern: &RelativePattern, settings: &AnimationSettings) {
+        self.hint = None;
+        match self.controller.play_macro(pattern) {
+            Some(moves) => self.animate_moves(&moves, settings),
+            None => self.status_message = Some(warning(t!("'{}' does not fit here", pattern.name()))),
+        }
+    }
+
+    /// Returns true while tiles are sliding or queued to slide
+    fn is_animating(&self) -> bool {
+        self.animation.is_some() || !self.animation_queue.is_empty()
+    }
+
+    /// Returns the slides in progress or queued, counting the one on screen
+    fn animation_depth(&self) -> usize {
+        usize::from(self.animation.is_some()) + self.animation_queue.len()
+    }
+
+    /// Describes what the tab's background tasks are doing, for the profiler
+    fn background_status(&self) -> String {
+        let controller = &self.controller;
+        let mut tasks = Vec::new();
+        if controller.is_solver_computing() {
+            tasks.push(match controller.solver_progress() {
+                Some(progress) => t!(
+                    "solving, {} nodes in {}s",
+                    progress.nodes_explored,
+                    format!("{:.1}", progress.elapsed.as_secs_f32())
+                ),
+                None => t!("solving").to_string(),
+            });
+        }
+        if controller.is_shuffling() {
+            tasks.push(t!("shuffling").to_string());
+        }
+        if controller.is_measuring() {
+            tasks.push(t!("measuring").to_string());
+        }
+        if controller.is_analyzing() {
+            tasks.push(t!("analyzing").to_string());
+        }
+        if tasks.is_empty() {
+            t!("idle").to_string()
+        } else {
+            tasks.join(", ")
+        }
+    }
+
+    /// Label shown in the tab bar
+    fn title(&self) -> String {
+        let size = self.controller.state().size();
+        if self.controller.is_solved() && !self.controller.is_shuffling() {
+            format!("{}×{} ✓", size, size)
+        } else {
+            format!("{}×{}", size, size)
+        }
+    }
+
+    /// Returns true while the tab needs continuous repaints
+    fn is_busy(&self) -> bool {
+        self.controller.is_auto_solving()
+            || self.is_animating()
+            || self.controller.is_solver_computing()
+            || self.controller.is_shuffling()
+            || self.controller.is_analyzing()
+            || self.controller.is_measuring()
+    }
+
+    /// Plays the rest of the auto-solve at once, dropping the slide in progress
+    fn skip_auto_solve(&mut self) {
+        // The sliding tile has not moved in the controller yet, so the skip plays it
+        self.animation = None;
+        self.animation_queue.clear();
+        let played = self.controller.skip_auto_solve_to_end();
+        self.status_message = Some(t!("Skipped {} moves", played));
+    }
+
+    /// Polls the background solver and advances animations
+    ///
+    /// Called every frame for every tab, so auto-solves keep playing in tabs that
+    /// are not being shown.
+    fn advance(&mut self, settings: &AnimationSettings) {
+        self.controller
+            .set_auto_solve_interval(Duration::from_millis(settings.auto_solve_interval_ms));
+
+        // Poll the background shuffle and solver (non-blocking)
+        self.controller.update_shuffle_state();
         if self.controller.update_solver_state() {
             // Solution ready, animation will start automatically
             self.status_message = None;
+        } else if let Some(e) = self.controller.take_auto_solve_error() {
+            // Say why, so a timeout reads differently from an unsolvable board
+            self.status_message = Some(warning(e));
+        }
+        self.controller.update_analysis();
+        if let Some(failure) = self.controller.update_challenge() {
+            self.animation = None;
+            self.animation_queue.clear();
+            self.status_message = Some(t!("✗ {} — challenge lost", tr_value(&failure)));
         }
 
         // Check if animation is complete
         if let Some(ref anim) = self.animation {
-            if anim.is_complete() {
+            if anim.is_complete() && anim.applied {
+                self.animation = None;
+            } else if anim.is_complete() {
                 // Animation done - NOW apply the move to puzzle state
                 let tile_pos = anim.tile_pos;
                 self.animation = None;
@@ -119,184 +660,2267 @@ impl eframe::App for GuiPresenter {
                         next_pos,
                         next_pos,
                         old_empty,
-                        200,
+                        settings,
                     ));
                 } else if !self.controller.is_auto_solving() {
                     // All animations done - complete the move sequence
                     self.controller.complete_move_sequence();
                 }
-            } else {
-                ctx.request_repaint(); // Continue animating
             }
         }
 
         // Check if auto-solve has a move ready (only if not currently animating)
         if self.animation.is_none() && self.animation_queue.is_empty() {
             if let Some(next_move) = self.controller.get_next_auto_solve_move() {
-                // Start animation for auto-solve move
+                // Start animation for auto-solve move, sped up with the playback
                 let old_empty = self.controller.state().empty_position();
-                self.animation = Some(TileAnimation::new(
-                    next_move,
-                    next_move,
-                    old_empty,
-                    200,
-                ));
+                let mut animation = TileAnimation::new(next_move, next_move, old_empty, settings);
+                animation.duration_ms = (animation.duration_ms as f32 / self.controller.auto_solve_speed()) as u64;
+                self.animation = Some(animation);
             }
         }
 
-        // Request repaint for smooth animation and background solver polling
-        if self.controller.is_auto_solving()
-            || self.animation.is_some()
-            || !self.animation_queue.is_empty()
-            || self.controller.is_solver_computing() {
-            ctx.request_repaint();
+        if let Some(game) = self.controller.take_finished_game() {
+            match self.marathon.as_mut() {
+                // A marathon skips the victory screen and goes straight to the next stage
+                Some(run) if !game.failed => {
+                    run.clear_stage(&game);
+                    let cleared = t!("Stage {} cleared: +{} points", run.stages_cleared(), run.last_points());
+                    self.play_marathon_stage();
+                    self.status_message.get_or_insert(cleared);
+                }
+                _ => self.victory = Some(game),
+            }
         }
+    }
 
-        // Top panel with controls
-        TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.heading("Slider Puzzle");
-
-                ui.separator();
+    /// Text copied by the victory screen's Share button
+    fn share_text(&self, game: &GameRecord) -> String {
+        let mut text = t!(
+            "I solved a {}×{} {} slider puzzle in {} with {} moves ({} tile moves)",
+            game.grid_size,
+            game.grid_size,
+            difficulty_name(game.difficulty),
+            format_clock(game.time()),
+            game.moves,
+            game.tile_moves
+        );
+        if let Some(optimal) = game.optimal_length {
+            text.push_str(&t!(", par {}", optimal));
+        }
+        if let Some(grade) = game.grade() {
+            text.push_str(&t!(", grade {}", grade));
+        }
+        if let Some(start) = self.controller.initial_state() {
+            text.push_str(&t!(". Try it: {}", start.to_share_code()));
+        }
+        text
+    }
+}
 
-                // Difficulty selection
-                ui.label("Difficulty:");
-                ui.radio_value(&mut self.difficulty, Difficulty::Easy, "Easy");
-                ui.radio_value(&mut self.difficulty, Difficulty::Medium, "Medium");
-                ui.radio_value(&mut self.difficulty, Difficulty::Hard, "Hard");
-                ui.radio_value(&mut self.difficulty, Difficulty::ExtraHard, "Extra Hard");
+/// One open Klotski puzzle
+struct KlotskiTab {
+    controller: KlotskiController,
+    renderer: KlotskiRenderer,
+    status_message: Option<String>,
+    hint: Option<(KlotskiMove, Instant)>, // Suggested move and when it was requested
+}
 
-                ui.separator();
+impl KlotskiTab {
+    fn new() -> Self {
+        Self {
+            controller: KlotskiController::classic(),
+            renderer: KlotskiRenderer::new(90.0, 5.0),
+            status_message: None,
+            hint: None,
+        }
+    }
 
-                // New game button
-                if ui.button("New Game").clicked() {
-                    self.controller.new_game(self.difficulty);
-                }
+    /// Slides the clicked block in the legal direction closest to where it was clicked
+    ///
+    /// `offset` runs from the block's center to the click, so clicking the right
+    /// half of a block that can go left or right moves it right.
+    fn click_block(&mut self, block: usize, offset: egui::Vec2) {
+        let best = self.controller.legal_directions(block).into_iter().max_by(|a, b| {
+            let score = |direction: Direction| {
+                let (dr, dc) = direction.delta();
+                offset.x * dc as f32 + offset.y * dr as f32
+            };
+            score(*a).total_cmp(&score(*b))
+        });
+        if let Some(direction) = best {
+            self.controller.move_block(block, direction);
+            self.hint = None;
+        }
+    }
 
-                // Reset button
-                if ui.button("Reset").clicked() {
-                    self.controller.reset();
-                }
+    /// Draws the Klotski toolbar and board
+    fn show(&mut self, ctx: &Context, settings: &AnimationSettings) {
+        if self.controller.is_timer_running() || self.hint.is_some() {
+            ctx.request_repaint();
+        }
 
+        TopBottomPanel::top("klotski_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("Klotski"));
                 ui.separator();
 
-                // Auto-solve button
-                if self.controller.is_auto_solving() {
-                    if ui.button("Stop Solve").clicked() {
-                        self.controller.stop_auto_solve();
-                        self.status_message = None;
-                    }
-                    if let Some((current, total)) = self.controller.auto_solve_progress() {
-                        ui.label(format!("{}/{}", current, total));
-                    }
-                } else if self.controller.is_solver_computing_for_autosolve() {
-                    // Solver running in background for auto-solve
-                    if ui.button("Cancel").clicked() {
-                        self.controller.stop_auto_solve();
-                        self.status_message = None;
-                    }
-                    ui.label("Computing solution...");
-                    ctx.request_repaint(); // Keep UI responsive
-                } else if ui.button("Auto Solve").clicked() {
-                    if !self.controller.start_auto_solve() {
-                        self.status_message = Some("⚠ Puzzle already solved or computation in progress".to_string());
-                    } else {
-                        self.status_message = Some("Computing solution in background...".to_string());
+                if ui.button(t!("Reset")).on_hover_text(t!("Return to the starting layout")).clicked() {
+                    self.controller.reset();
+                    self.hint = None;
+                    self.status_message = None;
+                }
+                if ui
+                    .add_enabled(!self.controller.is_solved(), egui::Button::new(t!("Hint")))
+                    .on_hover_text(t!("Flash the block to move next"))
+                    .clicked()
+                {
+                    match self.controller.hint() {
+                        Some(mv) => {
+                            self.hint = Some((mv, Instant::now()));
+                            self.status_message = Some(t!("Hint: move the highlighted block {}", tr_value(&mv.direction)));
+                        }
+                        None => self.status_message = Some(t!("No solution found from here").to_string()),
                     }
                 }
 
-                // Display status message if present
+                ui.separator();
+                ui.label(t!("Moves: {}", self.controller.move_count()));
+                ui.label(t!("Time: {}", format_clock(self.controller.elapsed_time())));
+
                 if let Some(ref msg) = self.status_message {
+                    ui.separator();
                     ui.label(msg);
                 }
+                if self.controller.is_solved() {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::GREEN, t!("✓ Solved!"));
+                }
+            });
+            ui.label(t!("Move the red block onto the outlined goal. Click a block on the side you want it to slide."));
+        });
 
-                ui.separator();
+        CentralPanel::default().show(ctx, |ui| {
+            let board = self.controller.board();
+            let board_size = self.renderer.board_size(board);
+            let available = ui.available_size();
+            let top_left = Pos2::new(
+                (available.x - board_size.x) / 2.0 + ui.min_rect().left(),
+                (available.y - board_size.y) / 2.0 + ui.min_rect().top(),
+            );
 
-                // Move counter with shuffle info
-                if let Some(shuffle_result) = self.controller.last_shuffle_result() {
-                    ui.label(format!(
-                        "Moves: {} [shuffle: {}]",
-                        self.controller.move_count(),
-                        shuffle_result.moves_made
-                    ));
-                } else {
-                    ui.label(format!("Moves: {}", self.controller.move_count()));
+            // Flash the hinted block until the hint expires
+            let highlight = match self.hint {
+                Some((mv, requested_at)) if requested_at.elapsed().as_millis() < HINT_FLASH_MS => {
+                    let phase = requested_at.elapsed().as_millis() as f32 / 250.0 * std::f32::consts::PI;
+                    Some((mv.block, settings.pulse(phase)))
                 }
+                _ => {
+                    self.hint = None;
+                    None
+                }
+            };
 
-                ui.separator();
+            if let Some((block, offset)) = self.renderer.render(ui, board, top_left, highlight) {
+                self.click_block(block, offset);
+            }
+        });
+    }
+}
 
-                // Entropy metrics display
-                let metrics = self.controller.all_entropy_metrics();
+/// One open Loopover puzzle, whose rows and columns rotate
+struct LoopoverTab {
+    controller: VariantController<LoopoverBoard>,
+    renderer: LoopoverRenderer,
+}
 
-                if self.show_performance {
-                    // Detailed view with performance metrics
-                    ui.label(format!("Manhattan: {}", metrics.manhattan_distance));
-                    ui.label(format!("Heuristic: {}", metrics.shortest_path_heuristic));
+impl LoopoverTab {
+    /// Opens a scrambled board of `size`
+    fn new(size: usize) -> Result<Self, PuzzleError> {
+        let mut controller = VariantController::new(LoopoverBoard::new(size)?);
+        controller.scramble(size * size * LOOPOVER_SCRAMBLE_PER_CELL, &mut rand::thread_rng());
+        // Cells fill about the same area as the sliding board
+        let cell_size = ((500.0 - (size as f32 - 1.0) * 5.0) / size as f32).clamp(40.0, 100.0);
+        Ok(Self {
+            controller,
+            renderer: LoopoverRenderer::new(cell_size, 5.0),
+        })
+    }
 
-                    if metrics.actual_solution_length < 999 {
-                        ui.label(format!(
-                            "Actual: {} (calc: {})",
-                            metrics.actual_solution_length,
-                            PerformanceMetrics::format_duration(metrics.performance.actual_time_micros)
-                        ));
-                    } else if metrics.performance.actual_time_micros > 0 {
-                        // Show solve time even if puzzle not yet solved
-                        ui.label(format!(
-                            "Actual: -- (last calc: {})",
-                            PerformanceMetrics::format_duration(metrics.performance.actual_time_micros)
-                        ));
-                    } else {
-                        ui.label("Actual: --");
-                    }
-                } else {
-                    // Compact view without timing
-                    ui.label(format!("Manhattan: {}", metrics.manhattan_distance));
-                    ui.label(format!("Heuristic: {}", metrics.shortest_path_heuristic));
+    /// Label shown in the tab bar
+    fn title(&self) -> String {
+        let size = self.controller.board().size();
+        if self.controller.is_solved() {
+            t!("Loopover {}×{} ✓", size, size)
+        } else {
+            t!("Loopover {}×{}", size, size)
+        }
+    }
 
-                    if metrics.actual_solution_length < 999 {
-                        ui.label(format!("Actual: {}", metrics.actual_solution_length));
-                    } else {
-                        ui.label("Actual: --");
-                    }
-                }
+    /// Draws the Loopover toolbar and board
+    fn show(&mut self, ctx: &Context) {
+        if self.controller.is_timer_running() {
+            ctx.request_repaint();
+        }
 
+        TopBottomPanel::top("loopover_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("Loopover"));
                 ui.separator();
 
-                // Performance toggle
-                ui.checkbox(&mut self.show_performance, "Show Performance");
+                if ui.button(t!("Scramble")).on_hover_text(t!("Start a new game on a freshly scrambled board")).clicked() {
+                    let size = self.controller.board().size();
+                    self.controller
+                        .scramble(size * size * LOOPOVER_SCRAMBLE_PER_CELL, &mut rand::thread_rng());
+                }
+                if ui.button(t!("Reset")).on_hover_text(t!("Return to the scrambled layout")).clicked() {
+                    self.controller.reset();
+                }
+
+                ui.separator();
+                ui.label(t!("Moves: {}", self.controller.move_count()));
+                ui.label(t!("Time: {}", format_clock(self.controller.elapsed_time())));
 
-                // Solved indicator
                 if self.controller.is_solved() {
                     ui.separator();
-                    ui.colored_label(egui::Color32::GREEN, "SOLVED!");
+                    ui.colored_label(egui::Color32::GREEN, t!("✓ Solved!"));
                 }
             });
+            ui.label(t!("Drag a row sideways or a column up or down; tiles pushed off one edge come back on the other. Sort the tiles 1, 2, 3… row by row."));
         });
 
-        // Central panel with puzzle grid
         CentralPanel::default().show(ctx, |ui| {
-            let grid_size = self.renderer.grid_size(self.controller.state().size());
-
-            // Center the grid
+            let board_size = self.renderer.board_size(self.controller.board());
             let available = ui.available_size();
             let top_left = Pos2::new(
-                (available.x - grid_size.x) / 2.0 + ui.min_rect().left(),
-                (available.y - grid_size.y) / 2.0 + ui.min_rect().top(),
+                (available.x - board_size.x) / 2.0 + ui.min_rect().left(),
+                (available.y - board_size.y) / 2.0 + ui.min_rect().top(),
             );
+            for mv in self.renderer.render(ui, self.controller.board(), top_left) {
+                self.controller.play(mv);
+            }
+        });
+    }
+}
 
-            // Collect clicked position before modifying state
+/// Keys for each racer: up, left, down, right
+const RACE_KEYS: [[egui::Key; 4]; 2] = [
+    [egui::Key::W, egui::Key::A, egui::Key::S, egui::Key::D],
+    [egui::Key::ArrowUp, egui::Key::ArrowLeft, egui::Key::ArrowDown, egui::Key::ArrowRight],
+];
+
+/// Directions matching the order of `RACE_KEYS`
+const RACE_DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+
+/// One side of a race
+struct Racer {
+    controller: GameController,
+    finish: Option<Duration>, // Race time when this board was solved
+}
+
+/// Two players racing on identical boards, one at each side of the keyboard
+///
+/// Race games use in-memory records, so they never touch the saved best times
+/// or statistics.
+struct RaceTab {
+    racers: [Racer; 2],
+    renderer: TileRenderer,
+    difficulty: Difficulty,
+    started_at: Option<Instant>, // When the current race began
+}
+
+impl RaceTab {
+    fn new(grid_size: usize) -> Result<Self, PuzzleError> {
+        let racer = || -> Result<Racer, PuzzleError> {
+            Ok(Racer {
+                controller: GameController::new(grid_size)?,
+                finish: None,
+            })
+        };
+        // Half the window each
+        let tile_size = tile_size_for(360.0, grid_size);
+
+        Ok(Self {
+            racers: [racer()?, racer()?],
+            renderer: TileRenderer::new(tile_size, TILE_GAP, grid_size),
+            difficulty: Difficulty::Medium,
+            started_at: None,
+        })
+    }
+
+    /// Shuffles both boards from one random seed and starts the race clock
+    fn start(&mut self) {
+        let seed = rand::random();
+        for racer in &mut self.racers {
+            racer.controller.new_seeded_game(self.difficulty, seed);
+            racer.finish = None;
+        }
+        self.started_at = Some(Instant::now());
+    }
+
+    /// Returns the index of the first player to finish
+    fn winner(&self) -> Option<usize> {
+        (0..self.racers.len())
+            .filter_map(|i| self.racers[i].finish.map(|time| (time, i)))
+            .min()
+            .map(|(_, i)| i)
+    }
+
+    fn title(&self) -> String {
+        let size = self.racers[0].controller.state().size();
+        t!("Race {}×{}", size, size)
+    }
+
+    /// Applies both players' key presses, moving instantly so neither waits on animation
+    fn handle_keys(&mut self, ctx: &Context) {
+        let Some(started_at) = self.started_at else {
+            return;
+        };
+        for (racer, keys) in self.racers.iter_mut().zip(RACE_KEYS) {
+            if racer.finish.is_some() {
+                continue;
+            }
+            for (key, direction) in keys.into_iter().zip(RACE_DIRECTIONS) {
+                if !ctx.input(|i| i.key_pressed(key)) {
+                    continue;
+                }
+                let controller = &mut racer.controller;
+                let Some(moves) = controller.tile_for_slide(direction).and_then(|pos| controller.handle_click(pos))
+                else {
+                    continue;
+                };
+                for pos in moves {
+                    controller.apply_move(pos);
+                }
+                controller.complete_move_sequence();
+                if controller.is_solved() {
+                    racer.finish = Some(started_at.elapsed());
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Draws the race toolbar and both boards
+    fn show(&mut self, ctx: &Context, color_mode: ColorMode, colorblind: bool, number_style: NumberStyle) {
+        self.handle_keys(ctx);
+        let racing = self.started_at.is_some() && self.winner().is_none();
+        if racing {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        TopBottomPanel::top("race_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("Race"));
+                ui.separator();
+
+                ui.label(t!("Difficulty:"));
+                ui.radio_value(&mut self.difficulty, Difficulty::Easy, t!("Easy"));
+                ui.radio_value(&mut self.difficulty, Difficulty::Medium, t!("Medium"));
+                ui.radio_value(&mut self.difficulty, Difficulty::Hard, t!("Hard"));
+
+                if ui
+                    .button(if self.started_at.is_some() { t!("Rematch") } else { t!("Start Race") })
+                    .on_hover_text(t!("Shuffle the same board for both players and start the clock"))
+                    .clicked()
+                {
+                    self.start();
+                }
+
+                if let Some(started_at) = self.started_at {
+                    ui.separator();
+                    let time = self
+                        .winner()
+                        .and_then(|i| self.racers[i].finish)
+                        .unwrap_or_else(|| started_at.elapsed());
+                    ui.label(t!("Time: {}", format_clock(time)));
+                }
+                if let Some(winner) = self.winner() {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::GOLD, t!("🏆 Player {} wins!", winner + 1));
+                }
+            });
+            ui.label(t!("Player 1 slides tiles with W A S D, player 2 with the arrow keys."));
+        });
+
+        self.renderer.set_color_mode(color_mode);
+        self.renderer.set_colorblind(colorblind);
+        self.renderer.set_number_style(number_style);
+        CentralPanel::default().show(ctx, |ui| {
+            ui.columns(2, |columns| {
+                for (i, (ui, racer)) in columns.iter_mut().zip(&self.racers).enumerate() {
+                    let keys = if i == 0 { "W A S D" } else { t!("arrows") };
+                    ui.heading(t!("Player {} ({})", i + 1, keys));
+                    let status = match racer.finish {
+                        Some(time) => t!("✓ Solved in {}", format_clock(time)),
+                        None => t!("Moves: {}", racer.controller.move_count()),
+                    };
+                    ui.label(status);
+
+                    let state = racer.controller.state();
+                    let (rect, _) = ui.allocate_exact_size(self.renderer.grid_size(state.size()), egui::Sense::hover());
+                    self.renderer.render_empty(ui, state.empty_position(), rect.min);
+                    for (pos, tile) in state.tiles() {
+                        self.renderer.render_tile(ui, tile, pos, rect.min);
+                    }
+                }
+            });
+        });
+    }
+}
+
+/// An open tab: a sliding-tile game, a Klotski or Loopover game, or a two-player race
+#[allow(clippy::large_enum_variant)] // Only a handful of tabs are ever open
+enum Tab {
+    Sliding(GameTab),
+    Klotski(KlotskiTab),
+    Loopover(LoopoverTab),
+    Race(RaceTab),
+}
+
+impl Tab {
+    fn sliding(&self) -> Option<&GameTab> {
+        match self {
+            Tab::Sliding(tab) => Some(tab),
+            Tab::Klotski(_) | Tab::Loopover(_) | Tab::Race(_) => None,
+        }
+    }
+
+    fn sliding_mut(&mut self) -> Option<&mut GameTab> {
+        match self {
+            Tab::Sliding(tab) => Some(tab),
+            Tab::Klotski(_) | Tab::Loopover(_) | Tab::Race(_) => None,
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            Tab::Sliding(tab) => tab.title(),
+            Tab::Klotski(tab) if tab.controller.is_solved() => t!("Klotski ✓").to_string(),
+            Tab::Klotski(_) => t!("Klotski").to_string(),
+            Tab::Loopover(tab) => tab.title(),
+            Tab::Race(tab) => tab.title(),
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.sliding().is_some_and(GameTab::is_busy)
+    }
+}
+
+/// Main GUI presenter using egui
+///
+/// Holds one [`Tab`] per open puzzle. Best times, statistics and achievements
+/// are shared: whenever a tab records a game, its up-to-date copies replace the
+/// other tabs'.
+pub struct GuiPresenter {
+    tabs: Vec<Tab>,
+    active: usize,
+    new_tab_size: usize, // Grid size for the next tab opened with "+"
+    games_recorded: usize, // Games in the shared statistics at the last sync
+    toasts: Vec<(Toast, Instant)>, // Messages being shown, and when each appeared
+    show_performance: bool,
+    show_profiler: bool, // Overlay of frame costs, slides and background tasks
+    frame_metrics: FrameMetrics,
+    gamepad: Option<Box<dyn GamepadSource>>, // Polled every frame alongside the keyboard
+    show_techniques: bool, // Side panel of catalog techniques that fit at the empty cell
+    show_heatmap: bool, // Tint tiles by when the solution first moves them
+    show_ghost: bool,   // Faint home labels previewing the solved board
+    show_statistics: bool,
+    show_analysis: bool, // Post-game move ratings for the active tab
+    show_search: bool,   // Live plot of the active tab's background solves
+    show_search_ghosts: bool, // Boards the solver expanded lately, in the search window
+    show_settings: bool,
+    marathon_records: MarathonRecords,                   // Best finished marathons
+    marathon_summary: Option<(MarathonSummary, Option<usize>)>, // Run just ended and its rank, shown until closed
+    color_mode: ColorMode, // Applied to every tab's renderer
+    colorblind: bool,      // Hue-free distance cues, also applied to every tab
+    number_style: NumberStyle, // How every tab's numbered tiles show their number
+    animation_settings: AnimationSettings,
+    label_style: LabelStyle,
+    label_text: String,          // Phrase or comma-separated labels for LabelStyle::Text
+    label_error: Option<String>, // Why the last labels were rejected
+    show_load_board: bool,
+    board_text: String,           // Layout or share code typed into the Import window
+    load_error: Option<String>,   // Why the last layout was rejected
+    solution_text: String,        // Blank moves typed into the Import window for playback
+    tutorial: Tutorial,           // Guided tour, started on first launch
+    show_macros: bool,
+    macros: MacroBook,  // Shared by every tab
+    macro_name: String, // Name for the macro being recorded
+    session: SessionStore,              // Snapshot of the game in progress, for crash recovery
+    recovered: Option<SessionSnapshot>, // Game left by a crashed run, until restored or discarded
+    last_autosave: Instant,
+    config: Config,                // Defaults for new tabs, theme and key bindings
+    config_path: Option<PathBuf>,  // Where "Save as defaults" writes `slider.toml`
+    config_status: Option<String>, // Outcome of the last save
+}
+
+impl GuiPresenter {
+    /// Creates the presenter with the defaults from `slider.toml` and a board of `grid_size`
+    pub fn new(grid_size: usize) -> Result<Self, PuzzleError> {
+        Self::with_config(Config {
+            grid_size,
+            ..Config::load_default()
+        })
+    }
+
+    /// Creates the presenter from an already loaded config
+    pub fn with_config(config: Config) -> Result<Self, PuzzleError> {
+        let grid_size = config.grid_size;
+        let mut tab = GameTab::new(
+            grid_size,
+            BestTimes::load_default(),
+            Stats::load_default(),
+            Achievements::load_default(),
+        )?;
+        tab.apply_config(&config);
+        i18n::set_locale(config.language);
+        let games_recorded = tab.controller.stats().games_played();
+        let session = SessionStore::open_default();
+
+        Ok(Self {
+            tabs: vec![Tab::Sliding(tab)],
+            active: 0,
+            new_tab_size: grid_size,
+            games_recorded,
+            toasts: Vec::new(),
+            show_performance: false,
+            show_profiler: false,
+            frame_metrics: FrameMetrics::new(),
+            gamepad: None,
+            show_techniques: false,
+            show_heatmap: false,
+            show_ghost: false,
+            show_statistics: false,
+            show_analysis: false,
+            show_search: false,
+            show_search_ghosts: true,
+            show_settings: false,
+            marathon_records: MarathonRecords::load_default(),
+            marathon_summary: None,
+            color_mode: config.color_mode,
+            colorblind: config.colorblind,
+            number_style: config.number_style,
+            animation_settings: AnimationSettings::from_config(&config),
+            label_style: LabelStyle::default(),
+            label_text: "RATE YOUR MIND PAL".to_string(),
+            label_error: None,
+            show_load_board: false,
+            board_text: String::new(),
+            solution_text: String::new(),
+            load_error: None,
+            tutorial: Tutorial::load_default(),
+            show_macros: false,
+            macros: MacroBook::load_default(),
+            macro_name: String::new(),
+            recovered: session.recover(),
+            session,
+            last_autosave: Instant::now(),
+            config,
+            config_path: Config::default_path(),
+            config_status: None,
+        })
+    }
+
+    /// Reads gamepad buttons from `source` as well as the keyboard
+    pub fn with_gamepad(mut self, source: Box<dyn GamepadSource>) -> Self {
+        self.gamepad = Some(source);
+        self
+    }
+
+    /// Writes the current settings to `slider.toml` as the defaults for future sessions
+    ///
+    /// Board size, difficulty and solver come from the active sliding tab. A solver
+    /// the dispatcher picks is saved as "auto".
+    fn save_config(&mut self) {
+        if let Some(tab) = self.tabs[self.active].sliding() {
+            let size = tab.controller.state().size();
+            self.config.grid_size = size;
+            self.config.difficulty = tab.difficulty;
+            self.config.solver = (!tab.controller.is_solver_auto()).then(|| tab.controller.solver());
+        }
+        let settings = self.animation_settings;
+        self.config.color_mode = self.color_mode;
+        self.config.colorblind = self.colorblind;
+        self.config.number_style = self.number_style;
+        self.config.slide_ms = settings.duration_ms;
+        self.config.auto_solve_interval_ms = settings.auto_solve_interval_ms;
+        self.config.reduce_motion = settings.reduce_motion;
+
+        let Some(path) = self.config_path.as_deref() else {
+            self.config_status = Some(warning(t!("No config directory; set SLIDER_CONFIG_DIR")));
+            return;
+        };
+        self.config_status = Some(match self.config.save(path) {
+            Ok(()) => t!("Saved to {}", path.display()),
+            Err(e) => warning(t!("Could not save {}: {}", path.display(), e)),
+        });
+    }
+
+    /// Writes the game in progress to the session file every `AUTOSAVE_INTERVAL`
+    ///
+    /// Saves the active tab's game, or else the first sliding tab with one, and
+    /// removes the file once no game is left to recover.
+    fn autosave(&mut self) {
+        // Keep a crashed run's game until the player decides what to do with it
+        if self.recovered.is_some() || self.last_autosave.elapsed() < AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.last_autosave = Instant::now();
+
+        let snapshot = self.tabs[self.active]
+            .sliding()
+            .and_then(|tab| tab.controller.snapshot())
+            .or_else(|| self.tabs.iter().filter_map(Tab::sliding).find_map(|tab| tab.controller.snapshot()));
+        let result = match snapshot {
+            Some(ref snapshot) => self.session.save(snapshot),
+            None => self.session.clear(),
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to save the session: {}", e);
+        }
+    }
+
+    /// Offers to restore the game of a run that did not exit cleanly
+    fn show_recovery(&mut self, ctx: &Context) {
+        let Some(ref snapshot) = self.recovered else {
+            return;
+        };
+        let mut restore = None;
+        egui::Modal::new(egui::Id::new("recover_session")).show(ctx, |ui| {
+            ui.heading(t!("Restore last game?"));
+            ui.label(t!(
+                "Slider did not exit cleanly. Pick up the {0}×{0} game after {1} moves and {2}?",
+                snapshot.grid_size(),
+                snapshot.moves,
+                format_clock(snapshot.time())
+            ));
+            ui.horizontal(|ui| {
+                if ui.button(t!("Restore")).clicked() {
+                    restore = Some(true);
+                }
+                if ui.button(t!("Discard")).clicked() {
+                    restore = Some(false);
+                }
+            });
+        });
+
+        let Some(restore) = restore else {
+            return;
+        };
+        let snapshot = self.recovered.take().expect("checked above");
+        if restore {
+            // Restore into the active tab, or the first sliding one if Klotski is shown
+            let index = if self.tabs[self.active].sliding().is_some() {
+                Some(self.active)
+            } else {
+                self.tabs.iter().position(|tab| tab.sliding().is_some())
+            };
+            if let Some(tab) = index.and_then(|index| self.tabs[index].sliding_mut()) {
+                if let Err(e) = tab.restore(&snapshot) {
+                    tab.status_message = Some(warning(t!("Could not restore the last game: {}", e)));
+                }
+            }
+        } else if let Err(e) = self.session.clear() {
+            log::warn!("Failed to discard the session: {}", e);
+        }
+    }
+
+    /// Opens a new tab with a fresh puzzle and switches to it
+    fn open_tab(&mut self, grid_size: usize) {
+        // Records come from the active tab, or any sliding tab when Klotski is shown
+        let current = self.tabs[self.active]
+            .sliding()
+            .or_else(|| self.tabs.iter().find_map(Tab::sliding));
+        let (best_times, stats, achievements) = match current {
+            Some(tab) => (
+                tab.controller.best_times().clone(),
+                tab.controller.stats().clone(),
+                tab.controller.achievements().clone(),
+            ),
+            None => (BestTimes::load_default(), Stats::load_default(), Achievements::load_default()),
+        };
+
+        match GameTab::new(grid_size, best_times, stats, achievements) {
+            Ok(mut tab) => {
+                tab.apply_config(&self.config);
+                self.tabs.push(Tab::Sliding(tab));
+                self.active = self.tabs.len() - 1;
+            }
+            Err(e) => {
+                if let Some(tab) = self.tabs[self.active].sliding_mut() {
+                    tab.status_message = Some(warning(e));
+                }
+            }
+        }
+    }
+
+    /// Opens a two-player race tab and switches to it
+    fn open_race_tab(&mut self, grid_size: usize) {
+        match RaceTab::new(grid_size) {
+            Ok(tab) => {
+                self.tabs.push(Tab::Race(tab));
+                self.active = self.tabs.len() - 1;
+            }
+            Err(e) => log::warn!("Could not open a race: {}", e),
+        }
+    }
+
+    /// Opens a scrambled Loopover tab and switches to it
+    fn open_loopover_tab(&mut self, size: usize) {
+        match LoopoverTab::new(size.clamp(MIN_LOOPOVER_SIZE, MAX_LOOPOVER_SIZE)) {
+            Ok(tab) => {
+                self.tabs.push(Tab::Loopover(tab));
+                self.active = self.tabs.len() - 1;
+            }
+            Err(e) => log::warn!("Could not open Loopover: {}", e),
+        }
+    }
+
+    /// Opens a new tab with the classic Klotski layout and switches to it
+    fn open_klotski_tab(&mut self) {
+        self.tabs.push(Tab::Klotski(KlotskiTab::new()));
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Closes a tab, cancelling any solve it has running
+    /// The last remaining tab cannot be closed
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() <= 1 {
+            return;
+        }
+        if let Tab::Sliding(mut tab) = self.tabs.remove(index) {
+            tab.controller.stop_auto_solve();
+            if let Some(summary) = tab.end_marathon() {
+                self.marathon_records.record(summary);
+            }
+        }
+        if self.active > index || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+    }
+
+    /// Keeps a finished marathon with the best runs and opens its summary
+    fn finish_marathon(&mut self, summary: MarathonSummary) {
+        let rank = self.marathon_records.record(summary.clone());
+        self.marathon_summary = Some((summary, rank));
+    }
+
+    /// Copies best times, statistics and achievements from a tab that just recorded a game
+    fn sync_records(&mut self) {
+        let Some(source) = self.tabs.iter().position(|tab| {
+            tab.sliding()
+                .is_some_and(|tab| tab.controller.stats().games_played() != self.games_recorded)
+        }) else {
+            return;
+        };
+
+        let controller = &self.tabs[source].sliding().expect("found above").controller;
+        let best_times = controller.best_times().clone();
+        let stats = controller.stats().clone();
+        let achievements = controller.achievements().clone();
+        self.games_recorded = stats.games_played();
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            let Some(tab) = tab.sliding_mut() else {
+                continue;
+            };
+            if i != source {
+                tab.controller.set_best_times(best_times.clone());
+                tab.controller.set_stats(stats.clone());
+                tab.controller.set_achievements(achievements.clone());
+            }
+        }
+    }
+
+    /// Queues a toast for each achievement a tab unlocked, then shows the queue
+    /// in the bottom-right corner until each toast expires
+    fn show_toasts(&mut self, ctx: &Context) {
+        for tab in self.tabs.iter_mut().filter_map(Tab::sliding_mut) {
+            for achievement in tab.controller.take_new_achievements() {
+                self.toasts.push((Toast::Achievement(achievement), Instant::now()));
+            }
+        }
+        self.toasts.retain(|(_, shown)| shown.elapsed().as_millis() < TOAST_MS);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                for (toast, _) in &self.toasts {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| match toast {
+                        Toast::Achievement(achievement) => {
+                            ui.strong(t!("🏆 Achievement unlocked: {}", tr_value(&achievement)));
+                            ui.label(tr(achievement.description()));
+                        }
+                        Toast::Error(message) => {
+                            ui.colored_label(ui.visuals().error_fg_color, warning(message));
+                        }
+                    });
+                }
+            });
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    /// Shows the profiler overlay in the bottom-left corner
+    ///
+    /// Frame times are eframe's CPU time per frame, painting included; the
+    /// board's and the background polling's share are timed here.
+    fn show_profiler(&self, ctx: &Context) {
+        let metrics = &self.frame_metrics;
+        let format = PerformanceMetrics::format_duration;
+        egui::Area::new(egui::Id::new("profiler"))
+            .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    let (Some(last), Some(worst)) = (metrics.last(), metrics.worst()) else {
+                        ui.label(t!("Waiting for the first frame…"));
+                        return;
+                    };
+                    let mean = metrics.mean();
+                    ui.monospace(t!(
+                        "Frame {} · mean {} · worst {}",
+                        format(last.total_micros),
+                        format(mean.total_micros),
+                        format(worst.total_micros)
+                    ));
+                    ui.monospace(t!(
+                        "Over {}: {} of {} frames",
+                        format(FRAME_BUDGET_MICROS),
+                        metrics.slow_frames(),
+                        metrics.frame_count()
+                    ));
+                    ui.monospace(t!(
+                        "Mean board {} · solvers and slides {}",
+                        format(mean.board_micros),
+                        format(mean.advance_micros)
+                    ));
+                    frame_time_bars(ui, metrics);
+
+                    ui.separator();
+                    if let Some(tab) = self.tabs[self.active].sliding() {
+                        ui.monospace(t!("Queued slides: {}", tab.animation_depth()));
+                        ui.monospace(t!("Background: {}", tab.background_status()));
+                    }
+                    let busy = self
+                        .tabs
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, tab)| i != self.active && tab.is_busy())
+                        .count();
+                    if busy > 0 {
+                        ui.monospace(t!("Other busy tabs: {}", busy));
+                    }
+                });
+            });
+    }
+
+    /// Parses the typed layout or share code and loads it into the active tab
+    fn load_board(&mut self) {
+        let Some(tab) = self.tabs[self.active].sliding_mut() else {
+            return;
+        };
+        match PuzzleState::from_notation_or_code(&self.board_text).and_then(|state| tab.load(state)) {
+            Ok(()) => {
+                self.load_error = None;
+                self.show_load_board = false;
+            }
+            Err(e) => self.load_error = Some(e.to_string()),
+        }
+    }
+
+    /// Loads a file dropped onto the window into the active tab, announcing
+    /// files that cannot be used in a toast
+    fn load_dropped_file(&mut self, ctx: &Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        let Some(file) = dropped.first() else {
+            return;
+        };
+        if let Err(message) = self.load_file(ctx, file) {
+            self.toasts.push((Toast::Error(message), Instant::now()));
+        }
+    }
+
+    /// Loads a `.sldr` puzzle, or with the `capture` feature a PNG to cut into
+    /// picture tiles; a solution in a puzzle file is put in the Import window,
+    /// ready to play
+    /// Returns why the file could not be used
+    #[cfg_attr(not(all(feature = "capture", not(target_arch = "wasm32"))), allow(unused_variables))]
+    fn load_file(&mut self, ctx: &Context, file: &egui::DroppedFile) -> Result<(), String> {
+        let Some(tab) = self.tabs[self.active].sliding_mut() else {
+            return Err(t!("Files can only be dropped on a sliding puzzle").to_string());
+        };
+        // Browsers hand over the contents; native windows the path
+        let name = file.path.as_ref().map_or(file.name.clone(), |path| path.display().to_string());
+        let bytes = match (&file.bytes, &file.path) {
+            (Some(bytes), _) => bytes.to_vec(),
+            (None, Some(path)) => std::fs::read(path).map_err(|e| t!("Cannot read {}: {}", name, e))?,
+            (None, None) => return Ok(()),
+        };
+        let extension = std::path::Path::new(&name)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+
+        match extension.as_deref() {
+            Some(SLDR_EXTENSION) => {
+                let puzzle = std::str::from_utf8(&bytes)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| PuzzleFile::from_text(text).map_err(|e| e.to_string()))
+                    .map_err(|e| t!("{} is not a valid puzzle file: {}", name, e))?;
+                let solution = puzzle
+                    .solution
+                    .as_ref()
+                    .map(|path| notation::blank_moves(puzzle.state.empty_position(), path));
+                tab.load(puzzle.state).map_err(|e| e.to_string())?;
+                let author = puzzle.author.map_or(String::new(), |author| t!(" by {}", author));
+                tab.status_message = Some(match solution {
+                    Some(moves) => {
+                        self.solution_text = moves;
+                        t!("Loaded a puzzle{}; its solution is in Import", author)
+                    }
+                    None => t!("Loaded a puzzle{}", author),
+                });
+                Ok(())
+            }
+            #[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+            Some("png") => {
+                let picture = capture::decode_picture(&bytes).map_err(|e| format!("{}: {}", name, e))?;
+                tab.scramble_picture(ctx, picture);
+                Ok(())
+            }
+            _ if cfg!(all(feature = "capture", not(target_arch = "wasm32"))) => {
+                Err(t!("{} is neither a .{} puzzle nor a PNG picture", name, SLDR_EXTENSION))
+            }
+            _ => Err(t!("{} is not a .{} puzzle file", name, SLDR_EXTENSION)),
+        }
+    }
+
+    /// Plays the typed blank moves on the active tab's board
+    ///
+    /// The moves before the first illegal one still play, so it is easy to see
+    /// where an imported solution goes wrong.
+    fn play_solution(&mut self) {
+        let Some(tab) = self.tabs[self.active].sliding_mut() else {
+            return;
+        };
+        let state = tab.controller.state();
+        let moves = notation::read_blank_moves(state.empty_position(), state.size(), &self.solution_text);
+        let played = moves.path.len();
+
+        match tab.controller.start_playback(moves.path) {
+            Ok(()) => {
+                tab.hint = None;
+                tab.status_message = Some(match moves.error {
+                    Some(e) if played > 0 => warning(t!("{} (playing the {} moves before it)", e, played)),
+                    Some(e) => warning(e),
+                    None => t!("Playing a {}-move solution", played),
+                });
+                self.load_error = None;
+                self.show_load_board = false;
+            }
+            Err(e) => self.load_error = Some(e.to_string()),
+        }
+    }
+}
+
+/// Builds a renderer whose tiles fit the window for the given grid size
+fn renderer_for(grid_size: usize) -> TileRenderer {
+    // Target total grid size ~600px (leaves room for UI controls)
+    TileRenderer::new(tile_size_for(600.0, grid_size), TILE_GAP, grid_size)
+}
+
+impl eframe::App for GuiPresenter {
+    /// Removes the session file, so only runs that did not exit cleanly offer a restore
+    /// Marathons still running are kept with the best runs
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        for tab in self.tabs.iter_mut().filter_map(Tab::sliding_mut) {
+            if let Some(summary) = tab.end_marathon() {
+                self.marathon_records.record(summary);
+            }
+        }
+        if let Err(e) = self.session.clear() {
+            log::warn!("Failed to clear the session: {}", e);
+        }
+    }
+
+    fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+        // eframe measures a frame once it is painted, so the last one is recorded now
+        if let Some(seconds) = frame.info().cpu_usage {
+            self.frame_metrics.finish_frame((seconds * 1_000_000.0) as u64);
+        }
+
+        // Background tabs keep solving and animating while hidden
+        let timer = PerformanceTimer::start();
+        for tab in self.tabs.iter_mut().filter_map(Tab::sliding_mut) {
+            tab.advance(&self.animation_settings);
+        }
+        self.frame_metrics.current.advance_micros += timer.elapsed_micros();
+        // Buttons are read every frame, so presses on other tabs do not pile up
+        let pad_actions = self
+            .gamepad
+            .as_mut()
+            .map_or_else(Vec::new, |gamepad| input::gamepad_actions(gamepad.as_mut()));
+        if self.gamepad.is_some() {
+            // egui only repaints on its own events, and gamepads are not among them
+            ctx.request_repaint_after(Duration::from_millis(30));
+        }
+        self.sync_records();
+        self.autosave();
+        self.show_recovery(ctx);
+        self.show_toasts(ctx);
+        if self.show_profiler {
+            self.show_profiler(ctx);
+        }
+        self.load_dropped_file(ctx);
+
+        // egui's own widget animations follow the reduced-motion setting too
+        let animation_time = if self.animation_settings.reduce_motion {
+            0.0
+        } else {
+            EGUI_ANIMATION_TIME
+        };
+        if ctx.style().animation_time != animation_time {
+            ctx.style_mut(|style| style.animation_time = animation_time);
+        }
+        let theme = self.config.theme.preference();
+        if ctx.options(|options| options.theme_preference) != theme {
+            ctx.set_theme(theme);
+        }
+
+        // Request repaint for smooth animation and background solver polling
+        if self.tabs.iter().any(Tab::is_busy) {
+            ctx.request_repaint();
+        }
+
+        // Tab bar listing every open puzzle
+        let mut close = None;
+        let mut open = false;
+        let mut open_klotski = false;
+        let mut open_loopover = false;
+        let mut open_race = false;
+        TopBottomPanel::top("tab_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (i, tab) in self.tabs.iter().enumerate() {
+                    if ui.selectable_label(i == self.active, tab.title()).clicked() {
+                        self.active = i;
+                    }
+                    if self.tabs.len() > 1
+                        && ui.small_button("×").on_hover_text(t!("Close this puzzle")).clicked()
+                    {
+                        close = Some(i);
+                    }
+                    ui.separator();
+                }
+
+                ui.add(
+                    egui::DragValue::new(&mut self.new_tab_size)
+                        .range(MIN_SIZE..=MAX_SIZE)
+                        .suffix(t!(" grid")),
+                );
+                if ui.button("+").on_hover_text(t!("Open another puzzle in a new tab")).clicked() {
+                    open = true;
+                }
+                if ui.button(t!("+ Klotski")).on_hover_text(t!("Open a Klotski block puzzle in a new tab")).clicked() {
+                    open_klotski = true;
+                }
+                if ui
+                    .button(t!("+ Loopover"))
+                    .on_hover_text(t!("Open a Loopover puzzle of the chosen size (up to 10×10), whose rows and columns rotate"))
+                    .clicked()
+                {
+                    open_loopover = true;
+                }
+                if ui.button(t!("+ Race")).on_hover_text(t!("Race a friend on identical boards at one keyboard")).clicked() {
+                    open_race = true;
+                }
+            });
+        });
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+        if open {
+            self.open_tab(self.new_tab_size);
+        }
+        if open_klotski {
+            self.open_klotski_tab();
+        }
+        if open_loopover {
+            self.open_loopover_tab(self.new_tab_size);
+        }
+        if open_race {
+            self.open_race_tab(self.new_tab_size);
+        }
+
+        let tab = match &mut self.tabs[self.active] {
+            Tab::Sliding(tab) => tab,
+            Tab::Klotski(tab) => {
+                tab.show(ctx, &self.animation_settings);
+                return;
+            }
+            Tab::Loopover(tab) => {
+                tab.show(ctx);
+                return;
+            }
+            Tab::Race(tab) => {
+                tab.show(ctx, self.color_mode, self.colorblind, self.number_style);
+                return;
+            }
+        };
+
+        let mut ended_marathon = None;
+
+        // Top panel with controls
+        TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("Slider Puzzle"));
+
+                ui.separator();
+
+                // Grid size selection (starts a fresh solved board)
+                let current_size = tab.controller.state().size();
+                let mut size = current_size;
+                egui::ComboBox::from_id_salt("size_select")
+                    .selected_text(t!("Size: {}×{}", size, size))
+                    .show_ui(ui, |ui| {
+                        for n in MIN_SIZE..=MAX_SIZE {
+                            ui.selectable_value(&mut size, n, format!("{}×{}", n, n));
+                        }
+                    });
+                if size != current_size {
+                    // Picking a size by hand ends a marathon
+                    ended_marathon = tab.end_marathon();
+                    if let Err(e) = tab.resize(size) {
+                        tab.status_message = Some(warning(e));
+                    }
+                }
+
+                ui.separator();
+
+                // Difficulty selection
+                ui.label(t!("Difficulty:"));
+                for (preset, label) in [
+                    (Difficulty::Easy, t!("Easy")),
+                    (Difficulty::Medium, t!("Medium")),
+                    (Difficulty::Hard, t!("Hard")),
+                    (Difficulty::ExtraHard, t!("Extra Hard")),
+                ] {
+                    let range = tab.controller.target_range(preset);
+                    ui.radio_value(&mut tab.difficulty, preset, label)
+                        .on_hover_text(t!("Entropy target {}–{}", range.start(), range.end()));
+                }
+                let custom = Difficulty::Custom {
+                    min_entropy: tab.custom_entropy,
+                };
+                ui.radio_value(&mut tab.difficulty, custom, t!("Custom"))
+                    .on_hover_text(t!("Shuffle until entropy reaches the chosen target, given in Manhattan distance and tuned to the entropy calculator"));
+                if matches!(tab.difficulty, Difficulty::Custom { .. }) {
+                    let max = Difficulty::max_entropy(tab.controller.state().size());
+                    ui.add(egui::Slider::new(&mut tab.custom_entropy, 1..=max).text(t!("min")));
+                    tab.difficulty = Difficulty::Custom {
+                        min_entropy: tab.custom_entropy,
+                    };
+                }
+
+                ui.checkbox(&mut tab.adaptive, t!("Adaptive")).on_hover_text(
+                    t!("Pick each new game's target from your recent efficiency and solve times"),
+                );
+                if tab.adaptive {
+                    let next = tab.controller.adaptive_difficulty();
+                    let label = ui.label(t!("Next: {}+ ({})", next.target(), next.adjustment()));
+                    if next.games_considered() > 0 {
+                        let efficiency = next.efficiency().map_or("-".to_string(), |e| format!("{:.0}%", e * 100.0));
+                        let pace = next.seconds_per_move().map_or("-".to_string(), |p| format!("{:.1}s", p));
+                        label.on_hover_text(t!(
+                            "Last {} games: {} efficiency, {} per par move",
+                            next.games_considered(),
+                            efficiency,
+                            pace
+                        ));
+                    } else {
+                        label.on_hover_text(t!("No finished games with a known par yet; starting at Medium"));
+                    }
+                }
+
+                ui.add(
+                    egui::DragValue::new(&mut tab.layers)
+                        .range(1..=MAX_LAYERS)
+                        .prefix(t!("Layers: ")),
+                )
+                .on_hover_text(t!("Stack several boards; the game is won once every layer is solved"));
+
+                // Challenge rules for the next game
+                let mut mode = tab.controller.game_mode();
+                egui::ComboBox::from_id_salt("game_mode")
+                    .selected_text(game_mode_name(mode))
+                    .show_ui(ui, |ui| {
+                        for preset in GameMode::PRESETS {
+                            let selected = std::mem::discriminant(&mode) == std::mem::discriminant(&preset);
+                            if ui.selectable_label(selected, game_mode_name(preset)).clicked() && !selected {
+                                mode = preset;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("Challenges are lost if the move limit or the countdown runs out; applies from the next game"));
+                match &mut mode {
+                    GameMode::Free => {}
+                    GameMode::MoveLimit { slack } => {
+                        ui.add(egui::DragValue::new(slack).range(0..=100).prefix(t!("par + ")));
+                    }
+                    GameMode::Countdown { seconds } => {
+                        ui.add(
+                            egui::DragValue::new(seconds)
+                                .range(10..=3600)
+                                .speed(5)
+                                .custom_formatter(|s, _| format!("{}:{:02}", s as u64 / 60, s as u64 % 60)),
+                        );
+                    }
+                }
+                tab.controller.set_game_mode(mode);
+
+                ui.separator();
+
+                // New game button (large boards shuffle in the background)
+                if tab.controller.is_shuffling() {
+                    ui.add(egui::Spinner::new());
+                    ui.label(t!("Shuffling…"));
+                    ctx.request_repaint();
+                } else if let Some(run) = &tab.marathon {
+                    let stage = run.stage();
+                    ui.label(t!(
+                        "Marathon stage {}: {}×{} {} · Score {}",
+                        run.stages_cleared() + 1,
+                        stage.grid_size,
+                        stage.grid_size,
+                        difficulty_name(stage.difficulty),
+                        run.score()
+                    ));
+                    if ui.button(t!("End Marathon")).on_hover_text(t!("Stop the run and see its summary")).clicked() {
+                        ended_marathon = tab.end_marathon();
+                    }
+                } else {
+                    if ui.button(t!("New Game")).clicked() {
+                        tab.start_game(tab.chosen_difficulty());
+                    }
+                    if ui
+                        .button(t!("Marathon"))
+                        .on_hover_text(t!("Endless run from an Easy 3×3: every solve moves on to a harder or larger board"))
+                        .clicked()
+                    {
+                        tab.start_marathon();
+                    }
+                }
+
+                if ui
+                    .button(t!("Random Board"))
+                    .on_hover_text(t!("Start from a uniformly random solvable layout (not timed for records)"))
+                    .clicked()
+                {
+                    tab.controller.new_random_game();
+                }
+
+                // Practice: scramble only the end of the solve
+                ui.menu_button(t!("Practice"), |ui| {
+                    let size = tab.controller.state().size();
+                    let regions = [
+                        (t!("Last two rows"), Region::bottom_rows(size, 2)),
+                        (t!("Last two columns"), Region::right_columns(size, 2)),
+                        (t!("Bottom-right 3×3"), Region::bottom_right(size, 3)),
+                        (t!("Bottom-right 2×2"), Region::bottom_right(size, 2)),
+                    ];
+                    for (label, region) in regions {
+                        if ui.button(label).clicked() {
+                            let difficulty = tab.difficulty;
+                            tab.status_message = tab
+                                .controller
+                                .new_practice_game(region, difficulty)
+                                .err()
+                                .map(warning);
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(t!("Scramble only part of the board to drill the end of a solve (not timed for records)"));
+
+                // Reset button
+                if ui.button(t!("Reset")).clicked() {
+                    tab.controller.reset();
+                }
+
+                ui.separator();
+
+                // Auto-solve button
+                if tab.controller.is_auto_solving() {
+                    if ui.button(t!("Stop Solve")).clicked() {
+                        tab.controller.stop_auto_solve();
+                        tab.status_message = None;
+                    }
+                    let paused = tab.controller.is_auto_solve_paused();
+                    if ui
+                        .button(if paused { "▶" } else { "⏸" })
+                        .on_hover_text(if paused { t!("Resume") } else { t!("Pause") })
+                        .clicked()
+                    {
+                        tab.controller.set_auto_solve_paused(!paused);
+                    }
+                    if let Some((current, total)) = tab.controller.auto_solve_progress() {
+                        ui.label(format!("{}/{}", current, total));
+                    }
+                    let mut speed = tab.controller.auto_solve_speed();
+                    if ui
+                        .add(
+                            egui::Slider::new(&mut speed, MIN_AUTO_SOLVE_SPEED..=MAX_AUTO_SOLVE_SPEED)
+                                .logarithmic(true)
+                                .max_decimals(1)
+                                .suffix("×"),
+                        )
+                        .on_hover_text(t!("Playback speed"))
+                        .changed()
+                    {
+                        tab.controller.set_auto_solve_speed(speed);
+                    }
+                    if ui
+                        .button(t!("Skip to End"))
+                        .on_hover_text(t!("Play the remaining moves at once, without animation"))
+                        .clicked()
+                    {
+                        tab.skip_auto_solve();
+                    }
+                } else if tab.controller.is_solver_computing_for_autosolve() {
+                    // Solver running in background for auto-solve
+                    if ui.button(t!("Cancel")).clicked() {
+                        tab.controller.stop_auto_solve();
+                        tab.status_message = None;
+                    }
+                    if let Some(progress) = tab.controller.solver_progress() {
+                        let budget = tab.controller.solver_node_budget().max(1);
+                        let fraction = progress.nodes_explored as f32 / budget as f32;
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .desired_width(160.0)
+                                .text(t!(
+                                    "{} nodes, depth {} ({}s)",
+                                    progress.nodes_explored,
+                                    progress.current_depth,
+                                    format!("{:.1}", progress.elapsed.as_secs_f32())
+                                )),
+                        );
+                    } else {
+                        ui.label(t!("Computing solution..."));
+                    }
+                    ctx.request_repaint(); // Keep UI responsive
+                } else {
+                    if ui.button(t!("Auto Solve")).clicked() {
+                        if !tab.controller.start_auto_solve() {
+                            tab.status_message = Some(warning(t!("Puzzle already solved or computation in progress")));
+                        } else {
+                            tab.status_message = Some(t!("Computing solution in background...").to_string());
+                            self.tutorial.handle(TutorialEvent::AutoSolveStarted);
+                        }
+                    }
+                    ui.menu_button(t!("Assist"), |ui| {
+                        for (target, label) in [
+                            (AssistTarget::Moves(5), t!("Next 5 moves")),
+                            (AssistTarget::Tiles(1), t!("Next tile")),
+                            (AssistTarget::Tiles(5), t!("Next 5 tiles")),
+                            (AssistTarget::NextRow, t!("Next row")),
+                        ] {
+                            if ui.button(label).clicked() {
+                                tab.status_message = Some(if tab.controller.start_partial_auto_solve(target) {
+                                    t!("Solving the {}, then it's your move", tr_value(&target))
+                                } else {
+                                    warning(t!("Nothing to solve for the {}", tr_value(&target)))
+                                });
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("Auto-solve part of the board, then hand it back"));
+                }
+
+                // Hint button (flashes the suggested tile)
+                if !tab.controller.is_auto_solving() && ui.button(t!("Hint")).clicked() {
+                    tab.hint = tab.controller.get_hint().map(|pos| (pos, Instant::now()));
+                    if tab.hint.is_none() {
+                        tab.status_message = Some(t!("No hint available").to_string());
+                    }
+                }
+
+                // Step button (plays one move of the solution per click)
+                if !tab.controller.is_auto_solving()
+                    && ui
+                        .add_enabled(
+                            !tab.is_animating() && !tab.controller.is_solved(),
+                            egui::Button::new(t!("Step")),
+                        )
+                        .on_hover_text(t!("Play one move of the solution, to study how it is solved"))
+                        .clicked()
+                {
+                    let old_empty = tab.controller.state().empty_position();
+                    if let Some(from) = tab.controller.advance_solution_step() {
+                        tab.hint = None;
+                        tab.status_message = None;
+                        tab.animation = Some(TileAnimation::replay(from, old_empty, &self.animation_settings));
+                    } else if let Some(e) = tab.controller.solver_error() {
+                        tab.status_message = Some(warning(e));
+                    } else {
+                        tab.status_message = Some(t!("Computing solution... press Step again").to_string());
+                    }
+                }
+
+                // Copy the known solution as text once a solve has found one
+                if let Some(path) = tab.controller.remaining_solution().filter(|path| !path.is_empty()) {
+                    if ui
+                        .button(t!("Copy solution"))
+                        .on_hover_text(t!("Copy the rest of the solution as blank moves (U/D/L/R) and as tile numbers"))
+                        .clicked()
+                    {
+                        let state = tab.controller.state();
+                        let text = t!(
+                            "{} ({} moves)\nTiles: {}",
+                            notation::blank_moves(state.empty_position(), &path),
+                            path.len(),
+                            notation::tile_numbers(state, &path)
+                        );
+                        ui.ctx().copy_text(text);
+                        tab.status_message = Some(t!("Solution copied to clipboard").to_string());
+                    }
+                }
+
+                // Display status message if present
+                if let Some(ref msg) = tab.status_message {
+                    ui.label(msg);
+                }
+                // Caption what a strategic auto-solve is doing
+                if let Some(step) = tab.controller.auto_solve_step() {
+                    ui.label(egui::RichText::new(solve_step_text(step)).italics());
+                }
+
+                ui.separator();
+
+                // Move counters (a chain move is one player move but several tile moves)
+                let moves = t!(
+                    "Moves: {} · Tiles: {}",
+                    tab.controller.move_count(),
+                    tab.controller.tile_move_count()
+                );
+                if let Some(shuffle_result) = tab.controller.last_shuffle_result() {
+                    ui.label(t!("{} [shuffle: {}]", moves, shuffle_result.moves_made));
+                } else {
+                    ui.label(moves);
+                }
+
+                // Par: optimal length of the shuffled board, solved in the background
+                if let Some(par) = tab.controller.initial_optimal_length() {
+                    ui.label(t!("Par: {}", par))
+                        .on_hover_text(t!("Fewest tile moves that solve this shuffle; finishing at par earns an S"));
+                } else if tab.controller.is_par_pending() {
+                    ui.label(t!("Par: …"));
+                }
+
+                ui.separator();
+
+                // Challenge limits left, or why the challenge was lost
+                if let Some(failure) = tab.controller.challenge_failure() {
+                    ui.colored_label(egui::Color32::RED, format!("✗ {}", tr_value(&failure)));
+                } else {
+                    if let Some(moves) = tab.controller.moves_remaining() {
+                        ui.label(t!("Moves left: {}", moves));
+                    } else if matches!(tab.controller.challenge(), GameMode::MoveLimit { .. }) {
+                        ui.label(t!("Moves left: …"))
+                            .on_hover_text(t!("The limit starts counting once par is known"));
+                    }
+                    if let Some(time) = tab.controller.time_remaining() {
+                        ui.label(t!("Time left: {}", format_clock(time)));
+                    }
+                }
+
+                // Game clock and best time for the selected difficulty
+                ui.label(t!("Time: {}", format_clock(tab.controller.elapsed_time())));
+                if let Some(best) = tab.controller.best_time(tab.difficulty) {
+                    ui.label(t!("Best: {}", format_clock(best)));
+                }
+                if tab.controller.is_timer_running() {
+                    ctx.request_repaint_after(Duration::from_millis(100));
+                }
+
+                ui.separator();
+
+                // Entropy metrics display
+                let metrics = tab.controller.all_entropy_metrics();
+                // Estimates from a quick weighted search are marked with ≈,
+                // and lengths of an earlier board still being measured with …
+                let mut actual = if metrics.actual_is_estimate {
+                    format!("≈{}", metrics.actual_solution_length)
+                } else {
+                    metrics.actual_solution_length.to_string()
+                };
+                if metrics.stale {
+                    actual.push('…');
+                }
+                let estimate_hint = if metrics.stale {
+                    t!("Measuring this board in the background; the length shown is from before the last move")
+                } else {
+                    t!("Estimated by a quick weighted search; the exact length \
+                     replaces it once the background solver finds it")
+                };
+
+                if self.show_performance {
+                    // Detailed view with performance metrics
+                    ui.label(t!("Manhattan: {}", metrics.manhattan_distance));
+                    ui.label(t!("Heuristic: {}", metrics.shortest_path_heuristic));
+
+                    if metrics.actual_solution_length < 999 {
+                        let label = ui.label(t!(
+                            "Actual: {} (calc: {})",
+                            actual,
+                            PerformanceMetrics::format_duration(metrics.performance.actual_time_micros)
+                        ));
+                        if metrics.actual_is_estimate || metrics.stale {
+                            label.on_hover_text(estimate_hint);
+                        }
+                    } else if metrics.performance.actual_time_micros > 0 {
+                        // Show solve time even if puzzle not yet solved
+                        ui.label(t!(
+                            "Actual: -- (last calc: {})",
+                            PerformanceMetrics::format_duration(metrics.performance.actual_time_micros)
+                        ));
+                    } else {
+                        ui.label(t!("Actual: --"));
+                    }
+
+                    if metrics.performance.solver_nodes_expanded > 0 {
+                        ui.label(t!("Nodes expanded: {}", metrics.performance.solver_nodes_expanded));
+                    }
+                    distance_sparkline(ui, tab.controller.distance_trail());
+                } else {
+                    // Compact view without timing
+                    ui.label(t!("Manhattan: {}", metrics.manhattan_distance));
+                    ui.label(t!("Heuristic: {}", metrics.shortest_path_heuristic));
+
+                    if metrics.actual_solution_length < 999 {
+                        let label = ui.label(t!("Actual: {}", actual));
+                        if metrics.actual_is_estimate || metrics.stale {
+                            label.on_hover_text(estimate_hint);
+                        }
+                    } else {
+                        ui.label(t!("Actual: --"));
+                    }
+                }
+
+                ui.separator();
+
+                // Performance toggle
+                ui.checkbox(&mut self.show_performance, t!("Show Performance"));
+                ui.checkbox(&mut self.show_profiler, t!("Profiler"))
+                    .on_hover_text(t!("Overlay frame times, queued slides and background tasks, to track down stutter"));
+                ui.checkbox(&mut self.show_techniques, t!("Techniques"))
+                    .on_hover_text(t!("List the techniques that can be played from the empty cell"));
+                ui.checkbox(&mut self.show_heatmap, t!("Heatmap"))
+                    .on_hover_text(t!("Tint tiles by how soon the solution moves them (orange first, violet last)"));
+                ui.checkbox(&mut self.show_ghost, t!("Ghost"))
+                    .on_hover_text(t!("Write each tile's label faintly in the cell it belongs in"));
+                #[cfg(all(feature = "board-export", not(target_arch = "wasm32")))]
+                ui.menu_button(t!("Export Image"), |ui| {
+                    for format in BoardImageFormat::ALL {
+                        if ui
+                            .button(format.to_string())
+                            .on_hover_text(t!("Save the board as shown, heatmap included, to the data directory"))
+                            .clicked()
+                        {
+                            tab.export_board_image(format, self.show_heatmap);
+                            ui.close_menu();
+                        }
+                    }
+                });
+
+                // Solver selection (applies to the next background solve); None is automatic
+                let current = (!tab.controller.is_solver_auto()).then(|| tab.controller.solver());
+                let mut solver = current;
+                egui::ComboBox::from_id_salt("solver_select")
+                    .selected_text(match current {
+                        Some(kind) => t!("Solver: {}", tr_value(&kind)),
+                        None => t!("Solver: Auto ({})", tr_value(&tab.controller.solver())),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut solver, None, t!("Auto"));
+                        for kind in SolverKind::ALL {
+                            ui.selectable_value(&mut solver, Some(kind), tr_value(&kind));
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("Auto: A* up to 4×4, IDA* for 5×5, Strategic beyond or when the others give up\nA*: fast, near-optimal\nIDA*: optimal, low memory (5×5+)\nGreedy: fast, not optimal\nStrategic: row by row, any grid size"));
+                if solver != current {
+                    match solver {
+                        Some(kind) => tab.controller.set_solver(kind),
+                        None => tab.controller.set_solver_auto(),
+                    }
+                }
+
+                // Entropy calculator (measures new shuffles and the current board)
+                let size = tab.controller.state().size();
+                let current = tab.controller.entropy_kind();
+                let mut chosen = current;
+                egui::ComboBox::from_id_salt("entropy_select")
+                    .selected_text(t!("Entropy: {}", current.map_or(t!("Custom").to_string(), |k| tr_value(&k))))
+                    .show_ui(ui, |ui| {
+                        for kind in EntropyKind::ALL {
+                            ui.add_enabled_ui(kind.supports(size), |ui| {
+                                ui.selectable_value(&mut chosen, Some(kind), tr_value(&kind))
+                                    .on_hover_text(tr(kind.description()));
+                            });
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("How disorder is measured; difficulty targets are scaled to match"));
+                if let Some(kind) = chosen.filter(|_| chosen != current) {
+                    if let Err(e) = tab.controller.set_entropy_kind(kind) {
+                        tab.status_message = Some(warning(e));
+                    }
+                }
+
+                if ui.button(t!("Statistics")).clicked() {
+                    self.show_statistics = !self.show_statistics;
+                }
+
+                if ui
+                    .button(t!("Analysis"))
+                    .on_hover_text(t!("Rate each move of the solved game against optimal play"))
+                    .clicked()
+                {
+                    self.show_analysis = !self.show_analysis;
+                }
+
+                if ui
+                    .button(t!("Search"))
+                    .on_hover_text(t!("Watch the solver search live: f-score, frontier size and boards expanded"))
+                    .clicked()
+                {
+                    self.show_search = !self.show_search;
+                }
+
+                if ui.button(t!("Settings")).clicked() {
+                    self.show_settings = !self.show_settings;
+                }
+
+                let macros_label = if tab.controller.is_recording() { t!("Macros (recording)") } else { t!("Macros") };
+                if ui
+                    .button(macros_label)
+                    .on_hover_text(t!("Record move sequences and replay them with keys 1-9"))
+                    .clicked()
+                {
+                    self.show_macros = !self.show_macros;
+                }
+
+                if ui
+                    .button(t!("Import"))
+                    .on_hover_text(t!("Load a board from a share code or layout notation"))
+                    .clicked()
+                {
+                    self.show_load_board = !self.show_load_board;
+                    if self.board_text.is_empty() {
+                        self.board_text = tab.controller.state().to_notation();
+                    }
+                }
+
+                if ui
+                    .button(t!("Export"))
+                    .on_hover_text(t!("Copy a share code for this board to the clipboard"))
+                    .clicked()
+                {
+                    let code = tab.controller.state().to_share_code();
+                    ui.ctx().copy_text(code.clone());
+                    tab.status_message = Some(t!("Share code copied: {}", code));
+                }
+
+                // Solved indicator
+                if tab.controller.is_solved() {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::GREEN, t!("SOLVED!"));
+                    if tab.controller.is_new_best() {
+                        ui.colored_label(egui::Color32::GOLD, t!("New best time!"));
+                    }
+                }
+            });
+        });
+
+        // Layer bar for layered games
+        if tab.controller.layer_count() > 1 {
+            TopBottomPanel::top("layer_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("Layer:"));
+                    // Switching mid-slide would apply the rest of the move to the wrong board
+                    let can_switch = !tab.is_animating();
+                    for layer in 0..tab.controller.layer_count() {
+                        let label = if tab.controller.layer_solved(layer) {
+                            format!("{} ✓", layer + 1)
+                        } else {
+                            format!("{}", layer + 1)
+                        };
+                        let selected = layer == tab.controller.active_layer();
+                        if ui
+                            .add_enabled(can_switch, egui::SelectableLabel::new(selected, label))
+                            .clicked()
+                            && tab.controller.set_active_layer(layer)
+                        {
+                            tab.hint = None;
+                            tab.status_message = None;
+                        }
+                    }
+                });
+            });
+        }
+
+        // Statistics window with aggregate results, best times, and recent games
+        egui::Window::new(t!("Statistics"))
+            // The title changes with the language, so the window keeps its place by id
+            .id(egui::Id::new("statistics_window"))
+            .open(&mut self.show_statistics)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let stats = tab.controller.stats();
+                ui.label(t!("Games played: {}", stats.games_played()));
+                if let Some(efficiency) = stats.average_efficiency() {
+                    ui.label(t!("Average efficiency: {}%", format!("{:.0}", efficiency * 100.0)));
+                }
+                if let Some(time) = stats.average_time() {
+                    ui.label(t!("Average time: {}", format_clock(time)));
+                }
+                let (won, lost) = stats.challenge_results();
+                if won + lost > 0 {
+                    ui.label(t!("Challenges: {} won, {} lost", won, lost));
+                }
+                if let Some(best) = self.marathon_records.runs().first() {
+                    ui.label(t!(
+                        "Best marathon: {} points over {} stages",
+                        best.score, best.stages_cleared
+                    ));
+                }
+
+                ui.separator();
+                ui.heading(t!("Best Times"));
+                let entries = tab.controller.best_times().entries();
+                if entries.is_empty() {
+                    ui.label(t!("No completed games yet."));
+                } else {
+                    egui::Grid::new("best_times_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong(t!("Grid"));
+                            ui.strong(t!("Difficulty"));
+                            ui.strong(t!("Best time"));
+                            ui.end_row();
+                            for (size, difficulty, time) in entries {
+                                ui.label(format!("{}×{}", size, size));
+                                ui.label(difficulty_name(difficulty));
+                                ui.label(format_clock(time));
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.separator();
+                let achievements = tab.controller.achievements();
+                ui.heading(t!(
+                    "Achievements ({}/{})",
+                    achievements.unlocked_count(),
+                    Achievement::ALL.len()
+                ));
+                egui::Grid::new("achievements_grid").striped(true).show(ui, |ui| {
+                    for achievement in Achievement::ALL {
+                        if achievements.is_unlocked(achievement) {
+                            ui.strong(format!("🏆 {}", tr_value(&achievement)));
+                        } else {
+                            ui.weak(format!("🔒 {}", tr_value(&achievement)));
+                        }
+                        ui.label(tr(achievement.description()));
+                        ui.end_row();
+                    }
+                });
+
+                let recent: Vec<_> = stats.games().iter().rev().take(10).collect();
+                if !recent.is_empty() {
+                    ui.separator();
+                    ui.heading(t!("Recent Games"));
+                    egui::Grid::new("recent_games_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong(t!("Grid"));
+                            ui.strong(t!("Difficulty"));
+                            ui.strong(t!("Moves"));
+                            ui.strong(t!("Tiles"));
+                            ui.strong(t!("Par"));
+                            ui.strong(t!("Grade"));
+                            ui.strong(t!("Time"));
+                            ui.end_row();
+                            for game in recent {
+                                ui.label(format!("{}×{}", game.grid_size, game.grid_size));
+                                ui.label(difficulty_name(game.difficulty));
+                                ui.label(if game.failed {
+                                    t!("{} (lost)", game.moves)
+                                } else if game.assisted {
+                                    t!("{} (auto)", game.moves)
+                                } else {
+                                    game.moves.to_string()
+                                });
+                                // Records from before tile counting have none
+                                ui.label(if game.tile_moves == 0 {
+                                    "--".to_string()
+                                } else {
+                                    game.tile_moves.to_string()
+                                });
+                                ui.label(
+                                    game.optimal_length
+                                        .map_or("--".to_string(), |n| n.to_string()),
+                                );
+                                ui.label(game.grade().map_or("--".to_string(), |g| g.to_string()));
+                                ui.label(format_clock(game.time()));
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+        // Analysis window rating each move of the solved game
+        egui::Window::new(t!("Analysis"))
+            .id(egui::Id::new("analysis_window"))
+            .open(&mut self.show_analysis)
+            .resizable(false)
+            .show(ctx, |ui| match tab.controller.analysis() {
+                Some(Ok(analysis)) => analysis_timeline(ui, analysis),
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::RED, t!("Analysis failed: {}", e));
+                }
+                None if tab.controller.is_analyzing() => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(t!("Replaying the game against optimal play…"));
+                    });
+                }
+                None => {
+                    ui.label(t!("Solve a game, then rate each of its moves against optimal play."));
+                    if ui
+                        .add_enabled(tab.controller.can_analyze(), egui::Button::new(t!("Analyze")))
+                        .on_disabled_hover_text(t!("Needs a solved board whose every move was recorded (not a restored or layered game)"))
+                        .clicked()
+                    {
+                        tab.controller.start_analysis();
+                    }
+                }
+            });
+
+        // Search window plotting the background solver's progress as it runs
+        tab.controller.set_search_tracing(self.show_search);
+        egui::Window::new(t!("Search"))
+            .id(egui::Id::new("search_window"))
+            .open(&mut self.show_search)
+            .resizable(false)
+            .show(ctx, |ui| match tab.controller.search_trace() {
+                Some(trace) if trace.latest().is_some() => {
+                    search_plot(ui, trace);
+                    ui.checkbox(&mut self.show_search_ghosts, t!("Ghost boards"))
+                        .on_hover_text(t!("Show the last boards the solver expanded, newest on the right"));
+                    if self.show_search_ghosts {
+                        ui.horizontal(|ui| {
+                            for board in trace.ghosts() {
+                                ghost_board(ui, board);
+                            }
+                        });
+                    }
+                }
+                _ => {
+                    ui.label(t!("Ask for a hint or an auto-solve on a hard board to watch the solver search."));
+                    if tab.controller.is_solver_computing() {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(t!("Waiting for the first progress report…"));
+                        });
+                    }
+                }
+            });
+
+        // Macros window for recording and replaying move sequences
+        egui::Window::new(t!("Macros"))
+            .id(egui::Id::new("macros_window"))
+            .open(&mut self.show_macros)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if tab.controller.is_recording() {
+                    ui.label(t!("Recording: {} moves so far", tab.controller.recorded_moves()));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.macro_name).hint_text("3-cycle top-left"));
+                        if ui.button(t!("Save")).clicked() {
+                            match tab.controller.finish_recording(&self.macro_name) {
+                                Ok(pattern) if self.macros.add(pattern.clone()) => {
+                                    tab.status_message = Some(t!("Saved macro {}", pattern));
+                                    self.macro_name.clear();
+                                }
+                                Ok(_) => {
+                                    tab.status_message =
+                                        Some(warning(t!("At most {} macros; delete one first", MAX_MACROS)));
+                                }
+                                Err(e) => tab.status_message = Some(warning(e)),
+                            }
+                        }
+                        if ui.button(t!("Cancel")).clicked() {
+                            tab.controller.cancel_recording();
+                        }
+                    });
+                } else if ui
+                    .button(t!("Record"))
+                    .on_hover_text(t!("Play the moves to record, then name and save them"))
+                    .clicked()
+                {
+                    tab.controller.start_recording();
+                }
+
+                ui.separator();
+                if self.macros.is_empty() {
+                    ui.label(t!("No macros yet"));
+                }
+                let mut play = None;
+                let mut remove = None;
+                egui::Grid::new("macro_grid").show(ui, |ui| {
+                    for (index, pattern) in self.macros.macros().iter().enumerate() {
+                        ui.label(format!("{}", index + 1));
+                        ui.label(pattern.to_string());
+                        let fits = !tab.is_animating() && tab.controller.macro_moves(pattern).is_some();
+                        if ui
+                            .add_enabled(fits, egui::Button::new(t!("Play")))
+                            .on_disabled_hover_text(t!("Does not fit at the empty cell"))
+                            .clicked()
+                        {
+                            play = Some(index);
+                        }
+                        if ui.small_button(t!("Delete")).clicked() {
+                            remove = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(pattern) = play.and_then(|index| self.macros.get(index)) {
+                    tab.play_macro(pattern, &self.animation_settings);
+                }
+                if let Some(index) = remove {
+                    self.macros.remove(index);
+                }
+            });
+
+        // Settings window with display preferences shared by all tabs
+        let mut save_config = false;
+        egui::Window::new(t!("Settings"))
+            .id(egui::Id::new("settings_window"))
+            .open(&mut self.show_settings)
+            .resizable(false)
+            .show(ctx, |ui| {
+                // Languages are named in themselves, so the list reads the same in any of them
+                let mut language = self.config.language;
+                egui::ComboBox::from_label(t!("Language"))
+                    .selected_text(language.to_string())
+                    .show_ui(ui, |ui| {
+                        for locale in Locale::ALL {
+                            ui.selectable_value(&mut language, locale, locale.to_string());
+                        }
+                    });
+                if language != self.config.language {
+                    self.config.language = language;
+                    i18n::set_locale(language);
+                }
+                egui::ComboBox::from_label(t!("Theme"))
+                    .selected_text(tr_value(&self.config.theme))
+                    .show_ui(ui, |ui| {
+                        for theme in Theme::ALL {
+                            ui.selectable_value(&mut self.config.theme, theme, tr_value(&theme));
+                        }
+                    });
+                egui::ComboBox::from_label(t!("Tile colors"))
+                    .selected_text(tr_value(&self.color_mode))
+                    .show_ui(ui, |ui| {
+                        for mode in ColorMode::ALL {
+                            ui.selectable_value(&mut self.color_mode, mode, tr_value(&mode));
+                        }
+                    });
+                ui.checkbox(&mut self.colorblind, t!("Colorblind mode")).on_hover_text(
+                    t!("Show distance from home by brightness, border thickness and hatching instead of hue"),
+                );
+
+                egui::ComboBox::from_label(t!("Tile numbers"))
+                    .selected_text(tr_value(&self.number_style))
+                    .show_ui(ui, |ui| {
+                        for style in NumberStyle::ALL {
+                            ui.selectable_value(&mut self.number_style, style, tr_value(&style));
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("How numbered tiles are written; letters and custom labels are unaffected"));
+
+                ui.separator();
+                egui::ComboBox::from_label(t!("Tile labels"))
+                    .selected_text(self.label_style.to_string())
+                    .show_ui(ui, |ui| {
+                        for style in LabelStyle::ALL {
+                            ui.selectable_value(&mut self.label_style, style, style.to_string());
+                        }
+                    });
+                if self.label_style == LabelStyle::Text {
+                    ui.text_edit_singleline(&mut self.label_text).on_hover_text(
+                        t!("One letter per tile in goal order (spaces ignored), or labels separated by commas"),
+                    );
+                }
+                if ui.button(t!("Apply to this board")).clicked() {
+                    let labels = self.label_style.labels(&self.label_text);
+                    self.label_error = self.tabs[self.active]
+                        .sliding_mut()
+                        .and_then(|tab| tab.controller.set_tile_labels(labels).err())
+                        .map(|e| e.to_string());
+                }
+                if let Some(error) = &self.label_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                #[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+                if let Some(tab) = self.tabs[self.active].sliding_mut() {
+                    ui.horizontal(|ui| {
+                        for source in CaptureSource::ALL {
+                            if ui
+                                .button(t!("Scramble {}", tr(&source.to_string().to_lowercase())))
+                                .on_hover_text(t!("Capture a picture for the tiles and shuffle the board"))
+                                .clicked()
+                            {
+                                tab.scramble_capture(ctx, source);
+                            }
+                        }
+                        if tab.renderer.picture().is_some() && ui.button(t!("Remove picture")).clicked() {
+                            tab.renderer.set_picture(None);
+                        }
+                    });
+                }
+
+                if let Some(tab) = self.tabs[self.active].sliding_mut() {
+                    let mut goal = tab.controller.goal();
+                    egui::ComboBox::from_label(t!("Goal"))
+                        .selected_text(tr_value(&goal))
+                        .show_ui(ui, |ui| {
+                            for option in GoalSpec::ALL {
+                                ui.selectable_value(&mut goal, option, tr_value(&option));
+                            }
+                        })
+                        .response
+                        .on_hover_text(t!("Solved layout for this board; changing it resets the board"));
+                    if goal != tab.controller.goal() {
+                        tab.controller.set_goal(goal);
+                    }
+                }
+
+                ui.separator();
+                let settings = &mut self.animation_settings;
+                ui.checkbox(&mut settings.reduce_motion, t!("Reduce motion"))
+                    .on_hover_text(t!("Tiles jump instead of sliding, and highlights hold steady instead of flashing"));
+                ui.add_enabled_ui(!settings.reduce_motion, |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut settings.duration_ms, 0..=MAX_SLIDE_MS)
+                            .text(t!("Slide (ms)"))
+                            .custom_formatter(|ms, _| {
+                                if ms == 0.0 {
+                                    t!("instant").to_string()
+                                } else {
+                                    format!("{}", ms)
+                                }
+                            }),
+                    );
+                    egui::ComboBox::from_label(t!("Easing"))
+                        .selected_text(settings.easing.to_string())
+                        .show_ui(ui, |ui| {
+                            for easing in Easing::ALL {
+                                ui.selectable_value(&mut settings.easing, easing, easing.to_string());
+                            }
+                        });
+                });
+                ui.add(
+                    egui::Slider::new(&mut settings.auto_solve_interval_ms, 0..=MAX_AUTO_SOLVE_INTERVAL_MS)
+                        .text(t!("Auto-solve step (ms)")),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(t!("Instant"))
+                        .on_hover_text(t!("No slides and no pause between auto-solve moves"))
+                        .clicked()
+                    {
+                        settings.duration_ms = 0;
+                        settings.auto_solve_interval_ms = 0;
+                    }
+                    if ui.button(t!("Defaults")).clicked() {
+                        *settings = AnimationSettings::default();
+                    }
+                });
+
+                ui.separator();
+                if ui
+                    .button(t!("Save as defaults"))
+                    .on_hover_text(
+                        t!("Start future sessions with this board size, difficulty, solver, language, theme, \
+                         tile colors and animation timing"),
+                    )
+                    .clicked()
+                {
+                    save_config = true;
+                }
+                if let Some(status) = &self.config_status {
+                    ui.label(status);
+                }
+                if ui.button(t!("Replay tutorial")).clicked() {
+                    self.tutorial.restart();
+                }
+            });
+        if save_config {
+            self.save_config();
+        }
+
+        // Tutorial card for the current step
+        if let Some(step) = self.tutorial.step() {
+            let solved = self.tabs[self.active]
+                .sliding()
+                .is_some_and(|tab| tab.controller.is_solved());
+            let mut event = None;
+            egui::Window::new(t!("Tutorial"))
+                .id(egui::Id::new("tutorial_window"))
+                .title_bar(false)
+                .resizable(false)
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+                .show(ctx, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.heading(tr(step.title()));
+                    ui.label(tr(step.text()));
+                    if step == TutorialStep::AutoSolve && solved {
+                        ui.label(t!("The board is solved already; press New Game first."));
+                    }
+                    ui.horizontal(|ui| {
+                        if step.waits_for_next() {
+                            let label = if step == TutorialStep::Finished { t!("Done") } else { t!("Next") };
+                            if ui.button(label).clicked() {
+                                event = Some(TutorialEvent::Next);
+                            }
+                        }
+                        if step != TutorialStep::Finished && ui.button(t!("Skip tutorial")).clicked() {
+                            event = Some(TutorialEvent::Skip);
+                        }
+                    });
+                });
+            if let Some(event) = event {
+                self.tutorial.handle(event);
+            }
+        }
+
+        // Import window for entering a share code or puzzle in text notation
+        let mut show_load_board = self.show_load_board;
+        let mut load_clicked = false;
+        let mut play_clicked = false;
+        egui::Window::new(t!("Import Board"))
+            .id(egui::Id::new("import_board_window"))
+            .open(&mut show_load_board)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(t!("Paste a share code, or rows separated by '/' or new lines with '_' for the empty cell:"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.board_text)
+                        .code_editor()
+                        .desired_rows(4)
+                        .hint_text("1 2 3 / 4 _ 6 / 7 5 8"),
+                );
+                if let Some(ref error) = self.load_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                if ui.button(t!("Load")).clicked() {
+                    load_clicked = true;
+                }
+                ui.separator();
+                ui.label(t!("Paste a solution in blank moves (U, D, L, R) to play it on the current board:"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.solution_text)
+                        .code_editor()
+                        .hint_text("RDLURD"),
+                );
+                if ui.button(t!("Play solution")).clicked() {
+                    play_clicked = true;
+                }
+            });
+        self.show_load_board &= show_load_board;
+        if load_clicked {
+            self.load_board();
+        }
+        if play_clicked {
+            self.play_solution();
+        }
+
+        let Some(tab) = self.tabs[self.active].sliding_mut() else {
+            return;
+        };
+        tab.renderer.set_color_mode(self.color_mode);
+        tab.renderer.set_colorblind(self.colorblind);
+        tab.renderer.set_number_style(self.number_style);
+
+        // Technique suggestions; hovering one marks the tiles it moves
+        let mut technique_tiles = Vec::new();
+        if self.show_techniques {
+            egui::SidePanel::right("techniques").show(ctx, |ui| {
+                ui.heading(t!("Techniques"));
+                let suggestions = if tab.is_animating() { Vec::new() } else { tab.controller.technique_suggestions() };
+                if suggestions.is_empty() {
+                    ui.label(t!("Nothing fits here right now"));
+                }
+                let mut play = None;
+                for suggestion in suggestions {
+                    let response = ui
+                        .button(t!("{} available here", suggestion.pattern.name()))
+                        .on_hover_text(t!(
+                            "{} moves; Manhattan {}",
+                            suggestion.moves.len(),
+                            format!("{:+}", suggestion.distance_change)
+                        ));
+                    if response.hovered() {
+                        technique_tiles = suggestion.moves.clone();
+                    }
+                    if response.clicked() {
+                        play = Some(suggestion.pattern);
+                    }
+                }
+                if let Some(pattern) = play {
+                    tab.play_macro(&pattern, &self.animation_settings);
+                }
+            });
+        }
+
+        // Central panel with puzzle grid
+        let timer = PerformanceTimer::start();
+        CentralPanel::default().show(ctx, |ui| {
+            let grid_size = tab.renderer.grid_size(tab.controller.state().size());
+
+            // Center the grid
+            let available = ui.available_size();
+            let top_left = Pos2::new(
+                (available.x - grid_size.x) / 2.0 + ui.min_rect().left(),
+                (available.y - grid_size.y) / 2.0 + ui.min_rect().top(),
+            );
+
+            // Collect clicked position before modifying state
             let mut clicked_pos = None;
 
-            // Only allow clicks if no animation is running
-            let can_interact = self.animation.is_none();
+            // Only allow clicks if no animation is running and the board is not being shuffled
+            let can_interact = tab.animation.is_none() && !tab.controller.is_shuffling();
 
             // Render empty cell first (so it appears behind animating tiles)
-            let empty_pos = self.controller.state().empty_position();
-            self.renderer.render_empty(ui, empty_pos, top_left);
+            let empty_pos = tab.controller.state().empty_position();
+            tab.renderer.render_empty(ui, empty_pos, top_left);
 
             // Render all tiles (with animation if active)
-            for (pos, tile) in self.controller.state().tiles() {
-                let render_pos = if let Some(ref anim) = self.animation {
+            for (pos, tile) in tab.controller.state().tiles() {
+                let render_pos = if let Some(ref anim) = tab.animation {
                     // Check if this is the animating tile
                     if pos == anim.tile_pos {
                         let (row, col) = anim.current_pos();
@@ -308,39 +2932,607 @@ impl eframe::App for GuiPresenter {
                     (pos.0 as f32, pos.1 as f32)
                 };
 
-                let clicked = self.renderer.render_tile_at(ui, tile, pos, render_pos, top_left);
+                let clicked = tab.renderer.render_tile_at(ui, tile, pos, render_pos, top_left);
                 if clicked && can_interact {
                     clicked_pos = Some(pos);
                 }
             }
 
+            // Mark every tile a click would shift, so chain moves are discoverable
+            if can_interact {
+                let hovered = ui
+                    .input(|i| i.pointer.hover_pos())
+                    .and_then(|pointer| tab.renderer.cell_at(top_left, pointer));
+                if let Some(preview) = hovered.and_then(|pos| tab.controller.preview_move(pos)) {
+                    tab.renderer.render_tile_set_highlight(ui, preview.tiles(), top_left);
+                }
+
+                // Wheel and trackpad scrolls slide the empty cell's row or column
+                let delta = ui.input(|i| i.raw_scroll_delta);
+                match hovered {
+                    Some(pos) if delta != egui::Vec2::ZERO => {
+                        if let Some(moves) = tab.scroll_over(pos, delta) {
+                            tab.hint = None;
+                            self.tutorial.handle(TutorialEvent::Moved { tiles: moves.len() });
+                            tab.animate_moves(&moves, &self.animation_settings);
+                        }
+                    }
+                    Some(_) => {}
+                    None => tab.scroll = egui::Vec2::ZERO,
+                }
+            }
+
+            // Heatmap of the solution, hidden mid-slide while the order is changing
+            if self.show_heatmap && tab.animation.is_none() {
+                if let Some(order) = tab.controller.solution_order() {
+                    let steps = order.values().max().map_or(0, |last| last + 1);
+                    for (&pos, &step) in &order {
+                        tab.renderer.render_tint(ui, pos, top_left, heatmap_color(step, steps));
+                    }
+                }
+            }
+
+            // Faint solved board over the tiles, so targets can be read at a glance
+            if self.show_ghost {
+                for (_, tile) in tab.controller.state().tiles() {
+                    tab.renderer.render_ghost(ui, tile, top_left);
+                }
+            }
+
+            // Flash the hinted tile until the hint expires
+            if let Some((hint_pos, requested_at)) = tab.hint {
+                let elapsed = requested_at.elapsed().as_millis();
+                if elapsed < HINT_FLASH_MS && tab.animation.is_none() {
+                    let phase = elapsed as f32 / 250.0 * std::f32::consts::PI;
+                    let intensity = self.animation_settings.pulse(phase);
+                    tab.renderer.render_highlight(ui, hint_pos, top_left, intensity);
+                    ctx.request_repaint();
+                } else {
+                    tab.hint = None;
+                }
+            }
+
+            // Mark the tiles of the hovered technique
+            if tab.animation.is_none() {
+                for &pos in &technique_tiles {
+                    tab.renderer.render_highlight(ui, pos, top_left, 0.6);
+                }
+            }
+
+            // Ring the gamepad's chain-move target while it is in line with the empty cell
+            let line_target = tab.line_target.filter(|pos| input::line_targets(tab.controller.state()).contains(pos));
+            if let Some(pos) = line_target.filter(|_| tab.animation.is_none()) {
+                tab.renderer.render_highlight(ui, pos, top_left, 1.0);
+            }
+
+            // Pulse the tiles the tutorial is talking about
+            if self.tutorial.is_active() && tab.animation.is_none() {
+                let phase = ui.input(|i| i.time) as f32 * std::f32::consts::TAU;
+                let intensity = self.animation_settings.pulse(phase);
+                for pos in self.tutorial.highlights(&tab.controller) {
+                    tab.renderer.render_highlight(ui, pos, top_left, intensity);
+                }
+                ctx.request_repaint();
+            }
+
             // Handle click after rendering (start animation sequence)
             if let Some(pos) = clicked_pos {
-                let old_empty = self.controller.state().empty_position();
-                if let Some(move_sequence) = self.controller.handle_click(pos) {
-                    // Move was successful - queue animations for all moves in sequence
-                    if !move_sequence.is_empty() {
-                        // Start first animation immediately
-                        let first_move = move_sequence[0];
-                        self.animation = Some(TileAnimation::new(
-                            first_move,
-                            first_move,
-                            old_empty,
-                            200,
-                        ));
+                tab.hint = None;
+                if let Some(move_sequence) = tab.controller.handle_click(pos) {
+                    self.tutorial.handle(TutorialEvent::Moved {
+                        tiles: move_sequence.len(),
+                    });
+                    // Move was successful - animate every tile in the sequence
+                    tab.animate_moves(&move_sequence, &self.animation_settings);
+                }
+            } else if can_interact {
+                // Shortcuts from slider.toml, then gamepad buttons, as the same actions
+                let mut actions = Vec::new();
+                if !ctx.wants_keyboard_input() {
+                    // Digit keys replay the saved macros
+                    let pressed = MACRO_KEYS.iter().position(|&key| ctx.input(|i| i.key_pressed(key)));
+                    if let Some(pattern) = pressed.and_then(|index| self.macros.get(index)) {
+                        tab.play_macro(pattern, &self.animation_settings);
+                    }
+                    actions = input::pressed_actions(ctx, &self.config.keys);
+                }
+                actions.extend(pad_actions.iter().copied());
 
-                        // Queue remaining animations (for chain moves)
-                        if move_sequence.len() > 1 {
-                            self.animation_queue = move_sequence[1..].to_vec();
+                let action = actions.first().copied();
+                let tile = match action {
+                    Some(InputAction::Slide(direction)) => tab.controller.tile_for_slide(direction),
+                    Some(InputAction::Confirm) => line_target,
+                    _ => None,
+                };
+                if let Some(moves) = tile.and_then(|pos| tab.controller.handle_click(pos)) {
+                    tab.hint = None;
+                    tab.line_target = None;
+                    self.tutorial.handle(TutorialEvent::Moved { tiles: moves.len() });
+                    tab.animate_moves(&moves, &self.animation_settings);
+                } else {
+                    match action {
+                        Some(InputAction::PreviousTarget) => {
+                            tab.line_target = input::step_target(tab.controller.state(), line_target, false);
+                        }
+                        Some(InputAction::NextTarget) => {
+                            tab.line_target = input::step_target(tab.controller.state(), line_target, true);
+                        }
+                        Some(InputAction::Hint) if !tab.controller.is_auto_solving() => {
+                            tab.hint = tab.controller.get_hint().map(|pos| (pos, Instant::now()));
+                        }
+                        Some(InputAction::NewGame) if tab.marathon.is_none() => {
+                            tab.start_game(tab.chosen_difficulty());
+                        }
+                        Some(InputAction::AutoSolve) if tab.controller.start_auto_solve() => {
+                            tab.status_message = Some(t!("Computing solution in background...").to_string());
+                            self.tutorial.handle(TutorialEvent::AutoSolveStarted);
                         }
+                        _ => {}
                     }
                 }
             }
         });
+        self.frame_metrics.current.board_micros += timer.elapsed_micros();
+
+        // Victory screen for a just-finished shuffled game
+        if let Some(game) = tab.victory.clone() {
+            let mut action = None;
+            let modal = egui::Modal::new(egui::Id::new("victory")).show(ctx, |ui| {
+                ui.heading(t!("Solved!"));
+                if tab.controller.is_new_best() {
+                    ui.colored_label(egui::Color32::GOLD, t!("New best time!"));
+                }
+                ui.label(format!("{}×{} {}", game.grid_size, game.grid_size, difficulty_name(game.difficulty)));
+                if let Some(grade) = game.grade() {
+                    ui.label(egui::RichText::new(t!("Grade {}", grade)).size(32.0).strong());
+                }
+
+                egui::Grid::new("victory_grid").show(ui, |ui| {
+                    ui.label(t!("Moves"));
+                    ui.strong(if game.assisted {
+                        t!("{} (auto)", game.moves)
+                    } else {
+                        game.moves.to_string()
+                    });
+                    ui.end_row();
+                    ui.label(t!("Tile moves"));
+                    ui.strong(game.tile_moves.to_string());
+                    ui.end_row();
+                    ui.label(t!("Time"));
+                    ui.strong(format_clock(game.time()));
+                    ui.end_row();
+                    ui.label(t!("Par"));
+                    ui.strong(game.optimal_length.map_or("--".to_string(), |n| n.to_string()));
+                    ui.end_row();
+                    ui.label(t!("Efficiency"));
+                    ui.strong(
+                        game.efficiency()
+                            .map_or("--".to_string(), |e| format!("{:.0}%", e * 100.0)),
+                    );
+                    ui.end_row();
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(t!("New Game"))
+                        .on_hover_text(t!("Shuffle with the difficulty selected in the toolbar"))
+                        .clicked()
+                    {
+                        action = Some(VictoryAction::NewGame);
+                    }
+                    if ui
+                        .button(t!("Same Difficulty"))
+                        .on_hover_text(t!("Shuffle another {} board", difficulty_name(game.difficulty)))
+                        .clicked()
+                    {
+                        action = Some(VictoryAction::SameDifficulty);
+                    }
+                    if ui
+                        .button(t!("Share"))
+                        .on_hover_text(t!("Copy your result and a share code for the starting board"))
+                        .clicked()
+                    {
+                        action = Some(VictoryAction::Share);
+                    }
+                    if tab.controller.can_analyze()
+                        && ui
+                            .button(t!("Analyze"))
+                            .on_hover_text(t!("Rate each of your moves against optimal play"))
+                            .clicked()
+                    {
+                        action = Some(VictoryAction::Analyze);
+                    }
+                    #[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+                    if tab.controller.replay().is_some() {
+                        for format in ReplayFormat::ALL {
+                            if ui
+                                .button(t!("Export {}", format))
+                                .on_hover_text(t!("Save an animated replay of this solve as {}", format))
+                                .clicked()
+                            {
+                                action = Some(VictoryAction::Export(format));
+                            }
+                        }
+                    }
+                    if ui.button(t!("Close")).clicked() {
+                        action = Some(VictoryAction::Close);
+                    }
+                });
+            });
+            if modal.should_close() {
+                action = action.or(Some(VictoryAction::Close));
+            }
+
+            match action {
+                Some(VictoryAction::Close) => tab.victory = None,
+                Some(VictoryAction::NewGame) => {
+                    tab.victory = None;
+                    tab.start_game(tab.chosen_difficulty());
+                }
+                Some(VictoryAction::SameDifficulty) => {
+                    tab.victory = None;
+                    tab.start_game(game.difficulty);
+                }
+                Some(VictoryAction::Share) => {
+                    let text = tab.share_text(&game);
+                    ctx.copy_text(text);
+                    tab.status_message = Some(t!("Result copied to clipboard").to_string());
+                    tab.victory = None;
+                }
+                Some(VictoryAction::Analyze) => {
+                    tab.victory = None;
+                    tab.controller.start_analysis();
+                    self.show_analysis = true;
+                }
+                #[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+                Some(VictoryAction::Export(format)) => {
+                    tab.victory = None;
+                    tab.export_replay(format);
+                }
+                None => {}
+            }
+        }
+
+        // Summary of the marathon that just ended, with the best runs so far
+        if let Some((summary, rank)) = self.marathon_summary.clone() {
+            let mut open = true;
+            let mut again = false;
+            egui::Window::new(t!("Marathon Summary"))
+                .id(egui::Id::new("marathon_summary_window"))
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    match rank {
+                        Some(0) => {
+                            ui.colored_label(egui::Color32::GOLD, t!("New best marathon!"));
+                        }
+                        Some(rank) => {
+                            ui.label(t!("#{} among your best runs", rank + 1));
+                        }
+                        None => {}
+                    }
+                    egui::Grid::new("marathon_summary_grid").show(ui, |ui| {
+                        ui.label(t!("Score"));
+                        ui.strong(summary.score.to_string());
+                        ui.end_row();
+                        ui.label(t!("Stages cleared"));
+                        ui.strong(summary.stages_cleared.to_string());
+                        ui.end_row();
+                        ui.label(t!("Ended on"));
+                        ui.strong(format!(
+                            "{}×{} {}",
+                            summary.reached_size, summary.reached_size, difficulty_name(summary.reached_difficulty)
+                        ));
+                        ui.end_row();
+                        ui.label(t!("Play time"));
+                        ui.strong(format_clock(summary.time()));
+                        ui.end_row();
+                    });
+
+                    ui.separator();
+                    ui.heading(t!("Best Runs"));
+                    egui::Grid::new("marathon_runs_grid").striped(true).show(ui, |ui| {
+                        ui.strong("#");
+                        ui.strong(t!("Score"));
+                        ui.strong(t!("Stages"));
+                        ui.strong(t!("Reached"));
+                        ui.strong(t!("Time"));
+                        ui.end_row();
+                        for (i, run) in self.marathon_records.runs().iter().enumerate() {
+                            ui.label((i + 1).to_string());
+                            ui.label(run.score.to_string());
+                            ui.label(run.stages_cleared.to_string());
+                            ui.label(format!("{}×{} {}", run.reached_size, run.reached_size, difficulty_name(run.reached_difficulty)));
+                            ui.label(format_clock(run.time()));
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.separator();
+                    again = ui.button(t!("Run Again")).clicked();
+                });
+            if again {
+                tab.start_marathon();
+            }
+            if again || !open {
+                self.marathon_summary = None;
+            }
+        }
+
+        if let Some(summary) = ended_marathon {
+            self.finish_marathon(summary);
+        }
+    }
+}
+
+/// Names a difficulty in the current locale
+fn difficulty_name(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Custom { min_entropy } => t!("Custom ({}+)", min_entropy),
+        preset => tr_value(&preset),
+    }
+}
+
+/// Names a challenge mode in the current locale
+fn game_mode_name(mode: GameMode) -> String {
+    match mode {
+        GameMode::Free => t!("Free play").to_string(),
+        GameMode::MoveLimit { slack } => t!("Par + {} moves", slack),
+        GameMode::Countdown { seconds } => t!("{} countdown", format!("{}:{:02}", seconds / 60, seconds % 60)),
+    }
+}
+
+/// Describes a step of a strategic auto-solve in the current locale
+fn solve_step_text(step: &SolveStep) -> String {
+    match step {
+        SolveStep::PlaceTile { tile } => t!("Place tile {}", tile),
+        SolveStep::FinishRow { row, tiles: [a, b] } => {
+            t!("Finish row {} by swinging tiles {} and {} in together", row + 1, a, b)
+        }
+        SolveStep::FinishColumn { column, tiles: [a, b] } => {
+            t!("Finish column {} by swinging tiles {} and {} in together", column + 1, a, b)
+        }
+        SolveStep::CycleBlock { tiles } => t!("Cycle tiles {} around the last 2×2 block", tiles.join(", ")),
+        SolveStep::SolveCorner { tiles } => {
+            t!("Sort tiles {} in the last 3×3 block, in the fewest moves", tiles.join(", "))
+        }
+    }
+}
+
+/// Formats a play time as m:ss.t
+fn format_clock(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, (tenths / 10) % 60, tenths % 10)
+}
+
+/// Plots the heuristic distance after each move as a small line chart
+///
+/// A falling line means the board is converging on the goal; a jagged flat one
+/// means moves are being undone as fast as they are made.
+fn distance_sparkline(ui: &mut egui::Ui, trail: &[u32]) {
+    let size = egui::vec2(120.0, 18.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    if trail.len() < 2 {
+        return;
     }
+    let peak = trail.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let step = rect.width() / (trail.len() - 1) as f32;
+    let points: Vec<Pos2> = trail
+        .iter()
+        .enumerate()
+        .map(|(index, &distance)| {
+            Pos2::new(
+                rect.left() + index as f32 * step,
+                rect.bottom() - rect.height() * distance as f32 / peak,
+            )
+        })
+        .collect();
+    let stroke = egui::Stroke::new(1.5, ui.visuals().selection.stroke.color);
+    ui.painter_at(rect.expand(1.0)).add(egui::Shape::line(points, stroke));
+
+    let start = trail[0];
+    let now = trail[trail.len() - 1];
+    let lowest = trail.iter().copied().min().unwrap_or(0);
+    response.on_hover_text(t!(
+        "Heuristic distance after each move\nStart: {}, now: {}, lowest: {}",
+        start, now, lowest
+    ));
+}
+
+/// Draws a bar per kept frame, red where it went over the frame budget
+fn frame_time_bars(ui: &mut egui::Ui, metrics: &FrameMetrics) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(240.0, 40.0), egui::Sense::hover());
+    // Bars are scaled to twice the budget, so the budget line sits halfway up
+    let scale = rect.height() / (2 * FRAME_BUDGET_MICROS) as f32;
+    let width = rect.width() / metrics.frames().len().max(1) as f32;
+    let painter = ui.painter_at(rect);
+    for (i, frame) in metrics.frames().enumerate() {
+        let height = (frame.total_micros as f32 * scale).min(rect.height());
+        let left = rect.left() + i as f32 * width;
+        let bar = egui::Rect::from_min_max(
+            Pos2::new(left, rect.bottom() - height),
+            Pos2::new(left + width.max(1.0), rect.bottom()),
+        );
+        let color = if frame.total_micros > FRAME_BUDGET_MICROS {
+            ui.visuals().error_fg_color
+        } else {
+            ui.visuals().selection.stroke.color
+        };
+        painter.rect_filled(bar, 0.0, color);
+    }
+    let budget = rect.bottom() - rect.height() / 2.0;
+    painter.hline(rect.x_range(), budget, egui::Stroke::new(1.0, ui.visuals().weak_text_color()));
+}
+
+/// Plots a traced search: f-score and frontier size against nodes expanded
+fn search_plot(ui: &mut egui::Ui, trace: &SearchTrace) {
+    let samples = trace.samples();
+    let Some(latest) = trace.latest() else {
+        return;
+    };
+    ui.label(t!(
+        "{} · {} · {} nodes in {}s",
+        tr_value(&trace.kind()),
+        if trace.is_finished() { t!("finished") } else { t!("searching") },
+        latest.nodes_explored,
+        format!("{:.1}", latest.elapsed.as_secs_f64())
+    ));
+    let f_color = egui::Color32::from_rgb(230, 150, 60);
+    let frontier_color = egui::Color32::from_rgb(90, 160, 230);
+    ui.horizontal(|ui| {
+        ui.colored_label(f_color, t!("f-score: {}", latest.f_score));
+        ui.colored_label(
+            frontier_color,
+            t!("Frontier: {} (peak {})", latest.frontier_size, trace.peak_frontier()),
+        );
+    });
+
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(360.0, 120.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect.expand(1.0));
+    painter.rect_stroke(rect, 0.0, (1.0, ui.visuals().widgets.noninteractive.bg_stroke.color));
+    if samples.len() < 2 {
+        return;
+    }
+    let last_nodes = latest.nodes_explored.max(1) as f32;
+    let x = |nodes: usize| rect.left() + rect.width() * nodes as f32 / last_nodes;
+    // f-scores only creep up, so the plot spans the range seen rather than starting at zero
+    let low_f = samples.iter().map(|s| s.f_score).min().unwrap_or(0) as f32;
+    let high_f = samples.iter().map(|s| s.f_score).max().unwrap_or(0) as f32;
+    let peak_frontier = trace.peak_frontier().max(1) as f32;
+    let f_points = samples
+        .iter()
+        .map(|s| Pos2::new(x(s.nodes_explored), rect.bottom() - rect.height() * (s.f_score as f32 - low_f) / (high_f - low_f).max(1.0)))
+        .collect();
+    let frontier_points = samples
+        .iter()
+        .map(|s| Pos2::new(x(s.nodes_explored), rect.bottom() - rect.height() * s.frontier_size as f32 / peak_frontier))
+        .collect();
+    painter.add(egui::Shape::line(frontier_points, egui::Stroke::new(1.5, frontier_color)));
+    painter.add(egui::Shape::line(f_points, egui::Stroke::new(1.5, f_color)));
+
+    let hovered = response.hover_pos().and_then(|pos| {
+        let nodes = ((pos.x - rect.left()) / rect.width() * last_nodes) as usize;
+        samples.iter().find(|s| s.nodes_explored >= nodes)
+    });
+    if let Some(sample) = hovered {
+        response.on_hover_text_at_pointer(t!(
+            "{} nodes at {}s\nf-score {}, frontier {}",
+            sample.nodes_explored,
+            format!("{:.2}", sample.elapsed.as_secs_f64()),
+            sample.f_score,
+            sample.frontier_size
+        ));
+    }
+}
+
+/// Draws a thumbnail of a board the solver expanded, tiles already home in green
+fn ghost_board(ui: &mut egui::Ui, board: &PuzzleState) {
+    let n = board.size();
+    let cell = (64.0 / n as f32).max(6.0);
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(cell * n as f32), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    for (pos, tile) in board.tiles() {
+        let min = rect.min + egui::vec2(pos.1 as f32 * cell, pos.0 as f32 * cell);
+        let cell_rect = egui::Rect::from_min_size(min, egui::Vec2::splat(cell)).shrink(0.5);
+        let fill = if tile.home_position == pos {
+            egui::Color32::from_rgb(70, 140, 80)
+        } else {
+            egui::Color32::from_gray(90)
+        };
+        painter.rect_filled(cell_rect, 1.0, fill);
+        if cell >= 12.0 {
+            if let Some(label) = tile.content.label() {
+                painter.text(
+                    cell_rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    label,
+                    egui::FontId::monospace(cell * 0.5),
+                    egui::Color32::WHITE,
+                );
+            }
+        }
+    }
+}
+
+/// Colour of a move in the analysis timeline
+fn quality_color(quality: MoveQuality) -> egui::Color32 {
+    match quality {
+        MoveQuality::Optimal => egui::Color32::from_rgb(80, 180, 90),
+        MoveQuality::Neutral => egui::Color32::from_rgb(220, 180, 60),
+        MoveQuality::Blunder => egui::Color32::from_rgb(210, 70, 60),
+    }
+}
+
+/// Shows the move counts and a timeline with one bar per move
+///
+/// Bar height is the optimal distance left after the move, so the timeline also
+/// charts how the game closed in on the goal.
+fn analysis_timeline(ui: &mut egui::Ui, analysis: &GameAnalysis) {
+    ui.horizontal(|ui| {
+        for quality in MoveQuality::ALL {
+            ui.colored_label(quality_color(quality), format!("{}: {}", tr_value(&quality), analysis.count(quality)));
+        }
+    });
+    ui.label(t!(
+        "Optimal distance at the start: {}, tile moves wasted: {}",
+        analysis.starting_distance().unwrap_or(0),
+        analysis.wasted_moves()
+    ));
+
+    let bar_width = 6.0;
+    let height = 80.0;
+    let peak = analysis
+        .moves
+        .iter()
+        .map(|m| m.distance_before.max(m.distance_after))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    egui::ScrollArea::horizontal().max_width(480.0).show(ui, |ui| {
+        let size = egui::vec2(bar_width * analysis.moves.len() as f32, height);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        for (index, rated) in analysis.moves.iter().enumerate() {
+            let left = rect.left() + index as f32 * bar_width;
+            let top = rect.bottom() - height * (rated.distance_after as f32 / peak).max(0.05);
+            let bar = egui::Rect::from_min_max(Pos2::new(left, top), Pos2::new(left + bar_width - 1.0, rect.bottom()));
+            painter.rect_filled(bar, 0.0, quality_color(rated.quality));
+        }
+
+        let hovered = response
+            .hover_pos()
+            .map(|pos| ((pos.x - rect.left()) / bar_width) as usize)
+            .and_then(|index| analysis.moves.get(index).map(|rated| (index, rated)));
+        if let Some((index, rated)) = hovered {
+            response.on_hover_text_at_pointer(t!(
+                "Move {}: {} ({} → {}, {})",
+                index + 1,
+                tr_value(&rated.quality),
+                rated.distance_before,
+                rated.distance_after,
+                if rated.tiles.len() == 1 {
+                    t!("1 tile").to_string()
+                } else {
+                    t!("{} tiles", rated.tiles.len())
+                }
+            ));
+        }
+    });
 }
 
+/// Launches the GUI with a board of `grid_size` and the other defaults from `slider.toml`
 pub fn run_gui(grid_size: usize) -> eframe::Result<()> {
+    run_gui_with_config(Config {
+        grid_size,
+        ..Config::load_default()
+    })
+}
+
+/// Launches the GUI with the given defaults (see [`Config::load_default`])
+/// With the `gamepad` feature on Linux, a pad plugged in at launch plays too
+pub fn run_gui_with_config(config: Config) -> eframe::Result<()> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([800.0, 600.0])
@@ -352,9 +3544,16 @@ pub fn run_gui(grid_size: usize) -> eframe::Result<()> {
         "Slider Puzzle",
         options,
         Box::new(|_cc| {
-            GuiPresenter::new(grid_size)
-                .map(|p| Box::new(p) as Box<dyn eframe::App>)
-                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            let presenter = GuiPresenter::with_config(config)?;
+            #[cfg(all(feature = "gamepad", target_os = "linux"))]
+            let presenter = match crate::presenter::joystick::JoystickSource::open_first() {
+                Ok(source) => presenter.with_gamepad(Box::new(source)),
+                Err(e) => {
+                    log::info!("No gamepad: {}", e);
+                    presenter
+                }
+            };
+            Ok(Box::new(presenter) as Box<dyn eframe::App>)
         }),
     )
 }
\ No newline at end of file
@@ -0,0 +1,1183 @@
+//! Translations of the GUI's text.
+//!
+//! Every message the GUI shows is written in English in the code and looked up
+//! in [`MESSAGES`], one row per message holding its English, Spanish and
+//! German text. Messages missing from the table show in English. Placeholders
+//! are `{}`, filled in order, or `{0}`, `{1}`… for translations that need the
+//! values in another order.
+//!
+//! The language is chosen once for the whole process with [`set_locale`], from
+//! `slider.toml` at startup and from Settings while playing, so text built
+//! anywhere in the presenter follows it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::presenter::i18n::{fill, tr_in, Locale};
+//!
+//! assert_eq!(tr_in(Locale::German, "New Game"), "Neues Spiel");
+//! assert_eq!(tr_in(Locale::Spanish, "Not in the table"), "Not in the table");
+//! assert_eq!(fill(tr_in(Locale::Spanish, "Moves: {}"), &[&12]), "Movimientos: 12");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Language of the GUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    German,
+}
+
+impl Locale {
+    /// Every locale, in the order of the table's columns
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::Spanish, Locale::German];
+
+    /// Returns the ISO 639-1 code used in `slider.toml`
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+            Locale::German => "de",
+        }
+    }
+
+    /// Returns the locale with the given code, ignoring any region (`de-AT` is German)
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_']).next()?.to_lowercase();
+        Self::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    /// Column of the locale in `MESSAGES`
+    fn column(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Locale {
+    /// Names each language in itself, so it can be found whatever is showing
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::English => write!(f, "English"),
+            Locale::Spanish => write!(f, "Español"),
+            Locale::German => write!(f, "Deutsch"),
+        }
+    }
+}
+
+/// Column of the language the GUI is showing
+static LOCALE: AtomicUsize = AtomicUsize::new(0);
+
+/// Switches the GUI to `locale`
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale.column(), Ordering::Relaxed);
+}
+
+/// Returns the language the GUI is showing
+pub fn locale() -> Locale {
+    Locale::ALL[LOCALE.load(Ordering::Relaxed)]
+}
+
+/// Returns `text` in the current locale
+pub fn tr(text: &str) -> &str {
+    tr_in(locale(), text)
+}
+
+/// Returns `text` in `locale`, or `text` itself if the table has no row for it
+pub fn tr_in(locale: Locale, text: &str) -> &str {
+    static ROWS: OnceLock<HashMap<&'static str, &'static [&'static str; 3]>> = OnceLock::new();
+    let rows = ROWS.get_or_init(|| MESSAGES.iter().map(|row| (row[0], row)).collect());
+    rows.get(text).map_or(text, |row| row[locale.column()])
+}
+
+/// Returns the current locale's name for a value whose `Display` text is in the table
+pub fn tr_value(value: &impl fmt::Display) -> String {
+    tr(&value.to_string()).to_string()
+}
+
+/// Replaces the placeholders in `template` with `args`
+///
+/// `{}` takes the argument after the last one used, and `{n}` the `n`th.
+/// Placeholders without an argument are left as they are.
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        text.push_str(&rest[..start]);
+        let index = match &rest[start + 1..end] {
+            "" => Some(next),
+            digits => digits.parse().ok(),
+        };
+        match index.and_then(|index: usize| args.get(index).map(|arg| (index, arg))) {
+            Some((index, arg)) => {
+                text.push_str(&arg.to_string());
+                next = index + 1;
+            }
+            None => text.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Prefixes a message with the warning sign
+///
+/// The sign is written as an escape so the source file's encoding can never
+/// turn it into mojibake.
+pub fn warning(message: impl fmt::Display) -> String {
+    format!("\u{26A0} {}", message)
+}
+
+/// Translates a message literal into the current locale, filling in any arguments
+///
+/// `t!("Hint")` returns a `&str`; `t!("Moves: {}", count)` a `String`.
+macro_rules! t {
+    ($text:literal) => {
+        $crate::presenter::i18n::tr($text)
+    };
+    ($text:literal, $($arg:expr),+ $(,)?) => {
+        $crate::presenter::i18n::fill(
+            $crate::presenter::i18n::tr($text),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+pub(crate) use t;
+
+/// Every message with its translations: English, Spanish, German
+pub const MESSAGES: &[[&str; 3]] = &[
+    // Settings values owned by the GUI
+    ["Linear", "Lineal", "Linear"],
+    ["Ease out", "Frenado suave", "Sanft abbremsen"],
+    ["Ease in-out", "Arranque y frenado suaves", "Sanft anfahren und abbremsen"],
+    ["Numbers", "Números", "Zahlen"],
+    ["Letters", "Letras", "Buchstaben"],
+    ["Word or custom", "Palabra o personalizadas", "Wort oder eigene"],
+    ["Blank", "En blanco", "Leer"],
+    // Status messages
+    [
+        "Restored the game from the last session",
+        "Partida de la última sesión restaurada",
+        "Spiel der letzten Sitzung wiederhergestellt",
+    ],
+    [
+        "Only a solved game with every move recorded can be exported",
+        "Solo se puede exportar una partida resuelta con todos sus movimientos registrados",
+        "Nur ein gelöstes Spiel mit allen aufgezeichneten Zügen kann exportiert werden",
+    ],
+    [
+        "No data directory to save the replay in",
+        "No hay carpeta de datos donde guardar la repetición",
+        "Kein Datenverzeichnis zum Speichern der Wiederholung",
+    ],
+    ["Replay saved to {}", "Repetición guardada en {}", "Wiederholung gespeichert unter {}"],
+    [
+        "No data directory to save the image in",
+        "No hay carpeta de datos donde guardar la imagen",
+        "Kein Datenverzeichnis zum Speichern des Bildes",
+    ],
+    ["Board image saved to {}", "Imagen del tablero guardada en {}", "Bild des Spielfelds gespeichert unter {}"],
+    [
+        "Could not write the image: {}",
+        "No se pudo escribir la imagen: {}",
+        "Das Bild konnte nicht geschrieben werden: {}",
+    ],
+    ["'{}' does not fit here", "«{}» no cabe aquí", "„{}“ passt hier nicht"],
+    ["Skipped {} moves", "{} movimientos saltados", "{} Züge übersprungen"],
+    ["✗ {} — challenge lost", "✗ {}: reto perdido", "✗ {} – Herausforderung verloren"],
+    ["Stage {} cleared: +{} points", "Etapa {} superada: +{} puntos", "Etappe {} geschafft: +{} Punkte"],
+    [
+        "I solved a {}×{} {} slider puzzle in {} with {} moves ({} tile moves)",
+        "Resolví un puzle deslizante de {}×{} ({}) en {} con {} movimientos ({} movimientos de fichas)",
+        "Ich habe ein {}×{}-Schiebepuzzle ({}) in {} mit {} Zügen gelöst ({} Steinzüge)",
+    ],
+    [", par {}", ", par {}", ", Par {}"],
+    [", grade {}", ", nota {}", ", Note {}"],
+    [". Try it: {}", ". Pruébalo: {}", ". Probier es selbst: {}"],
+    // Klotski
+    ["Klotski", "Klotski", "Klotski"],
+    ["Reset", "Reiniciar", "Zurücksetzen"],
+    ["Return to the starting layout", "Volver a la disposición inicial", "Zur Ausgangsstellung zurückkehren"],
+    ["Hint", "Pista", "Tipp"],
+    [
+        "Flash the block to move next",
+        "Hacer parpadear el bloque que hay que mover",
+        "Den als Nächstes zu ziehenden Block blinken lassen",
+    ],
+    [
+        "Hint: move the highlighted block {}",
+        "Pista: mueve el bloque resaltado hacia {}",
+        "Tipp: den markierten Block nach {} schieben",
+    ],
+    ["No solution found from here", "No hay solución desde aquí", "Von hier aus keine Lösung gefunden"],
+    ["Moves: {}", "Movimientos: {}", "Züge: {}"],
+    ["Time: {}", "Tiempo: {}", "Zeit: {}"],
+    ["✓ Solved!", "✓ ¡Resuelto!", "✓ Gelöst!"],
+    [
+        "Move the red block onto the outlined goal. Click a block on the side you want it to slide.",
+        "Lleva el bloque rojo a la meta marcada. Haz clic en un bloque por el lado hacia el que quieras deslizarlo.",
+        "Schiebe den roten Block auf das umrandete Ziel. Klicke einen Block auf der Seite an, zu der er gleiten soll.",
+    ],
+    // Loopover
+    ["Loopover {}×{} ✓", "Loopover {}×{} ✓", "Loopover {}×{} ✓"],
+    ["Loopover {}×{}", "Loopover {}×{}", "Loopover {}×{}"],
+    ["Loopover", "Loopover", "Loopover"],
+    ["Scramble", "Mezclar", "Mischen"],
+    [
+        "Start a new game on a freshly scrambled board",
+        "Empezar una partida nueva en un tablero recién mezclado",
+        "Ein neues Spiel auf frisch gemischtem Feld beginnen",
+    ],
+    ["Return to the scrambled layout", "Volver a la disposición mezclada", "Zur gemischten Stellung zurückkehren"],
+    [
+        "Drag a row sideways or a column up or down; tiles pushed off one edge come back on the other. Sort the tiles 1, 2, 3… row by row.",
+        "Arrastra una fila hacia un lado o una columna hacia arriba o abajo; las fichas que salen por un borde vuelven por el otro. Ordena las fichas 1, 2, 3… fila a fila.",
+        "Ziehe eine Zeile seitwärts oder eine Spalte nach oben oder unten; Steine, die an einem Rand hinausgeschoben werden, kommen am anderen wieder herein. Sortiere die Steine 1, 2, 3… Zeile für Zeile.",
+    ],
+    // Race
+    ["Race {}×{}", "Carrera {}×{}", "Rennen {}×{}"],
+    ["Race", "Carrera", "Rennen"],
+    ["Difficulty:", "Dificultad:", "Schwierigkeit:"],
+    ["Easy", "Fácil", "Leicht"],
+    ["Medium", "Media", "Mittel"],
+    ["Hard", "Difícil", "Schwer"],
+    ["Rematch", "Revancha", "Revanche"],
+    ["Start Race", "Empezar carrera", "Rennen starten"],
+    [
+        "Shuffle the same board for both players and start the clock",
+        "Mezclar el mismo tablero para ambos jugadores y poner en marcha el reloj",
+        "Für beide Spieler dasselbe Feld mischen und die Uhr starten",
+    ],
+    ["🏆 Player {} wins!", "🏆 ¡Gana el jugador {}!", "🏆 Spieler {} gewinnt!"],
+    [
+        "Player 1 slides tiles with W A S D, player 2 with the arrow keys.",
+        "El jugador 1 desliza las fichas con W A S D y el jugador 2 con las flechas.",
+        "Spieler 1 schiebt die Steine mit W A S D, Spieler 2 mit den Pfeiltasten.",
+    ],
+    ["arrows", "flechas", "Pfeiltasten"],
+    ["Player {} ({})", "Jugador {} ({})", "Spieler {} ({})"],
+    ["✓ Solved in {}", "✓ Resuelto en {}", "✓ Gelöst in {}"],
+    ["Klotski ✓", "Klotski ✓", "Klotski ✓"],
+    // Config and recovery
+    [
+        "No config directory; set SLIDER_CONFIG_DIR",
+        "No hay carpeta de configuración; define SLIDER_CONFIG_DIR",
+        "Kein Konfigurationsverzeichnis; SLIDER_CONFIG_DIR setzen",
+    ],
+    ["Saved to {}", "Guardado en {}", "Gespeichert unter {}"],
+    ["Could not save {}: {}", "No se pudo guardar {}: {}", "{} konnte nicht gespeichert werden: {}"],
+    ["Restore last game?", "¿Restaurar la última partida?", "Letztes Spiel wiederherstellen?"],
+    [
+        "Slider did not exit cleanly. Pick up the {0}×{0} game after {1} moves and {2}?",
+        "Slider no se cerró correctamente. ¿Continuar la partida de {0}×{0} tras {1} movimientos y {2}?",
+        "Slider wurde nicht sauber beendet. Das {0}×{0}-Spiel nach {1} Zügen und {2} fortsetzen?",
+    ],
+    ["Restore", "Restaurar", "Wiederherstellen"],
+    ["Discard", "Descartar", "Verwerfen"],
+    [
+        "Could not restore the last game: {}",
+        "No se pudo restaurar la última partida: {}",
+        "Das letzte Spiel konnte nicht wiederhergestellt werden: {}",
+    ],
+    ["🏆 Achievement unlocked: {}", "🏆 Logro desbloqueado: {}", "🏆 Erfolg freigeschaltet: {}"],
+    // Dropped files
+    [
+        "Files can only be dropped on a sliding puzzle",
+        "Solo se pueden soltar archivos sobre un puzle deslizante",
+        "Dateien können nur auf einem Schiebepuzzle abgelegt werden",
+    ],
+    ["Cannot read {}: {}", "No se puede leer {}: {}", "{} kann nicht gelesen werden: {}"],
+    [
+        "{} is not a valid puzzle file: {}",
+        "{} no es un archivo de puzle válido: {}",
+        "{} ist keine gültige Puzzledatei: {}",
+    ],
+    [" by {}", " de {}", " von {}"],
+    [
+        "Loaded a puzzle{}; its solution is in Import",
+        "Puzle{} cargado; su solución está en Importar",
+        "Puzzle{} geladen; seine Lösung steht unter Importieren",
+    ],
+    ["Loaded a puzzle{}", "Puzle{} cargado", "Puzzle{} geladen"],
+    [
+        "{} is neither a .{} puzzle nor a PNG picture",
+        "{} no es ni un puzle .{} ni una imagen PNG",
+        "{} ist weder ein .{}-Puzzle noch ein PNG-Bild",
+    ],
+    ["{} is not a .{} puzzle file", "{} no es un archivo de puzle .{}", "{} ist keine .{}-Puzzledatei"],
+    [
+        "{} (playing the {} moves before it)",
+        "{} (se juegan los {} movimientos anteriores)",
+        "{} (die {} Züge davor werden gespielt)",
+    ],
+    [
+        "Playing a {}-move solution",
+        "Reproduciendo una solución de {} movimientos",
+        "Lösung mit {} Zügen wird abgespielt",
+    ],
+    // Profiler
+    ["solving, {} nodes in {}s", "resolviendo, {} nodos en {} s", "löst, {} Knoten in {} s"],
+    ["solving", "resolviendo", "löst"],
+    ["shuffling", "mezclando", "mischt"],
+    ["measuring", "midiendo", "misst"],
+    ["analyzing", "analizando", "analysiert"],
+    ["idle", "inactivo", "untätig"],
+    ["Waiting for the first frame…", "Esperando el primer fotograma…", "Warte auf das erste Bild…"],
+    ["Frame {} · mean {} · worst {}", "Fotograma {} · media {} · peor {}", "Bild {} · Mittel {} · schlechtestes {}"],
+    ["Over {}: {} of {} frames", "Más de {}: {} de {} fotogramas", "Über {}: {} von {} Bildern"],
+    [
+        "Mean board {} · solvers and slides {}",
+        "Media tablero {} · solucionadores y deslizamientos {}",
+        "Mittel Feld {} · Löser und Schiebezüge {}",
+    ],
+    ["Queued slides: {}", "Deslizamientos en cola: {}", "Wartende Schiebezüge: {}"],
+    ["Background: {}", "Segundo plano: {}", "Hintergrund: {}"],
+    ["Other busy tabs: {}", "Otras pestañas ocupadas: {}", "Andere beschäftigte Tabs: {}"],
+    // Tab bar
+    ["Close this puzzle", "Cerrar este puzle", "Dieses Puzzle schließen"],
+    [" grid", " cuadrícula", " Raster"],
+    [
+        "Open another puzzle in a new tab",
+        "Abrir otro puzle en una pestaña nueva",
+        "Ein weiteres Puzzle in einem neuen Tab öffnen",
+    ],
+    ["+ Klotski", "+ Klotski", "+ Klotski"],
+    [
+        "Open a Klotski block puzzle in a new tab",
+        "Abrir un puzle de bloques Klotski en una pestaña nueva",
+        "Ein Klotski-Blockpuzzle in einem neuen Tab öffnen",
+    ],
+    ["+ Loopover", "+ Loopover", "+ Loopover"],
+    [
+        "Open a Loopover puzzle of the chosen size (up to 10×10), whose rows and columns rotate",
+        "Abrir un puzle Loopover del tamaño elegido (hasta 10×10), cuyas filas y columnas giran",
+        "Ein Loopover-Puzzle der gewählten Größe (bis 10×10) öffnen, dessen Zeilen und Spalten rotieren",
+    ],
+    ["+ Race", "+ Carrera", "+ Rennen"],
+    [
+        "Race a friend on identical boards at one keyboard",
+        "Competir con un amigo en tableros idénticos con un solo teclado",
+        "Gegen einen Freund auf gleichen Feldern an einer Tastatur antreten",
+    ],
+    ["Slider Puzzle", "Puzle deslizante", "Schiebepuzzle"],
+    ["Size: {}×{}", "Tamaño: {}×{}", "Größe: {}×{}"],
+    ["Extra Hard", "Muy difícil", "Sehr schwer"],
+    ["Entropy target {}–{}", "Entropía objetivo {}–{}", "Ziel-Entropie {}–{}"],
+    ["Custom", "Personalizada", "Eigene"],
+    [
+        "Shuffle until entropy reaches the chosen target, given in Manhattan distance and tuned to the entropy calculator",
+        "Mezclar hasta que la entropía alcance el objetivo elegido, dado en distancia Manhattan y ajustado a la medida de entropía",
+        "Mischen, bis die Entropie das gewählte Ziel erreicht, angegeben als Manhattan-Distanz und auf das Entropiemaß abgestimmt",
+    ],
+    ["min", "mín.", "min."],
+    ["Adaptive", "Adaptativa", "Adaptiv"],
+    [
+        "Pick each new game's target from your recent efficiency and solve times",
+        "Elegir el objetivo de cada partida nueva según tu eficiencia y tus tiempos recientes",
+        "Das Ziel jedes neuen Spiels nach deiner jüngsten Effizienz und deinen Lösungszeiten wählen",
+    ],
+    ["Next: {}+ ({})", "Siguiente: {}+ ({})", "Nächstes: {}+ ({})"],
+    [
+        "Last {} games: {} efficiency, {} per par move",
+        "Últimas {} partidas: {} de eficiencia, {} por movimiento de par",
+        "Letzte {} Spiele: {} Effizienz, {} pro Par-Zug",
+    ],
+    [
+        "No finished games with a known par yet; starting at Medium",
+        "Aún no hay partidas terminadas con par conocido; se empieza en Media",
+        "Noch keine beendeten Spiele mit bekanntem Par; Start mit Mittel",
+    ],
+    ["Layers: ", "Capas: ", "Ebenen: "],
+    [
+        "Stack several boards; the game is won once every layer is solved",
+        "Apilar varios tableros; la partida se gana cuando todas las capas están resueltas",
+        "Mehrere Felder stapeln; das Spiel ist gewonnen, sobald jede Ebene gelöst ist",
+    ],
+    [
+        "Challenges are lost if the move limit or the countdown runs out; applies from the next game",
+        "Los retos se pierden si se agota el límite de movimientos o la cuenta atrás; se aplica desde la próxima partida",
+        "Herausforderungen sind verloren, wenn das Zuglimit oder der Countdown abläuft; gilt ab dem nächsten Spiel",
+    ],
+    ["par + ", "par + ", "Par + "],
+    ["Shuffling…", "Mezclando…", "Wird gemischt…"],
+    [
+        "Marathon stage {}: {}×{} {} · Score {}",
+        "Etapa {} del maratón: {}×{} {} · Puntuación {}",
+        "Marathon-Etappe {}: {}×{} {} · Punkte {}",
+    ],
+    ["End Marathon", "Terminar maratón", "Marathon beenden"],
+    [
+        "Stop the run and see its summary",
+        "Detener la serie y ver su resumen",
+        "Den Lauf beenden und die Zusammenfassung ansehen",
+    ],
+    ["New Game", "Nueva partida", "Neues Spiel"],
+    ["Marathon", "Maratón", "Marathon"],
+    [
+        "Endless run from an Easy 3×3: every solve moves on to a harder or larger board",
+        "Serie sin fin desde un 3×3 fácil: cada tablero resuelto lleva a uno más difícil o más grande",
+        "Endloser Lauf ab einem leichten 3×3: jede Lösung führt zu einem schwereren oder größeren Feld",
+    ],
+    ["Random Board", "Tablero aleatorio", "Zufälliges Feld"],
+    [
+        "Start from a uniformly random solvable layout (not timed for records)",
+        "Empezar desde una disposición resoluble al azar (no cuenta para récords)",
+        "Mit einer gleichverteilt zufälligen lösbaren Stellung beginnen (zählt nicht für Rekorde)",
+    ],
+    ["Practice", "Práctica", "Übung"],
+    ["Last two rows", "Dos últimas filas", "Letzte zwei Zeilen"],
+    ["Last two columns", "Dos últimas columnas", "Letzte zwei Spalten"],
+    ["Bottom-right 3×3", "3×3 inferior derecho", "3×3 unten rechts"],
+    ["Bottom-right 2×2", "2×2 inferior derecho", "2×2 unten rechts"],
+    [
+        "Scramble only part of the board to drill the end of a solve (not timed for records)",
+        "Mezclar solo parte del tablero para practicar el final de una resolución (no cuenta para récords)",
+        "Nur einen Teil des Felds mischen, um das Ende einer Lösung zu üben (zählt nicht für Rekorde)",
+    ],
+    ["Stop Solve", "Detener resolución", "Lösen abbrechen"],
+    ["Resume", "Reanudar", "Fortsetzen"],
+    ["Pause", "Pausa", "Pause"],
+    ["Playback speed", "Velocidad de reproducción", "Wiedergabetempo"],
+    ["Skip to End", "Saltar al final", "Zum Ende springen"],
+    [
+        "Play the remaining moves at once, without animation",
+        "Jugar de golpe los movimientos restantes, sin animación",
+        "Die restlichen Züge auf einmal ohne Animation ausführen",
+    ],
+    ["Cancel", "Cancelar", "Abbrechen"],
+    ["{} nodes, depth {} ({}s)", "{} nodos, profundidad {} ({} s)", "{} Knoten, Tiefe {} ({} s)"],
+    ["Computing solution...", "Calculando la solución...", "Lösung wird berechnet..."],
+    ["Auto Solve", "Resolver solo", "Automatisch lösen"],
+    [
+        "Puzzle already solved or computation in progress",
+        "El puzle ya está resuelto o hay un cálculo en curso",
+        "Puzzle bereits gelöst oder Berechnung läuft",
+    ],
+    [
+        "Computing solution in background...",
+        "Calculando la solución en segundo plano...",
+        "Lösung wird im Hintergrund berechnet...",
+    ],
+    ["Assist", "Ayuda", "Hilfe"],
+    ["Next 5 moves", "5 movimientos siguientes", "Nächste 5 Züge"],
+    ["Next tile", "Ficha siguiente", "Nächster Stein"],
+    ["Next 5 tiles", "5 fichas siguientes", "Nächste 5 Steine"],
+    ["Next row", "Fila siguiente", "Nächste Zeile"],
+    ["Solving the {}, then it's your move", "Resolviendo: {}; después te toca a ti", "Löse: {}; danach bist du am Zug"],
+    ["Nothing to solve for the {}", "Nada que resolver: {}", "Nichts zu lösen: {}"],
+    [
+        "Auto-solve part of the board, then hand it back",
+        "Resolver solo una parte del tablero y devolverte el control",
+        "Einen Teil des Felds automatisch lösen und dann zurückgeben",
+    ],
+    ["No hint available", "No hay pista disponible", "Kein Tipp verfügbar"],
+    ["Step", "Paso", "Schritt"],
+    [
+        "Play one move of the solution, to study how it is solved",
+        "Jugar un movimiento de la solución para estudiar cómo se resuelve",
+        "Einen Zug der Lösung ausführen, um zu lernen, wie es gelöst wird",
+    ],
+    [
+        "Computing solution... press Step again",
+        "Calculando la solución... pulsa Paso otra vez",
+        "Lösung wird berechnet... nochmals Schritt drücken",
+    ],
+    ["Copy solution", "Copiar solución", "Lösung kopieren"],
+    [
+        "Copy the rest of the solution as blank moves (U/D/L/R) and as tile numbers",
+        "Copiar el resto de la solución como movimientos del hueco (U/D/L/R) y como números de ficha",
+        "Den Rest der Lösung als Züge der Lücke (U/D/L/R) und als Steinnummern kopieren",
+    ],
+    ["{} ({} moves)\nTiles: {}", "{} ({} movimientos)\nFichas: {}", "{} ({} Züge)\nSteine: {}"],
+    ["Solution copied to clipboard", "Solución copiada al portapapeles", "Lösung in die Zwischenablage kopiert"],
+    ["Moves: {} · Tiles: {}", "Movimientos: {} · Fichas: {}", "Züge: {} · Steine: {}"],
+    ["{} [shuffle: {}]", "{} [mezcla: {}]", "{} [Mischung: {}]"],
+    ["Par: {}", "Par: {}", "Par: {}"],
+    [
+        "Fewest tile moves that solve this shuffle; finishing at par earns an S",
+        "Mínimo de movimientos de fichas que resuelven esta mezcla; terminar en par da una S",
+        "Wenigste Steinzüge, die diese Mischung lösen; wer mit Par fertig wird, bekommt ein S",
+    ],
+    ["Par: …", "Par: …", "Par: …"],
+    ["Moves left: {}", "Movimientos restantes: {}", "Verbleibende Züge: {}"],
+    ["Moves left: …", "Movimientos restantes: …", "Verbleibende Züge: …"],
+    [
+        "The limit starts counting once par is known",
+        "El límite empieza a contar cuando se conoce el par",
+        "Das Limit zählt, sobald Par bekannt ist",
+    ],
+    ["Time left: {}", "Tiempo restante: {}", "Verbleibende Zeit: {}"],
+    ["Best: {}", "Mejor: {}", "Bestzeit: {}"],
+    [
+        "Measuring this board in the background; the length shown is from before the last move",
+        "Midiendo este tablero en segundo plano; la longitud mostrada es de antes del último movimiento",
+        "Dieses Feld wird im Hintergrund vermessen; die angezeigte Länge stammt von vor dem letzten Zug",
+    ],
+    [
+        "Estimated by a quick weighted search; the exact length replaces it once the background solver finds it",
+        "Estimado con una búsqueda ponderada rápida; se sustituye por la longitud exacta cuando el solucionador en segundo plano la encuentra",
+        "Mit einer schnellen gewichteten Suche geschätzt; die genaue Länge ersetzt sie, sobald der Hintergrundlöser sie findet",
+    ],
+    ["Manhattan: {}", "Manhattan: {}", "Manhattan: {}"],
+    ["Heuristic: {}", "Heurística: {}", "Heuristik: {}"],
+    ["Actual: {} (calc: {})", "Real: {} (cálc.: {})", "Tatsächlich: {} (ber.: {})"],
+    ["Actual: -- (last calc: {})", "Real: -- (último cálc.: {})", "Tatsächlich: -- (zuletzt ber.: {})"],
+    ["Actual: --", "Real: --", "Tatsächlich: --"],
+    ["Nodes expanded: {}", "Nodos expandidos: {}", "Expandierte Knoten: {}"],
+    ["Actual: {}", "Real: {}", "Tatsächlich: {}"],
+    ["Show Performance", "Mostrar rendimiento", "Leistung anzeigen"],
+    ["Profiler", "Perfilador", "Profiler"],
+    [
+        "Overlay frame times, queued slides and background tasks, to track down stutter",
+        "Superponer tiempos de fotograma, deslizamientos en cola y tareas en segundo plano, para localizar tirones",
+        "Bildzeiten, wartende Schiebezüge und Hintergrundaufgaben einblenden, um Ruckler aufzuspüren",
+    ],
+    ["Techniques", "Técnicas", "Techniken"],
+    [
+        "List the techniques that can be played from the empty cell",
+        "Listar las técnicas que se pueden jugar desde el hueco",
+        "Die Techniken auflisten, die von der Lücke aus spielbar sind",
+    ],
+    ["Heatmap", "Mapa de calor", "Heatmap"],
+    [
+        "Tint tiles by how soon the solution moves them (orange first, violet last)",
+        "Colorear las fichas según lo pronto que las mueve la solución (naranja primero, violeta al final)",
+        "Steine danach tönen, wie früh die Lösung sie bewegt (orange zuerst, violett zuletzt)",
+    ],
+    ["Ghost", "Fantasma", "Geist"],
+    [
+        "Write each tile's label faintly in the cell it belongs in",
+        "Escribir tenuemente la etiqueta de cada ficha en su casilla de destino",
+        "Die Beschriftung jedes Steins blass in sein Zielfeld schreiben",
+    ],
+    ["Export Image", "Exportar imagen", "Bild exportieren"],
+    [
+        "Save the board as shown, heatmap included, to the data directory",
+        "Guardar el tablero tal como se ve, con el mapa de calor, en la carpeta de datos",
+        "Das Feld wie angezeigt, samt Heatmap, im Datenverzeichnis speichern",
+    ],
+    ["Solver: {}", "Solucionador: {}", "Löser: {}"],
+    ["Solver: Auto ({})", "Solucionador: Automático ({})", "Löser: Automatisch ({})"],
+    ["Auto", "Automático", "Automatisch"],
+    [
+        "Auto: A* up to 4×4, IDA* for 5×5, Strategic beyond or when the others give up\nA*: fast, near-optimal\nIDA*: optimal, low memory (5×5+)\nGreedy: fast, not optimal\nStrategic: row by row, any grid size",
+        "Automático: A* hasta 4×4, IDA* para 5×5, Estratégico más allá o cuando los otros se rinden\nA*: rápido, casi óptimo\nIDA*: óptimo, poca memoria (5×5+)\nVoraz: rápido, no óptimo\nEstratégico: fila a fila, cualquier tamaño",
+        "Automatisch: A* bis 4×4, IDA* für 5×5, Strategisch darüber oder wenn die anderen aufgeben\nA*: schnell, nahezu optimal\nIDA*: optimal, wenig Speicher (5×5+)\nGierig: schnell, nicht optimal\nStrategisch: Zeile für Zeile, jede Größe",
+    ],
+    ["Entropy: {}", "Entropía: {}", "Entropie: {}"],
+    [
+        "How disorder is measured; difficulty targets are scaled to match",
+        "Cómo se mide el desorden; los objetivos de dificultad se ajustan en consecuencia",
+        "Wie Unordnung gemessen wird; die Schwierigkeitsziele werden passend skaliert",
+    ],
+    ["Statistics", "Estadísticas", "Statistik"],
+    ["Analysis", "Análisis", "Analyse"],
+    [
+        "Rate each move of the solved game against optimal play",
+        "Valorar cada movimiento de la partida resuelta frente al juego óptimo",
+        "Jeden Zug des gelösten Spiels mit optimalem Spiel vergleichen",
+    ],
+    ["Search", "Búsqueda", "Suche"],
+    [
+        "Watch the solver search live: f-score, frontier size and boards expanded",
+        "Ver la búsqueda del solucionador en directo: f-score, tamaño de la frontera y tableros expandidos",
+        "Dem Löser live beim Suchen zusehen: f-Wert, Größe der Front und expandierte Felder",
+    ],
+    ["Settings", "Ajustes", "Einstellungen"],
+    ["Macros (recording)", "Macros (grabando)", "Makros (Aufnahme)"],
+    ["Macros", "Macros", "Makros"],
+    [
+        "Record move sequences and replay them with keys 1-9",
+        "Grabar secuencias de movimientos y repetirlas con las teclas 1-9",
+        "Zugfolgen aufnehmen und mit den Tasten 1-9 abspielen",
+    ],
+    ["Import", "Importar", "Importieren"],
+    [
+        "Load a board from a share code or layout notation",
+        "Cargar un tablero desde un código para compartir o una notación",
+        "Ein Feld aus einem Teilen-Code oder einer Stellungsnotation laden",
+    ],
+    ["Export", "Exportar", "Exportieren"],
+    [
+        "Copy a share code for this board to the clipboard",
+        "Copiar al portapapeles un código para compartir este tablero",
+        "Einen Teilen-Code für dieses Feld in die Zwischenablage kopieren",
+    ],
+    ["Share code copied: {}", "Código copiado: {}", "Teilen-Code kopiert: {}"],
+    ["SOLVED!", "¡RESUELTO!", "GELÖST!"],
+    ["New best time!", "¡Nuevo mejor tiempo!", "Neue Bestzeit!"],
+    ["Layer:", "Capa:", "Ebene:"],
+    ["Games played: {}", "Partidas jugadas: {}", "Gespielte Spiele: {}"],
+    ["Average efficiency: {}%", "Eficiencia media: {} %", "Durchschnittliche Effizienz: {} %"],
+    ["Average time: {}", "Tiempo medio: {}", "Durchschnittliche Zeit: {}"],
+    ["Challenges: {} won, {} lost", "Retos: {} ganados, {} perdidos", "Herausforderungen: {} gewonnen, {} verloren"],
+    [
+        "Best marathon: {} points over {} stages",
+        "Mejor maratón: {} puntos en {} etapas",
+        "Bester Marathon: {} Punkte in {} Etappen",
+    ],
+    ["Best Times", "Mejores tiempos", "Bestzeiten"],
+    ["No completed games yet.", "Aún no hay partidas terminadas.", "Noch keine beendeten Spiele."],
+    ["Grid", "Cuadrícula", "Raster"],
+    ["Difficulty", "Dificultad", "Schwierigkeit"],
+    ["Best time", "Mejor tiempo", "Bestzeit"],
+    ["Achievements ({}/{})", "Logros ({}/{})", "Erfolge ({}/{})"],
+    ["Recent Games", "Partidas recientes", "Letzte Spiele"],
+    ["Moves", "Movimientos", "Züge"],
+    ["Tiles", "Fichas", "Steine"],
+    ["Par", "Par", "Par"],
+    ["Grade", "Nota", "Note"],
+    ["Time", "Tiempo", "Zeit"],
+    ["{} (lost)", "{} (perdida)", "{} (verloren)"],
+    ["{} (auto)", "{} (automática)", "{} (automatisch)"],
+    ["Analysis failed: {}", "El análisis falló: {}", "Analyse fehlgeschlagen: {}"],
+    [
+        "Replaying the game against optimal play…",
+        "Repitiendo la partida frente al juego óptimo…",
+        "Spiel wird mit optimalem Spiel verglichen…",
+    ],
+    [
+        "Solve a game, then rate each of its moves against optimal play.",
+        "Resuelve una partida y luego valora cada movimiento frente al juego óptimo.",
+        "Löse ein Spiel und vergleiche dann jeden Zug mit optimalem Spiel.",
+    ],
+    ["Analyze", "Analizar", "Analysieren"],
+    [
+        "Needs a solved board whose every move was recorded (not a restored or layered game)",
+        "Requiere un tablero resuelto con todos sus movimientos registrados (no una partida restaurada ni por capas)",
+        "Braucht ein gelöstes Feld mit allen aufgezeichneten Zügen (kein wiederhergestelltes Spiel und keines mit Ebenen)",
+    ],
+    ["Ghost boards", "Tableros fantasma", "Geisterfelder"],
+    [
+        "Show the last boards the solver expanded, newest on the right",
+        "Mostrar los últimos tableros que expandió el solucionador, el más reciente a la derecha",
+        "Die zuletzt vom Löser expandierten Felder zeigen, das neueste rechts",
+    ],
+    [
+        "Ask for a hint or an auto-solve on a hard board to watch the solver search.",
+        "Pide una pista o una resolución automática en un tablero difícil para ver buscar al solucionador.",
+        "Fordere auf einem schweren Feld einen Tipp oder eine automatische Lösung an, um dem Löser zuzusehen.",
+    ],
+    [
+        "Waiting for the first progress report…",
+        "Esperando el primer informe de progreso…",
+        "Warte auf den ersten Fortschrittsbericht…",
+    ],
+    ["Recording: {} moves so far", "Grabando: {} movimientos hasta ahora", "Aufnahme: bisher {} Züge"],
+    ["Save", "Guardar", "Speichern"],
+    ["Saved macro {}", "Macro {} guardada", "Makro {} gespeichert"],
+    [
+        "At most {} macros; delete one first",
+        "Como máximo {} macros; borra una primero",
+        "Höchstens {} Makros; zuerst eines löschen",
+    ],
+    ["Record", "Grabar", "Aufnehmen"],
+    [
+        "Play the moves to record, then name and save them",
+        "Juega los movimientos que quieras grabar y luego ponles nombre y guárdalos",
+        "Die aufzunehmenden Züge spielen, dann benennen und speichern",
+    ],
+    ["No macros yet", "Aún no hay macros", "Noch keine Makros"],
+    ["Play", "Jugar", "Abspielen"],
+    ["Does not fit at the empty cell", "No encaja en el hueco", "Passt nicht an die Lücke"],
+    ["Delete", "Borrar", "Löschen"],
+    ["Language", "Idioma", "Sprache"],
+    ["Theme", "Tema", "Design"],
+    ["Tile colors", "Colores de las fichas", "Steinfarben"],
+    ["Colorblind mode", "Modo para daltónicos", "Farbenblind-Modus"],
+    [
+        "Show distance from home by brightness, border thickness and hatching instead of hue",
+        "Mostrar la distancia a casa con brillo, grosor del borde y tramado en lugar de color",
+        "Den Abstand zum Zielfeld durch Helligkeit, Randstärke und Schraffur statt durch Farbton zeigen",
+    ],
+    ["Tile numbers", "Números de las fichas", "Steinnummern"],
+    [
+        "How numbered tiles are written; letters and custom labels are unaffected",
+        "Cómo se escriben las fichas numeradas; las letras y etiquetas personalizadas no cambian",
+        "Wie nummerierte Steine geschrieben werden; Buchstaben und eigene Beschriftungen bleiben unverändert",
+    ],
+    ["Tile labels", "Etiquetas de las fichas", "Steinbeschriftung"],
+    [
+        "One letter per tile in goal order (spaces ignored), or labels separated by commas",
+        "Una letra por ficha en el orden de la meta (se ignoran los espacios), o etiquetas separadas por comas",
+        "Ein Buchstabe pro Stein in Zielreihenfolge (Leerzeichen werden ignoriert) oder durch Kommas getrennte Beschriftungen",
+    ],
+    ["Apply to this board", "Aplicar a este tablero", "Auf dieses Feld anwenden"],
+    ["Scramble {}", "Mezclar {}", "{} mischen"],
+    [
+        "Capture a picture for the tiles and shuffle the board",
+        "Capturar una imagen para las fichas y mezclar el tablero",
+        "Ein Bild für die Steine aufnehmen und das Feld mischen",
+    ],
+    ["Remove picture", "Quitar imagen", "Bild entfernen"],
+    ["Goal", "Meta", "Ziel"],
+    [
+        "Solved layout for this board; changing it resets the board",
+        "Disposición resuelta de este tablero; cambiarla reinicia el tablero",
+        "Gelöste Stellung dieses Felds; eine Änderung setzt das Feld zurück",
+    ],
+    ["Reduce motion", "Reducir movimiento", "Bewegung reduzieren"],
+    [
+        "Tiles jump instead of sliding, and highlights hold steady instead of flashing",
+        "Las fichas saltan en lugar de deslizarse y los resaltados se mantienen fijos en lugar de parpadear",
+        "Steine springen statt zu gleiten, und Hervorhebungen bleiben ruhig statt zu blinken",
+    ],
+    ["Slide (ms)", "Deslizamiento (ms)", "Gleiten (ms)"],
+    ["instant", "instantáneo", "sofort"],
+    ["Easing", "Suavizado", "Verlauf"],
+    ["Auto-solve step (ms)", "Paso de resolución automática (ms)", "Schritt beim automatischen Lösen (ms)"],
+    ["Instant", "Instantáneo", "Sofort"],
+    [
+        "No slides and no pause between auto-solve moves",
+        "Sin deslizamientos ni pausas entre los movimientos de la resolución automática",
+        "Kein Gleiten und keine Pause zwischen automatischen Lösungszügen",
+    ],
+    ["Defaults", "Valores predeterminados", "Standardwerte"],
+    ["Save as defaults", "Guardar como predeterminados", "Als Standard speichern"],
+    [
+        "Start future sessions with this board size, difficulty, solver, language, theme, tile colors and animation timing",
+        "Empezar las próximas sesiones con este tamaño de tablero, dificultad, solucionador, idioma, tema, colores de fichas y tiempos de animación",
+        "Künftige Sitzungen mit dieser Feldgröße, Schwierigkeit, diesem Löser, dieser Sprache, diesem Design, diesen Steinfarben und Animationszeiten beginnen",
+    ],
+    ["Replay tutorial", "Repetir tutorial", "Einführung wiederholen"],
+    ["Tutorial", "Tutorial", "Einführung"],
+    [
+        "The board is solved already; press New Game first.",
+        "El tablero ya está resuelto; pulsa Nueva partida primero.",
+        "Das Feld ist bereits gelöst; zuerst Neues Spiel drücken.",
+    ],
+    ["Done", "Hecho", "Fertig"],
+    ["Next", "Siguiente", "Weiter"],
+    ["Skip tutorial", "Saltar tutorial", "Einführung überspringen"],
+    ["Import Board", "Importar tablero", "Feld importieren"],
+    [
+        "Paste a share code, or rows separated by '/' or new lines with '_' for the empty cell:",
+        "Pega un código para compartir, o filas separadas por '/' o saltos de línea con '_' para el hueco:",
+        "Füge einen Teilen-Code ein oder Zeilen, getrennt durch '/' oder Zeilenumbrüche, mit '_' für die Lücke:",
+    ],
+    ["Load", "Cargar", "Laden"],
+    [
+        "Paste a solution in blank moves (U, D, L, R) to play it on the current board:",
+        "Pega una solución en movimientos del hueco (U, D, L, R) para jugarla en el tablero actual:",
+        "Füge eine Lösung als Züge der Lücke (U, D, L, R) ein, um sie auf dem aktuellen Feld abzuspielen:",
+    ],
+    ["Play solution", "Jugar solución", "Lösung abspielen"],
+    ["Nothing fits here right now", "Ahora mismo no encaja nada aquí", "Hier passt gerade nichts"],
+    ["{} available here", "{} disponibles aquí", "{} hier verfügbar"],
+    ["{} moves; Manhattan {}", "{} movimientos; Manhattan {}", "{} Züge; Manhattan {}"],
+    ["Solved!", "¡Resuelto!", "Gelöst!"],
+    ["Grade {}", "Nota {}", "Note {}"],
+    ["Tile moves", "Movimientos de fichas", "Steinzüge"],
+    ["Efficiency", "Eficiencia", "Effizienz"],
+    [
+        "Shuffle with the difficulty selected in the toolbar",
+        "Mezclar con la dificultad elegida en la barra de herramientas",
+        "Mit der in der Werkzeugleiste gewählten Schwierigkeit mischen",
+    ],
+    ["Same Difficulty", "Misma dificultad", "Gleiche Schwierigkeit"],
+    ["Shuffle another {} board", "Mezclar otro tablero {}", "Ein weiteres Feld ({}) mischen"],
+    ["Share", "Compartir", "Teilen"],
+    [
+        "Copy your result and a share code for the starting board",
+        "Copiar tu resultado y un código para compartir el tablero inicial",
+        "Dein Ergebnis und einen Teilen-Code für das Startfeld kopieren",
+    ],
+    [
+        "Rate each of your moves against optimal play",
+        "Valorar cada uno de tus movimientos frente al juego óptimo",
+        "Jeden deiner Züge mit optimalem Spiel vergleichen",
+    ],
+    ["Export {}", "Exportar {}", "{} exportieren"],
+    [
+        "Save an animated replay of this solve as {}",
+        "Guardar una repetición animada de esta resolución como {}",
+        "Eine animierte Wiederholung dieser Lösung als {} speichern",
+    ],
+    ["Close", "Cerrar", "Schließen"],
+    ["Result copied to clipboard", "Resultado copiado al portapapeles", "Ergebnis in die Zwischenablage kopiert"],
+    ["Marathon Summary", "Resumen del maratón", "Marathon-Zusammenfassung"],
+    ["New best marathon!", "¡Nuevo mejor maratón!", "Neuer bester Marathon!"],
+    ["#{} among your best runs", "N.º {} entre tus mejores series", "Platz {} deiner besten Läufe"],
+    ["Score", "Puntuación", "Punkte"],
+    ["Stages cleared", "Etapas superadas", "Geschaffte Etappen"],
+    ["Ended on", "Terminó en", "Beendet auf"],
+    ["Play time", "Tiempo de juego", "Spielzeit"],
+    ["Best Runs", "Mejores series", "Beste Läufe"],
+    ["Stages", "Etapas", "Etappen"],
+    ["Reached", "Alcanzado", "Erreicht"],
+    ["Run Again", "Otra vez", "Noch einmal"],
+    ["Custom ({}+)", "Personalizada ({}+)", "Eigene ({}+)"],
+    ["Free play", "Juego libre", "Freies Spiel"],
+    ["Par + {} moves", "Par + {} movimientos", "Par + {} Züge"],
+    ["{} countdown", "Cuenta atrás de {}", "{} Countdown"],
+    ["Place tile {}", "Coloca la ficha {}", "Stein {} setzen"],
+    [
+        "Finish row {} by swinging tiles {} and {} in together",
+        "Completa la fila {} metiendo juntas las fichas {} y {}",
+        "Zeile {} abschließen, indem die Steine {} und {} gemeinsam eingeschwenkt werden",
+    ],
+    [
+        "Finish column {} by swinging tiles {} and {} in together",
+        "Completa la columna {} metiendo juntas las fichas {} y {}",
+        "Spalte {} abschließen, indem die Steine {} und {} gemeinsam eingeschwenkt werden",
+    ],
+    [
+        "Cycle tiles {} around the last 2×2 block",
+        "Gira las fichas {} por el último bloque 2×2",
+        "Die Steine {} im letzten 2×2-Block rotieren",
+    ],
+    [
+        "Sort tiles {} in the last 3×3 block, in the fewest moves",
+        "Ordena las fichas {} en el último bloque 3×3 con los menos movimientos",
+        "Die Steine {} im letzten 3×3-Block mit den wenigsten Zügen sortieren",
+    ],
+    [
+        "Heuristic distance after each move\nStart: {}, now: {}, lowest: {}",
+        "Distancia heurística tras cada movimiento\nInicio: {}, ahora: {}, mínima: {}",
+        "Heuristische Distanz nach jedem Zug\nStart: {}, jetzt: {}, niedrigste: {}",
+    ],
+    ["{} · {} · {} nodes in {}s", "{} · {} · {} nodos en {} s", "{} · {} · {} Knoten in {} s"],
+    ["finished", "terminado", "fertig"],
+    ["searching", "buscando", "sucht"],
+    ["f-score: {}", "f-score: {}", "f-Wert: {}"],
+    ["Frontier: {} (peak {})", "Frontera: {} (máx. {})", "Front: {} (Spitze {})"],
+    [
+        "{} nodes at {}s\nf-score {}, frontier {}",
+        "{} nodos a los {} s\nf-score {}, frontera {}",
+        "{} Knoten nach {} s\nf-Wert {}, Front {}",
+    ],
+    [
+        "Optimal distance at the start: {}, tile moves wasted: {}",
+        "Distancia óptima al inicio: {}, movimientos de fichas desperdiciados: {}",
+        "Optimale Distanz zu Beginn: {}, verschwendete Steinzüge: {}",
+    ],
+    ["Move {}: {} ({} → {}, {})", "Movimiento {}: {} ({} → {}, {})", "Zug {}: {} ({} → {}, {})"],
+    ["1 tile", "1 ficha", "1 Stein"],
+    ["{} tiles", "{} fichas", "{} Steine"],
+    // Klotski directions
+    ["up", "arriba", "oben"],
+    ["down", "abajo", "unten"],
+    ["left", "la izquierda", "links"],
+    ["right", "la derecha", "rechts"],
+    // Challenge failures
+    ["Out of moves", "Sin movimientos", "Keine Züge mehr"],
+    ["Time's up", "Se acabó el tiempo", "Die Zeit ist um"],
+    // Assist targets
+    ["next 5 moves", "5 movimientos siguientes", "nächste 5 Züge"],
+    ["next 1 tile", "ficha siguiente", "nächster Stein"],
+    ["next 5 tiles", "5 fichas siguientes", "nächste 5 Steine"],
+    ["next row", "fila siguiente", "nächste Zeile"],
+    // Achievements
+    ["First Solve", "Primera resolución", "Erste Lösung"],
+    ["Under Par", "Bajo par", "Unter Par"],
+    ["Big Board", "Tablero grande", "Großes Feld"],
+    ["Centurion", "Centurión", "Zenturio"],
+    ["No Hints Needed", "Sin pistas", "Ohne Tipps"],
+    [
+        "Solve a shuffled board on your own",
+        "Resuelve un tablero mezclado por tu cuenta",
+        "Löse ein gemischtes Feld selbst",
+    ],
+    [
+        "Solve a board in no more moves than par",
+        "Resuelve un tablero en no más movimientos que el par",
+        "Löse ein Feld in nicht mehr Zügen als Par",
+    ],
+    [
+        "Solve a 5×5 or larger board on your own",
+        "Resuelve por tu cuenta un tablero de 5×5 o mayor",
+        "Löse selbst ein Feld von 5×5 oder größer",
+    ],
+    ["Complete 100 games", "Completa 100 partidas", "Beende 100 Spiele"],
+    ["Solve a Hard board without hints", "Resuelve un tablero difícil sin pistas", "Löse ein schweres Feld ohne Tipps"],
+    // Solver kinds
+    ["Greedy", "Voraz", "Gierig"],
+    ["Strategic", "Estratégico", "Strategisch"],
+    // Entropy calculators
+    ["Manhattan", "Manhattan", "Manhattan"],
+    ["Shortest Path", "Camino más corto", "Kürzester Weg"],
+    ["Enhanced", "Mejorada", "Erweitert"],
+    ["Walking Distance", "Distancia de paseo", "Walking Distance"],
+    ["Empty Cell Path", "Camino del hueco", "Weg der Lücke"],
+    [
+        "Sum of each tile's distance from home",
+        "Suma de la distancia de cada ficha a su casa",
+        "Summe der Abstände jedes Steins von seinem Zielfeld",
+    ],
+    [
+        "Manhattan distance plus 2 per pair of tiles blocking each other in a line",
+        "Distancia Manhattan más 2 por cada par de fichas que se bloquean en una línea",
+        "Manhattan-Distanz plus 2 je Paar von Steinen, die sich in einer Linie blockieren",
+    ],
+    [
+        "Shortest path plus penalties for misplaced corner and edge tiles",
+        "Camino más corto más penalizaciones por fichas de esquina y borde mal colocadas",
+        "Kürzester Weg plus Strafpunkte für falsch stehende Eck- und Randsteine",
+    ],
+    [
+        "Table-driven lower bound that counts tiles stepping around each other (up to 4×4)",
+        "Cota inferior basada en tablas que cuenta las fichas que se rodean entre sí (hasta 4×4)",
+        "Tabellenbasierte untere Schranke, die zählt, wie Steine einander ausweichen (bis 4×4)",
+    ],
+    [
+        "Manhattan distance plus the empty cell's walk to the nearest misplaced tile",
+        "Distancia Manhattan más el recorrido del hueco hasta la ficha mal colocada más cercana",
+        "Manhattan-Distanz plus der Weg der Lücke zum nächsten falsch stehenden Stein",
+    ],
+    // Themes
+    ["System", "Sistema", "System"],
+    ["Dark", "Oscuro", "Dunkel"],
+    ["Light", "Claro", "Hell"],
+    // Tile colors
+    ["Distance from home", "Distancia a casa", "Abstand zum Zielfeld"],
+    ["Goal row", "Fila de destino", "Zielzeile"],
+    ["Number band", "Franja de números", "Zahlenbereich"],
+    ["Off", "Desactivado", "Aus"],
+    // Number styles
+    ["Decimal", "Decimal", "Dezimal"],
+    ["Zero-based", "Desde cero", "Ab null"],
+    ["Hexadecimal", "Hexadecimal", "Hexadezimal"],
+    ["Roman numerals", "Números romanos", "Römische Zahlen"],
+    ["Pips", "Puntos", "Augen"],
+    // Goals
+    ["Standard", "Estándar", "Standard"],
+    ["Blank first", "Hueco primero", "Lücke zuerst"],
+    ["Reversed", "Invertido", "Umgekehrt"],
+    ["Spiral", "Espiral", "Spirale"],
+    // Move qualities
+    ["Optimal", "Óptimo", "Optimal"],
+    ["Neutral", "Neutral", "Neutral"],
+    ["Blunder", "Error grave", "Patzer"],
+    // Capture sources
+    ["screenshot", "captura de pantalla", "Bildschirmfoto"],
+    ["webcam", "cámara web", "Webcam"],
+    // Tutorial
+    ["Welcome to Slider", "Bienvenido a Slider", "Willkommen bei Slider"],
+    ["Slide a tile", "Desliza una ficha", "Einen Stein schieben"],
+    ["Slide a whole line", "Desliza una línea entera", "Eine ganze Reihe schieben"],
+    ["Reading the board", "Leer el tablero", "Das Feld lesen"],
+    ["Let the solver finish", "Deja que termine el solucionador", "Den Löser fertig machen lassen"],
+    ["You're ready", "Ya estás listo", "Du bist bereit"],
+    [
+        "Put the tiles back in order by sliding them into the empty cell. This short tour shows the controls; skip it at any time.",
+        "Vuelve a ordenar las fichas deslizándolas hacia el hueco. Este breve recorrido muestra los controles; puedes saltarlo cuando quieras.",
+        "Bringe die Steine wieder in Ordnung, indem du sie in die Lücke schiebst. Diese kurze Tour zeigt die Bedienung; du kannst sie jederzeit überspringen.",
+    ],
+    [
+        "Click a highlighted tile next to the empty cell to slide it in.",
+        "Haz clic en una ficha resaltada junto al hueco para deslizarla dentro.",
+        "Klicke einen markierten Stein neben der Lücke an, um ihn hineinzuschieben.",
+    ],
+    [
+        "Click a highlighted tile further along the empty cell's row or column: every tile in between slides together, as one move.",
+        "Haz clic en una ficha resaltada más lejos en la fila o columna del hueco: todas las fichas intermedias se deslizan juntas, en un solo movimiento.",
+        "Klicke einen markierten Stein weiter entfernt in der Zeile oder Spalte der Lücke an: alle Steine dazwischen gleiten gemeinsam, als ein Zug.",
+    ],
+    [
+        "The numbers in the toolbar measure how scrambled the board is. Manhattan adds up how far each tile is from home, Heuristic also counts tiles blocking each other, and Actual is the fewest moves that finish the board, found in the background. All of them fall to zero as you solve it.",
+        "Los números de la barra de herramientas miden lo mezclado que está el tablero. Manhattan suma lo lejos que está cada ficha de su casa, Heurística cuenta además las fichas que se bloquean entre sí, y Real es el mínimo de movimientos que terminan el tablero, calculado en segundo plano. Todos bajan a cero a medida que lo resuelves.",
+        "Die Zahlen in der Werkzeugleiste messen, wie durcheinander das Feld ist. Manhattan summiert, wie weit jeder Stein von seinem Zielfeld entfernt ist, Heuristik zählt zusätzlich Steine, die sich gegenseitig blockieren, und Tatsächlich ist die im Hintergrund ermittelte kleinste Zahl von Zügen, die das Feld lösen. Alle fallen beim Lösen auf null.",
+    ],
+    [
+        "Stuck? Press Auto Solve to watch the solver finish the board one move at a time (auto-solved games do not count towards best times).",
+        "¿Atascado? Pulsa Resolver solo para ver cómo el solucionador termina el tablero movimiento a movimiento (las partidas resueltas automáticamente no cuentan para los mejores tiempos).",
+        "Festgefahren? Drücke Automatisch lösen, um zuzusehen, wie der Löser das Feld Zug für Zug beendet (automatisch gelöste Spiele zählen nicht für Bestzeiten).",
+    ],
+    [
+        "Press New Game to shuffle a board at the difficulty you pick. Replay this tour from Settings whenever you like.",
+        "Pulsa Nueva partida para mezclar un tablero con la dificultad que elijas. Repite este recorrido desde Ajustes cuando quieras.",
+        "Drücke Neues Spiel, um ein Feld mit der gewählten Schwierigkeit zu mischen. Diese Tour kannst du jederzeit in den Einstellungen wiederholen.",
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{Achievement, AssistTarget, ChallengeFailure};
+    use crate::model::analysis::MoveQuality;
+    use crate::model::{Difficulty, Direction, EntropyKind, GoalSpec, SolverKind};
+    use crate::presenter::config::Theme;
+    use crate::presenter::tile_renderer::{ColorMode, NumberStyle};
+    use crate::presenter::tutorial::TutorialStep;
+
+    /// Returns the message literal of every `t!` call in `source`, unescaped
+    fn messages_in(source: &str) -> Vec<String> {
+        let mut messages = Vec::new();
+        let mut rest = source;
+        while let Some(start) = rest.find("t!(") {
+            let is_macro = !rest[..start].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+            rest = rest[start + 3..].trim_start();
+            if !is_macro || !rest.starts_with('"') {
+                continue;
+            }
+            let mut message = String::new();
+            let mut chars = rest[1..].chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => message.push('\n'),
+                        Some('\n') => {
+                            chars = chars.as_str().trim_start().chars();
+                        }
+                        Some(escaped) => message.push(escaped),
+                        None => break,
+                    },
+                    c => message.push(c),
+                }
+            }
+            messages.push(message);
+            rest = chars.as_str();
+        }
+        messages
+    }
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut found: Vec<&str> = text
+            .match_indices('{')
+            .filter_map(|(i, _)| text[i..].split_once('}'))
+            .map(|(p, _)| p)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    #[test]
+    fn test_every_gui_message_has_a_row() {
+        // Read as text, so files behind features are checked in every build
+        let sources = [
+            ("gui_presenter.rs", include_str!("gui_presenter.rs")),
+            ("board_image.rs", include_str!("board_image.rs")),
+            ("capture.rs", include_str!("capture.rs")),
+            ("config.rs", include_str!("config.rs")),
+            ("input.rs", include_str!("input.rs")),
+            ("joystick.rs", include_str!("joystick.rs")),
+            ("replay_export.rs", include_str!("replay_export.rs")),
+            ("tile_renderer.rs", include_str!("tile_renderer.rs")),
+        ];
+        assert!(messages_in(sources[0].1).len() > 300);
+        for (file, source) in sources {
+            for message in messages_in(source) {
+                assert!(
+                    MESSAGES.iter().any(|row| row[0] == message),
+                    "no translation of {:?} from {}",
+                    message,
+                    file
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_shown_values_have_rows() {
+        let mut texts: Vec<String> = Vec::new();
+        texts.extend(Direction::ALL.iter().map(ToString::to_string));
+        texts.extend(
+            [ChallengeFailure::OutOfMoves, ChallengeFailure::OutOfTime]
+                .iter()
+                .map(ToString::to_string),
+        );
+        texts.extend(
+            [
+                AssistTarget::Moves(5),
+                AssistTarget::Tiles(1),
+                AssistTarget::Tiles(5),
+                AssistTarget::NextRow,
+            ]
+            .iter()
+            .map(ToString::to_string),
+        );
+        for achievement in Achievement::ALL {
+            texts.extend([achievement.to_string(), achievement.description().to_string()]);
+        }
+        for kind in EntropyKind::ALL {
+            texts.extend([kind.to_string(), kind.description().to_string()]);
+        }
+        for step in TutorialStep::ALL {
+            texts.extend([step.title().to_string(), step.text().to_string()]);
+        }
+        texts.extend(SolverKind::ALL.iter().map(ToString::to_string));
+        texts.extend(
+            [
+                Difficulty::Easy,
+                Difficulty::Medium,
+                Difficulty::Hard,
+                Difficulty::ExtraHard,
+            ]
+            .iter()
+            .map(ToString::to_string),
+        );
+        texts.extend(Theme::ALL.iter().map(ToString::to_string));
+        texts.extend(ColorMode::ALL.iter().map(ToString::to_string));
+        texts.extend(NumberStyle::ALL.iter().map(ToString::to_string));
+        texts.extend(GoalSpec::ALL.iter().map(ToString::to_string));
+        texts.extend(MoveQuality::ALL.iter().map(ToString::to_string));
+        #[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+        texts.extend(
+            crate::presenter::capture::CaptureSource::ALL
+                .iter()
+                .map(|s| s.to_string().to_lowercase()),
+        );
+
+        // Names without words, such as "A*", read the same in every language
+        for text in texts.iter().filter(|text| text.chars().any(char::is_lowercase)) {
+            assert!(
+                MESSAGES.iter().any(|row| row[0] == text),
+                "no translation of {:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_rows_are_unique_and_keep_their_placeholders() {
+        for (i, row) in MESSAGES.iter().enumerate() {
+            assert!(
+                MESSAGES[..i].iter().all(|earlier| earlier[0] != row[0]),
+                "{:?} is listed twice",
+                row[0]
+            );
+            let english = placeholders(row[0]);
+            let numbered = english.iter().any(|p| !p.is_empty());
+            for text in &row[1..] {
+                let translated = placeholders(text);
+                assert_eq!(
+                    translated.len(),
+                    english.len(),
+                    "{:?} has other placeholders than {:?}",
+                    text,
+                    row[0]
+                );
+                if numbered {
+                    assert_eq!(translated, english, "{:?} numbers its placeholders differently", text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_and_fill() {
+        assert_eq!(Locale::from_code("de-AT"), Some(Locale::German));
+        assert_eq!(Locale::from_code("ES"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("fr"), None);
+        assert_eq!(tr_in(Locale::English, "New Game"), "New Game");
+        assert_eq!(tr_in(Locale::Spanish, "New Game"), "Nueva partida");
+
+        assert_eq!(fill("{} of {}", &[&1, &"two"]), "1 of two");
+        assert_eq!(fill("{1}, {0}", &[&"a", &"b"]), "b, a");
+        assert_eq!(fill("{0}×{0} after {1}", &[&4, &9]), "4×4 after 9");
+        assert_eq!(fill("{} and {}", &[&1]), "1 and {}");
+        assert_eq!(fill("{name} {", &[&1]), "{name} {");
+        assert_eq!(warning("careful"), "\u{26A0} careful");
+    }
+}
@@ -0,0 +1,221 @@
+//! # Input Actions
+//!
+//! Keyboard shortcuts and gamepad buttons are both turned into [`InputAction`]s,
+//! so the sliding puzzle handles a button exactly as it handles the key bound
+//! to the same action.
+//!
+//! A gamepad slides tiles with its d-pad. For chain moves, the shoulder buttons
+//! step a target through the tiles in the empty cell's row and column, and A
+//! slides the target and every tile between it and the empty cell. Gamepads are
+//! read through a [`GamepadSource`], which the presenter polls once per frame;
+//! with the `gamepad` feature on Linux, `run_gui` reads the first joystick
+//! device (see `joystick`).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use slider::model::{Direction, PuzzleState};
+//! use slider::presenter::input::{line_targets, step_target, GamepadButton, InputAction};
+//!
+//! assert_eq!(GamepadButton::DPadUp.action(), InputAction::Slide(Direction::Up));
+//! let state = PuzzleState::new(3)?;
+//! assert_eq!(line_targets(&state), vec![(2, 0), (2, 1), (0, 2), (1, 2)]);
+//! assert_eq!(step_target(&state, None, true), Some((2, 0)));
+//! assert_eq!(step_target(&state, Some((1, 2)), true), Some((2, 0)));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::model::{Direction, Position, PuzzleState};
+use crate::presenter::config::KeyBindings;
+use eframe::egui;
+
+/// Something the player asked the sliding puzzle to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    /// Slide the tile next to the empty cell in this direction
+    Slide(Direction),
+    /// Move the chain-move target back through the empty cell's row and column
+    PreviousTarget,
+    /// Move the chain-move target on through the empty cell's row and column
+    NextTarget,
+    /// Slide the chain-move target and the tiles before it
+    Confirm,
+    Hint,
+    NewGame,
+    AutoSolve,
+}
+
+/// A gamepad button, named by its place on the pad
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    LeftShoulder,
+    RightShoulder,
+    /// A on Xbox pads, cross on PlayStation pads
+    South,
+    /// X on Xbox pads, square on PlayStation pads
+    West,
+    /// Y on Xbox pads, triangle on PlayStation pads
+    North,
+    Start,
+}
+
+impl GamepadButton {
+    /// Returns the action the button performs
+    pub fn action(self) -> InputAction {
+        match self {
+            GamepadButton::DPadUp => InputAction::Slide(Direction::Up),
+            GamepadButton::DPadDown => InputAction::Slide(Direction::Down),
+            GamepadButton::DPadLeft => InputAction::Slide(Direction::Left),
+            GamepadButton::DPadRight => InputAction::Slide(Direction::Right),
+            GamepadButton::LeftShoulder => InputAction::PreviousTarget,
+            GamepadButton::RightShoulder => InputAction::NextTarget,
+            GamepadButton::South => InputAction::Confirm,
+            GamepadButton::West => InputAction::Hint,
+            GamepadButton::North => InputAction::AutoSolve,
+            GamepadButton::Start => InputAction::NewGame,
+        }
+    }
+}
+
+/// Where gamepad button presses come from
+pub trait GamepadSource {
+    /// Returns the buttons pressed since the last poll, in order
+    fn poll(&mut self) -> Vec<GamepadButton>;
+}
+
+/// Returns each key binding with the action it performs
+pub fn key_actions(keys: &KeyBindings) -> [(egui::Key, InputAction); 7] {
+    let [up, down, left, right] = keys.slides().map(|(key, direction)| (key, InputAction::Slide(direction)));
+    [
+        up,
+        down,
+        left,
+        right,
+        (keys.hint, InputAction::Hint),
+        (keys.new_game, InputAction::NewGame),
+        (keys.auto_solve, InputAction::AutoSolve),
+    ]
+}
+
+/// Returns the actions of the buttons `source` saw pressed since its last poll
+pub fn gamepad_actions(source: &mut dyn GamepadSource) -> Vec<InputAction> {
+    source.poll().into_iter().map(GamepadButton::action).collect()
+}
+
+/// Returns the actions of the bound keys pressed this frame
+pub fn pressed_actions(ctx: &egui::Context, keys: &KeyBindings) -> Vec<InputAction> {
+    key_actions(keys)
+        .into_iter()
+        .filter(|&(key, _)| ctx.input(|i| i.key_pressed(key)))
+        .map(|(_, action)| action)
+        .collect()
+}
+
+/// Returns the tiles a chain move can slide: the empty cell's row from left to
+/// right, then its column from top to bottom
+pub fn line_targets(state: &PuzzleState) -> Vec<Position> {
+    let (row, col) = state.empty_position();
+    let size = state.size();
+    (0..size)
+        .map(|c| (row, c))
+        .chain((0..size).map(|r| (r, col)))
+        .filter(|&pos| pos != (row, col))
+        .collect()
+}
+
+/// Returns the target after `current` among `line_targets`, or before it if
+/// `forward` is false, wrapping around at either end
+///
+/// A target that is no longer in line with the empty cell starts over from the
+/// first tile (or the last, going back).
+pub fn step_target(state: &PuzzleState, current: Option<Position>, forward: bool) -> Option<Position> {
+    let targets = line_targets(state);
+    let count = targets.len();
+    let index = match current.and_then(|pos| targets.iter().position(|&target| target == pos)) {
+        Some(index) if forward => (index + 1) % count,
+        Some(index) => (index + count - 1) % count,
+        None if forward => 0,
+        None => count.checked_sub(1)?,
+    };
+    targets.get(index).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Replays canned polls, one per frame
+    struct FakeGamepad(VecDeque<Vec<GamepadButton>>);
+
+    impl GamepadSource for FakeGamepad {
+        fn poll(&mut self) -> Vec<GamepadButton> {
+            self.0.pop_front().unwrap_or_default()
+        }
+    }
+
+    #[test]
+    fn test_targets_follow_the_empty_cell() {
+        let mut state = PuzzleState::new(4).unwrap();
+        state.apply_immediate_move((2, 3));
+        state.apply_immediate_move((2, 2));
+        assert_eq!(state.empty_position(), (2, 2));
+        let targets = line_targets(&state);
+        assert_eq!(targets, vec![(2, 0), (2, 1), (2, 3), (0, 2), (1, 2), (3, 2)]);
+
+        assert_eq!(step_target(&state, Some((2, 3)), true), Some((0, 2)));
+        assert_eq!(step_target(&state, Some((2, 0)), false), Some((3, 2)));
+        assert_eq!(step_target(&state, None, false), Some((3, 2)));
+        // A target left behind by the last move starts over
+        assert_eq!(step_target(&state, Some((0, 0)), true), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_keys_and_buttons_share_actions() {
+        let keys = KeyBindings::default();
+        let actions = key_actions(&keys);
+        assert!(actions.contains(&(keys.slide_up, InputAction::Slide(Direction::Up))));
+        assert!(actions.contains(&(keys.hint, InputAction::Hint)));
+
+        // Every keyboard action has a button, and the pad adds chain-move targeting
+        for (_, action) in actions {
+            assert!(
+                [
+                    GamepadButton::DPadUp,
+                    GamepadButton::DPadDown,
+                    GamepadButton::DPadLeft,
+                    GamepadButton::DPadRight,
+                    GamepadButton::West,
+                    GamepadButton::North,
+                    GamepadButton::Start,
+                ]
+                .iter()
+                .any(|button| button.action() == action)
+            );
+        }
+        assert_eq!(GamepadButton::RightShoulder.action(), InputAction::NextTarget);
+        assert_eq!(GamepadButton::South.action(), InputAction::Confirm);
+    }
+
+    #[test]
+    fn test_gamepad_presses_become_actions() {
+        let mut pad = FakeGamepad(VecDeque::from([
+            vec![GamepadButton::DPadLeft],
+            vec![],
+            vec![GamepadButton::RightShoulder, GamepadButton::South],
+            vec![GamepadButton::West, GamepadButton::Start, GamepadButton::North],
+        ]));
+        assert_eq!(gamepad_actions(&mut pad), [InputAction::Slide(Direction::Left)]);
+        assert!(gamepad_actions(&mut pad).is_empty());
+        assert_eq!(gamepad_actions(&mut pad), [InputAction::NextTarget, InputAction::Confirm]);
+        assert_eq!(
+            gamepad_actions(&mut pad),
+            [InputAction::Hint, InputAction::NewGame, InputAction::AutoSolve]
+        );
+        assert!(gamepad_actions(&mut pad).is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+//! Gamepads read through the Linux joystick interface.
+//!
+//! Built with the `gamepad` feature. Rather than link a gamepad library, the
+//! first `/dev/input/js*` device is read directly: the kernel reports every
+//! button and axis change as an 8-byte `js_event`. A thread blocks on the device
+//! and queues the presses for [`JoystickSource::poll`], so a frame never waits on
+//! the pad. Buttons are numbered as the kernel's `xpad` driver numbers them for
+//! Xbox-style pads, which most other pads follow. The pad must be plugged in
+//! when the GUI starts.
+
+use crate::presenter::input::{GamepadButton, GamepadSource};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Devices tried by `JoystickSource::open_first`, in order
+const DEVICES: [&str; 4] = ["/dev/input/js0", "/dev/input/js1", "/dev/input/js2", "/dev/input/js3"];
+
+/// Size of a `js_event`: time (u32), value (i16), type (u8), number (u8)
+pub const EVENT_BYTES: usize = 8;
+
+const JS_EVENT_BUTTON: u8 = 0x01;
+const JS_EVENT_AXIS: u8 = 0x02;
+/// Marks the events sent on open that describe the pad's state, not a change
+const JS_EVENT_INIT: u8 = 0x80;
+
+/// Axes the d-pad reports on (a hat switch, as `xpad` exposes it)
+const DPAD_X_AXIS: u8 = 6;
+const DPAD_Y_AXIS: u8 = 7;
+
+/// Axis value past which a d-pad direction counts as held
+const DPAD_THRESHOLD: i16 = 16_384;
+
+/// Turns joystick events into button presses
+///
+/// The d-pad is tracked per axis, so a direction is pressed once as it goes down
+/// and not again while it is held or as it comes back up.
+#[derive(Debug, Default)]
+pub struct EventDecoder {
+    dpad: [i8; 2], // Direction held on the x and y axes: -1, 0 or 1
+}
+
+impl EventDecoder {
+    /// Returns the button pressed by one `js_event`, if it pressed one
+    pub fn decode(&mut self, event: [u8; EVENT_BYTES]) -> Option<GamepadButton> {
+        let value = i16::from_ne_bytes([event[4], event[5]]);
+        let (kind, number) = (event[6], event[7]);
+        let initial = kind & JS_EVENT_INIT != 0;
+        match kind & !JS_EVENT_INIT {
+            JS_EVENT_BUTTON if value == 1 && !initial => button(number),
+            JS_EVENT_AXIS if number == DPAD_X_AXIS || number == DPAD_Y_AXIS => {
+                let axis = usize::from(number - DPAD_X_AXIS);
+                let direction = match value {
+                    v if v <= -DPAD_THRESHOLD => -1,
+                    v if v >= DPAD_THRESHOLD => 1,
+                    _ => 0,
+                };
+                let pressed = direction != 0 && direction != self.dpad[axis] && !initial;
+                self.dpad[axis] = direction;
+                match (pressed, axis, direction) {
+                    (false, ..) => None,
+                    (true, 0, -1) => Some(GamepadButton::DPadLeft),
+                    (true, 0, _) => Some(GamepadButton::DPadRight),
+                    (true, _, -1) => Some(GamepadButton::DPadUp),
+                    (true, ..) => Some(GamepadButton::DPadDown),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the button the joystick interface numbers `number`
+/// Pads whose d-pad is four buttons (`xpad` with `dpad_to_buttons`) use 11 to 14
+fn button(number: u8) -> Option<GamepadButton> {
+    match number {
+        0 => Some(GamepadButton::South),
+        2 => Some(GamepadButton::West),
+        3 => Some(GamepadButton::North),
+        4 => Some(GamepadButton::LeftShoulder),
+        5 => Some(GamepadButton::RightShoulder),
+        7 => Some(GamepadButton::Start),
+        11 => Some(GamepadButton::DPadLeft),
+        12 => Some(GamepadButton::DPadRight),
+        13 => Some(GamepadButton::DPadUp),
+        14 => Some(GamepadButton::DPadDown),
+        _ => None,
+    }
+}
+
+/// A gamepad read from a joystick device on a thread of its own
+pub struct JoystickSource {
+    presses: Receiver<GamepadButton>,
+}
+
+impl JoystickSource {
+    /// Opens the first joystick device that is present
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from opening the last device tried
+    pub fn open_first() -> io::Result<Self> {
+        let mut error = io::Error::new(io::ErrorKind::NotFound, "no joystick device");
+        for device in DEVICES {
+            match Self::open(device) {
+                Ok(source) => {
+                    log::info!("Reading gamepad buttons from {}", device);
+                    return Ok(source);
+                }
+                Err(e) => error = e,
+            }
+        }
+        Err(error)
+    }
+
+    /// Opens the joystick device at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from opening the device
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_reader(File::open(path)?))
+    }
+
+    /// Reads `js_event`s from `reader` until it ends, fails, or the source is dropped
+    pub fn from_reader(mut reader: impl Read + Send + 'static) -> Self {
+        let (sender, presses) = mpsc::channel();
+        thread::spawn(move || {
+            let mut decoder = EventDecoder::default();
+            let mut event = [0; EVENT_BYTES];
+            while reader.read_exact(&mut event).is_ok() {
+                if let Some(button) = decoder.decode(event) {
+                    if sender.send(button).is_err() {
+                        return;
+                    }
+                }
+            }
+            log::info!("Gamepad disconnected");
+        });
+        Self { presses }
+    }
+}
+
+impl GamepadSource for JoystickSource {
+    fn poll(&mut self) -> Vec<GamepadButton> {
+        self.presses.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::time::{Duration, Instant};
+
+    /// Encodes a `js_event` as the kernel writes it
+    fn event(kind: u8, number: u8, value: i16) -> [u8; EVENT_BYTES] {
+        let [low, high] = value.to_ne_bytes();
+        [0, 0, 0, 0, low, high, kind, number]
+    }
+
+    #[test]
+    fn test_decode_buttons_and_dpad() {
+        let mut decoder = EventDecoder::default();
+        assert_eq!(decoder.decode(event(JS_EVENT_BUTTON, 0, 1)), Some(GamepadButton::South));
+        assert_eq!(decoder.decode(event(JS_EVENT_BUTTON, 0, 0)), None); // Released
+        assert_eq!(decoder.decode(event(JS_EVENT_BUTTON | JS_EVENT_INIT, 7, 1)), None);
+        assert_eq!(
+            decoder.decode(event(JS_EVENT_BUTTON, 13, 1)),
+            Some(GamepadButton::DPadUp)
+        );
+
+        // A held direction presses once, and again only after it comes back up
+        assert_eq!(
+            decoder.decode(event(JS_EVENT_AXIS, DPAD_X_AXIS, -32_767)),
+            Some(GamepadButton::DPadLeft)
+        );
+        assert_eq!(decoder.decode(event(JS_EVENT_AXIS, DPAD_X_AXIS, -32_767)), None);
+        assert_eq!(decoder.decode(event(JS_EVENT_AXIS, DPAD_X_AXIS, 0)), None);
+        assert_eq!(
+            decoder.decode(event(JS_EVENT_AXIS, DPAD_X_AXIS, 32_767)),
+            Some(GamepadButton::DPadRight)
+        );
+        assert_eq!(
+            decoder.decode(event(JS_EVENT_AXIS, DPAD_Y_AXIS, 32_767)),
+            Some(GamepadButton::DPadDown)
+        );
+        // Stick axes are not the d-pad
+        assert_eq!(decoder.decode(event(JS_EVENT_AXIS, 0, 32_767)), None);
+    }
+
+    #[test]
+    fn test_source_reads_events_in_order() {
+        let events = [
+            event(JS_EVENT_BUTTON | JS_EVENT_INIT, 5, 0),
+            event(JS_EVENT_AXIS, DPAD_Y_AXIS, -32_767),
+            event(JS_EVENT_BUTTON, 5, 1),
+            event(JS_EVENT_BUTTON, 5, 0),
+            event(JS_EVENT_BUTTON, 2, 1),
+        ];
+        let mut source = JoystickSource::from_reader(Cursor::new(events.concat()));
+
+        let started = Instant::now();
+        let mut pressed = Vec::new();
+        while pressed.len() < 3 && started.elapsed() < Duration::from_secs(5) {
+            pressed.extend(source.poll());
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(
+            pressed,
+            [GamepadButton::DPadUp, GamepadButton::RightShoulder, GamepadButton::West]
+        );
+        assert!(source.poll().is_empty());
+    }
+}
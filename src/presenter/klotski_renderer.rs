@@ -0,0 +1,92 @@
+use crate::model::{BlockShape, KlotskiBoard};
+use egui::{Color32, Pos2, Rect, Sense, Ui, Vec2};
+
+/// Goal block color (pale red)
+const GOAL_COLOR: Color32 = Color32::from_rgb(255, 160, 160);
+
+/// Background of the board's free cells
+const FLOOR_COLOR: Color32 = Color32::from_rgb(50, 50, 50);
+
+/// Renders Klotski boards, whose blocks span one to four cells
+pub struct KlotskiRenderer {
+    cell_size: f32,
+    gap: f32,
+}
+
+impl KlotskiRenderer {
+    pub fn new(cell_size: f32, gap: f32) -> Self {
+        Self { cell_size, gap }
+    }
+
+    /// Returns the screen rectangle covering `size` cells from `cell`
+    fn cell_rect(&self, top_left: Pos2, cell: (usize, usize), size: (usize, usize)) -> Rect {
+        let step = self.cell_size + self.gap;
+        let min = top_left + Vec2::new(cell.1 as f32 * step, cell.0 as f32 * step);
+        let span = |cells: usize| cells as f32 * step - self.gap;
+        Rect::from_min_size(min, Vec2::new(span(size.1), span(size.0)))
+    }
+
+    fn block_color(shape: BlockShape) -> Color32 {
+        match shape {
+            BlockShape::Small => Color32::from_rgb(135, 206, 235),
+            BlockShape::Wide => Color32::from_rgb(255, 190, 140),
+            BlockShape::Tall => Color32::from_rgb(170, 225, 160),
+            BlockShape::Big => Color32::from_rgb(215, 180, 235),
+        }
+    }
+
+    /// Draws the board, outlining the goal and ringing the `highlight` block
+    /// Returns the clicked block and the click's offset from the block center
+    pub fn render(
+        &self,
+        ui: &mut Ui,
+        board: &KlotskiBoard,
+        top_left: Pos2,
+        highlight: Option<(usize, f32)>,
+    ) -> Option<(usize, Vec2)> {
+        let board_rect = self.cell_rect(top_left, (0, 0), (board.rows(), board.cols()));
+        ui.painter().rect_filled(board_rect.expand(self.gap), 5.0, FLOOR_COLOR);
+
+        // Goal outline sits under the blocks so it shows only while uncovered
+        let (goal_block, goal_position) = board.goal();
+        let goal_shape = board.blocks()[goal_block].shape;
+        let goal_rect = self.cell_rect(top_left, goal_position, (goal_shape.height(), goal_shape.width()));
+        ui.painter().rect_stroke(goal_rect, 5.0, (3.0, GOAL_COLOR));
+
+        let mut clicked = None;
+        for (index, block) in board.blocks().iter().enumerate() {
+            let rect = self.cell_rect(top_left, block.position, (block.shape.height(), block.shape.width()));
+            let response = ui.allocate_rect(rect, Sense::click());
+
+            let color = if response.hovered() {
+                Color32::from_rgb(255, 255, 150)
+            } else if index == goal_block {
+                GOAL_COLOR
+            } else {
+                Self::block_color(block.shape)
+            };
+            ui.painter().rect_filled(rect, 5.0, color);
+            ui.painter()
+                .rect_stroke(rect, 5.0, (2.0, Color32::from_rgb(80, 80, 80)));
+
+            if let Some((_, intensity)) = highlight.filter(|&(block, _)| block == index) {
+                let alpha = (intensity.clamp(0.0, 1.0) * 255.0) as u8;
+                let ring = Color32::from_rgba_unmultiplied(255, 140, 0, alpha);
+                ui.painter().rect_stroke(rect, 5.0, (4.0, ring));
+            }
+
+            if response.clicked() {
+                let offset = response
+                    .interact_pointer_pos()
+                    .map_or(Vec2::ZERO, |pos| pos - rect.center());
+                clicked = Some((index, offset));
+            }
+        }
+        clicked
+    }
+
+    /// Calculates the total size needed for the board
+    pub fn board_size(&self, board: &KlotskiBoard) -> Vec2 {
+        self.cell_rect(Pos2::ZERO, (0, 0), (board.rows(), board.cols())).size()
+    }
+}
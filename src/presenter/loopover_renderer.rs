@@ -0,0 +1,105 @@
+use crate::model::{Direction, LoopoverBoard, LoopoverMove};
+use egui::ecolor::Hsva;
+use egui::{Color32, FontId, Pos2, Rect, Sense, Ui, Vec2};
+
+/// Pointer travel (in points) before a press counts as dragging a line
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Renders Loopover boards and turns drags into row and column rotations
+pub struct LoopoverRenderer {
+    cell_size: f32,
+    gap: f32,
+}
+
+impl LoopoverRenderer {
+    pub fn new(cell_size: f32, gap: f32) -> Self {
+        Self { cell_size, gap }
+    }
+
+    fn step(&self) -> f32 {
+        self.cell_size + self.gap
+    }
+
+    /// Returns the screen rectangle of the cell at `pos`
+    fn cell_rect(&self, top_left: Pos2, pos: (usize, usize)) -> Rect {
+        let min = top_left + Vec2::new(pos.1 as f32 * self.step(), pos.0 as f32 * self.step());
+        Rect::from_min_size(min, Vec2::splat(self.cell_size))
+    }
+
+    /// Tiles of one goal row share a hue, so rows are easy to pick out while scrambled
+    fn tile_color(home_row: usize, size: usize) -> Color32 {
+        Hsva::new(home_row as f32 / size as f32, 0.45, 0.95, 1.0).into()
+    }
+
+    /// Draws the board, sliding the dragged row or column with the pointer
+    /// Returns the rotations to play once a drag is released (empty otherwise)
+    pub fn render(&self, ui: &mut Ui, board: &LoopoverBoard, top_left: Pos2) -> Vec<LoopoverMove> {
+        let n = board.size();
+        let board_rect = Rect::from_min_size(top_left, self.board_size(board));
+        let response = ui.allocate_rect(board_rect, Sense::drag());
+
+        // The pressed cell picks the line; the longer axis of travel picks row or column
+        let drag = ui
+            .input(|i| i.pointer.press_origin().zip(i.pointer.latest_pos()))
+            .filter(|_| response.dragged() || response.drag_stopped())
+            .and_then(|(origin, latest)| {
+                let cell = (origin - top_left) / self.step();
+                let delta = latest - origin;
+                let on_board = (0.0..n as f32).contains(&cell.x) && (0.0..n as f32).contains(&cell.y);
+                (on_board && delta.length() >= DRAG_THRESHOLD).then(|| {
+                    if delta.x.abs() >= delta.y.abs() {
+                        (true, cell.y as usize, delta.x)
+                    } else {
+                        (false, cell.x as usize, delta.y)
+                    }
+                })
+            });
+
+        let painter = ui.painter_at(board_rect);
+        let span = n as f32 * self.step();
+        for row in 0..n {
+            for col in 0..n {
+                let offset = match drag {
+                    Some((true, line, travel)) if line == row => Vec2::new(travel.rem_euclid(span), 0.0),
+                    Some((false, line, travel)) if line == col => Vec2::new(0.0, travel.rem_euclid(span)),
+                    _ => Vec2::ZERO,
+                };
+                let tile = board.tile_at((row, col));
+                let color = Self::tile_color(board.home_of(tile).0, n);
+                // A shifted tile is drawn again one board back, to fill the edge it leaves
+                for wrap in [Vec2::ZERO, -offset.normalized() * span] {
+                    let rect = self.cell_rect(top_left, (row, col)).translate(offset + wrap);
+                    painter.rect_filled(rect, 5.0, color);
+                    painter.rect_stroke(rect, 5.0, (2.0, Color32::from_rgb(80, 80, 80)));
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        tile.to_string(),
+                        FontId::proportional(self.cell_size * 0.4),
+                        Color32::BLACK,
+                    );
+                    if offset == Vec2::ZERO {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let Some((is_row, line, travel)) = drag.filter(|_| response.drag_stopped()) else {
+            return Vec::new();
+        };
+        let steps = (travel / self.step()).round() as isize % n as isize;
+        let direction = match (is_row, steps > 0) {
+            (true, true) => Direction::Right,
+            (true, false) => Direction::Left,
+            (false, true) => Direction::Down,
+            (false, false) => Direction::Up,
+        };
+        vec![LoopoverMove { line, direction }; steps.unsigned_abs()]
+    }
+
+    /// Calculates the total size needed for the board
+    pub fn board_size(&self, board: &LoopoverBoard) -> Vec2 {
+        Vec2::splat(board.size() as f32 * self.step() - self.gap)
+    }
+}
@@ -1,10 +1,35 @@
 //! Presenter layer handling GUI rendering and user input.
 //!
 //! This module contains the egui-based graphical user interface implementation,
-//! including tile rendering and visual feedback for player interactions.
+//! including tile rendering, the Klotski and Loopover board renderers, visual feedback
+//! for player interactions, keyboard and gamepad input, the first-launch
+//! tutorial, the English, Spanish and German text of the GUI, the `slider.toml`
+//! configuration, (with the `capture` feature) screenshot and webcam pictures
+//! for the tiles, (with the `replay-export` feature) solves exported as
+//! animated GIFs or MP4s, and (with the `gamepad` feature) gamepads read from
+//! the Linux joystick devices.
 
+pub mod board_image;
+#[cfg(all(feature = "capture", not(target_arch = "wasm32")))]
+pub mod capture;
+pub mod config;
 pub mod gui_presenter;
+pub mod i18n;
+pub mod input;
+#[cfg(all(feature = "gamepad", target_os = "linux"))]
+pub mod joystick;
+pub mod klotski_renderer;
+pub mod loopover_renderer;
+#[cfg(all(feature = "replay-export", not(target_arch = "wasm32")))]
+pub mod replay_export;
 pub mod tile_renderer;
+pub mod tutorial;
 
-pub use gui_presenter::{run_gui, GuiPresenter};
-pub use tile_renderer::TileRenderer;
\ No newline at end of file
+pub use config::{Config, KeyBindings, Theme};
+pub use gui_presenter::{run_gui, run_gui_with_config, GuiPresenter};
+pub use i18n::Locale;
+pub use input::{GamepadButton, GamepadSource, InputAction};
+pub use klotski_renderer::KlotskiRenderer;
+pub use loopover_renderer::LoopoverRenderer;
+pub use tile_renderer::{ColorMode, NumberStyle, TileRenderer};
+pub use tutorial::{Tutorial, TutorialEvent, TutorialStep};
\ No newline at end of file
@@ -0,0 +1,450 @@
+//! Replay export: a solved game as an animated GIF or an MP4, for sharing.
+//!
+//! Built with the `replay-export` feature. Frames are drawn headlessly by
+//! [`TileRenderer::rasterize`] from the game's starting board and recorded
+//! slides, one frame per slide, with the first and last boards held. GIFs are
+//! written by a small built-in encoder (one global palette, LZW, looping
+//! forever), so they need no extra libraries. MP4s are piped as raw frames
+//! through the system's own `ffmpeg`, as the `capture` feature does for webcams.
+
+use crate::controller::storage;
+use crate::model::{Position, PuzzleState};
+use crate::presenter::tile_renderer::TileRenderer;
+use eframe::egui::{Color32, ColorImage};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Time each slide is shown, in hundredths of a second (the GIF time unit)
+pub const STEP_CENTIS: u16 = 15;
+
+/// Time the starting and solved boards are held, in hundredths of a second
+pub const HOLD_CENTIS: u16 = 150;
+
+/// Subdirectory of the data directory that exports are written to
+pub const REPLAY_DIR: &str = "replays";
+
+/// Largest code the GIF flavor of LZW may use (12 bits)
+const MAX_LZW_CODE: u16 = 4095;
+
+/// File type of an exported replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayFormat {
+    /// Animated GIF, encoded in-process
+    Gif,
+    /// H.264 video, encoded by `ffmpeg`
+    Mp4,
+}
+
+impl ReplayFormat {
+    pub const ALL: [ReplayFormat; 2] = [ReplayFormat::Gif, ReplayFormat::Mp4];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReplayFormat::Gif => "gif",
+            ReplayFormat::Mp4 => "mp4",
+        }
+    }
+}
+
+impl fmt::Display for ReplayFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayFormat::Gif => write!(f, "GIF"),
+            ReplayFormat::Mp4 => write!(f, "MP4"),
+        }
+    }
+}
+
+/// Why an export failed
+#[derive(Debug)]
+pub enum ExportError {
+    /// `ffmpeg` is not installed, so MP4s cannot be encoded
+    NoEncoder,
+    /// `ffmpeg` ran but did not produce a video
+    EncoderFailed(String),
+    /// The export could not be written
+    Io(io::Error),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::NoEncoder => write!(f, "No video encoder found (install ffmpeg, or export a GIF)"),
+            ExportError::EncoderFailed(message) => write!(f, "ffmpeg failed: {}", message),
+            ExportError::Io(e) => write!(f, "Could not write the replay: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+/// Returns a fresh path for an export inside the data directory
+/// Returns None if no data directory can be determined
+pub fn replay_file(format: ReplayFormat, grid_size: usize) -> Option<PathBuf> {
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let name = format!("slider-{}x{}-{}.{}", grid_size, grid_size, stamp, format.extension());
+    storage::data_file(REPLAY_DIR).map(|dir| dir.join(name))
+}
+
+/// Returns the frames of a replay: the starting board, then the board after each slide
+/// Slides that cannot be played are skipped
+pub fn replay_frames<'a>(
+    renderer: &'a TileRenderer,
+    initial: &PuzzleState,
+    slides: &'a [Position],
+) -> impl Iterator<Item = ColorImage> + Clone + 'a {
+    let start = renderer.rasterize(initial, &[]);
+    let boards = slides.iter().scan(initial.clone(), |state, &pos| Some(state.apply_immediate_move(pos).then(|| state.clone())));
+    std::iter::once(start).chain(boards.flatten().map(move |state| renderer.rasterize(&state, &[])))
+}
+
+/// Renders the replay of `slides` from `initial` and writes it to `path`
+///
+/// Blocks until the file is written; long MP4 exports can take a few seconds.
+///
+/// # Errors
+///
+/// Returns `ExportError::NoEncoder` if an MP4 is asked for without `ffmpeg`
+/// installed, `ExportError::EncoderFailed` if `ffmpeg` fails, or
+/// `ExportError::Io` if the file cannot be written
+pub fn export_replay(
+    renderer: &TileRenderer,
+    initial: &PuzzleState,
+    slides: &[Position],
+    format: ReplayFormat,
+    path: &Path,
+) -> Result<(), ExportError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let frames = replay_frames(renderer, initial, slides);
+    match format {
+        ReplayFormat::Gif => fs::write(path, encode_gif(frames))?,
+        ReplayFormat::Mp4 => encode_mp4(frames, path)?,
+    }
+    log::info!("Exported a {}-slide replay to {}", slides.len(), path.display());
+    Ok(())
+}
+
+/// Encodes frames of equal size as a looping animated GIF
+///
+/// The first and last frames are held for `HOLD_CENTIS`, the rest shown for
+/// `STEP_CENTIS`. The frames are walked twice, once to build the palette and
+/// once to encode, so only two are ever held in memory. Past 256 colors, colors
+/// are rounded to fewer levels per channel until they fit.
+pub fn encode_gif<I>(frames: I) -> Vec<u8>
+where
+    I: Iterator<Item = ColorImage> + Clone,
+{
+    let colors: HashSet<[u8; 3]> = frames.clone().flat_map(|frame| frame.pixels).map(rgb).collect();
+    let shift = (0..8)
+        .find(|&shift| colors.iter().map(|&c| quantize(c, shift)).collect::<HashSet<_>>().len() <= 256)
+        .unwrap_or(8);
+    let mut palette: Vec<[u8; 3]> = colors.iter().map(|&c| quantize(c, shift)).collect::<HashSet<_>>().into_iter().collect();
+    palette.sort_unstable();
+    let index: HashMap<[u8; 3], u8> = palette.iter().enumerate().map(|(i, &c)| (c, i as u8)).collect();
+    let table_bits = (palette.len().max(4) as f64).log2().ceil() as u8;
+    palette.resize(1 << table_bits, [0, 0, 0]);
+
+    let mut frames = frames.peekable();
+    let Some(first) = frames.peek() else {
+        return Vec::new();
+    };
+    let (width, height) = (first.width(), first.height());
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend_from_slice(&(width as u16).to_le_bytes());
+    gif.extend_from_slice(&(height as u16).to_le_bytes());
+    gif.extend_from_slice(&[0xF0 | (table_bits - 1), 0, 0]); // Global table, 8-bit color resolution
+    gif.extend(palette.iter().flatten());
+    // Loop forever
+    gif.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+    gif.extend_from_slice(b"NETSCAPE2.0");
+    gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    let mut previous: Option<ColorImage> = None;
+    while let Some(frame) = frames.next() {
+        let held = previous.is_none() || frames.peek().is_none();
+        let delay = if held { HOLD_CENTIS } else { STEP_CENTIS };
+        // Only the part that changed is stored; earlier frames show through the rest
+        let (left, top, right, bottom) = match &previous {
+            Some(previous) => changed_bounds(previous, &frame).unwrap_or((0, 0, 1, 1)),
+            None => (0, 0, width, height),
+        };
+        gif.extend_from_slice(&[0x21, 0xF9, 0x04, 0x04]); // Graphic control: leave the frame in place
+        gif.extend_from_slice(&delay.to_le_bytes());
+        gif.extend_from_slice(&[0x00, 0x00]);
+        gif.push(0x2C);
+        for value in [left, top, right - left, bottom - top] {
+            gif.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        gif.push(0x00);
+
+        let indices: Vec<u8> = (top..bottom)
+            .flat_map(|y| (left..right).map(move |x| (x, y)))
+            .map(|(x, y)| index[&quantize(rgb(frame[(x, y)]), shift)])
+            .collect();
+        let min_code_size = table_bits.max(2);
+        gif.push(min_code_size);
+        for block in lzw_encode(&indices, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend_from_slice(block);
+        }
+        gif.push(0x00);
+        previous = Some(frame);
+    }
+    gif.push(0x3B);
+    gif
+}
+
+fn rgb(color: Color32) -> [u8; 3] {
+    [color.r(), color.g(), color.b()]
+}
+
+/// Drops the low `shift` bits of each channel, keeping the middle of the range they covered
+fn quantize(color: [u8; 3], shift: u32) -> [u8; 3] {
+    if shift == 0 {
+        return color;
+    }
+    color.map(|channel| ((channel >> shift) << shift) | (1 << (shift - 1)))
+}
+
+/// Returns the bounds (left, top, right, bottom; exclusive) of the pixels that
+/// differ between two frames of equal size, or None if they are identical
+fn changed_bounds(a: &ColorImage, b: &ColorImage) -> Option<(usize, usize, usize, usize)> {
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for y in 0..a.height() {
+        for x in 0..a.width() {
+            if a[(x, y)] != b[(x, y)] {
+                let (l, t, r, bt) = bounds.unwrap_or((x, y, x + 1, y + 1));
+                bounds = Some((l.min(x), t.min(y), r.max(x + 1), bt.max(y + 1)));
+            }
+        }
+    }
+    bounds
+}
+
+/// Compresses palette indices with GIF's variable-width LZW
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter::default();
+    let mut code_size = min_code_size + 1;
+    let mut next = end + 1;
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+
+    out.write(clear, code_size);
+    let Some((&first, rest)) = indices.split_first() else {
+        out.write(end, code_size);
+        return out.finish();
+    };
+    let mut prefix = first as u16;
+    for &index in rest {
+        if let Some(&code) = codes.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        out.write(prefix, code_size);
+        if next <= MAX_LZW_CODE {
+            codes.insert((prefix, index), next);
+            // The decoder widens its codes when it adds this entry, one code later
+            if next == 1 << code_size {
+                code_size += 1;
+            }
+            next += 1;
+        } else {
+            out.write(clear, code_size);
+            codes.clear();
+            code_size = min_code_size + 1;
+            next = end + 1;
+        }
+        prefix = index as u16;
+    }
+    out.write(prefix, code_size);
+    out.write(end, code_size);
+    out.finish()
+}
+
+/// Packs codes least significant bit first, as GIF expects
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    pending: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.pending |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.pending as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Pipes the frames through `ffmpeg` into an H.264 MP4 at `path`
+fn encode_mp4<I: Iterator<Item = ColorImage>>(frames: I, path: &Path) -> Result<(), ExportError> {
+    let mut frames = frames.peekable();
+    let Some(first) = frames.peek() else {
+        return Ok(());
+    };
+    let size = format!("{}x{}", first.width(), first.height());
+    let spawned = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-f", "rawvideo", "-pix_fmt", "rgba", "-s", &size])
+        .args(["-framerate", &format!("100/{}", STEP_CENTIS), "-i", "-"])
+        // H.264 in yuv420p wants even dimensions
+        .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2", "-pix_fmt", "yuv420p", "-c:v", "libx264"])
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(ExportError::NoEncoder),
+        Err(e) => return Err(ExportError::Io(e)),
+    };
+
+    // Held boards are repeated, since every video frame lasts one step
+    let hold = (HOLD_CENTIS / STEP_CENTIS).max(1) as usize;
+    let mut written = Ok(());
+    if let Some(mut stdin) = child.stdin.take() {
+        let mut is_first = true;
+        while let Some(frame) = frames.next() {
+            let repeats = if is_first || frames.peek().is_none() { hold } else { 1 };
+            is_first = false;
+            written = (0..repeats).try_for_each(|_| stdin.write_all(frame.as_raw()));
+            if written.is_err() {
+                break;
+            }
+        }
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(ExportError::EncoderFailed(if message.is_empty() { output.status.to_string() } else { message }));
+    }
+    written.map_err(ExportError::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes one GIF LZW stream back into palette indices
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.extend([Vec::new(), Vec::new()]);
+        };
+        reset(&mut table);
+        let (mut code_size, mut bit, mut out, mut last): (u8, usize, Vec<u8>, Option<Vec<u8>>) =
+            (min_code_size + 1, 0, Vec::new(), None);
+        loop {
+            let code = (0..code_size as usize).fold(0, |code, i| {
+                let b = bit + i;
+                code | (((data[b / 8] >> (b % 8)) & 1) as usize) << i
+            });
+            bit += code_size as usize;
+            if code == clear {
+                reset(&mut table);
+                code_size = min_code_size + 1;
+                last = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (table.get(code), &last) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(last)) => [last.clone(), vec![last[0]]].concat(),
+                (None, None) => panic!("code {} before any entry", code),
+            };
+            out.extend(&entry);
+            if let Some(last) = last {
+                if table.len() < 4096 {
+                    table.push([last, vec![entry[0]]].concat());
+                }
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            last = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trips() {
+        // Long enough to fill the code table and force a clear
+        let noisy: Vec<u8> = (0..40_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8 % 16).collect();
+        let runs: Vec<u8> = (0..5000).map(|i| (i / 37 % 3) as u8).collect();
+        for (indices, min_code_size) in [(noisy, 4), (runs, 2), (vec![1], 2), (Vec::new(), 2)] {
+            assert_eq!(lzw_decode(&lzw_encode(&indices, min_code_size), min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn test_gif_holds_first_and_last_frames() {
+        let frame = |color| ColorImage::new([6, 4], color);
+        let frames = vec![frame(Color32::RED), frame(Color32::BLUE), frame(Color32::BLUE), frame(Color32::RED)];
+        let gif = encode_gif(frames.into_iter());
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif[6..10], [6, 0, 4, 0]);
+        assert_eq!(gif.last(), Some(&0x3B));
+
+        let delays: Vec<u16> = gif
+            .windows(8)
+            .filter(|w| w[..4] == [0x21, 0xF9, 0x04, 0x04] && w[6..] == [0x00, 0x00])
+            .map(|w| u16::from_le_bytes([w[4], w[5]]))
+            .collect();
+        assert_eq!(delays, [HOLD_CENTIS, STEP_CENTIS, STEP_CENTIS, HOLD_CENTIS]);
+        assert!(encode_gif(std::iter::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_replay_frames_follow_the_slides() {
+        let renderer = TileRenderer::new(20.0, 2.0, 3);
+        let initial: PuzzleState = "1 2 3 / 4 5 6 / 7 _ 8".parse().unwrap();
+        let slides = [(2, 2), (0, 0)]; // The second cannot be played
+        let frames: Vec<ColorImage> = replay_frames(&renderer, &initial, &slides).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].size, frames[1].size);
+        assert_eq!(frames[1], renderer.rasterize(&PuzzleState::new(3).unwrap(), &[]));
+        assert!(changed_bounds(&frames[0], &frames[1]).is_some());
+    }
+
+    #[test]
+    fn test_many_colors_fit_one_palette() {
+        let mut gradient = ColorImage::new([32, 32], Color32::BLACK);
+        for y in 0..32 {
+            for x in 0..32 {
+                gradient[(x, y)] = Color32::from_rgb(x as u8 * 8, y as u8 * 8, 128);
+            }
+        }
+        let gif = encode_gif(std::iter::once(gradient));
+        assert_eq!(gif[10] & 0x07, 7); // 256-entry global table
+    }
+}
@@ -1,15 +1,427 @@
-use crate::model::{Tile, TileContent};
-use egui::{Color32, FontId, Pos2, Rect, Sense, Ui, Vec2};
+use crate::model::{Position, PuzzleState, Tile, TileContent};
+use crate::presenter::board_image::{Canvas, Surface, SvgCanvas};
+use egui::{Color32, ColorImage, FontId, Pos2, Rect, Sense, TextureHandle, Ui, Vec2};
+use std::fmt;
+
+/// Tile color for distance 0 in `ColorMode::Distance` (light sky blue)
+const HOME_COLOR: (u8, u8, u8) = (135, 206, 235);
+
+/// Tile color for the farthest possible distance (pale red)
+const FAR_COLOR: (u8, u8, u8) = (255, 160, 160);
+
+/// Tile color when coloring is off
+const PLAIN_COLOR: Color32 = Color32::from_rgb(210, 210, 210);
+
+/// Widest a label may be, as a fraction of the tile size
+const LABEL_FILL: f32 = 0.85;
+
+/// Tile numbers per color band in `ColorMode::Band`
+const BAND_WIDTH: u32 = 5;
+
+/// Colors cycled through by the row and band modes
+const PALETTE: [Color32; 6] = [
+    Color32::from_rgb(135, 206, 235),
+    Color32::from_rgb(255, 190, 140),
+    Color32::from_rgb(170, 225, 160),
+    Color32::from_rgb(215, 180, 235),
+    Color32::from_rgb(255, 230, 140),
+    Color32::from_rgb(255, 170, 180),
+];
+
+/// Ghost labels previewing the solved board (translucent dark gray)
+const GHOST_COLOR: Color32 = Color32::from_rgba_premultiplied(30, 30, 30, 110);
+
+/// Backdrop around the board in headless images (egui's dark panel gray)
+const BOARD_BACKGROUND: Color32 = Color32::from_rgb(27, 27, 27);
+
+/// Most pips drawn on a tile; larger numbers fall back to digits
+pub const MAX_PIPS: u32 = 25;
+
+/// Wash over tiles a hovered click would move (translucent yellow)
+const PREVIEW_TINT: Color32 = Color32::from_rgba_premultiplied(90, 90, 40, 90);
+
+/// Outline around tiles a hovered click would move
+const PREVIEW_OUTLINE: Color32 = Color32::from_rgb(200, 160, 0);
+
+/// Tile brightness at home and at the farthest distance in colorblind mode
+const CB_LIGHT: u8 = 235;
+const CB_DARK: u8 = 140;
+
+/// Hatch line width and translucent dark gray in colorblind mode
+const HATCH_WIDTH: f32 = 1.5;
+const HATCH_SHADE: (u8, u8) = (40, 90);
+
+/// Most hatch lines drawn on a tile in colorblind mode (at the farthest distance)
+const CB_MAX_HATCH: usize = 6;
+
+/// Light tints of the Okabe–Ito palette, distinguishable with common color vision deficiencies
+const CB_PALETTE: [Color32; 6] = [
+    Color32::from_rgb(160, 210, 240), // Sky blue
+    Color32::from_rgb(245, 200, 120), // Orange
+    Color32::from_rgb(140, 215, 190), // Bluish green
+    Color32::from_rgb(245, 240, 150), // Yellow
+    Color32::from_rgb(225, 185, 210), // Reddish purple
+    Color32::from_rgb(200, 200, 200), // Grey
+];
+
+/// Heatmap tint for the next tile to move (orange)
+const HEAT_SOON: (u8, u8, u8) = (255, 120, 0);
+
+/// Heatmap tint for the last tile to move (violet)
+const HEAT_LATE: (u8, u8, u8) = (110, 60, 200);
+
+/// Opacity of heatmap tints, low enough to keep numbers readable
+const HEAT_ALPHA: u8 = 120;
+
+/// Space between tiles, in points
+pub const TILE_GAP: f32 = 5.0;
+
+/// Largest tile drawn, however much room a board has
+const MAX_TILE_SIZE: f32 = 100.0;
+
+/// Returns the largest tile size, up to `MAX_TILE_SIZE`, at which `size` tiles
+/// and the `TILE_GAP`s between them fit in `available` points
+/// Big grids in small spaces get small tiles rather than overflowing
+pub fn tile_size_for(available: f32, size: usize) -> f32 {
+    let gaps = (size as f32 - 1.0) * TILE_GAP;
+    ((available - gaps) / size as f32).clamp(0.0, MAX_TILE_SIZE)
+}
+
+/// Returns the heatmap tint for a tile that first moves at `step`
+/// The gradient runs from step 0 (orange) to step `steps - 1` (violet)
+pub fn heatmap_color(step: usize, steps: usize) -> Color32 {
+    let ratio = if steps > 1 {
+        (step as f32 / (steps - 1) as f32).min(1.0)
+    } else {
+        0.0
+    };
+    let mix = |from: u8, to: u8| (from as f32 + ratio * (to as f32 - from as f32)) as u8;
+    Color32::from_rgba_unmultiplied(
+        mix(HEAT_SOON.0, HEAT_LATE.0),
+        mix(HEAT_SOON.1, HEAT_LATE.1),
+        mix(HEAT_SOON.2, HEAT_LATE.2),
+        HEAT_ALPHA,
+    )
+}
+
+fn hatch_color() -> Color32 {
+    Color32::from_rgba_unmultiplied(HATCH_SHADE.0, HATCH_SHADE.0, HATCH_SHADE.0, HATCH_SHADE.1)
+}
+
+/// How tile backgrounds are colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Gradient from blue (home) to red (farthest possible from home)
+    #[default]
+    Distance,
+    /// One color per goal row, to help solving row by row
+    Row,
+    /// One color per band of consecutive tile numbers
+    Band,
+    /// Every tile the same color
+    Off,
+}
+
+impl ColorMode {
+    /// Every mode, in display order
+    pub const ALL: [ColorMode; 4] = [
+        ColorMode::Distance,
+        ColorMode::Row,
+        ColorMode::Band,
+        ColorMode::Off,
+    ];
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorMode::Distance => write!(f, "Distance from home"),
+            ColorMode::Row => write!(f, "Goal row"),
+            ColorMode::Band => write!(f, "Number band"),
+            ColorMode::Off => write!(f, "Off"),
+        }
+    }
+}
+
+/// How numbered tiles show their number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberStyle {
+    /// 1, 2, 3, ...
+    #[default]
+    Decimal,
+    /// 0, 1, 2, ... as some programmers' 15-puzzles number them
+    ZeroBased,
+    /// 1 to F on a 4×4 board
+    Hexadecimal,
+    /// I, II, III, ...
+    Roman,
+    /// Dots like a domino's, up to `MAX_PIPS`
+    Pips,
+}
+
+impl NumberStyle {
+    /// Every style, in display order
+    pub const ALL: [NumberStyle; 5] = [
+        NumberStyle::Decimal,
+        NumberStyle::ZeroBased,
+        NumberStyle::Hexadecimal,
+        NumberStyle::Roman,
+        NumberStyle::Pips,
+    ];
+
+    /// Returns the text shown for tile number `n`, or None if it is drawn as pips
+    pub fn format(self, n: u32) -> Option<String> {
+        match self {
+            NumberStyle::Decimal => Some(n.to_string()),
+            NumberStyle::ZeroBased => Some(n.saturating_sub(1).to_string()),
+            NumberStyle::Hexadecimal => Some(format!("{:X}", n)),
+            NumberStyle::Roman => Some(roman_numeral(n)),
+            NumberStyle::Pips if (1..=MAX_PIPS).contains(&n) => None,
+            NumberStyle::Pips => Some(n.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for NumberStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberStyle::Decimal => write!(f, "Decimal"),
+            NumberStyle::ZeroBased => write!(f, "Zero-based"),
+            NumberStyle::Hexadecimal => write!(f, "Hexadecimal"),
+            NumberStyle::Roman => write!(f, "Roman numerals"),
+            NumberStyle::Pips => write!(f, "Pips"),
+        }
+    }
+}
+
+/// Writes `n` in Roman numerals (0 has none, so it is written as N for nulla)
+fn roman_numeral(mut n: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if n == 0 {
+        return "N".to_string();
+    }
+    let mut numeral = String::new();
+    for (value, letters) in NUMERALS {
+        while n >= value {
+            numeral.push_str(letters);
+            n -= value;
+        }
+    }
+    numeral
+}
+
+/// How one tile is drawn: its fill plus the hue-free cues of colorblind mode
+struct TileStyle {
+    fill: Color32,
+    border_width: f32,
+    hatch_lines: usize,
+}
 
 /// Renders individual tiles in the GUI
 pub struct TileRenderer {
     tile_size: f32,
     gap: f32,
+    puzzle_size: usize, // Grid size the colors are scaled for
+    color_mode: ColorMode,
+    colorblind: bool, // Show distance by brightness, border and hatching instead of hue
+    picture: Option<TextureHandle>, // Square image cut into the tile faces, replacing labels
+    number_style: NumberStyle,
 }
 
 impl TileRenderer {
-    pub fn new(tile_size: f32, gap: f32) -> Self {
-        Self { tile_size, gap }
+    pub fn new(tile_size: f32, gap: f32, puzzle_size: usize) -> Self {
+        Self {
+            tile_size,
+            gap,
+            puzzle_size,
+            color_mode: ColorMode::default(),
+            colorblind: false,
+            picture: None,
+            number_style: NumberStyle::default(),
+        }
+    }
+
+    /// Returns how tiles are colored
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Changes how tiles are colored
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+    }
+
+    /// Returns whether colorblind mode is on
+    pub fn colorblind(&self) -> bool {
+        self.colorblind
+    }
+
+    /// Turns colorblind mode on or off
+    /// Distance is then shown by brightness, border thickness and hatching rather
+    /// than hue, and the row and band modes use a colorblind-safe palette
+    pub fn set_colorblind(&mut self, colorblind: bool) {
+        self.colorblind = colorblind;
+    }
+
+    /// Returns how numbered tiles show their number
+    pub fn number_style(&self) -> NumberStyle {
+        self.number_style
+    }
+
+    /// Changes how numbered tiles show their number
+    /// Letters and custom labels are drawn as they are
+    pub fn set_number_style(&mut self, style: NumberStyle) {
+        self.number_style = style;
+    }
+
+    /// Returns the picture shown on the tiles, if any
+    pub fn picture(&self) -> Option<&TextureHandle> {
+        self.picture.as_ref()
+    }
+
+    /// Shows a square picture across the tiles instead of their labels
+    /// Each tile shows the part of the picture over its home cell, so the solved
+    /// board shows the whole picture bar the empty cell. None goes back to labels.
+    pub fn set_picture(&mut self, picture: Option<TextureHandle>) {
+        self.picture = picture;
+    }
+
+    /// Returns the part of the picture shown by a tile whose home is `home`, in texture coordinates
+    fn picture_uv(&self, home: (usize, usize)) -> Rect {
+        let cell = 1.0 / self.puzzle_size as f32;
+        Rect::from_min_size(
+            Pos2::new(home.1 as f32 * cell, home.0 as f32 * cell),
+            Vec2::splat(cell),
+        )
+    }
+
+    /// Returns how to draw `tile` sitting at `grid_pos`
+    fn tile_style(&self, tile: &Tile, grid_pos: (usize, usize)) -> TileStyle {
+        let (home_row, home_col) = tile.home_position;
+        let palette = if self.colorblind { &CB_PALETTE } else { &PALETTE };
+        let plain = |fill| TileStyle {
+            fill,
+            border_width: 2.0,
+            hatch_lines: 0,
+        };
+        match self.color_mode {
+            ColorMode::Distance => {
+                // Interpolate from home to the farthest possible distance (corner to corner)
+                let distance = home_row.abs_diff(grid_pos.0) + home_col.abs_diff(grid_pos.1);
+                let max_distance = (2 * (self.puzzle_size.max(2) - 1)) as f32;
+                let ratio = (distance as f32 / max_distance).min(1.0);
+
+                let mix = |from: u8, to: u8| (from as f32 + ratio * (to as f32 - from as f32)) as u8;
+                if self.colorblind {
+                    let shade = mix(CB_LIGHT, CB_DARK);
+                    return TileStyle {
+                        fill: Color32::from_rgb(shade, shade, shade),
+                        border_width: 1.0 + ratio * 4.0,
+                        hatch_lines: (ratio * CB_MAX_HATCH as f32).round() as usize,
+                    };
+                }
+                plain(Color32::from_rgb(
+                    mix(HOME_COLOR.0, FAR_COLOR.0),
+                    mix(HOME_COLOR.1, FAR_COLOR.1),
+                    mix(HOME_COLOR.2, FAR_COLOR.2),
+                ))
+            }
+            ColorMode::Row => plain(palette[home_row % palette.len()]),
+            ColorMode::Band => {
+                let number = (home_row * self.puzzle_size + home_col) as u32;
+                plain(palette[(number / BAND_WIDTH) as usize % palette.len()])
+            }
+            ColorMode::Off => plain(PLAIN_COLOR),
+        }
+    }
+
+    /// Draws `text` centered in `rect` at `scale` times the tile size, shrinking
+    /// it as needed so long labels fit inside the tile
+    fn render_label(&self, ui: &mut Ui, rect: Rect, text: String, scale: f32) {
+        let max_width = self.tile_size * LABEL_FILL;
+        let mut font_size = self.tile_size * scale;
+        let mut galley = ui
+            .painter()
+            .layout_no_wrap(text.clone(), FontId::proportional(font_size), Color32::BLACK);
+        if galley.size().x > max_width {
+            font_size *= max_width / galley.size().x;
+            galley = ui
+                .painter()
+                .layout_no_wrap(text, FontId::proportional(font_size), Color32::BLACK);
+        }
+
+        let text_pos = Pos2::new(
+            rect.center().x - galley.size().x / 2.0,
+            rect.center().y - galley.size().y / 2.0,
+        );
+        ui.painter().galley(text_pos, galley, Color32::BLACK);
+    }
+
+    /// Returns the centers and radius of `count` pips centered in `rect`, in rows
+    /// like the faces of a domino
+    fn pip_layout(&self, rect: Rect, count: u32) -> (Vec<Pos2>, f32) {
+        let columns = (count as f32).sqrt().ceil() as u32;
+        let rows = count.div_ceil(columns);
+        let spacing = self.tile_size * LABEL_FILL / (columns.max(rows) as f32 + 1.0);
+        let centers = (0..count)
+            .map(|i| {
+                let (row, column) = (i / columns, i % columns);
+                // The last row is centered when it is not full
+                let in_row = if row == rows - 1 { count - row * columns } else { columns };
+                let x = (column as f32 - (in_row - 1) as f32 / 2.0) * spacing;
+                let y = (row as f32 - (rows - 1) as f32 / 2.0) * spacing;
+                rect.center() + Vec2::new(x, y)
+            })
+            .collect();
+        (centers, spacing * 0.3)
+    }
+
+    /// Draws `count` pips centered in `rect`
+    fn render_pips(&self, ui: &mut Ui, rect: Rect, count: u32) {
+        let (centers, radius) = self.pip_layout(rect, count);
+        for center in centers {
+            ui.painter().circle_filled(center, radius, Color32::BLACK);
+        }
+    }
+
+    /// Returns `lines` evenly spaced diagonal hatch lines across `rect`
+    fn hatch_lines(rect: Rect, lines: usize) -> Vec<[Pos2; 2]> {
+        let side = rect.width();
+        (1..=lines)
+            .map(|i| {
+                // Distance along the top and left edges, running on down the right and bottom
+                let t = 2.0 * side * i as f32 / (lines + 1) as f32;
+                if t <= side {
+                    [Pos2::new(rect.left() + t, rect.top()), Pos2::new(rect.left(), rect.top() + t)]
+                } else {
+                    [
+                        Pos2::new(rect.right(), rect.top() + t - side),
+                        Pos2::new(rect.left() + t - side, rect.bottom()),
+                    ]
+                }
+            })
+            .collect()
+    }
+
+    /// Draws `lines` evenly spaced diagonal hatch lines across `rect`
+    fn render_hatching(&self, ui: &mut Ui, rect: Rect, lines: usize) {
+        for segment in Self::hatch_lines(rect, lines) {
+            ui.painter().line_segment(segment, (HATCH_WIDTH, hatch_color()));
+        }
     }
 
     /// Renders a single tile at the given grid position
@@ -45,77 +457,133 @@ impl TileRenderer {
 
         let response = ui.allocate_rect(rect, Sense::click());
 
-        // Calculate Manhattan distance from home position
-        let manhattan_distance =
-            (tile.home_position.0 as i32 - grid_pos.0 as i32).abs() +
-            (tile.home_position.1 as i32 - grid_pos.1 as i32).abs();
-
-        // Determine color based on Manhattan distance
-        // Distance 0 (home) = light sky blue (135, 206, 235)
-        // Distance 6 (max for 4x4) = pale red (255, 160, 160)
-        // Interpolate between these colors
-        let max_distance = 6.0; // Maximum possible Manhattan distance in 4x4 grid
-        let ratio = (manhattan_distance as f32 / max_distance).min(1.0);
-
-        let blue_r = 135;
-        let blue_g = 206;
-        let blue_b = 235;
-        let red_r = 255;
-        let red_g = 160;
-        let red_b = 160;
-
-        let r = (blue_r as f32 + ratio * (red_r as f32 - blue_r as f32)) as u8;
-        let g = (blue_g as f32 + ratio * (red_g as f32 - blue_g as f32)) as u8;
-        let b = (blue_b as f32 + ratio * (red_b as f32 - blue_b as f32)) as u8;
-
-        let color = Color32::from_rgb(r, g, b);
+        let style = self.tile_style(tile, grid_pos);
 
         // Highlight on hover
         let color = if response.hovered() {
             Color32::from_rgb(255, 255, 150)
         } else {
-            color
+            style.fill
         };
 
-        // Draw tile background
-        ui.painter().rect_filled(rect, 5.0, color);
+        // Draw tile background, or its part of the picture
+        if let Some(picture) = &self.picture {
+            let tint = if response.hovered() { color } else { Color32::WHITE };
+            ui.painter().image(picture.id(), rect, self.picture_uv(tile.home_position), tint);
+        } else {
+            ui.painter().rect_filled(rect, 5.0, color);
+            self.render_hatching(ui, rect, style.hatch_lines);
+        }
 
         // Draw border
         ui.painter()
-            .rect_stroke(rect, 5.0, (2.0, Color32::from_rgb(80, 80, 80)));
+            .rect_stroke(rect, 5.0, (style.border_width, Color32::from_rgb(80, 80, 80)));
 
         // Draw tile content
         match &tile.content {
-            TileContent::Numeric(n) => {
-                let text = format!("{}", n);
-                let font = FontId::proportional(self.tile_size * 0.4);
-                let galley = ui.painter().layout_no_wrap(text, font, Color32::BLACK);
-
-                let text_pos = Pos2::new(
-                    rect.center().x - galley.size().x / 2.0,
-                    rect.center().y - galley.size().y / 2.0,
-                );
-
-                ui.painter().galley(text_pos, galley, Color32::BLACK);
-            }
+            _ if self.picture.is_some() => {}
             TileContent::Image(_) => {
                 // Placeholder for future image rendering
-                let text = "IMG";
-                let font = FontId::proportional(self.tile_size * 0.3);
-                let galley = ui.painter().layout_no_wrap(text.to_string(), font, Color32::BLACK);
-
-                let text_pos = Pos2::new(
-                    rect.center().x - galley.size().x / 2.0,
-                    rect.center().y - galley.size().y / 2.0,
-                );
-
-                ui.painter().galley(text_pos, galley, Color32::BLACK);
+                self.render_label(ui, rect, "IMG".to_string(), 0.3);
+            }
+            &TileContent::Numeric(n) => match self.number_style.format(n) {
+                Some(text) => self.render_label(ui, rect, text, 0.4),
+                None => self.render_pips(ui, rect, n),
+            },
+            content => {
+                if let Some(text) = content.label().filter(|text| !text.is_empty()) {
+                    self.render_label(ui, rect, text, 0.4);
+                }
             }
         }
 
         response.clicked()
     }
 
+    /// Draws a highlight ring over the cell at the given grid position
+    /// `intensity` ranges from 0.0 (invisible) to 1.0 (fully opaque)
+    pub fn render_highlight(
+        &self,
+        ui: &mut Ui,
+        grid_pos: (usize, usize),
+        top_left: Pos2,
+        intensity: f32,
+    ) {
+        let (row, col) = grid_pos;
+        let x = top_left.x + col as f32 * (self.tile_size + self.gap);
+        let y = top_left.y + row as f32 * (self.tile_size + self.gap);
+
+        let rect = Rect::from_min_size(
+            Pos2::new(x, y),
+            Vec2::new(self.tile_size, self.tile_size),
+        );
+
+        let alpha = (intensity.clamp(0.0, 1.0) * 255.0) as u8;
+        let color = Color32::from_rgba_unmultiplied(255, 140, 0, alpha);
+        ui.painter().rect_stroke(rect, 5.0, (4.0, color));
+    }
+
+    /// Washes a translucent overlay color over the cell at the given grid position
+    pub fn render_tint(&self, ui: &mut Ui, grid_pos: (usize, usize), top_left: Pos2, color: Color32) {
+        let (row, col) = grid_pos;
+        let x = top_left.x + col as f32 * (self.tile_size + self.gap);
+        let y = top_left.y + row as f32 * (self.tile_size + self.gap);
+
+        let rect = Rect::from_min_size(
+            Pos2::new(x, y),
+            Vec2::new(self.tile_size, self.tile_size),
+        );
+        ui.painter().rect_filled(rect, 5.0, color);
+    }
+
+    /// Writes `tile`'s label small and faint in the corner of its home cell
+    ///
+    /// Drawn over the board for every tile, this previews the solved layout so
+    /// beginners can see where each tile belongs. Pips and pictures are written
+    /// as plain numbers, which stay readable at ghost size.
+    pub fn render_ghost(&self, ui: &mut Ui, tile: &Tile, top_left: Pos2) {
+        let text = match tile.content {
+            TileContent::Numeric(n) => self.number_style.format(n).unwrap_or_else(|| n.to_string()),
+            ref content => match content.label() {
+                Some(text) if !text.is_empty() => text,
+                _ => return,
+            },
+        };
+        let (row, col) = tile.home_position;
+        let inset = self.tile_size * 0.08;
+        let corner = Pos2::new(
+            top_left.x + col as f32 * (self.tile_size + self.gap) + inset,
+            top_left.y + row as f32 * (self.tile_size + self.gap) + inset,
+        );
+        ui.painter().text(
+            corner,
+            egui::Align2::LEFT_TOP,
+            text,
+            FontId::proportional(self.tile_size * 0.2),
+            GHOST_COLOR,
+        );
+    }
+
+    /// Marks a set of cells, such as the tiles a hovered click would move
+    pub fn render_tile_set_highlight(
+        &self,
+        ui: &mut Ui,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        top_left: Pos2,
+    ) {
+        for (row, col) in cells {
+            let x = top_left.x + col as f32 * (self.tile_size + self.gap);
+            let y = top_left.y + row as f32 * (self.tile_size + self.gap);
+
+            let rect = Rect::from_min_size(
+                Pos2::new(x, y),
+                Vec2::new(self.tile_size, self.tile_size),
+            );
+            ui.painter().rect_filled(rect, 5.0, PREVIEW_TINT);
+            ui.painter().rect_stroke(rect, 5.0, (3.0, PREVIEW_OUTLINE));
+        }
+    }
+
     /// Renders the empty cell
     pub fn render_empty(&self, ui: &mut Ui, grid_pos: (usize, usize), top_left: Pos2) {
         let (row, col) = grid_pos;
@@ -132,9 +600,111 @@ impl TileRenderer {
             .rect_filled(rect, 5.0, Color32::from_rgb(50, 50, 50));
     }
 
+    /// Returns the grid cell under `point`, or None over a gap or outside the grid
+    pub fn cell_at(&self, top_left: Pos2, point: Pos2) -> Option<(usize, usize)> {
+        let pitch = self.tile_size + self.gap;
+        let (x, y) = (point.x - top_left.x, point.y - top_left.y);
+        if x < 0.0 || y < 0.0 || x % pitch > self.tile_size || y % pitch > self.tile_size {
+            return None;
+        }
+        let cell = ((y / pitch) as usize, (x / pitch) as usize);
+        (cell.0 < self.puzzle_size && cell.1 < self.puzzle_size).then_some(cell)
+    }
+
     /// Calculates the total size needed for the grid
     pub fn grid_size(&self, puzzle_size: usize) -> Vec2 {
         let total = puzzle_size as f32 * self.tile_size + (puzzle_size - 1) as f32 * self.gap;
         Vec2::new(total, total)
     }
-}
\ No newline at end of file
+
+    /// Returns the size of a headless image of a `puzzle_size` board: the grid
+    /// framed by a margin one gap wide
+    pub fn board_image_size(&self, puzzle_size: usize) -> Vec2 {
+        self.grid_size(puzzle_size) + Vec2::splat(2.0 * self.gap)
+    }
+
+    /// Paints `state` onto `surface` without a GUI, one unit per point
+    ///
+    /// Tiles get the same colors, borders, hatching, labels and pips as on
+    /// screen, and `tints` are washed over their cells as the heatmap is. Picture
+    /// tiles are drawn with their colors, since the picture lives on the GPU.
+    pub fn draw_board(&self, surface: &mut impl Surface, state: &PuzzleState, tints: &[(Position, Color32)]) {
+        let pitch = self.tile_size + self.gap;
+        let cell = |(row, col): Position| {
+            let min = Pos2::new(self.gap + col as f32 * pitch, self.gap + row as f32 * pitch);
+            Rect::from_min_size(min, Vec2::splat(self.tile_size))
+        };
+        surface.fill_rounded(cell(state.empty_position()), 5.0, Color32::from_rgb(50, 50, 50));
+        for (pos, tile) in state.tiles() {
+            let rect = cell(pos);
+            let style = self.tile_style(tile, pos);
+            surface.fill_rounded(rect, 5.0, style.fill);
+            for [from, to] in Self::hatch_lines(rect, style.hatch_lines) {
+                surface.line(from, to, HATCH_WIDTH, hatch_color());
+            }
+            surface.stroke_rounded(rect, 5.0, style.border_width, Color32::from_rgb(80, 80, 80));
+
+            let max_width = self.tile_size * LABEL_FILL;
+            let label = match &tile.content {
+                &TileContent::Numeric(n) => self.number_style.format(n),
+                content => content.label(),
+            };
+            match (label, &tile.content) {
+                (Some(text), _) => surface.text(rect.center(), &text, self.tile_size * 0.3, max_width, Color32::BLACK),
+                (None, &TileContent::Numeric(n)) => {
+                    let (centers, radius) = self.pip_layout(rect, n);
+                    for center in centers {
+                        surface.fill_circle(center, radius, Color32::BLACK);
+                    }
+                }
+                (None, _) => {}
+            }
+        }
+        for &(pos, color) in tints {
+            surface.fill_rounded(cell(pos), 5.0, color);
+        }
+    }
+
+    /// Draws `state` as pixels; see `draw_board`
+    pub fn rasterize(&self, state: &PuzzleState, tints: &[(Position, Color32)]) -> ColorImage {
+        let mut canvas = Canvas::new(self.board_image_size(state.size()), BOARD_BACKGROUND);
+        self.draw_board(&mut canvas, state, tints);
+        canvas.into_image()
+    }
+
+    /// Draws `state` as an SVG document; see `draw_board`
+    pub fn to_svg(&self, state: &PuzzleState, tints: &[(Position, Color32)]) -> String {
+        let mut svg = SvgCanvas::new(self.board_image_size(state.size()), BOARD_BACKGROUND);
+        self.draw_board(&mut svg, state, tints);
+        svg.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_styles() {
+        let shown = |style: NumberStyle| [1, 10, 15].map(|n| style.format(n));
+        assert_eq!(shown(NumberStyle::ZeroBased), [Some("0".into()), Some("9".into()), Some("14".into())]);
+        assert_eq!(shown(NumberStyle::Hexadecimal), [Some("1".into()), Some("A".into()), Some("F".into())]);
+        assert_eq!(shown(NumberStyle::Roman), [Some("I".into()), Some("X".into()), Some("XV".into())]);
+        assert_eq!(shown(NumberStyle::Pips), [None, None, None]);
+        assert_eq!(NumberStyle::Pips.format(MAX_PIPS + 1), Some((MAX_PIPS + 1).to_string()));
+        assert_eq!(roman_numeral(483), "CDLXXXIII"); // Largest tile on a 22×22 board
+        assert_eq!(roman_numeral(49), "XLIX");
+    }
+
+    #[test]
+    fn test_tile_size_fits() {
+        assert_eq!(tile_size_for(600.0, 3), MAX_TILE_SIZE);
+        for available in [360.0, 600.0] {
+            for size in 3..=22 {
+                let tile = tile_size_for(available, size);
+                assert!(tile > 0.0);
+                assert!(size as f32 * tile + (size as f32 - 1.0) * TILE_GAP <= available + 0.01);
+            }
+        }
+    }
+}
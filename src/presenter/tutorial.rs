@@ -0,0 +1,202 @@
+//! Guided tutorial shown on first launch.
+//!
+//! A small state machine walks new players through the basics: sliding one
+//! tile, sliding a whole line at once, reading the entropy display, and
+//! letting the solver finish. Steps that ask for an action advance when the
+//! GUI reports it; the others wait for "Next". The tiles a step talks about
+//! are highlighted on the board. Finishing or skipping writes a marker to the
+//! data directory so the tutorial only starts by itself once.
+
+use crate::controller::storage;
+use crate::controller::GameController;
+use crate::model::{MoveSystem, Position};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// File name of the tutorial marker inside the data directory
+pub const TUTORIAL_FILE: &str = "tutorial.json";
+
+/// Serialized marker recording that the tutorial was seen
+#[derive(Debug, Serialize, Deserialize)]
+struct TutorialFile {
+    completed: bool,
+}
+
+/// One page of the tutorial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    Welcome,
+    ImmediateMove,
+    ChainMove,
+    Entropy,
+    AutoSolve,
+    Finished,
+}
+
+impl TutorialStep {
+    /// Every step in order, ending with `Finished`
+    pub const ALL: [TutorialStep; 6] = [
+        TutorialStep::Welcome,
+        TutorialStep::ImmediateMove,
+        TutorialStep::ChainMove,
+        TutorialStep::Entropy,
+        TutorialStep::AutoSolve,
+        TutorialStep::Finished,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            TutorialStep::Welcome => "Welcome to Slider",
+            TutorialStep::ImmediateMove => "Slide a tile",
+            TutorialStep::ChainMove => "Slide a whole line",
+            TutorialStep::Entropy => "Reading the board",
+            TutorialStep::AutoSolve => "Let the solver finish",
+            TutorialStep::Finished => "You're ready",
+        }
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            TutorialStep::Welcome => {
+                "Put the tiles back in order by sliding them into the empty cell. \
+                 This short tour shows the controls; skip it at any time."
+            }
+            TutorialStep::ImmediateMove => {
+                "Click a highlighted tile next to the empty cell to slide it in."
+            }
+            TutorialStep::ChainMove => {
+                "Click a highlighted tile further along the empty cell's row or column: \
+                 every tile in between slides together, as one move."
+            }
+            TutorialStep::Entropy => {
+                "The numbers in the toolbar measure how scrambled the board is. Manhattan \
+                 adds up how far each tile is from home, Heuristic also counts tiles blocking \
+                 each other, and Actual is the fewest moves that finish the board, found in \
+                 the background. All of them fall to zero as you solve it."
+            }
+            TutorialStep::AutoSolve => {
+                "Stuck? Press Auto Solve to watch the solver finish the board one move \
+                 at a time (auto-solved games do not count towards best times)."
+            }
+            TutorialStep::Finished => {
+                "Press New Game to shuffle a board at the difficulty you pick. \
+                 Replay this tour from Settings whenever you like."
+            }
+        }
+    }
+
+    /// Returns true if the step advances with "Next" rather than an action on the board
+    pub fn waits_for_next(self) -> bool {
+        matches!(
+            self,
+            TutorialStep::Welcome | TutorialStep::Entropy | TutorialStep::Finished
+        )
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(index + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+/// Something the player did that a step may be waiting for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialEvent {
+    /// Pressed "Next"
+    Next,
+    /// Started a move that slides `tiles` tiles
+    Moved { tiles: usize },
+    /// Started an auto-solve
+    AutoSolveStarted,
+    /// Pressed "Skip tutorial"
+    Skip,
+}
+
+/// Progress through the tutorial, if it is running
+#[derive(Debug, Default)]
+pub struct Tutorial {
+    step: Option<TutorialStep>, // None once finished or skipped
+    path: Option<PathBuf>,      // Marker written on completion (None = in-memory only)
+}
+
+impl Tutorial {
+    /// Starts the tutorial unless the marker in the data directory says it was seen
+    pub fn load_default() -> Self {
+        let path = storage::data_file(TUTORIAL_FILE);
+        let seen = path
+            .as_deref()
+            .and_then(|path| storage::load_json::<TutorialFile>(path).ok())
+            .is_some_and(|file| file.completed);
+        Self {
+            step: (!seen).then_some(TutorialStep::Welcome),
+            path,
+        }
+    }
+
+    /// Starts over from the first step
+    pub fn restart(&mut self) {
+        self.step = Some(TutorialStep::Welcome);
+    }
+
+    /// Returns the current step, or None if the tutorial is not running
+    pub fn step(&self) -> Option<TutorialStep> {
+        self.step
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.step.is_some()
+    }
+
+    /// Advances the state machine if `event` is what the current step waits for
+    pub fn handle(&mut self, event: TutorialEvent) {
+        let Some(step) = self.step else {
+            return;
+        };
+        let advance = match (step, event) {
+            (_, TutorialEvent::Skip) | (TutorialStep::Finished, TutorialEvent::Next) => {
+                self.finish();
+                return;
+            }
+            (step, TutorialEvent::Next) => step.waits_for_next(),
+            (TutorialStep::ImmediateMove, TutorialEvent::Moved { tiles }) => tiles == 1,
+            (TutorialStep::ChainMove, TutorialEvent::Moved { tiles }) => tiles > 1,
+            (TutorialStep::AutoSolve, TutorialEvent::AutoSolveStarted) => true,
+            _ => false,
+        };
+        if advance {
+            self.step = Some(step.next());
+        }
+    }
+
+    /// Returns the tiles the current step points at on `controller`'s board
+    pub fn highlights(&self, controller: &GameController) -> Vec<Position> {
+        let state = controller.state();
+        let empty = state.empty_position();
+        match self.step {
+            Some(TutorialStep::ImmediateMove) => state.legal_moves(),
+            Some(TutorialStep::ChainMove) => {
+                let size = state.size();
+                (0..size)
+                    .map(|col| (empty.0, col))
+                    .chain((0..size).map(|row| (row, empty.1)))
+                    .filter(|&pos| {
+                        controller
+                            .preview_move(pos)
+                            .is_some_and(|preview| preview.len() > 1)
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Stops the tutorial and records that it was seen
+    fn finish(&mut self) {
+        self.step = None;
+        if let Some(ref path) = self.path {
+            if let Err(e) = storage::save_json(path, &TutorialFile { completed: true }) {
+                log::warn!("Failed to save tutorial progress: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! # Property Testing Helpers
+//!
+//! Seeded generators for boards and moves, and a small runner that checks a
+//! property over many generated cases. Enabled in this crate's tests and, for
+//! downstream crates, with the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! slider = { version = "0.1", features = ["testing"] }
+//! ```
+//!
+//! A failing case reports the seed that produced it; set `SLIDER_PROPERTY_SEED`
+//! to that value to rerun just that case.
+//!
+//! ```rust,ignore
+//! // Requires the `testing` feature
+//! use slider::testing::{check, solvable_board};
+//!
+//! check("boards are solvable", 50, |rng| {
+//!     let board = solvable_board(rng, 4);
+//!     if board.is_solvable() { Ok(()) } else { Err(board.to_notation()) }
+//! });
+//! ```
+
+use crate::model::puzzle_state::{MAX_SIZE, MIN_SIZE};
+use crate::model::{MoveSystem, Position, PuzzleState};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Environment variable that replays a single case by seed
+pub const PROPERTY_SEED_ENV: &str = "SLIDER_PROPERTY_SEED";
+
+/// Base seed for case generation; case `i` uses `BASE_SEED + i`
+const BASE_SEED: u64 = 0x51_1D_E5;
+
+/// Runs `property` on `cases` generated cases, panicking with the seed of the first failure
+///
+/// The property returns `Err` with a description of the counterexample (usually
+/// the board in notation) when the case fails.
+pub fn check(name: &str, cases: u64, mut property: impl FnMut(&mut StdRng) -> Result<(), String>) {
+    let seeds: Vec<u64> = match std::env::var(PROPERTY_SEED_ENV)
+        .ok()
+        .and_then(|s| s.parse().ok())
+    {
+        Some(seed) => vec![seed],
+        None => (0..cases).map(|i| BASE_SEED.wrapping_add(i)).collect(),
+    };
+    for seed in seeds {
+        let mut rng = StdRng::seed_from_u64(seed);
+        if let Err(counterexample) = property(&mut rng) {
+            panic!(
+                "property '{}' failed: {}\nrerun with {}={}",
+                name, counterexample, PROPERTY_SEED_ENV, seed
+            );
+        }
+    }
+}
+
+/// Returns a grid size in `sizes`, clamped to the supported range
+pub fn grid_size(rng: &mut impl Rng, sizes: std::ops::RangeInclusive<usize>) -> usize {
+    rng.gen_range(sizes).clamp(MIN_SIZE, MAX_SIZE)
+}
+
+/// Returns a uniformly random solvable board
+///
+/// Cells are shuffled at random; if the result has the wrong parity, two tiles
+/// are swapped, which always flips it.
+pub fn solvable_board(rng: &mut impl Rng, size: usize) -> PuzzleState {
+    let mut cells: Vec<Option<u32>> = (1..(size * size) as u32).map(Some).collect();
+    cells.push(None);
+    cells.shuffle(rng);
+
+    let to_layout =
+        |cells: &[Option<u32>]| cells.chunks(size).map(<[_]>::to_vec).collect::<Vec<_>>();
+    if let Ok(state) = PuzzleState::from_layout(&to_layout(&cells)) {
+        return state;
+    }
+    let tiles: Vec<usize> = (0..cells.len())
+        .filter(|&i| cells[i].is_some())
+        .take(2)
+        .collect();
+    cells.swap(tiles[0], tiles[1]);
+    PuzzleState::from_layout(&to_layout(&cells)).expect("swapping two tiles fixes parity")
+}
+
+/// Returns the board reached by `steps` random immediate moves from solved
+///
+/// The walk never undoes its previous move, so the optimum is at most `steps`.
+pub fn walked_board(rng: &mut impl Rng, size: usize, steps: usize) -> PuzzleState {
+    let mut state = PuzzleState::new(size).expect("valid size");
+    state.random_walk(steps, rng);
+    state
+}
+
+/// Returns a random cell in line with the empty cell (never the empty cell itself)
+pub fn in_line_position(rng: &mut impl Rng, state: &PuzzleState) -> Position {
+    let (row, col) = state.empty_position();
+    let size = state.size();
+    let candidates: Vec<Position> = (0..size)
+        .map(|c| (row, c))
+        .chain((0..size).map(|r| (r, col)))
+        .filter(|&pos| pos != (row, col))
+        .collect();
+    *candidates
+        .choose(rng)
+        .expect("grids have at least three cells per line")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::ShuffleController;
+    use crate::model::{
+        Difficulty, EntropyCalculator, HeuristicCache, IDAStarSolver, ManhattanDistance,
+        ShortestPathHeuristic, SolverConfig, SolverKind, WalkingDistance,
+    };
+
+    /// Moves the tiles between `target` and the empty cell one step toward the
+    /// empty cell, working on the layout directly rather than through moves
+    fn slide_layout(state: &PuzzleState, target: Position) -> Vec<Vec<Option<u32>>> {
+        let mut layout = state.to_layout();
+        let (mut row, mut col) = state.empty_position();
+        while (row, col) != target {
+            let next = (
+                (row as isize + (target.0 as isize - row as isize).signum()) as usize,
+                (col as isize + (target.1 as isize - col as isize).signum()) as usize,
+            );
+            layout[row][col] = layout[next.0][next.1].take();
+            (row, col) = next;
+        }
+        layout
+    }
+
+    #[test]
+    fn prop_shuffles_are_solvable() {
+        let difficulties = [
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::ExtraHard,
+        ];
+        check("shuffles are solvable", 60, |rng| {
+            let size = grid_size(rng, 3..=6);
+            let difficulty = *difficulties.choose(rng).unwrap();
+            let mut state = PuzzleState::new(size).unwrap();
+            ShuffleController::new(size).unwrap().shuffle_seeded(
+                &mut state,
+                difficulty,
+                &ManhattanDistance,
+                rng.gen(),
+            );
+            if state.is_solvable() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} shuffle gave {}",
+                    difficulty,
+                    state.to_notation()
+                ))
+            }
+        });
+    }
+
+    #[test]
+    fn prop_solver_paths_solve() {
+        let config = SolverConfig {
+            max_iterations: 200_000,
+            ..SolverConfig::default()
+        };
+        check("solver paths solve their input", 25, |rng| {
+            let steps = rng.gen_range(0..=30);
+            let board = walked_board(rng, 3, steps);
+            for kind in SolverKind::ALL {
+                let Some(solution) = kind.solve_instrumented(config, &board, None, &mut |_| {})
+                else {
+                    return Err(format!(
+                        "{} found no path for {}",
+                        kind,
+                        board.to_notation()
+                    ));
+                };
+                let mut replay = board.clone();
+                if !solution
+                    .path
+                    .iter()
+                    .all(|&pos| replay.apply_immediate_move(pos))
+                    || !replay.is_solved()
+                {
+                    return Err(format!(
+                        "{} path does not solve {}",
+                        kind,
+                        board.to_notation()
+                    ));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn prop_heuristics_are_admissible() {
+        let walking = WalkingDistance::load_or_build(3, &HeuristicCache::disabled()).unwrap();
+        let heuristics: [(&str, &dyn EntropyCalculator); 3] = [
+            ("manhattan", &ManhattanDistance),
+            ("linear conflict", &ShortestPathHeuristic),
+            ("walking distance", &walking),
+        ];
+        let optimal = IDAStarSolver::new();
+        check("heuristics never exceed the optimum", 40, |rng| {
+            let board = solvable_board(rng, 3);
+            let optimum = optimal.solve(&board).ok_or("IDA* gave up")?;
+            for (name, heuristic) in heuristics {
+                let estimate = heuristic.calculate(&board);
+                if estimate > optimum {
+                    return Err(format!(
+                        "{} gives {} > {} for {}",
+                        name,
+                        estimate,
+                        optimum,
+                        board.to_notation()
+                    ));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn prop_chain_moves_match_sliding() {
+        check(
+            "chain moves slide the line toward the empty cell",
+            200,
+            |rng| {
+                let size = grid_size(rng, 3..=7);
+                let board = solvable_board(rng, size);
+                let target = in_line_position(rng, &board);
+
+                let mut chained = board.clone();
+                if !chained.apply_chain_move(target) {
+                    return Err(format!(
+                        "chain move to {:?} rejected on {}",
+                        target,
+                        board.to_notation()
+                    ));
+                }
+                if chained.to_layout() != slide_layout(&board, target)
+                    || chained.empty_position() != target
+                {
+                    return Err(format!(
+                        "chain move to {:?} went wrong on {}",
+                        target,
+                        board.to_notation()
+                    ));
+                }
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn test_generators_repeat_by_seed() {
+        let boards = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (
+                solvable_board(&mut rng, 4).to_layout(),
+                walked_board(&mut rng, 5, 40).to_layout(),
+            )
+        };
+        assert_eq!(boards(3), boards(3));
+        assert_ne!(boards(3), boards(4));
+    }
+}